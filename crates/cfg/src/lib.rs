@@ -140,6 +140,14 @@ impl CfgDiff {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    pub fn enable(&self) -> &[CfgAtom] {
+        &self.enable
+    }
+
+    pub fn disable(&self) -> &[CfgAtom] {
+        &self.disable
+    }
 }
 
 impl fmt::Display for CfgDiff {
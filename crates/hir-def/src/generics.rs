@@ -14,7 +14,7 @@ use intern::Interned;
 use la_arena::Arena;
 use once_cell::unsync::Lazy;
 use stdx::impl_from;
-use syntax::ast::{self, HasGenericParams, HasName, HasTypeBounds};
+use syntax::ast::{self, AstNode, HasGenericParams, HasName, HasTypeBounds};
 use triomphe::Arc;
 
 use crate::{
@@ -290,6 +290,14 @@ impl GenericParamsCollector {
             let param = LifetimeParamData { name: name.clone() };
             let idx = self.lifetimes.alloc(param);
             let lifetime_ref = LifetimeRef::new_name(name);
+            // The bounds of `'a: 'b + 'c` aren't wrapped in a type bound list.
+            for bound in lifetime_param.syntax().children().filter_map(ast::Lifetime::cast).skip(1)
+            {
+                self.where_predicates.push(WherePredicate::Lifetime {
+                    target: lifetime_ref.clone(),
+                    bound: LifetimeRef::new(&bound),
+                });
+            }
             self.fill_bounds(
                 lower_ctx,
                 lifetime_param.type_bound_list(),
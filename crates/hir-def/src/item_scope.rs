@@ -16,6 +16,8 @@ use syntax::ast;
 
 use crate::{
     db::DefDatabase,
+    item_tree::ImportKind,
+    path::{ModPath, PathKind},
     per_ns::PerNs,
     visibility::{Visibility, VisibilityExplicitness},
     AdtId, BuiltinType, ConstId, ExternCrateId, HasModule, ImplId, LocalModuleId, Lookup, MacroId,
@@ -137,6 +139,36 @@ pub(crate) enum BuiltinShadowMode {
     Other,
 }
 
+/// Returns the module a non-glob import imports from, and the name it imports.
+fn import_source(db: &dyn DefDatabase, import: ImportId) -> Option<(ModuleId, Name)> {
+    let loc = import.import.lookup(db);
+    let tree = loc.id.item_tree(db);
+    let mut path = None;
+    tree[loc.id.value].use_tree.expand(|idx, it, _, _| {
+        if idx == import.idx {
+            path = Some(it);
+        }
+    });
+    let path = path?;
+    let (name, qualifier) = path.segments().split_last()?;
+    if qualifier.is_empty() && path.kind == PathKind::Plain {
+        // Extern crates and items of the crate root, which we don't follow.
+        return None;
+    }
+    let qualifier = ModPath::from_segments(path.kind, qualifier.iter().cloned());
+    Some((resolve_module(db, loc.container, &qualifier)?, name.clone()))
+}
+
+fn resolve_module(db: &dyn DefDatabase, from: ModuleId, path: &ModPath) -> Option<ModuleId> {
+    let def_map = from.def_map(db);
+    let (res, unresolved_segment) =
+        def_map.resolve_path(db, from.local_id, path, BuiltinShadowMode::Module, None);
+    match (res.take_types()?, unresolved_segment) {
+        (ModuleDefId::ModuleId(it), None) => Some(it),
+        _ => None,
+    }
+}
+
 /// Legacy macros can only be accessed through special methods like `get_legacy_macros`.
 /// Other methods will only resolve values, types and module scoped macros only.
 impl ItemScope {
@@ -218,6 +250,100 @@ impl ItemScope {
         res
     }
 
+    /// Returns the imports through which `item` ends up in this scope under `name`, starting
+    /// with the import in this scope and following the re-exports up to the one importing the
+    /// definition itself. Renamed re-exports are followed by their original name, glob re-exports
+    /// are reported as their `*` use tree.
+    pub fn reexport_chain(
+        &self,
+        db: &dyn DefDatabase,
+        name: &Name,
+        item: ItemInNs,
+    ) -> Vec<ImportId> {
+        let mut chain = Vec::new();
+        let mut def_map;
+        let mut scope = self;
+        let mut name = name.clone();
+        // Glob imports may form cycles, so bound the walk.
+        for _ in 0..32 {
+            let module = match scope.import_of(&name, item) {
+                Some(Some(ImportOrExternCrate::Import(import))) => {
+                    chain.push(import);
+                    let Some((module, next_name)) = import_source(db, import) else { break };
+                    name = next_name;
+                    module
+                }
+                Some(Some(ImportOrExternCrate::ExternCrate(_))) | None => break,
+                Some(None) => {
+                    let def = match item {
+                        ItemInNs::Types(it) | ItemInNs::Values(it) => it,
+                        ItemInNs::Macros(it) => it.into(),
+                    };
+                    if scope.declarations.contains(&def) {
+                        break;
+                    }
+                    let Some((glob, module)) = scope.glob_source(db, &name, item) else { break };
+                    if chain.contains(&glob) {
+                        break;
+                    }
+                    chain.push(glob);
+                    module
+                }
+            };
+            def_map = module.def_map(db);
+            scope = &def_map[module.local_id].scope;
+        }
+        chain
+    }
+
+    /// Returns `None` if `name` doesn't resolve to `item` in this scope, and otherwise the import
+    /// it came from, if any.
+    fn import_of(&self, name: &Name, item: ItemInNs) -> Option<Option<ImportOrExternCrate>> {
+        match item {
+            ItemInNs::Types(def) => match self.types.get(name) {
+                Some(&(it, _, import)) if it == def => Some(import),
+                _ => None,
+            },
+            ItemInNs::Values(def) => match self.values.get(name) {
+                Some(&(it, _, import)) if it == def => {
+                    Some(import.map(ImportOrExternCrate::Import))
+                }
+                _ => None,
+            },
+            ItemInNs::Macros(def) => match self.macros.get(name) {
+                Some(&(it, _, import)) if it == def => {
+                    Some(import.map(ImportOrExternCrate::Import))
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// Finds the glob import of this scope that `item` has been imported through as `name`.
+    fn glob_source(
+        &self,
+        db: &dyn DefDatabase,
+        name: &Name,
+        item: ItemInNs,
+    ) -> Option<(ImportId, ModuleId)> {
+        self.use_decls.iter().find_map(|&use_id| {
+            let loc = use_id.lookup(db);
+            let tree = loc.id.item_tree(db);
+            let mut found = None;
+            tree[loc.id.value].use_tree.expand(|idx, path, kind, _| {
+                if found.is_some() || !matches!(kind, ImportKind::Glob) {
+                    return;
+                }
+                let Some(module) = resolve_module(db, loc.container, &path) else { return };
+                let def_map = module.def_map(db);
+                if def_map[module.local_id].scope.import_of(name, item).is_some() {
+                    found = Some((ImportId { import: use_id, idx }, module));
+                }
+            });
+            found
+        })
+    }
+
     pub fn declarations(&self) -> impl Iterator<Item = ModuleDefId> + '_ {
         self.declarations.iter().copied()
     }
@@ -367,6 +493,10 @@ impl ItemScope {
         self.extern_crate_decls.push(extern_crate);
     }
 
+    pub(crate) fn define_use_decl(&mut self, use_: UseId) {
+        self.use_decls.push(use_);
+    }
+
     pub(crate) fn define_unnamed_const(&mut self, konst: ConstId) {
         self.unnamed_consts.push(konst);
     }
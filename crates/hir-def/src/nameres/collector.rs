@@ -1619,6 +1619,7 @@ impl ModCollector<'_, '_> {
                         id: ItemTreeId::new(self.tree_id, item_tree_id),
                     }
                     .intern(db);
+                    self.def_collector.def_map.modules[self.module_id].scope.define_use_decl(id);
                     let is_prelude = attrs.by_key("prelude_import").exists();
                     Import::from_use(
                         self.item_tree,
@@ -381,7 +381,10 @@ macro_rules! __known_path {
     (core::ops::RangeInclusive) => {};
     (core::future::Future) => {};
     (core::future::IntoFuture) => {};
+    (core::marker::Send) => {};
     (core::ops::Try) => {};
+    (alloc::vec::Vec) => {};
+    (std::vec::Vec) => {};
     ($path:path) => {
         compile_error!("Please register your known path in the path module")
     };
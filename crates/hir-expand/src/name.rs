@@ -295,9 +295,11 @@ pub mod known {
         ops,
         fmt,
         future,
+        marker,
         result,
         string,
         boxed,
+        vec,
         option,
         prelude,
         rust_2015,
@@ -340,6 +342,8 @@ pub mod known {
         RangeTo,
         Range,
         String,
+        Vec,
+        Send,
         Neg,
         Not,
         None,
@@ -354,6 +358,10 @@ pub mod known {
         // Components of known path (function name)
         filter_map,
         next,
+        collect,
+        copied,
+        cloned,
+        into_iter,
         iter_mut,
         len,
         is_empty,
@@ -472,6 +480,7 @@ pub mod known {
     pub const SELF_TYPE: super::Name = super::Name::new_static("Self");
 
     pub const STATIC_LIFETIME: super::Name = super::Name::new_static("'static");
+    pub const UNDERSCORE_LIFETIME: super::Name = super::Name::new_static("'_");
     pub const DOLLAR_CRATE: super::Name = super::Name::new_static("$crate");
 
     #[macro_export]
@@ -15,13 +15,17 @@ use stdx::never;
 use triomphe::Arc;
 
 use crate::{
-    db::HirDatabase, infer::InferenceContext, lower::ParamLoweringMode,
-    mir::monomorphize_mir_body_bad, to_placeholder_idx, utils::Generics, Const, ConstData,
-    ConstScalar, ConstValue, GenericArg, Interner, MemoryMap, Substitution, TraitEnvironment, Ty,
-    TyBuilder,
+    db::HirDatabase,
+    infer::{InferenceContext, InferenceDiagnostic},
+    lower::ParamLoweringMode,
+    mir::monomorphize_mir_body_bad,
+    to_placeholder_idx,
+    utils::Generics,
+    Const, ConstData, ConstScalar, ConstValue, GenericArg, Interner, MemoryMap, Substitution,
+    TraitEnvironment, Ty, TyBuilder,
 };
 
-use super::mir::{interpret_mir, lower_to_mir, pad16, MirEvalError, MirLowerError};
+use super::mir::{interpret_mir, lower_to_mir, pad16, MirEvalError, MirLowerError, MirSpan};
 
 /// Extension trait for [`Const`]
 pub trait ConstExt {
@@ -312,8 +316,19 @@ pub(crate) fn eval_to_const(
         }
     }
     if let Ok(mir_body) = lower_to_mir(ctx.db, ctx.owner, ctx.body, &infer, expr) {
-        if let Ok(result) = interpret_mir(db, Arc::new(mir_body), true, None).0 {
-            return result;
+        let mut err = match interpret_mir(db, Arc::new(mir_body), true, None).0 {
+            Ok(result) => return result,
+            Err(err) => err,
+        };
+        while let MirEvalError::InFunction(inner, _) = err {
+            err = *inner;
+        }
+        if let MirEvalError::ArithmeticOverflow { op, lhs, rhs, ty, span, owner } = err {
+            let expr = match span {
+                MirSpan::ExprId(it) if owner == ctx.owner => it,
+                _ => expr,
+            };
+            ctx.push_diagnostic(InferenceDiagnostic::ArithmeticOverflow { expr, op, lhs, rhs, ty });
         }
     }
     unknown_const(infer[expr].clone())
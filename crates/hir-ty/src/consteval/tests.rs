@@ -10,7 +10,7 @@ use crate::{
 };
 
 use super::{
-    super::mir::{MirEvalError, MirLowerError},
+    super::mir::{BinOp, MirEvalError, MirLowerError},
     ConstEvalError,
 };
 
@@ -133,7 +133,15 @@ fn bit_op() {
     check_number(r#"const GOAL: i8 = 1 << 7"#, (1i8 << 7) as i128);
     check_number(r#"const GOAL: i8 = -1 << 2"#, (-1i8 << 2) as i128);
     check_fail(r#"const GOAL: i8 = 1 << 8"#, |e| {
-        e == ConstEvalError::MirEvalError(MirEvalError::Panic("Overflow in Shl".to_owned()))
+        matches!(
+            e,
+            ConstEvalError::MirEvalError(MirEvalError::ArithmeticOverflow {
+                op: BinOp::Shl,
+                lhs: 1,
+                rhs: 8,
+                ..
+            })
+        )
     });
     check_number(r#"const GOAL: i32 = 100000000i32 << 11"#, (100000000i32 << 11) as i128);
 }
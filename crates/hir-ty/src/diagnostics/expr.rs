@@ -4,20 +4,21 @@
 
 use std::fmt;
 
+use chalk_ir::{DebruijnIndex, TypeFlags};
 use either::Either;
 use hir_def::lang_item::LangItem;
+use hir_def::resolver::{resolver_for_expr, ValueNs};
 use hir_def::{resolver::HasResolver, AdtId, AssocItemId, DefWithBodyId, HasModule};
-use hir_def::{ItemContainerId, Lookup};
-use hir_expand::name;
+use hir_def::{ItemContainerId, Lookup, TraitId};
+use hir_expand::{mod_path::path, name};
 use itertools::Itertools;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use rustc_pattern_analysis::constructor::Constructor;
 use syntax::{ast, AstNode};
 use tracing::debug;
 use triomphe::Arc;
 use typed_arena::Arena;
 
-use crate::Interner;
 use crate::{
     db::HirDatabase,
     diagnostics::match_check::{
@@ -25,12 +26,14 @@ use crate::{
         pat_analysis::{self, DeconstructedPat, MatchCheckCtx, WitnessPat},
     },
     display::HirDisplay,
-    InferenceResult, Ty, TyExt,
+    method_resolution::TyFingerprint,
+    InferenceResult, Substitution, Ty, TyExt, TyKind,
 };
+use crate::{fold_tys, Canonical, CanonicalVarKinds, FnTrait, InEnvironment, Interner, TyBuilder};
 
 pub(crate) use hir_def::{
     body::Body,
-    hir::{Expr, ExprId, MatchArm, Pat, PatId, Statement},
+    hir::{BindingId, Expr, ExprId, MatchArm, Pat, PatId, Statement},
     LocalFieldId, VariantId,
 };
 
@@ -43,6 +46,9 @@ pub enum BodyValidationDiagnostic {
     ReplaceFilterMapNextWithFindMap {
         method_call_expr: ExprId,
     },
+    CloneOnCopy {
+        method_call_expr: ExprId,
+    },
     MissingMatchArms {
         match_expr: ExprId,
         uncovered_patterns: String,
@@ -57,6 +63,15 @@ pub enum BodyValidationDiagnostic {
     RemoveUnnecessaryElse {
         if_expr: ExprId,
     },
+    NeedlessCollect {
+        collect_expr: ExprId,
+        iter_expr: ExprId,
+    },
+    NonSendHeldAcrossAwait {
+        await_expr: ExprId,
+        binding_pat: PatId,
+        ty: String,
+    },
 }
 
 impl BodyValidationDiagnostic {
@@ -69,8 +84,14 @@ impl BodyValidationDiagnostic {
             tracing::span!(tracing::Level::INFO, "BodyValidationDiagnostic::collect").entered();
         let infer = db.infer(owner);
         let body = db.body(owner);
-        let mut validator =
-            ExprValidator { owner, body, infer, diagnostics: Vec::new(), validate_lints };
+        let mut validator = ExprValidator {
+            owner,
+            body,
+            infer,
+            diagnostics: Vec::new(),
+            validate_lints,
+            method_call_on: None,
+        };
         validator.validate_body(db);
         validator.diagnostics
     }
@@ -82,6 +103,8 @@ struct ExprValidator {
     infer: Arc<InferenceResult>,
     diagnostics: Vec<BodyValidationDiagnostic>,
     validate_lints: bool,
+    /// Maps receivers to the method calls made on them, built on first use.
+    method_call_on: Option<FxHashMap<ExprId, ExprId>>,
 }
 
 impl ExprValidator {
@@ -121,6 +144,9 @@ impl ExprValidator {
                 Expr::Block { .. } => {
                     self.validate_block(db, expr);
                 }
+                Expr::Async { .. } => {
+                    self.check_for_non_send_across_await(db, id);
+                }
                 _ => {}
             }
         }
@@ -154,11 +180,16 @@ impl ExprValidator {
             // FIXME: Due to shortcomings in the current type system implementation, only emit
             // this diagnostic if there are no type mismatches in the containing function.
         } else if let Expr::MethodCall { receiver, .. } = expr {
-            let (callee, _) = match self.infer.method_resolution(call_id) {
+            let (callee, subst) = match self.infer.method_resolution(call_id) {
                 Some(it) => it,
                 None => return,
             };
 
+            if self.is_clone_on_copy(db, *receiver, callee, &subst) {
+                self.diagnostics
+                    .push(BodyValidationDiagnostic::CloneOnCopy { method_call_expr: call_id });
+            }
+
             let checker = filter_map_next_checker.get_or_insert_with(|| {
                 FilterMapNextChecker::new(&self.owner.resolver(db.upcast()), db)
             });
@@ -169,11 +200,327 @@ impl ExprValidator {
                 });
             }
 
+            if let Some(collect_expr) = self.needless_collect(db, call_id, callee) {
+                self.diagnostics.push(BodyValidationDiagnostic::NeedlessCollect {
+                    collect_expr,
+                    iter_expr: call_id,
+                });
+            }
+
             let receiver_ty = self.infer[*receiver].clone();
             checker.prev_receiver_ty = Some(receiver_ty);
         }
     }
 
+    /// Checks whether `callee` is `Clone::clone` called on a receiver whose type is `Copy`.
+    ///
+    /// Receivers that got auto-dereferenced are skipped, as dropping the `.clone()` there would
+    /// change the type of the expression.
+    fn is_clone_on_copy(
+        &self,
+        db: &dyn HirDatabase,
+        receiver: ExprId,
+        callee: hir_def::FunctionId,
+        subst: &Substitution,
+    ) -> bool {
+        let ItemContainerId::TraitId(trait_) = callee.lookup(db.upcast()).container else {
+            return false;
+        };
+        if db.lang_attr(trait_.into()) != Some(LangItem::Clone) {
+            return false;
+        }
+        let Some(self_ty) = subst.at(Interner, 0).ty(Interner) else {
+            return false;
+        };
+        let receiver_ty = &self.infer[receiver];
+        if receiver_ty != self_ty || self_ty.contains_unknown() {
+            return false;
+        }
+        self_ty.clone().is_copy(db, self.owner)
+    }
+
+    /// Checks whether `call` iterates over the `Vec` that `Iterator::collect` just created, through
+    /// `.into_iter()` or through `.iter().copied()` and `.iter().cloned()`, and returns the
+    /// `collect` call.
+    ///
+    /// The `Vec` might be needed to end a borrow of what was collected, so this conservatively
+    /// bails out if the new iterator outlives the method chain, or if closures later in the chain
+    /// mutate or move their captures.
+    fn needless_collect(
+        &mut self,
+        db: &dyn HirDatabase,
+        call: ExprId,
+        callee: hir_def::FunctionId,
+    ) -> Option<ExprId> {
+        let receiver_of = |expr: ExprId| match &self.body[expr] {
+            Expr::MethodCall { receiver, .. } => Some(*receiver),
+            _ => None,
+        };
+        let iterator_trait = match db
+            .lang_item(self.owner.krate(db.upcast()), LangItem::IteratorNext)?
+            .as_function()?
+            .lookup(db.upcast())
+            .container
+        {
+            ItemContainerId::TraitId(it) => it,
+            _ => return None,
+        };
+        let is_iterator_method = |f: hir_def::FunctionId| {
+            f.lookup(db.upcast()).container == ItemContainerId::TraitId(iterator_trait)
+        };
+
+        let callee_name = &db.function_data(callee).name;
+        let vec_expr = if db.lang_attr(callee.into()) == Some(LangItem::IntoIterIntoIter) {
+            receiver_of(call)?
+        } else if is_iterator_method(callee)
+            && (*callee_name == name![copied] || *callee_name == name![cloned])
+        {
+            let iter_call = receiver_of(call)?;
+            let (iter_fn, _) = self.infer.method_resolution(iter_call)?;
+            if db.function_data(iter_fn).name != name![iter] {
+                return None;
+            }
+            receiver_of(iter_call)?
+        } else {
+            return None;
+        };
+        let (collect_fn, _) = self.infer.method_resolution(vec_expr)?;
+        if !is_iterator_method(collect_fn) || db.function_data(collect_fn).name != name![collect] {
+            return None;
+        }
+        let resolver = self.owner.resolver(db.upcast());
+        let vec = resolver
+            .resolve_known_struct(db.upcast(), &path![alloc::vec::Vec])
+            .or_else(|| resolver.resolve_known_struct(db.upcast(), &path![std::vec::Vec]))?;
+        match self.infer[vec_expr].as_adt() {
+            Some((AdtId::StructId(it), _)) if it == vec => (),
+            _ => return None,
+        }
+
+        let body = &self.body;
+        let method_call_on = self.method_call_on.get_or_insert_with(|| {
+            body.exprs
+                .iter()
+                .filter_map(|(id, expr)| match expr {
+                    Expr::MethodCall { receiver, .. } => Some((*receiver, id)),
+                    _ => None,
+                })
+                .collect()
+        });
+        let mut top = call;
+        while let Some(&parent) = method_call_on.get(&top) {
+            let Expr::MethodCall { args, .. } = &body[parent] else { break };
+            let mutating_closure = args.iter().any(|arg| {
+                self.infer[*arg]
+                    .as_closure()
+                    .map_or(false, |it| self.infer.closure_info(&it).1 != FnTrait::Fn)
+            });
+            if mutating_closure {
+                return None;
+            }
+            top = parent;
+        }
+        let iter_ty = &self.infer[call];
+        let mut escapes = false;
+        fold_tys(
+            self.infer[top].clone(),
+            |ty, _| {
+                escapes |= ty == *iter_ty;
+                ty
+            },
+            DebruijnIndex::INNERMOST,
+        );
+        (!escapes && !iter_ty.contains_unknown()).then_some(vec_expr)
+    }
+
+    /// Looks for `let` bindings of non-`Send` values that are still alive at an `.await` of the
+    /// future created by the async block `async_expr`, which makes that future non-`Send`.
+    ///
+    /// This approximates rustc's liveness analysis per statement: a binding is held across an
+    /// `.await` in a later statement of its block if it is used after that statement, or if it
+    /// needs to be dropped at the end of the block and was not mentioned (and so possibly moved
+    /// away) before.
+    fn check_for_non_send_across_await(&mut self, db: &dyn HirDatabase, async_expr: ExprId) {
+        if !self.validate_lints {
+            return;
+        }
+        let Some(send_trait) = self
+            .owner
+            .resolver(db.upcast())
+            .resolve_known_trait(db.upcast(), &path![core::marker::Send])
+        else {
+            return;
+        };
+        let body = Arc::clone(&self.body);
+        let mut blocks = vec![async_expr];
+        while let Some(expr) = blocks.pop() {
+            if let Expr::Block { statements, tail, .. }
+            | Expr::Unsafe { statements, tail, .. }
+            | Expr::Async { statements, tail, .. } = &body[expr]
+            {
+                self.check_block_for_non_send_across_await(db, send_trait, statements, *tail);
+            }
+            body[expr].walk_child_exprs(|child| {
+                // Closures and nested async blocks don't suspend this future.
+                if !matches!(body[child], Expr::Closure { .. } | Expr::Async { .. }) {
+                    blocks.push(child);
+                }
+            });
+        }
+    }
+
+    fn check_block_for_non_send_across_await(
+        &mut self,
+        db: &dyn HirDatabase,
+        send_trait: TraitId,
+        statements: &[Statement],
+        tail: Option<ExprId>,
+    ) {
+        let statement_exprs = |stmt: &Statement| match *stmt {
+            Statement::Let { initializer, else_branch, .. } => {
+                initializer.into_iter().chain(else_branch)
+            }
+            Statement::Expr { expr, .. } => Some(expr).into_iter().chain(None),
+            Statement::Item => None.into_iter().chain(None),
+        };
+        for (idx, stmt) in statements.iter().enumerate() {
+            let Statement::Let { pat, .. } = *stmt else { continue };
+            let later: Vec<ExprId> =
+                statements[idx + 1..].iter().flat_map(statement_exprs).chain(tail).collect();
+            let Some((await_idx, await_expr)) = later
+                .iter()
+                .enumerate()
+                .find_map(|(await_idx, &expr)| Some((await_idx, self.first_await(expr)?)))
+            else {
+                continue;
+            };
+
+            let mut bindings = Vec::new();
+            self.body.walk_pats(pat, &mut |pat| {
+                if let Pat::Bind { id, .. } = self.body[pat] {
+                    bindings.push((id, pat));
+                }
+            });
+            for (binding, binding_pat) in bindings {
+                let ty = &self.infer[binding];
+                // Whether values of generic, projected or opaque types are `Send` is up to the
+                // caller, so only concrete types are reported.
+                if ty.contains_unknown()
+                    || ty
+                        .data(Interner)
+                        .flags
+                        .intersects(TypeFlags::HAS_TY_PLACEHOLDER | TypeFlags::HAS_PROJECTION)
+                    || self.is_send(db, send_trait, ty)
+                {
+                    continue;
+                }
+                let mentioned_in =
+                    |exprs: &[ExprId]| exprs.iter().any(|&it| self.mentions(db, it, binding));
+                let used_after_await = mentioned_in(&later[await_idx + 1..]);
+                let dropped_at_end =
+                    !ty.clone().is_copy(db, self.owner) && !mentioned_in(&later[..=await_idx]);
+                if used_after_await || dropped_at_end {
+                    self.diagnostics.push(BodyValidationDiagnostic::NonSendHeldAcrossAwait {
+                        await_expr,
+                        binding_pat,
+                        ty: ty.display(db).to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Returns the first `.await` evaluated by `expr`, outside of closures and async blocks.
+    fn first_await(&self, expr: ExprId) -> Option<ExprId> {
+        match &self.body[expr] {
+            Expr::Closure { .. } | Expr::Async { .. } => return None,
+            _ => {}
+        }
+        let mut found = None;
+        self.body[expr].walk_child_exprs(|child| {
+            if found.is_none() {
+                found = self.first_await(child);
+            }
+        });
+        found.or_else(|| matches!(self.body[expr], Expr::Await { .. }).then_some(expr))
+    }
+
+    fn mentions(&self, db: &dyn HirDatabase, expr: ExprId, binding: BindingId) -> bool {
+        if let Expr::Path(path) = &self.body[expr] {
+            if path.mod_path().and_then(|it| it.as_ident()) == Some(&self.body[binding].name) {
+                let resolver = resolver_for_expr(db.upcast(), self.owner, expr);
+                if resolver.resolve_path_in_value_ns_fully(db.upcast(), path)
+                    == Some(ValueNs::LocalBinding(binding))
+                {
+                    return true;
+                }
+            }
+        }
+        let mut found = false;
+        self.body[expr].walk_child_exprs(|child| {
+            found = found || self.mentions(db, child, binding);
+        });
+        found
+    }
+
+    fn is_send(&self, db: &dyn HirDatabase, send_trait: TraitId, ty: &Ty) -> bool {
+        self.is_send_(db, send_trait, ty, &mut FxHashSet::default())
+    }
+
+    fn is_send_(
+        &self,
+        db: &dyn HirDatabase,
+        send_trait: TraitId,
+        ty: &Ty,
+        visited: &mut FxHashSet<AdtId>,
+    ) -> bool {
+        let krate = self.owner.module(db.upcast()).krate();
+        let trait_ref = TyBuilder::trait_ref(db, send_trait).push(ty.clone()).build();
+        let env = db.trait_environment_for_body(self.owner);
+        let goal = Canonical {
+            value: InEnvironment::new(&env.env, chalk_ir::cast::Cast::cast(trait_ref, Interner)),
+            binders: CanonicalVarKinds::empty(Interner),
+        };
+        if db.trait_solve(krate, None, goal).is_none() {
+            return false;
+        }
+        // Chalk doesn't know the fields of ADTs, so it considers every ADT without a negative impl
+        // to be `Send`. Check the fields of those that don't implement `Send` explicitly.
+        match ty.kind(Interner) {
+            TyKind::Adt(chalk_ir::AdtId(adt), subst) => {
+                if !visited.insert(*adt) {
+                    return true;
+                }
+                let has_impl = db.trait_impls_in_deps(krate).iter().any(|impls| {
+                    impls.has_impls_for_trait_and_self_ty(send_trait, TyFingerprint::Adt(*adt))
+                });
+                if has_impl {
+                    return true;
+                }
+                let variants: Vec<VariantId> = match *adt {
+                    AdtId::StructId(it) => vec![it.into()],
+                    AdtId::UnionId(it) => vec![it.into()],
+                    AdtId::EnumId(it) => {
+                        db.enum_data(it).variants.iter().map(|&(it, _)| it.into()).collect()
+                    }
+                };
+                variants.into_iter().all(|variant| {
+                    db.field_types(variant).iter().all(|(_, field_ty)| {
+                        let field_ty = field_ty.clone().substitute(Interner, subst);
+                        self.is_send_(db, send_trait, &field_ty, visited)
+                    })
+                })
+            }
+            TyKind::Tuple(_, subst) => subst
+                .type_parameters(Interner)
+                .all(|it| self.is_send_(db, send_trait, &it, visited)),
+            TyKind::Array(elem, _) | TyKind::Slice(elem) => {
+                self.is_send_(db, send_trait, elem, visited)
+            }
+            _ => true,
+        }
+    }
+
     fn validate_match(
         &mut self,
         match_expr: ExprId,
@@ -318,7 +665,13 @@ impl ExprValidator {
         match &body.exprs[body_expr] {
             Expr::Block { statements, tail, .. } => {
                 let last_stmt = tail.or_else(|| match statements.last()? {
-                    Statement::Expr { expr, .. } => Some(*expr),
+                    // A semicolon discards the value of anything but a direct `return`, so
+                    // the returns nested in e.g. `match x { .. };` are not in tail position.
+                    Statement::Expr { expr, has_semi }
+                        if !has_semi || matches!(body.exprs[*expr], Expr::Return { .. }) =>
+                    {
+                        Some(*expr)
+                    }
                     _ => None,
                 });
                 if let Some(last_stmt) = last_stmt {
@@ -1,6 +1,7 @@
 //! Provides validations for unsafe code. Currently checks if unsafe functions are missing
 //! unsafe blocks.
 
+use chalk_ir::Safety;
 use hir_def::{
     body::Body,
     hir::{Expr, ExprId, UnaryOp},
@@ -77,10 +78,14 @@ fn walk_unsafe(
     let expr = &body.exprs[current];
     match expr {
         &Expr::Call { callee, .. } => {
-            if let Some(func) = infer[callee].as_fn_def(db) {
-                if is_fn_unsafe_to_call(db, func) {
-                    unsafe_expr_cb(UnsafeExpr { expr: current, inside_unsafe_block });
+            let is_unsafe = match infer[callee].kind(Interner) {
+                TyKind::Function(fn_ptr) => fn_ptr.sig.safety == Safety::Unsafe,
+                _ => {
+                    infer[callee].as_fn_def(db).map_or(false, |func| is_fn_unsafe_to_call(db, func))
                 }
+            };
+            if is_unsafe {
+                unsafe_expr_cb(UnsafeExpr { expr: current, inside_unsafe_block });
             }
         }
         Expr::Path(path) => {
@@ -462,6 +462,19 @@ impl HirDisplay for Const {
             ConstValue::Concrete(c) => match &c.interned {
                 ConstScalar::Bytes(b, m) => render_const_scalar(f, b, m, &data.ty),
                 ConstScalar::UnevaluatedConst(c, parameters) => {
+                    // Show the value if the constant can be evaluated, but keep the name in code
+                    // that is inserted into the source or when evaluation fails (for example
+                    // because it still depends on generic parameters).
+                    if matches!(f.display_target, DisplayTarget::Diagnostics) {
+                        if let Ok(evaluated) = f.db.const_eval(*c, parameters.clone(), None) {
+                            if let ConstValue::Concrete(ConcreteConst {
+                                interned: ConstScalar::Bytes(b, m),
+                            }) = &evaluated.data(Interner).value
+                            {
+                                return render_const_scalar(f, b, m, &data.ty);
+                            }
+                        }
+                    }
                     write!(f, "{}", c.name(f.db.upcast()))?;
                     hir_fmt_generics(
                         f,
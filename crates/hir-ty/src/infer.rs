@@ -29,7 +29,7 @@ use chalk_ir::{
     fold::TypeFoldable,
     interner::HasInterner,
     visit::{TypeSuperVisitable, TypeVisitable, TypeVisitor},
-    DebruijnIndex, Mutability, Safety, Scalar, TyKind, TypeFlags, Variance,
+    DebruijnIndex, Mutability, Safety, Scalar, TyKind, TyVariableKind, TypeFlags, Variance,
 };
 use either::Either;
 use hir_def::{
@@ -37,7 +37,7 @@ use hir_def::{
     builtin_type::{BuiltinInt, BuiltinType, BuiltinUint},
     data::{ConstData, StaticData},
     hir::LabelId,
-    hir::{BindingAnnotation, BindingId, ExprId, ExprOrPatId, PatId},
+    hir::{BindingAnnotation, BindingId, Expr, ExprId, ExprOrPatId, PatId},
     lang_item::{LangItem, LangItemTarget},
     layout::Integer,
     path::{ModPath, Path},
@@ -58,6 +58,7 @@ use crate::{
     error_lifetime, fold_tys,
     infer::{coerce::CoerceMany, unify::InferenceTable},
     lower::ImplTraitLoweringMode,
+    mir::BinOp,
     to_assoc_type_id,
     traits::FnTrait,
     utils::{Generics, InTypeConstIdMetadata, UnevaluatedConstEvaluatorFolder},
@@ -251,6 +252,15 @@ pub enum InferenceDiagnostic {
         expr: ExprId,
         expected: Ty,
     },
+    /// An integer operation overflowed, or divided by zero, while evaluating a constant
+    /// expression like an array length.
+    ArithmeticOverflow {
+        expr: ExprId,
+        op: BinOp,
+        lhs: i128,
+        rhs: i128,
+        ty: Ty,
+    },
 }
 
 /// A mismatch between an expected and an inferred type.
@@ -453,6 +463,9 @@ pub struct InferenceResult {
     pub(crate) closure_info: FxHashMap<ClosureId, (Vec<CapturedItem>, FnTrait)>,
     // FIXME: remove this field
     pub mutated_bindings_in_closure: FxHashSet<BindingId>,
+    /// Integer and float literals whose type was left unconstrained and fell back to `i32` or
+    /// `f64`.
+    pub defaulted_literals: FxHashSet<ExprId>,
 }
 
 impl InferenceResult {
@@ -641,6 +654,7 @@ impl<'a> InferenceContext<'a> {
     // there is no problem in it being `pub(crate)`, remove this comment.
     pub(crate) fn resolve_all(self) -> InferenceResult {
         let InferenceContext {
+            body,
             mut table,
             mut result,
             deferred_cast_checks,
@@ -672,8 +686,18 @@ impl<'a> InferenceContext<'a> {
             closure_info: _,
             mutated_bindings_in_closure: _,
             tuple_field_access_types: _,
+            defaulted_literals,
         } = &mut result;
 
+        for (expr, ty) in type_of_expr.iter() {
+            let unconstrained = matches!(
+                table.resolve_ty_shallow(ty).kind(Interner),
+                TyKind::InferenceVar(_, TyVariableKind::Integer | TyVariableKind::Float)
+            );
+            if unconstrained && matches!(body[expr], Expr::Literal(_)) {
+                defaulted_literals.insert(expr);
+            }
+        }
         table.fallback_if_possible();
 
         // Comment from rustc:
@@ -1033,7 +1057,7 @@ impl<'a> InferenceContext<'a> {
         self.result.type_of_binding.insert(id, ty);
     }
 
-    fn push_diagnostic(&mut self, diagnostic: InferenceDiagnostic) {
+    pub(crate) fn push_diagnostic(&mut self, diagnostic: InferenceDiagnostic) {
         self.result.diagnostics.push(diagnostic);
     }
 
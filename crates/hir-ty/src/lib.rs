@@ -94,7 +94,7 @@ pub use mapping::{
 };
 pub use method_resolution::check_orphan_rules;
 pub use traits::TraitEnvironment;
-pub use utils::{all_super_traits, is_fn_unsafe_to_call};
+pub use utils::{all_super_traits, direct_super_traits, is_fn_unsafe_to_call};
 
 pub use chalk_ir::{
     cast::Cast,
@@ -1005,6 +1005,10 @@ pub fn known_const_to_ast(konst: &Const, db: &dyn HirDatabase) -> Option<ConstAr
             ConstScalar::UnevaluatedConst(GeneralConstId::InTypeConstId(cid), _) => {
                 return Some(cid.source(db.upcast()));
             }
+            // Refer to named constants by name instead of the value they evaluate to.
+            ConstScalar::UnevaluatedConst(id @ GeneralConstId::ConstId(_), _) => {
+                return Some(make::expr_const_value(&id.name(db.upcast())));
+            }
             ConstScalar::Unknown => return None,
             _ => (),
         }
@@ -242,6 +242,15 @@ impl TraitImpls {
             .flat_map(|map| map.values().flat_map(|v| v.iter().copied()))
     }
 
+    /// Queries all impls that may apply to any self type, together with their trait.
+    pub fn blanket_impls(&self) -> impl Iterator<Item = (TraitId, ImplId)> + '_ {
+        self.map.iter().flat_map(|(&trait_, map)| {
+            map.get(&None)
+                .into_iter()
+                .flat_map(move |impls| impls.iter().map(move |&it| (trait_, it)))
+        })
+    }
+
     /// Queries all impls of `trait_` that may apply to `self_ty`.
     pub fn for_trait_and_self_ty(
         &self,
@@ -323,6 +323,16 @@ pub enum MirEvalError {
     /// then use this type of error.
     UndefinedBehavior(String),
     Panic(String),
+    /// An integer operation overflowed or divided by zero. `span` is the statement of `owner`
+    /// performing it, if known.
+    ArithmeticOverflow {
+        op: BinOp,
+        lhs: i128,
+        rhs: i128,
+        ty: Ty,
+        span: MirSpan,
+        owner: DefWithBodyId,
+    },
     // FIXME: This should be folded into ConstEvalError?
     MirLowerError(FunctionId, MirLowerError),
     MirLowerErrorForClosure(ClosureId, MirLowerError),
@@ -416,6 +426,7 @@ impl MirEvalError {
             MirEvalError::UndefinedBehavior(_)
             | MirEvalError::TargetDataLayoutNotAvailable(_)
             | MirEvalError::Panic(_)
+            | MirEvalError::ArithmeticOverflow { .. }
             | MirEvalError::MirLowerErrorForClosure(_, _)
             | MirEvalError::TypeIsUnsized(_, _)
             | MirEvalError::NotSupported(_)
@@ -443,6 +454,9 @@ impl std::fmt::Debug for MirEvalError {
                 f.debug_tuple("UndefinedBehavior").field(arg0).finish()
             }
             Self::Panic(msg) => write!(f, "Panic with message:\n{msg:?}"),
+            Self::ArithmeticOverflow { op, lhs, rhs, .. } => {
+                write!(f, "Overflow in {op:?} of {lhs} and {rhs}")
+            }
             Self::TargetDataLayoutNotAvailable(arg0) => {
                 f.debug_tuple("TargetDataLayoutNotAvailable").field(arg0).finish()
             }
@@ -847,6 +861,8 @@ impl Evaluator<'_> {
             let Some(mut my_stack_frame) = self.code_stack.pop() else {
                 not_supported!("missing stack frame");
             };
+            // The frame is moved into the closure below, but is part of the stack of its errors.
+            let current_frame = (my_stack_frame.locals.body.owner, my_stack_frame.span);
             let e = (|| {
                 let locals = &mut my_stack_frame.locals;
                 let body = locals.body.clone();
@@ -861,7 +877,24 @@ impl Evaluator<'_> {
                         match &statement.kind {
                             StatementKind::Assign(l, r) => {
                                 let addr = self.place_addr(l, locals)?;
-                                let result = self.eval_rvalue(r, locals)?;
+                                let result = self.eval_rvalue(r, locals).map_err(|e| match e {
+                                    MirEvalError::ArithmeticOverflow {
+                                        span: MirSpan::Unknown,
+                                        op,
+                                        lhs,
+                                        rhs,
+                                        ty,
+                                        owner,
+                                    } => MirEvalError::ArithmeticOverflow {
+                                        op,
+                                        lhs,
+                                        rhs,
+                                        ty,
+                                        span: statement.span,
+                                        owner,
+                                    },
+                                    e => e,
+                                })?;
                                 self.copy_from_interval_or_owned(addr, result)?;
                                 locals.drop_flags.add_place(*l, &locals.body.projection_store);
                             }
@@ -961,9 +994,11 @@ impl Evaluator<'_> {
                 Err(e) => {
                     let my_code_stack = mem::replace(&mut self.code_stack, prev_code_stack);
                     let mut error_stack = vec![];
-                    for frame in my_code_stack.into_iter().rev() {
-                        if let DefWithBodyId::FunctionId(f) = frame.locals.body.owner {
-                            error_stack.push((Either::Left(f), frame.span.0, frame.span.1));
+                    let frames =
+                        my_code_stack.iter().rev().map(|it| (it.locals.body.owner, it.span));
+                    for (owner, span) in iter::once(current_frame).chain(frames) {
+                        if let DefWithBodyId::FunctionId(f) = owner {
+                            error_stack.push((Either::Left(f), span.0, span.1));
                         }
                     }
                     return Err(MirEvalError::InFunction(Box::new(e), error_stack));
@@ -1219,15 +1254,27 @@ impl Evaluator<'_> {
                     let is_signed = matches!(ty.as_builtin(), Some(BuiltinType::Int(_)));
                     let l128 = i128::from_le_bytes(pad16(lc, is_signed));
                     let r128 = i128::from_le_bytes(pad16(rc, is_signed));
+                    let overflow = || MirEvalError::ArithmeticOverflow {
+                        op: op.clone(),
+                        lhs: l128,
+                        rhs: r128,
+                        ty: ty.clone(),
+                        span: MirSpan::Unknown,
+                        owner: locals.body.owner,
+                    };
                     let check_overflow = |r: i128| {
-                        // FIXME: this is not very correct, and only catches the basic cases.
-                        let r = r.to_le_bytes();
-                        for &k in &r[lc.len()..] {
-                            if k != 0 && (k != 255 || !is_signed) {
-                                return Err(MirEvalError::Panic(format!("Overflow in {op:?}")));
-                            }
+                        let bits = lc.len() as u32 * 8;
+                        // 128-bit operations wrap above, so their overflow isn't detected.
+                        let fits = bits >= 128
+                            || if is_signed {
+                                (-(1i128 << (bits - 1))..1i128 << (bits - 1)).contains(&r)
+                            } else {
+                                (0..1i128 << bits).contains(&r)
+                            };
+                        if !fits {
+                            return Err(overflow());
                         }
-                        Ok(Owned(r[0..lc.len()].into()))
+                        Ok(Owned(r.to_le_bytes()[0..lc.len()].into()))
                     };
                     match op {
                         BinOp::Ge | BinOp::Gt | BinOp::Le | BinOp::Lt | BinOp::Eq | BinOp::Ne => {
@@ -1245,12 +1292,8 @@ impl Evaluator<'_> {
                             let r = match op {
                                 BinOp::Add => l128.overflowing_add(r128).0,
                                 BinOp::Mul => l128.overflowing_mul(r128).0,
-                                BinOp::Div => l128.checked_div(r128).ok_or_else(|| {
-                                    MirEvalError::Panic(format!("Overflow in {op:?}"))
-                                })?,
-                                BinOp::Rem => l128.checked_rem(r128).ok_or_else(|| {
-                                    MirEvalError::Panic(format!("Overflow in {op:?}"))
-                                })?,
+                                BinOp::Div => l128.checked_div(r128).ok_or_else(overflow)?,
+                                BinOp::Rem => l128.checked_rem(r128).ok_or_else(overflow)?,
                                 BinOp::Sub => l128.overflowing_sub(r128).0,
                                 BinOp::BitAnd => l128 & r128,
                                 BinOp::BitOr => l128 | r128,
@@ -1268,15 +1311,13 @@ impl Evaluator<'_> {
                                         _ => unreachable!(),
                                     };
                                     if shift_amount as usize >= lc.len() * 8 {
-                                        return Err(MirEvalError::Panic(format!(
-                                            "Overflow in {op:?}"
-                                        )));
+                                        return Err(overflow());
                                     }
                                     if let Some(r) = r {
                                         break 'b r;
                                     }
                                 };
-                                return Err(MirEvalError::Panic(format!("Overflow in {op:?}")));
+                                return Err(overflow());
                             };
                             Owned(r.to_le_bytes()[..lc.len()].to_vec())
                         }
@@ -2438,10 +2479,11 @@ impl Evaluator<'_> {
         target_bb: Option<BasicBlockId>,
     ) -> Result<Option<StackFrame>> {
         Ok(if let Some(target_bb) = target_bb {
+            // The span is the call, in the body of the caller.
+            let span = (span, locals.body.owner);
             let (mut locals, prev_stack_ptr) =
                 self.create_locals_for_body(&mir_body, Some(destination))?;
             self.fill_locals_for_body(&mir_body, &mut locals, arg_bytes.into_iter())?;
-            let span = (span, locals.body.owner);
             Some(StackFrame { locals, destination: Some(target_bb), prev_stack_ptr, span })
         } else {
             let result = self.interpret_mir(mir_body, arg_bytes).map_err(|e| {
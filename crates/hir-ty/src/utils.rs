@@ -59,7 +59,7 @@ pub fn all_super_traits(db: &dyn DefDatabase, trait_: TraitId) -> SmallVec<[Trai
     while let Some(&t) = result.get(i) {
         // yeah this is quadratic, but trait hierarchies should be flat
         // enough that this doesn't matter
-        direct_super_traits(db, t, |tt| {
+        direct_super_traits_cb(db, t, |tt| {
             if !result.contains(&tt) {
                 result.push(tt);
             }
@@ -69,6 +69,17 @@ pub fn all_super_traits(db: &dyn DefDatabase, trait_: TraitId) -> SmallVec<[Trai
     result
 }
 
+/// Returns the direct super traits of the given trait, excluding the trait itself.
+pub fn direct_super_traits(db: &dyn DefDatabase, trait_: TraitId) -> SmallVec<[TraitId; 4]> {
+    let mut result = smallvec![];
+    direct_super_traits_cb(db, trait_, |tt| {
+        if tt != trait_ && !result.contains(&tt) {
+            result.push(tt);
+        }
+    });
+    result
+}
+
 /// Given a trait ref (`Self: Trait`), builds all the implied trait refs for
 /// super traits. The original trait ref will be included. So the difference to
 /// `all_super_traits` is that we keep track of type parameters; for example if
@@ -158,7 +169,7 @@ impl Iterator for ClauseElaborator<'_> {
     }
 }
 
-fn direct_super_traits(db: &dyn DefDatabase, trait_: TraitId, cb: impl FnMut(TraitId)) {
+fn direct_super_traits_cb(db: &dyn DefDatabase, trait_: TraitId, cb: impl FnMut(TraitId)) {
     let resolver = trait_.resolver(db);
     let generic_params = db.generic_params(trait_.into());
     let trait_self = generic_params.find_trait_self_param();
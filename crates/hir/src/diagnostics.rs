@@ -47,7 +47,9 @@ macro_rules! diagnostics {
 // ]
 
 diagnostics![
+    ArithmeticOverflow,
     BreakOutsideOfLoop,
+    CloneOnCopy,
     ExpectedFunction,
     InactiveCode,
     IncoherentImpl,
@@ -63,8 +65,10 @@ diagnostics![
     MissingMatchArms,
     MissingUnsafe,
     MovedOutOfRef,
+    NeedlessCollect,
     NeedMut,
     NonExhaustiveLet,
+    NonSendHeldAcrossAwait,
     NoSuchField,
     PrivateAssocItem,
     PrivateField,
@@ -93,6 +97,18 @@ diagnostics![
     UnusedVariable,
 ];
 
+/// An integer operation overflowed, or divided by zero, while evaluating a constant expression.
+#[derive(Debug)]
+pub struct ArithmeticOverflow {
+    pub expr: InFile<AstPtr<ast::Expr>>,
+    /// The operator, e.g. `+` or `<<`.
+    pub op: String,
+    pub lhs: i128,
+    pub rhs: i128,
+    /// The type of the left operand.
+    pub ty: Type,
+}
+
 #[derive(Debug)]
 pub struct BreakOutsideOfLoop {
     pub expr: InFile<AstPtr<ast::Expr>>,
@@ -274,6 +290,28 @@ pub struct ReplaceFilterMapNextWithFindMap {
     pub next_expr: AstPtr<ast::Expr>,
 }
 
+#[derive(Debug)]
+pub struct CloneOnCopy {
+    pub method_call_expr: InFile<AstPtr<ast::MethodCallExpr>>,
+}
+
+#[derive(Debug)]
+pub struct NeedlessCollect {
+    /// The `.collect()` call.
+    pub collect_expr: InFile<AstPtr<ast::MethodCallExpr>>,
+    /// The call iterating over the collected `Vec`.
+    pub iter_expr: InFile<AstPtr<ast::MethodCallExpr>>,
+}
+
+#[derive(Debug)]
+pub struct NonSendHeldAcrossAwait {
+    /// The `.await` suspending the future while the value is alive.
+    pub await_expr: InFile<AstPtr<ast::AwaitExpr>>,
+    /// The binding holding the value.
+    pub binding: InFile<AstPtr<ast::IdentPat>>,
+    pub ty: String,
+}
+
 #[derive(Debug)]
 pub struct MismatchedArgCount {
     pub call_expr: InFile<AstPtr<ast::Expr>>,
@@ -444,6 +482,53 @@ impl AnyDiagnostic {
                     );
                 }
             }
+            BodyValidationDiagnostic::CloneOnCopy { method_call_expr } => {
+                if let Ok(source_ptr) = source_map.expr_syntax(method_call_expr) {
+                    if let Some(ptr) = source_ptr.value.cast::<ast::MethodCallExpr>() {
+                        return Some(
+                            CloneOnCopy { method_call_expr: InFile::new(source_ptr.file_id, ptr) }
+                                .into(),
+                        );
+                    }
+                }
+            }
+            BodyValidationDiagnostic::NeedlessCollect { collect_expr, iter_expr } => {
+                let collect_expr = source_map.expr_syntax(collect_expr).ok()?;
+                let iter_expr = source_map.expr_syntax(iter_expr).ok()?;
+                if collect_expr.file_id == iter_expr.file_id {
+                    if let (Some(collect_ptr), Some(iter_ptr)) = (
+                        collect_expr.value.cast::<ast::MethodCallExpr>(),
+                        iter_expr.value.cast::<ast::MethodCallExpr>(),
+                    ) {
+                        return Some(
+                            NeedlessCollect {
+                                collect_expr: InFile::new(collect_expr.file_id, collect_ptr),
+                                iter_expr: InFile::new(iter_expr.file_id, iter_ptr),
+                            }
+                            .into(),
+                        );
+                    }
+                }
+            }
+            BodyValidationDiagnostic::NonSendHeldAcrossAwait { await_expr, binding_pat, ty } => {
+                let await_expr = source_map.expr_syntax(await_expr).ok()?;
+                let binding = source_map.pat_syntax(binding_pat).ok()?;
+                if await_expr.file_id == binding.file_id {
+                    if let (Some(await_ptr), Some(binding_ptr)) = (
+                        await_expr.value.cast::<ast::AwaitExpr>(),
+                        binding.value.cast::<ast::IdentPat>(),
+                    ) {
+                        return Some(
+                            NonSendHeldAcrossAwait {
+                                await_expr: InFile::new(await_expr.file_id, await_ptr),
+                                binding: InFile::new(binding.file_id, binding_ptr),
+                                ty,
+                            }
+                            .into(),
+                        );
+                    }
+                }
+            }
             BodyValidationDiagnostic::MissingMatchArms { match_expr, uncovered_patterns } => {
                 match source_map.expr_syntax(match_expr) {
                     Ok(source_ptr) => {
@@ -610,6 +695,17 @@ impl AnyDiagnostic {
                 let expr = expr_syntax(*expr)?;
                 TypedHole { expr, expected: Type::new(db, def, expected.clone()) }.into()
             }
+            InferenceDiagnostic::ArithmeticOverflow { expr, op, lhs, rhs, ty } => {
+                let expr = expr_syntax(*expr)?;
+                ArithmeticOverflow {
+                    expr,
+                    op: op.to_string(),
+                    lhs: *lhs,
+                    rhs: *rhs,
+                    ty: Type::new(db, def, ty.clone()),
+                }
+                .into()
+            }
             &InferenceDiagnostic::MismatchedTupleStructPatArgCount { pat, expected, found } => {
                 let expr_or_pat = match pat {
                     ExprOrPatId::ExprId(expr) => expr_syntax(expr)?.map(AstPtr::wrap_left),
@@ -436,7 +436,59 @@ impl HirDisplay for TypeParam {
 
 impl HirDisplay for LifetimeParam {
     fn hir_fmt(&self, f: &mut HirFormatter<'_>) -> Result<(), HirDisplayError> {
-        write!(f, "{}", self.name(f.db).display(f.db.upcast()))
+        let name = self.name(f.db);
+        write!(f, "{}", name.display(f.db.upcast()))?;
+
+        if f.omit_verbose_types() {
+            return Ok(());
+        }
+
+        let params = f.db.generic_params(self.id.parent);
+        let bounds = params.where_predicates.iter().filter_map(|pred| match pred {
+            WherePredicate::Lifetime { target, bound } if target.name == name => Some(bound),
+            _ => None,
+        });
+        for (idx, bound) in bounds.enumerate() {
+            f.write_str(if idx == 0 { ": " } else { " + " })?;
+            write!(f, "{}", bound.name.display(f.db.upcast()))?;
+        }
+
+        // The constraints the lifetime has to outlive are shown as a where clause.
+        let outlived_by = params.where_predicates.iter().filter(|pred| match pred {
+            WherePredicate::Lifetime { bound, .. } => bound.name == name,
+            WherePredicate::TypeBound { target, bound } => {
+                let is_named = match target {
+                    WherePredicateTypeTarget::TypeRef(_) => true,
+                    WherePredicateTypeTarget::TypeOrConstParam(id) => {
+                        params.type_or_consts[*id].name().is_some()
+                    }
+                };
+                is_named && matches!(&**bound, TypeBound::Lifetime(it) if it.name == name)
+            }
+            WherePredicate::ForLifetime { .. } => false,
+        });
+        for (idx, pred) in outlived_by.enumerate() {
+            f.write_str(if idx == 0 { "\nwhere\n    " } else { ",\n    " })?;
+            match pred {
+                WherePredicate::Lifetime { target, .. } => {
+                    write!(f, "{}", target.name.display(f.db.upcast()))?
+                }
+                WherePredicate::TypeBound {
+                    target: WherePredicateTypeTarget::TypeRef(ty), ..
+                } => ty.hir_fmt(f)?,
+                WherePredicate::TypeBound {
+                    target: WherePredicateTypeTarget::TypeOrConstParam(id),
+                    ..
+                } => {
+                    if let Some(name) = params.type_or_consts[*id].name() {
+                        write!(f, "{}", name.display(f.db.upcast()))?
+                    }
+                }
+                WherePredicate::ForLifetime { .. } => (),
+            }
+            write!(f, ": {}", name.display(f.db.upcast()))?;
+        }
+        Ok(())
     }
 }
 
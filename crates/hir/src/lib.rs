@@ -52,7 +52,7 @@ use hir_def::{
     path::ImportAlias,
     per_ns::PerNs,
     resolver::{HasResolver, Resolver},
-    src::HasSource as _,
+    src::{HasChildSource as _, HasSource as _},
     AssocItemId, AssocItemLoc, AttrDefId, ConstId, ConstParamId, CrateRootModuleId, DefWithBodyId,
     EnumId, EnumVariantId, ExternCrateId, FunctionId, GenericDefId, GenericParamId, HasModule,
     ImplId, InTypeConstId, ItemContainerId, LifetimeParamId, LocalFieldId, Lookup, MacroExpander,
@@ -513,6 +513,27 @@ impl Module {
         res
     }
 
+    /// Returns the use trees through which `item` is re-exported from this module as `name`,
+    /// starting with the one in this module and ending with the one importing the definition.
+    pub fn reexport_chain(
+        self,
+        db: &dyn HirDatabase,
+        name: &Name,
+        item: ItemInNs,
+    ) -> Vec<InFile<ast::UseTree>> {
+        let def_map = self.id.def_map(db.upcast());
+        def_map[self.id.local_id]
+            .scope
+            .reexport_chain(db.upcast(), name, item.into())
+            .into_iter()
+            .filter_map(|import| {
+                let source = import.import.child_source(db.upcast());
+                let use_tree = source.value.get(import.idx)?.clone();
+                Some(source.with_value(use_tree))
+            })
+            .collect()
+    }
+
     /// Returns a `ModuleScope`: a set of items, visible in this module.
     pub fn scope(
         self,
@@ -541,6 +562,24 @@ impl Module {
             .collect()
     }
 
+    /// Returns the module's `#[cfg]`-disabled items, without computing any other diagnostics.
+    pub fn inactive_code(self, db: &dyn HirDatabase) -> Vec<InactiveCode> {
+        let def_map = self.id.def_map(db.upcast());
+        def_map
+            .diagnostics()
+            .iter()
+            .filter(|diag| diag.in_module == self.id.local_id)
+            .filter_map(|diag| match &diag.kind {
+                DefDiagnosticKind::UnconfiguredCode { ast, cfg, opts } => Some(InactiveCode {
+                    node: ast.with_value(ast.to_ptr(db.upcast())),
+                    cfg: cfg.clone(),
+                    opts: opts.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Fills `acc` with the module's diagnostics.
     pub fn diagnostics(
         self,
@@ -1607,6 +1646,21 @@ impl DefWithBody {
         }
     }
 
+    /// Returns the `#[cfg]`-disabled code in this def's body, without running type inference.
+    pub fn inactive_code(self, db: &dyn HirDatabase) -> Vec<InactiveCode> {
+        let (_, source_map) = db.body_with_source_map(self.into());
+        source_map
+            .diagnostics()
+            .iter()
+            .filter_map(|diag| match diag {
+                BodyDiagnostic::InactiveCode { node, cfg, opts } => {
+                    Some(InactiveCode { node: *node, cfg: cfg.clone(), opts: opts.clone() })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// A textual representation of the HIR of this def's body for debugging purposes.
     pub fn debug_hir(self, db: &dyn HirDatabase) -> String {
         let body = db.body(self.id());
@@ -1810,6 +1864,42 @@ impl DefWithBody {
             acc.extend(AnyDiagnostic::body_validation_diagnostic(db, diagnostic, &source_map));
         }
 
+        let eval_error = match self {
+            DefWithBody::Const(it) => {
+                db.const_eval(it.id.into(), Substitution::empty(Interner), None).err()
+            }
+            DefWithBody::Static(it) => db.const_eval_static(it.id).err(),
+            DefWithBody::Variant(it) => db.const_eval_discriminant(it.id).err(),
+            DefWithBody::Function(_) | DefWithBody::InTypeConst(_) => None,
+        };
+        if let Some(ConstEvalError::MirEvalError(err)) = &eval_error {
+            let def = DefWithBodyId::from(self);
+            // Point at the overflowing operation, or at the call in this body that led to it.
+            let (mut err, mut call_span) = (err, None);
+            while let mir::MirEvalError::InFunction(inner, stack) = err {
+                call_span = call_span
+                    .or_else(|| stack.iter().find(|(_, _, it)| *it == def).map(|&(_, it, _)| it));
+                err = inner;
+            }
+            if let mir::MirEvalError::ArithmeticOverflow { op, lhs, rhs, ty, span, owner } = err {
+                let span = if *owner == def { Some(*span) } else { call_span };
+                if let Some(mir::MirSpan::ExprId(expr)) = span {
+                    if let Ok(expr) = source_map.expr_syntax(expr) {
+                        acc.push(
+                            ArithmeticOverflow {
+                                expr,
+                                op: op.to_string(),
+                                lhs: *lhs,
+                                rhs: *rhs,
+                                ty: Type::new(db, def, ty.clone()),
+                            }
+                            .into(),
+                        );
+                    }
+                }
+            }
+        }
+
         let def: ModuleDef = match self {
             DefWithBody::Function(it) => it.into(),
             DefWithBody::Static(it) => it.into(),
@@ -2412,6 +2502,11 @@ impl Trait {
         db.trait_data(self.id).items.iter().map(|(_name, it)| (*it).into()).collect()
     }
 
+    /// Returns the traits this trait directly requires to be implemented.
+    pub fn direct_supertraits(self, db: &dyn HirDatabase) -> Vec<Trait> {
+        hir_ty::direct_super_traits(db.upcast(), self.id).into_iter().map(Trait::from).collect()
+    }
+
     pub fn items_with_supertraits(self, db: &dyn HirDatabase) -> Vec<AssocItem> {
         let traits = all_super_traits(db.upcast(), self.into());
         traits.iter().flat_map(|tr| Trait::from(*tr).items(db)).collect()
@@ -3658,6 +3753,44 @@ impl Impl {
         all
     }
 
+    /// Returns the blanket impls that apply to `ty`, from its crate as well as from the crates
+    /// depending on or depended on by it.
+    ///
+    /// Only impls of traits without generic parameters are considered, as the trait arguments
+    /// for other traits can't be inferred from `ty` alone. The sysroot crates are skipped: their
+    /// blanket impls, like `impl<T: 'static> Any for T`, apply to almost every type, and checking
+    /// all of them is expensive.
+    pub fn blanket_impls_for_type(db: &dyn HirDatabase, ty: Type) -> Vec<Impl> {
+        let direct_traits: FxHashSet<_> =
+            Impl::all_for_type(db, ty.clone()).into_iter().filter_map(|it| it.trait_(db)).collect();
+        let crate_graph = db.crate_graph();
+        let mut crates: Vec<_> = crate_graph
+            .transitive_deps(ty.env.krate)
+            .chain(crate_graph.transitive_rev_deps(ty.env.krate))
+            .filter(|&id| !crate_graph[id].origin.is_lang())
+            .collect();
+        crates.sort();
+        crates.dedup();
+
+        let mut all = Vec::new();
+        for id in crates {
+            for (trait_, impl_) in db.trait_impls_in_crate(id).blanket_impls() {
+                let (trait_, impl_) = (Trait { id: trait_ }, Impl { id: impl_ });
+                // Coherence rules out a blanket impl for types with an impl of their own.
+                if direct_traits.contains(&trait_)
+                    || trait_.type_or_const_param_count(db, false) != 0
+                    || !impl_.is_blanket(db)
+                {
+                    continue;
+                }
+                if ty.impls_trait(db, trait_, &[]) {
+                    all.push(impl_);
+                }
+            }
+        }
+        all
+    }
+
     pub fn all_for_trait(db: &dyn HirDatabase, trait_: Trait) -> Vec<Impl> {
         let module = trait_.module(db);
         let krate = module.krate();
@@ -3698,6 +3831,11 @@ impl Impl {
         db.impl_data(self.id).items.iter().map(|&it| it.into()).collect()
     }
 
+    /// Whether this is an impl for all types satisfying some bounds, like `impl<T: Bound> Trait for T`.
+    pub fn is_blanket(self, db: &dyn HirDatabase) -> bool {
+        self.trait_(db).is_some() && self.self_ty(db).as_type_param(db).is_some()
+    }
+
     pub fn is_negative(self, db: &dyn HirDatabase) -> bool {
         db.impl_data(self.id).is_negative
     }
@@ -4242,6 +4380,10 @@ impl Type {
         matches!(self.ty.kind(Interner), TyKind::FnDef(..) | TyKind::Function { .. })
     }
 
+    pub fn is_unsafe_fn_ptr(&self) -> bool {
+        matches!(self.ty.kind(Interner), TyKind::Function(fn_ptr) if fn_ptr.sig.safety == hir_ty::Safety::Unsafe)
+    }
+
     pub fn is_array(&self) -> bool {
         matches!(self.ty.kind(Interner), TyKind::Array(..))
     }
@@ -4924,6 +5066,37 @@ impl Callable {
     pub fn return_type(&self) -> Type {
         self.ty.derived(self.sig.ret().clone())
     }
+    /// Returns the arguments the callee's own type and const parameters were inferred to, in
+    /// declaration order. Const arguments are rendered as source text. Lifetimes and the
+    /// arguments of a parent impl or trait are not included.
+    ///
+    /// Returns `None` if the callee is not a function, or if it has `impl Trait` parameters, as
+    /// those prevent specifying the arguments explicitly.
+    pub fn type_and_const_arguments(
+        &self,
+        db: &dyn HirDatabase,
+    ) -> Option<Vec<Either<Type, String>>> {
+        let Callee::Def(CallableDefId::FunctionId(func)) = self.callee else { return None };
+        let TyKind::FnDef(_, substs) = self.ty.ty.kind(Interner) else { return None };
+        // The function's own parameters come before those of its parent.
+        db.generic_params(func.into())
+            .type_or_consts
+            .iter()
+            .enumerate()
+            .map(|(idx, (_, param))| {
+                if let TypeOrConstParamData::TypeParamData(it) = param {
+                    if it.provenance != TypeParamProvenance::TypeParamList {
+                        return None;
+                    }
+                }
+                let arg = substs.as_slice(Interner).get(idx)?;
+                match arg.ty(Interner) {
+                    Some(ty) => Some(Either::Left(self.ty.derived(ty.clone()))),
+                    None => Some(Either::Right(arg.constant(Interner)?.display(db).to_string())),
+                }
+            })
+            .collect()
+    }
     pub fn sig(&self) -> &CallableSig {
         &self.sig
     }
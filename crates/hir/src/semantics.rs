@@ -1146,6 +1146,12 @@ impl<'db> SemanticsImpl<'db> {
             .map(|(ty, coerced)| TypeInfo { original: ty, adjusted: coerced })
     }
 
+    /// Whether the expression is an integer or float literal whose type wasn't constrained by
+    /// anything and fell back to `i32` or `f64`.
+    pub fn is_defaulted_literal(&self, expr: &ast::Expr) -> bool {
+        self.analyze(expr.syntax()).map_or(false, |it| it.is_defaulted_literal(self.db, expr))
+    }
+
     pub fn type_of_pat(&self, pat: &ast::Pat) -> Option<TypeInfo> {
         self.analyze(pat.syntax())?
             .type_of_pat(self.db, pat)
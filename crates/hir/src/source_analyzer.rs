@@ -190,6 +190,13 @@ impl SourceAnalyzer {
         Some((mk_ty(ty), coerced.map(mk_ty)))
     }
 
+    pub(crate) fn is_defaulted_literal(&self, db: &dyn HirDatabase, expr: &ast::Expr) -> bool {
+        let (Some(expr_id), Some(infer)) = (self.expr_id(db, expr), self.infer.as_ref()) else {
+            return false;
+        };
+        infer.defaulted_literals.contains(&expr_id)
+    }
+
     pub(crate) fn type_of_pat(
         &self,
         db: &dyn HirDatabase,
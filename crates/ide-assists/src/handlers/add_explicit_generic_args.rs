@@ -0,0 +1,200 @@
+use either::Either;
+use hir::HirDisplay;
+use syntax::ast::{self, AstNode};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: add_explicit_generic_args
+//
+// Adds a turbofish with the inferred type and const arguments to a call of a generic function
+// or method.
+//
+// ```
+// fn make<T>() -> T { loop {} }
+// fn main() {
+//     let x: u32 = make$0();
+// }
+// ```
+// ->
+// ```
+// fn make<T>() -> T { loop {} }
+// fn main() {
+//     let x: u32 = make::<u32>();
+// }
+// ```
+pub(crate) fn add_explicit_generic_args(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let name_ref = ctx.find_node_at_offset::<ast::NameRef>()?;
+    let parent = name_ref.syntax().parent()?;
+    let (callable, has_generic_args) = match ast::MethodCallExpr::cast(parent.clone()) {
+        Some(method_call) => (
+            ctx.sema.resolve_method_call_as_callable(&method_call)?,
+            method_call.generic_arg_list().is_some(),
+        ),
+        None => {
+            let segment = ast::PathSegment::cast(parent)?;
+            let path_expr =
+                segment.parent_path().syntax().parent().and_then(ast::PathExpr::cast)?;
+            let call = path_expr.syntax().parent().and_then(ast::CallExpr::cast)?;
+            let callee = call.expr()?;
+            if callee.syntax() != path_expr.syntax() {
+                return None;
+            }
+            (ctx.sema.resolve_expr_as_callable(&callee)?, segment.generic_arg_list().is_some())
+        }
+    };
+    if has_generic_args {
+        return None;
+    }
+
+    let args = callable.type_and_const_arguments(ctx.db())?;
+    if args.is_empty() {
+        return None;
+    }
+    let module = ctx.sema.scope(name_ref.syntax())?.module();
+    let args = args
+        .into_iter()
+        .map(|arg| match arg {
+            Either::Left(ty) if ty.contains_unknown() => None,
+            // Closures and opaque types can't be named, so rendering them fails.
+            Either::Left(ty) => ty.display_source_code(ctx.db(), module.into(), false).ok(),
+            Either::Right(konst) if konst.starts_with('-') => Some(format!("{{ {konst} }}")),
+            Either::Right(konst) => Some(konst),
+        })
+        .collect::<Option<Vec<_>>>();
+    let Some(args) = args else {
+        cov_mark::hit!(add_explicit_generic_args_unnameable);
+        return None;
+    };
+
+    let turbofish = format!("::<{}>", args.join(", "));
+    let range = name_ref.syntax().text_range();
+    acc.add(
+        AssistId("add_explicit_generic_args", AssistKind::RefactorRewrite),
+        format!("Add explicit generic arguments `{turbofish}`"),
+        range,
+        |builder| builder.insert(range.end(), turbofish),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn function_call() {
+        check_assist(
+            add_explicit_generic_args,
+            r#"
+fn pick<'a, T, const N: usize>(items: &'a [T; N]) -> &'a T { &items[0] }
+fn f() {
+    let x = pick$0(&[1u8, 2, 3]);
+}
+"#,
+            r#"
+fn pick<'a, T, const N: usize>(items: &'a [T; N]) -> &'a T { &items[0] }
+fn f() {
+    let x = pick::<u8, 3>(&[1u8, 2, 3]);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn method_call_skips_impl_args() {
+        check_assist(
+            add_explicit_generic_args,
+            r#"
+struct Wrapper<T>(T);
+impl<T> Wrapper<T> {
+    fn convert<U>(&self) -> Wrapper<U> { loop {} }
+}
+fn f(w: Wrapper<i32>) -> Wrapper<Option<bool>> {
+    w.conv$0ert()
+}
+enum Option<T> { None, Some(T) }
+"#,
+            r#"
+struct Wrapper<T>(T);
+impl<T> Wrapper<T> {
+    fn convert<U>(&self) -> Wrapper<U> { loop {} }
+}
+fn f(w: Wrapper<i32>) -> Wrapper<Option<bool>> {
+    w.convert::<Option<bool>>()
+}
+enum Option<T> { None, Some(T) }
+"#,
+        );
+    }
+
+    #[test]
+    fn qualified_path_call() {
+        check_assist(
+            add_explicit_generic_args,
+            r#"
+mod m {
+    pub fn make<T>() -> T { loop {} }
+}
+fn f() -> u64 {
+    m::make$0()
+}
+"#,
+            r#"
+mod m {
+    pub fn make<T>() -> T { loop {} }
+}
+fn f() -> u64 {
+    m::make::<u64>()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_unnameable_types() {
+        cov_mark::check!(add_explicit_generic_args_unnameable);
+        check_assist_not_applicable(
+            add_explicit_generic_args,
+            r#"
+fn id<T>(t: T) -> T { t }
+fn f() {
+    id$0(|| ());
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable() {
+        check_assist_not_applicable(
+            add_explicit_generic_args,
+            r#"
+fn id<T>(t: T) -> T { t }
+fn f() {
+    id$0::<u8>(0);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            add_explicit_generic_args,
+            r#"
+fn plain(x: u8) -> u8 { x }
+fn f() {
+    plain$0(0);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            add_explicit_generic_args,
+            r#"
+trait Tr {}
+impl Tr for u8 {}
+fn g<T>(t: T, _: impl Tr) -> T { t }
+fn f() {
+    g$0(0u16, 0u8);
+}
+"#,
+        );
+    }
+}
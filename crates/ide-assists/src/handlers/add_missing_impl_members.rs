@@ -915,6 +915,60 @@ impl Foo for S {
         )
     }
 
+    #[test]
+    fn test_assoc_type_defaults_are_default_members() {
+        check_assist(
+            add_missing_impl_members,
+            r#"
+trait Foo {
+    type Required;
+    type Defaulted = u32;
+
+    fn foo(&self, value: Self::Required);
+}
+struct S;
+impl Foo for S { $0 }"#,
+            r#"
+trait Foo {
+    type Required;
+    type Defaulted = u32;
+
+    fn foo(&self, value: Self::Required);
+}
+struct S;
+impl Foo for S {
+    $0type Required;
+
+    fn foo(&self, value: Self::Required) {
+        todo!()
+    }
+}"#,
+        );
+        check_assist(
+            add_missing_default_members,
+            r#"
+trait Foo {
+    type Required;
+    type Defaulted = u32;
+
+    fn foo(&self, value: Self::Required);
+}
+struct S;
+impl Foo for S { $0 }"#,
+            r#"
+trait Foo {
+    type Required;
+    type Defaulted = u32;
+
+    fn foo(&self, value: Self::Required);
+}
+struct S;
+impl Foo for S {
+    $0type Defaulted = u32;
+}"#,
+        );
+    }
+
     #[test]
     fn test_generic_single_default_parameter() {
         check_assist(
@@ -347,7 +347,7 @@ fn is_variant_missing(existing_pats: &[Pat], var: &Pat) -> bool {
 }
 
 // Fixme: this is still somewhat limited, use hir_ty::diagnostics::match_check?
-fn does_pat_match_variant(pat: &Pat, var: &Pat) -> bool {
+pub(crate) fn does_pat_match_variant(pat: &Pat, var: &Pat) -> bool {
     match (pat, var) {
         (Pat::WildcardPat(_), _) => true,
         (Pat::SlicePat(spat), Pat::SlicePat(svar)) => {
@@ -362,20 +362,20 @@ fn does_pat_match_variant(pat: &Pat, var: &Pat) -> bool {
 }
 
 #[derive(Eq, PartialEq, Clone, Copy)]
-enum ExtendedEnum {
+pub(crate) enum ExtendedEnum {
     Bool,
     Enum(hir::Enum),
 }
 
 #[derive(Eq, PartialEq, Clone, Copy, Debug)]
-enum ExtendedVariant {
+pub(crate) enum ExtendedVariant {
     True,
     False,
     Variant(hir::Variant),
 }
 
 impl ExtendedVariant {
-    fn should_be_hidden(self, db: &RootDatabase, krate: Crate) -> bool {
+    pub(crate) fn should_be_hidden(self, db: &RootDatabase, krate: Crate) -> bool {
         match self {
             ExtendedVariant::Variant(var) => {
                 var.attrs(db).has_doc_hidden() && var.module(db).krate() != krate
@@ -399,7 +399,7 @@ impl ExtendedEnum {
         }
     }
 
-    fn variants(self, db: &RootDatabase) -> Vec<ExtendedVariant> {
+    pub(crate) fn variants(self, db: &RootDatabase) -> Vec<ExtendedVariant> {
         match self {
             ExtendedEnum::Enum(e) => {
                 e.variants(db).into_iter().map(ExtendedVariant::Variant).collect::<Vec<_>>()
@@ -411,7 +411,10 @@ impl ExtendedEnum {
     }
 }
 
-fn resolve_enum_def(sema: &Semantics<'_, RootDatabase>, expr: &ast::Expr) -> Option<ExtendedEnum> {
+pub(crate) fn resolve_enum_def(
+    sema: &Semantics<'_, RootDatabase>,
+    expr: &ast::Expr,
+) -> Option<ExtendedEnum> {
     sema.type_of_expr(expr)?.adjusted().autoderef(sema.db).find_map(|ty| match ty.as_adt() {
         Some(Adt::Enum(e)) => Some(ExtendedEnum::Enum(e)),
         _ => ty.is_bool().then_some(ExtendedEnum::Bool),
@@ -453,7 +456,7 @@ fn resolve_array_of_enum_def(
     })
 }
 
-fn build_pat(
+pub(crate) fn build_pat(
     db: &RootDatabase,
     module: hir::Module,
     var: ExtendedVariant,
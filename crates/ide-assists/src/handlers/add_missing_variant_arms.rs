@@ -0,0 +1,244 @@
+use std::iter;
+
+use either::Either;
+use itertools::Itertools;
+use syntax::ast::{self, edit::IndentLevel, AstNode};
+
+use crate::{
+    handlers::add_missing_match_arms::{build_pat, does_pat_match_variant, resolve_enum_def},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: add_missing_variant_arms
+//
+// Adds arms for the variants a `match` only handles through its `_` fallthrough, keeping the
+// fallthrough and the existing arms untouched.
+//
+// ```
+// enum Action { Move { distance: u32 }, Jump(u32), Stop }
+//
+// fn handle(action: Action) {
+//     $0match action {
+//         Action::Stop => {}
+//         _ => {}
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Action { Move { distance: u32 }, Jump(u32), Stop }
+//
+// fn handle(action: Action) {
+//     match action {
+//         Action::Stop => {}
+//         Action::Move { distance } => todo!(),
+//         Action::Jump(_) => todo!(),
+//         _ => {}
+//     }
+// }
+// ```
+pub(crate) fn add_missing_variant_arms(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let match_arm_list = match_expr.match_arm_list()?;
+    let catch_all_arm = match_arm_list
+        .arms()
+        .find(|arm| arm.guard().is_none() && matches!(arm.pat(), Some(ast::Pat::WildcardPat(_))))?;
+
+    let selection = ctx.selection_trimmed();
+    let on_catch_all = catch_all_arm.pat()?.syntax().text_range().contains_range(selection);
+    if !on_catch_all && match_arm_list.syntax().text_range().contains_range(selection) {
+        return None;
+    }
+
+    let expr = match_expr.expr()?;
+    let module = ctx.sema.scope(expr.syntax())?.module();
+    let enum_def = resolve_enum_def(&ctx.sema, &expr)?;
+
+    let existing_pats = match_arm_list
+        .arms()
+        .filter_map(|arm| arm.pat())
+        .flat_map(|pat| match pat {
+            ast::Pat::OrPat(or_pat) => Either::Left(or_pat.pats()),
+            _ => Either::Right(iter::once(pat)),
+        })
+        .filter(|pat| !matches!(pat, ast::Pat::WildcardPat(_)))
+        .collect::<Vec<_>>();
+
+    // Hidden variants stay covered by the fallthrough.
+    let missing_pats = enum_def
+        .variants(ctx.db())
+        .into_iter()
+        .filter(|variant| !variant.should_be_hidden(ctx.db(), module.krate()))
+        .filter_map(|variant| {
+            build_pat(
+                ctx.db(),
+                module,
+                variant,
+                ctx.config.prefer_no_std,
+                ctx.config.prefer_prelude,
+            )
+        })
+        .filter(|pat| !existing_pats.iter().any(|existing| does_pat_match_variant(existing, pat)))
+        .collect::<Vec<_>>();
+    if missing_pats.is_empty() {
+        return None;
+    }
+
+    acc.add(
+        AssistId("add_missing_variant_arms", AssistKind::QuickFix),
+        "Add missing variant arms",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(catch_all_arm.syntax());
+            let arms = missing_pats
+                .iter()
+                .format_with("", |pat, f| f(&format_args!("{pat} => todo!(),\n{indent}")));
+            builder.insert(catch_all_arm.syntax().text_range().start(), arms.to_string());
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::add_missing_variant_arms;
+
+    #[test]
+    fn inserts_before_fallthrough_in_declaration_order() {
+        check_assist(
+            add_missing_variant_arms,
+            r#"
+enum E { A, B(i32, bool), C { x: i32 }, D }
+fn f(e: E) {
+    match e$0 {
+        E::D | E::A => (),
+        _ => {
+            return;
+        }
+        E::B(..) => (),
+    }
+}
+"#,
+            r#"
+enum E { A, B(i32, bool), C { x: i32 }, D }
+fn f(e: E) {
+    match e {
+        E::D | E::A => (),
+        E::C { x } => todo!(),
+        _ => {
+            return;
+        }
+        E::B(..) => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn on_fallthrough_pattern() {
+        check_assist(
+            add_missing_variant_arms,
+            r#"
+enum E { A, B }
+fn f(e: &E) {
+    match e {
+        E::A => (),
+        _$0 => (),
+    }
+}
+"#,
+            r#"
+enum E { A, B }
+fn f(e: &E) {
+    match e {
+        E::A => (),
+        E::B => todo!(),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn skips_hidden_variants() {
+        check_assist(
+            add_missing_variant_arms,
+            r#"
+//- /main.rs crate:main deps:e
+fn f(e: e::E) {
+    $0match e {
+        _ => (),
+    }
+}
+//- /e.rs crate:e
+pub enum E { A, #[doc(hidden)] Hidden }
+"#,
+            r#"
+fn f(e: e::E) {
+    match e {
+        e::E::A => todo!(),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable() {
+        // no fallthrough
+        check_assist_not_applicable(
+            add_missing_variant_arms,
+            r#"
+enum E { A, B }
+fn f(e: E) {
+    $0match e {
+        E::A => (),
+    }
+}
+"#,
+        );
+        // guarded fallthrough
+        check_assist_not_applicable(
+            add_missing_variant_arms,
+            r#"
+enum E { A, B }
+fn f(e: E, c: bool) {
+    $0match e {
+        E::A => (),
+        _ if c => (),
+    }
+}
+"#,
+        );
+        // nothing missing
+        check_assist_not_applicable(
+            add_missing_variant_arms,
+            r#"
+enum E { A, B }
+fn f(e: E) {
+    $0match e {
+        E::A | E::B => (),
+        _ => (),
+    }
+}
+"#,
+        );
+        // cursor inside an arm
+        check_assist_not_applicable(
+            add_missing_variant_arms,
+            r#"
+enum E { A, B }
+fn f(e: E) {
+    match e {
+        E::A => ($0),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+}
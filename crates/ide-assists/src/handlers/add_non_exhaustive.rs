@@ -0,0 +1,343 @@
+use hir::HasAttrs;
+use ide_db::{
+    base_db::FileId, defs::Definition, syntax_helpers::node_ext::insert_outer_attr, FxHashSet,
+};
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasName},
+    Direction, SyntaxKind, TextSize,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: add_non_exhaustive
+//
+// Adds `#[non_exhaustive]` to an enum or struct. For enums, a wildcard arm is added to the
+// exhaustive matches in the other crates of the workspace, which would stop compiling otherwise.
+// Matches within the enum's own crate are not affected by the attribute and are left alone.
+//
+// ```
+// pub enum $0Color { Red, Green }
+// ```
+// ->
+// ```
+// #[non_exhaustive]
+// pub enum Color { Red, Green }
+// ```
+pub(crate) fn add_non_exhaustive(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let adt = ctx.find_node_at_offset::<ast::Adt>()?;
+    let name = adt.name()?;
+    let keyword = adt
+        .syntax()
+        .children_with_tokens()
+        .find(|it| matches!(it.kind(), SyntaxKind::ENUM_KW | SyntaxKind::STRUCT_KW))?;
+    // Only offer the assist on the item's header, as it has to search for matches.
+    if !keyword.text_range().cover(name.syntax().text_range()).contains_inclusive(ctx.offset()) {
+        return None;
+    }
+    let def = match &adt {
+        ast::Adt::Enum(it) => hir::Adt::Enum(ctx.sema.to_def(it)?),
+        ast::Adt::Struct(it) => hir::Adt::Struct(ctx.sema.to_def(it)?),
+        ast::Adt::Union(_) => return None,
+    };
+    let db = ctx.db();
+    if def.attrs(db).by_key("non_exhaustive").exists() {
+        return None;
+    }
+
+    let arms = match def {
+        hir::Adt::Enum(enum_) => wildcard_arm_insertions(ctx, enum_),
+        _ => Vec::new(),
+    };
+    let label = match arms.len() {
+        0 => format!("Add `#[non_exhaustive]` to `{name}`"),
+        1 => format!("Add `#[non_exhaustive]` to `{name}` and a wildcard arm to 1 match"),
+        n => format!("Add `#[non_exhaustive]` to `{name}` and a wildcard arm to {n} matches"),
+    };
+
+    let (offset, attr) = insert_outer_attr(adt.syntax(), "non_exhaustive")?;
+
+    acc.add(
+        AssistId("add_non_exhaustive", AssistKind::RefactorRewrite),
+        label,
+        name.syntax().text_range(),
+        |builder| {
+            builder.insert(offset, attr);
+            for (file_id, edits) in arms {
+                builder.edit_file(file_id);
+                for (offset, text) in edits {
+                    builder.insert(offset, text);
+                }
+            }
+        },
+    )
+}
+
+/// Finds the matches on `enum_` outside of its crate without a catch-all arm, and the text to
+/// insert to add one.
+fn wildcard_arm_insertions(
+    ctx: &AssistContext<'_>,
+    enum_: hir::Enum,
+) -> Vec<(FileId, Vec<(TextSize, String)>)> {
+    let db = ctx.db();
+    let krate = enum_.module(db).krate();
+    let defs = std::iter::once(Definition::Adt(hir::Adt::Enum(enum_)))
+        .chain(enum_.variants(db).into_iter().map(Definition::Variant));
+
+    let mut seen = FxHashSet::default();
+    let mut res = Vec::new();
+    for def in defs {
+        for (file_id, refs) in def.usages(&ctx.sema).all() {
+            // `#[non_exhaustive]` only applies to other crates.
+            match ctx.sema.file_to_module_def(file_id) {
+                Some(module) if module.krate() != krate => (),
+                _ => continue,
+            }
+            for reference in refs {
+                let Some(name_ref) = reference.name.as_name_ref() else { continue };
+                let Some(match_) = name_ref.syntax().ancestors().find_map(ast::MatchExpr::cast)
+                else {
+                    continue;
+                };
+                if !seen.insert((file_id, match_.syntax().text_range())) {
+                    continue;
+                }
+                if let Some(edits) = wildcard_arm(ctx, enum_, &match_) {
+                    res.push((file_id, edits));
+                }
+            }
+        }
+    }
+    res.sort_by_key(|(file_id, edits)| (*file_id, edits.first().map(|(offset, _)| *offset)));
+    res
+}
+
+fn wildcard_arm(
+    ctx: &AssistContext<'_>,
+    enum_: hir::Enum,
+    match_: &ast::MatchExpr,
+) -> Option<Vec<(TextSize, String)>> {
+    let scrutinee = ctx.sema.type_of_expr(&match_.expr()?)?.adjusted();
+    if scrutinee.strip_references().as_adt() != Some(hir::Adt::Enum(enum_)) {
+        return None;
+    }
+    let arm_list = match_.match_arm_list()?;
+    let is_catch_all = |arm: &ast::MatchArm| {
+        arm.guard().is_none()
+            && match arm.pat() {
+                Some(ast::Pat::WildcardPat(_)) => true,
+                Some(ast::Pat::IdentPat(pat)) => {
+                    pat.pat().is_none() && ctx.sema.to_def(&pat).is_some()
+                }
+                _ => false,
+            }
+    };
+    if arm_list.arms().any(|arm| is_catch_all(&arm)) {
+        return None;
+    }
+
+    let last_arm = arm_list.arms().last()?;
+    let arm_end = last_arm.syntax().text_range().end();
+    let needs_comma =
+        last_arm.comma_token().is_none() && !last_arm.expr().map_or(false, |it| it.is_block_like());
+    let comma = if needs_comma { "," } else { "" };
+    if !arm_list.syntax().text().contains_char('\n') {
+        return Some(vec![(arm_end, format!("{comma} _ => todo!(),"))]);
+    }
+    // Keep a comment trailing the last arm on its line.
+    let comment_end = last_arm
+        .syntax()
+        .siblings_with_tokens(Direction::Next)
+        .skip(1)
+        .take_while(|it| match it.kind() {
+            SyntaxKind::WHITESPACE => !it.to_string().contains('\n'),
+            SyntaxKind::COMMENT => true,
+            _ => false,
+        })
+        .filter(|it| it.kind() == SyntaxKind::COMMENT)
+        .last()
+        .map(|it| it.text_range().end());
+    let indent = IndentLevel::from_node(last_arm.syntax());
+    let arm = format!("\n{indent}_ => todo!(),");
+    Some(match comment_end {
+        Some(end) if needs_comma => vec![(arm_end, comma.to_owned()), (end, arm)],
+        Some(end) => vec![(end, arm)],
+        None => vec![(arm_end, format!("{comma}{arm}"))],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn struct_gets_attribute() {
+        check_assist(
+            add_non_exhaustive,
+            r#"
+/// Docs.
+#[derive(Clone)]
+pub struct $0Config { pub verbose: bool }
+"#,
+            r#"
+/// Docs.
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct Config { pub verbose: bool }
+"#,
+        );
+    }
+
+    #[test]
+    fn updates_exhaustive_matches_in_other_crates() {
+        check_assist(
+            add_non_exhaustive,
+            r#"
+//- /lib.rs crate:lib
+pub enum Kind$0 { A, B(u32) }
+
+fn in_crate(kind: Kind) -> bool {
+    match kind { Kind::A => true, Kind::B(_) => false }
+}
+//- /main.rs crate:main deps:lib
+mod other;
+use lib::Kind::{self, *};
+
+fn exhaustive(kind: &Kind) -> u32 {
+    match kind {
+        A => 0,
+        B(n) => *n
+    }
+}
+
+fn already_catch_all(kind: Kind) {
+    match kind {
+        Kind::A => (),
+        other => drop(other),
+    }
+}
+
+fn guarded(kind: Kind) {
+    match kind {
+        Kind::A => {}
+        _ if true => {}
+        Kind::B(_) => {} // the rest
+    }
+}
+//- /other.rs
+use lib::Kind;
+
+fn in_other_file(kind: Kind) -> bool {
+    match kind { Kind::A => true, Kind::B(..) => false }
+}
+"#,
+            r#"
+//- /lib.rs
+#[non_exhaustive]
+pub enum Kind { A, B(u32) }
+
+fn in_crate(kind: Kind) -> bool {
+    match kind { Kind::A => true, Kind::B(_) => false }
+}
+//- /main.rs
+mod other;
+use lib::Kind::{self, *};
+
+fn exhaustive(kind: &Kind) -> u32 {
+    match kind {
+        A => 0,
+        B(n) => *n,
+        _ => todo!(),
+    }
+}
+
+fn already_catch_all(kind: Kind) {
+    match kind {
+        Kind::A => (),
+        other => drop(other),
+    }
+}
+
+fn guarded(kind: Kind) {
+    match kind {
+        Kind::A => {}
+        _ if true => {}
+        Kind::B(_) => {} // the rest
+        _ => todo!(),
+    }
+}
+//- /other.rs
+use lib::Kind;
+
+fn in_other_file(kind: Kind) -> bool {
+    match kind { Kind::A => true, Kind::B(..) => false, _ => todo!(), }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn reports_updated_matches() {
+        check_assist_by_label(
+            add_non_exhaustive,
+            r#"
+//- /lib.rs crate:lib
+pub enum E$0 { A }
+//- /main.rs crate:main deps:lib
+fn f(e: lib::E) {
+    match e {
+        lib::E::A => {}
+    }
+}
+"#,
+            r#"
+//- /lib.rs
+#[non_exhaustive]
+pub enum E { A }
+//- /main.rs
+fn f(e: lib::E) {
+    match e {
+        lib::E::A => {}
+        _ => todo!(),
+    }
+}
+"#,
+            "Add `#[non_exhaustive]` to `E` and a wildcard arm to 1 match",
+        );
+    }
+
+    #[test]
+    fn ignores_matches_in_same_crate() {
+        check_assist_by_label(
+            add_non_exhaustive,
+            r#"
+enum E$0 { A }
+fn f(e: E) { match e { E::A => {} } }
+"#,
+            r#"
+#[non_exhaustive]
+enum E { A }
+fn f(e: E) { match e { E::A => {} } }
+"#,
+            "Add `#[non_exhaustive]` to `E`",
+        );
+    }
+
+    #[test]
+    fn not_applicable() {
+        check_assist_not_applicable(
+            add_non_exhaustive,
+            r#"
+#[non_exhaustive]
+enum E$0 { A }
+"#,
+        );
+        check_assist_not_applicable(
+            add_non_exhaustive,
+            r#"
+enum E { A$0 }
+"#,
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use hir::{ModuleDef, PathResolution};
+use ide_db::syntax_helpers::node_ext::insert_outer_attr;
+use syntax::{
+    ast::{self, edit::IndentLevel, make, AstNode, HasAttrs, HasName},
+    SyntaxKind, TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: add_tracing_instrumentation
+//
+// Instruments a function with a `tracing` span by adding the `#[tracing::instrument]` attribute.
+// Only applicable if the crate can refer to the `tracing` crate.
+//
+// ```
+// # //- /main.rs crate:main deps:tracing
+// fn comp$0ute(a: u32) -> u32 {
+//     a + 1
+// }
+// # //- /tracing.rs crate:tracing
+// ```
+// ->
+// ```
+// #[tracing::instrument]
+// fn compute(a: u32) -> u32 {
+//     a + 1
+// }
+// ```
+pub(crate) fn add_tracing_instrumentation(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let (fn_, _) = instrumentable_fn(ctx)?;
+
+    let already_instrumented = fn_.attrs().filter_map(|attr| attr.path()).any(|path| {
+        let path = path.syntax().text();
+        path == "instrument" || path == "tracing::instrument"
+    });
+    if already_instrumented {
+        cov_mark::hit!(add_tracing_instrumentation_already_instrumented);
+        return None;
+    }
+
+    let (offset, attr) = insert_outer_attr(fn_.syntax(), "tracing::instrument")?;
+    acc.add(
+        AssistId("add_tracing_instrumentation", AssistKind::Generate),
+        "Add `#[tracing::instrument]`",
+        fn_.syntax().text_range(),
+        |edit| edit.insert(offset, attr),
+    )
+}
+
+// Assist: add_tracing_span
+//
+// Runs the body of a function inside a manually entered `tracing` span named after the function.
+// Only applicable if the crate can refer to the `tracing` crate.
+//
+// ```
+// # //- /main.rs crate:main deps:tracing
+// fn comp$0ute(a: u32) -> u32 {
+//     a + 1
+// }
+// # //- /tracing.rs crate:tracing
+// ```
+// ->
+// ```
+// fn compute(a: u32) -> u32 {
+//     let _span = tracing::span!(tracing::Level::INFO, "compute").entered();
+//     a + 1
+// }
+// ```
+pub(crate) fn add_tracing_span(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let (fn_, stmt_list) = instrumentable_fn(ctx)?;
+    if fn_.async_token().is_some() {
+        // An entered span must not be held across `.await` points.
+        cov_mark::hit!(add_tracing_span_async_fn);
+        return None;
+    }
+    let name = fn_.name()?;
+    let l_curly = stmt_list.l_curly_token()?;
+    let r_curly = stmt_list.r_curly_token()?;
+
+    acc.add(
+        AssistId("add_tracing_span", AssistKind::Generate),
+        "Wrap body in a `tracing` span",
+        fn_.syntax().text_range(),
+        |edit| {
+            let indent = IndentLevel::from_node(fn_.syntax());
+            let inner_indent = indent + 1;
+            let guard = format!(
+                "\n{inner_indent}let _span = tracing::span!(tracing::Level::INFO, \"{name}\").entered();"
+            );
+
+            let is_empty = stmt_list.syntax().children_with_tokens().all(|it| {
+                matches!(it.kind(), SyntaxKind::L_CURLY | SyntaxKind::R_CURLY | SyntaxKind::WHITESPACE)
+            });
+            if is_empty {
+                edit.replace(
+                    TextRange::new(l_curly.text_range().end(), r_curly.text_range().start()),
+                    format!("{guard}\n{indent}"),
+                );
+                return;
+            }
+
+            // Put the existing body onto its own lines if it was written inline.
+            match l_curly.next_token() {
+                Some(ws) if ws.kind() == SyntaxKind::WHITESPACE => {
+                    if ws.text().contains('\n') {
+                        edit.insert(l_curly.text_range().end(), guard);
+                    } else {
+                        edit.replace(ws.text_range(), format!("{guard}\n{inner_indent}"));
+                    }
+                }
+                _ => edit.insert(l_curly.text_range().end(), format!("{guard}\n{inner_indent}")),
+            }
+            match r_curly.prev_token() {
+                Some(ws) if ws.kind() == SyntaxKind::WHITESPACE => {
+                    if !ws.text().contains('\n') {
+                        edit.replace(ws.text_range(), format!("\n{indent}"));
+                    }
+                }
+                _ => edit.insert(r_curly.text_range().start(), format!("\n{indent}")),
+            }
+        },
+    )
+}
+
+/// Finds the function whose signature the cursor is on, if its crate can refer to `tracing`.
+fn instrumentable_fn(ctx: &AssistContext<'_>) -> Option<(ast::Fn, ast::StmtList)> {
+    let fn_ = ctx.find_node_at_offset::<ast::Fn>()?;
+    let stmt_list = fn_.body()?.stmt_list()?;
+    if stmt_list.syntax().text_range().contains_inclusive(ctx.offset()) {
+        return None;
+    }
+
+    let scope = ctx.sema.scope(fn_.syntax())?;
+    let resolves_tracing = matches!(
+        scope.speculative_resolve(&make::ext::ident_path("tracing")),
+        Some(PathResolution::Def(ModuleDef::Module(it))) if it.is_crate_root()
+    );
+    if !resolves_tracing {
+        cov_mark::hit!(add_tracing_instrumentation_no_tracing);
+        return None;
+    }
+    Some((fn_, stmt_list))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn adds_instrument_attr_with_tracing_dependency() {
+        check_assist(
+            add_tracing_instrumentation,
+            r#"
+//- /main.rs crate:main deps:tracing
+/// Docs.
+#[inline]
+pub fn comp$0ute(a: u32) -> u32 {
+    a + 1
+}
+//- /tracing.rs crate:tracing
+"#,
+            r#"
+/// Docs.
+#[inline]
+#[tracing::instrument]
+pub fn compute(a: u32) -> u32 {
+    a + 1
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn adds_instrument_attr_to_method() {
+        check_assist(
+            add_tracing_instrumentation,
+            r#"
+//- /main.rs crate:main deps:tracing
+struct S;
+impl S {
+    async fn $0run(&self) {}
+}
+//- /tracing.rs crate:tracing
+"#,
+            r#"
+struct S;
+impl S {
+    #[tracing::instrument]
+    async fn run(&self) {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_already_instrumented() {
+        cov_mark::check_count!(add_tracing_instrumentation_already_instrumented, 2);
+        check_assist_not_applicable(
+            add_tracing_instrumentation,
+            r#"
+//- /main.rs crate:main deps:tracing
+#[tracing::instrument(skip(a))]
+fn comp$0ute(a: u32) {}
+//- /tracing.rs crate:tracing
+"#,
+        );
+        check_assist_not_applicable(
+            add_tracing_instrumentation,
+            r#"
+//- /main.rs crate:main deps:tracing
+use tracing::instrument;
+#[instrument]
+fn comp$0ute(a: u32) {}
+//- /tracing.rs crate:tracing
+"#,
+        );
+    }
+
+    #[test]
+    fn wraps_body_in_span_guard() {
+        check_assist(
+            add_tracing_span,
+            r#"
+//- /main.rs crate:main deps:tracing
+mod m {
+    fn comp$0ute(a: u32) -> u32 {
+        let b = a * 2;
+        b + 1
+    }
+}
+//- /tracing.rs crate:tracing
+"#,
+            r#"
+mod m {
+    fn compute(a: u32) -> u32 {
+        let _span = tracing::span!(tracing::Level::INFO, "compute").entered();
+        let b = a * 2;
+        b + 1
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn wraps_inline_and_empty_bodies() {
+        check_assist(
+            add_tracing_span,
+            r#"
+//- /main.rs crate:main deps:tracing
+fn comp$0ute(a: u32) -> u32 { a + 1 }
+//- /tracing.rs crate:tracing
+"#,
+            r#"
+fn compute(a: u32) -> u32 {
+    let _span = tracing::span!(tracing::Level::INFO, "compute").entered();
+    a + 1
+}
+"#,
+        );
+        check_assist(
+            add_tracing_span,
+            r#"
+//- /main.rs crate:main deps:tracing
+fn $0noop() {}
+//- /tracing.rs crate:tracing
+"#,
+            r#"
+fn noop() {
+    let _span = tracing::span!(tracing::Level::INFO, "noop").entered();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn span_not_applicable_to_async_fn() {
+        cov_mark::check!(add_tracing_span_async_fn);
+        check_assist_not_applicable(
+            add_tracing_span,
+            r#"
+//- /main.rs crate:main deps:tracing
+async fn comp$0ute(a: u32) -> u32 {
+    a + 1
+}
+//- /tracing.rs crate:tracing
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_tracing() {
+        check_assist_not_applicable(
+            add_tracing_instrumentation,
+            r#"
+//- /main.rs crate:main deps:tracing
+fn compute(a: u32) -> u32 {
+    a $0+ 1
+}
+//- /tracing.rs crate:tracing
+"#,
+        );
+        cov_mark::check_count!(add_tracing_instrumentation_no_tracing, 3);
+        check_assist_not_applicable(
+            add_tracing_instrumentation,
+            r#"
+fn comp$0ute(a: u32) -> u32 {
+    a + 1
+}
+"#,
+        );
+        check_assist_not_applicable(
+            add_tracing_span,
+            r#"
+fn comp$0ute(a: u32) -> u32 {
+    a + 1
+}
+"#,
+        );
+        check_assist_not_applicable(
+            add_tracing_instrumentation,
+            r#"
+//- /main.rs crate:main deps:tracing
+mod tracing {}
+fn comp$0ute(a: u32) -> u32 {
+    a + 1
+}
+//- /tracing.rs crate:tracing
+"#,
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use syntax::{
+    ast::{self, edit::AstNodeEdit, edit::IndentLevel, AstNode, HasAttrs},
+    Edition, SyntaxKind, T,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: collapse_nested_if_let
+//
+// Collapses an `if` whose body is nothing but another `if` into a single `if` with a let chain,
+// if neither of them has an `else`.
+//
+// ```
+// //- /main.rs crate:main edition:2024
+// fn main() {
+//     let (x, y) = (Some(1), Some(2));
+//     $0if let Some(a) = x {
+//         if let Some(b) = y {
+//             println!("{a} {b}");
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let (x, y) = (Some(1), Some(2));
+//     if let Some(a) = x && let Some(b) = y {
+//         println!("{a} {b}");
+//     }
+// }
+// ```
+pub(crate) fn collapse_nested_if_let(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let if_keyword = ctx.find_token_syntax_at_offset(T![if])?;
+    let outer = ast::IfExpr::cast(if_keyword.parent()?)?;
+    if !if_keyword.text_range().contains_range(ctx.selection_trimmed()) {
+        return None;
+    }
+    // Let chains are only stable from edition 2024 on.
+    if ctx.sema.scope(outer.syntax())?.krate().edition(ctx.db()) < Edition::Edition2024 {
+        return None;
+    }
+
+    // The `else` would also run when only the inner condition fails after collapsing.
+    if outer.else_branch().is_some() {
+        return None;
+    }
+
+    let outer_cond = outer.condition()?;
+    let outer_then = outer.then_branch()?;
+    let stmt_list = outer_then.stmt_list()?;
+    if stmt_list.statements().next().is_some()
+        || stmt_list.syntax().children_with_tokens().any(|it| it.kind() == SyntaxKind::COMMENT)
+    {
+        return None;
+    }
+    let ast::Expr::IfExpr(inner) = stmt_list.tail_expr()? else { return None };
+    if inner.else_branch().is_some() || inner.attrs().next().is_some() {
+        return None;
+    }
+    let inner_cond = inner.condition()?;
+    let inner_then = inner.then_branch()?;
+    // Plain boolean conditions can be joined with `&&` in any edition.
+    if !is_let_chain(&outer_cond) && !is_let_chain(&inner_cond) {
+        return None;
+    }
+
+    let target = outer_cond.syntax().text_range().cover(outer_then.syntax().text_range());
+    acc.add(
+        AssistId("collapse_nested_if_let", AssistKind::RefactorRewrite),
+        "Collapse nested `if let`",
+        target,
+        |builder| {
+            let body = inner_then.dedent(IndentLevel(1));
+            builder.replace(
+                target,
+                format!("{} && {} {body}", chain_operand(&outer_cond), chain_operand(&inner_cond)),
+            );
+        },
+    )
+}
+
+fn is_let_chain(cond: &ast::Expr) -> bool {
+    match cond {
+        ast::Expr::LetExpr(_) => true,
+        ast::Expr::BinExpr(bin)
+            if bin.op_kind() == Some(ast::BinaryOp::LogicOp(ast::LogicOp::And)) =>
+        {
+            bin.lhs().map_or(false, |it| is_let_chain(&it))
+                || bin.rhs().map_or(false, |it| is_let_chain(&it))
+        }
+        _ => false,
+    }
+}
+
+/// Renders `cond` as an operand of `&&`, parenthesizing conditions that bind weaker.
+fn chain_operand(cond: &ast::Expr) -> String {
+    match cond {
+        ast::Expr::BinExpr(bin)
+            if bin.op_kind() == Some(ast::BinaryOp::LogicOp(ast::LogicOp::Or))
+                || matches!(bin.op_kind(), Some(ast::BinaryOp::Assignment { .. })) =>
+        {
+            format!("({cond})")
+        }
+        ast::Expr::RangeExpr(_) | ast::Expr::ClosureExpr(_) => format!("({cond})"),
+        _ => cond.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn parenthesizes_or() {
+        check_assist(
+            collapse_nested_if_let,
+            r#"
+//- /main.rs crate:main edition:2024
+fn f(x: Option<i32>, p: bool, q: bool) {
+    $0if let Some(a) = x {
+        if p || q {}
+    }
+}
+"#,
+            r#"
+fn f(x: Option<i32>, p: bool, q: bool) {
+    if let Some(a) = x && (p || q) {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable() {
+        // Edition 2021.
+        check_assist_not_applicable(
+            collapse_nested_if_let,
+            r#"
+fn f(x: Option<i32>, y: Option<i32>) {
+    $0if let Some(a) = x {
+        if let Some(b) = y {}
+    }
+}
+"#,
+        );
+        // Intervening statement.
+        check_assist_not_applicable(
+            collapse_nested_if_let,
+            r#"
+//- /main.rs crate:main edition:2024
+fn f(x: Option<i32>, y: Option<i32>) {
+    $0if let Some(a) = x {
+        let c = a;
+        if let Some(b) = y {}
+    }
+}
+"#,
+        );
+        // Outer else.
+        check_assist_not_applicable(
+            collapse_nested_if_let,
+            r#"
+//- /main.rs crate:main edition:2024
+fn f(x: Option<i32>, y: Option<i32>) -> i32 {
+    if$0 let Some(a) = x {
+        if let Some(b) = y {
+            a + b
+        }
+    } else {
+        0
+    }
+}
+"#,
+        );
+        // Inner else.
+        check_assist_not_applicable(
+            collapse_nested_if_let,
+            r#"
+//- /main.rs crate:main edition:2024
+fn f(x: Option<i32>, y: Option<i32>) {
+    $0if let Some(a) = x {
+        if let Some(b) = y {} else {}
+    }
+}
+"#,
+        );
+        // No `let`.
+        check_assist_not_applicable(
+            collapse_nested_if_let,
+            r#"
+//- /main.rs crate:main edition:2024
+fn f(p: bool, q: bool) {
+    $0if p {
+        if q {}
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,517 @@
+use hir::{Adt, HasVisibility, HirDisplay};
+use ide_db::{
+    famous_defs::FamousDefs, helpers::mod_path_to_ast, syntax_helpers::node_ext::preorder_expr,
+    FxHashSet,
+};
+use itertools::Itertools;
+use stdx::{format_to, to_camel_case};
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasName},
+    SyntaxNode, WalkEvent,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_boxed_error_to_enum
+//
+// Replaces the `Box<dyn Error>` error type of a function with a new error enum that has a
+// variant for every error type propagated with `?` in the function's body.
+//
+// ```
+// # //- minicore: result, try, error, fmt
+// # //- /main.rs crate:main deps:alloc
+// use alloc::boxed::Box;
+// use core::error::Error;
+// # struct IoError;
+// # fn read() -> Result<u32, IoError> { loop {} }
+//
+// fn load() -> $0Result<u32, Box<dyn Error>> {
+//     Ok(read()?)
+// }
+// # //- /alloc.rs crate:alloc
+// # pub mod boxed { pub struct Box<T: ?Sized>(T); }
+// ```
+// ->
+// ```
+// use alloc::boxed::Box;
+// use core::error::Error;
+// # struct IoError;
+// # fn read() -> Result<u32, IoError> { loop {} }
+//
+// fn load() -> Result<u32, LoadError> {
+//     Ok(read()?)
+// }
+//
+// #[derive(Debug)]
+// enum LoadError {
+//     Io(IoError),
+// }
+//
+// impl core::fmt::Display for LoadError {
+//     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+//         match self {
+//             Self::Io(err) => write!(f, "{err}"),
+//         }
+//     }
+// }
+//
+// impl Error for LoadError {}
+//
+// impl From<IoError> for LoadError {
+//     fn from(err: IoError) -> Self {
+//         Self::Io(err)
+//     }
+// }
+// ```
+pub(crate) fn convert_boxed_error_to_enum(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let ret_type = ctx.find_node_at_offset::<ast::RetType>()?;
+    let func = ret_type.syntax().parent().and_then(ast::Fn::cast)?;
+    let body = func.body()?;
+    let ast::Type::PathType(result_ty) = ret_type.ty()? else { return None };
+    let error_arg = result_ty.path()?.segment()?.generic_arg_list()?.generic_args().nth(1)?;
+
+    let db = ctx.db();
+    let fn_def = ctx.sema.to_def(&func)?;
+    let module = fn_def.module(db);
+    let famous_defs = FamousDefs(&ctx.sema, module.krate());
+    let ret_ty = fn_def.ret_type(db);
+    let result = famous_defs.core_result_Result()?;
+    if ret_ty.as_adt()? != Adt::Enum(result) {
+        return None;
+    }
+    let boxed_error = ret_ty.type_arguments().nth(1)?;
+    if boxed_error.as_adt()? != Adt::Struct(famous_defs.alloc_boxed_Box()?) {
+        return None;
+    }
+    let error_trait = boxed_error.type_arguments().next()?.as_dyn_trait()?;
+    if error_trait != famous_defs.core_error_Error()? {
+        return None;
+    }
+
+    // Collect the error types of the `?`s that return from this function, skipping the ones in
+    // closures and async blocks.
+    let mut errors: Vec<(String, String)> = Vec::new();
+    let mut seen = FxHashSet::default();
+    // The enum is returned by the function and holds the error types, so it must be visible
+    // wherever the function is, but can't be more visible than any of the error types.
+    let mut visibility = fn_def.visibility(db);
+    preorder_expr(&ast::Expr::BlockExpr(body), &mut |ev| {
+        let expr = match ev {
+            WalkEvent::Enter(expr) => expr,
+            WalkEvent::Leave(_) => return false,
+        };
+        match &expr {
+            ast::Expr::ClosureExpr(_) => return true,
+            ast::Expr::BlockExpr(it) if it.async_token().is_some() => return true,
+            ast::Expr::TryExpr(it) => {
+                let Some(operand) = it.expr().and_then(|it| ctx.sema.type_of_expr(&it)) else {
+                    return false;
+                };
+                let operand = operand.original;
+                if operand.as_adt() != Some(Adt::Enum(result)) {
+                    return false;
+                }
+                let Some(err) = operand.type_arguments().nth(1) else { return false };
+                if err.contains_unknown() || err == boxed_error {
+                    return false;
+                }
+                if let Ok(rendered) = err.display_source_code(db, module.into(), true) {
+                    if seen.insert(rendered.clone()) {
+                        errors.push((variant_name(db, &err), rendered));
+                        visibility = narrower_visibility(db, visibility, &err);
+                    }
+                }
+            }
+            _ => (),
+        }
+        false
+    });
+    if errors.is_empty() {
+        return None;
+    }
+    let mut taken = FxHashSet::default();
+    for (name, _) in &mut errors {
+        let base = name.clone();
+        let mut idx = 1;
+        while !taken.insert(name.clone()) {
+            idx += 1;
+            *name = format!("{base}{idx}");
+        }
+    }
+
+    let fmt_path = mod_path_to_ast(&module.find_use_path(
+        db,
+        hir::ModuleDef::Module(famous_defs.core_fmt_Debug()?.module(db)),
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )?);
+    let error_path = mod_path_to_ast(&module.find_use_path(
+        db,
+        hir::ModuleDef::Trait(error_trait),
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )?);
+
+    let enum_name = format!("{}Error", to_camel_case(&func.name()?.text()));
+    let anchor = item_anchor(func.syntax())?;
+    let indent = IndentLevel::from_node(&anchor);
+    let visibility = render_visibility(db, module, visibility);
+
+    let mut scaffold = format!("#[derive(Debug)]\n{visibility}enum {enum_name} {{\n");
+    for (variant, ty) in &errors {
+        format_to!(scaffold, "    {variant}({ty}),\n");
+    }
+    scaffold.push_str("}\n\n");
+    format_to!(
+        scaffold,
+        "impl {fmt_path}::Display for {enum_name} {{
+    fn fmt(&self, f: &mut {fmt_path}::Formatter<'_>) -> {fmt_path}::Result {{
+        match self {{
+{}
+        }}
+    }}
+}}
+
+impl {error_path} for {enum_name} {{}}",
+        errors
+            .iter()
+            .map(|(variant, _)| format!(
+                "            Self::{variant}(err) => write!(f, \"{{err}}\"),"
+            ))
+            .join("\n"),
+    );
+    for (variant, ty) in &errors {
+        format_to!(
+            scaffold,
+            "\n\nimpl From<{ty}> for {enum_name} {{
+    fn from(err: {ty}) -> Self {{
+        Self::{variant}(err)
+    }}
+}}"
+        );
+    }
+    let scaffold = scaffold
+        .lines()
+        .map(|line| if line.is_empty() { String::new() } else { format!("{indent}{line}") })
+        .join("\n");
+
+    acc.add(
+        AssistId("convert_boxed_error_to_enum", AssistKind::RefactorRewrite),
+        format!("Replace `Box<dyn Error>` with `{enum_name}`"),
+        ret_type.syntax().text_range(),
+        |builder| {
+            builder.replace(error_arg.syntax().text_range(), enum_name.clone());
+            builder.insert(anchor.text_range().end(), format!("\n\n{scaffold}"));
+        },
+    )
+}
+
+/// Names the variant after the error type, dropping an `Error` suffix. Types called just
+/// `Error`, like `io::Error`, are named after their module instead.
+fn variant_name(db: &ide_db::RootDatabase, ty: &hir::Type) -> String {
+    let Some(adt) = ty.as_adt() else { return "Other".to_owned() };
+    let name = adt.name(db).to_smol_str();
+    match name.strip_suffix("Error") {
+        Some("") => adt
+            .module(db)
+            .name(db)
+            .map(|it| to_camel_case(&it.to_smol_str()))
+            .unwrap_or_else(|| name.to_string()),
+        Some(stripped) => stripped.to_owned(),
+        None => name.to_string(),
+    }
+}
+
+/// Narrows `vis` to the visibility of the ADTs mentioned in `ty`.
+fn narrower_visibility(
+    db: &ide_db::RootDatabase,
+    vis: hir::Visibility,
+    ty: &hir::Type,
+) -> hir::Visibility {
+    let vis = match ty.as_adt().map(|it| it.visibility(db)) {
+        None | Some(hir::Visibility::Public) => vis,
+        Some(ty_vis @ hir::Visibility::Module(ty_module, _)) => match vis {
+            hir::Visibility::Public => ty_vis,
+            hir::Visibility::Module(module, _) => {
+                // Both are visible from the function's module, so one contains the other.
+                let ty_module = hir::Module::from(ty_module);
+                if ty_module.path_to_root(db).contains(&module.into()) {
+                    ty_vis
+                } else {
+                    vis
+                }
+            }
+        },
+    };
+    ty.type_arguments().fold(vis, |vis, arg| narrower_visibility(db, vis, &arg))
+}
+
+/// Renders `vis` for an item in `module`.
+fn render_visibility(
+    db: &ide_db::RootDatabase,
+    module: hir::Module,
+    vis: hir::Visibility,
+) -> String {
+    let hir::Visibility::Module(vis_module, _) = vis else { return "pub ".to_owned() };
+    let vis_module = hir::Module::from(vis_module);
+    if vis_module == module {
+        String::new()
+    } else if vis_module.is_crate_root() {
+        "pub(crate) ".to_owned()
+    } else if module.parent(db) == Some(vis_module) {
+        "pub(super) ".to_owned()
+    } else {
+        let path = vis_module
+            .path_to_root(db)
+            .into_iter()
+            .rev()
+            .filter_map(|it| it.name(db))
+            .map(|it| it.display(db).to_string())
+            .join("::");
+        format!("pub(in crate::{path}) ")
+    }
+}
+
+/// Returns the module-level item containing `node`, after which the new items are inserted.
+fn item_anchor(node: &SyntaxNode) -> Option<SyntaxNode> {
+    node.ancestors().find(|it| {
+        ast::Item::can_cast(it.kind())
+            && it.parent().map_or(false, |parent| {
+                ast::SourceFile::can_cast(parent.kind()) || ast::ItemList::can_cast(parent.kind())
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn collects_distinct_errors() {
+        check_assist(
+            convert_boxed_error_to_enum,
+            r#"
+//- minicore: result, try, error, fmt
+//- /main.rs crate:main deps:alloc
+use alloc::boxed::Box;
+mod io {
+    pub struct Error;
+}
+struct ParseIntError;
+fn read() -> Result<u32, io::Error> { loop {} }
+fn parse(raw: u32) -> Result<u32, ParseIntError> { loop {} }
+
+pub fn load_config() -> Result<u32, Box<dyn core::error::Error>>$0 {
+    let raw = read()?;
+    let again = read()?;
+    let inner = || -> Result<(), bool> { Err(false)?; Ok(()) };
+    Ok(parse(raw + again)?)
+}
+//- /alloc.rs crate:alloc
+pub mod boxed { pub struct Box<T: ?Sized>(T); }
+"#,
+            r#"
+use alloc::boxed::Box;
+mod io {
+    pub struct Error;
+}
+struct ParseIntError;
+fn read() -> Result<u32, io::Error> { loop {} }
+fn parse(raw: u32) -> Result<u32, ParseIntError> { loop {} }
+
+pub fn load_config() -> Result<u32, LoadConfigError> {
+    let raw = read()?;
+    let again = read()?;
+    let inner = || -> Result<(), bool> { Err(false)?; Ok(()) };
+    Ok(parse(raw + again)?)
+}
+
+#[derive(Debug)]
+enum LoadConfigError {
+    Io(io::Error),
+    ParseInt(ParseIntError),
+}
+
+impl core::fmt::Display for LoadConfigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::ParseInt(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl core::error::Error for LoadConfigError {}
+
+impl From<io::Error> for LoadConfigError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ParseIntError> for LoadConfigError {
+    fn from(err: ParseIntError) -> Self {
+        Self::ParseInt(err)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn method_in_nested_module() {
+        check_assist(
+            convert_boxed_error_to_enum,
+            r#"
+//- minicore: result, try, error, fmt
+//- /main.rs crate:main deps:alloc
+mod m {
+    use alloc::boxed::Box;
+    use core::error::Error;
+    struct Missing;
+    struct Db;
+    impl Db {
+        fn fetch(&self) -> $0Result<(), Box<dyn Error>> {
+            Err(Missing)?
+        }
+    }
+}
+//- /alloc.rs crate:alloc
+pub mod boxed { pub struct Box<T: ?Sized>(T); }
+"#,
+            r#"
+mod m {
+    use alloc::boxed::Box;
+    use core::error::Error;
+    struct Missing;
+    struct Db;
+    impl Db {
+        fn fetch(&self) -> Result<(), FetchError> {
+            Err(Missing)?
+        }
+    }
+
+    #[derive(Debug)]
+    enum FetchError {
+        Missing(Missing),
+    }
+
+    impl core::fmt::Display for FetchError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            match self {
+                Self::Missing(err) => write!(f, "{err}"),
+            }
+        }
+    }
+
+    impl Error for FetchError {}
+
+    impl From<Missing> for FetchError {
+        fn from(err: Missing) -> Self {
+            Self::Missing(err)
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn narrows_visibility_to_error_types() {
+        check_assist(
+            convert_boxed_error_to_enum,
+            r#"
+//- minicore: result, try, error, fmt
+//- /main.rs crate:main deps:alloc
+mod m {
+    use alloc::boxed::Box;
+    use core::error::Error;
+    pub(crate) struct Missing;
+    pub struct Invalid;
+    pub fn fetch() -> $0Result<(), Box<dyn Error>> {
+        Err(Invalid)?;
+        Err(Missing)?
+    }
+}
+//- /alloc.rs crate:alloc
+pub mod boxed { pub struct Box<T: ?Sized>(T); }
+"#,
+            r#"
+mod m {
+    use alloc::boxed::Box;
+    use core::error::Error;
+    pub(crate) struct Missing;
+    pub struct Invalid;
+    pub fn fetch() -> Result<(), FetchError> {
+        Err(Invalid)?;
+        Err(Missing)?
+    }
+
+    #[derive(Debug)]
+    pub(crate) enum FetchError {
+        Invalid(Invalid),
+        Missing(Missing),
+    }
+
+    impl core::fmt::Display for FetchError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            match self {
+                Self::Invalid(err) => write!(f, "{err}"),
+                Self::Missing(err) => write!(f, "{err}"),
+            }
+        }
+    }
+
+    impl Error for FetchError {}
+
+    impl From<Invalid> for FetchError {
+        fn from(err: Invalid) -> Self {
+            Self::Invalid(err)
+        }
+    }
+
+    impl From<Missing> for FetchError {
+        fn from(err: Missing) -> Self {
+            Self::Missing(err)
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable() {
+        check_assist_not_applicable(
+            convert_boxed_error_to_enum,
+            r#"
+//- minicore: result, try, error, fmt
+//- /main.rs crate:main deps:alloc
+struct E;
+fn f() -> $0Result<(), E> {
+    Err(E)?
+}
+//- /alloc.rs crate:alloc
+pub mod boxed { pub struct Box<T: ?Sized>(T); }
+"#,
+        );
+        check_assist_not_applicable(
+            convert_boxed_error_to_enum,
+            r#"
+//- minicore: result, try, error, fmt
+//- /main.rs crate:main deps:alloc
+use alloc::boxed::Box;
+fn f() -> $0Result<(), Box<dyn core::error::Error>> {
+    Ok(())
+}
+//- /alloc.rs crate:alloc
+pub mod boxed { pub struct Box<T: ?Sized>(T); }
+"#,
+        );
+    }
+}
@@ -0,0 +1,357 @@
+use either::Either;
+use hir::{HirDisplay, ModuleSource, PathResolution};
+use syntax::{
+    ast::{self, edit::AstNodeEdit, edit::IndentLevel, AstNode},
+    SyntaxKind, SyntaxNode,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_closure_to_fn
+//
+// Hoists a closure that doesn't capture anything into a named function, and replaces the
+// closure with a path to it.
+//
+// ```
+// fn main() {
+//     let double = $0|x: u32| x * 2;
+//     double(2);
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let double = fun_name;
+//     double(2);
+// }
+//
+// fn fun_name(x: u32) -> u32 {
+//     x * 2
+// }
+// ```
+pub(crate) fn convert_closure_to_fn(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let closure = ctx.find_node_at_offset::<ast::ClosureExpr>()?;
+    let body = closure.body()?;
+    if body.syntax().text_range().contains_range(ctx.selection_trimmed())
+        || closure.async_token().is_some()
+        || closure.const_token().is_some()
+        || closure.static_token().is_some()
+        || closure.generic_param_list().is_some()
+    {
+        return None;
+    }
+    let db = ctx.db();
+    let ty = ctx.sema.type_of_expr(&ast::Expr::ClosureExpr(closure.clone()))?.original;
+    // FIXME: Captured variables could be passed as extra parameters, turning the closure into a
+    // call of the new function.
+    if !ty.as_closure()?.captured_items(db).is_empty() {
+        return None;
+    }
+    let callable = ty.as_callable(db)?;
+    let scope = ctx.sema.scope(closure.syntax())?;
+    let module = scope.module();
+    let render_ty = |ty: &hir::Type| {
+        if ty.contains_unknown() || !ty.generic_params(db).is_empty() {
+            return None;
+        }
+        ty.display_source_code(db, module.into(), false).ok()
+    };
+
+    let mut params = Vec::new();
+    for (pat, ty) in callable.params(db) {
+        let Some(Either::Right(pat)) = pat else { return None };
+        params.push(format!("{pat}: {}", render_ty(&ty)?));
+    }
+    let ret_ty = callable.return_type();
+    let ret = if ret_ty.is_unit() { String::new() } else { format!(" -> {}", render_ty(&ret_ty)?) };
+
+    // Generic parameters and `Self` of the surrounding items aren't available to the function.
+    let mut uses_block_items = false;
+    for path in body.syntax().descendants().filter_map(ast::Path::cast) {
+        match ctx.sema.resolve_path(&path) {
+            Some(
+                PathResolution::TypeParam(_)
+                | PathResolution::ConstParam(_)
+                | PathResolution::SelfType(_),
+            ) => return None,
+            Some(PathResolution::Def(def)) => {
+                uses_block_items |= def.module(db).map_or(false, |module| {
+                    match module.definition_source(db).value {
+                        ModuleSource::BlockExpr(block) => {
+                            !body.syntax().text_range().contains_range(block.syntax().text_range())
+                        }
+                        _ => false,
+                    }
+                });
+            }
+            _ => (),
+        }
+    }
+
+    let mut names_in_scope = Vec::new();
+    scope.process_all_names(&mut |name, _| names_in_scope.push(name.display(db).to_string()));
+    let mut name = "fun_name".to_owned();
+    let mut counter = 0;
+    while names_in_scope.contains(&name) {
+        counter += 1;
+        name = format!("fun_name{counter}");
+    }
+
+    let render_fn = |indent: IndentLevel| {
+        let body = match &body {
+            ast::Expr::BlockExpr(block)
+                if block.modifier().is_none() && block.label().is_none() =>
+            {
+                block.reset_indent().indent(indent).to_string()
+            }
+            _ => {
+                format!("{{\n{}{}\n{indent}}}", indent + 1, body.reset_indent().indent(indent + 1))
+            }
+        };
+        format!("fn {name}({}){ret} {body}", params.join(", "))
+    };
+    let target = closure.syntax().text_range();
+
+    let top_level = closure.syntax().ancestors().find(|it| {
+        ast::Item::can_cast(it.kind())
+            && it.parent().map_or(false, |parent| {
+                matches!(parent.kind(), SyntaxKind::SOURCE_FILE | SyntaxKind::ITEM_LIST)
+            })
+    });
+    if let Some(item) = top_level.filter(|_| !uses_block_items) {
+        acc.add(
+            AssistId("convert_closure_to_fn", AssistKind::RefactorRewrite),
+            "Convert closure to a function",
+            target,
+            |builder| {
+                let indent = IndentLevel::from_node(&item);
+                builder.replace(target, &name);
+                builder
+                    .insert(item.text_range().end(), format!("\n\n{indent}{}", render_fn(indent)));
+            },
+        );
+    }
+
+    if let Some(stmt) = enclosing_stmt(closure.syntax()) {
+        acc.add(
+            AssistId("convert_closure_to_fn", AssistKind::RefactorRewrite),
+            "Convert closure to a nested function",
+            target,
+            |builder| {
+                let indent = IndentLevel::from_node(&stmt);
+                builder.replace(target, &name);
+                builder.insert(
+                    stmt.text_range().start(),
+                    format!("{}\n\n{indent}", render_fn(indent)),
+                );
+            },
+        );
+    }
+    Some(())
+}
+
+/// Returns the statement, or tail expression, of the innermost block containing `node`.
+fn enclosing_stmt(node: &SyntaxNode) -> Option<SyntaxNode> {
+    node.ancestors()
+        .take_while(|it| !ast::Item::can_cast(it.kind()))
+        .find(|it| it.parent().map_or(false, |parent| parent.kind() == SyntaxKind::STMT_LIST))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn expression_closure() {
+        check_assist_by_label(
+            convert_closure_to_fn,
+            r#"
+mod m {
+    fn fun_name() {}
+    fn f() {
+        let double = $0|x: u32| x * 2;
+        double(2);
+    }
+}
+"#,
+            r#"
+mod m {
+    fn fun_name() {}
+    fn f() {
+        let double = fun_name1;
+        double(2);
+    }
+
+    fn fun_name1(x: u32) -> u32 {
+        x * 2
+    }
+}
+"#,
+            "Convert closure to a function",
+        );
+    }
+
+    #[test]
+    fn block_closure_with_inferred_types() {
+        check_assist_by_label(
+            convert_closure_to_fn,
+            r#"
+//- minicore: fn
+fn apply(f: impl Fn(i32, (u8, bool))) {}
+fn main() {
+    apply(move |n, (mut x, _)|$0 {
+        x += 1;
+        if n > 0 {
+            return;
+        }
+    });
+}
+"#,
+            r#"
+fn apply(f: impl Fn(i32, (u8, bool))) {}
+fn main() {
+    apply(fun_name);
+}
+
+fn fun_name(n: i32, (mut x, _): (u8, bool)) {
+    x += 1;
+    if n > 0 {
+        return;
+    }
+}
+"#,
+            "Convert closure to a function",
+        );
+    }
+
+    #[test]
+    fn nested_function() {
+        check_assist_by_label(
+            convert_closure_to_fn,
+            r#"
+fn main() {
+    struct Local(u8);
+    if true {
+        let wrap = |$0x| Local(x);
+        wrap(1);
+    }
+}
+"#,
+            r#"
+fn main() {
+    struct Local(u8);
+    if true {
+        fn fun_name(x: u8) -> Local {
+            Local(x)
+        }
+
+        let wrap = fun_name;
+        wrap(1);
+    }
+}
+"#,
+            "Convert closure to a nested function",
+        );
+    }
+
+    #[test]
+    fn block_items() {
+        check_assist_by_label(
+            convert_closure_to_fn,
+            r#"
+fn main() {
+    struct Local;
+    let make = $0|| Local;
+}
+"#,
+            r#"
+fn main() {
+    struct Local;
+    fn fun_name() -> Local {
+        Local
+    }
+
+    let make = fun_name;
+}
+"#,
+            "Convert closure to a nested function",
+        );
+        check_assist_not_applicable(
+            convert_closure_to_fn,
+            r#"
+fn main() {
+    struct Local;
+    const MAKE: fn() -> Local = $0|| Local;
+}
+"#,
+        );
+        check_assist(
+            convert_closure_to_fn,
+            r#"
+const F: fn() = $0|| {
+    struct Local;
+    let _ = Local;
+};
+"#,
+            r#"
+const F: fn() = fun_name;
+
+fn fun_name() {
+    struct Local;
+    let _ = Local;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_captures() {
+        check_assist_not_applicable(
+            convert_closure_to_fn,
+            r#"
+fn main() {
+    let offset = 1;
+    let add = |$0x: u32| x + offset;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_generics() {
+        check_assist_not_applicable(
+            convert_closure_to_fn,
+            r#"
+fn f<T: Copy>(t: T) {
+    let dup = |$0t: T| (t, t);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_closure_to_fn,
+            r#"
+struct S;
+impl S {
+    fn new() -> Self {
+        let make = $0|| Self;
+        make()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_body() {
+        check_assist_not_applicable(
+            convert_closure_to_fn,
+            r#"
+fn main() {
+    let f = |x: u32| x $0+ 1;
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,575 @@
+use hir::HirDisplay;
+use ide_db::{base_db::FileId, defs::Definition, FxHashMap, FxHashSet};
+use itertools::Itertools;
+use syntax::{
+    ast::{
+        self, edit::IndentLevel, AstNode, HasAttrs, HasDocComments, HasGenericParams, HasName,
+        HasVisibility,
+    },
+    match_ast, SyntaxKind, SyntaxNode, TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+/// Converting bigger enums produces more scaffolding than it saves.
+const MAX_VARIANTS: usize = 8;
+
+// Assist: convert_enum_to_trait
+//
+// Converts an enum with data into a trait with a struct per variant implementing it. Each
+// `match` on the enum becomes a trait method, and the arms are left as `todo!()` stubs in the
+// implementations.
+//
+// ```
+// enum $0Shape {
+//     Circle { radius: f64 },
+//     Square(f64),
+// }
+//
+// fn area(shape: &Shape) -> f64 {
+//     match shape {
+//         Shape::Circle { radius } => 3.14 * radius * radius,
+//         Shape::Square(side) => side * side,
+//     }
+// }
+// ```
+// ->
+// ```
+// trait Shape {
+//     /// Replaces the `match` in `area`.
+//     fn area(&self) -> f64;
+// }
+//
+// struct Circle { radius: f64 }
+//
+// impl Shape for Circle {
+//     fn area(&self) -> f64 {
+//         todo!("move the `Shape::Circle` arm of the `match` in `area` here")
+//     }
+// }
+//
+// struct Square(f64);
+//
+// impl Shape for Square {
+//     fn area(&self) -> f64 {
+//         todo!("move the `Shape::Square` arm of the `match` in `area` here")
+//     }
+// }
+//
+// fn area(shape: &dyn Shape) -> f64 {
+//     shape.area()
+// }
+// ```
+pub(crate) fn convert_enum_to_trait(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let enum_ = ctx.find_node_at_offset::<ast::Enum>()?;
+    let name = enum_.name()?;
+    let header = enum_.enum_token()?.text_range().cover(name.syntax().text_range());
+    if !header.contains_inclusive(ctx.offset()) || enum_.generic_param_list().is_some() {
+        return None;
+    }
+    let variants = enum_.variant_list()?.variants().collect::<Vec<_>>();
+    if variants.is_empty()
+        || variants.len() > MAX_VARIANTS
+        || variants.iter().all(|variant| variant.field_list().is_none())
+    {
+        return None;
+    }
+    let db = ctx.db();
+    let def = ctx.sema.to_def(&enum_)?;
+    // The structs are put next to the trait, so their names must be free in the module.
+    let taken: FxHashSet<_> = def
+        .module(db)
+        .scope(db, None)
+        .into_iter()
+        .filter(|(_, it)| !matches!(it, hir::ScopeDef::ModuleDef(hir::ModuleDef::Variant(_))))
+        .map(|(name, _)| name)
+        .collect();
+    if def.variants(db).iter().any(|variant| taken.contains(&variant.name(db))) {
+        cov_mark::hit!(convert_enum_to_trait_name_collision);
+        return None;
+    }
+    let enum_file = ctx.file_id();
+    let enum_range = enum_.syntax().text_range();
+
+    // The matches on the enum, which become trait methods.
+    let mut matches = Vec::new();
+    let mut seen = FxHashSet::default();
+    for variant in def.variants(db) {
+        for (file_id, refs) in Definition::Variant(variant).usages(&ctx.sema).all() {
+            for reference in refs {
+                let Some(name_ref) = reference.name.as_name_ref() else { continue };
+                let Some(arm) = name_ref.syntax().ancestors().find_map(ast::MatchArm::cast) else {
+                    continue;
+                };
+                let in_pat = arm.pat().map_or(false, |pat| {
+                    pat.syntax().text_range().contains_range(name_ref.syntax().text_range())
+                });
+                let Some(match_) = arm.syntax().ancestors().find_map(ast::MatchExpr::cast) else {
+                    continue;
+                };
+                if in_pat && seen.insert((file_id, match_.syntax().text_range())) {
+                    matches.push((file_id, match_));
+                }
+            }
+        }
+    }
+    // Nested matches are replaced along with the outer one.
+    let ranges =
+        matches.iter().map(|(file_id, it)| (*file_id, it.syntax().text_range())).collect_vec();
+    matches.retain(|(file_id, it)| {
+        let range = it.syntax().text_range();
+        !ranges.iter().any(|&(f, r)| f == *file_id && r != range && r.contains_range(range))
+    });
+    matches.sort_by_key(|(file_id, it)| (*file_id, it.syntax().text_range().start()));
+
+    let mut method_names = FxHashMap::default();
+    let mut methods = Vec::new();
+    for (file_id, match_) in &matches {
+        let scrutinee = match_.expr()?;
+        let scrutinee_ty = ctx.sema.type_of_expr(&scrutinee)?.original;
+        if scrutinee_ty.strip_references().as_adt() != Some(hir::Adt::Enum(def)) {
+            return None;
+        }
+        let origin = match_.syntax().ancestors().find_map(ast::Fn::cast).and_then(|it| it.name());
+        let origin = origin.map_or_else(|| "method".to_owned(), |it| it.text().to_string());
+        let count = method_names.entry(origin.clone()).or_insert(0);
+        *count += 1;
+        let method = if *count == 1 { origin.clone() } else { format!("{origin}{count}") };
+        let receiver = if scrutinee_ty.is_mutable_reference() { "&mut self" } else { "&self" };
+        let module = ctx.sema.scope(match_.syntax())?.module();
+        let ret_ty = ctx.sema.type_of_expr(&ast::Expr::MatchExpr(match_.clone()))?.original;
+        let ret = if ret_ty.is_unit() {
+            String::new()
+        } else {
+            format!(" -> {}", ret_ty.display_source_code(db, module.into(), false).ok()?)
+        };
+        let signature = format!("fn {method}({receiver}){ret}");
+        let call = match scrutinee {
+            ast::Expr::PathExpr(_)
+            | ast::Expr::FieldExpr(_)
+            | ast::Expr::MethodCallExpr(_)
+            | ast::Expr::CallExpr(_)
+            | ast::Expr::ParenExpr(_)
+            | ast::Expr::IndexExpr(_) => format!("{scrutinee}.{method}()"),
+            _ => format!("({scrutinee}).{method}()"),
+        };
+        methods.push((*file_id, match_.syntax().text_range(), signature, origin, call));
+    }
+
+    // Uses of the enum as a type become trait objects, and variant paths become struct paths.
+    let mut edits = Vec::new();
+    for (file_id, refs) in Definition::Adt(hir::Adt::Enum(def)).usages(&ctx.sema).all() {
+        for reference in refs {
+            let Some(name_ref) = reference.name.as_name_ref() else { continue };
+            let Some(path_ty) = name_ref.syntax().ancestors().nth(3).and_then(ast::PathType::cast)
+            else {
+                continue;
+            };
+            if path_ty.path()?.segment()?.name_ref().as_ref() != Some(name_ref) {
+                continue;
+            }
+            let in_pointer =
+                path_ty.syntax().parent().map_or(false, |parent| match parent.kind() {
+                    SyntaxKind::REF_TYPE | SyntaxKind::PTR_TYPE | SyntaxKind::IMPL => true,
+                    SyntaxKind::TYPE_ARG => parent
+                        .ancestors()
+                        .find_map(ast::PathSegment::cast)
+                        .and_then(|it| it.name_ref())
+                        .map_or(false, |it| matches!(it.text().as_str(), "Box" | "Rc" | "Arc")),
+                    _ => false,
+                });
+            let replacement =
+                if in_pointer { format!("dyn {path_ty}") } else { format!("Box<dyn {path_ty}>") };
+            edits.push((file_id, path_ty.syntax().text_range(), replacement));
+        }
+    }
+    for variant in def.variants(db) {
+        let variant_name = variant.name(db).display(db).to_string();
+        for (file_id, refs) in Definition::Variant(variant).usages(&ctx.sema).all() {
+            for reference in refs {
+                let Some(name_ref) = reference.name.as_name_ref() else { continue };
+                let Some(path) = name_ref.syntax().ancestors().nth(2).and_then(ast::Path::cast)
+                else {
+                    continue;
+                };
+                if path.qualifier().is_none()
+                    || path.syntax().ancestors().any(|it| ast::UseTree::can_cast(it.kind()))
+                {
+                    continue;
+                }
+                match constructor_to_box(&path) {
+                    Some(expr) => {
+                        let range = expr.syntax().text_range();
+                        edits.push((
+                            file_id,
+                            path.syntax().text_range(),
+                            format!("Box::new({variant_name}"),
+                        ));
+                        edits.push((file_id, TextRange::empty(range.end()), ")".to_owned()));
+                    }
+                    None => edits.push((file_id, path.syntax().text_range(), variant_name.clone())),
+                }
+            }
+        }
+    }
+    edits.retain(|&(file_id, range, _)| {
+        !methods.iter().any(|&(f, r, ..)| f == file_id && r.contains_range(range))
+    });
+
+    let name = name.text().to_string();
+    let label = match methods.len() {
+        1 => format!("Convert `{name}` to a trait, replacing 1 match"),
+        n => format!("Convert `{name}` to a trait, replacing {n} matches"),
+    };
+    acc.add(
+        AssistId("convert_enum_to_trait", AssistKind::RefactorRewrite),
+        label,
+        header,
+        |builder| {
+            let indent = IndentLevel::from_node(enum_.syntax());
+            let vis = enum_.visibility().map_or(String::new(), |it| format!("{it} "));
+            let (enum_edits, other_edits): (Vec<_>, Vec<_>) =
+                edits.into_iter().partition(|&(file_id, range, _)| {
+                    file_id == enum_file && enum_range.contains_range(range)
+                });
+            let render = |node: &SyntaxNode| {
+                let edits = enum_edits
+                    .iter()
+                    .filter(|(_, range, _)| node.text_range().contains_range(*range))
+                    .map(|(_, range, text)| (*range, text.clone()))
+                    .collect();
+                apply_edits(node, edits)
+            };
+
+            let docs = enum_.doc_comments().map(|it| format!("{it}\n{indent}")).join("");
+            // Other attributes of the enum, like `repr`, don't apply to the structs.
+            let derives = enum_
+                .attrs()
+                .filter(|it| it.simple_name().as_deref() == Some("derive"))
+                .map(|it| format!("{it}\n{indent}"))
+                .join("");
+            let mut buf = format!("{docs}{vis}trait {name} {{");
+            for (_, _, signature, origin, _) in &methods {
+                buf.push_str(&format!(
+                    "\n{}/// Replaces the `match` in `{origin}`.\n{}{signature};",
+                    indent + 1,
+                    indent + 1
+                ));
+            }
+            buf.push_str(if methods.is_empty() { "}" } else { "\n" });
+            if !methods.is_empty() {
+                buf.push_str(&format!("{indent}}}"));
+            }
+            for variant in &variants {
+                let Some(variant_name) = variant.name() else { continue };
+                let variant_docs =
+                    variant.doc_comments().map(|it| format!("{it}\n{indent}")).join("");
+                let variant_attrs = variant.attrs().map(|it| format!("{it}\n{indent}")).join("");
+                // The trait object replacing the enum doesn't implement the derived traits.
+                let holds_trait_object = variant.field_list().map_or(false, |fields| {
+                    let range = fields.syntax().text_range();
+                    enum_edits.iter().any(|(_, it, _)| range.contains_range(*it))
+                });
+                let derives = if holds_trait_object { "" } else { &derives };
+                let fields = match variant.field_list() {
+                    Some(ast::FieldList::RecordFieldList(it)) => {
+                        let fields = it
+                            .fields()
+                            .map(|field| format!("{vis}{}", render(field.syntax())))
+                            .join(", ");
+                        format!(" {{ {fields} }}")
+                    }
+                    Some(ast::FieldList::TupleFieldList(it)) => {
+                        let fields = it
+                            .fields()
+                            .map(|field| format!("{vis}{}", render(field.syntax())))
+                            .join(", ");
+                        format!("({fields});")
+                    }
+                    None => ";".to_owned(),
+                };
+                buf.push_str(&format!(
+                    "\n\n{indent}{variant_docs}{derives}{variant_attrs}{vis}struct {variant_name}{fields}\n\n{indent}impl {name} for {variant_name} {{"
+                ));
+                for (i, (_, _, signature, origin, _)) in methods.iter().enumerate() {
+                    if i > 0 {
+                        buf.push('\n');
+                    }
+                    buf.push_str(&format!(
+                        "\n{}{signature} {{\n{}todo!(\"move the `{name}::{variant_name}` arm of the `match` in `{origin}` here\")\n{}}}",
+                        indent + 1,
+                        indent + 2,
+                        indent + 1,
+                    ));
+                }
+                buf.push_str(if methods.is_empty() { "}" } else { "\n" });
+                if !methods.is_empty() {
+                    buf.push_str(&format!("{indent}}}"));
+                }
+            }
+
+            let mut file_edits: Vec<(FileId, TextRange, String)> = other_edits;
+            file_edits.push((enum_file, enum_range, buf));
+            for (file_id, range, _, _, call) in methods {
+                file_edits.push((file_id, range, call));
+            }
+            file_edits.sort_by_key(|(file_id, range, _)| (*file_id, range.start()));
+            for (file_id, edits) in &file_edits.into_iter().group_by(|(file_id, ..)| *file_id) {
+                builder.edit_file(file_id);
+                for (_, range, text) in edits {
+                    builder.replace(range, text);
+                }
+            }
+        },
+    )
+}
+
+/// Returns the expression constructing a variant at `path` if it has to be boxed, as it is used
+/// where the enum was expected and is now a `Box<dyn Trait>`.
+fn constructor_to_box(path: &ast::Path) -> Option<ast::Expr> {
+    let parent = path.syntax().parent()?;
+    let expr = match_ast! {
+        match parent {
+            ast::RecordExpr(it) => ast::Expr::from(it),
+            ast::PathExpr(it) => match it.syntax().parent().and_then(ast::CallExpr::cast) {
+                Some(call) => ast::Expr::from(call),
+                None => ast::Expr::from(it),
+            },
+            _ => return None,
+        }
+    };
+    let parent = expr.syntax().parent()?;
+    let is_unboxed_use = match_ast! {
+        match parent {
+            // Coerces to a reference to the trait object.
+            ast::RefExpr(_) => true,
+            // Already put into a pointer, as in `Box::new(Enum::Variant)`.
+            ast::ArgList(it) => it
+                .syntax()
+                .parent()
+                .and_then(ast::CallExpr::cast)
+                .and_then(|call| match call.expr()? {
+                    ast::Expr::PathExpr(it) => it.path(),
+                    _ => None,
+                })
+                .and_then(|it| it.qualifier()?.segment()?.name_ref())
+                .map_or(false, |it| matches!(it.text().as_str(), "Box" | "Rc" | "Arc")),
+            // The binding gets the type of the struct.
+            ast::LetStmt(it) => it.ty().is_none(),
+            _ => false,
+        }
+    };
+    (!is_unboxed_use).then_some(expr)
+}
+
+/// Renders the text of `node` with the non-overlapping `edits` applied.
+fn apply_edits(node: &SyntaxNode, mut edits: Vec<(TextRange, String)>) -> String {
+    edits.sort_by_key(|(range, _)| range.start());
+    let text = node.text().to_string();
+    let offset = node.text_range().start();
+    let mut buf = String::new();
+    let mut pos = 0;
+    for (range, replacement) in edits {
+        let range = range - offset;
+        buf.push_str(&text[pos..usize::from(range.start())]);
+        buf.push_str(&replacement);
+        pos = range.end().into();
+    }
+    buf.push_str(&text[pos..]);
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn converts_enum_and_matches() {
+        check_assist(
+            convert_enum_to_trait,
+            r#"
+/// An expression.
+#[derive(Debug)]
+pub enum Expr$0 {
+    Num(i64),
+    /// Addition.
+    Add { lhs: Box<Expr>, rhs: Box<Expr> },
+    Zero,
+}
+
+fn eval(expr: &Expr) -> i64 {
+    match expr {
+        Expr::Num(n) => *n,
+        Expr::Add { lhs, rhs } => eval(lhs) + eval(rhs),
+        Expr::Zero => 0,
+    }
+}
+
+fn simplify(expr: &mut Expr) {
+    match expr {
+        Expr::Add { .. } => {}
+        _ => (),
+    }
+}
+
+fn two() -> Expr {
+    Expr::Add { lhs: Box::new(Expr::Num(1)), rhs: Box::new(Expr::Num(1)) }
+}
+"#,
+            r#"
+/// An expression.
+pub trait Expr {
+    /// Replaces the `match` in `eval`.
+    fn eval(&self) -> i64;
+    /// Replaces the `match` in `simplify`.
+    fn simplify(&mut self);
+}
+
+#[derive(Debug)]
+pub struct Num(pub i64);
+
+impl Expr for Num {
+    fn eval(&self) -> i64 {
+        todo!("move the `Expr::Num` arm of the `match` in `eval` here")
+    }
+
+    fn simplify(&mut self) {
+        todo!("move the `Expr::Num` arm of the `match` in `simplify` here")
+    }
+}
+
+/// Addition.
+pub struct Add { pub lhs: Box<dyn Expr>, pub rhs: Box<dyn Expr> }
+
+impl Expr for Add {
+    fn eval(&self) -> i64 {
+        todo!("move the `Expr::Add` arm of the `match` in `eval` here")
+    }
+
+    fn simplify(&mut self) {
+        todo!("move the `Expr::Add` arm of the `match` in `simplify` here")
+    }
+}
+
+#[derive(Debug)]
+pub struct Zero;
+
+impl Expr for Zero {
+    fn eval(&self) -> i64 {
+        todo!("move the `Expr::Zero` arm of the `match` in `eval` here")
+    }
+
+    fn simplify(&mut self) {
+        todo!("move the `Expr::Zero` arm of the `match` in `simplify` here")
+    }
+}
+
+fn eval(expr: &dyn Expr) -> i64 {
+    expr.eval()
+}
+
+fn simplify(expr: &mut dyn Expr) {
+    expr.simplify()
+}
+
+fn two() -> Box<dyn Expr> {
+    Box::new(Add { lhs: Box::new(Num(1)), rhs: Box::new(Num(1)) })
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable() {
+        check_assist_not_applicable(
+            convert_enum_to_trait,
+            r#"
+enum Fieldless$0 { A, B }
+"#,
+        );
+        check_assist_not_applicable(
+            convert_enum_to_trait,
+            r#"
+enum Generic$0<T> { A(T), B }
+"#,
+        );
+        check_assist_not_applicable(
+            convert_enum_to_trait,
+            r#"
+enum Big$0 { A(u8), B, C, D, E, F, G, H, I }
+"#,
+        );
+        check_assist_not_applicable(
+            convert_enum_to_trait,
+            r#"
+enum E { A(u8$0) }
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_struct_names_are_taken() {
+        cov_mark::check!(convert_enum_to_trait_name_collision);
+        check_assist_not_applicable(
+            convert_enum_to_trait,
+            r#"
+struct Circle;
+enum Shape$0 { Circle(f64), Square(f64) }
+"#,
+        );
+    }
+
+    #[test]
+    fn boxes_constructors() {
+        check_assist(
+            convert_enum_to_trait,
+            r#"
+#[repr(u8)]
+enum Shape$0 { Circle(f64), Empty }
+fn area(shape: &Shape) -> f64 {
+    match shape {
+        Shape::Circle(r) => r * r,
+        Shape::Empty => 0.0,
+    }
+}
+fn make(empty: bool) -> Shape {
+    let circle = Shape::Circle(1.0);
+    area(&Shape::Empty);
+    if empty { Shape::Empty } else { Shape::Circle(2.0) }
+}
+"#,
+            r#"
+trait Shape {
+    /// Replaces the `match` in `area`.
+    fn area(&self) -> f64;
+}
+
+struct Circle(f64);
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        todo!("move the `Shape::Circle` arm of the `match` in `area` here")
+    }
+}
+
+struct Empty;
+
+impl Shape for Empty {
+    fn area(&self) -> f64 {
+        todo!("move the `Shape::Empty` arm of the `match` in `area` here")
+    }
+}
+fn area(shape: &dyn Shape) -> f64 {
+    shape.area()
+}
+fn make(empty: bool) -> Box<dyn Shape> {
+    let circle = Circle(1.0);
+    area(&Empty);
+    if empty { Box::new(Empty) } else { Box::new(Circle(2.0)) }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,530 @@
+use hir::{AsAssocItem, HasCrate};
+use ide_db::{
+    defs::Definition,
+    search::{FileReference, SearchScope},
+};
+use syntax::{
+    ast::{
+        self, edit::IndentLevel, edit_in_place::Indent, make, AstNode, HasArgList, HasGenericParams,
+    },
+    ted, SyntaxKind, SyntaxNode,
+};
+
+use crate::{assist_context::SourceChangeBuilder, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_fn_to_method
+//
+// Moves a free function taking a type of the same module as its first parameter into an `impl`
+// block of that type, turning the parameter into `self` and all calls into method calls.
+//
+// ```
+// struct Counter { count: u32 }
+//
+// fn $0increment(counter: &mut Counter, by: u32) {
+//     counter.count += by;
+// }
+//
+// fn main() {
+//     let mut counter = Counter { count: 0 };
+//     increment(&mut counter, 2);
+// }
+// ```
+// ->
+// ```
+// struct Counter { count: u32 }
+//
+// impl Counter {
+//     fn increment(&mut self, by: u32) {
+//         self.count += by;
+//     }
+// }
+//
+// fn main() {
+//     let mut counter = Counter { count: 0 };
+//     counter.increment(2);
+// }
+// ```
+pub(crate) fn convert_fn_to_method(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let db = ctx.db();
+    let name = ctx.find_node_at_offset::<ast::Name>()?;
+    let fn_ = name.syntax().parent().and_then(ast::Fn::cast)?;
+    let item_list = fn_.syntax().parent()?;
+    if !matches!(item_list.kind(), SyntaxKind::SOURCE_FILE | SyntaxKind::ITEM_LIST) {
+        return None;
+    }
+    let fn_def = ctx.sema.to_def(&fn_)?;
+    if fn_def.as_assoc_item(db).is_some() {
+        return None;
+    }
+
+    let param_list = fn_.param_list()?;
+    let param = param_list.params().next()?;
+    let ast::Pat::IdentPat(ident_pat) = param.pat()? else { return None };
+    if ident_pat.ref_token().is_some() || ident_pat.pat().is_some() {
+        return None;
+    }
+    let (receiver, self_ty) = match param.ty()? {
+        ast::Type::RefType(ref_ty) if ref_ty.lifetime().is_none() => match ref_ty.mut_token() {
+            Some(_) => (Receiver::RefMut, ref_ty.ty()?),
+            None => (Receiver::Ref, ref_ty.ty()?),
+        },
+        ast::Type::RefType(_) => return None,
+        ty => (Receiver::Owned { is_mut: ident_pat.mut_token().is_some() }, ty),
+    };
+    // Only types of the same module can get inherent methods that are visible wherever the
+    // function was, and generic types would need the method to be generic over the impl as well.
+    let ast::Type::PathType(path_ty) = &self_ty else { return None };
+    if path_ty.path()?.segments().any(|it| it.generic_arg_list().is_some()) {
+        return None;
+    }
+    let adt = match ctx.sema.resolve_path(&path_ty.path()?)? {
+        hir::PathResolution::Def(hir::ModuleDef::Adt(adt)) => adt,
+        _ => return None,
+    };
+    if adt.module(db) != fn_def.module(db) || !hir::GenericDef::from(adt).params(db).is_empty() {
+        return None;
+    }
+    let fn_name = fn_def.name(db);
+    let ty = adt.ty(db);
+    if ty
+        .iterate_assoc_items(db, ty.krate(db), |it| it.name(db).filter(|it| *it == fn_name))
+        .is_some()
+    {
+        return None;
+    }
+
+    let mut usages = Vec::new();
+    for (file_id, references) in Definition::Function(fn_def).usages(&ctx.sema).all() {
+        let references = references
+            .into_iter()
+            .map(|FileReference { name, .. }| Usage::new(name.as_name_ref()?.clone()))
+            .collect::<Option<Vec<_>>>();
+        let Some(references) = references else {
+            cov_mark::hit!(convert_fn_to_method_non_call_usage);
+            return None;
+        };
+        usages.push((file_id, references));
+    }
+    let param_usages = ctx.sema.to_def(&ident_pat).map_or_else(Vec::new, |local| {
+        Definition::Local(local)
+            .usages(&ctx.sema)
+            .in_scope(&SearchScope::single_file(ctx.file_id()))
+            .all()
+            .references
+            .remove(&ctx.file_id())
+            .unwrap_or_default()
+    });
+    let param_usages = param_usages
+        .into_iter()
+        .map(|FileReference { name, .. }| name.as_name_ref().cloned())
+        .collect::<Option<Vec<_>>>()?;
+    let existing_impl = item_list.children().filter_map(ast::Impl::cast).find(|impl_| {
+        impl_.trait_().is_none()
+            && impl_.generic_param_list().is_none()
+            && ctx.sema.to_def(impl_).and_then(|it| it.self_ty(db).as_adt()) == Some(adt)
+    });
+
+    acc.add(
+        AssistId("convert_fn_to_method", AssistKind::RefactorRewrite),
+        format!("Convert `{}` into a method of `{self_ty}`", fn_name.display(db)),
+        name.syntax().text_range(),
+        |builder| {
+            // Everything in a file has to be made mutable before the first edit to it, so handle
+            // the file of the function first.
+            usages.sort_by_key(|(file_id, _)| *file_id != ctx.file_id());
+            if usages.first().map_or(true, |(file_id, _)| *file_id != ctx.file_id()) {
+                usages.insert(0, (ctx.file_id(), Vec::new()));
+            }
+
+            for (file_id, references) in usages {
+                builder.edit_file(file_id);
+                let fn_and_params = (file_id == ctx.file_id()).then(|| {
+                    let param_usages: Vec<_> =
+                        param_usages.iter().map(|it| builder.make_mut(it.clone())).collect();
+                    let assoc_item_list = existing_impl
+                        .as_ref()
+                        .map(|it| builder.make_mut(it.clone()).get_or_create_assoc_item_list());
+                    (builder.make_mut(fn_.clone()), param_usages, assoc_item_list)
+                });
+                let mut references: Vec<_> =
+                    references.into_iter().map(|it| it.make_mut(builder)).collect();
+                // Rewrite nested calls first, so that the outer ones pick up the new receivers.
+                references.sort_by_key(|it| std::cmp::Reverse(it.syntax().text_range().start()));
+
+                if let Some((_, param_usages, _)) = &fn_and_params {
+                    for name_ref in param_usages {
+                        replace_with_self(name_ref);
+                    }
+                }
+                for usage in references {
+                    match usage {
+                        Usage::Call(call) => {
+                            rewrite_call(call, &fn_name, receiver);
+                        }
+                        Usage::Import(use_tree) => use_tree.remove_recursive(),
+                    }
+                }
+
+                if let Some((fn_, _, assoc_item_list)) = fn_and_params {
+                    let params = fn_.param_list().into_iter().flat_map(|it| it.params()).skip(1);
+                    let param_list = make::param_list(Some(receiver.to_self_param()), params);
+                    if let Some(old) = fn_.param_list() {
+                        ted::replace(old.syntax(), param_list.clone_for_update().syntax());
+                    }
+                    fn_.indent(IndentLevel(1));
+
+                    let assoc_item_list = match assoc_item_list {
+                        Some(it) => {
+                            remove_with_whitespace(&fn_);
+                            it
+                        }
+                        None => {
+                            let impl_ = make::impl_(None, None, self_ty.clone(), None, None)
+                                .clone_for_update();
+                            ted::replace(fn_.syntax(), impl_.syntax());
+                            impl_.get_or_create_assoc_item_list()
+                        }
+                    };
+                    assoc_item_list.add_item(fn_.into());
+                }
+            }
+        },
+    )
+}
+
+#[derive(Clone, Copy)]
+enum Receiver {
+    Owned { is_mut: bool },
+    Ref,
+    RefMut,
+}
+
+impl Receiver {
+    fn to_self_param(self) -> ast::SelfParam {
+        match self {
+            Receiver::Owned { is_mut } => make::owned_self_param(is_mut),
+            Receiver::Ref => make::self_param(),
+            Receiver::RefMut => make::mut_self_param(),
+        }
+    }
+}
+
+enum Usage {
+    Call(ast::CallExpr),
+    Import(ast::UseTree),
+}
+
+impl Usage {
+    fn new(name_ref: ast::NameRef) -> Option<Usage> {
+        // References in macro calls can't be edited.
+        if !ast::SourceFile::can_cast(name_ref.syntax().ancestors().last()?.kind()) {
+            return None;
+        }
+        let path = name_ref.syntax().parent().and_then(ast::PathSegment::cast)?.parent_path();
+        if let Some(use_tree) = path.syntax().parent().and_then(ast::UseTree::cast) {
+            return Some(Usage::Import(use_tree));
+        }
+        let path_expr = path.syntax().parent().and_then(ast::PathExpr::cast)?;
+        let call = path_expr.syntax().parent().and_then(ast::CallExpr::cast)?;
+        if call.expr()?.syntax() != path_expr.syntax() || call.arg_list()?.args().next().is_none() {
+            return None;
+        }
+        Some(Usage::Call(call))
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        match self {
+            Usage::Call(it) => it.syntax(),
+            Usage::Import(it) => it.syntax(),
+        }
+    }
+
+    fn make_mut(self, builder: &mut SourceChangeBuilder) -> Usage {
+        match self {
+            Usage::Call(it) => Usage::Call(builder.make_mut(it)),
+            Usage::Import(it) => Usage::Import(builder.make_mut(it)),
+        }
+    }
+}
+
+fn replace_with_self(name_ref: &ast::NameRef) {
+    let self_expr = make::expr_path(make::ext::ident_path("self"));
+    let field = ast::RecordExprField::for_field_name(name_ref).filter(|it| it.name_ref().is_none());
+    match field {
+        // `Foo { param }` has to become `Foo { param: self }`.
+        Some(field) => ted::replace(
+            field.syntax(),
+            make::record_expr_field(name_ref.clone(), Some(self_expr)).clone_for_update().syntax(),
+        ),
+        None => ted::replace(name_ref.syntax(), make::name_ref("self").clone_for_update().syntax()),
+    }
+}
+
+fn rewrite_call(call: ast::CallExpr, fn_name: &hir::Name, receiver: Receiver) -> Option<()> {
+    let mut args = call.arg_list()?.args();
+    let mut receiver_expr = args.next()?;
+    // Autoref takes care of borrowing the receiver.
+    if let ast::Expr::RefExpr(ref_expr) = &receiver_expr {
+        let strip = match receiver {
+            Receiver::Owned { .. } => false,
+            Receiver::Ref => ref_expr.raw_token().is_none(),
+            Receiver::RefMut => ref_expr.raw_token().is_none() && ref_expr.mut_token().is_some(),
+        };
+        if strip {
+            receiver_expr = ref_expr.expr()?;
+        }
+    }
+    if !matches!(
+        receiver_expr,
+        ast::Expr::PathExpr(_)
+            | ast::Expr::ParenExpr(_)
+            | ast::Expr::CallExpr(_)
+            | ast::Expr::MethodCallExpr(_)
+            | ast::Expr::FieldExpr(_)
+            | ast::Expr::IndexExpr(_)
+            | ast::Expr::TryExpr(_)
+            | ast::Expr::AwaitExpr(_)
+            | ast::Expr::MacroExpr(_)
+            | ast::Expr::TupleExpr(_)
+            | ast::Expr::ArrayExpr(_)
+    ) {
+        receiver_expr = make::expr_paren(receiver_expr);
+    }
+
+    let method_name = make::name_ref(&fn_name.unescaped().to_smol_str());
+    let method_call =
+        make::expr_method_call(receiver_expr, method_name, make::arg_list(args)).clone_for_update();
+    // `call` is already mutable, so the turbofish has to be detached from its tree.
+    let generic_args = match &call.expr()? {
+        ast::Expr::PathExpr(it) => it.path()?.segment()?.generic_arg_list(),
+        _ => None,
+    };
+    if let (ast::Expr::MethodCallExpr(method_call), Some(generic_args)) =
+        (&method_call, generic_args)
+    {
+        ted::insert_raw(
+            ted::Position::after(method_call.name_ref()?.syntax()),
+            generic_args.syntax().clone_subtree().clone_for_update(),
+        );
+    }
+    ted::replace(call.syntax(), method_call.syntax());
+    Some(())
+}
+
+fn remove_with_whitespace(fn_: &ast::Fn) {
+    let whitespace = fn_
+        .syntax()
+        .prev_sibling_or_token()
+        .or_else(|| fn_.syntax().next_sibling_or_token())
+        .filter(|it| it.kind() == SyntaxKind::WHITESPACE);
+    if let Some(whitespace) = whitespace {
+        ted::remove(whitespace);
+    }
+    ted::remove(fn_.syntax());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn by_value_into_existing_impl() {
+        check_assist(
+            convert_fn_to_method,
+            r#"
+struct Meters(f64);
+
+impl Meters {
+    fn new(value: f64) -> Self {
+        Meters(value)
+    }
+}
+
+fn $0scale(mut meters: Meters, factor: f64) -> Meters {
+    meters.0 *= factor;
+    Meters { 0: meters.0 }
+}
+
+fn main() {
+    let m = scale(Meters::new(1.0), 2.0);
+    let n = scale(scale(m, 0.5), 3.0);
+}
+"#,
+            r#"
+struct Meters(f64);
+
+impl Meters {
+    fn new(value: f64) -> Self {
+        Meters(value)
+    }
+
+    fn scale(mut self, factor: f64) -> Meters {
+        self.0 *= factor;
+        Meters { 0: self.0 }
+    }
+}
+
+fn main() {
+    let m = Meters::new(1.0).scale(2.0);
+    let n = m.scale(0.5).scale(3.0);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn shared_ref_with_imports_and_recursion() {
+        check_assist(
+            convert_fn_to_method,
+            r#"
+//- /main.rs
+mod shapes;
+
+use shapes::{depth, Node};
+
+fn main() {
+    let node = Node { child: None };
+    let d = depth(&node);
+    let boxed = Box::new(node);
+    shapes::depth(&*boxed);
+}
+//- /shapes.rs
+pub struct Node {
+    pub child: Option<Box<Node>>,
+}
+
+pub fn $0depth(node: &Node) -> usize {
+    match &node.child {
+        Some(child) => 1 + depth(child),
+        None => 0,
+    }
+}
+"#,
+            r#"
+//- /main.rs
+mod shapes;
+
+use shapes::Node;
+
+fn main() {
+    let node = Node { child: None };
+    let d = node.depth();
+    let boxed = Box::new(node);
+    (*boxed).depth();
+}
+//- /shapes.rs
+pub struct Node {
+    pub child: Option<Box<Node>>,
+}
+
+impl Node {
+    pub fn depth(&self) -> usize {
+        match &self.child {
+            Some(child) => 1 + child.depth(),
+            None => 0,
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn field_shorthand_and_turbofish() {
+        check_assist(
+            convert_fn_to_method,
+            r#"
+struct Config { verbose: bool }
+struct Wrapper<T> { config: T }
+
+fn $0wrap<T>(config: &mut Config, _marker: Option<T>) -> Wrapper<&mut Config> {
+    Wrapper { config }
+}
+
+fn f(config: &mut Config) {
+    wrap::<u8>(config, None);
+}
+"#,
+            r#"
+struct Config { verbose: bool }
+struct Wrapper<T> { config: T }
+
+impl Config {
+    fn wrap<T>(&mut self, _marker: Option<T>) -> Wrapper<&mut Config> {
+        Wrapper { config: self }
+    }
+}
+
+fn f(config: &mut Config) {
+    config.wrap::<u8>(None);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_non_call_usages() {
+        cov_mark::check!(convert_fn_to_method_non_call_usage);
+        check_assist_not_applicable(
+            convert_fn_to_method,
+            r#"
+struct S;
+fn $0f(s: &S) {}
+fn g(items: &[S]) {
+    items.iter().for_each(f);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable() {
+        // The first parameter isn't of a type of this module.
+        check_assist_not_applicable(
+            convert_fn_to_method,
+            r#"
+mod m {
+    pub struct S;
+}
+fn $0f(s: &m::S) {}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_fn_to_method,
+            r#"
+struct S;
+fn $0f(s: &[S]) {}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_fn_to_method,
+            r#"
+struct S<T>(T);
+fn $0f(s: &S<u8>) {}
+"#,
+        );
+        // There already is a method of that name.
+        check_assist_not_applicable(
+            convert_fn_to_method,
+            r#"
+struct S;
+impl S {
+    fn f(&self) {}
+}
+fn $0f(s: &S) {}
+"#,
+        );
+        // Already a method.
+        check_assist_not_applicable(
+            convert_fn_to_method,
+            r#"
+struct S;
+impl S {
+    fn $0f(s: &S) {}
+}
+"#,
+        );
+    }
+}
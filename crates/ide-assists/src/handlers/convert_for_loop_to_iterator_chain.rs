@@ -0,0 +1,449 @@
+use hir::PathResolution;
+use ide_db::famous_defs::FamousDefs;
+use stdx::format_to;
+use syntax::{
+    ast::{self, HasArgList, HasLoopBody, HasName},
+    AstNode, SyntaxKind, T,
+};
+
+use crate::{
+    handlers::convert_iter_for_each_to_for::iterator_from_iterable,
+    utils::invert_boolean_expression, AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: convert_for_loop_to_iterator_chain
+//
+// Converts a `for` loop that fills a freshly created `Vec` into an iterator chain ending in
+// `collect`.
+//
+// ```
+// # //- minicore: iterator, copy
+// # //- /main.rs crate:main deps:alloc
+// use alloc::vec::Vec;
+// fn main() {
+//     let xs = [1, 2, 3];
+//     let mut ys = Vec::new();
+//     $0for x in xs {
+//         if x > 1 {
+//             ys.push(x * 2);
+//         }
+//     }
+// }
+// # //- /alloc.rs crate:alloc
+// # pub mod vec {
+// #     pub struct Vec<T>(T);
+// #     impl<T> Vec<T> {
+// #         pub fn new() -> Self { loop {} }
+// #         pub fn push(&mut self, value: T) {}
+// #     }
+// # }
+// ```
+// ->
+// ```
+// use alloc::vec::Vec;
+// fn main() {
+//     let xs = [1, 2, 3];
+//     let mut ys: Vec<_> = xs.into_iter().filter(|&x| x > 1).map(|x| x * 2).collect();
+// }
+// ```
+pub(crate) fn convert_for_loop_to_iterator_chain(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let for_keyword = ctx.find_token_syntax_at_offset(T![for])?;
+    let for_loop = ast::ForExpr::cast(for_keyword.parent()?)?;
+    if for_loop.label().is_some() {
+        return None;
+    }
+    let pat = for_loop.pat()?;
+    let iterable = for_loop.iterable()?;
+    let body = for_loop.loop_body()?.stmt_list()?;
+    if has_comments(body.syntax()) {
+        return None;
+    }
+
+    // The loop has to directly follow the creation of the `Vec` it fills.
+    let parent = for_loop.syntax().parent()?;
+    let for_stmt = match parent.kind() {
+        SyntaxKind::EXPR_STMT => parent,
+        SyntaxKind::STMT_LIST => for_loop.syntax().clone(),
+        _ => return None,
+    };
+    let let_stmt = ast::LetStmt::cast(for_stmt.prev_sibling()?)?;
+    if let_stmt.let_else().is_some()
+        || let_stmt
+            .syntax()
+            .siblings_with_tokens(syntax::Direction::Next)
+            .take_while(|it| it.as_node() != Some(&for_stmt))
+            .any(|it| it.kind() == SyntaxKind::COMMENT)
+    {
+        return None;
+    }
+    let ast::Pat::IdentPat(vec_pat) = let_stmt.pat()? else { return None };
+    if !is_empty_vec(&let_stmt.initializer()?) {
+        return None;
+    }
+    let vec_local = ctx.sema.to_def(&vec_pat)?;
+    let krate = ctx.sema.scope(for_loop.syntax())?.krate();
+    let vec = FamousDefs(&ctx.sema, krate).alloc_vec_Vec()?;
+    if vec_local.ty(ctx.db()).as_adt() != Some(hir::Adt::Struct(vec)) {
+        return None;
+    }
+
+    let mut parts = body
+        .statements()
+        .map(|stmt| match stmt {
+            ast::Stmt::ExprStmt(it) => it.expr(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    parts.extend(body.tail_expr());
+    let (filter, value) = match parts.as_slice() {
+        [push] => match push {
+            ast::Expr::IfExpr(if_expr) => {
+                let (cond, then) = if_without_else(if_expr)?;
+                (Some(cond), pushed_value(ctx, &then, vec_local)?)
+            }
+            _ => (None, pushed_value(ctx, push, vec_local)?),
+        },
+        [ast::Expr::IfExpr(if_expr), push] => {
+            // `if cond { continue; }` skips exactly the items a `filter` would drop.
+            let (cond, then) = if_without_else(if_expr)?;
+            match then {
+                ast::Expr::ContinueExpr(it) if it.lifetime().is_none() => {}
+                _ => return None,
+            }
+            (Some(invert_boolean_expression(cond)), pushed_value(ctx, push, vec_local)?)
+        }
+        _ => return None,
+    };
+
+    let vec_name = vec_pat.name()?;
+    let is_movable = |expr: &ast::Expr| {
+        !expr.syntax().descendants().any(|node| {
+            matches!(
+                node.kind(),
+                SyntaxKind::BREAK_EXPR
+                    | SyntaxKind::CONTINUE_EXPR
+                    | SyntaxKind::RETURN_EXPR
+                    | SyntaxKind::BECOME_EXPR
+                    | SyntaxKind::TRY_EXPR
+                    | SyntaxKind::AWAIT_EXPR
+                    | SyntaxKind::YIELD_EXPR
+                    | SyntaxKind::MACRO_EXPR
+            ) || ast::NameRef::cast(node).map_or(false, |it| vec_name.text() == it.text())
+        })
+    };
+    if !is_movable(&value) || filter.as_ref().map_or(false, |it| !is_movable(it)) {
+        return None;
+    }
+    if filter.is_some() {
+        // `filter` hands out references to the items, so its closure destructures `&pat`,
+        // which only works for `Copy` items bound by plain patterns.
+        let item_is_copy =
+            ctx.sema.type_of_pat(&pat).map_or(false, |it| it.original.is_copy(ctx.db()));
+        let has_binding_modes = pat
+            .syntax()
+            .descendants()
+            .filter_map(ast::IdentPat::cast)
+            .any(|it| it.mut_token().is_some() || it.ref_token().is_some());
+        if !item_is_copy || has_binding_modes {
+            return None;
+        }
+    }
+
+    let target = let_stmt.syntax().text_range().cover(for_stmt.text_range());
+    acc.add(
+        AssistId("convert_for_loop_to_iterator_chain", AssistKind::RefactorRewrite),
+        "Replace this for loop with an iterator chain",
+        target,
+        |builder| {
+            let ty = let_stmt.ty().map_or_else(|| "Vec<_>".to_owned(), |it| it.to_string());
+            let mut buf = format!("let {vec_pat}: {ty} = ");
+            buf.push_str(&iterator_from_iterable(ctx, &iterable));
+            if let Some(filter) = filter {
+                format_to!(buf, ".filter(|&{pat}| {filter})");
+            }
+            if value.syntax().text() != pat.syntax().text() {
+                format_to!(buf, ".map(|{pat}| {value})");
+            }
+            buf.push_str(".collect();");
+
+            builder.replace(target, buf);
+        },
+    )
+}
+
+fn has_comments(node: &syntax::SyntaxNode) -> bool {
+    node.descendants_with_tokens().any(|it| it.kind() == SyntaxKind::COMMENT)
+}
+
+/// Whether `init` creates an empty `Vec`, via `Vec::new()` or `vec![]`.
+fn is_empty_vec(init: &ast::Expr) -> bool {
+    match init {
+        ast::Expr::CallExpr(call) => {
+            let is_new = match call.expr() {
+                Some(ast::Expr::PathExpr(path)) => path
+                    .path()
+                    .and_then(|it| it.segment())
+                    .and_then(|it| it.name_ref())
+                    .map_or(false, |it| it.text() == "new"),
+                _ => false,
+            };
+            is_new && call.arg_list().map_or(false, |it| it.args().next().is_none())
+        }
+        ast::Expr::MacroExpr(mac) => {
+            let Some(call) = mac.macro_call() else { return false };
+            let is_vec = call.path().map_or(false, |it| it.syntax().text() == "vec");
+            let is_empty = call
+                .token_tree()
+                .map_or(false, |it| it.syntax().children_with_tokens().count() == 2);
+            is_vec && is_empty
+        }
+        _ => false,
+    }
+}
+
+/// Splits an `if` without an `else` whose block holds a single expression.
+fn if_without_else(if_expr: &ast::IfExpr) -> Option<(ast::Expr, ast::Expr)> {
+    if if_expr.else_branch().is_some() {
+        return None;
+    }
+    let cond = if_expr.condition()?;
+    if cond.syntax().descendants().any(|it| it.kind() == SyntaxKind::LET_EXPR) {
+        return None;
+    }
+    let stmt_list = if_expr.then_branch()?.stmt_list()?;
+    let mut stmts = stmt_list.statements();
+    let expr = match (stmts.next(), stmts.next(), stmt_list.tail_expr()) {
+        (Some(ast::Stmt::ExprStmt(stmt)), None, None) => stmt.expr()?,
+        (None, None, Some(tail)) => tail,
+        _ => return None,
+    };
+    Some((cond, expr))
+}
+
+/// Returns `value` if `expr` is `vec.push(value)` on the given local.
+fn pushed_value(ctx: &AssistContext<'_>, expr: &ast::Expr, vec: hir::Local) -> Option<ast::Expr> {
+    let ast::Expr::MethodCallExpr(call) = expr else { return None };
+    if call.name_ref()?.text() != "push" {
+        return None;
+    }
+    let ast::Expr::PathExpr(receiver) = call.receiver()? else { return None };
+    match ctx.sema.resolve_path(&receiver.path()?)? {
+        PathResolution::Local(local) if local == vec => {}
+        _ => return None,
+    }
+    let mut args = call.arg_list()?.args();
+    let value = args.next()?;
+    args.next().is_none().then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn map_only() {
+        check_assist(
+            convert_for_loop_to_iterator_chain,
+            r#"
+//- minicore: iterator, copy
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+fn f(xs: [i32; 3]) {
+    let mut ys = Vec::new();
+    for$0 x in xs {
+        ys.push(x + 1);
+    }
+}
+//- /alloc.rs crate:alloc
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn new() -> Self { loop {} }
+        pub fn push(&mut self, value: T) {}
+    }
+}
+"#,
+            r#"
+use alloc::vec::Vec;
+fn f(xs: [i32; 3]) {
+    let mut ys: Vec<_> = xs.into_iter().map(|x| x + 1).collect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn filter_only_keeps_annotation() {
+        check_assist(
+            convert_for_loop_to_iterator_chain,
+            r#"
+//- minicore: iterator, copy
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+fn f(xs: [i32; 3]) {
+    let mut ys: Vec<i32> = Vec::new();
+    $0for x in xs {
+        if x != 0 {
+            ys.push(x)
+        }
+    }
+}
+//- /alloc.rs crate:alloc
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn new() -> Self { loop {} }
+        pub fn push(&mut self, value: T) {}
+    }
+}
+"#,
+            r#"
+use alloc::vec::Vec;
+fn f(xs: [i32; 3]) {
+    let mut ys: Vec<i32> = xs.into_iter().filter(|&x| x != 0).collect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn continue_becomes_filter() {
+        check_assist(
+            convert_for_loop_to_iterator_chain,
+            r#"
+//- minicore: iterator, copy
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+fn f(xs: [i32; 3]) {
+    let mut ys = Vec::new();
+    $0for x in xs {
+        if x == 0 {
+            continue;
+        }
+        ys.push(x * 2);
+    }
+}
+//- /alloc.rs crate:alloc
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn new() -> Self { loop {} }
+        pub fn push(&mut self, value: T) {}
+    }
+}
+"#,
+            r#"
+use alloc::vec::Vec;
+fn f(xs: [i32; 3]) {
+    let mut ys: Vec<_> = xs.into_iter().filter(|&x| x != 0).map(|x| x * 2).collect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_break() {
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator_chain,
+            r#"
+//- minicore: iterator, copy
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+fn f(xs: [i32; 3]) {
+    let mut ys = Vec::new();
+    $0for x in xs {
+        if x == 0 {
+            break;
+        }
+        ys.push(x);
+    }
+}
+//- /alloc.rs crate:alloc
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn new() -> Self { loop {} }
+        pub fn push(&mut self, value: T) {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_non_empty_or_non_vec() {
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator_chain,
+            r#"
+//- minicore: iterator, copy
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+fn f(xs: [i32; 3], mut ys: Vec<i32>) {
+    let zs = 0;
+    $0for x in xs {
+        ys.push(x);
+    }
+}
+//- /alloc.rs crate:alloc
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn new() -> Self { loop {} }
+        pub fn push(&mut self, value: T) {}
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator_chain,
+            r#"
+//- minicore: iterator, copy
+struct Bag;
+impl Bag {
+    fn new() -> Self { Bag }
+    fn push(&mut self, _: i32) {}
+}
+fn f(xs: [i32; 3]) {
+    let mut ys = Bag::new();
+    $0for x in xs {
+        ys.push(x);
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_filtering_non_copy_items() {
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator_chain,
+            r#"
+//- minicore: iterator, copy
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+struct S(i32);
+fn f(xs: [S; 3]) {
+    let mut ys = Vec::new();
+    $0for x in xs {
+        if x.0 > 0 {
+            ys.push(x);
+        }
+    }
+}
+//- /alloc.rs crate:alloc
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn new() -> Self { loop {} }
+        pub fn push(&mut self, value: T) {}
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,421 @@
+use hir::PathResolution;
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        make, AstNode, HasAttrs, HasName,
+    },
+    Direction,
+    SyntaxKind::WHITESPACE,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_guard_to_nested_match
+//
+// Converts a match arm guard into a nested `match` on the guard condition. The arms following the
+// guarded one are repeated in the `false` branch, so the value still falls through to them.
+//
+// ```
+// # //- minicore: option, copy
+// fn handle(value: Option<i32>) {
+//     match value {
+//         Some(x) $0if x > 0 => foo(x),
+//         _ => (),
+//     }
+// }
+// ```
+// ->
+// ```
+// fn handle(value: Option<i32>) {
+//     match value {
+//         Some(x) => match x > 0 {
+//             true => foo(x),
+//             false => match value {
+//                 _ => (),
+//             }
+//         },
+//         _ => (),
+//     }
+// }
+// ```
+pub(crate) fn convert_guard_to_nested_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let guard = ctx.find_node_at_offset::<ast::MatchGuard>()?;
+    let match_arm = guard.syntax().parent().and_then(ast::MatchArm::cast)?;
+    if !guard.syntax().text_range().contains_inclusive(ctx.offset()) {
+        return None;
+    }
+
+    let condition = guard.condition()?;
+    if matches!(condition, ast::Expr::LetExpr(_)) {
+        cov_mark::hit!(convert_guard_to_nested_match_let_guard);
+        return None;
+    }
+    let arm_expr = match_arm.expr()?;
+    let space_before_guard = guard.syntax().prev_sibling_or_token();
+
+    // The `false` branch matches the scrutinee again, which has to be cheap to evaluate and must
+    // not have been moved out of by the bindings of the guarded arm.
+    let match_expr = match_arm.syntax().ancestors().find_map(ast::MatchExpr::cast)?;
+    let scrutinee = match match_expr.expr()? {
+        ast::Expr::PathExpr(it) => it,
+        _ => {
+            cov_mark::hit!(convert_guard_to_nested_match_complex_scrutinee);
+            return None;
+        }
+    };
+    let bindings: Vec<_> =
+        match_arm.pat()?.syntax().descendants().filter_map(ast::IdentPat::cast).collect();
+    let moves_out = bindings.iter().any(|it| {
+        it.ref_token().is_none()
+            && ctx.sema.to_def(it).map_or(false, |local| !local.ty(ctx.db()).is_copy(ctx.db()))
+    });
+    if moves_out {
+        cov_mark::hit!(convert_guard_to_nested_match_moving_bindings);
+        return None;
+    }
+    // A binding of the same name would shadow the scrutinee in the `false` branch.
+    let scrutinee_name = scrutinee.path().and_then(|it| it.as_single_name_ref());
+    let shadows_scrutinee = scrutinee_name.map_or(false, |scrutinee| {
+        bindings.iter().any(|it| it.name().map_or(false, |it| it.text() == scrutinee.text()))
+    });
+    if shadows_scrutinee {
+        cov_mark::hit!(convert_guard_to_nested_match_shadowed_scrutinee);
+        return None;
+    }
+    let later_arms: Vec<_> = match_arm
+        .syntax()
+        .siblings(Direction::Next)
+        .skip(1)
+        .filter_map(ast::MatchArm::cast)
+        .collect();
+    // Without a catch-all arm the repeated arms might not be exhaustive on their own.
+    let has_catch_all = later_arms
+        .iter()
+        .any(|arm| arm.guard().is_none() && arm.pat().map_or(false, |it| is_irrefutable(ctx, &it)));
+    if !has_catch_all || later_arms.iter().any(|arm| arm.attrs().next().is_some()) {
+        cov_mark::hit!(convert_guard_to_nested_match_no_fall_through);
+        return None;
+    }
+
+    let target = guard.syntax().text_range();
+    acc.add(
+        AssistId("convert_guard_to_nested_match", AssistKind::RefactorRewrite),
+        "Convert guard to nested match",
+        target,
+        |edit| {
+            let indent = IndentLevel::from_node(match_arm.syntax());
+            let fall_through_arms = later_arms.iter().filter_map(|arm| {
+                let expr = arm.expr()?;
+                let expr = expr.dedent(IndentLevel::from_node(expr.syntax())).indent(1.into());
+                Some(make::match_arm(arm.pat(), arm.guard().and_then(|it| it.condition()), expr))
+            });
+            let fall_through = make::expr_match(
+                ast::Expr::PathExpr(scrutinee),
+                make::match_arm_list(fall_through_arms),
+            )
+            .indent(1.into());
+            let body = arm_expr.dedent(IndentLevel::from_node(arm_expr.syntax())).indent(1.into());
+            let arms = [
+                make::match_arm([make::literal_pat("true").into()], None, body),
+                make::match_arm([make::literal_pat("false").into()], None, fall_through),
+            ];
+            let nested_match =
+                make::expr_match(condition, make::match_arm_list(arms)).indent(indent);
+
+            match space_before_guard {
+                Some(element) if element.kind() == WHITESPACE => {
+                    edit.delete(element.text_range());
+                }
+                _ => (),
+            };
+            edit.delete(guard.syntax().text_range());
+            edit.replace(arm_expr.syntax().text_range(), nested_match.to_string());
+        },
+    )
+}
+
+/// Whether `pat` matches every value of its type.
+fn is_irrefutable(ctx: &AssistContext<'_>, pat: &ast::Pat) -> bool {
+    let is_struct = |path: Option<ast::Path>| {
+        matches!(
+            path.and_then(|it| ctx.sema.resolve_path(&it)),
+            Some(PathResolution::Def(hir::ModuleDef::Adt(hir::Adt::Struct(_))))
+        )
+    };
+    match pat {
+        ast::Pat::WildcardPat(_) | ast::Pat::RestPat(_) => true,
+        ast::Pat::IdentPat(it) => {
+            ctx.sema.resolve_bind_pat_to_const(it).is_none()
+                && it.pat().map_or(true, |it| is_irrefutable(ctx, &it))
+        }
+        ast::Pat::OrPat(it) => it.pats().any(|it| is_irrefutable(ctx, &it)),
+        ast::Pat::TuplePat(it) => it.fields().all(|it| is_irrefutable(ctx, &it)),
+        ast::Pat::ParenPat(it) => it.pat().map_or(false, |it| is_irrefutable(ctx, &it)),
+        ast::Pat::RefPat(it) => it.pat().map_or(false, |it| is_irrefutable(ctx, &it)),
+        ast::Pat::BoxPat(it) => it.pat().map_or(false, |it| is_irrefutable(ctx, &it)),
+        ast::Pat::TupleStructPat(it) => {
+            is_struct(it.path()) && it.fields().all(|it| is_irrefutable(ctx, &it))
+        }
+        ast::Pat::RecordPat(it) => {
+            is_struct(it.path())
+                && it.record_pat_field_list().map_or(false, |it| {
+                    it.fields().all(|it| it.pat().map_or(false, |it| is_irrefutable(ctx, &it)))
+                })
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{check_assist, check_assist_not_applicable, check_assist_target};
+
+    #[test]
+    fn convert_guard_not_applicable_outside_guard() {
+        check_assist_not_applicable(
+            convert_guard_to_nested_match,
+            r#"
+fn main() {
+    match 92 {
+        x if x > 10 => $0false,
+        _ => true
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_guard_to_nested_match,
+            r#"
+fn main() {
+    match 92 {
+        $0x => false,
+        _ => true
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_guard_not_applicable_to_let_guard() {
+        cov_mark::check!(convert_guard_to_nested_match_let_guard);
+        check_assist_not_applicable(
+            convert_guard_to_nested_match,
+            r#"
+fn main() {
+    match 92 {
+        x $0if let 1 = x => false,
+        _ => true
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_guard_target() {
+        check_assist_target(
+            convert_guard_to_nested_match,
+            r#"
+//- minicore: copy
+fn main() {
+    let n = 92;
+    match n {
+        x if x $0> 10 => false,
+        _ => true
+    }
+}
+"#,
+            "if x > 10",
+        );
+    }
+
+    #[test]
+    fn convert_guard_with_expr_body() {
+        check_assist(
+            convert_guard_to_nested_match,
+            r#"
+//- minicore: copy
+fn main() {
+    let n = 92;
+    match n {
+        x $0if x > 10 => false,
+        _ => true
+    }
+}
+"#,
+            r#"
+fn main() {
+    let n = 92;
+    match n {
+        x => match x > 10 {
+            true => false,
+            false => match n {
+                _ => true,
+            }
+        },
+        _ => true
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_guard_with_block_body() {
+        check_assist(
+            convert_guard_to_nested_match,
+            r#"
+//- minicore: copy
+fn main() {
+    let n = 92;
+    match n {
+        x $0if x > 10 => {
+            let y = x + 1;
+            y > 20
+        }
+        1 => false,
+        _ => {
+            let z = 2;
+            z > 1
+        }
+    }
+}
+"#,
+            r#"
+fn main() {
+    let n = 92;
+    match n {
+        x => match x > 10 {
+            true => {
+                let y = x + 1;
+                y > 20
+            }
+            false => match n {
+                1 => false,
+                _ => {
+                    let z = 2;
+                    z > 1
+                }
+            }
+        }
+        1 => false,
+        _ => {
+            let z = 2;
+            z > 1
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_guard_with_multiple_bindings() {
+        check_assist(
+            convert_guard_to_nested_match,
+            r#"
+//- minicore: copy
+fn main() {
+    let pair = (1, 2);
+    match pair {
+        (a, b) if$0 a > b && b != 0 => a / b,
+        (a, _) if a > 0 => a,
+        (_, b) => b,
+    }
+}
+"#,
+            r#"
+fn main() {
+    let pair = (1, 2);
+    match pair {
+        (a, b) => match a > b && b != 0 {
+            true => a / b,
+            false => match pair {
+                (a, _) if a > 0 => a,
+                (_, b) => b,
+            }
+        },
+        (a, _) if a > 0 => a,
+        (_, b) => b,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_guard_not_applicable_without_fall_through() {
+        cov_mark::check!(convert_guard_to_nested_match_no_fall_through);
+        check_assist_not_applicable(
+            convert_guard_to_nested_match,
+            r#"
+//- minicore: option, copy
+fn f(v: Option<i32>) -> i32 {
+    match v {
+        Some(x) $0if x > 0 => x,
+        Some(_) => 0,
+        None => -1,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_guard_not_applicable_to_complex_scrutinee() {
+        cov_mark::check!(convert_guard_to_nested_match_complex_scrutinee);
+        check_assist_not_applicable(
+            convert_guard_to_nested_match,
+            r#"
+fn next() -> i32 { 0 }
+fn f() -> bool {
+    match next() {
+        x $0if x > 10 => false,
+        _ => true
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_guard_not_applicable_to_shadowed_scrutinee() {
+        cov_mark::check!(convert_guard_to_nested_match_shadowed_scrutinee);
+        check_assist_not_applicable(
+            convert_guard_to_nested_match,
+            r#"
+//- minicore: option, copy
+fn f(x: Option<i32>) -> i32 {
+    match x {
+        Some(x) $0if x > 0 => x,
+        _ => 0
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_guard_not_applicable_to_moving_bindings() {
+        cov_mark::check!(convert_guard_to_nested_match_moving_bindings);
+        check_assist_not_applicable(
+            convert_guard_to_nested_match,
+            r#"
+struct S;
+fn f(v: (S, i32)) -> i32 {
+    match v {
+        (s, n) $0if n > 0 => n,
+        _ => 0
+    }
+}
+"#,
+        );
+    }
+}
@@ -112,25 +112,7 @@ pub(crate) fn convert_for_loop_with_for_each(
         "Replace this for loop with `Iterator::for_each`",
         for_loop.syntax().text_range(),
         |builder| {
-            let mut buf = String::new();
-
-            if let Some((expr_behind_ref, method)) =
-                is_ref_and_impls_iter_method(&ctx.sema, &iterable)
-            {
-                // We have either "for x in &col" and col implements a method called iter
-                //             or "for x in &mut col" and col implements a method called iter_mut
-                format_to!(buf, "{expr_behind_ref}.{}()", method.display(ctx.db()));
-            } else if let ast::Expr::RangeExpr(..) = iterable {
-                // range expressions need to be parenthesized for the syntax to be correct
-                format_to!(buf, "({iterable})");
-            } else if impls_core_iter(&ctx.sema, &iterable) {
-                format_to!(buf, "{iterable}");
-            } else if let ast::Expr::RefExpr(_) = iterable {
-                format_to!(buf, "({iterable}).into_iter()");
-            } else {
-                format_to!(buf, "{iterable}.into_iter()");
-            }
-
+            let mut buf = iterator_from_iterable(ctx, &iterable);
             format_to!(buf, ".for_each(|{pat}| {body});");
 
             builder.replace(for_loop.syntax().text_range(), buf)
@@ -138,6 +120,26 @@ pub(crate) fn convert_for_loop_with_for_each(
     )
 }
 
+/// Renders the iterator a `for` loop over `iterable` would walk, as a method receiver.
+pub(crate) fn iterator_from_iterable(ctx: &AssistContext<'_>, iterable: &ast::Expr) -> String {
+    let mut buf = String::new();
+    if let Some((expr_behind_ref, method)) = is_ref_and_impls_iter_method(&ctx.sema, iterable) {
+        // We have either "for x in &col" and col implements a method called iter
+        //             or "for x in &mut col" and col implements a method called iter_mut
+        format_to!(buf, "{expr_behind_ref}.{}()", method.display(ctx.db()));
+    } else if let ast::Expr::RangeExpr(..) = iterable {
+        // range expressions need to be parenthesized for the syntax to be correct
+        format_to!(buf, "({iterable})");
+    } else if impls_core_iter(&ctx.sema, iterable) {
+        format_to!(buf, "{iterable}");
+    } else if let ast::Expr::RefExpr(_) = iterable {
+        format_to!(buf, "({iterable}).into_iter()");
+    } else {
+        format_to!(buf, "{iterable}.into_iter()");
+    }
+    buf
+}
+
 /// If iterable is a reference where the expression behind the reference implements a method
 /// returning an Iterator called iter or iter_mut (depending on the type of reference) then return
 /// the expression behind the reference and the method name
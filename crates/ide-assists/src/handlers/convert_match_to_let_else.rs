@@ -11,7 +11,7 @@ use crate::{
 
 // Assist: convert_match_to_let_else
 //
-// Converts let statement with match initializer to let-else statement.
+// Converts let statement with match or if-let initializer to let-else statement.
 //
 // ```
 // # //- minicore: option
@@ -35,34 +35,70 @@ pub(crate) fn convert_match_to_let_else(acc: &mut Assists, ctx: &AssistContext<'
         return None;
     }
 
-    let Some(ast::Expr::MatchExpr(initializer)) = let_stmt.initializer() else { return None };
-    let initializer_expr = initializer.expr()?;
-
-    let (extracting_arm, diverging_arm) = find_arms(ctx, &initializer)?;
-    if extracting_arm.guard().is_some() {
-        cov_mark::hit!(extracting_arm_has_guard);
-        return None;
-    }
+    let (label, extracting_pat, extracting_expr, initializer_expr, diverging_expr) = match let_stmt
+        .initializer()?
+    {
+        ast::Expr::MatchExpr(initializer) => {
+            let initializer_expr = initializer.expr()?;
+            let (extracting_arm, diverging_arm) = find_arms(ctx, &initializer)?;
+            if extracting_arm.guard().is_some() {
+                cov_mark::hit!(extracting_arm_has_guard);
+                return None;
+            }
+            (
+                "Convert match to let-else",
+                extracting_arm.pat()?,
+                extracting_arm.expr()?,
+                initializer_expr,
+                diverging_arm.expr()?,
+            )
+        }
+        ast::Expr::IfExpr(initializer) => {
+            let ast::Expr::LetExpr(let_expr) = initializer.condition()? else { return None };
+            let then_branch = initializer.then_branch()?;
+            let Some(ast::ElseBranch::Block(else_branch)) = initializer.else_branch() else {
+                return None;
+            };
+            if !is_diverging(ctx, &else_branch.clone().into())? {
+                cov_mark::hit!(non_diverging_if_let);
+                return None;
+            }
+            let extracting_expr = match then_branch.stmt_list()? {
+                stmt_list if stmt_list.statements().next().is_none() => stmt_list.tail_expr()?,
+                _ => {
+                    cov_mark::hit!(extracting_arm_is_not_an_identity_expr);
+                    return None;
+                }
+            };
+            (
+                "Convert if-let to let-else",
+                let_expr.pat()?,
+                extracting_expr,
+                let_expr.expr()?,
+                else_branch.into(),
+            )
+        }
+        _ => return None,
+    };
 
-    let diverging_arm_expr = match diverging_arm.expr()? {
+    let diverging_expr = match diverging_expr {
         ast::Expr::BlockExpr(block) if block.modifier().is_none() && block.label().is_none() => {
             block.to_string()
         }
         other => format!("{{ {other} }}"),
     };
-    let extracting_arm_pat = extracting_arm.pat()?;
-    let extracted_variable_positions = find_extracted_variable(ctx, &extracting_arm)?;
+    let extracted_variable_positions = find_extracted_variable(ctx, &extracting_expr)?;
 
     acc.add(
         AssistId("convert_match_to_let_else", AssistKind::RefactorRewrite),
-        "Convert match to let-else",
+        label,
         let_stmt.syntax().text_range(),
         |builder| {
-            let extracting_arm_pat =
-                rename_variable(&extracting_arm_pat, &extracted_variable_positions, pat);
+            let extracting_pat =
+                rename_variable(&extracting_pat, &extracted_variable_positions, pat);
             builder.replace(
                 let_stmt.syntax().text_range(),
-                format!("let {extracting_arm_pat} = {initializer_expr} else {diverging_arm_expr};"),
+                format!("let {extracting_pat} = {initializer_expr} else {diverging_expr};"),
             )
         },
     )
@@ -81,7 +117,7 @@ fn find_arms(
     let mut extracting = None;
     let mut diverging = None;
     for arm in arms {
-        if ctx.sema.type_of_expr(&arm.expr()?)?.original().is_never() {
+        if is_diverging(ctx, &arm.expr()?)? {
             diverging = Some(arm);
         } else {
             extracting = Some(arm);
@@ -97,9 +133,26 @@ fn find_arms(
     }
 }
 
-// Given an extracting arm, find the extracted variable.
-fn find_extracted_variable(ctx: &AssistContext<'_>, arm: &ast::MatchArm) -> Option<Vec<Name>> {
-    match arm.expr()? {
+// Whether `expr` never evaluates to a value. A block without a tail expression gets its type
+// from the surrounding expression, so we look at its last statement instead.
+fn is_diverging(ctx: &AssistContext<'_>, expr: &ast::Expr) -> Option<bool> {
+    if ctx.sema.type_of_expr(expr)?.original().is_never() {
+        return Some(true);
+    }
+    let ast::Expr::BlockExpr(block) = expr else { return Some(false) };
+    let stmt_list = block.stmt_list()?;
+    if stmt_list.tail_expr().is_some() {
+        return Some(false);
+    }
+    match stmt_list.statements().last() {
+        Some(ast::Stmt::ExprStmt(stmt)) => is_diverging(ctx, &stmt.expr()?),
+        _ => Some(false),
+    }
+}
+
+// Given the expression of an extracting arm or branch, find the extracted variable.
+fn find_extracted_variable(ctx: &AssistContext<'_>, expr: &ast::Expr) -> Option<Vec<Name>> {
+    match expr {
         ast::Expr::PathExpr(path) => {
             let name_ref = path.syntax().descendants().find_map(ast::NameRef::cast)?;
             match NameRefClass::classify(&ctx.sema, &name_ref)? {
@@ -490,6 +543,112 @@ fn f() {
             return
         };
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn if_let_basic_pattern() {
+        check_assist(
+            convert_match_to_let_else,
+            r#"
+//- minicore: option
+fn foo(opt: Option<()>) {
+    let val$0 = if let Some(it) = opt { it } else { return };
+}
+    "#,
+            r#"
+fn foo(opt: Option<()>) {
+    let Some(val) = opt else { return };
+}
+    "#,
+        );
+    }
+
+    #[test]
+    fn if_let_keeps_diverging_block() {
+        check_assist(
+            convert_match_to_let_else,
+            r#"
+//- minicore: option
+fn foo(opt: Option<i32>) {
+    loop {
+        let va$0l = if let Some(it) = opt {
+            it
+        } else {
+            // nothing to do here
+            bar();
+            continue;
+        };
+    }
+}
+fn bar() {}
+    "#,
+            r#"
+fn foo(opt: Option<i32>) {
+    loop {
+        let Some(val) = opt else {
+            // nothing to do here
+            bar();
+            continue;
+        };
+    }
+}
+fn bar() {}
+    "#,
+        );
+    }
+
+    #[test]
+    fn should_not_be_applicable_for_non_diverging_if_let() {
+        cov_mark::check!(non_diverging_if_let);
+        check_assist_not_applicable(
+            convert_match_to_let_else,
+            r#"
+//- minicore: option
+fn foo(opt: Option<()>) {
+    let val$0 = if let Some(it) = opt { it } else { () };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn should_not_be_applicable_if_then_branch_is_not_an_identity_expr() {
+        cov_mark::check_count!(extracting_arm_is_not_an_identity_expr, 2);
+        check_assist_not_applicable(
+            convert_match_to_let_else,
+            r#"
+//- minicore: option
+fn foo(opt: Option<i32>) {
+    let val$0 = if let Some(it) = opt { it + 1 } else { return };
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_match_to_let_else,
+            r#"
+//- minicore: option
+fn foo(opt: Option<i32>) {
+    let val$0 = if let Some(it) = opt {
+        let x = it;
+        x
+    } else {
+        return
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn should_not_be_applicable_for_if_without_let() {
+        check_assist_not_applicable(
+            convert_match_to_let_else,
+            r#"
+fn foo(cond: bool) {
+    let val$0 = if cond { 1 } else { return };
+}
 "#,
         );
     }
@@ -1,9 +1,15 @@
+use std::cmp::Reverse;
+
 use either::Either;
-use ide_db::{defs::Definition, search::FileReference};
+use ide_db::{
+    base_db::{FileRange, SourceDatabaseExt},
+    defs::Definition,
+    search::FileReference,
+};
 use itertools::Itertools;
 use syntax::{
-    ast::{self, AstNode, HasGenericParams, HasVisibility},
-    match_ast, SyntaxKind,
+    ast::{self, AstNode, HasGenericParams, HasName, HasVisibility},
+    SyntaxKind, SyntaxNode,
 };
 
 use crate::{assist_context::SourceChangeBuilder, AssistContext, AssistId, AssistKind, Assists};
@@ -66,14 +72,33 @@ pub(crate) fn convert_named_struct_to_tuple_struct(
         Either::Left(s) => Either::Left(ctx.sema.to_def(s)?),
         Either::Right(v) => Either::Right(ctx.sema.to_def(v)?),
     };
+    let field_names: Vec<_> =
+        record_fields.fields().map(|f| f.name().map(|it| it.text().to_string())).collect();
+
+    let (field_accesses, other_field_refs) = field_references(ctx, record_fields.fields());
+    let records = struct_references(ctx, strukt_def)?;
+    // Fields can't be referenced by name anymore, except in the records that are rewritten.
+    let in_record = |range: &FileRange| records.iter().any(|(record, _)| contains(record, range));
+    if !other_field_refs.iter().all(in_record) {
+        return None;
+    }
+    let updated = records.len() + field_accesses.len();
+    let edits = rewrite_records(ctx, records, field_accesses, &field_names)?;
 
+    let label = match updated {
+        0 => "Convert to tuple struct".to_owned(),
+        1 => "Convert to tuple struct, updating 1 usage".to_owned(),
+        n => format!("Convert to tuple struct, updating {n} usages"),
+    };
     acc.add(
         AssistId("convert_named_struct_to_tuple_struct", AssistKind::RefactorRewrite),
-        "Convert to tuple struct",
+        label,
         strukt.syntax().text_range(),
         |edit| {
-            edit_field_references(ctx, edit, record_fields.fields());
-            edit_struct_references(ctx, edit, strukt_def);
+            for (range, text) in edits {
+                edit.edit_file(range.file_id);
+                edit.replace(range.range, text);
+            }
             edit_struct_def(ctx, edit, &strukt, record_fields);
         },
     )
@@ -131,30 +156,41 @@ fn edit_struct_def(
     }
 }
 
-fn edit_struct_references(
+/// Returns the record expressions and patterns of the struct, or `None` if one of them can't be
+/// translated to the tuple syntax.
+fn struct_references(
     ctx: &AssistContext<'_>,
-    edit: &mut SourceChangeBuilder,
     strukt: Either<hir::Struct, hir::Variant>,
-) {
+) -> Option<Vec<(FileRange, Either<ast::RecordPat, ast::RecordExpr>)>> {
     let strukt_def = match strukt {
         Either::Left(s) => Definition::Adt(hir::Adt::Struct(s)),
         Either::Right(v) => Definition::Variant(v),
     };
     let usages = strukt_def.usages(&ctx.sema).include_self_refs().all();
 
-    for (file_id, refs) in usages {
-        edit.edit_file(file_id);
+    let mut res = Vec::new();
+    for (_, refs) in usages {
         for r in refs {
-            process_struct_name_reference(ctx, r, edit);
+            let Some(record) = process_struct_name_reference(r) else { continue };
+            // When we failed to get the original range for the whole struct expression/pattern
+            // node, we can't provide any reasonable edit. Leave it untouched.
+            let Some(file_range) = ctx.sema.original_range_opt(record.syntax()) else { continue };
+            if let Either::Right(record_expr) = &record {
+                // Struct update syntax has no tuple counterpart.
+                let field_list = record_expr.record_expr_field_list()?;
+                if field_list.spread().is_some() || field_list.dotdot_token().is_some() {
+                    return None;
+                }
+            }
+            res.push((file_range, record));
         }
     }
+    Some(res)
 }
 
 fn process_struct_name_reference(
-    ctx: &AssistContext<'_>,
     r: FileReference,
-    edit: &mut SourceChangeBuilder,
-) -> Option<()> {
+) -> Option<Either<ast::RecordPat, ast::RecordExpr>> {
     // First check if it's the last semgnet of a path that directly belongs to a record
     // expression/pattern.
     let name_ref = r.name.as_name_ref()?;
@@ -169,50 +205,17 @@ fn process_struct_name_reference(
         return None;
     }
 
-    let parent = full_path.syntax().parent()?;
-    match_ast! {
-        match parent {
-            ast::RecordPat(record_struct_pat) => {
-                // When we failed to get the original range for the whole struct expression node,
-                // we can't provide any reasonable edit. Leave it untouched.
-                let file_range = ctx.sema.original_range_opt(record_struct_pat.syntax())?;
-                edit.replace(
-                    file_range.range,
-                    ast::make::tuple_struct_pat(
-                        record_struct_pat.path()?,
-                        record_struct_pat
-                            .record_pat_field_list()?
-                            .fields()
-                            .filter_map(|pat| pat.pat())
-                    )
-                    .to_string()
-                );
-            },
-            ast::RecordExpr(record_expr) => {
-                // When we failed to get the original range for the whole struct pattern node,
-                // we can't provide any reasonable edit. Leave it untouched.
-                let file_range = ctx.sema.original_range_opt(record_expr.syntax())?;
-                let path = record_expr.path()?;
-                let args = record_expr
-                    .record_expr_field_list()?
-                    .fields()
-                    .filter_map(|f| f.expr())
-                    .join(", ");
-
-                edit.replace(file_range.range, format!("{path}({args})"));
-            },
-            _ => {}
-        }
-    }
-
-    Some(())
+    <Either<ast::RecordPat, ast::RecordExpr>>::cast(full_path.syntax().parent()?)
 }
 
-fn edit_field_references(
+/// Returns the edits turning the `.field` accesses into `.0` etc., and the ranges of all other
+/// references to the fields.
+fn field_references(
     ctx: &AssistContext<'_>,
-    edit: &mut SourceChangeBuilder,
     fields: impl Iterator<Item = ast::RecordField>,
-) {
+) -> (Vec<(FileRange, String)>, Vec<FileRange>) {
+    let mut accesses = Vec::new();
+    let mut others = Vec::new();
     for (index, field) in fields.enumerate() {
         let field = match ctx.sema.to_def(&field) {
             Some(it) => it,
@@ -221,22 +224,144 @@ fn edit_field_references(
         let def = Definition::Field(field);
         let usages = def.usages(&ctx.sema).all();
         for (file_id, refs) in usages {
-            edit.edit_file(file_id);
             for r in refs {
-                if let Some(name_ref) = r.name.as_name_ref() {
-                    // Only edit the field reference if it's part of a `.field` access
-                    if name_ref.syntax().parent().and_then(ast::FieldExpr::cast).is_some() {
-                        edit.replace(r.range, index.to_string());
-                    }
+                let range = FileRange { file_id, range: r.range };
+                let is_field_access = r
+                    .name
+                    .as_name_ref()
+                    .and_then(|name_ref| name_ref.syntax().parent())
+                    .map_or(false, |it| ast::FieldExpr::can_cast(it.kind()));
+                if is_field_access {
+                    accesses.push((range, index.to_string()));
+                } else {
+                    others.push(range);
                 }
             }
         }
     }
+    (accesses, others)
+}
+
+/// Adds the rewritten records to `edits`, and returns the outermost edits, or `None` if one of the
+/// records can't be rewritten.
+fn rewrite_records(
+    ctx: &AssistContext<'_>,
+    mut records: Vec<(FileRange, Either<ast::RecordPat, ast::RecordExpr>)>,
+    mut edits: Vec<(FileRange, String)>,
+    field_names: &[Option<String>],
+) -> Option<Vec<(FileRange, String)>> {
+    // Records can contain field accesses and other records, so they are rewritten innermost
+    // first, on top of the edits inside of them.
+    records.sort_by_key(|(range, _)| range.range.len());
+    for (range, record) in records {
+        let text = rewrite_record(ctx, &record, field_names, &edits)?;
+        edits.push((range, text));
+    }
+    Some(outermost(&edits).cloned().collect())
+}
+
+fn rewrite_record(
+    ctx: &AssistContext<'_>,
+    record: &Either<ast::RecordPat, ast::RecordExpr>,
+    field_names: &[Option<String>],
+    edits: &[(FileRange, String)],
+) -> Option<String> {
+    let text_of = |node: &SyntaxNode| {
+        let range = ctx.sema.original_range_opt(node)?;
+        Some(apply_edits(ctx, range, edits))
+    };
+    let field_index = |name: String| field_names.iter().position(|it| it.as_ref() == Some(&name));
+    match record {
+        Either::Left(record_struct_pat) => {
+            let field_list = record_struct_pat.record_pat_field_list()?;
+
+            // The fields are positional now, so the subpatterns have to be put in declaration
+            // order, with the missing ones filled up by `_` or `..`.
+            let mut pats = vec![None; field_names.len()];
+            for field in field_list.fields() {
+                let index = field_index(field.field_name()?.to_string())?;
+                pats[index] = Some(text_of(field.pat()?.syntax())?);
+            }
+            let mentioned = match field_list.rest_pat() {
+                Some(_) => pats.iter().rposition(Option::is_some).map_or(0, |it| it + 1),
+                None => pats.len(),
+            };
+            let mut pats: Vec<String> = pats
+                .into_iter()
+                .take(mentioned)
+                .map(|pat| pat.unwrap_or_else(|| "_".to_owned()))
+                .collect();
+            if field_list.rest_pat().is_some() && mentioned < field_names.len() {
+                pats.push("..".to_owned());
+            }
+
+            let path = text_of(record_struct_pat.path()?.syntax())?;
+            Some(format!("{path}({})", pats.join(", ")))
+        }
+        Either::Right(record_expr) => {
+            let fields = record_expr
+                .record_expr_field_list()?
+                .fields()
+                .map(|f| Some((field_index(f.field_name()?.to_string())?, f.expr()?)))
+                .collect::<Option<Vec<_>>>()?;
+
+            // The field expressions are evaluated in the order they are written in, so they can
+            // only be put in declaration order if that can't be observed.
+            let in_order = fields.iter().tuple_windows().all(|((a, _), (b, _))| a < b);
+            if !in_order && !fields.iter().all(|(_, expr)| is_side_effect_free(expr)) {
+                cov_mark::hit!(convert_named_struct_to_tuple_struct_reordered_side_effects);
+                return None;
+            }
+            let args = fields
+                .into_iter()
+                .sorted_by_key(|&(index, _)| index)
+                .map(|(_, expr)| text_of(expr.syntax()))
+                .collect::<Option<Vec<_>>>()?
+                .join(", ");
+
+            let path = text_of(record_expr.path()?.syntax())?;
+            Some(format!("{path}({args})"))
+        }
+    }
+}
+
+/// Whether evaluating `expr` has no effects that could depend on its order relative to other
+/// expressions, i.e. it is a literal or a place expression made of a path and field accesses.
+fn is_side_effect_free(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::Literal(_) | ast::Expr::PathExpr(_) => true,
+        ast::Expr::FieldExpr(it) => it.expr().map_or(false, |it| is_side_effect_free(&it)),
+        ast::Expr::ParenExpr(it) => it.expr().map_or(false, |it| is_side_effect_free(&it)),
+        ast::Expr::RefExpr(it) => it.expr().map_or(false, |it| is_side_effect_free(&it)),
+        _ => false,
+    }
+}
+
+/// Returns the text at `range` with the edits inside of it applied.
+fn apply_edits(ctx: &AssistContext<'_>, range: FileRange, edits: &[(FileRange, String)]) -> String {
+    let file_text = ctx.db().file_text(range.file_id);
+    let mut text = file_text[range.range].to_owned();
+    let inner: Vec<_> = edits.iter().filter(|(it, _)| contains(&range, it)).cloned().collect();
+    for (it, replacement) in outermost(&inner).sorted_by_key(|(it, _)| Reverse(it.range.start())) {
+        let it = it.range - range.range.start();
+        text.replace_range(std::ops::Range::<usize>::from(it), replacement);
+    }
+    text
+}
+
+fn outermost(edits: &[(FileRange, String)]) -> impl Iterator<Item = &(FileRange, String)> {
+    edits.iter().filter(|(range, _)| {
+        !edits.iter().any(|(other, _)| other != range && contains(other, range))
+    })
+}
+
+fn contains(outer: &FileRange, inner: &FileRange) -> bool {
+    outer.file_id == inner.file_id && outer.range.contains_range(inner.range)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_not_applicable};
+    use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
 
     use super::*;
 
@@ -975,6 +1100,137 @@ impl HasAssoc for Struct {
         let Self::Assoc { value } = a;
     }
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_fields_in_declaration_order() {
+        check_assist_by_label(
+            convert_named_struct_to_tuple_struct,
+            r#"
+struct $0P { pub x: i32, pub y: i32, pub z: i32 }
+
+fn f(p: P) -> P {
+    let P { z, x: a, y: _ } = p;
+    P { y: a, z, x: p.y }
+}
+"#,
+            r#"
+struct P(pub i32, pub i32, pub i32);
+
+fn f(p: P) -> P {
+    let P(a, _, z) = p;
+    P(p.1, a, z)
+}
+"#,
+            "Convert to tuple struct, updating 3 usages",
+        );
+    }
+
+    #[test]
+    fn convert_pattern_with_rest() {
+        check_assist(
+            convert_named_struct_to_tuple_struct,
+            r#"
+struct $0P { x: i32, y: i32, z: i32 }
+
+fn f(p: &P) -> i32 {
+    let P { y, .. } = p;
+    let P { z, .. } = p;
+    let P { .. } = p;
+    y + z
+}
+"#,
+            r#"
+struct P(i32, i32, i32);
+
+fn f(p: &P) -> i32 {
+    let P(_, y, ..) = p;
+    let P(_, _, z) = p;
+    let P(..) = p;
+    y + z
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_struct_update_syntax() {
+        check_assist_not_applicable(
+            convert_named_struct_to_tuple_struct,
+            r#"
+struct $0P { x: i32, y: i32 }
+
+fn f(p: P) -> P {
+    P { x: 0, ..p }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_record_through_alias() {
+        check_assist_not_applicable(
+            convert_named_struct_to_tuple_struct,
+            r#"
+struct $0P { x: i32 }
+type Q = P;
+
+fn f() -> Q {
+    Q { x: 0 }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_reordering_side_effects() {
+        cov_mark::check!(convert_named_struct_to_tuple_struct_reordered_side_effects);
+        check_assist_not_applicable(
+            convert_named_struct_to_tuple_struct,
+            r#"
+struct $0P { x: i32, y: i32 }
+
+fn next(counter: &mut i32) -> i32 {
+    *counter += 1;
+    *counter
+}
+
+fn f(counter: &mut i32) -> P {
+    P { y: next(counter), x: next(counter) }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_side_effects_in_declaration_order() {
+        check_assist(
+            convert_named_struct_to_tuple_struct,
+            r#"
+struct $0P { x: i32, y: i32 }
+
+fn next(counter: &mut i32) -> i32 {
+    *counter += 1;
+    *counter
+}
+
+fn f(counter: &mut i32) -> P {
+    P { x: next(counter), y: next(counter) }
+}
+"#,
+            r#"
+struct P(i32, i32);
+
+fn next(counter: &mut i32) -> i32 {
+    *counter += 1;
+    *counter
+}
+
+fn f(counter: &mut i32) -> P {
+    P(next(counter), next(counter))
+}
 "#,
         );
     }
@@ -0,0 +1,548 @@
+use hir::{PathResolution, Semantics};
+use ide_db::RootDatabase;
+use itertools::Itertools;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasArgList, HasLoopBody, HasName},
+    match_ast, SyntaxNode, TextRange, WalkEvent,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_recursion_to_explicit_stack
+//
+// Converts a self-recursive function into a loop over an explicit stack of the arguments of the
+// recursive calls. The calls of one iteration are pushed in reverse, so that they are still
+// processed in source order. Results of recursive calls can't be translated automatically and are
+// left as `todo!()`.
+//
+// ```
+// struct Node { children: Vec<Node> }
+// fn visit$0(node: &Node) {
+//     for child in &node.children {
+//         visit(child);
+//     }
+// }
+// ```
+// ->
+// ```
+// struct Node { children: Vec<Node> }
+// fn visit(node: &Node) {
+//     let mut stack = vec![node];
+//     while let Some(node) = stack.pop() {
+//         let mut pending = Vec::new();
+//         for child in &node.children {
+//             pending.push(child);
+//         }
+//         stack.extend(pending.into_iter().rev());
+//     }
+// }
+// ```
+pub(crate) fn convert_recursion_to_explicit_stack(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let fn_ = ctx.find_node_at_offset::<ast::Fn>()?;
+    let body = fn_.body()?;
+    if body.syntax().text_range().contains_inclusive(ctx.offset()) || fn_.async_token().is_some() {
+        return None;
+    }
+    let stmt_list = body.stmt_list()?;
+    // Early exits would leave the loop instead of only the current call.
+    if body
+        .syntax()
+        .descendants()
+        .any(|it| ast::ReturnExpr::can_cast(it.kind()) || ast::TryExpr::can_cast(it.kind()))
+    {
+        cov_mark::hit!(convert_recursion_to_explicit_stack_early_exit);
+        return None;
+    }
+    let db = ctx.db();
+    let def = ctx.sema.to_def(&fn_)?;
+
+    let param_list = fn_.param_list()?;
+    if param_list.self_param().is_some() {
+        return None;
+    }
+    let mut names = Vec::new();
+    for param in param_list.params() {
+        let ast::Pat::IdentPat(pat) = param.pat()? else { return None };
+        if pat.ref_token().is_some() || pat.pat().is_some() {
+            return None;
+        }
+        names.push((pat.name()?.to_string(), pat.to_string()));
+    }
+    // Functions taking callbacks are higher-order, and usually can't be turned into a loop.
+    if names.is_empty()
+        || def.assoc_fn_params(db).iter().any(|param| {
+            let ty = param.ty().strip_references();
+            ty.as_callable(db).is_some() || ty.impls_fnonce(db)
+        })
+    {
+        return None;
+    }
+
+    let calls = recursive_calls(&ctx.sema, def, body.syntax())?;
+    if calls.is_empty()
+        || calls.iter().tuple_combinations().any(|(a, b)| {
+            let (a, b) = (a.syntax().text_range(), b.syntax().text_range());
+            a.contains_range(b) || b.contains_range(a)
+        })
+    {
+        return None;
+    }
+
+    let returns_unit = def.ret_type(db).is_unit();
+    let result_is_unused = |call: &ast::CallExpr| {
+        returns_unit
+            || call.syntax().parent().map_or(false, |parent| ast::ExprStmt::can_cast(parent.kind()))
+    };
+    // Code running after a recursive call would now run before the call is processed. This is
+    // fine if the result is used, as the call is left as `todo!()` in that case.
+    if calls.iter().any(|call| result_is_unused(call) && runs_code_after(call, &body, &calls)) {
+        cov_mark::hit!(convert_recursion_to_explicit_stack_code_after_call);
+        return None;
+    }
+    let pack = |items: Vec<String>| match &items[..] {
+        [item] => item.clone(),
+        _ => format!("({})", items.join(", ")),
+    };
+
+    acc.add(
+        AssistId("convert_recursion_to_explicit_stack", AssistKind::RefactorRewrite),
+        "Convert recursion to an explicit stack",
+        fn_.syntax().text_range(),
+        |builder| {
+            let mut edits = Vec::new();
+            for call in &calls {
+                let args = call
+                    .arg_list()
+                    .into_iter()
+                    .flat_map(|it| it.args())
+                    .map(|arg| arg.syntax().text().to_string())
+                    .collect();
+                let push = format!("pending.push({})", pack(args));
+                let replacement = if result_is_unused(call) {
+                    push
+                } else {
+                    format!("{{ {push}; todo!(\"use the result of `{call}`\") }}")
+                };
+                edits.push((call.syntax().text_range(), replacement));
+            }
+            // The loop body can't evaluate to the value returned by the function.
+            if let Some(tail) = stmt_list.tail_expr().filter(|_| !returns_unit) {
+                let range = tail.syntax().text_range();
+                edits.push((TextRange::empty(range.start()), "let _result = ".to_owned()));
+                edits.push((TextRange::empty(range.end()), ";".to_owned()));
+            }
+
+            let inner = match (stmt_list.l_curly_token(), stmt_list.r_curly_token()) {
+                (Some(l), Some(r)) => TextRange::new(l.text_range().end(), r.text_range().start()),
+                _ => return,
+            };
+            let inner_text = apply_edits(fn_.syntax(), inner, edits);
+            let indent = IndentLevel::from_node(fn_.syntax());
+            let body_indent = indent + 2;
+            let loop_indent = indent + 1;
+            // The pending calls are pushed in reverse, so that the first one is popped first.
+            let inner_text = format!(
+                "let mut pending = Vec::new();\n{loop_indent}{}\n{loop_indent}stack.extend(pending.into_iter().rev());",
+                inner_text.trim(),
+            );
+            let inner_text = inner_text
+                .lines()
+                .map(|line| if line.trim().is_empty() { String::new() } else { format!("    {line}") })
+                .join("\n");
+
+            let init = pack(names.iter().map(|(name, _)| name.clone()).collect());
+            let pat = pack(names.iter().map(|(_, pat)| pat.clone()).collect());
+            let mut buf = format!(
+                "{{\n{loop_indent}let mut stack = vec![{init}];\n{loop_indent}while let Some({pat}) = stack.pop() {{\n{body_indent}{}\n{loop_indent}}}\n",
+                inner_text.trim_start(),
+            );
+            if !returns_unit {
+                buf.push_str(&format!("{loop_indent}todo!(\"return the result\")\n"));
+            }
+            buf.push_str(&format!("{indent}}}"));
+            builder.replace(body.syntax().text_range(), buf);
+        },
+    )
+}
+
+/// Collects the direct calls of `def` in `body`, or returns `None` if the function is used in
+/// another way: as a value, from a closure or through another, mutually recursive function.
+fn recursive_calls(
+    sema: &Semantics<'_, RootDatabase>,
+    def: hir::Function,
+    body: &SyntaxNode,
+) -> Option<Vec<ast::CallExpr>> {
+    let resolve = |path: &ast::PathExpr| match sema.resolve_path(&path.path()?)? {
+        PathResolution::Def(hir::ModuleDef::Function(it)) => Some(it),
+        _ => None,
+    };
+    let krate = def.module(sema.db).krate();
+
+    let mut calls = Vec::new();
+    let mut preorder = body.preorder();
+    while let Some(event) = preorder.next() {
+        let WalkEvent::Enter(node) = event else { continue };
+        if node != *body && ast::Item::can_cast(node.kind()) {
+            preorder.skip_subtree();
+            continue;
+        }
+        let callee = match_ast! {
+            match node {
+                ast::PathExpr(path) => resolve(&path),
+                ast::MethodCallExpr(call) => sema.resolve_method_call(&call),
+                _ => None,
+            }
+        };
+        let Some(callee) = callee else { continue };
+        if callee != def {
+            if callee.module(sema.db).krate() == krate && calls_function(sema, callee, def) {
+                return None;
+            }
+            continue;
+        }
+        let call = node.parent().and_then(ast::CallExpr::cast)?;
+        if call.expr()?.syntax() != &node
+            || node
+                .ancestors()
+                .take_while(|it| it != body)
+                .any(|it| ast::ClosureExpr::can_cast(it.kind()))
+        {
+            return None;
+        }
+        calls.push(call);
+    }
+    Some(calls)
+}
+
+/// Whether code other than the recursive `calls` can run after `call` in the same invocation of
+/// the function.
+fn runs_code_after(call: &ast::CallExpr, body: &ast::BlockExpr, calls: &[ast::CallExpr]) -> bool {
+    let loop_body_only_calls = |body: Option<ast::BlockExpr>| {
+        body.map_or(false, |it| only_recursive_calls(&it.into(), calls))
+    };
+    let mut prev = call.syntax().clone();
+    for node in call.syntax().ancestors().skip(1).take_while(|it| it != body.syntax()) {
+        let is_transparent = match_ast! {
+            match (node.clone()) {
+                ast::StmtList(it) => {
+                    let mut after = it
+                        .statements()
+                        .map(|stmt| stmt.syntax().clone())
+                        .chain(it.tail_expr().map(|it| it.syntax().clone()))
+                        .skip_while(|it| *it != prev)
+                        .skip(1);
+                    after.all(|it| {
+                        let expr = match ast::Stmt::cast(it.clone()) {
+                            Some(ast::Stmt::ExprStmt(stmt)) => stmt.expr(),
+                            Some(_) => None,
+                            None => ast::Expr::cast(it),
+                        };
+                        expr.map_or(false, |it| only_recursive_calls(&it, calls))
+                    })
+                },
+                // Code before the call in the loop body runs after it in the next iteration.
+                ast::ForExpr(it) => loop_body_only_calls(it.loop_body()),
+                ast::WhileExpr(it) => loop_body_only_calls(it.loop_body()),
+                ast::LoopExpr(it) => loop_body_only_calls(it.loop_body()),
+                ast::ExprStmt(_) => true,
+                ast::BlockExpr(_) => true,
+                ast::IfExpr(_) => true,
+                ast::MatchArm(_) => true,
+                ast::MatchArmList(_) => true,
+                ast::MatchExpr(_) => true,
+                _ => false,
+            }
+        };
+        if !is_transparent {
+            return true;
+        }
+        prev = node;
+    }
+    false
+}
+
+/// Whether `expr` does nothing but (conditionally) make some of the recursive `calls`.
+fn only_recursive_calls(expr: &ast::Expr, calls: &[ast::CallExpr]) -> bool {
+    let all_calls = |stmt_list: Option<ast::StmtList>| {
+        stmt_list.map_or(false, |it| {
+            it.statements().all(|stmt| match stmt {
+                ast::Stmt::ExprStmt(stmt) => {
+                    stmt.expr().map_or(false, |it| only_recursive_calls(&it, calls))
+                }
+                _ => false,
+            }) && it.tail_expr().map_or(true, |it| only_recursive_calls(&it, calls))
+        })
+    };
+    match expr {
+        ast::Expr::CallExpr(call) => calls.contains(call),
+        ast::Expr::BlockExpr(block) => block.modifier().is_none() && all_calls(block.stmt_list()),
+        ast::Expr::ForExpr(it) => all_calls(it.loop_body().and_then(|it| it.stmt_list())),
+        ast::Expr::WhileExpr(it) => all_calls(it.loop_body().and_then(|it| it.stmt_list())),
+        ast::Expr::IfExpr(it) => {
+            all_calls(it.then_branch().and_then(|it| it.stmt_list()))
+                && it.else_branch().map_or(true, |it| match it {
+                    ast::ElseBranch::Block(it) => all_calls(it.stmt_list()),
+                    ast::ElseBranch::IfExpr(it) => only_recursive_calls(&it.into(), calls),
+                })
+        }
+        _ => false,
+    }
+}
+
+fn calls_function(
+    sema: &Semantics<'_, RootDatabase>,
+    caller: hir::Function,
+    callee: hir::Function,
+) -> bool {
+    let Some(body) = sema.source(caller).and_then(|it| it.value.body()) else { return false };
+    body.syntax().descendants().any(|node| {
+        let called = match_ast! {
+            match node {
+                ast::PathExpr(path) => match path.path().and_then(|it| sema.resolve_path(&it)) {
+                    Some(PathResolution::Def(hir::ModuleDef::Function(it))) => Some(it),
+                    _ => None,
+                },
+                ast::MethodCallExpr(call) => sema.resolve_method_call(&call),
+                _ => None,
+            }
+        };
+        called == Some(callee)
+    })
+}
+
+/// Renders the text of `range` within `node` with the non-overlapping `edits` applied.
+fn apply_edits(node: &SyntaxNode, range: TextRange, mut edits: Vec<(TextRange, String)>) -> String {
+    edits.sort_by_key(|(range, _)| (range.start(), range.end()));
+    let text = node.text().to_string();
+    let offset = node.text_range().start();
+    let slice = |range: TextRange| {
+        let range = range - offset;
+        text[std::ops::Range::<usize>::from(range)].to_owned()
+    };
+    let mut buf = String::new();
+    let mut pos = range.start();
+    for (edit_range, replacement) in edits {
+        buf.push_str(&slice(TextRange::new(pos, edit_range.start())));
+        buf.push_str(&replacement);
+        pos = edit_range.end();
+    }
+    buf.push_str(&slice(TextRange::new(pos, range.end())));
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn unit_function_with_several_params() {
+        check_assist(
+            convert_recursion_to_explicit_stack,
+            r#"
+struct Node { value: u32, children: Vec<Node> }
+fn print_all$0(node: &Node, mut depth: usize) {
+    depth += 1;
+    if node.value > 0 {
+        print_all(node, depth)
+    }
+
+    for child in &node.children {
+        print_all(child, depth + 1);
+    }
+}
+"#,
+            r#"
+struct Node { value: u32, children: Vec<Node> }
+fn print_all(node: &Node, mut depth: usize) {
+    let mut stack = vec![(node, depth)];
+    while let Some((node, mut depth)) = stack.pop() {
+        let mut pending = Vec::new();
+        depth += 1;
+        if node.value > 0 {
+            pending.push((node, depth))
+        }
+
+        for child in &node.children {
+            pending.push((child, depth + 1));
+        }
+        stack.extend(pending.into_iter().rev());
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn results_of_recursive_calls_are_left_as_todo() {
+        check_assist(
+            convert_recursion_to_explicit_stack,
+            r#"
+struct Node { value: u32, children: Vec<Node> }
+fn $0sum(node: &Node) -> u32 {
+    let mut total = node.value;
+    for child in &node.children {
+        total += sum(child);
+    }
+    total
+}
+"#,
+            r#"
+struct Node { value: u32, children: Vec<Node> }
+fn sum(node: &Node) -> u32 {
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        let mut pending = Vec::new();
+        let mut total = node.value;
+        for child in &node.children {
+            total += { pending.push(child); todo!("use the result of `sum(child)`") };
+        }
+        let _result = total;
+        stack.extend(pending.into_iter().rev());
+    }
+    todo!("return the result")
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn nested_in_module() {
+        check_assist(
+            convert_recursion_to_explicit_stack,
+            r#"
+mod m {
+    fn count$0(n: u32) -> u32 { if n == 0 { 0 } else { count(n - 1) + 1 } }
+}
+"#,
+            r#"
+mod m {
+    fn count(n: u32) -> u32 {
+        let mut stack = vec![n];
+        while let Some(n) = stack.pop() {
+            let mut pending = Vec::new();
+            let _result = if n == 0 { 0 } else { { pending.push(n - 1); todo!("use the result of `count(n - 1)`") } + 1 };
+            stack.extend(pending.into_iter().rev());
+        }
+        todo!("return the result")
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_recursion() {
+        check_assist_not_applicable(
+            convert_recursion_to_explicit_stack,
+            r#"
+fn other(n: u32) {}
+fn f$0(n: u32) { other(n) }
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_mutual_recursion() {
+        check_assist_not_applicable(
+            convert_recursion_to_explicit_stack,
+            r#"
+fn is_odd(n: u32) -> bool { n != 0 && is_even$0(n - 1) }
+fn is_even(n: u32) -> bool { n == 0 || is_odd(n - 1) || is_even(n - 2) }
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_higher_order_use() {
+        check_assist_not_applicable(
+            convert_recursion_to_explicit_stack,
+            r#"
+fn apply(f: fn(u32), n: u32) { f(n) }
+fn f$0(n: u32) { apply(f, n - 1) }
+"#,
+        );
+        check_assist_not_applicable(
+            convert_recursion_to_explicit_stack,
+            r#"
+//- minicore: fn
+fn walk$0(n: u32, visit: &impl Fn(u32)) { visit(n); walk(n - 1, visit) }
+"#,
+        );
+        check_assist_not_applicable(
+            convert_recursion_to_explicit_stack,
+            r#"
+//- minicore: fn
+fn walk$0(n: u32) { let g = || walk(n - 1); g() }
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_body() {
+        check_assist_not_applicable(
+            convert_recursion_to_explicit_stack,
+            r#"
+fn f(n: u32) { f$0(n - 1) }
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_early_exit() {
+        cov_mark::check_count!(convert_recursion_to_explicit_stack_early_exit, 2);
+        check_assist_not_applicable(
+            convert_recursion_to_explicit_stack,
+            r#"
+fn f$0(n: u32) {
+    if n == 0 {
+        return;
+    }
+    f(n - 1);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_recursion_to_explicit_stack,
+            r#"
+//- minicore: option
+fn f$0(n: Option<u32>) -> Option<()> {
+    f(Some(n? - 1));
+    None
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_code_after_call() {
+        cov_mark::check_count!(convert_recursion_to_explicit_stack_code_after_call, 2);
+        check_assist_not_applicable(
+            convert_recursion_to_explicit_stack,
+            r#"
+fn print(n: u32) {}
+fn f$0(n: u32) {
+    f(n - 1);
+    print(n);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_recursion_to_explicit_stack,
+            r#"
+struct Node { value: u32, children: Vec<Node> }
+fn print(n: u32) {}
+fn visit$0(node: &Node) {
+    for child in &node.children {
+        print(child.value);
+        visit(child);
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,531 @@
+use hir::{ModuleDef, PathResolution, ScopeDef};
+use ide_db::{
+    famous_defs::FamousDefs,
+    helpers::mod_path_to_ast,
+    imports::insert_use::{insert_use, ImportScope},
+    syntax_helpers::node_ext::{for_each_tail_expr, walk_expr},
+};
+use syntax::{
+    ast::{self, make, AstNode, Expr, HasArgList},
+    ted,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_result_to_anyhow
+//
+// Converts a function returning `Result<T, E>` to return `anyhow::Result<T>`, converting the
+// returned errors.
+//
+// ```
+// # //- minicore: result
+// # //- /main.rs crate:main deps:anyhow
+// struct ParseError;
+//
+// fn parse(text: &str) -> Result<u32, ParseError>$0 {
+//     if text.is_empty() {
+//         return Err(ParseError);
+//     }
+//     Ok(0)
+// }
+// # //- /anyhow.rs crate:anyhow
+// # pub struct Error;
+// # pub type Result<T, E = Error> = core::result::Result<T, E>;
+// ```
+// ->
+// ```
+// use anyhow::Result;
+//
+// struct ParseError;
+//
+// fn parse(text: &str) -> Result<u32> {
+//     if text.is_empty() {
+//         return Err(ParseError.into());
+//     }
+//     Ok(0)
+// }
+// ```
+pub(crate) fn convert_result_to_anyhow(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let ret_type = ctx.find_node_at_offset::<ast::RetType>()?;
+    let fn_ = ast::Fn::cast(ret_type.syntax().parent()?)?;
+    let body = fn_.body()?;
+    let ast::Type::PathType(type_ref) = ret_type.ty()? else { return None };
+    let path = type_ref.path()?;
+    let [ok_ty, err_ty] = type_args(&path)?;
+
+    let scope = ctx.sema.scope(fn_.syntax())?;
+    let result_enum = FamousDefs(&ctx.sema, scope.krate()).core_result_Result()?;
+    let anyhow = Anyhow::find(ctx, scope.krate())?;
+    // With `anyhow::Result` imported, `Result<T, E>` names the alias with an explicit error.
+    match ctx.sema.resolve_path(&path)? {
+        PathResolution::Def(ModuleDef::Adt(hir::Adt::Enum(it))) if it == result_enum => (),
+        PathResolution::Def(ModuleDef::TypeAlias(it)) if it == anyhow.result => (),
+        _ => return None,
+    }
+    let err = ctx.sema.resolve_type(&err_ty)?;
+    if err.as_adt() == Some(hir::Adt::Struct(anyhow.error)) {
+        return None;
+    }
+
+    // Code inspecting the concrete error would lose the information `anyhow` erases.
+    let matches_error = body.syntax().descendants().filter_map(ast::Pat::cast).any(|pat| {
+        !matches!(pat, ast::Pat::IdentPat(ref it) if it.pat().is_none())
+            && !matches!(pat, ast::Pat::WildcardPat(_))
+            && ctx.sema.type_of_pat(&pat).map_or(false, |ty| ty.original == err)
+    });
+    if matches_error {
+        return None;
+    }
+
+    let ret_ty = ctx.sema.resolve_type(&ast::Type::PathType(type_ref.clone()))?;
+    let mut returned = Vec::new();
+    collect_returned_exprs(&ast::Expr::BlockExpr(body), &mut returned);
+    let mut errors = Vec::new();
+    let mut results = Vec::new();
+    for expr in returned {
+        match result_variant(ctx, result_enum, &expr) {
+            Some((name, arg)) if name == "Err" => errors.push(arg),
+            Some(_) => (),
+            None if ctx.sema.type_of_expr(&expr).map_or(false, |ty| ty.original == ret_ty) => {
+                results.push(expr)
+            }
+            None => (),
+        }
+    }
+
+    // `Result` might already name the alias, or be free to import it.
+    let (needs_import, result_path) =
+        match scope.speculative_resolve(&make::ext::ident_path("Result")) {
+            Some(PathResolution::Def(ModuleDef::TypeAlias(it))) if it == anyhow.result => {
+                (false, "Result")
+            }
+            Some(PathResolution::Def(ModuleDef::Adt(hir::Adt::Enum(it)))) if it == result_enum => {
+                (true, "Result")
+            }
+            _ => (false, "anyhow::Result"),
+        };
+    let import_scope = ImportScope::find_insert_use_container(fn_.syntax(), &ctx.sema);
+
+    acc.add(
+        AssistId("convert_result_to_anyhow", AssistKind::RefactorRewrite),
+        "Convert to `anyhow::Result`",
+        type_ref.syntax().text_range(),
+        |builder| {
+            let errors: Vec<_> = errors.into_iter().map(|it| builder.make_mut(it)).collect();
+            let results: Vec<_> = results.into_iter().map(|it| builder.make_mut(it)).collect();
+            let type_ref = builder.make_mut(type_ref);
+            let import_scope = import_scope.filter(|_| needs_import).map(|it| match it {
+                ImportScope::File(it) => ImportScope::File(builder.make_mut(it)),
+                ImportScope::Module(it) => ImportScope::Module(builder.make_mut(it)),
+                ImportScope::Block(it) => ImportScope::Block(builder.make_mut(it)),
+            });
+
+            // `?` keeps working through `anyhow::Error`'s blanket `From` impl, but values
+            // returned directly need an explicit conversion.
+            for arg in errors {
+                let receiver =
+                    if needs_parens(&arg) { make::expr_paren(arg.clone()) } else { arg.clone() };
+                let converted =
+                    make::expr_method_call(receiver, make::name_ref("into"), make::arg_list(None));
+                ted::replace(arg.syntax(), converted.clone_for_update().syntax());
+            }
+            for expr in results {
+                let ok = make::expr_call(
+                    make::expr_path(make::ext::ident_path("Ok")),
+                    make::arg_list(Some(make::expr_try(expr.clone()))),
+                );
+                ted::replace(expr.syntax(), ok.clone_for_update().syntax());
+            }
+
+            let new_ty = make::ty(&format!("{result_path}<{ok_ty}>")).clone_for_update();
+            ted::replace(type_ref.syntax(), new_ty.syntax());
+            if let Some(import_scope) = import_scope {
+                insert_use(
+                    &import_scope,
+                    make::path_from_text("anyhow::Result"),
+                    &ctx.config.insert_use,
+                );
+            }
+        },
+    )
+}
+
+// Assist: convert_anyhow_to_result
+//
+// Converts a function returning `anyhow::Result<T>` to return `Result<T, E>`, for a concrete
+// error type to be filled in.
+//
+// ```
+// # //- minicore: result
+// # //- /main.rs crate:main deps:anyhow
+// fn parse(text: &str) -> anyhow::Result<u32>$0 {
+//     Ok(0)
+// }
+// # //- /anyhow.rs crate:anyhow
+// # pub struct Error;
+// # pub type Result<T, E = Error> = core::result::Result<T, E>;
+// ```
+// ->
+// ```
+// fn parse(text: &str) -> Result<u32, ${0:anyhow::Error}> {
+//     Ok(0)
+// }
+// ```
+pub(crate) fn convert_anyhow_to_result(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let ret_type = ctx.find_node_at_offset::<ast::RetType>()?;
+    ast::Fn::cast(ret_type.syntax().parent()?)?;
+    let ast::Type::PathType(type_ref) = ret_type.ty()? else { return None };
+    let path = type_ref.path()?;
+    let [ok_ty] = type_args(&path)?;
+
+    let db = ctx.db();
+    let scope = ctx.sema.scope(type_ref.syntax())?;
+    let anyhow = Anyhow::find(ctx, scope.krate())?;
+    match ctx.sema.resolve_path(&path)? {
+        PathResolution::Def(ModuleDef::TypeAlias(it)) if it == anyhow.result => (),
+        _ => return None,
+    }
+
+    let result_path = match scope.speculative_resolve(&make::ext::ident_path("Result")) {
+        // The alias takes the error type as its second, defaulted parameter.
+        Some(PathResolution::Def(ModuleDef::TypeAlias(it))) if it == anyhow.result => {
+            make::ext::ident_path("Result")
+        }
+        _ => {
+            let result_enum = FamousDefs(&ctx.sema, scope.krate()).core_result_Result()?;
+            mod_path_to_ast(&scope.module().find_use_path(
+                db,
+                ModuleDef::Adt(hir::Adt::Enum(result_enum)),
+                ctx.config.prefer_no_std,
+                ctx.config.prefer_prelude,
+            )?)
+        }
+    };
+
+    acc.add(
+        AssistId("convert_anyhow_to_result", AssistKind::RefactorRewrite),
+        "Convert to `Result<T, E>`",
+        type_ref.syntax().text_range(),
+        |builder| {
+            let type_ref = builder.make_mut(type_ref);
+            let new_ty =
+                make::ty(&format!("{result_path}<{ok_ty}, anyhow::Error>")).clone_for_update();
+            ted::replace(type_ref.syntax(), new_ty.syntax());
+            let err_ty = new_ty
+                .syntax()
+                .descendants()
+                .find_map(ast::GenericArgList::cast)
+                .and_then(|it| it.generic_args().last());
+            if let (Some(cap), Some(err_ty)) = (ctx.config.snippet_cap, err_ty) {
+                builder.add_placeholder_snippet(cap, err_ty);
+            }
+        },
+    )
+}
+
+struct Anyhow {
+    result: hir::TypeAlias,
+    error: hir::Struct,
+}
+
+impl Anyhow {
+    /// Looks up the items of `anyhow`, if `krate` depends on it.
+    fn find(ctx: &AssistContext<'_>, krate: hir::Crate) -> Option<Anyhow> {
+        let db = ctx.db();
+        let dep = krate
+            .dependencies(db)
+            .into_iter()
+            .find(|dep| dep.name.display(db).to_string() == "anyhow")?;
+        let mut result = None;
+        let mut error = None;
+        for (name, def) in dep.krate.root_module().scope(db, None) {
+            match (name.display(db).to_string().as_str(), def) {
+                ("Result", ScopeDef::ModuleDef(ModuleDef::TypeAlias(it))) => result = Some(it),
+                ("Error", ScopeDef::ModuleDef(ModuleDef::Adt(hir::Adt::Struct(it)))) => {
+                    error = Some(it)
+                }
+                _ => (),
+            }
+        }
+        Some(Anyhow { result: result?, error: error? })
+    }
+}
+
+/// Returns the type arguments of `path`, if there are exactly `N` of them.
+fn type_args<const N: usize>(path: &ast::Path) -> Option<[ast::Type; N]> {
+    let args = path.segment()?.generic_arg_list()?.generic_args();
+    let args: Vec<_> = args
+        .map(|arg| match arg {
+            ast::GenericArg::TypeArg(it) => it.ty(),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    args.try_into().ok()
+}
+
+fn collect_returned_exprs(body: &Expr, acc: &mut Vec<Expr>) {
+    let tail_cb = &mut |e: &_| tail_cb_impl(acc, e);
+    walk_expr(body, &mut |expr| {
+        if let Expr::ReturnExpr(ret_expr) = expr {
+            if let Some(ret_expr_arg) = &ret_expr.expr() {
+                for_each_tail_expr(ret_expr_arg, tail_cb);
+            }
+        }
+    });
+    for_each_tail_expr(body, tail_cb);
+}
+
+fn tail_cb_impl(acc: &mut Vec<Expr>, e: &Expr) {
+    match e {
+        Expr::BreakExpr(break_expr) => {
+            if let Some(break_expr_arg) = break_expr.expr() {
+                for_each_tail_expr(&break_expr_arg, &mut |e| tail_cb_impl(acc, e))
+            }
+        }
+        Expr::ReturnExpr(_) => {
+            // all return expressions have already been handled by the walk loop
+        }
+        e => acc.push(e.clone()),
+    }
+}
+
+/// Matches `Ok(arg)` and `Err(arg)`, returning the variant name and `arg`.
+fn result_variant(
+    ctx: &AssistContext<'_>,
+    result_enum: hir::Enum,
+    expr: &Expr,
+) -> Option<(String, Expr)> {
+    let Expr::CallExpr(call) = expr else { return None };
+    let Some(Expr::PathExpr(callee)) = call.expr() else { return None };
+    let variant = match ctx.sema.resolve_path(&callee.path()?)? {
+        PathResolution::Def(ModuleDef::Variant(it)) if it.parent_enum(ctx.db()) == result_enum => {
+            it
+        }
+        _ => return None,
+    };
+    let arg = call.arg_list()?.args().next()?;
+    Some((variant.name(ctx.db()).display(ctx.db()).to_string(), arg))
+}
+
+fn needs_parens(expr: &Expr) -> bool {
+    !matches!(
+        expr,
+        Expr::PathExpr(_)
+            | Expr::CallExpr(_)
+            | Expr::MethodCallExpr(_)
+            | Expr::FieldExpr(_)
+            | Expr::Literal(_)
+            | Expr::ParenExpr(_)
+            | Expr::MacroExpr(_)
+            | Expr::IndexExpr(_)
+            | Expr::RecordExpr(_)
+            | Expr::TupleExpr(_)
+            | Expr::ArrayExpr(_)
+            | Expr::TryExpr(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn converts_returned_errors_and_results() {
+        check_assist(
+            convert_result_to_anyhow,
+            r#"
+//- minicore: result
+//- /main.rs crate:main deps:anyhow
+struct ParseError;
+fn other() -> Result<u32, ParseError> { Ok(1) }
+
+fn parse(text: &str) -> Result$0<u32, ParseError> {
+    let n = other()?;
+    if n == 0 {
+        return other();
+    }
+    match text {
+        "" => Err(ParseError),
+        _ => Ok(n),
+    }
+}
+//- /anyhow.rs crate:anyhow
+pub struct Error;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+"#,
+            r#"
+use anyhow::Result;
+
+struct ParseError;
+fn other() -> Result<u32, ParseError> { Ok(1) }
+
+fn parse(text: &str) -> Result<u32> {
+    let n = other()?;
+    if n == 0 {
+        return Ok(other()?);
+    }
+    match text {
+        "" => Err(ParseError.into()),
+        _ => Ok(n),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn uses_existing_import() {
+        check_assist(
+            convert_result_to_anyhow,
+            r#"
+//- minicore: result
+//- /main.rs crate:main deps:anyhow
+use anyhow::Result;
+
+fn parse(flag: bool) -> Result<u32, u8>$0 {
+    if flag { Err(1 + 1) } else { Ok(0) }
+}
+//- /anyhow.rs crate:anyhow
+pub struct Error;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+"#,
+            r#"
+use anyhow::Result;
+
+fn parse(flag: bool) -> Result<u32> {
+    if flag { Err((1 + 1).into()) } else { Ok(0) }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn qualifies_path_when_result_is_taken() {
+        check_assist(
+            convert_result_to_anyhow,
+            r#"
+//- minicore: result
+//- /main.rs crate:main deps:anyhow
+mod io { pub type Result<T> = core::result::Result<T, ()>; }
+use io::Result;
+
+fn parse() -> core::result::Result<u32, ()>$0 {
+    Ok(0)
+}
+//- /anyhow.rs crate:anyhow
+pub struct Error;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+"#,
+            r#"
+mod io { pub type Result<T> = core::result::Result<T, ()>; }
+use io::Result;
+
+fn parse() -> anyhow::Result<u32> {
+    Ok(0)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_error_is_matched() {
+        check_assist_not_applicable(
+            convert_result_to_anyhow,
+            r#"
+//- minicore: result
+//- /main.rs crate:main deps:anyhow
+enum Error { NotFound, Other }
+fn find() -> Result<u32, Error> { Err(Error::NotFound) }
+
+fn get() -> Result<u32, Error>$0 {
+    match find() {
+        Err(Error::NotFound) => Ok(0),
+        res => res,
+    }
+}
+//- /anyhow.rs crate:anyhow
+pub struct Error;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_anyhow() {
+        check_assist_not_applicable(
+            convert_result_to_anyhow,
+            r#"
+//- minicore: result
+fn parse() -> Result<u32, ()>$0 { Ok(0) }
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_anyhow_result() {
+        check_assist_not_applicable(
+            convert_result_to_anyhow,
+            r#"
+//- minicore: result
+//- /main.rs crate:main deps:anyhow
+fn parse() -> Result<u32, anyhow::Error>$0 { Ok(0) }
+//- /anyhow.rs crate:anyhow
+pub struct Error;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+"#,
+        );
+    }
+
+    #[test]
+    fn anyhow_to_result() {
+        check_assist(
+            convert_anyhow_to_result,
+            r#"
+//- minicore: result
+//- /main.rs crate:main deps:anyhow
+fn parse() -> anyhow::Result<u32>$0 { Ok(0) }
+//- /anyhow.rs crate:anyhow
+pub struct Error;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+"#,
+            r#"
+fn parse() -> Result<u32, ${0:anyhow::Error}> { Ok(0) }
+"#,
+        );
+    }
+
+    #[test]
+    fn anyhow_to_result_keeps_imported_alias() {
+        check_assist(
+            convert_anyhow_to_result,
+            r#"
+//- minicore: result
+//- /main.rs crate:main deps:anyhow
+use anyhow::Result;
+
+fn parse() -> Result<u32>$0 { Ok(0) }
+//- /anyhow.rs crate:anyhow
+pub struct Error;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+"#,
+            r#"
+use anyhow::Result;
+
+fn parse() -> Result<u32, ${0:anyhow::Error}> { Ok(0) }
+"#,
+        );
+        check_assist_not_applicable(
+            convert_anyhow_to_result,
+            r#"
+//- minicore: result
+//- /main.rs crate:main deps:anyhow
+fn parse() -> Result<u32, ()>$0 { Ok(0) }
+//- /anyhow.rs crate:anyhow
+pub struct Error;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+"#,
+        );
+    }
+}
@@ -0,0 +1,447 @@
+use hir::Semantics;
+use ide_db::{
+    defs::Definition, famous_defs::FamousDefs, search::FileReference, FxHashSet, RootDatabase,
+};
+use syntax::{
+    ast::{self, HasArgList, HasGenericParams, HasName},
+    match_ast, AstNode, TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_slice_param_to_impl_into_iterator
+//
+// Generalizes a `&[T]` parameter that is only iterated once to `impl IntoIterator<Item = &T>`.
+// Call sites passing something that merely derefs to a slice are updated to call `.iter()`.
+//
+// ```
+// fn sum(values$0: &[u32]) -> u32 {
+//     let mut total = 0;
+//     for value in values {
+//         total += value;
+//     }
+//     total
+// }
+// ```
+// ->
+// ```
+// fn sum<'a>(values: impl IntoIterator<Item = &'a u32>) -> u32 {
+//     let mut total = 0;
+//     for value in values {
+//         total += value;
+//     }
+//     total
+// }
+// ```
+pub(crate) fn convert_slice_param_to_impl_into_iterator(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let param = ctx.find_node_at_offset::<ast::Param>()?;
+    let ast::Pat::IdentPat(ident_pat) = param.pat()? else { return None };
+    if ident_pat.ref_token().is_some() || ident_pat.pat().is_some() {
+        return None;
+    }
+    let ast::Type::RefType(ref_ty) = param.ty()? else { return None };
+    if ref_ty.mut_token().is_some() {
+        return None;
+    }
+    let ast::Type::SliceType(slice_ty) = ref_ty.ty()? else { return None };
+    let elem_ty = slice_ty.ty()?;
+
+    let param_list = param.syntax().parent().and_then(ast::ParamList::cast)?;
+    let func = param_list.syntax().parent().and_then(ast::Fn::cast)?;
+    let body = func.body()?;
+    if func
+        .syntax()
+        .parent()
+        .and_then(|it| it.parent())
+        .and_then(ast::Impl::cast)
+        .map_or(false, |imp| imp.trait_().is_some())
+    {
+        // The signature is dictated by the trait.
+        return None;
+    }
+    if param_list.self_param().is_none() && func.ret_type().map_or(false, has_elided_lifetime) {
+        // The return type may borrow from the slice, which elision would no longer allow.
+        return None;
+    }
+
+    let local = ctx.sema.to_def(&ident_pat)?;
+    let usages = Definition::Local(local).usages(&ctx.sema).all();
+    let mut references = usages.iter().flat_map(|(_, refs)| refs);
+    let body_edit = match (references.next(), references.next()) {
+        (Some(reference), None) => iteration_edit(reference, &body)?,
+        _ => return None,
+    };
+
+    let lifetime = match ref_ty.lifetime() {
+        Some(lifetime) => lifetime.to_string(),
+        None => fresh_lifetime(&func),
+    };
+    let fn_def = ctx.sema.to_def(&func)?;
+    let mut arg_position = param_list.params().position(|it| it == param)?;
+    let has_self_param = param_list.self_param().is_some();
+    if has_self_param {
+        arg_position += 1;
+    }
+    let module = ctx.sema.scope(func.syntax())?.module();
+    let into_iterator = FamousDefs(&ctx.sema, module.krate()).core_iter_IntoIterator();
+
+    acc.add(
+        AssistId("convert_slice_param_to_impl_into_iterator", AssistKind::RefactorRewrite),
+        "Convert to `impl IntoIterator`",
+        param.syntax().text_range(),
+        |builder| {
+            builder.replace(
+                ref_ty.syntax().text_range(),
+                format!("impl IntoIterator<Item = &{lifetime} {elem_ty}>"),
+            );
+            if ref_ty.lifetime().is_none() {
+                let lifetime_param = match func.generic_param_list() {
+                    Some(generic_params) => generic_params
+                        .l_angle_token()
+                        .map(|it| (it.text_range().end(), format!("{lifetime}, "))),
+                    None => func
+                        .name()
+                        .map(|it| (it.syntax().text_range().end(), format!("<{lifetime}>"))),
+                };
+                if let Some((offset, text)) = lifetime_param {
+                    builder.insert(offset, text);
+                }
+            }
+            if let Some((range, replacement)) = body_edit {
+                builder.replace(range, replacement);
+            }
+
+            let Some(into_iterator) = into_iterator else { return };
+            for (file_id, references) in Definition::Function(fn_def).usages(&ctx.sema).all() {
+                builder.edit_file(file_id);
+                for reference in references {
+                    let Some(arg) = call_arg(&reference, arg_position, has_self_param) else {
+                        continue;
+                    };
+                    if let Some((range, replacement)) =
+                        call_site_edit(&ctx.sema, into_iterator, &arg)
+                    {
+                        builder.replace(range, replacement);
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Checks that `reference` iterates the parameter exactly once, returning the edit turning a
+/// `param.iter()` call into `param.into_iter()`.
+fn iteration_edit(
+    reference: &FileReference,
+    body: &ast::BlockExpr,
+) -> Option<Option<(TextRange, String)>> {
+    let name_ref = reference.name.as_name_ref()?;
+    let path_expr = name_ref.syntax().ancestors().find_map(ast::PathExpr::cast)?;
+    let parent = path_expr.syntax().parent()?;
+    let (iterating_expr, edit) = match_ast! {
+        match parent {
+            ast::ForExpr(for_expr) => {
+                if for_expr.iterable()?.syntax() != path_expr.syntax() {
+                    return None;
+                }
+                (for_expr.syntax().clone(), None)
+            },
+            ast::MethodCallExpr(method_call) => {
+                if method_call.receiver()?.syntax() != path_expr.syntax()
+                    || method_call.name_ref()?.text() != "iter"
+                    || method_call.arg_list()?.args().next().is_some()
+                {
+                    return None;
+                }
+                let edit = (
+                    method_call.syntax().text_range(),
+                    format!("{}.into_iter()", path_expr.syntax().text()),
+                );
+                (method_call.syntax().clone(), Some(edit))
+            },
+            _ => return None,
+        }
+    };
+    // The iterator is consumed, so it must not be created again by an enclosing loop or closure.
+    let iterated_repeatedly =
+        iterating_expr.ancestors().skip(1).take_while(|it| it != body.syntax()).any(|it| {
+            ast::ForExpr::can_cast(it.kind())
+                || ast::WhileExpr::can_cast(it.kind())
+                || ast::LoopExpr::can_cast(it.kind())
+                || ast::ClosureExpr::can_cast(it.kind())
+        });
+    if iterated_repeatedly {
+        return None;
+    }
+    Some(edit)
+}
+
+fn has_elided_lifetime(ret_type: ast::RetType) -> bool {
+    ret_type.syntax().descendants().any(|node| {
+        match_ast! {
+            match node {
+                ast::RefType(it) => it.lifetime().is_none(),
+                ast::Lifetime(it) => it.text() == "'_",
+                _ => false,
+            }
+        }
+    })
+}
+
+/// Picks a lifetime name that is neither declared on `func` nor on its enclosing impl or trait.
+fn fresh_lifetime(func: &ast::Fn) -> String {
+    let enclosing_params = func.syntax().ancestors().skip(1).find_map(|node| {
+        match_ast! {
+            match node {
+                ast::Impl(it) => it.generic_param_list(),
+                ast::Trait(it) => it.generic_param_list(),
+                _ => None,
+            }
+        }
+    });
+    let used = func
+        .generic_param_list()
+        .into_iter()
+        .chain(enclosing_params)
+        .flat_map(|it| it.lifetime_params())
+        .filter_map(|it| it.lifetime())
+        .map(|it| it.to_string())
+        .collect::<FxHashSet<_>>();
+    ('a'..='z')
+        .map(|c| format!("'{c}"))
+        .find(|it| !used.contains(it))
+        .unwrap_or_else(|| "'a".into())
+}
+
+/// Finds the argument passed for the converted parameter at a usage of the function.
+fn call_arg(
+    reference: &FileReference,
+    arg_position: usize,
+    has_self_param: bool,
+) -> Option<ast::Expr> {
+    let name_ref = reference.name.as_name_ref()?;
+    if let Some(method_call) = name_ref.syntax().parent().and_then(ast::MethodCallExpr::cast) {
+        if !has_self_param {
+            return None;
+        }
+        return method_call.arg_list()?.args().nth(arg_position - 1);
+    }
+    let path_expr = name_ref.syntax().ancestors().find_map(ast::PathExpr::cast)?;
+    let call = path_expr.syntax().parent().and_then(ast::CallExpr::cast)?;
+    if call.expr()?.syntax() != path_expr.syntax() {
+        return None;
+    }
+    call.arg_list()?.args().nth(arg_position)
+}
+
+/// Arguments that only coerced to a slice, like a `&Vec<T>`-like type without an `IntoIterator`
+/// impl, get an explicit `.iter()`.
+fn call_site_edit(
+    sema: &Semantics<'_, RootDatabase>,
+    into_iterator: hir::Trait,
+    arg: &ast::Expr,
+) -> Option<(TextRange, String)> {
+    let ty = sema.type_of_expr(arg)?.original;
+    if ty.impls_trait(sema.db, into_iterator, &[]) {
+        return None;
+    }
+    let receiver = match arg {
+        ast::Expr::RefExpr(ref_expr) if ref_expr.mut_token().is_none() => ref_expr.expr()?,
+        _ => arg.clone(),
+    };
+    let receiver = match receiver {
+        ast::Expr::PathExpr(_)
+        | ast::Expr::FieldExpr(_)
+        | ast::Expr::CallExpr(_)
+        | ast::Expr::MethodCallExpr(_)
+        | ast::Expr::ParenExpr(_) => receiver.syntax().text().to_string(),
+        _ => format!("({})", receiver.syntax().text()),
+    };
+    Some((arg.syntax().text_range(), format!("{receiver}.iter()")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn for_loop() {
+        check_assist(
+            convert_slice_param_to_impl_into_iterator,
+            r#"
+fn print<T>(items: &$0[T]) {
+    for item in items {
+        let _ = item;
+    }
+}
+"#,
+            r#"
+fn print<'a, T>(items: impl IntoIterator<Item = &'a T>) {
+    for item in items {
+        let _ = item;
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn iter_call_and_named_lifetime() {
+        check_assist(
+            convert_slice_param_to_impl_into_iterator,
+            r#"
+struct S;
+impl S {
+    fn count<'x>(&self, items$0: &'x [u32]) -> usize {
+        items.iter().count()
+    }
+}
+"#,
+            r#"
+struct S;
+impl S {
+    fn count<'x>(&self, items: impl IntoIterator<Item = &'x u32>) -> usize {
+        items.into_iter().count()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn avoids_lifetimes_of_the_impl() {
+        check_assist(
+            convert_slice_param_to_impl_into_iterator,
+            r#"
+struct S<'a>(&'a u32);
+impl<'a> S<'a> {
+    fn count(&self, items$0: &[u32]) -> usize {
+        items.iter().count()
+    }
+}
+"#,
+            r#"
+struct S<'a>(&'a u32);
+impl<'a> S<'a> {
+    fn count<'b>(&self, items: impl IntoIterator<Item = &'b u32>) -> usize {
+        items.into_iter().count()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn updates_call_sites() {
+        check_assist(
+            convert_slice_param_to_impl_into_iterator,
+            r#"
+//- minicore: iterator, deref, slice
+struct Vec<T>(T);
+impl<T> core::ops::Deref for Vec<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] { loop {} }
+}
+
+fn total(values$0: &[u32]) {
+    for _ in values {}
+}
+
+fn main(v: Vec<u32>, s: &[u32]) {
+    total(&v);
+    total(s);
+}
+"#,
+            r#"
+struct Vec<T>(T);
+impl<T> core::ops::Deref for Vec<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] { loop {} }
+}
+
+fn total<'a>(values: impl IntoIterator<Item = &'a u32>) {
+    for _ in values {}
+}
+
+fn main(v: Vec<u32>, s: &[u32]) {
+    total(v.iter());
+    total(s);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_random_access() {
+        check_assist_not_applicable(
+            convert_slice_param_to_impl_into_iterator,
+            r#"
+fn first(items$0: &[u32]) -> u32 {
+    items[0]
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_slice_param_to_impl_into_iterator,
+            r#"
+fn len(items$0: &[u32]) -> usize {
+    items.len()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_iterated_repeatedly() {
+        check_assist_not_applicable(
+            convert_slice_param_to_impl_into_iterator,
+            r#"
+fn twice(items$0: &[u32]) {
+    for _ in items {}
+    for _ in items {}
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_slice_param_to_impl_into_iterator,
+            r#"
+fn nested(items$0: &[u32], n: u32) {
+    for _ in 0..n {
+        for _ in items {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_returning_a_borrow() {
+        check_assist_not_applicable(
+            convert_slice_param_to_impl_into_iterator,
+            r#"
+fn max(items$0: &[u32]) -> Option<&u32> {
+    items.iter().max()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_mutable_slices() {
+        check_assist_not_applicable(
+            convert_slice_param_to_impl_into_iterator,
+            r#"
+fn zero(items$0: &mut [u32]) {
+    for item in items {
+        *item = 0;
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use std::iter;
+
+use hir::PathResolution;
+use ide_db::famous_defs::FamousDefs;
+use itertools::Itertools;
+use syntax::ast::{self, AstNode, HasArgList};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_string_concat_to_format
+//
+// Converts a chain of string concatenations into a `format!` call.
+//
+// ```
+// # //- /main.rs crate:main deps:alloc
+// # use alloc::string::{String, ToString};
+// fn greet(name: &str, punctuation: String) -> String {
+//     name.to_string() $0+ " says hi" + &punctuation
+// }
+// # //- /alloc.rs crate:alloc
+// # pub mod string {
+// #     pub struct String;
+// #     pub trait ToString { fn to_string(&self) -> String; }
+// #     impl ToString for str { fn to_string(&self) -> String { String } }
+// # }
+// ```
+// ->
+// ```
+// # use alloc::string::{String, ToString};
+// fn greet(name: &str, punctuation: String) -> String {
+//     format!("{name} says hi{punctuation}")
+// }
+// ```
+pub(crate) fn convert_string_concat_to_format(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let bin_expr = ctx.find_node_at_offset::<ast::BinExpr>()?;
+    // Work on the whole chain, not just the innermost `+` under the cursor.
+    let bin_expr =
+        iter::successors(Some(bin_expr), |it| it.syntax().parent().and_then(ast::BinExpr::cast))
+            .take_while(is_add)
+            .last()?;
+
+    let mut operands = Vec::new();
+    let mut lhs = ast::Expr::BinExpr(bin_expr.clone());
+    loop {
+        match lhs {
+            ast::Expr::BinExpr(it) if is_add(&it) => {
+                operands.push(it.rhs()?);
+                lhs = it.lhs()?;
+            }
+            _ => {
+                operands.push(lhs);
+                break;
+            }
+        }
+    }
+    operands.reverse();
+
+    let krate = ctx.sema.scope(bin_expr.syntax())?.krate();
+    let string = FamousDefs(&ctx.sema, krate).alloc_string_String()?;
+    let is_string_typed = |expr: &ast::Expr| {
+        let Some(ty) = ctx.sema.type_of_expr(expr) else { return false };
+        let ty = ty.original.strip_references();
+        ty.as_builtin().is_some_and(|it| it.is_str())
+            || ty.as_adt() == Some(hir::Adt::Struct(string))
+    };
+    if !operands.iter().all(is_string_typed) {
+        cov_mark::hit!(convert_string_concat_to_format_not_strings);
+        return None;
+    }
+
+    let mut template = String::new();
+    let mut args = Vec::new();
+    for operand in operands {
+        match Part::of(ctx, operand) {
+            Part::Text(text) => template.push_str(&escape_format_text(&text)),
+            Part::Capture(name) => {
+                template.push('{');
+                template.push_str(&name);
+                template.push('}');
+            }
+            Part::Arg(expr) => {
+                template.push_str("{}");
+                args.push(expr);
+            }
+        }
+    }
+
+    acc.add(
+        AssistId("convert_string_concat_to_format", AssistKind::RefactorRewrite),
+        "Convert string concatenation to `format!`",
+        bin_expr.syntax().text_range(),
+        |builder| {
+            let args = args.iter().format_with("", |arg, f| f(&format_args!(", {arg}")));
+            builder
+                .replace(bin_expr.syntax().text_range(), format!("format!(\"{template}\"{args})"));
+        },
+    )
+}
+
+fn is_add(bin_expr: &ast::BinExpr) -> bool {
+    bin_expr.op_kind() == Some(ast::BinaryOp::ArithOp(ast::ArithOp::Add))
+}
+
+/// Escapes `text` for use in the string literal of a format string.
+fn escape_format_text(text: &str) -> String {
+    let mut buf = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '{' => buf.push_str("{{"),
+            '}' => buf.push_str("}}"),
+            '\\' | '"' | '\n' | '\r' | '\t' => buf.extend(c.escape_default()),
+            _ => buf.push(c),
+        }
+    }
+    buf
+}
+
+enum Part {
+    /// The value of a string literal.
+    Text(String),
+    /// A local that can be captured by the format string.
+    Capture(String),
+    /// Any other expression, passed as a positional argument.
+    Arg(ast::Expr),
+}
+
+impl Part {
+    fn of(ctx: &AssistContext<'_>, mut expr: ast::Expr) -> Part {
+        // `&s`, `s.to_string()` and `s.to_owned()` all format the same as `s`.
+        if let ast::Expr::RefExpr(ref_expr) = &expr {
+            if ref_expr.mut_token().is_none() && ref_expr.raw_token().is_none() {
+                if let Some(inner) = ref_expr.expr() {
+                    expr = inner;
+                }
+            }
+        }
+        if let ast::Expr::MethodCallExpr(call) = &expr {
+            let is_conversion = call
+                .name_ref()
+                .is_some_and(|it| matches!(it.text().as_str(), "to_string" | "to_owned"))
+                && call.generic_arg_list().is_none()
+                && call.arg_list().is_some_and(|it| it.args().next().is_none());
+            if is_conversion {
+                if let Some(receiver) = call.receiver() {
+                    expr = receiver;
+                }
+            }
+        }
+
+        match &expr {
+            ast::Expr::Literal(literal) => {
+                if let ast::LiteralKind::String(string) = literal.kind() {
+                    if let Some(value) = string.value() {
+                        return Part::Text(value.into_owned());
+                    }
+                }
+            }
+            ast::Expr::PathExpr(path_expr) => {
+                let name = path_expr
+                    .path()
+                    .filter(|path| path.qualifier().is_none())
+                    .and_then(|path| path.segment()?.name_ref())
+                    .filter(|name| !name.text().starts_with("r#"));
+                if let Some(name) = name {
+                    if let Some(PathResolution::Local(_)) =
+                        path_expr.path().and_then(|path| ctx.sema.resolve_path(&path))
+                    {
+                        return Part::Capture(name.text().to_string());
+                    }
+                }
+            }
+            _ => (),
+        }
+        Part::Arg(expr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn concat_of_locals_and_literals() {
+        check_assist(
+            convert_string_concat_to_format,
+            r#"
+//- /main.rs crate:main deps:alloc
+use alloc::string::{String, ToString};
+fn f(a: &str, b: String) -> String {
+    a.to_string() + &b +$0 "x{}"
+}
+//- /alloc.rs crate:alloc
+pub mod string {
+    pub struct String;
+    pub trait ToString { fn to_string(&self) -> String; }
+    impl ToString for str { fn to_string(&self) -> String { String } }
+}
+"#,
+            r#"
+use alloc::string::{String, ToString};
+fn f(a: &str, b: String) -> String {
+    format!("{a}{b}x{{}}")
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn to_owned_and_complex_operands() {
+        check_assist(
+            convert_string_concat_to_format,
+            r#"
+//- /main.rs crate:main deps:alloc
+use alloc::{borrow::ToOwned, string::String};
+struct S { name: String }
+fn f(s: &S) -> String {
+    let suffix = "q";
+    "id: ".to_owned()$0 + &s.name + suffix
+}
+//- /alloc.rs crate:alloc
+pub mod string { pub struct String; }
+pub mod borrow {
+    pub trait ToOwned { type Owned; fn to_owned(&self) -> Self::Owned; }
+    impl ToOwned for str { type Owned = crate::string::String; fn to_owned(&self) -> crate::string::String { crate::string::String } }
+}
+"#,
+            r#"
+use alloc::{borrow::ToOwned, string::String};
+struct S { name: String }
+fn f(s: &S) -> String {
+    let suffix = "q";
+    format!("id: {}{suffix}", s.name)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn raw_string_literals() {
+        check_assist(
+            convert_string_concat_to_format,
+            r##"
+//- /main.rs crate:main deps:alloc
+use alloc::string::String;
+fn f(s: String) -> String {
+    s $0+ r#"a\"b"#
+}
+//- /alloc.rs crate:alloc
+pub mod string { pub struct String; }
+"##,
+            r#"
+use alloc::string::String;
+fn f(s: String) -> String {
+    format!("{s}a\\\"b")
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn escapes_in_literals() {
+        check_assist(
+            convert_string_concat_to_format,
+            r#"
+//- /main.rs crate:main deps:alloc
+use alloc::string::String;
+fn f(s: String) -> String {
+    s $0+ "\u{41}\t\"{}\\"
+}
+//- /alloc.rs crate:alloc
+pub mod string { pub struct String; }
+"#,
+            r#"
+use alloc::string::String;
+fn f(s: String) -> String {
+    format!("{s}A\t\"{{}}\\")
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_non_strings() {
+        cov_mark::check!(convert_string_concat_to_format_not_strings);
+        check_assist_not_applicable(
+            convert_string_concat_to_format,
+            r#"
+//- /main.rs crate:main deps:alloc
+fn f(a: i32, b: i32) -> i32 {
+    a $0+ b
+}
+//- /alloc.rs crate:alloc
+pub mod string { pub struct String; }
+"#,
+        );
+    }
+}
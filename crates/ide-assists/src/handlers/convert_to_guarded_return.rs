@@ -1,6 +1,8 @@
 use std::iter::once;
 
+use either::Either;
 use ide_db::{
+    famous_defs::FamousDefs,
     syntax_helpers::node_ext::{is_pattern_cond, single_let},
     ty_filter::TryEnum,
 };
@@ -58,9 +60,12 @@ fn if_expr_to_guarded_return(
     acc: &mut Assists,
     ctx: &AssistContext<'_>,
 ) -> Option<()> {
-    if if_expr.else_branch().is_some() {
-        return None;
-    }
+    // An `else` branch can only be kept if it leaves the block.
+    let else_block = match if_expr.else_branch() {
+        None => None,
+        Some(ast::ElseBranch::Block(block)) if is_diverging(ctx, &block) => Some(block),
+        Some(_) => return None,
+    };
 
     let cond = if_expr.condition()?;
 
@@ -104,12 +109,9 @@ fn if_expr_to_guarded_return(
         return None;
     }
 
-    let parent_container = parent_block.syntax().parent()?;
-
-    let early_expression: ast::Expr = match parent_container.kind() {
-        WHILE_EXPR | LOOP_EXPR | FOR_EXPR => make::expr_continue(None),
-        FN => make::expr_return(None),
-        _ => return None,
+    let early_exit = match else_block {
+        Some(block) => Either::Left(block.reset_indent()),
+        None => Either::Right(early_expression(ctx, &parent_block)?),
     };
 
     then_block.syntax().first_child_or_token().map(|t| t.kind() == T!['{'])?;
@@ -137,8 +139,9 @@ fn if_expr_to_guarded_return(
                 None => {
                     // If.
                     let new_expr = {
-                        let then_branch =
-                            make::block_expr(once(make::expr_stmt(early_expression).into()), None);
+                        let then_branch = early_exit.left_or_else(|early_expression| {
+                            make::block_expr(once(make::expr_stmt(early_expression).into()), None)
+                        });
                         let cond = invert_boolean_expression(cond_expr);
                         make::expr_if(cond, then_branch, None).indent(if_indent_level)
                     };
@@ -146,12 +149,8 @@ fn if_expr_to_guarded_return(
                 }
                 Some(pat) => {
                     // If-let.
-                    let let_else_stmt = make::let_else_stmt(
-                        pat,
-                        None,
-                        cond_expr,
-                        ast::make::tail_only_block_expr(early_expression),
-                    );
+                    let else_block = early_exit.left_or_else(make::tail_only_block_expr);
+                    let let_else_stmt = make::let_else_stmt(pat, None, cond_expr, else_block);
                     let let_else_stmt = let_else_stmt.indent(if_indent_level);
                     let_else_stmt.syntax().clone_for_update()
                 }
@@ -196,17 +195,8 @@ fn let_stmt_to_guarded_return(
     let happy_pattern = try_enum.happy_pattern(pat);
     let target = let_stmt.syntax().text_range();
 
-    let early_expression: ast::Expr = {
-        let parent_block =
-            let_stmt.syntax().parent()?.ancestors().find_map(ast::BlockExpr::cast)?;
-        let parent_container = parent_block.syntax().parent()?;
-
-        match parent_container.kind() {
-            WHILE_EXPR | LOOP_EXPR | FOR_EXPR => make::expr_continue(None),
-            FN => make::expr_return(None),
-            _ => return None,
-        }
-    };
+    let parent_block = let_stmt.syntax().parent()?.ancestors().find_map(ast::BlockExpr::cast)?;
+    let early_expression = early_expression(ctx, &parent_block)?;
 
     acc.add(
         AssistId("convert_to_guarded_return", AssistKind::RefactorRewrite),
@@ -232,6 +222,49 @@ fn let_stmt_to_guarded_return(
     )
 }
 
+fn is_diverging(ctx: &AssistContext<'_>, block: &ast::BlockExpr) -> bool {
+    let stmt_list = match block.stmt_list() {
+        Some(it) => it,
+        None => return false,
+    };
+    let last_expr = stmt_list.tail_expr().or_else(|| match stmt_list.statements().last()? {
+        ast::Stmt::ExprStmt(stmt) => stmt.expr(),
+        _ => None,
+    });
+    last_expr
+        .and_then(|expr| ctx.sema.type_of_expr(&expr))
+        .map_or(false, |ty| ty.original.is_never())
+}
+
+/// Returns the expression that leaves `parent_block` early: `continue` in loops, and a `return`
+/// with a value fitting the return type in function bodies.
+fn early_expression(ctx: &AssistContext<'_>, parent_block: &ast::BlockExpr) -> Option<ast::Expr> {
+    let parent_container = parent_block.syntax().parent()?;
+    match parent_container.kind() {
+        WHILE_EXPR | LOOP_EXPR | FOR_EXPR => Some(make::expr_continue(None)),
+        FN => {
+            let fn_ = ctx.sema.to_def(&ast::Fn::cast(parent_container)?)?;
+            let ret_ty = fn_.ret_type(ctx.db());
+            let krate = fn_.module(ctx.db()).krate();
+            let value = if ret_ty.is_unit() {
+                None
+            } else if matches!(TryEnum::from_ty(&ctx.sema, &ret_ty), Some(TryEnum::Option)) {
+                Some(make::expr_path(make::ext::ident_path("None")))
+            } else if FamousDefs(&ctx.sema, krate)
+                .core_default_Default()
+                .map_or(false, |default| ret_ty.impls_trait(ctx.db(), default, &[]))
+            {
+                let default = make::ext::path_from_idents(["Default", "default"])?;
+                Some(make::expr_call(make::expr_path(default), make::arg_list(None)))
+            } else {
+                None
+            };
+            Some(make::expr_return(value))
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::{check_assist, check_assist_not_applicable};
@@ -534,6 +567,105 @@ fn main() {
         );
     }
 
+    #[test]
+    fn convert_let_returns_default_of_return_type() {
+        check_assist(
+            convert_to_guarded_return,
+            r#"
+//- minicore: option
+fn main(n: Option<i32>) -> Option<i32> {
+    if$0 let Some(n) = n {
+        foo(n);
+    }
+}
+"#,
+            r#"
+fn main(n: Option<i32>) -> Option<i32> {
+    let Some(n) = n else { return None };
+    foo(n);
+}
+"#,
+        );
+        check_assist(
+            convert_to_guarded_return,
+            r#"
+//- minicore: option, default
+struct S;
+impl Default for S {
+    fn default() -> Self { S }
+}
+fn main(n: Option<i32>) -> S {
+    if$0 let Some(n) = n {
+        foo(n);
+    }
+}
+"#,
+            r#"
+struct S;
+impl Default for S {
+    fn default() -> Self { S }
+}
+fn main(n: Option<i32>) -> S {
+    let Some(n) = n else { return Default::default() };
+    foo(n);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_let_with_diverging_else() {
+        check_assist(
+            convert_to_guarded_return,
+            r#"
+//- minicore: option
+fn main(n: Option<i32>) -> Result<i32, ()> {
+    if$0 let Some(n) = n {
+        let m = n + 1;
+        Ok(m)
+    } else {
+        log();
+        return Err(());
+    }
+}
+"#,
+            r#"
+fn main(n: Option<i32>) -> Result<i32, ()> {
+    let Some(n) = n else {
+        log();
+        return Err(());
+    };
+    let m = n + 1;
+    Ok(m)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_with_diverging_else() {
+        check_assist(
+            convert_to_guarded_return,
+            r#"
+fn main() {
+    if$0 ready() {
+        foo();
+    } else {
+        return;
+    }
+}
+"#,
+            r#"
+fn main() {
+    if !ready() {
+        return;
+    }
+    foo();
+}
+"#,
+        );
+    }
+
     #[test]
     fn convert_let_stmt_inside_fn() {
         check_assist(
@@ -5,11 +5,15 @@ use syntax::{
     match_ast, SyntaxNode,
 };
 
-use crate::{assist_context::SourceChangeBuilder, AssistContext, AssistId, AssistKind, Assists};
+use crate::{
+    assist_context::SourceChangeBuilder, utils::suggest_name, AssistContext, AssistId, AssistKind,
+    Assists,
+};
 
 // Assist: convert_tuple_struct_to_named_struct
 //
 // Converts tuple struct to struct with named fields, and analogously for tuple enum variants.
+// Fields are named after their types where that gives each field a distinct name.
 //
 // ```
 // struct Point$0(f32, f32);
@@ -68,7 +72,7 @@ pub(crate) fn convert_tuple_struct_to_named_struct(
         "Convert to named struct",
         target,
         |edit| {
-            let names = generate_names(tuple_fields.fields());
+            let names = generate_names(ctx, tuple_fields.fields());
             edit_field_references(ctx, edit, tuple_fields.fields(), &names);
             edit_struct_references(ctx, edit, strukt_def, &names);
             edit_struct_def(ctx, edit, &strukt, tuple_fields, names);
@@ -140,10 +144,15 @@ fn edit_struct_references(
                             tuple_struct_pat.path()?,
                             ast::make::record_pat_field_list(tuple_struct_pat.fields().zip(names).map(
                                 |(pat, name)| {
-                                    ast::make::record_pat_field(
-                                        ast::make::name_ref(&name.to_string()),
-                                        pat,
-                                    )
+                                    let name_ref = ast::make::name_ref(&name.to_string());
+                                    match &pat {
+                                        ast::Pat::IdentPat(it)
+                                            if it.syntax().text() == name.text().as_str() =>
+                                        {
+                                            ast::make::record_pat_field_shorthand(name_ref)
+                                        }
+                                        _ => ast::make::record_pat_field(name_ref, pat),
+                                    }
                                 },
                             ), None),
                         )
@@ -171,9 +180,11 @@ fn edit_struct_references(
                             path,
                             ast::make::record_expr_field_list(arg_list.args().zip(names).map(
                                 |(expr, name)| {
+                                    let is_shorthand = expr.syntax().text() == name.text().as_str()
+                                        && matches!(expr, ast::Expr::PathExpr(_));
                                     ast::make::record_expr_field(
                                         ast::make::name_ref(&name.to_string()),
-                                        Some(expr),
+                                        (!is_shorthand).then_some(expr),
                                     )
                                 },
                             )),
@@ -223,12 +234,29 @@ fn edit_field_references(
     }
 }
 
-fn generate_names(fields: impl Iterator<Item = ast::TupleField>) -> Vec<ast::Name> {
-    fields
+/// Names each field after its type if no other field's type suggests the same name, falling back
+/// to `field{index}`.
+fn generate_names(
+    ctx: &AssistContext<'_>,
+    fields: impl Iterator<Item = ast::TupleField>,
+) -> Vec<ast::Name> {
+    let type_names: Vec<_> = fields
+        .map(|field| {
+            let ty = ctx.sema.to_def(&field)?.ty(ctx.db());
+            suggest_name::for_type(&ty, ctx.db())
+        })
+        .collect();
+    type_names
+        .iter()
         .enumerate()
-        .map(|(i, _)| {
+        .map(|(i, type_name)| {
             let idx = i + 1;
-            ast::make::name(&format!("field{idx}"))
+            match type_name {
+                Some(name) if type_names.iter().filter(|it| *it == type_name).count() == 1 => {
+                    ast::make::name(name)
+                }
+                _ => ast::make::name(&format!("field{idx}")),
+            }
         })
         .collect()
 }
@@ -289,11 +317,11 @@ impl A {
 }"#,
             r#"
 struct Inner;
-struct A { field1: Inner }
+struct A { inner: Inner }
 
 impl A {
     fn new(inner: Inner) -> A {
-        A { field1: inner }
+        A { inner }
     }
 
     fn new_with_default() -> A {
@@ -301,7 +329,7 @@ impl A {
     }
 
     fn into_inner(self) -> Inner {
-        self.field1
+        self.inner
     }
 }"#,
         );
@@ -330,11 +358,11 @@ impl A {
 }"#,
             r#"
 struct Inner;
-struct A { field1: Inner }
+struct A { inner: Inner }
 
 impl A {
     fn new(inner: Inner) -> Self {
-        Self { field1: inner }
+        Self { inner }
     }
 
     fn new_with_default() -> Self {
@@ -342,7 +370,7 @@ impl A {
     }
 
     fn into_inner(self) -> Inner {
-        self.field1
+        self.inner
     }
 }"#,
         );
@@ -369,16 +397,16 @@ impl A {
 }"#,
             r#"
 struct Inner;
-struct A { field1: Inner }
+struct A { inner: Inner }
 
 impl A {
     fn into_inner(self) -> Inner {
-        let A { field1: first } = self;
+        let A { inner: first } = self;
         first
     }
 
     fn into_inner_via_self(self) -> Inner {
-        let Self { field1: first } = self;
+        let Self { inner: first } = self;
         first
     }
 }"#,
@@ -488,19 +516,19 @@ impl Outer {
 }"#,
             r#"
 struct Inner(u32);
-struct Outer { field1: Inner }
+struct Outer { inner: Inner }
 
 impl Outer {
     fn new() -> Self {
-        Self { field1: Inner(42) }
+        Self { inner: Inner(42) }
     }
 
     fn into_inner(self) -> u32 {
-        (self.field1).0
+        (self.inner).0
     }
 
     fn into_inner_destructed(self) -> u32 {
-        let Outer { field1: Inner(x) } = self;
+        let Outer { inner: Inner(x) } = self;
         x
     }
 }"#,
@@ -527,14 +555,49 @@ fn f() {
             r#"
 //- /main.rs
 struct Inner;
-struct A { field1: Inner }
+struct A { inner: Inner }
 
 mod foo;
 
 //- /foo.rs
 use crate::{A, Inner};
 fn f() {
-    let a = A { field1: Inner };
+    let a = A { inner: Inner };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_struct_names_fields_after_types() {
+        check_assist(
+            convert_tuple_struct_to_named_struct,
+            r#"
+struct Point;
+struct Size;
+struct Rect$0(Point, Point, Size, u32);
+
+fn area(size: Size) -> Rect {
+    Rect(Point, Point, size, 0)
+}
+
+fn size(rect: &Rect) -> &Size {
+    let Rect(_, _, size, _) = rect;
+    size
+}
+"#,
+            r#"
+struct Point;
+struct Size;
+struct Rect { field1: Point, field2: Point, size: Size, field4: u32 }
+
+fn area(size: Size) -> Rect {
+    Rect { field1: Point, field2: Point, size, field4: 0 }
+}
+
+fn size(rect: &Rect) -> &Size {
+    let Rect { field1: _, field2: _, size, field4: _ } = rect;
+    size
 }
 "#,
         );
@@ -793,16 +856,16 @@ enum Inner {
     Variant(usize),
 }
 enum Outer {
-    Variant { field1: Inner },
+    Variant { inner: Inner },
 }
 
 impl Outer {
     fn new() -> Self {
-        Self::Variant { field1: Inner::Variant(42) }
+        Self::Variant { inner: Inner::Variant(42) }
     }
 
     fn into_inner_destructed(self) -> u32 {
-        let Outer::Variant { field1: Inner::Variant(x) } = self;
+        let Outer::Variant { inner: Inner::Variant(x) } = self;
         x
     }
 }"#,
@@ -832,7 +895,7 @@ fn f() {
 //- /main.rs
 struct Inner;
 enum A {
-    Variant { field1: Inner },
+    Variant { inner: Inner },
 }
 
 mod foo;
@@ -840,7 +903,7 @@ mod foo;
 //- /foo.rs
 use crate::{A, Inner};
 fn f() {
-    let a = A::Variant { field1: Inner };
+    let a = A::Variant { inner: Inner };
 }
 "#,
         );
@@ -869,7 +932,7 @@ fn f() {
 //- /main.rs
 struct Inner;
 enum A {
-    Variant { field1: Inner },
+    Variant { inner: Inner },
 }
 
 mod foo;
@@ -877,7 +940,7 @@ mod foo;
 //- /foo.rs
 use crate::{A::Variant, Inner};
 fn f() {
-    let a = Variant { field1: Inner };
+    let a = Variant { inner: Inner };
 }
 "#,
         );
@@ -0,0 +1,464 @@
+use hir::{AsAssocItem, AssocItemContainer, ModuleDef};
+use ide_db::{
+    base_db::FileId, defs::Definition, famous_defs::FamousDefs, helpers::mod_path_to_ast,
+    search::FileReferenceNode, FxHashMap,
+};
+use syntax::{ast, match_ast, AstNode, SyntaxNode, TextRange};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_vec_field_to_boxed_slice
+//
+// Converts a `Vec<T>` struct field into a `Box<[T]>`, which is smaller as it doesn't need to store
+// a capacity, and converts the values it is initialized with and the values moved out of it.
+//
+// ```
+// # //- /main.rs crate:main deps:alloc
+// use alloc::{boxed::Box, vec::Vec};
+// struct Data {
+//     $0items: Vec<u32>,
+// }
+// fn make(items: Vec<u32>) -> Data {
+//     Data { items }
+// }
+// # //- /alloc.rs crate:alloc
+// # pub mod boxed { pub struct Box<T: ?Sized>(T); }
+// # pub mod vec { pub struct Vec<T>(T); }
+// ```
+// ->
+// ```
+// use alloc::{boxed::Box, vec::Vec};
+// struct Data {
+//     items: Box<[u32]>,
+// }
+// fn make(items: Vec<u32>) -> Data {
+//     Data { items: items.into_boxed_slice() }
+// }
+// ```
+pub(crate) fn convert_vec_field_to_boxed_slice(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let record_field = ctx.find_node_at_offset::<ast::RecordField>()?;
+    let ast::Type::PathType(field_ty) = record_field.ty()? else { return None };
+    let field = ctx.sema.to_def(&record_field)?;
+
+    let scope = ctx.sema.scope(record_field.syntax())?;
+    let famous_defs = FamousDefs(&ctx.sema, scope.krate());
+    let vec = famous_defs.alloc_vec_Vec()?;
+    if field.ty(ctx.db()).as_adt() != Some(hir::Adt::Struct(vec)) {
+        return None;
+    }
+    let elem_ty = field_ty.path()?.segment()?.generic_arg_list()?.generic_args().find_map(
+        |arg| match arg {
+            ast::GenericArg::TypeArg(it) => it.ty(),
+            _ => None,
+        },
+    )?;
+    // `Box` isn't in the prelude of `no_std` crates.
+    let box_ = famous_defs.alloc_boxed_Box()?;
+    let box_path = mod_path_to_ast(&scope.module().find_use_path(
+        ctx.db(),
+        ModuleDef::Adt(hir::Adt::Struct(box_)),
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )?);
+
+    let mut edits: FxHashMap<FileId, Vec<(TextRange, String)>> = FxHashMap::default();
+    let mut converted_uses = 0;
+    for (file_id, references) in Definition::Field(field).usages(&ctx.sema).all() {
+        for reference in references {
+            let FileReferenceNode::NameRef(name_ref) = reference.name else { continue };
+            let edit = match usage_edit(ctx, vec, &name_ref) {
+                Usage::Conversion(edit) => edit,
+                Usage::Read => continue,
+                Usage::Resizing => {
+                    cov_mark::hit!(convert_vec_field_to_boxed_slice_resizing_use);
+                    return None;
+                }
+            };
+            converted_uses += 1;
+            edits.entry(file_id).or_default().push(edit);
+        }
+    }
+
+    let label = match converted_uses {
+        1 => "Convert to `Box<[T]>`, updating 1 use".to_owned(),
+        n => format!("Convert to `Box<[T]>`, updating {n} uses"),
+    };
+    acc.add(
+        AssistId("convert_vec_field_to_boxed_slice", AssistKind::RefactorRewrite),
+        label,
+        record_field.syntax().text_range(),
+        |builder| {
+            builder.edit_file(ctx.file_id());
+            builder.replace(field_ty.syntax().text_range(), format!("{box_path}<[{elem_ty}]>"));
+            for (file_id, edits) in edits {
+                builder.edit_file(file_id);
+                for (range, text) in edits {
+                    builder.replace(range, text);
+                }
+            }
+        },
+    )
+}
+
+enum Usage {
+    /// The field is initialized or assigned, or its value is moved out, and the value needs to be
+    /// converted.
+    Conversion((TextRange, String)),
+    /// The field is only read from in a way that works for slices as well.
+    Read,
+    /// The field is used in a way that requires a `Vec`, like pushing to it.
+    Resizing,
+}
+
+fn usage_edit(ctx: &AssistContext<'_>, vec: hir::Struct, name_ref: &ast::NameRef) -> Usage {
+    if let Some(record_field) = ast::RecordExprField::for_field_name(name_ref) {
+        return match record_field.expr() {
+            Some(expr) if record_field.name_ref().is_some() => {
+                Usage::Conversion((expr.syntax().text_range(), into_boxed_slice(&expr)))
+            }
+            // field init shorthand
+            Some(expr) => Usage::Conversion((
+                expr.syntax().text_range(),
+                format!("{name_ref}: {}", into_boxed_slice(&expr)),
+            )),
+            None => Usage::Read,
+        };
+    }
+
+    let Some(field_expr) = name_ref.syntax().parent().and_then(ast::FieldExpr::cast) else {
+        return Usage::Read;
+    };
+    let Some(parent) = field_expr.syntax().parent() else { return Usage::Read };
+    if is_moved_into(&parent, field_expr.syntax()) {
+        return Usage::Conversion((
+            field_expr.syntax().text_range(),
+            format!("{field_expr}.into_vec()"),
+        ));
+    }
+    if let Some(method_call) = ast::MethodCallExpr::cast(parent.clone()) {
+        let is_receiver =
+            method_call.receiver().map_or(false, |it| it.syntax() == field_expr.syntax());
+        let Some(method) = ctx.sema.resolve_method_call(&method_call) else { return Usage::Read };
+        if is_receiver && is_vec_only_method(ctx, vec, method) {
+            return Usage::Resizing;
+        }
+    } else if let Some(bin_expr) = ast::BinExpr::cast(parent.clone()) {
+        let is_assignment =
+            matches!(bin_expr.op_kind(), Some(ast::BinaryOp::Assignment { op: None }));
+        if is_assignment && bin_expr.lhs().map_or(false, |it| it.syntax() == field_expr.syntax()) {
+            if let Some(rhs) = bin_expr.rhs() {
+                return Usage::Conversion((rhs.syntax().text_range(), into_boxed_slice(&rhs)));
+            }
+        }
+    } else if let Some(ref_expr) = ast::RefExpr::cast(parent) {
+        // `&mut self.field` might be passed somewhere expecting a `&mut Vec<T>`.
+        let still_a_vec = ctx.sema.type_of_expr(&ref_expr.clone().into()).map_or(false, |ty| {
+            ty.adjusted().remove_ref().and_then(|it| it.as_adt()) == Some(hir::Adt::Struct(vec))
+        });
+        if ref_expr.mut_token().is_some() && still_a_vec {
+            return Usage::Resizing;
+        }
+    }
+    Usage::Read
+}
+
+/// Whether the value of `expr` is moved into `parent`, instead of `expr` being used as a place.
+fn is_moved_into(parent: &SyntaxNode, expr: &SyntaxNode) -> bool {
+    let is = |it: Option<ast::Expr>| it.map_or(false, |it| it.syntax() == expr);
+    match_ast! {
+        match parent {
+            ast::LetStmt(it) => is(it.initializer()),
+            ast::StmtList(it) => is(it.tail_expr()),
+            ast::BinExpr(it) => {
+                matches!(it.op_kind(), Some(ast::BinaryOp::Assignment { op: None }))
+                    && is(it.rhs())
+            },
+            ast::ArgList(_) => true,
+            ast::ArrayExpr(_) => true,
+            ast::BreakExpr(_) => true,
+            ast::RecordExprField(_) => true,
+            ast::ReturnExpr(_) => true,
+            ast::TupleExpr(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Whether the method is only available on `Vec`s, not on slices.
+fn is_vec_only_method(ctx: &AssistContext<'_>, vec: hir::Struct, method: hir::Function) -> bool {
+    let db = ctx.db();
+    let Some(AssocItemContainer::Impl(impl_)) = method.as_assoc_item(db).map(|it| it.container(db))
+    else {
+        return false;
+    };
+    if impl_.self_ty(db).as_adt() != Some(hir::Adt::Struct(vec)) {
+        return false;
+    }
+    // These are also available on slices.
+    !matches!(
+        &*method.name(db).to_smol_str(),
+        "len" | "is_empty" | "as_ptr" | "as_mut_ptr" | "iter" | "iter_mut" | "clone"
+    )
+}
+
+fn into_boxed_slice(expr: &ast::Expr) -> String {
+    match expr {
+        ast::Expr::BinExpr(_)
+        | ast::Expr::CastExpr(_)
+        | ast::Expr::ClosureExpr(_)
+        | ast::Expr::PrefixExpr(_)
+        | ast::Expr::RangeExpr(_)
+        | ast::Expr::RefExpr(_) => format!("({expr}).into_boxed_slice()"),
+        _ => format!("{expr}.into_boxed_slice()"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
+
+    #[test]
+    fn converts_field_and_construction_sites() {
+        check_assist_by_label(
+            convert_vec_field_to_boxed_slice,
+            r#"
+//- /main.rs crate:main deps:alloc
+use alloc::{boxed::Box, vec::Vec};
+struct Data {
+    id: u32,
+    items$0: Vec<u32>,
+}
+impl Data {
+    fn new(items: Vec<u32>) -> Self {
+        Data { id: 0, items }
+    }
+    fn empty() -> Self {
+        Self { items: Vec::new(), id: 1 }
+    }
+    fn reset(&mut self) {
+        self.items = Vec::new();
+    }
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+//- /alloc.rs crate:alloc
+pub mod boxed {
+    pub struct Box<T: ?Sized>(T);
+}
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn new() -> Self { loop {} }
+        pub fn len(&self) -> usize { 0 }
+    }
+}
+"#,
+            r#"
+use alloc::{boxed::Box, vec::Vec};
+struct Data {
+    id: u32,
+    items: Box<[u32]>,
+}
+impl Data {
+    fn new(items: Vec<u32>) -> Self {
+        Data { id: 0, items: items.into_boxed_slice() }
+    }
+    fn empty() -> Self {
+        Self { items: Vec::new().into_boxed_slice(), id: 1 }
+    }
+    fn reset(&mut self) {
+        self.items = Vec::new().into_boxed_slice();
+    }
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+"#,
+            "Convert to `Box<[T]>`, updating 3 uses",
+        );
+    }
+
+    #[test]
+    fn converts_construction_sites_in_other_files() {
+        check_assist_by_label(
+            convert_vec_field_to_boxed_slice,
+            r#"
+//- /main.rs crate:main deps:alloc
+mod other;
+use alloc::boxed::Box;
+pub struct Data {
+    pub items$0: alloc::vec::Vec<&'static str>,
+}
+//- /other.rs
+use alloc::vec::Vec;
+fn make(v: Option<Vec<&'static str>>) -> crate::Data {
+    crate::Data { items: match v { Some(v) => v, None => Vec::new() } }
+}
+//- /alloc.rs crate:alloc
+pub mod boxed {
+    pub struct Box<T: ?Sized>(T);
+}
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn new() -> Self { loop {} }
+    }
+}
+"#,
+            r#"
+//- /main.rs
+mod other;
+use alloc::boxed::Box;
+pub struct Data {
+    pub items: Box<[&'static str]>,
+}
+//- /other.rs
+use alloc::vec::Vec;
+fn make(v: Option<Vec<&'static str>>) -> crate::Data {
+    crate::Data { items: match v { Some(v) => v, None => Vec::new() }.into_boxed_slice() }
+}
+"#,
+            "Convert to `Box<[T]>`, updating 1 use",
+        );
+    }
+
+    #[test]
+    fn converts_values_moved_out() {
+        check_assist_by_label(
+            convert_vec_field_to_boxed_slice,
+            r#"
+//- /main.rs crate:main deps:alloc
+use alloc::{boxed::Box, vec::Vec};
+struct Data {
+    items$0: Vec<u32>,
+}
+fn take(_: Vec<u32>) {}
+fn split(d: Data, e: Data) -> Vec<u32> {
+    let first = &d.items;
+    let v: Vec<_> = e.items;
+    take(v);
+    d.items
+}
+//- /alloc.rs crate:alloc
+pub mod boxed {
+    pub struct Box<T: ?Sized>(T);
+}
+pub mod vec {
+    pub struct Vec<T>(T);
+}
+"#,
+            r#"
+use alloc::{boxed::Box, vec::Vec};
+struct Data {
+    items: Box<[u32]>,
+}
+fn take(_: Vec<u32>) {}
+fn split(d: Data, e: Data) -> Vec<u32> {
+    let first = &d.items;
+    let v: Vec<_> = e.items.into_vec();
+    take(v);
+    d.items.into_vec()
+}
+"#,
+            "Convert to `Box<[T]>`, updating 2 uses",
+        );
+    }
+
+    #[test]
+    fn uses_path_to_box_outside_of_prelude() {
+        check_assist(
+            convert_vec_field_to_boxed_slice,
+            r#"
+//- /main.rs crate:main deps:alloc
+struct Data {
+    items$0: alloc::vec::Vec<u32>,
+}
+//- /alloc.rs crate:alloc
+pub mod boxed {
+    pub struct Box<T: ?Sized>(T);
+}
+pub mod vec {
+    pub struct Vec<T>(T);
+}
+"#,
+            r#"
+struct Data {
+    items: alloc::boxed::Box<[u32]>,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_resized() {
+        cov_mark::check!(convert_vec_field_to_boxed_slice_resizing_use);
+        check_assist_not_applicable(
+            convert_vec_field_to_boxed_slice,
+            r#"
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+struct Data {
+    items$0: Vec<u32>,
+}
+fn add(data: &mut Data) {
+    data.items.push(1);
+}
+//- /alloc.rs crate:alloc
+pub mod boxed {
+    pub struct Box<T: ?Sized>(T);
+}
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn push(&mut self, value: T) {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_borrowed_mutably_as_vec() {
+        cov_mark::check!(convert_vec_field_to_boxed_slice_resizing_use);
+        check_assist_not_applicable(
+            convert_vec_field_to_boxed_slice,
+            r#"
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+struct Data {
+    items$0: Vec<u32>,
+}
+fn fill(v: &mut Vec<u32>) {}
+fn add(data: &mut Data) {
+    fill(&mut data.items);
+}
+//- /alloc.rs crate:alloc
+pub mod boxed {
+    pub struct Box<T: ?Sized>(T);
+}
+pub mod vec {
+    pub struct Vec<T>(T);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_other_types() {
+        check_assist_not_applicable(
+            convert_vec_field_to_boxed_slice,
+            r#"
+struct Vec<T>(T);
+struct Data {
+    items$0: Vec<u32>,
+}
+"#,
+        );
+    }
+}
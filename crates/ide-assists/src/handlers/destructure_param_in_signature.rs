@@ -0,0 +1,195 @@
+use ide_db::defs::Definition;
+use syntax::{
+    ast::{self, AstNode, AstToken},
+    SyntaxElement, TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: destructure_param_in_signature
+//
+// Moves a destructuring `let` at the start of a function body into the parameter it destructures.
+//
+// ```
+// struct Point { x: i32, y: i32 }
+//
+// fn length(p$0: Point) -> i32 {
+//     let Point { x, y } = p;
+//     x * x + y * y
+// }
+// ```
+// ->
+// ```
+// struct Point { x: i32, y: i32 }
+//
+// fn length(Point { x, y }: Point) -> i32 {
+//     x * x + y * y
+// }
+// ```
+pub(crate) fn destructure_param_in_signature(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let param = ctx.find_node_at_offset::<ast::Param>()?;
+    let ast::Pat::IdentPat(ident_pat) = param.pat()? else { return None };
+    if ident_pat.ref_token().is_some()
+        || ident_pat.mut_token().is_some()
+        || ident_pat.pat().is_some()
+    {
+        return None;
+    }
+    let func = param.syntax().ancestors().find_map(ast::Fn::cast)?;
+    let body = func.body()?;
+
+    let let_stmt = match body.stmt_list()?.statements().next()? {
+        ast::Stmt::LetStmt(it) => it,
+        _ => return None,
+    };
+    if let_stmt.ty().is_some() || let_stmt.let_else().is_some() {
+        return None;
+    }
+    let pat = let_stmt.pat()?;
+    if !matches!(pat, ast::Pat::RecordPat(_) | ast::Pat::TuplePat(_) | ast::Pat::TupleStructPat(_))
+    {
+        return None;
+    }
+    let ast::Expr::PathExpr(init) = let_stmt.initializer()? else { return None };
+
+    // The destructuring has to be the only use of the parameter.
+    let local = ctx.sema.to_def(&ident_pat)?;
+    let usages = Definition::Local(local).usages(&ctx.sema).all();
+    let mut references = usages.iter().flat_map(|(_, refs)| refs);
+    match (references.next(), references.next()) {
+        (Some(reference), None) if init.syntax().text_range().contains_range(reference.range) => {}
+        _ => return None,
+    }
+
+    acc.add(
+        AssistId("destructure_param_in_signature", AssistKind::RefactorRewrite),
+        "Destructure parameter in signature",
+        param.syntax().text_range(),
+        |builder| {
+            builder.replace(ident_pat.syntax().text_range(), pat.syntax().to_string());
+            let delete_range = match let_stmt
+                .syntax()
+                .prev_sibling_or_token()
+                .and_then(SyntaxElement::into_token)
+                .and_then(ast::Whitespace::cast)
+            {
+                Some(whitespace) => TextRange::new(
+                    whitespace.syntax().text_range().start(),
+                    let_stmt.syntax().text_range().end(),
+                ),
+                None => let_stmt.syntax().text_range(),
+            };
+            builder.delete(delete_range);
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn record_pattern() {
+        check_assist(
+            destructure_param_in_signature,
+            r#"
+struct Point { x: i32, y: i32 }
+fn f(a: i32, $0p: Point) -> i32 {
+    let Point { x, y: mut height } = p;
+    height += a;
+    x + height
+}
+"#,
+            r#"
+struct Point { x: i32, y: i32 }
+fn f(a: i32, Point { x, y: mut height }: Point) -> i32 {
+    height += a;
+    x + height
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn tuple_patterns() {
+        check_assist(
+            destructure_param_in_signature,
+            r#"
+fn f(pair$0: (i32, i32)) -> i32 {
+    let (a, _) = pair;
+    a
+}
+"#,
+            r#"
+fn f((a, _): (i32, i32)) -> i32 {
+    a
+}
+"#,
+        );
+        check_assist(
+            destructure_param_in_signature,
+            r#"
+struct Wrapper(u8);
+fn f(w$0: Wrapper) {
+    let Wrapper(inner) = w;
+}
+"#,
+            r#"
+struct Wrapper(u8);
+fn f(Wrapper(inner): Wrapper) {
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_used_elsewhere() {
+        check_assist_not_applicable(
+            destructure_param_in_signature,
+            r#"
+#[derive(Clone, Copy)]
+struct Point { x: i32, y: i32 }
+fn f(p$0: Point) -> Point {
+    let Point { x, y } = p;
+    p
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_not_immediate() {
+        check_assist_not_applicable(
+            destructure_param_in_signature,
+            r#"
+struct Point { x: i32, y: i32 }
+fn f(p$0: Point) {
+    let _ = 1;
+    let Point { x, y } = p;
+}
+"#,
+        );
+        check_assist_not_applicable(
+            destructure_param_in_signature,
+            r#"
+struct Point { x: i32, y: i32 }
+fn f(p$0: Point, q: Point) {
+    let Point { x, y } = q;
+}
+"#,
+        );
+        check_assist_not_applicable(
+            destructure_param_in_signature,
+            r#"
+fn f(p$0: (i32, i32)) {
+    let a = p;
+}
+"#,
+        );
+    }
+}
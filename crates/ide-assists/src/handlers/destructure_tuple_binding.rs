@@ -1,7 +1,10 @@
+use std::iter;
+
 use ide_db::{
     assists::{AssistId, AssistKind},
     defs::Definition,
     search::{FileReference, SearchScope, UsageSearchResult},
+    FxHashSet,
 };
 use itertools::Itertools;
 use syntax::{
@@ -12,7 +15,7 @@ use text_edit::TextRange;
 
 use crate::{
     assist_context::{AssistContext, Assists, SourceChangeBuilder},
-    utils::ref_field_expr::determine_ref_and_parens,
+    utils::{ref_field_expr::determine_ref_and_parens, suggest_name},
 };
 
 // Assist: destructure_tuple_binding
@@ -131,22 +134,68 @@ fn collect_data(ident_pat: IdentPat, ctx: &AssistContext<'_>) -> Option<TupleDat
             .all()
     });
 
-    let field_names = (0..field_types.len())
-        .map(|i| generate_name(ctx, i, &name, &ident_pat, &usages))
+    let mut taken_names = FxHashSet::default();
+    taken_names.insert(name);
+    // The initializer sees all locals that are in scope right before the new bindings, which
+    // these must not shadow. The usages may additionally see locals declared later on, which
+    // would capture the rewritten usages.
+    let scope_node = ident_pat
+        .syntax()
+        .parent()
+        .and_then(ast::LetStmt::cast)
+        .and_then(|let_stmt| let_stmt.initializer())
+        .map_or_else(|| ident_pat.syntax().clone(), |it| it.syntax().clone());
+    let usage_nodes = usages
+        .iter()
+        .flat_map(|usages| usages.iter())
+        .flat_map(|(_, refs)| refs)
+        .filter_map(|it| it.name.clone().into_name_like())
+        .map(|it| it.syntax().clone());
+    for node in iter::once(scope_node).chain(usage_nodes) {
+        let Some(scope) = ctx.sema.scope(&node) else { continue };
+        scope.process_all_names(&mut |name, def| {
+            if let hir::ScopeDef::Local(_) = def {
+                taken_names.insert(name.display(ctx.db()).to_string());
+            }
+        });
+    }
+    let field_names = field_types
+        .iter()
+        .enumerate()
+        .map(|(index, ty)| generate_name(ctx, index, ty, &field_types, &mut taken_names))
         .collect::<Vec<_>>();
 
     Some(TupleData { ident_pat, ref_type, field_names, usages })
 }
 
+/// Names a tuple field after its type if that name is unambiguous, falling back to `_{index}`.
+/// Never picks a name of an existing local, as the new binding would shadow it.
 fn generate_name(
-    _ctx: &AssistContext<'_>,
+    ctx: &AssistContext<'_>,
     index: usize,
-    _tuple_name: &str,
-    _ident_pat: &IdentPat,
-    _usages: &Option<UsageSearchResult>,
+    ty: &hir::Type,
+    field_types: &[hir::Type],
+    taken_names: &mut FxHashSet<String>,
 ) -> String {
-    // FIXME: detect if name already used
-    format!("_{index}")
+    let type_name = suggest_name::for_type(ty, ctx.db()).filter(|name| {
+        field_types
+            .iter()
+            .filter(|ty| suggest_name::for_type(ty, ctx.db()).as_ref() == Some(name))
+            .count()
+            == 1
+    });
+    let base = match type_name {
+        Some(name) if !taken_names.contains(&name) => name,
+        _ => format!("_{index}"),
+    };
+    let mut name = base.clone();
+    let mut count = 0;
+    while taken_names.contains(&name) {
+        name = format!("{base}_{count}");
+        count += 1;
+    }
+    taken_names.insert(name.clone());
+    name
 }
 
 enum RefType {
@@ -562,6 +611,119 @@ fn main() {
         )
     }
 
+    #[test]
+    fn names_from_element_types() {
+        check_assist(
+            assist,
+            r#"
+struct Point;
+struct Size;
+fn returns_tuple() -> (Point, Size, u32) { loop {} }
+fn main() {
+    let $0t = returns_tuple();
+    let v = t.1;
+}
+            "#,
+            r#"
+struct Point;
+struct Size;
+fn returns_tuple() -> (Point, Size, u32) { loop {} }
+fn main() {
+    let ($0point, size, _2) = returns_tuple();
+    let v = size;
+}
+            "#,
+        )
+    }
+
+    #[test]
+    fn ambiguous_element_type_names_fall_back_to_index() {
+        check_assist(
+            assist,
+            r#"
+struct Point;
+fn returns_tuple() -> (Point, &'static Point) { loop {} }
+fn main() {
+    let $0t = returns_tuple();
+}
+            "#,
+            r#"
+struct Point;
+fn returns_tuple() -> (Point, &'static Point) { loop {} }
+fn main() {
+    let ($0_0, _1) = returns_tuple();
+}
+            "#,
+        )
+    }
+
+    #[test]
+    fn dont_shadow_existing_locals() {
+        check_assist(
+            assist,
+            r#"
+struct Point;
+fn main() {
+    let point = Point;
+    let _0 = 0;
+    let $0t = (Point, 1, 2);
+    let v = (point, _0, t.1);
+}
+            "#,
+            r#"
+struct Point;
+fn main() {
+    let point = Point;
+    let _0 = 0;
+    let ($0_0_0, _1, _2) = (Point, 1, 2);
+    let v = (point, _0, _1);
+}
+            "#,
+        )
+    }
+
+    #[test]
+    fn dont_get_shadowed_by_later_locals() {
+        check_assist(
+            assist,
+            r#"
+struct Point;
+fn main() {
+    let $0t = (Point, 1);
+    let point = 5;
+    let _1 = 2;
+    let v = (t.0, t.1, point, _1);
+}
+            "#,
+            r#"
+struct Point;
+fn main() {
+    let ($0_0, _1_0) = (Point, 1);
+    let point = 5;
+    let _1 = 2;
+    let v = (_0, _1_0, point, _1);
+}
+            "#,
+        )
+    }
+
+    #[test]
+    fn destructure_nested_tuple_only_top_level() {
+        check_assist(
+            assist,
+            r#"
+fn main() {
+    let $0t = ((1, 2), 3);
+}
+            "#,
+            r#"
+fn main() {
+    let ($0_0, _1) = ((1, 2), 3);
+}
+            "#,
+        )
+    }
+
     #[test]
     fn keep_type() {
         check_assist(
@@ -1769,14 +1931,14 @@ struct S4 {
 }
 
 fn foo() -> Option<()> {
-    let ($0_0, _1, _2, _3, _4, _5) = &(0, (1,"1"), Some(2), [3;3], S4 { value: 4 }, &5);
+    let ($0_0, _1, _2, _3, s4, _5) = &(0, (1,"1"), Some(2), [3;3], S4 { value: 4 }, &5);
     let v: i32 = *_0;           // deref, no parens
     let v: &i32 = _0;         // no deref, no parens, remove `&`
     f1(*_0);                    // deref, no parens
     f2(_0);                   // `&*` -> cancel out -> no deref, no parens
     // https://github.com/rust-lang/rust-analyzer/issues/1109#issuecomment-658868639
     // let v: i32 = t.1.0;      // no deref, no parens
-    let v: i32 = _4.value;     // no deref, no parens
+    let v: i32 = s4.value;     // no deref, no parens
     (*_0).do_stuff();             // deref, parens
     let v: i32 = (*_2)?;          // deref, parens
     let v: i32 = _3[0];        // no deref, no parens
@@ -1815,8 +1977,8 @@ impl S {
 }
 
 fn main() {
-    let ($0_0, _1) = &(S,2);
-    let s = _0.f();
+    let ($0s, _1) = &(S,2);
+    let s = s.f();
 }
                 "#,
             )
@@ -1845,8 +2007,8 @@ impl S {
 }
 
 fn main() {
-    let ($0_0, _1) = &(S,2);
-    let s = (*_0).f();
+    let ($0s, _1) = &(S,2);
+    let s = (*s).f();
 }
                 "#,
             )
@@ -1882,8 +2044,8 @@ impl T for &S {
 }
 
 fn main() {
-    let ($0_0, _1) = &(S,2);
-    let s = (*_0).f();
+    let ($0s, _1) = &(S,2);
+    let s = (*s).f();
 }
                 "#,
             )
@@ -1923,8 +2085,8 @@ impl T for &S {
 }
 
 fn main() {
-    let ($0_0, _1) = &(S,2);
-    let s = (*_0).f();
+    let ($0s, _1) = &(S,2);
+    let s = (*s).f();
 }
                 "#,
             )
@@ -1,8 +1,10 @@
 use either::Either;
+use hir::PathResolution;
 use ide_db::syntax_helpers::node_ext::walk_ty;
+use itertools::Itertools;
 use syntax::{
     ast::{self, edit::IndentLevel, make, AstNode, HasGenericParams, HasName},
-    ted,
+    ted, NodeOrToken, SyntaxNode,
 };
 
 use crate::{AssistContext, AssistId, AssistKind, Assists};
@@ -11,6 +13,9 @@ use crate::{AssistContext, AssistId, AssistKind, Assists};
 //
 // Extracts the selected type as a type alias.
 //
+// If the same type occurs several times in the enclosing module, all occurrences can be replaced
+// by the new alias at once.
+//
 // ```
 // struct S {
 //     field: $0(u8, u8, u8)$0,
@@ -39,6 +44,22 @@ pub(crate) fn extract_type_alias(acc: &mut Assists, ctx: &AssistContext<'_>) ->
     );
     let target = ty.syntax().text_range();
 
+    let mut known_generics = match item.generic_param_list() {
+        Some(it) => it.generic_params().collect(),
+        None => Vec::new(),
+    };
+    if let Some(it) = assoc_owner.as_ref().and_then(|it| match it {
+        Either::Left(it) => it.generic_param_list(),
+        Either::Right(it) => it.generic_param_list(),
+    }) {
+        known_generics.extend(it.generic_params());
+    }
+    let generics = collect_used_generics(&ty, &known_generics);
+    let generic_params = generics.map(|it| make::generic_param_list(it.into_iter().cloned()));
+    let ty_args =
+        generic_params.as_ref().map_or(String::new(), |it| it.to_generic_args().to_string());
+    let occurrences = find_occurrences(ctx, &ty);
+
     acc.add(
         AssistId("extract_type_alias", AssistKind::RefactorExtract),
         "Extract type as type alias",
@@ -47,34 +68,55 @@ pub(crate) fn extract_type_alias(acc: &mut Assists, ctx: &AssistContext<'_>) ->
             let node = edit.make_syntax_mut(node.clone());
             let target_ty = edit.make_mut(ty.clone());
 
-            let mut known_generics = match item.generic_param_list() {
-                Some(it) => it.generic_params().collect(),
-                None => Vec::new(),
-            };
-            if let Some(it) = assoc_owner.as_ref().and_then(|it| match it {
-                Either::Left(it) => it.generic_param_list(),
-                Either::Right(it) => it.generic_param_list(),
-            }) {
-                known_generics.extend(it.generic_params());
-            }
-            let generics = collect_used_generics(&ty, &known_generics);
-            let generic_params =
-                generics.map(|it| make::generic_param_list(it.into_iter().cloned()));
-
             // Replace original type with the alias
-            let ty_args = generic_params
-                .as_ref()
-                .map_or(String::new(), |it| it.to_generic_args().to_string());
             // FIXME: replace with a `ast::make` constructor
             let new_ty = make::ty(&format!("Type{ty_args}")).clone_for_update();
             ted::replace(target_ty.syntax(), new_ty.syntax());
 
             // Insert new alias
             let indent = IndentLevel::from_node(&node);
+            let ty_alias = make::ty_alias(
+                "Type",
+                generic_params.clone(),
+                None,
+                None,
+                Some((ty.clone(), None)),
+            )
+            .clone_for_update();
+            ted::insert_all(
+                ted::Position::before(node),
+                vec![
+                    ty_alias.syntax().clone().into(),
+                    make::tokens::whitespace(&format!("\n\n{indent}")).into(),
+                ],
+            );
+
+            if let Some(cap) = ctx.config.snippet_cap {
+                if let Some(name) = ty_alias.name() {
+                    edit.add_tabstop_before(cap, name);
+                }
+            }
+        },
+    );
+
+    let (anchor, occurrences) = occurrences?;
+    acc.add(
+        AssistId("extract_type_alias", AssistKind::RefactorExtract),
+        "Extract all occurrences of type as type alias",
+        target,
+        |edit| {
+            let occurrences: Vec<_> = occurrences.into_iter().map(|it| edit.make_mut(it)).collect();
+            let anchor = edit.make_syntax_mut(anchor);
+            for occurrence in occurrences {
+                let new_ty = make::ty(&format!("Type{ty_args}")).clone_for_update();
+                ted::replace(occurrence.syntax(), new_ty.syntax());
+            }
+
+            let indent = IndentLevel::from_node(&anchor);
             let ty_alias = make::ty_alias("Type", generic_params, None, None, Some((ty, None)))
                 .clone_for_update();
             ted::insert_all(
-                ted::Position::before(node),
+                ted::Position::before(anchor),
                 vec![
                     ty_alias.syntax().clone().into(),
                     make::tokens::whitespace(&format!("\n\n{indent}")).into(),
@@ -90,6 +132,75 @@ pub(crate) fn extract_type_alias(acc: &mut Assists, ctx: &AssistContext<'_>) ->
     )
 }
 
+/// Finds all types in the module enclosing `ty` that are structurally equal to it, returning the
+/// module-level item the alias has to be inserted before together with the occurrences in source
+/// order.
+///
+/// Two types are considered equal if they consist of the same tokens and all paths in them resolve
+/// to the same items, except for paths referring to generic parameters, which become parameters of
+/// the alias.
+fn find_occurrences(
+    ctx: &AssistContext<'_>,
+    ty: &ast::Type,
+) -> Option<(SyntaxNode, Vec<ast::Type>)> {
+    // Aliases of `impl Trait` types aren't stable.
+    if ty.syntax().descendants().any(|it| ast::ImplTraitType::can_cast(it.kind())) {
+        return None;
+    }
+    let scope = ty
+        .syntax()
+        .ancestors()
+        .find(|it| ast::SourceFile::can_cast(it.kind()) || ast::ItemList::can_cast(it.kind()))?;
+    let tokens = significant_tokens(ty.syntax());
+    let resolutions = path_resolutions(ctx, ty);
+
+    let occurrences: Vec<_> = scope
+        .descendants()
+        .filter_map(ast::Type::cast)
+        .filter(|it| {
+            // Nested modules don't see the alias, anything in a macro call is opaque to us, and
+            // bounds in where clauses are kept as written.
+            !it.syntax().ancestors().take_while(|it| *it != scope).any(|it| {
+                ast::ItemList::can_cast(it.kind())
+                    || ast::MacroCall::can_cast(it.kind())
+                    || ast::WhereClause::can_cast(it.kind())
+            })
+        })
+        .filter(|it| significant_tokens(it.syntax()) == tokens)
+        .filter(|it| {
+            let other = path_resolutions(ctx, it);
+            resolutions.len() == other.len()
+                && resolutions.iter().zip(&other).all(|(res, other)| match res {
+                    Some(PathResolution::TypeParam(_) | PathResolution::ConstParam(_)) => true,
+                    res => res == other,
+                })
+        })
+        .collect();
+    if occurrences.len() < 2 || !occurrences.contains(ty) {
+        return None;
+    }
+
+    let anchor =
+        occurrences[0].syntax().ancestors().find(|it| it.parent().as_ref() == Some(&scope))?;
+    Some((anchor, occurrences))
+}
+
+fn path_resolutions(ctx: &AssistContext<'_>, ty: &ast::Type) -> Vec<Option<PathResolution>> {
+    ty.syntax()
+        .descendants()
+        .filter_map(ast::Path::cast)
+        .map(|it| ctx.sema.resolve_path(&it))
+        .collect()
+}
+
+fn significant_tokens(node: &SyntaxNode) -> Vec<String> {
+    node.descendants_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .filter(|it| !it.kind().is_trivia())
+        .map(|it| it.text().to_owned())
+        .collect()
+}
+
 fn collect_used_generics<'gp>(
     ty: &ast::Type,
     known_generics: &'gp [ast::GenericParam],
@@ -178,6 +289,8 @@ fn collect_used_generics<'gp>(
         };
         false
     });
+    // a parameter may be used several times
+    let mut generics: Vec<_> = generics.into_iter().unique().collect();
     // stable resort to lifetime, type, const
     generics.sort_by_key(|gp| match gp {
         ast::GenericParam::ConstParam(_) => 2,
@@ -190,7 +303,10 @@ fn collect_used_generics<'gp>(
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_not_applicable};
+    use crate::tests::{
+        check_assist, check_assist_by_label, check_assist_not_applicable,
+        check_assist_not_applicable_by_label,
+    };
 
     use super::*;
 
@@ -388,4 +504,103 @@ where
             "#,
         );
     }
+
+    #[test]
+    fn extract_all_occurrences() {
+        check_assist_by_label(
+            extract_type_alias,
+            r#"
+struct Foo;
+struct HashMap<K, V>(K, V);
+
+struct S {
+    map: $0HashMap<u32, Vec<(u8, Option<Foo>)>>$0,
+}
+
+impl S {
+    fn get(&self) -> &HashMap<u32, Vec<(u8, Option<Foo>)>> {
+        let map: &HashMap<u32, Vec<(u8, Option<Foo>)>> = &self.map;
+        map
+    }
+}
+
+fn other(_: HashMap<u32, Vec<(u16, Option<Foo>)>>) {}
+"#,
+            r#"
+struct Foo;
+struct HashMap<K, V>(K, V);
+
+type $0Type = HashMap<u32, Vec<(u8, Option<Foo>)>>;
+
+struct S {
+    map: Type,
+}
+
+impl S {
+    fn get(&self) -> &Type {
+        let map: &Type = &self.map;
+        map
+    }
+}
+
+fn other(_: HashMap<u32, Vec<(u16, Option<Foo>)>>) {}
+"#,
+            "Extract all occurrences of type as type alias",
+        );
+    }
+
+    #[test]
+    fn extract_all_occurrences_with_generics() {
+        check_assist_by_label(
+            extract_type_alias,
+            r#"
+struct Pair<A, B>(A, B);
+
+fn first<T>(_: Pair<T, Vec<T>>) {}
+fn second<T>() -> $0Pair<T, Vec<T>>$0 { loop {} }
+"#,
+            r#"
+struct Pair<A, B>(A, B);
+
+type $0Type<T> = Pair<T, Vec<T>>;
+
+fn first<T>(_: Type<T>) {}
+fn second<T>() -> Type<T> { loop {} }
+"#,
+            "Extract all occurrences of type as type alias",
+        );
+    }
+
+    #[test]
+    fn extract_all_occurrences_skips_other_scopes() {
+        check_assist_not_applicable_by_label(
+            extract_type_alias,
+            r#"
+struct Foo;
+
+fn f(_: $0Option<Foo>$0) {}
+
+mod m {
+    struct Foo;
+
+    fn f(_: Option<Foo>) {}
+}
+"#,
+            "Extract all occurrences of type as type alias",
+        );
+        check_assist_not_applicable_by_label(
+            extract_type_alias,
+            r#"
+struct Foo;
+
+fn f(_: $0Option<Foo>$0) {}
+
+fn g() {
+    struct Foo;
+    let _: Option<Foo>;
+}
+"#,
+            "Extract all occurrences of type as type alias",
+        );
+    }
 }
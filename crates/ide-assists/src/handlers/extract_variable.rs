@@ -1,7 +1,11 @@
-use hir::TypeInfo;
+use hir::{Semantics, TypeInfo};
+use ide_db::{
+    defs::{Definition, NameRefClass},
+    RootDatabase,
+};
 use syntax::{
     ast::{self, edit::IndentLevel, edit_in_place::Indent, make, AstNode, HasName},
-    ted, NodeOrToken,
+    match_ast, ted, NodeOrToken,
     SyntaxKind::{BLOCK_EXPR, BREAK_EXPR, COMMENT, LOOP_EXPR, MATCH_GUARD, PATH_EXPR, RETURN_EXPR},
     SyntaxNode,
 };
@@ -12,6 +16,9 @@ use crate::{utils::suggest_name, AssistContext, AssistId, AssistKind, Assists};
 //
 // Extracts subexpression into a variable.
 //
+// If the same side-effect free expression occurs several times in the enclosing block, all
+// occurrences can be replaced by the new variable at once.
+//
 // ```
 // fn main() {
 //     $0(1 + 2)$0 * 4;
@@ -64,6 +71,7 @@ pub(crate) fn extract_variable(acc: &mut Assists, ctx: &AssistContext<'_>) -> Op
 
     let anchor = Anchor::from(&to_extract)?;
     let target = to_extract.syntax().text_range();
+    let occurrences = ty.as_ref().and_then(|ty| find_occurrences(ctx, &to_extract, ty));
     acc.add(
         AssistId("extract_variable", AssistKind::RefactorExtract),
         "Extract into variable",
@@ -198,9 +206,225 @@ pub(crate) fn extract_variable(acc: &mut Assists, ctx: &AssistContext<'_>) -> Op
                 }
             }
         },
+    );
+
+    let (anchor, occurrences) = occurrences?;
+    acc.add(
+        AssistId("extract_variable", AssistKind::RefactorExtract),
+        "Extract all occurrences into variable",
+        target,
+        move |edit| {
+            let var_name = suggest_name::for_variable(&occurrences[0], &ctx.sema);
+            let prev_ws = anchor.prev_sibling_or_token().and_then(|it| it.into_token());
+            let indent_to = IndentLevel::from_node(&anchor);
+
+            let ident_pat = make::ident_pat(false, false, make::name(&var_name));
+            let let_stmt =
+                make::let_stmt(ident_pat.into(), None, Some(occurrences[0].clone_subtree()))
+                    .clone_for_update();
+
+            let occurrences: Vec<_> =
+                occurrences.into_iter().map(|expr| edit.make_mut(expr)).collect();
+            let insert_place = edit.make_syntax_mut(anchor);
+            for expr in occurrences {
+                let name_expr =
+                    make::expr_path(make::ext::ident_path(&var_name)).clone_for_update();
+                ted::replace(expr.syntax(), name_expr.syntax());
+            }
+
+            let trailing_ws = if prev_ws.is_some_and(|it| it.text().starts_with('\n')) {
+                format!("\n{indent_to}")
+            } else {
+                " ".to_owned()
+            };
+            ted::insert_all_raw(
+                ted::Position::before(insert_place),
+                vec![
+                    let_stmt.syntax().clone().into(),
+                    make::tokens::whitespace(&trailing_ws).into(),
+                ],
+            );
+
+            if let Some(cap) = ctx.config.snippet_cap {
+                if let Some(ast::Pat::IdentPat(ident_pat)) = let_stmt.pat() {
+                    if let Some(name) = ident_pat.name() {
+                        edit.add_tabstop_before(cap, name);
+                    }
+                }
+            }
+        },
     )
 }
 
+/// Finds all occurrences of `to_extract` in its innermost enclosing block, returning the statement
+/// the new `let` has to be inserted before together with the occurrences in source order.
+///
+/// Only expressions which are free of side effects, can't panic and whose value can't change
+/// between the occurrences are considered, that is field accesses of immutable locals, constants,
+/// literals, and builtin operators that can't overflow applied to them. Occurrences that are only
+/// evaluated conditionally, e.g. in an `if` branch or a match arm, are never hoisted out.
+fn find_occurrences(
+    ctx: &AssistContext<'_>,
+    to_extract: &ast::Expr,
+    ty: &hir::Type,
+) -> Option<(SyntaxNode, Vec<ast::Expr>)> {
+    if !ty.is_copy(ctx.db()) || !is_pure(&ctx.sema, to_extract) {
+        return None;
+    }
+    let stmt_list = to_extract.syntax().ancestors().find_map(ast::StmtList::cast)?;
+    if is_conditionally_evaluated(to_extract.syntax(), &stmt_list) {
+        return None;
+    }
+    let defs = referenced_defs(&ctx.sema, to_extract)?;
+    let tokens = significant_tokens(to_extract.syntax());
+
+    let occurrences: Vec<_> = stmt_list
+        .syntax()
+        .descendants()
+        .filter_map(ast::Expr::cast)
+        .filter(|expr| {
+            // Nested items have their own scope, and anything in a macro call is opaque to us.
+            !expr
+                .syntax()
+                .ancestors()
+                .take_while(|it| it != stmt_list.syntax())
+                .any(|it| ast::Item::can_cast(it.kind()) || ast::MacroCall::can_cast(it.kind()))
+        })
+        .filter(|expr| significant_tokens(expr.syntax()) == tokens)
+        .filter(|expr| referenced_defs(&ctx.sema, expr).as_ref() == Some(&defs))
+        .filter(|expr| !is_conditionally_evaluated(expr.syntax(), &stmt_list))
+        .collect();
+    if occurrences.len() < 2 {
+        return None;
+    }
+
+    let anchor = occurrences[0]
+        .syntax()
+        .ancestors()
+        .find(|it| it.parent().as_ref() == Some(stmt_list.syntax()))?;
+    let defined_before_anchor = defs.iter().all(|def| match def {
+        Definition::Local(local) => {
+            let src = local.primary_source(ctx.db());
+            src.file() == ctx.file_id().into()
+                && !anchor.text_range().contains_range(src.syntax().text_range())
+        }
+        _ => true,
+    });
+    defined_before_anchor.then_some((anchor, occurrences))
+}
+
+/// Whether `node` is only evaluated under some condition, or not at all, when the statements of
+/// `stmt_list` are executed.
+fn is_conditionally_evaluated(node: &SyntaxNode, stmt_list: &ast::StmtList) -> bool {
+    let is_not = |child: &SyntaxNode, expr: Option<ast::Expr>| {
+        expr.map_or(true, |expr| expr.syntax() != child)
+    };
+    let mut child = node.clone();
+    while let Some(parent) = child.parent() {
+        if &parent == stmt_list.syntax() {
+            break;
+        }
+        let conditional = match_ast! {
+            match (parent.clone()) {
+                ast::IfExpr(it) => is_not(&child, it.condition()),
+                ast::MatchExpr(it) => is_not(&child, it.expr()),
+                ast::WhileExpr(it) => is_not(&child, it.condition()),
+                ast::ForExpr(it) => is_not(&child, it.iterable()),
+                ast::LoopExpr(_) => true,
+                ast::ClosureExpr(_) => true,
+                ast::LetElse(_) => true,
+                ast::BlockExpr(it) => it.async_token().is_some() || it.try_token().is_some(),
+                ast::BinExpr(it) => {
+                    matches!(it.op_kind(), Some(ast::BinaryOp::LogicOp(_)))
+                        && !is_not(&child, it.rhs())
+                },
+                _ => false,
+            }
+        };
+        if conditional {
+            return true;
+        }
+        child = parent;
+    }
+    false
+}
+
+fn is_pure(sema: &Semantics<'_, RootDatabase>, expr: &ast::Expr) -> bool {
+    let is_builtin_operand = |expr: Option<ast::Expr>| {
+        expr.map_or(false, |expr| {
+            is_pure(sema, &expr)
+                && sema.type_of_expr(&expr).map_or(false, |ty| ty.original.is_scalar())
+        })
+    };
+    let is_float_operand = |expr: Option<ast::Expr>| {
+        expr.map_or(false, |expr| {
+            is_pure(sema, &expr)
+                && sema.type_of_expr(&expr).map_or(false, |ty| ty.original.is_float())
+        })
+    };
+    match expr {
+        ast::Expr::Literal(_) => true,
+        ast::Expr::ParenExpr(expr) => expr.expr().map_or(false, |expr| is_pure(sema, &expr)),
+        ast::Expr::FieldExpr(expr) => expr.expr().map_or(false, |expr| is_pure(sema, &expr)),
+        ast::Expr::CastExpr(expr) => is_builtin_operand(expr.expr()),
+        ast::Expr::PrefixExpr(expr) => match expr.op_kind() {
+            Some(ast::UnaryOp::Not) => is_builtin_operand(expr.expr()),
+            // Negating the minimum value of a signed integer overflows.
+            Some(ast::UnaryOp::Neg) => is_float_operand(expr.expr()),
+            Some(ast::UnaryOp::Deref) | None => false,
+        },
+        ast::Expr::BinExpr(expr) => match expr.op_kind() {
+            // Integer arithmetic can overflow or divide by zero, so it may panic.
+            Some(ast::BinaryOp::ArithOp(
+                ast::ArithOp::BitXor | ast::ArithOp::BitOr | ast::ArithOp::BitAnd,
+            ))
+            | Some(ast::BinaryOp::CmpOp(_) | ast::BinaryOp::LogicOp(_)) => {
+                is_builtin_operand(expr.lhs()) && is_builtin_operand(expr.rhs())
+            }
+            Some(ast::BinaryOp::ArithOp(_)) => {
+                is_float_operand(expr.lhs()) && is_float_operand(expr.rhs())
+            }
+            Some(ast::BinaryOp::Assignment { .. }) | None => false,
+        },
+        ast::Expr::PathExpr(expr) => {
+            let Some(name_ref) = expr.path().and_then(|it| it.as_single_name_ref()) else {
+                return false;
+            };
+            match NameRefClass::classify(sema, &name_ref) {
+                Some(NameRefClass::Definition(Definition::Local(local))) => {
+                    !local.is_mut(sema.db) && !local.ty(sema.db).is_mutable_reference()
+                }
+                Some(NameRefClass::Definition(Definition::Const(_))) => true,
+                Some(NameRefClass::Definition(Definition::Static(it))) => !it.is_mut(sema.db),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn referenced_defs(
+    sema: &Semantics<'_, RootDatabase>,
+    expr: &ast::Expr,
+) -> Option<Vec<Definition>> {
+    expr.syntax()
+        .descendants()
+        .filter_map(ast::NameRef::cast)
+        .map(|name_ref| match NameRefClass::classify(sema, &name_ref)? {
+            NameRefClass::Definition(def) => Some(def),
+            _ => None,
+        })
+        .collect()
+}
+
+fn significant_tokens(node: &SyntaxNode) -> Vec<String> {
+    node.descendants_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .filter(|it| !it.kind().is_trivia())
+        .map(|it| it.text().to_owned())
+        .collect()
+}
+
 /// Check whether the node is a valid expression which can be extracted to a variable.
 /// In general that's true for any expression, but in some cases that would produce invalid code.
 fn valid_target_expr(node: SyntaxNode) -> Option<ast::Expr> {
@@ -271,7 +495,10 @@ impl Anchor {
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_not_applicable, check_assist_target};
+    use crate::tests::{
+        check_assist, check_assist_by_label, check_assist_not_applicable,
+        check_assist_not_applicable_by_label, check_assist_target,
+    };
 
     use super::*;
 
@@ -1460,4 +1687,240 @@ fn foo() {
 }"#,
         );
     }
+
+    #[test]
+    fn test_extract_var_all_occurrences() {
+        check_assist_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy
+struct S { a: f64, b: f64 }
+fn foo(s: S) -> f64 {
+    let x = $0s.a * 2.0$0 + 1.0;
+    let y = s.b - s.a *  2.0;
+    x + y + s.a * 2.0
+}
+"#,
+            r#"
+struct S { a: f64, b: f64 }
+fn foo(s: S) -> f64 {
+    let $0var_name = s.a * 2.0;
+    let x = var_name + 1.0;
+    let y = s.b - var_name;
+    x + y + var_name
+}
+"#,
+            "Extract all occurrences into variable",
+        );
+    }
+
+    #[test]
+    fn test_extract_var_all_occurrences_before_first() {
+        check_assist_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy
+const N: usize = 4;
+fn foo(len: usize) {
+    bar(len | N);
+    bar($0len | N$0);
+}
+fn bar(_: usize) {}
+"#,
+            r#"
+const N: usize = 4;
+fn foo(len: usize) {
+    let $0var_name = len | N;
+    bar(var_name);
+    bar(var_name);
+}
+fn bar(_: usize) {}
+"#,
+            "Extract all occurrences into variable",
+        );
+    }
+
+    #[test]
+    fn test_extract_var_all_occurrences_not_in_conditional_branches() {
+        check_assist_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy, option
+struct P { x: u32 }
+fn foo(p: P, o: Option<u32>) -> u32 {
+    let w = bar(p.x & 0xff);
+    if $0p.x & 0xff$0 == w || p.x & 0xff > 8 {
+        return p.x & 0xff;
+    }
+    match o {
+        Some(v) => v & (p.x & 0xff),
+        None => p.x & 0xff,
+    }
+}
+fn bar(width: u32) -> u32 { width }
+"#,
+            r#"
+struct P { x: u32 }
+fn foo(p: P, o: Option<u32>) -> u32 {
+    let $0width = p.x & 0xff;
+    let w = bar(width);
+    if width == w || p.x & 0xff > 8 {
+        return p.x & 0xff;
+    }
+    match o {
+        Some(v) => v & (p.x & 0xff),
+        None => p.x & 0xff,
+    }
+}
+fn bar(width: u32) -> u32 { width }
+"#,
+            "Extract all occurrences into variable",
+        );
+    }
+
+    #[test]
+    fn test_extract_var_all_occurrences_in_match_arms() {
+        check_assist_not_applicable_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy, option
+struct P { x: u32 }
+fn foo(p: P, o: Option<u32>) -> u32 {
+    match o {
+        Some(v) => bar(v, $0p.x | 1$0),
+        None => p.x | 1,
+    }
+}
+fn bar(a: u32, width: u32) -> u32 { a | width }
+"#,
+            "Extract all occurrences into variable",
+        );
+    }
+
+    #[test]
+    fn test_extract_var_all_occurrences_single_occurrence() {
+        check_assist_not_applicable_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy
+fn foo(a: i32) -> i32 {
+    let b = a + 1;
+    $0a + 2$0
+}
+"#,
+            "Extract all occurrences into variable",
+        );
+    }
+
+    #[test]
+    fn test_extract_var_all_occurrences_not_pure() {
+        check_assist_not_applicable_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy
+fn bar() -> i32 { 0 }
+fn foo() -> i32 {
+    let a = $0bar() + 1$0;
+    a + bar() + 1
+}
+"#,
+            "Extract all occurrences into variable",
+        );
+        check_assist_not_applicable_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy
+fn foo(mut a: i32) -> i32 {
+    let b = $0a + 1$0;
+    a += 1;
+    b + a + 1
+}
+"#,
+            "Extract all occurrences into variable",
+        );
+        check_assist_not_applicable_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy
+struct S { a: i32 }
+fn foo(s: &mut S) -> i32 {
+    let b = $0s.a * 2$0;
+    s.a = 0;
+    b + s.a * 2
+}
+"#,
+            "Extract all occurrences into variable",
+        );
+    }
+
+    #[test]
+    fn test_extract_var_all_occurrences_may_panic() {
+        check_assist_not_applicable_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy
+fn foo(x: i32, d: i32) -> i32 {
+    let a = $0x / d$0;
+    if d != 0 { a + x / d } else { 0 }
+}
+"#,
+            "Extract all occurrences into variable",
+        );
+        check_assist_not_applicable_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy
+fn foo(x: u8) -> u8 {
+    let a = $0x + 1$0;
+    a + x + 1
+}
+"#,
+            "Extract all occurrences into variable",
+        );
+        check_assist_not_applicable_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy
+fn foo(x: i32) -> i32 {
+    let a = $0-x$0;
+    a | -x
+}
+"#,
+            "Extract all occurrences into variable",
+        );
+    }
+
+    #[test]
+    fn test_extract_var_all_occurrences_shadowed() {
+        check_assist_not_applicable_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy
+fn foo() -> i32 {
+    let a = 1;
+    let b = $0a + 1$0;
+    let a = 2;
+    b + a + 1
+}
+"#,
+            "Extract all occurrences into variable",
+        );
+    }
+
+    #[test]
+    fn test_extract_var_all_occurrences_local_defined_in_anchor() {
+        check_assist_not_applicable_by_label(
+            extract_variable,
+            r#"
+//- minicore: copy, option
+fn foo(o: Option<i32>) -> i32 {
+    match o {
+        Some(v) => $0v * 2$0 + v * 2,
+        None => 0,
+    }
+}
+"#,
+            "Extract all occurrences into variable",
+        );
+    }
 }
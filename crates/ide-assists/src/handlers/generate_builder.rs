@@ -0,0 +1,457 @@
+use ide_db::{assists::GroupLabel, famous_defs::FamousDefs};
+use stdx::format_to;
+use syntax::ast::{self, edit::IndentLevel, AstNode, HasGenericParams, HasName, HasVisibility};
+
+use crate::{
+    utils::{find_struct_impl, generate_impl, generate_impl_text},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: generate_builder
+//
+// Generates a builder for a struct with named fields. Fields of type `Option<T>` are optional,
+// all other fields have to be passed to the `builder()` constructor.
+//
+// ```
+// # //- minicore: option
+// struct $0Request {
+//     url: String,
+//     timeout: Option<u32>,
+// }
+// ```
+// ->
+// ```
+// struct Request {
+//     url: String,
+//     timeout: Option<u32>,
+// }
+//
+// impl Request {
+//     fn builder(url: String) -> RequestBuilder {
+//         RequestBuilder { url, timeout: None }
+//     }
+// }
+//
+// struct RequestBuilder {
+//     url: String,
+//     timeout: Option<u32>,
+// }
+//
+// impl RequestBuilder {
+//     fn url(mut self, url: String) -> Self {
+//         self.url = url;
+//         self
+//     }
+//
+//     fn timeout(mut self, timeout: u32) -> Self {
+//         self.timeout = Some(timeout);
+//         self
+//     }
+//
+//     fn build(self) -> Request {
+//         Request { url: self.url, timeout: self.timeout }
+//     }
+// }
+// ```
+pub(crate) fn generate_builder(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    add_builder_assist(acc, ctx, false)
+}
+
+// Assist: generate_fallible_builder
+//
+// Generates a builder for a struct with named fields, whose `build()` method fails if a
+// required field wasn't set. Fields of type `Option<T>` are optional.
+//
+// ```
+// # //- minicore: option, result
+// struct $0Request {
+//     url: String,
+//     timeout: Option<u32>,
+// }
+// ```
+// ->
+// ```
+// struct Request {
+//     url: String,
+//     timeout: Option<u32>,
+// }
+//
+// impl Request {
+//     fn builder() -> RequestBuilder {
+//         RequestBuilder { url: None, timeout: None }
+//     }
+// }
+//
+// struct RequestBuilder {
+//     url: Option<String>,
+//     timeout: Option<u32>,
+// }
+//
+// impl RequestBuilder {
+//     fn url(mut self, url: String) -> Self {
+//         self.url = Some(url);
+//         self
+//     }
+//
+//     fn timeout(mut self, timeout: u32) -> Self {
+//         self.timeout = Some(timeout);
+//         self
+//     }
+//
+//     fn build(self) -> Result<Request, &'static str> {
+//         Ok(Request { url: self.url.ok_or("missing field `url`")?, timeout: self.timeout })
+//     }
+// }
+// ```
+pub(crate) fn generate_fallible_builder(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    add_builder_assist(acc, ctx, true)
+}
+
+struct BuilderField {
+    name: String,
+    ty: String,
+    /// The `T` of an `Option<T>` field.
+    optional: Option<String>,
+}
+
+fn add_builder_assist(acc: &mut Assists, ctx: &AssistContext<'_>, fallible: bool) -> Option<()> {
+    let strukt = ctx.find_node_at_offset::<ast::Struct>()?;
+    if ctx.find_node_at_offset::<ast::FieldList>().is_some() {
+        return None;
+    }
+    let ast::FieldList::RecordFieldList(field_list) = strukt.field_list()? else { return None };
+    let name = strukt.name()?;
+    let adt = ast::Adt::Struct(strukt.clone());
+    find_struct_impl(ctx, &adt, &["builder".to_owned()])?;
+
+    let option_enum = FamousDefs(&ctx.sema, ctx.sema.scope(strukt.syntax())?.krate())
+        .core_option_Option()
+        .map(hir::Adt::Enum);
+    let fields = field_list
+        .fields()
+        .map(|field| {
+            let name = field.name()?.to_string();
+            let ty = field.ty()?;
+            let is_option = ctx.sema.to_def(&field)?.ty(ctx.db()).as_adt() == option_enum;
+            let optional = if is_option { option_arg(&ty) } else { None };
+            Some(BuilderField { name, ty: ty.to_string(), optional })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    // The setters would clash with `build` itself.
+    if fields.is_empty() || fields.iter().any(|field| field.name == "build") {
+        return None;
+    }
+    if fallible && fields.iter().all(|field| field.optional.is_some()) {
+        return None;
+    }
+
+    let (id, label) = if fallible {
+        ("generate_fallible_builder", "Generate builder with fallible `build`")
+    } else {
+        ("generate_builder", "Generate builder")
+    };
+    acc.add_group(
+        &GroupLabel("Generate builder".to_owned()),
+        AssistId(id, AssistKind::Generate),
+        label,
+        strukt.syntax().text_range(),
+        |builder| {
+            let vis = strukt.visibility().map_or_else(String::new, |it| format!("{it} "));
+            let builder_name = format!("{name}Builder");
+            let args = strukt
+                .generic_param_list()
+                .map_or_else(String::new, |it| it.to_generic_args().to_string());
+
+            // `Foo::builder()`
+            let mut params = Vec::new();
+            let mut inits = Vec::new();
+            for field in &fields {
+                if !fallible && field.optional.is_none() {
+                    params.push(format!("{}: {}", field.name, field.ty));
+                    inits.push(field.name.clone());
+                } else {
+                    inits.push(format!("{}: None", field.name));
+                }
+            }
+            let constructor = format!(
+                "    {vis}fn builder({}) -> {builder_name}{args} {{\n        {builder_name} {{ {} }}\n    }}",
+                params.join(", "),
+                inits.join(", "),
+            );
+
+            // The builder struct.
+            let mut builder_struct = format!("{vis}struct {builder_name}");
+            if let Some(generic_params) = strukt.generic_param_list() {
+                format_to!(builder_struct, "{generic_params}");
+            }
+            match strukt.where_clause() {
+                Some(where_clause) => format_to!(builder_struct, "\n{where_clause}\n{{\n"),
+                None => builder_struct.push_str(" {\n"),
+            }
+            for field in &fields {
+                let ty = match &field.optional {
+                    None if fallible => format!("Option<{}>", field.ty),
+                    _ => field.ty.clone(),
+                };
+                format_to!(builder_struct, "    {}: {ty},\n", field.name);
+            }
+            builder_struct.push('}');
+
+            // The setters and `build()`.
+            let mut methods = Vec::new();
+            for field in &fields {
+                let field_name = &field.name;
+                let (ty, value) = match &field.optional {
+                    Some(inner) => (inner.clone(), format!("Some({field_name})")),
+                    None if fallible => (field.ty.clone(), format!("Some({field_name})")),
+                    None => (field.ty.clone(), field_name.clone()),
+                };
+                methods.push(format!(
+                    "    {vis}fn {field_name}(mut self, {field_name}: {ty}) -> Self {{\n        self.{field_name} = {value};\n        self\n    }}"
+                ));
+            }
+            let field_values = fields
+                .iter()
+                .map(|field| {
+                    if fallible && field.optional.is_none() {
+                        let missing = format!("missing field `{}`", field.name);
+                        format!("{0}: self.{0}.ok_or({missing:?})?", field.name)
+                    } else {
+                        format!("{0}: self.{0}", field.name)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let build = if fallible {
+                format!(
+                    "    {vis}fn build(self) -> Result<{name}{args}, &'static str> {{\n        Ok({name} {{ {field_values} }})\n    }}"
+                )
+            } else {
+                format!("    {vis}fn build(self) -> {name}{args} {{\n        {name} {{ {field_values} }}\n    }}")
+            };
+            methods.push(build);
+
+            let mut buf = generate_impl_text(&adt, &constructor);
+            format_to!(buf, "\n\n{builder_struct}\n\n");
+            let impl_ = generate_impl(&adt);
+            buf.push_str("impl");
+            if let Some(generic_params) = impl_.generic_param_list() {
+                format_to!(buf, "{generic_params}");
+            }
+            format_to!(buf, " {builder_name}{args}");
+            match impl_.where_clause() {
+                Some(where_clause) => format_to!(buf, "\n{where_clause}\n{{\n"),
+                None => buf.push_str(" {\n"),
+            }
+            format_to!(buf, "{}\n}}", methods.join("\n\n"));
+
+            let indent = IndentLevel::from_node(strukt.syntax());
+            let buf = indent_text(&buf, indent);
+            builder.insert(strukt.syntax().text_range().end(), buf);
+        },
+    )
+}
+
+/// Returns `T` for a type written as `Option<T>`.
+fn option_arg(ty: &ast::Type) -> Option<String> {
+    let ast::Type::PathType(path_ty) = ty else { return None };
+    let segment = path_ty.path()?.segment()?;
+    let mut args = segment.generic_arg_list()?.generic_args();
+    match (args.next(), args.next()) {
+        (Some(ast::GenericArg::TypeArg(arg)), None) => Some(arg.ty()?.to_string()),
+        _ => None,
+    }
+}
+
+fn indent_text(text: &str, indent: IndentLevel) -> String {
+    if indent.0 == 0 {
+        return text.to_owned();
+    }
+    text.split('\n')
+        .map(|line| if line.is_empty() { line.to_owned() } else { format!("{indent}{line}") })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn generic_struct() {
+        check_assist(
+            generate_builder,
+            r#"
+//- minicore: option
+pub struct $0Point<T: Copy, U = ()>
+where
+    U: Clone,
+{
+    pub x: T,
+    y: Option<U>,
+}
+"#,
+            r#"
+pub struct Point<T: Copy, U = ()>
+where
+    U: Clone,
+{
+    pub x: T,
+    y: Option<U>,
+}
+
+impl<T: Copy, U> Point<T, U>
+where
+    U: Clone,
+{
+    pub fn builder(x: T) -> PointBuilder<T, U> {
+        PointBuilder { x, y: None }
+    }
+}
+
+pub struct PointBuilder<T: Copy, U = ()>
+where
+    U: Clone,
+{
+    x: T,
+    y: Option<U>,
+}
+
+impl<T: Copy, U> PointBuilder<T, U>
+where
+    U: Clone,
+{
+    pub fn x(mut self, x: T) -> Self {
+        self.x = x;
+        self
+    }
+
+    pub fn y(mut self, y: U) -> Self {
+        self.y = Some(y);
+        self
+    }
+
+    pub fn build(self) -> Point<T, U> {
+        Point { x: self.x, y: self.y }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn fallible_builder_in_module() {
+        check_assist_by_label(
+            generate_fallible_builder,
+            r#"
+//- minicore: option, result
+mod config {
+    pub(crate) struct Config$0 {
+        name: String,
+        retries: u8,
+        verbose: Option<bool>,
+    }
+}
+"#,
+            r#"
+mod config {
+    pub(crate) struct Config {
+        name: String,
+        retries: u8,
+        verbose: Option<bool>,
+    }
+
+    impl Config {
+        pub(crate) fn builder() -> ConfigBuilder {
+            ConfigBuilder { name: None, retries: None, verbose: None }
+        }
+    }
+
+    pub(crate) struct ConfigBuilder {
+        name: Option<String>,
+        retries: Option<u8>,
+        verbose: Option<bool>,
+    }
+
+    impl ConfigBuilder {
+        pub(crate) fn name(mut self, name: String) -> Self {
+            self.name = Some(name);
+            self
+        }
+
+        pub(crate) fn retries(mut self, retries: u8) -> Self {
+            self.retries = Some(retries);
+            self
+        }
+
+        pub(crate) fn verbose(mut self, verbose: bool) -> Self {
+            self.verbose = Some(verbose);
+            self
+        }
+
+        pub(crate) fn build(self) -> Result<Config, &'static str> {
+            Ok(Config { name: self.name.ok_or("missing field `name`")?, retries: self.retries.ok_or("missing field `retries`")?, verbose: self.verbose })
+        }
+    }
+}
+"#,
+            "Generate builder with fallible `build`",
+        );
+    }
+
+    #[test]
+    fn fallible_builder_needs_required_fields() {
+        check_assist_not_applicable(
+            generate_fallible_builder,
+            r#"
+//- minicore: option, result
+struct $0Config {
+    verbose: Option<bool>,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable() {
+        check_assist_not_applicable(
+            generate_builder,
+            r#"
+//- minicore: option
+struct $0Unit;
+"#,
+        );
+        check_assist_not_applicable(
+            generate_builder,
+            r#"
+//- minicore: option
+struct $0Job {
+    build: u32,
+}
+"#,
+        );
+        check_assist_not_applicable(
+            generate_builder,
+            r#"
+//- minicore: option
+struct $0Job {
+    id: u32,
+}
+impl Job {
+    fn builder() {}
+}
+"#,
+        );
+        check_assist_not_applicable(
+            generate_builder,
+            r#"
+//- minicore: option
+struct Job {
+    id: u32$0,
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use either::Either;
+use ide_db::{famous_defs::FamousDefs, helpers::mod_path_to_ast, FxHashSet};
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        edit_in_place::GenericParamsOwnerEdit,
+        make, AstNode, HasName,
+    },
+    ted,
+};
+
+use crate::{utils, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: generate_debug_impl
+//
+// Adds a manual `Debug` impl for a struct, from which fields can then be removed individually.
+// Fields whose type doesn't implement `Debug` are printed as a placeholder.
+//
+// ```
+// # //- minicore: fmt, builtin_impls
+// struct Secret;
+//
+// struct $0Login {
+//     user: u32,
+//     password: Secret,
+// }
+// ```
+// ->
+// ```
+// struct Secret;
+//
+// struct Login {
+//     user: u32,
+//     password: Secret,
+// }
+//
+// impl core::fmt::Debug for Login {
+//     $0fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+//         f.debug_struct("Login")
+//             .field("user", &self.user)
+//             .field("password", &"...")
+//             .finish()
+//     }
+// }
+// ```
+pub(crate) fn generate_debug_impl(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let strukt = ctx.find_node_at_offset::<ast::Struct>()?;
+    if ctx.find_node_at_offset::<ast::FieldList>().is_some() {
+        return None;
+    }
+    let name = strukt.name()?;
+    let db = ctx.db();
+    let def = ctx.sema.to_def(&strukt)?;
+    let module = def.module(db);
+    let debug_trait = FamousDefs(&ctx.sema, module.krate()).core_fmt_Debug()?;
+    let has_debug_impl = hir::Impl::all_for_type(db, def.ty(db))
+        .into_iter()
+        .any(|impl_| impl_.trait_(db) == Some(debug_trait));
+    if has_debug_impl {
+        cov_mark::hit!(generate_debug_impl_already_implemented);
+        return None;
+    }
+    let fmt_path = mod_path_to_ast(&module.find_use_path(
+        db,
+        hir::ModuleDef::Module(debug_trait.module(db)),
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )?);
+
+    // Fields of a type mentioning generic parameters are kept and get a `where` bound instead,
+    // as whether they implement `Debug` depends on the impl's parameters.
+    let mut bounds: Vec<ast::Type> = Vec::new();
+    let body = utils::gen_debug_struct_expr(&strukt, &mut |field| {
+        let (field_ty, def) = match field {
+            Either::Left(field) => (field.ty(), ctx.sema.to_def(field)),
+            Either::Right(field) => (field.ty(), ctx.sema.to_def(field)),
+        };
+        let Some(def) = def else { return false };
+        let ty = def.ty(db);
+        if ty.impls_trait(db, debug_trait, &[]) {
+            return true;
+        }
+        match field_ty {
+            Some(field_ty) if !ty.generic_params(db).is_empty() => {
+                bounds.push(field_ty);
+                true
+            }
+            _ => false,
+        }
+    })?;
+
+    let target = strukt.syntax().text_range();
+    acc.add(
+        AssistId("generate_debug_impl", AssistKind::Generate),
+        format!("Generate `Debug` impl for `{name}`"),
+        target,
+        |edit| {
+            let adt = ast::Adt::Struct(strukt.clone());
+            let debug_trait = make::ty(&format!("{fmt_path}::Debug"));
+            let impl_ = utils::generate_trait_impl_intransitive(&adt, debug_trait.clone());
+
+            let mut seen = FxHashSet::default();
+            for ty in bounds {
+                if seen.insert(ty.to_string()) {
+                    let bound = make::type_bound(debug_trait.clone());
+                    let pred = make::where_pred(Either::Right(ty), [bound]).clone_for_update();
+                    impl_.get_or_create_where_clause().add_predicate(pred);
+                }
+            }
+
+            let params = make::param_list(
+                Some(make::self_param()),
+                [make::param(
+                    make::ident_pat(false, false, make::name("f")).into(),
+                    make::ty(&format!("&mut {fmt_path}::Formatter<'_>")),
+                )],
+            );
+            let fn_ = make::fn_(
+                None,
+                make::name("fmt"),
+                None,
+                None,
+                params,
+                make::block_expr(None, Some(body)).indent(IndentLevel(1)),
+                Some(make::ret_type(make::ty(&format!("{fmt_path}::Result")))),
+                false,
+                false,
+                false,
+            )
+            .clone_for_update();
+            impl_.get_or_create_assoc_item_list().add_item(fn_.clone().into());
+            if let Some(tail) = fn_.body().and_then(|body| body.tail_expr()) {
+                split_method_chain(&tail);
+            }
+
+            if let Some(cap) = ctx.config.snippet_cap {
+                edit.add_tabstop_before(cap, fn_);
+            }
+
+            let strukt = edit.make_mut(strukt);
+            let indent = IndentLevel::from_node(strukt.syntax());
+            ted::insert_all_raw(
+                ted::Position::after(strukt.syntax()),
+                vec![
+                    make::tokens::whitespace(&format!("\n\n{indent}")).into(),
+                    impl_.syntax().clone().into(),
+                ],
+            );
+        },
+    )
+}
+
+/// Puts every call of a method chain like `f.debug_struct("Name").field(..).finish()` but the
+/// first one onto its own line, if there are any calls in between.
+fn split_method_chain(expr: &ast::Expr) {
+    let indent = IndentLevel::from_node(expr.syntax()) + 1;
+    let mut dots = Vec::new();
+    let mut call = ast::MethodCallExpr::cast(expr.syntax().clone());
+    while let Some(it) = call {
+        call = it.receiver().and_then(|it| ast::MethodCallExpr::cast(it.syntax().clone()));
+        if call.is_some() {
+            dots.extend(it.dot_token());
+        }
+    }
+    if dots.len() < 2 {
+        return;
+    }
+    for dot in dots {
+        ted::insert(ted::Position::before(dot), make::tokens::whitespace(&format!("\n{indent}")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn record_struct_with_non_debug_field() {
+        check_assist(
+            generate_debug_impl,
+            r#"
+//- minicore: fmt, builtin_impls
+struct NotDebug;
+
+struct Foo$0 {
+    a: u32,
+    b: NotDebug,
+    c: &'static [u8],
+}
+"#,
+            r#"
+struct NotDebug;
+
+struct Foo {
+    a: u32,
+    b: NotDebug,
+    c: &'static [u8],
+}
+
+impl core::fmt::Debug for Foo {
+    $0fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Foo")
+            .field("a", &self.a)
+            .field("b", &"...")
+            .field("c", &self.c)
+            .finish()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn tuple_struct() {
+        check_assist(
+            generate_debug_impl,
+            r#"
+//- minicore: fmt, builtin_impls
+struct NotDebug;
+
+struct $0Foo(u32, NotDebug);
+"#,
+            r#"
+struct NotDebug;
+
+struct Foo(u32, NotDebug);
+
+impl core::fmt::Debug for Foo {
+    $0fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Foo")
+            .field(&self.0)
+            .field(&"...")
+            .finish()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unit_struct() {
+        check_assist(
+            generate_debug_impl,
+            r#"
+//- minicore: fmt, builtin_impls
+struct $0Foo;
+"#,
+            r#"
+struct Foo;
+
+impl core::fmt::Debug for Foo {
+    $0fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Foo").finish()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generic_struct_bounds_included_fields() {
+        check_assist(
+            generate_debug_impl,
+            r#"
+//- minicore: fmt, builtin_impls
+struct NotDebug;
+struct Wrapper<T>(T);
+
+struct $0Foo<T, U: Copy> {
+    value: T,
+    other: T,
+    wrapped: Wrapper<U>,
+    hidden: NotDebug,
+}
+"#,
+            r#"
+struct NotDebug;
+struct Wrapper<T>(T);
+
+struct Foo<T, U: Copy> {
+    value: T,
+    other: T,
+    wrapped: Wrapper<U>,
+    hidden: NotDebug,
+}
+
+impl<T, U: Copy> core::fmt::Debug for Foo<T, U> where T: core::fmt::Debug, Wrapper<U>: core::fmt::Debug {
+    $0fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Foo")
+            .field("value", &self.value)
+            .field("other", &self.other)
+            .field("wrapped", &self.wrapped)
+            .field("hidden", &"...")
+            .finish()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_field_list() {
+        check_assist_not_applicable(
+            generate_debug_impl,
+            r#"
+//- minicore: fmt, builtin_impls
+struct Foo {
+    a: u32$0,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_if_debug_is_implemented() {
+        cov_mark::check_count!(generate_debug_impl_already_implemented, 2);
+        check_assist_not_applicable(
+            generate_debug_impl,
+            r#"
+//- minicore: fmt, builtin_impls, derive
+#[derive(core::fmt::Debug)]
+struct $0Foo<T>(T);
+"#,
+        );
+        check_assist_not_applicable(
+            generate_debug_impl,
+            r#"
+//- minicore: fmt, builtin_impls
+struct $0Foo;
+
+impl core::fmt::Debug for Foo {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Foo")
+    }
+}
+"#,
+        );
+    }
+}
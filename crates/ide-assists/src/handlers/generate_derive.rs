@@ -1,6 +1,9 @@
+use hir::HasSource;
+use ide_db::{famous_defs::FamousDefs, RootDatabase};
+use itertools::Itertools;
 use syntax::{
-    ast::{self, edit_in_place::AttrsOwnerEdit, make, AstNode, HasAttrs},
-    T,
+    ast::{self, edit_in_place::AttrsOwnerEdit, make, AstNode, HasAttrs, HasName},
+    ted, NodeOrToken, T,
 };
 
 use crate::{AssistContext, AssistId, AssistKind, Assists};
@@ -72,9 +75,173 @@ pub(crate) fn generate_derive(acc: &mut Assists, ctx: &AssistContext<'_>) -> Opt
     })
 }
 
+// Assist: generate_derive_clone
+//
+// Adds `Clone` to the derives of a struct or enum, and to the derives of the types of its fields
+// that are defined in the current crate and don't implement `Clone` yet. Not applicable if the
+// type of a field can't derive `Clone`, like a union or a type of another crate.
+//
+// ```
+// # //- minicore: derive, clone
+// struct Point { x: u32, y: u32 }
+//
+// struct Line {$0
+//     from: Point,
+//     to: Point,
+// }
+// ```
+// ->
+// ```
+// #[derive(Clone)]
+// struct Point { x: u32, y: u32 }
+//
+// #[derive(Clone)]
+// struct Line {
+//     from: Point,
+//     to: Point,
+// }
+// ```
+pub(crate) fn generate_derive_clone(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let nominal = ctx.find_node_at_offset::<ast::Adt>()?;
+    let db = ctx.db();
+    let adt = ctx.sema.to_def(&nominal)?;
+    let krate = adt.module(db).krate();
+    let clone = FamousDefs(&ctx.sema, krate).core_clone_Clone()?;
+    if adt.ty(db).impls_trait(db, clone, &[]) {
+        return None;
+    }
+
+    let fields = match adt {
+        hir::Adt::Struct(it) => it.fields(db),
+        // Unions can only derive `Clone` if they are `Copy`, which requires `Clone` already.
+        hir::Adt::Union(_) => return None,
+        hir::Adt::Enum(it) => it.variants(db).into_iter().flat_map(|it| it.fields(db)).collect(),
+    };
+    let mut missing = MissingClone { krate, adts: Vec::new(), underivable: false };
+    for field in fields {
+        missing.find_in(db, clone, &field.ty(db));
+    }
+    if missing.underivable {
+        cov_mark::hit!(derive_clone_underivable_field);
+        return None;
+    }
+
+    // Only the field types themselves get the derive; their own fields are left to a follow-up.
+    let mut targets = vec![(ctx.file_id(), nominal.clone())];
+    for dependency in missing.adts.iter().filter(|&&it| it != adt) {
+        let source = dependency.source(db)?;
+        targets.push((source.file_id.file_id()?, source.value));
+    }
+
+    let label = if targets.len() == 1 {
+        "Add `#[derive(Clone)]`".to_owned()
+    } else {
+        let names = targets.iter().filter_map(|(_, it)| it.name()).join("`, `");
+        format!("Add `#[derive(Clone)]` to `{names}`")
+    };
+
+    acc.add(
+        AssistId("generate_derive_clone", AssistKind::Generate),
+        label,
+        nominal.syntax().text_range(),
+        |builder| {
+            for (file_id, targets) in targets.into_iter().into_group_map() {
+                builder.edit_file(file_id);
+                let targets: Vec<_> = targets.into_iter().map(|it| builder.make_mut(it)).collect();
+                for target in targets {
+                    add_clone_derive(&target);
+                }
+            }
+        },
+    )
+}
+
+/// The reasons why a type doesn't implement `Clone`.
+struct MissingClone {
+    krate: hir::Crate,
+    /// Types of the current crate that could derive `Clone`.
+    adts: Vec<hir::Adt>,
+    /// Whether a type can't derive `Clone` here.
+    underivable: bool,
+}
+
+impl MissingClone {
+    /// Returns whether anything in `ty` explains why it doesn't implement `Clone`.
+    fn find_in(&mut self, db: &RootDatabase, clone: hir::Trait, ty: &hir::Type) -> bool {
+        if ty.impls_trait(db, clone, &[]) {
+            return false;
+        }
+        // The derive requires the parameters to implement `Clone`.
+        if ty.as_type_param(db).is_some() {
+            return true;
+        }
+        if ty.is_mutable_reference() {
+            self.underivable = true;
+            return true;
+        }
+        let parts = match ty.as_array(db) {
+            Some((elem, _)) => vec![elem],
+            None if ty.as_adt().is_some() => ty.type_arguments().collect(),
+            None => ty.tuple_fields(db),
+        };
+        let mut found = false;
+        for part in &parts {
+            found |= self.find_in(db, clone, part);
+        }
+        if found {
+            return true;
+        }
+        match ty.as_adt() {
+            Some(adt @ (hir::Adt::Struct(_) | hir::Adt::Enum(_)))
+                if adt.module(db).krate() == self.krate =>
+            {
+                if !self.adts.contains(&adt) {
+                    self.adts.push(adt);
+                }
+            }
+            _ => self.underivable = true,
+        }
+        true
+    }
+}
+
+fn add_clone_derive(adt: &ast::Adt) {
+    let derive = adt
+        .attrs()
+        .filter_map(|it| it.as_simple_call())
+        .find(|(name, _)| name == "derive")
+        .map(|(_, tt)| tt);
+    let clone = make::tokens::ident("Clone");
+    match derive.as_ref().and_then(|tt| Some((tt, tt.left_delimiter_token()?))) {
+        Some((tt, l_delimiter)) => {
+            // `ident` comes from an immutable tree, but the token is moved into a mutable one.
+            let Some(clone) = make::name_ref("Clone").clone_for_update().ident_token() else {
+                return;
+            };
+            let mut tokens = vec![NodeOrToken::Token(clone)];
+            // Skip the delimiters.
+            if tt.token_trees_and_tokens().count() > 2 {
+                tokens.push(NodeOrToken::Token(make::token(T![,])));
+                tokens.push(NodeOrToken::Token(make::tokens::single_space()));
+            }
+            ted::insert_all_raw(ted::Position::after(l_delimiter), tokens);
+        }
+        None => {
+            let derive = make::attr_outer(make::meta_token_tree(
+                make::ext::ident_path("derive"),
+                make::token_tree(T!['('], vec![NodeOrToken::Token(clone)]),
+            ))
+            .clone_for_update();
+            adt.add_attr(derive);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_target};
+    use crate::tests::{
+        check_assist, check_assist_by_label, check_assist_not_applicable, check_assist_target,
+    };
 
     use super::*;
 
@@ -199,4 +366,99 @@ struct EvenMoreIrrelevant;
 struct Foo { a: i32, }",
         );
     }
+
+    #[test]
+    fn derive_clone_with_field_types() {
+        check_assist_by_label(
+            generate_derive_clone,
+            r#"
+//- minicore: derive, clone, builtin_impls
+struct Id(u32);
+
+/// A tag.
+#[derive(PartialEq)]
+struct Tag { name: Id }
+
+#[derive(Debug)]
+enum Item$0 {
+    Tagged(Tag, [Id; 2]),
+    Untagged { ids: (Id, u8), parent: &'static Item },
+}
+"#,
+            r#"
+#[derive(Clone)]
+struct Id(u32);
+
+/// A tag.
+#[derive(Clone, PartialEq)]
+struct Tag { name: Id }
+
+#[derive(Clone, Debug)]
+enum Item {
+    Tagged(Tag, [Id; 2]),
+    Untagged { ids: (Id, u8), parent: &'static Item },
+}
+"#,
+            "Add `#[derive(Clone)]` to `Item`, `Tag`, `Id`",
+        );
+    }
+
+    #[test]
+    fn derive_clone_not_applicable_with_underivable_fields() {
+        cov_mark::check_count!(derive_clone_underivable_field, 3);
+        check_assist_not_applicable(
+            generate_derive_clone,
+            r#"
+//- minicore: derive, clone
+//- /main.rs crate:main deps:ext
+use ext::Handle;
+
+struct Wrapper<T>$0 {
+    handles: [Handle; 2],
+    value: T,
+}
+//- /ext.rs crate:ext
+pub struct Handle;
+"#,
+        );
+        check_assist_not_applicable(
+            generate_derive_clone,
+            r#"
+//- minicore: derive, clone
+struct Wrapper<'a>$0 {
+    value: &'a mut u32,
+}
+"#,
+        );
+        check_assist_not_applicable(
+            generate_derive_clone,
+            r#"
+//- minicore: derive, clone
+union Bits { int: u32, float: f32 }
+
+struct Wrapper$0 {
+    bits: Bits,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn derive_clone_not_applicable() {
+        check_assist_not_applicable(
+            generate_derive_clone,
+            r#"
+//- minicore: derive, clone
+#[derive(Clone)]
+struct Foo$0 { a: i32 }
+"#,
+        );
+        check_assist_not_applicable(
+            generate_derive_clone,
+            r#"
+//- minicore: derive, clone
+union Foo$0 { a: i32 }
+"#,
+        );
+    }
 }
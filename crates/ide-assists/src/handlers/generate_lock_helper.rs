@@ -0,0 +1,366 @@
+use hir::{HirDisplay, ModuleDef};
+use ide_db::{famous_defs::FamousDefs, helpers::mod_path_to_ast};
+use syntax::{
+    ast::{self, AstNode, HasName, HasVisibility},
+    TextRange,
+};
+
+use crate::{
+    utils::{add_method_to_adt, find_struct_impl},
+    AssistContext, AssistId, AssistKind, Assists, GroupLabel,
+};
+
+// Assist: generate_lock_helper
+//
+// Generate a method running a closure on the value behind an `Arc<Mutex<T>>` field.
+//
+// ```
+// # //- /main.rs crate:main deps:std,alloc
+// # use alloc::sync::Arc;
+// # use std::sync::Mutex;
+// struct Counter {
+//     coun$0t: Arc<Mutex<u32>>,
+// }
+// # //- /alloc.rs crate:alloc
+// # pub mod sync { pub struct Arc<T>(T); }
+// # //- /std.rs crate:std
+// # pub mod sync { pub struct Mutex<T>(T); }
+// ```
+// ->
+// ```
+// # use alloc::sync::Arc;
+// # use std::sync::Mutex;
+// struct Counter {
+//     count: Arc<Mutex<u32>>,
+// }
+//
+// impl Counter {
+//     fn with_count<R>(&self, f: impl FnOnce(&mut u32) -> R) -> R {
+//         f(&mut self.count.lock().unwrap())
+//     }
+// }
+// ```
+pub(crate) fn generate_lock_helper(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let field = MutexField::at_cursor(ctx, "with_")?;
+    acc.add_group(
+        &GroupLabel("Generate a locking helper".to_owned()),
+        AssistId("generate_lock_helper", AssistKind::Generate),
+        "Generate a locking helper",
+        field.target,
+        |builder| {
+            let MutexField { vis, fn_name, field_name, inner_ty, .. } = &field;
+            let method = format!(
+                "    {vis}fn {fn_name}<R>(&self, f: impl FnOnce(&mut {inner_ty}) -> R) -> R {{
+        f(&mut self.{field_name}.lock().unwrap())
+    }}"
+            );
+            add_method_to_adt(builder, &field.adt, field.impl_def.clone(), &method);
+        },
+    )
+}
+
+// Assist: generate_try_lock_helper
+//
+// Generate a method running a closure on the value behind an `Arc<Mutex<T>>` field, returning
+// the `PoisonError` if the mutex is poisoned.
+//
+// ```
+// # //- /main.rs crate:main deps:std,alloc
+// # use alloc::sync::Arc;
+// # use std::sync::Mutex;
+// struct Counter {
+//     coun$0t: Arc<Mutex<u32>>,
+// }
+// # //- /alloc.rs crate:alloc
+// # pub mod sync { pub struct Arc<T>(T); }
+// # //- /std.rs crate:std
+// # pub mod sync {
+// #     pub struct Mutex<T>(T);
+// #     pub struct MutexGuard<'a, T>(&'a T);
+// #     pub struct PoisonError<T>(T);
+// # }
+// ```
+// ->
+// ```
+// # use alloc::sync::Arc;
+// # use std::sync::Mutex;
+// struct Counter {
+//     count: Arc<Mutex<u32>>,
+// }
+//
+// impl Counter {
+//     fn try_with_count<R>(
+//         &self,
+//         f: impl FnOnce(&mut u32) -> R,
+//     ) -> Result<R, std::sync::PoisonError<std::sync::MutexGuard<'_, u32>>> {
+//         let mut guard = self.count.lock()?;
+//         Ok(f(&mut guard))
+//     }
+// }
+// ```
+pub(crate) fn generate_try_lock_helper(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let field = MutexField::at_cursor(ctx, "try_with_")?;
+    let famous_defs = FamousDefs(&ctx.sema, field.module.krate());
+    let use_path = |strukt: hir::Struct| {
+        field
+            .module
+            .find_use_path(
+                ctx.db(),
+                ModuleDef::from(strukt),
+                ctx.config.prefer_no_std,
+                ctx.config.prefer_prelude,
+            )
+            .map(|it| mod_path_to_ast(&it))
+    };
+    let poison_error = use_path(famous_defs.std_sync_PoisonError()?)?;
+    let mutex_guard = use_path(famous_defs.std_sync_MutexGuard()?)?;
+    acc.add_group(
+        &GroupLabel("Generate a locking helper".to_owned()),
+        AssistId("generate_try_lock_helper", AssistKind::Generate),
+        "Generate a locking helper propagating `PoisonError`",
+        field.target,
+        |builder| {
+            let MutexField { vis, fn_name, field_name, inner_ty, .. } = &field;
+            let method = format!(
+                "    {vis}fn {fn_name}<R>(
+        &self,
+        f: impl FnOnce(&mut {inner_ty}) -> R,
+    ) -> Result<R, {poison_error}<{mutex_guard}<'_, {inner_ty}>>> {{
+        let mut guard = self.{field_name}.lock()?;
+        Ok(f(&mut guard))
+    }}"
+            );
+            add_method_to_adt(builder, &field.adt, field.impl_def.clone(), &method);
+        },
+    )
+}
+
+/// A struct field of type `Arc<Mutex<T>>`.
+struct MutexField {
+    module: hir::Module,
+    adt: ast::Adt,
+    impl_def: Option<ast::Impl>,
+    target: TextRange,
+    vis: String,
+    fn_name: String,
+    field_name: ast::Name,
+    /// The rendered `T`.
+    inner_ty: String,
+}
+
+impl MutexField {
+    fn at_cursor(ctx: &AssistContext<'_>, fn_prefix: &str) -> Option<MutexField> {
+        let record_field = ctx.find_node_at_offset::<ast::RecordField>()?;
+        let strukt = record_field.syntax().ancestors().find_map(ast::Struct::cast)?;
+        let field_name = record_field.name()?;
+        let field = ctx.sema.to_def(&record_field)?;
+
+        let module = ctx.sema.scope(record_field.syntax())?.module();
+        let famous_defs = FamousDefs(&ctx.sema, module.krate());
+        let arc = famous_defs.alloc_sync_Arc()?;
+        let mutex = famous_defs.std_sync_Mutex()?;
+        let arc_ty = field.ty(ctx.db());
+        if arc_ty.as_adt() != Some(hir::Adt::Struct(arc)) {
+            return None;
+        }
+        let mutex_ty = arc_ty.type_arguments().next()?;
+        if mutex_ty.as_adt() != Some(hir::Adt::Struct(mutex)) {
+            return None;
+        }
+        let inner_ty = mutex_ty
+            .type_arguments()
+            .next()?
+            .display_source_code(ctx.db(), module.into(), true)
+            .ok()?;
+
+        let fn_name = format!("{fn_prefix}{}", field_name.text());
+        let adt = ast::Adt::Struct(strukt.clone());
+        // Return early if we've found an existing fn
+        let impl_def = find_struct_impl(ctx, &adt, &[fn_name.clone()])?;
+        let vis = strukt.visibility().map_or(String::new(), |v| format!("{v} "));
+
+        Some(MutexField {
+            module,
+            adt,
+            impl_def,
+            target: record_field.syntax().text_range(),
+            vis,
+            fn_name,
+            field_name,
+            inner_ty,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn generic_field_into_existing_impl() {
+        check_assist(
+            generate_lock_helper,
+            r#"
+//- /main.rs crate:main deps:std,alloc
+use alloc::sync::Arc;
+use std::sync::Mutex;
+pub struct Shared<T> {
+    items$0: Arc<Mutex<Vec<T>>>,
+}
+impl<T> Shared<T> {
+    fn new() -> Self { loop {} }
+}
+struct Vec<T>(T);
+//- /alloc.rs crate:alloc
+pub mod sync { pub struct Arc<T>(T); }
+//- /std.rs crate:std
+pub mod sync {
+    pub struct Mutex<T>(T);
+    pub struct MutexGuard<'a, T>(&'a T);
+    pub struct PoisonError<T>(T);
+}
+"#,
+            r#"
+use alloc::sync::Arc;
+use std::sync::Mutex;
+pub struct Shared<T> {
+    items: Arc<Mutex<Vec<T>>>,
+}
+impl<T> Shared<T> {
+    fn new() -> Self { loop {} }
+
+    pub fn with_items<R>(&self, f: impl FnOnce(&mut Vec<T>) -> R) -> R {
+        f(&mut self.items.lock().unwrap())
+    }
+}
+struct Vec<T>(T);
+"#,
+        );
+    }
+
+    #[test]
+    fn poison_propagating_variant() {
+        check_assist(
+            generate_try_lock_helper,
+            r#"
+//- /main.rs crate:main deps:std,alloc
+use alloc::sync::Arc;
+use std::sync::Mutex;
+struct Counter {
+    count$0: Arc<Mutex<u32>>,
+}
+//- /alloc.rs crate:alloc
+pub mod sync { pub struct Arc<T>(T); }
+//- /std.rs crate:std
+pub mod sync {
+    pub struct Mutex<T>(T);
+    pub struct MutexGuard<'a, T>(&'a T);
+    pub struct PoisonError<T>(T);
+}
+"#,
+            r#"
+use alloc::sync::Arc;
+use std::sync::Mutex;
+struct Counter {
+    count: Arc<Mutex<u32>>,
+}
+
+impl Counter {
+    fn try_with_count<R>(
+        &self,
+        f: impl FnOnce(&mut u32) -> R,
+    ) -> Result<R, std::sync::PoisonError<std::sync::MutexGuard<'_, u32>>> {
+        let mut guard = self.count.lock()?;
+        Ok(f(&mut guard))
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn poison_error_uses_imported_paths() {
+        check_assist(
+            generate_try_lock_helper,
+            r#"
+//- /main.rs crate:main deps:std,alloc
+use alloc::sync::Arc;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+struct Counter {
+    count$0: Arc<Mutex<u32>>,
+}
+//- /alloc.rs crate:alloc
+pub mod sync { pub struct Arc<T>(T); }
+//- /std.rs crate:std
+pub mod sync {
+    pub struct Mutex<T>(T);
+    pub struct MutexGuard<'a, T>(&'a T);
+    pub struct PoisonError<T>(T);
+}
+"#,
+            r#"
+use alloc::sync::Arc;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+struct Counter {
+    count: Arc<Mutex<u32>>,
+}
+
+impl Counter {
+    fn try_with_count<R>(
+        &self,
+        f: impl FnOnce(&mut u32) -> R,
+    ) -> Result<R, PoisonError<MutexGuard<'_, u32>>> {
+        let mut guard = self.count.lock()?;
+        Ok(f(&mut guard))
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable() {
+        check_assist_not_applicable(
+            generate_lock_helper,
+            r#"
+//- /main.rs crate:main deps:std,alloc
+use std::sync::Mutex;
+struct Counter {
+    count$0: Mutex<u32>,
+}
+//- /alloc.rs crate:alloc
+pub mod sync { pub struct Arc<T>(T); }
+//- /std.rs crate:std
+pub mod sync {
+    pub struct Mutex<T>(T);
+    pub struct MutexGuard<'a, T>(&'a T);
+    pub struct PoisonError<T>(T);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            generate_lock_helper,
+            r#"
+//- /main.rs crate:main deps:std,alloc
+use alloc::sync::Arc;
+use std::sync::Mutex;
+struct Counter {
+    count$0: Arc<Mutex<u32>>,
+}
+impl Counter {
+    fn with_count(&self) {}
+}
+//- /alloc.rs crate:alloc
+pub mod sync { pub struct Arc<T>(T); }
+//- /std.rs crate:std
+pub mod sync {
+    pub struct Mutex<T>(T);
+    pub struct MutexGuard<'a, T>(&'a T);
+    pub struct PoisonError<T>(T);
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use hir::{HasSource, HirDisplay, ModuleDef};
+use ide_db::{assists::GroupLabel, famous_defs::FamousDefs};
+use syntax::ast::{self, edit::IndentLevel, AstNode, HasGenericParams, HasName};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: generate_newtype_conversion
+//
+// Generates a `From` impl converting this newtype into another newtype of the same module, when
+// their wrapped types are convertible. Lossy conversions get a `TryFrom` impl instead.
+//
+// ```
+// //- minicore: from
+// struct $0Meters(f64);
+// struct Length(f64);
+// ```
+// ->
+// ```
+// struct Meters(f64);
+//
+// impl From<Meters> for Length {
+//     fn from(value: Meters) -> Self {
+//         Self(value.0)
+//     }
+// }
+// struct Length(f64);
+// ```
+pub(crate) fn generate_newtype_conversion(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let strukt = ctx.find_node_at_offset::<ast::Struct>()?;
+    let name = strukt.name()?;
+    let header = strukt.struct_token()?.text_range().cover(name.syntax().text_range());
+    if !header.contains_inclusive(ctx.offset()) {
+        return None;
+    }
+    let db = ctx.db();
+    let def = ctx.sema.to_def(&strukt)?;
+    let inner = newtype_inner(ctx, def)?;
+    let module = def.module(db);
+    let famous_defs = FamousDefs(&ctx.sema, module.krate());
+    let from_trait = famous_defs.core_convert_From()?;
+    let try_from_trait = famous_defs.core_convert_TryFrom();
+    let render = |ty: &hir::Type| ty.display_source_code(db, module.into(), true).ok();
+
+    let others = module.declarations(db).into_iter().filter_map(|it| match it {
+        ModuleDef::Adt(hir::Adt::Struct(it)) if it != def => Some(it),
+        _ => None,
+    });
+    let indent = IndentLevel::from_node(strukt.syntax());
+    for other in others {
+        let Some(other_inner) = newtype_inner(ctx, other) else { continue };
+        for ((from, from_inner), (to, to_inner)) in
+            [((def, &inner), (other, &other_inner)), ((other, &other_inner), (def, &inner))]
+        {
+            let (from_ty, to_ty) = (from.ty(db), to.ty(db));
+            if to_ty.impls_trait(db, from_trait, &[from_ty.clone()])
+                || try_from_trait.map_or(false, |it| to_ty.impls_trait(db, it, &[from_ty.clone()]))
+            {
+                continue;
+            }
+            let (from_name, to_name) =
+                (from.name(db).display(db).to_string(), to.name(db).display(db).to_string());
+            let (label, impl_) = if from_inner == to_inner
+                || to_inner.impls_trait(db, from_trait, &[from_inner.clone()])
+            {
+                let value = if from_inner == to_inner { "value.0" } else { "value.0.into()" };
+                (
+                    format!("Generate `From<{from_name}> for {to_name}`"),
+                    format!(
+                        "impl From<{from_name}> for {to_name} {{\n\
+                         {0}fn from(value: {from_name}) -> Self {{\n\
+                         {1}Self({value})\n\
+                         {0}}}\n\
+                         }}",
+                        IndentLevel(1),
+                        IndentLevel(2),
+                    ),
+                )
+            } else if try_from_trait
+                .map_or(false, |it| to_inner.impls_trait(db, it, &[from_inner.clone()]))
+            {
+                let (Some(from_inner), Some(to_inner)) = (render(from_inner), render(to_inner))
+                else {
+                    continue;
+                };
+                (
+                    format!("Generate `TryFrom<{from_name}> for {to_name}`"),
+                    format!(
+                        "impl TryFrom<{from_name}> for {to_name} {{\n\
+                         {0}type Error = <{to_inner} as TryFrom<{from_inner}>>::Error;\n\
+                         \n\
+                         {0}fn try_from(value: {from_name}) -> Result<Self, Self::Error> {{\n\
+                         {1}{to_inner}::try_from(value.0).map(Self)\n\
+                         {0}}}\n\
+                         }}",
+                        IndentLevel(1),
+                        IndentLevel(2),
+                    ),
+                )
+            } else {
+                continue;
+            };
+            let impl_ = impl_
+                .lines()
+                .map(|line| if line.is_empty() { String::new() } else { format!("{indent}{line}") })
+                .collect::<Vec<_>>()
+                .join("\n");
+            acc.add_group(
+                &GroupLabel("Generate conversion between newtypes".to_owned()),
+                AssistId("generate_newtype_conversion", AssistKind::Generate),
+                label,
+                header,
+                |builder| {
+                    builder.insert(strukt.syntax().text_range().end(), format!("\n\n{impl_}"));
+                },
+            );
+        }
+    }
+    Some(())
+}
+
+/// Returns the wrapped type of a tuple struct with a single field and no generics.
+fn newtype_inner(ctx: &AssistContext<'_>, strukt: hir::Struct) -> Option<hir::Type> {
+    let source = strukt.source(ctx.db())?.value;
+    if source.generic_param_list().is_some() {
+        return None;
+    }
+    match source.field_list()? {
+        ast::FieldList::TupleFieldList(it) if it.fields().count() == 1 => (),
+        _ => return None,
+    }
+    let ty = strukt.fields(ctx.db()).first()?.ty(ctx.db());
+    (!ty.contains_unknown()).then_some(ty)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist_by_label, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn both_directions() {
+        check_assist_by_label(
+            generate_newtype_conversion,
+            r#"
+//- minicore: from
+mod m {
+    pub struct UserId(u64);
+    pub struct $0Id(u64);
+}
+"#,
+            r#"
+mod m {
+    pub struct UserId(u64);
+    pub struct Id(u64);
+
+    impl From<UserId> for Id {
+        fn from(value: UserId) -> Self {
+            Self(value.0)
+        }
+    }
+}
+"#,
+            "Generate `From<UserId> for Id`",
+        );
+    }
+
+    #[test]
+    fn lossless_and_lossy_conversions() {
+        let before = r#"
+//- minicore: from, try_from
+pub struct TryFromIntError(());
+impl From<u8> for u32 {
+    fn from(value: u8) -> u32 { loop {} }
+}
+impl TryFrom<u32> for u8 {
+    type Error = TryFromIntError;
+    fn try_from(value: u32) -> Result<u8, TryFromIntError> { loop {} }
+}
+
+struct $0Small(u8);
+struct Big(u32);
+"#;
+        check_assist_by_label(
+            generate_newtype_conversion,
+            before,
+            r#"
+pub struct TryFromIntError(());
+impl From<u8> for u32 {
+    fn from(value: u8) -> u32 { loop {} }
+}
+impl TryFrom<u32> for u8 {
+    type Error = TryFromIntError;
+    fn try_from(value: u32) -> Result<u8, TryFromIntError> { loop {} }
+}
+
+struct Small(u8);
+
+impl From<Small> for Big {
+    fn from(value: Small) -> Self {
+        Self(value.0.into())
+    }
+}
+struct Big(u32);
+"#,
+            "Generate `From<Small> for Big`",
+        );
+        check_assist_by_label(
+            generate_newtype_conversion,
+            before,
+            r#"
+pub struct TryFromIntError(());
+impl From<u8> for u32 {
+    fn from(value: u8) -> u32 { loop {} }
+}
+impl TryFrom<u32> for u8 {
+    type Error = TryFromIntError;
+    fn try_from(value: u32) -> Result<u8, TryFromIntError> { loop {} }
+}
+
+struct Small(u8);
+
+impl TryFrom<Big> for Small {
+    type Error = <u8 as TryFrom<u32>>::Error;
+
+    fn try_from(value: Big) -> Result<Self, Self::Error> {
+        u8::try_from(value.0).map(Self)
+    }
+}
+struct Big(u32);
+"#,
+            "Generate `TryFrom<Big> for Small`",
+        );
+    }
+
+    #[test]
+    fn not_applicable() {
+        // Unrelated inner types.
+        check_assist_not_applicable(
+            generate_newtype_conversion,
+            r#"
+//- minicore: from
+struct $0Name(bool);
+struct Count(u32);
+"#,
+        );
+        // Other modules.
+        check_assist_not_applicable(
+            generate_newtype_conversion,
+            r#"
+//- minicore: from
+struct UserId(u64);
+mod m {
+    pub struct $0Id(pub u64);
+}
+"#,
+        );
+        // Existing impls.
+        check_assist_not_applicable(
+            generate_newtype_conversion,
+            r#"
+//- minicore: from
+struct $0A(u32);
+struct B(u32);
+impl From<A> for B { fn from(a: A) -> B { B(a.0) } }
+impl From<B> for A { fn from(b: B) -> A { A(b.0) } }
+"#,
+        );
+        // Not newtypes.
+        check_assist_not_applicable(
+            generate_newtype_conversion,
+            r#"
+//- minicore: from
+struct $0A(u32, u32);
+struct B(u32);
+struct C<T>(T);
+struct D { inner: u32 }
+"#,
+        );
+        check_assist_not_applicable(
+            generate_newtype_conversion,
+            r#"
+//- minicore: from
+struct A(u32$0);
+struct B(u32);
+"#,
+        );
+    }
+}
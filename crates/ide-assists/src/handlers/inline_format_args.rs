@@ -0,0 +1,367 @@
+use hir::{DescendPreference, PathResolution};
+use ide_db::syntax_helpers::format_string::{
+    is_format_string, lex_format_specifiers, FormatSpecifier,
+};
+use syntax::{
+    ast::{self, make},
+    AstNode, AstToken, NodeOrToken,
+    SyntaxKind::{COMMA, IDENT},
+    TextRange, TextSize,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: inline_format_args
+//
+// Moves positional format arguments which are plain identifiers into the format string.
+//
+// ```
+// # //- minicore: fmt
+// fn main() {
+//     let name = "world";
+//     let count = 3;
+//     print!("$0{} {:?}", name, count);
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let name = "world";
+//     let count = 3;
+//     print!("{name} {count:?}");
+// }
+// ```
+pub(crate) fn inline_format_args(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let fmt_string = ctx.find_token_at_offset::<ast::String>()?;
+    let tt = fmt_string.syntax().parent().and_then(ast::TokenTree::cast)?;
+
+    let expanded_t = ast::String::cast(
+        ctx.sema
+            .descend_into_macros_single(DescendPreference::SameKind, fmt_string.syntax().clone()),
+    )?;
+    if !is_format_string(&expanded_t) {
+        return None;
+    }
+
+    let placeholders = positional_placeholders(&fmt_string)?;
+    if placeholders.is_empty() {
+        return None;
+    }
+
+    // Collect the arguments following the format string, bailing if any of them is something
+    // other than a single identifier, for example `name = value` or a field access.
+    let mut elements: Vec<_> = tt
+        .syntax()
+        .children_with_tokens()
+        .skip_while(|it| it.as_token() != Some(fmt_string.syntax()))
+        .skip(1)
+        .filter(|it| !it.kind().is_trivia())
+        .collect();
+    // the closing delimiter
+    elements.pop();
+    let last_element = elements.last()?.clone();
+    let mut groups = elements.split(|it| it.kind() == COMMA).peekable();
+    if !groups.next()?.is_empty() {
+        return None;
+    }
+    let mut args = Vec::new();
+    while let Some(group) = groups.next() {
+        match group {
+            // Format strings can't refer to raw identifiers.
+            [NodeOrToken::Token(ident)] if ident.text().starts_with("r#") => {
+                cov_mark::hit!(inline_format_args_raw_ident);
+                return None;
+            }
+            [NodeOrToken::Token(ident)] if ident.kind() == IDENT => args.push(ident.clone()),
+            // trailing comma
+            [] if groups.peek().is_none() => (),
+            _ => return None,
+        }
+    }
+    if args.is_empty() {
+        return None;
+    }
+
+    let scope = ctx.sema.scope(tt.syntax())?;
+    let all_locals = args.iter().all(|arg| {
+        matches!(
+            scope.speculative_resolve(&make::ext::ident_path(arg.text())),
+            Some(PathResolution::Local(_))
+        )
+    });
+    if !all_locals {
+        cov_mark::hit!(inline_format_args_not_a_local);
+        return None;
+    }
+
+    let mut implicit_idx = 0;
+    let mut used = vec![false; args.len()];
+    let mut edits = Vec::with_capacity(placeholders.len());
+    for placeholder in placeholders {
+        let (range, idx) = match placeholder {
+            Placeholder::Implicit(offset) => {
+                implicit_idx += 1;
+                (TextRange::empty(offset), implicit_idx - 1)
+            }
+            Placeholder::Explicit(range, idx) => (range, idx),
+        };
+        let arg = args.get(idx)?;
+        used[idx] = true;
+        edits.push((range + fmt_string.syntax().text_range().start(), arg.text().to_owned()));
+    }
+    if used.contains(&false) {
+        return None;
+    }
+
+    acc.add(
+        AssistId("inline_format_args", AssistKind::RefactorRewrite),
+        "Inline format arguments",
+        fmt_string.syntax().text_range(),
+        |edit| {
+            for (range, name) in edits {
+                edit.replace(range, name);
+            }
+            // Also removes a trailing comma after the last argument.
+            edit.delete(TextRange::new(
+                fmt_string.syntax().text_range().end(),
+                last_element.text_range().end(),
+            ));
+        },
+    )
+}
+
+enum Placeholder {
+    /// `{}` or `{:?}`, with the offset right after the opening brace.
+    Implicit(TextSize),
+    /// `{0}` or `{0:?}`, with the range of the index.
+    Explicit(TextRange, usize),
+}
+
+/// Returns the placeholders referring to positional arguments, or `None` if the format string uses
+/// arguments for width or precision, which can't be inlined as easily.
+fn positional_placeholders(fmt_string: &ast::String) -> Option<Vec<Placeholder>> {
+    let text = fmt_string.text();
+    let mut placeholders = Vec::new();
+    let mut open_end = None;
+    let mut unsupported = false;
+    lex_format_specifiers(fmt_string, &mut |range, kind| match kind {
+        FormatSpecifier::Open => {
+            open_end = Some(range.end());
+            placeholders.push(Placeholder::Implicit(range.end()));
+        }
+        FormatSpecifier::Integer | FormatSpecifier::Identifier
+            if open_end == Some(range.start()) =>
+        {
+            placeholders.pop();
+            if let FormatSpecifier::Integer = kind {
+                match text[range].parse() {
+                    Ok(idx) => placeholders.push(Placeholder::Explicit(range, idx)),
+                    Err(_) => unsupported = true,
+                }
+            }
+        }
+        FormatSpecifier::DollarSign | FormatSpecifier::Asterisk => unsupported = true,
+        FormatSpecifier::Close => open_end = None,
+        _ => (),
+    });
+    (!unsupported).then_some(placeholders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn inline_implicit_args() {
+        check_assist(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+fn main() {
+    let name = "world";
+    let count = 3;
+    print!("hello {} {:?} {:>5}$0", name, count, name);
+}
+"#,
+            r#"
+fn main() {
+    let name = "world";
+    let count = 3;
+    print!("hello {name} {count:?} {name:>5}");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn inline_explicit_args() {
+        check_assist(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+fn main() {
+    let a = 1;
+    let b = 2;
+    print!("{1} {0:x} {} {1}$0", a, b,);
+}
+"#,
+            r#"
+fn main() {
+    let a = 1;
+    let b = 2;
+    print!("{b} {a:x} {a} {b}");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn inline_keeps_existing_inline_args() {
+        check_assist(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+fn main() {
+    let a = 1;
+    let b = 2;
+    print!("{a} {{}} {}$0", b);
+}
+"#,
+            r#"
+fn main() {
+    let a = 1;
+    let b = 2;
+    print!("{a} {{}} {b}");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn inline_in_write_like_macro() {
+        check_assist(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+macro_rules! write {
+    ($dst:expr, $($arg:tt)*) => {
+        $dst.write_fmt(format_args!($($arg)*))
+    };
+}
+struct W;
+impl W {
+    fn write_fmt(&mut self, _: core::fmt::Arguments<'_>) {}
+}
+fn f(w: &mut W, x: u32) {
+    write!(w, "$0{}", x);
+}
+"#,
+            r#"
+macro_rules! write {
+    ($dst:expr, $($arg:tt)*) => {
+        $dst.write_fmt(format_args!($($arg)*))
+    };
+}
+struct W;
+impl W {
+    fn write_fmt(&mut self, _: core::fmt::Arguments<'_>) {}
+}
+fn f(w: &mut W, x: u32) {
+    write!(w, "{x}");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn inline_not_applicable_to_complex_args() {
+        check_assist_not_applicable(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+struct S { a: u32 }
+fn main() {
+    let s = S { a: 1 };
+    print!("{}$0", s.a);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+fn main() {
+    let a = 1;
+    print!("{}$0", a + 1);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+fn main() {
+    let a = 1;
+    print!("{x}$0", x = a);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn inline_not_applicable_with_width_args() {
+        check_assist_not_applicable(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+fn main() {
+    let a = 1;
+    let w = 5;
+    print!("{:1$}$0", a, w);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+fn main() {
+    let a = 1.0;
+    let p = 5;
+    print!("{:.*}$0", p, a);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn inline_not_applicable_to_non_locals() {
+        cov_mark::check!(inline_format_args_not_a_local);
+        check_assist_not_applicable(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+const C: u32 = 0;
+fn main() {
+    print!("{}$0", C);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn inline_not_applicable_to_raw_idents() {
+        cov_mark::check!(inline_format_args_raw_ident);
+        check_assist_not_applicable(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+fn main() {
+    let r#type = 1;
+    print!("{}$0", r#type);
+}
+"#,
+        );
+    }
+}
@@ -32,8 +32,7 @@ pub(crate) fn introduce_named_generic(acc: &mut Assists, ctx: &AssistContext<'_>
             let impl_trait_type = edit.make_mut(impl_trait_type);
             let fn_ = edit.make_mut(fn_);
             let fn_generic_param_list = fn_.get_or_create_generic_param_list();
-            let type_param_name =
-                suggest_name::for_impl_trait_as_generic(&impl_trait_type, &fn_generic_param_list);
+            let type_param_name = suggest_name::for_impl_trait_as_generic(&impl_trait_type);
 
             let type_param = make::type_param(make::name(&type_param_name), Some(type_bound_list))
                 .clone_for_update();
@@ -175,4 +174,38 @@ fn foo<
             r#"fn f<$0I: Iterator<Item = i32>>(x: &mut Vec<I>) {}"#,
         );
     }
+
+    #[test]
+    fn replace_impl_trait_avoids_shadowing_used_types() {
+        check_assist(
+            introduce_named_generic,
+            r#"
+struct B;
+fn foo(b: B, bar: $0impl Bar) -> B { b }
+"#,
+            r#"
+struct B;
+fn foo<$0B0: Bar>(b: B, bar: B0) -> B { b }
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_impl_trait_avoids_shadowing_types_used_in_body() {
+        check_assist(
+            introduce_named_generic,
+            r#"
+struct I;
+fn foo(iter: $0impl Iterator<Item = i32>) {
+    let _ = I;
+}
+"#,
+            r#"
+struct I;
+fn foo<$0I0: Iterator<Item = i32>>(iter: I0) {
+    let _ = I;
+}
+"#,
+        );
+    }
 }
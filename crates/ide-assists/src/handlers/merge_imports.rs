@@ -1,3 +1,5 @@
+use std::iter;
+
 use either::Either;
 use ide_db::imports::{
     insert_use::{ImportGranularity, InsertUseConfig},
@@ -38,8 +40,11 @@ pub(crate) fn merge_imports(acc: &mut Assists, ctx: &AssistContext<'_>) -> Optio
         let target = tree.syntax().text_range();
 
         let use_item = tree.syntax().parent().and_then(ast::Use::cast)?;
-        let mut neighbor = next_prev().find_map(|dir| neighbor(&use_item, dir)).into_iter();
-        let edits = use_item.try_merge_from(&mut neighbor, &ctx.config.insert_use);
+        // Fall back to the previous neighbor if the next one can't be merged, e.g. because its
+        // visibility differs.
+        let edits = next_prev().filter_map(|dir| neighbor(&use_item, dir)).find_map(|neighbor| {
+            use_item.clone().try_merge_from(&mut iter::once(neighbor), &ctx.config.insert_use)
+        });
         (target, edits?)
     } else {
         // Merge selected
@@ -385,6 +390,41 @@ pub(in this::path) use std::fmt::{Debug, Display};
         )
     }
 
+    #[test]
+    fn merge_prev_if_next_has_different_visibility() {
+        check_assist(
+            merge_imports,
+            r"
+use std::fmt::Debug;
+use std::fmt$0::Display;
+pub use std::fmt::Write;
+",
+            r"
+use std::fmt::{Debug, Display};
+pub use std::fmt::Write;
+",
+        )
+    }
+
+    #[test]
+    fn merge_prev_with_glob_and_alias() {
+        check_assist(
+            merge_imports,
+            r"
+#[cfg(test)]
+use std::fmt::*;
+#[cfg(test)]
+use std::fmt$0::Result as FmtResult;
+use std::fmt::Write;
+",
+            r"
+#[cfg(test)]
+use std::fmt::{Result as FmtResult, *};
+use std::fmt::Write;
+",
+        )
+    }
+
     #[test]
     fn test_merge_nested() {
         check_assist(
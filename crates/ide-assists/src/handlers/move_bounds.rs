@@ -1,17 +1,19 @@
+use either::Either;
 use syntax::{
     ast::{
         self,
         edit_in_place::{GenericParamsOwnerEdit, Removable},
-        make, AstNode, HasName, HasTypeBounds,
+        make, AstNode, HasGenericParams, HasName, HasTypeBounds,
     },
-    match_ast,
+    match_ast, ted, Direction, SyntaxElement, SyntaxKind, T,
 };
 
 use crate::{AssistContext, AssistId, AssistKind, Assists};
 
 // Assist: move_bounds_to_where_clause
 //
-// Moves inline type bounds to a where clause.
+// Moves inline type bounds to a where clause. Bounds of parameters that already have a
+// predicate in the where clause are merged into it.
 //
 // ```
 // fn apply<T, U, $0F: FnOnce(T) -> U>(f: F, x: T) -> U {
@@ -65,28 +67,184 @@ pub(crate) fn move_bounds_to_where_clause(
                     ast::TypeOrConstParam::Type(x) => x,
                     ast::TypeOrConstParam::Const(_) => continue,
                 };
-                if let Some(tbl) = type_param.type_bound_list() {
-                    if let Some(predicate) = build_predicate(type_param) {
-                        where_clause.add_predicate(predicate)
+                let Some(tbl) = type_param.type_bound_list() else { continue };
+                let Some(name) = type_param.name() else { continue };
+                let existing = where_clause
+                    .predicates()
+                    .find(|pred| predicate_param(pred).as_deref() == Some(name.text().as_str()));
+                match existing {
+                    // The inline bounds come first, as they did in the source.
+                    Some(pred) => {
+                        let bounds = tbl
+                            .bounds()
+                            .chain(pred.type_bound_list().into_iter().flat_map(|it| it.bounds()));
+                        let merged = build_predicate(&name, bounds);
+                        ted::replace(pred.syntax(), merged.syntax());
                     }
-                    tbl.remove()
+                    None => where_clause.add_predicate(build_predicate(&name, tbl.bounds())),
                 }
+                tbl.remove()
             }
         },
     )
 }
 
-fn build_predicate(param: ast::TypeParam) -> Option<ast::WherePred> {
-    let path = make::ext::ident_path(&param.name()?.syntax().to_string());
-    let predicate = make::where_pred(path, param.type_bound_list()?.bounds());
-    Some(predicate.clone_for_update())
+// Assist: move_where_clause_to_bounds
+//
+// Moves the predicates of a where clause that bound a generic type parameter to the parameter's
+// inline bounds. Other predicates are kept in the where clause.
+//
+// ```
+// fn apply<T, U, F>(f: F, x: T) -> U where F: FnOnce(T) -> U, $0Vec<T>: Clone {
+//     f(x)
+// }
+// ```
+// ->
+// ```
+// fn apply<T, U, F: FnOnce(T) -> U>(f: F, x: T) -> U where Vec<T>: Clone {
+//     f(x)
+// }
+// ```
+pub(crate) fn move_where_clause_to_bounds(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let where_clause = ctx.find_node_at_offset::<ast::WhereClause>()?;
+    let parent = ast::AnyHasGenericParams::cast(where_clause.syntax().parent()?)?;
+    let type_param_list = parent.generic_param_list()?;
+
+    let inlinable = |type_param_list: &ast::GenericParamList, pred: &ast::WherePred| {
+        let name = predicate_param(pred)?;
+        pred.type_bound_list()?;
+        type_param_list.type_or_const_params().find_map(|param| match param {
+            ast::TypeOrConstParam::Type(it) if it.name()?.text() == name.as_str() => Some(it),
+            _ => None,
+        })
+    };
+    if !where_clause.predicates().any(|pred| inlinable(&type_param_list, &pred).is_some()) {
+        return None;
+    }
+
+    let target = where_clause.syntax().text_range();
+    acc.add(
+        AssistId("move_where_clause_to_bounds", AssistKind::RefactorRewrite),
+        "Move to inline bounds",
+        target,
+        |edit| {
+            let where_clause = edit.make_mut(where_clause);
+            let type_param_list = edit.make_mut(type_param_list);
+
+            for pred in where_clause.predicates() {
+                let Some(type_param) = inlinable(&type_param_list, &pred) else { continue };
+                let Some(pred_bounds) = pred.type_bound_list() else { continue };
+                match type_param.type_bound_list() {
+                    Some(tbl) => {
+                        let Some(merged) =
+                            make::type_bound_list(tbl.bounds().chain(pred_bounds.bounds()))
+                        else {
+                            continue;
+                        };
+                        ted::replace(tbl.syntax(), merged.clone_for_update().syntax());
+                    }
+                    None => {
+                        let Some(name) = type_param.name() else { continue };
+                        ted::insert_all_raw(
+                            ted::Position::after(name.syntax()),
+                            vec![
+                                make::token(T![:]).into(),
+                                make::tokens::single_space().into(),
+                                pred_bounds
+                                    .clone_subtree()
+                                    .clone_for_update()
+                                    .syntax()
+                                    .clone()
+                                    .into(),
+                            ],
+                        );
+                    }
+                }
+                remove_predicate(&pred);
+            }
+
+            if where_clause.predicates().next().is_none() {
+                remove_where_clause(&where_clause);
+            }
+        },
+    )
+}
+
+fn build_predicate(
+    name: &ast::Name,
+    bounds: impl IntoIterator<Item = ast::TypeBound>,
+) -> ast::WherePred {
+    let path = make::ext::ident_path(&name.syntax().to_string());
+    make::where_pred(Either::Right(make::ty_path(path)), bounds).clone_for_update()
+}
+
+/// Returns the name of the type parameter a predicate like `T: Clone` bounds, if it is as simple
+/// as that.
+fn predicate_param(pred: &ast::WherePred) -> Option<String> {
+    if pred.generic_param_list().is_some() {
+        return None;
+    }
+    let ast::Type::PathType(ty) = pred.ty()? else { return None };
+    let path = ty.path()?;
+    if path.qualifier().is_some() {
+        return None;
+    }
+    let segment = path.segment()?;
+    if segment.generic_arg_list().is_some() {
+        return None;
+    }
+    Some(segment.name_ref()?.text().to_string())
+}
+
+fn remove_predicate(pred: &ast::WherePred) {
+    let pred_elem: SyntaxElement = pred.syntax().clone().into();
+    let is_trivia = |it: &SyntaxElement| it.kind() == SyntaxKind::WHITESPACE;
+    match pred.syntax().next_sibling_or_token().filter(|it| it.kind() == T![,]) {
+        Some(comma) => {
+            // `A, B` => `B`, but `A, B,` => `A,` if `B` is removed.
+            let ws = comma.next_sibling_or_token().filter(is_trivia);
+            match ws {
+                Some(ws) if ws.next_sibling_or_token().is_some() => ted::remove_all(pred_elem..=ws),
+                _ => {
+                    let start = pred.syntax().prev_sibling_or_token().filter(is_trivia);
+                    ted::remove_all(start.unwrap_or(pred_elem)..=comma)
+                }
+            }
+        }
+        None => {
+            let prev = pred
+                .syntax()
+                .siblings_with_tokens(Direction::Prev)
+                .skip(1)
+                .find(|it| !is_trivia(it))
+                .filter(|it| it.kind() == T![,]);
+            ted::remove_all(prev.unwrap_or_else(|| pred_elem.clone())..=pred_elem)
+        }
+    }
+}
+
+fn remove_where_clause(where_clause: &ast::WhereClause) {
+    if let Some(next) = where_clause.syntax().next_sibling_or_token() {
+        if next.kind() == SyntaxKind::WHITESPACE && next.to_string().contains('\n') {
+            ted::replace(next, make::tokens::single_space());
+        }
+    }
+    match where_clause.syntax().prev_sibling_or_token() {
+        Some(prev) if prev.kind() == SyntaxKind::WHITESPACE => {
+            ted::remove_all(prev..=where_clause.syntax().clone().into())
+        }
+        _ => ted::remove(where_clause.syntax()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::tests::check_assist;
+    use crate::tests::{check_assist, check_assist_not_applicable};
 
     #[test]
     fn move_bounds_to_where_clause_fn() {
@@ -123,4 +281,97 @@ mod tests {
             r#"struct Pair<T>(T, T) where T: u32;"#,
         );
     }
+
+    #[test]
+    fn move_bounds_to_where_clause_merges_existing_predicate() {
+        check_assist(
+            move_bounds_to_where_clause,
+            r#"fn foo<$0T: Clone, U>() where Vec<U>: Clone, T: Copy {}"#,
+            r#"fn foo<T, U>() where Vec<U>: Clone, T: Clone + Copy {}"#,
+        );
+    }
+
+    #[test]
+    fn move_where_clause_to_bounds_fn() {
+        check_assist(
+            move_where_clause_to_bounds,
+            r#"fn foo<T, F>() where $0T: u32, F: FnOnce(T) -> T {}"#,
+            r#"fn foo<T: u32, F: FnOnce(T) -> T>() {}"#,
+        );
+    }
+
+    #[test]
+    fn move_where_clause_to_bounds_keeps_other_predicates() {
+        check_assist(
+            move_where_clause_to_bounds,
+            r#"fn foo<'a, T: Clone, U>() where Vec<T>: Clone, T: Copy + 'a, $0'a: 'static, U::Item: Copy, for<'b> U: Fn(&'b T) {}"#,
+            r#"fn foo<'a, T: Clone + Copy + 'a, U>() where Vec<T>: Clone, 'a: 'static, U::Item: Copy, for<'b> U: Fn(&'b T) {}"#,
+        );
+    }
+
+    #[test]
+    fn move_where_clause_to_bounds_multiline() {
+        check_assist(
+            move_where_clause_to_bounds,
+            r#"
+struct Pair<T, U = ()>
+where
+    T: Clone,$0
+    U: Default,
+{
+    first: T,
+    second: U,
+}
+"#,
+            r#"
+struct Pair<T: Clone, U: Default = ()> {
+    first: T,
+    second: U,
+}
+"#,
+        );
+        check_assist(
+            move_where_clause_to_bounds,
+            r#"
+impl<T, U> Pair<T, U>
+where
+    Vec<T>: Clone,
+    U: Default,$0
+{
+}
+"#,
+            r#"
+impl<T, U: Default> Pair<T, U>
+where
+    Vec<T>: Clone,
+{
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn move_where_clause_to_bounds_tuple_struct() {
+        check_assist(
+            move_where_clause_to_bounds,
+            r#"struct Pair<T>(T, T) where $0T: u32;"#,
+            r#"struct Pair<T: u32>(T, T);"#,
+        );
+    }
+
+    #[test]
+    fn move_where_clause_to_bounds_not_applicable() {
+        check_assist_not_applicable(
+            move_where_clause_to_bounds,
+            r#"fn foo<T>() where $0Vec<T>: Clone, T::Item: Copy {}"#,
+        );
+    }
+
+    #[test]
+    fn move_bounds_round_trip() {
+        let inline = r#"fn foo<T: Clone, U: Clone + Default>() where Vec<T>: Clone {}"#;
+        let moved = r#"fn foo<T, U>() where Vec<T>: Clone, T: Clone, U: Clone + Default {}"#;
+        check_assist(move_bounds_to_where_clause, &inline.replace("<T", "<$0T"), moved);
+        check_assist(move_where_clause_to_bounds, &moved.replace("where ", "where $0"), inline);
+    }
 }
@@ -1,7 +1,7 @@
 use std::iter;
 
 use ast::edit::IndentLevel;
-use ide_db::base_db::AnchoredPathBuf;
+use ide_db::{base_db::AnchoredPathBuf, RootDatabase};
 use itertools::Itertools;
 use stdx::format_to;
 use syntax::{
@@ -42,6 +42,7 @@ pub(crate) fn move_module_to_file(acc: &mut Assists, ctx: &AssistContext<'_>) ->
         iter::successors(Some(module_ast.clone()), |module| module.parent()).last()?;
     let module_def = ctx.sema.to_def(&outermost_mod_decl)?;
     let parent_module = module_def.parent(ctx.db())?;
+    let use_mod_rs = crate_prefers_mod_rs(ctx.db(), module_def.krate());
 
     acc.add(
         AssistId("move_module_to_file", AssistKind::RefactorExtract),
@@ -65,7 +66,7 @@ pub(crate) fn move_module_to_file(acc: &mut Assists, ctx: &AssistContext<'_>) ->
 
                 // We need to special case mod named `r#mod` and place the file in a
                 // subdirectory as "mod.rs" would be of its parent module otherwise.
-                if module_name.text() == "r#mod" {
+                if use_mod_rs || module_name.text() == "r#mod" {
                     format_to!(buf, "/mod.rs");
                 } else {
                     format_to!(buf, ".rs");
@@ -101,6 +102,17 @@ pub(crate) fn move_module_to_file(acc: &mut Assists, ctx: &AssistContext<'_>) ->
     )
 }
 
+/// Whether all of the crate's out-of-line modules live in `mod.rs` files, in which case we
+/// follow that convention instead of creating `foo.rs`.
+fn crate_prefers_mod_rs(db: &RootDatabase, krate: hir::Crate) -> bool {
+    let mut file_modules = krate
+        .modules(db)
+        .into_iter()
+        .filter(|module| !module.is_crate_root() && !module.is_inline(db))
+        .peekable();
+    file_modules.peek().is_some() && file_modules.all(|module| module.is_mod_rs(db))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::{check_assist, check_assist_not_applicable};
@@ -165,12 +177,62 @@ fn g() {}
 //- /submodule/mod.rs
 mod inner;
 fn g() {}
-//- /submodule/inner.rs
+//- /submodule/inner/mod.rs
+fn f() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_following_mod_rs_convention() {
+        check_assist(
+            move_module_to_file,
+            r#"
+//- /main.rs
+mod submodule;
+mod $0inner {
+    fn f() {}
+}
+//- /submodule/mod.rs
+"#,
+            r#"
+//- /main.rs
+mod submodule;
+mod inner;
+//- /inner/mod.rs
 fn f() {}
 "#,
         );
     }
 
+    #[test]
+    fn extract_with_inner_attributes() {
+        check_assist(
+            move_module_to_file,
+            r#"
+#[allow(unused)]
+pub(crate) mod $0tests {
+    #![allow(dead_code)]
+
+    fn helper() {
+        let _ = 1;
+    }
+}
+"#,
+            r#"
+//- /main.rs
+#[allow(unused)]
+pub(crate) mod tests;
+//- /tests.rs
+#![allow(dead_code)]
+
+fn helper() {
+    let _ = 1;
+}
+"#,
+        );
+    }
+
     #[test]
     fn extract_public() {
         check_assist(
@@ -8,7 +8,8 @@ use crate::{AssistContext, AssistId, AssistKind, Assists};
 
 // Assist: remove_dbg
 //
-// Removes `dbg!()` macro call.
+// Removes `dbg!()` macro call. With the cursor inside a single call, only that call is removed,
+// while a selection removes every `dbg!()` in it, nested ones included.
 //
 // ```
 // fn main() {
@@ -22,6 +23,7 @@ use crate::{AssistContext, AssistId, AssistKind, Assists};
 // }
 // ```
 pub(crate) fn remove_dbg(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let strip_nested = !ctx.has_empty_selection();
     let macro_calls = if ctx.has_empty_selection() {
         vec![ctx.find_node_at_offset::<ast::MacroExpr>()?]
     } else {
@@ -37,8 +39,10 @@ pub(crate) fn remove_dbg(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<(
             .collect()
     };
 
-    let replacements =
-        macro_calls.into_iter().filter_map(compute_dbg_replacement).collect::<Vec<_>>();
+    let replacements = macro_calls
+        .into_iter()
+        .filter_map(|it| compute_dbg_replacement(it, strip_nested))
+        .collect::<Vec<_>>();
 
     acc.add(
         AssistId("remove_dbg", AssistKind::Refactor),
@@ -62,7 +66,12 @@ pub(crate) fn remove_dbg(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<(
 /// - (`macro_expr` has no parent - is that possible?)
 ///
 /// Returns `Some(_, None)` when the macro call should just be removed.
-fn compute_dbg_replacement(macro_expr: ast::MacroExpr) -> Option<(TextRange, Option<ast::Expr>)> {
+///
+/// `dbg!()` calls nested in the arguments are removed as well if `strip_nested` is set.
+fn compute_dbg_replacement(
+    macro_expr: ast::MacroExpr,
+    strip_nested: bool,
+) -> Option<(TextRange, Option<ast::Expr>)> {
     let macro_call = macro_expr.macro_call()?;
     let tt = macro_call.token_tree()?;
     let r_delim = NodeOrToken::Token(tt.right_delimiter_token()?);
@@ -80,6 +89,7 @@ fn compute_dbg_replacement(macro_expr: ast::MacroExpr) -> Option<(TextRange, Opt
         .map(|mut tokens| syntax::hacks::parse_expr_from_str(&tokens.join("")))
         .collect::<Option<Vec<ast::Expr>>>()?;
 
+    let strip = |expr| if strip_nested { replace_nested_dbgs(expr) } else { expr };
     let parent = macro_expr.syntax().parent()?;
     Some(match &*input_expressions {
         // dbg!()
@@ -144,13 +154,13 @@ fn compute_dbg_replacement(macro_expr: ast::MacroExpr) -> Option<(TextRange, Opt
                 },
                 None => false,
             };
-            let expr = replace_nested_dbgs(expr.clone());
+            let expr = strip(expr.clone());
             let expr = if wrap { make::expr_paren(expr) } else { expr.clone_subtree() };
             (macro_call.syntax().text_range(), Some(expr))
         }
         // dbg!(expr0, expr1, ...)
         exprs => {
-            let exprs = exprs.iter().cloned().map(replace_nested_dbgs);
+            let exprs = exprs.iter().cloned().map(strip);
             let expr = make::expr_tuple(exprs);
             (macro_call.syntax().text_range(), Some(expr))
         }
@@ -162,7 +172,7 @@ fn replace_nested_dbgs(expanded: ast::Expr) -> ast::Expr {
         // Special-case when `expanded` itself is `dbg!()` since we cannot replace the whole tree
         // with `ted`. It should be fairly rare as it means the user wrote `dbg!(dbg!(..))` but you
         // never know how code ends up being!
-        let replaced = if let Some((_, expr_opt)) = compute_dbg_replacement(mac.clone()) {
+        let replaced = if let Some((_, expr_opt)) = compute_dbg_replacement(mac.clone(), true) {
             match expr_opt {
                 Some(expr) => expr,
                 None => {
@@ -184,7 +194,7 @@ fn replace_nested_dbgs(expanded: ast::Expr) -> ast::Expr {
         expanded.syntax().descendants().filter_map(ast::MacroExpr::cast).collect();
 
     for mac in macro_exprs {
-        let expr_opt = match compute_dbg_replacement(mac.clone()) {
+        let expr_opt = match compute_dbg_replacement(mac.clone(), true) {
             Some((_, expr)) => expr,
             None => continue,
         };
@@ -357,6 +367,12 @@ fn f() {
         check(r#"$0dbg!(10, dbg!(), dbg!(20, 30))$0"#, r#"(10, (), (20, 30))"#);
     }
 
+    #[test]
+    fn test_nested_dbg_at_cursor() {
+        check(r#"let x = dbg!(dbg!(dbg!(0 +$0 1)) * 2);"#, r#"let x = dbg!(dbg!(0 + 1)) * 2;"#);
+        check(r#"let x = $0dbg!(1, dbg!(2));"#, r#"let x = (1, dbg!(2));"#);
+    }
+
     #[test]
     fn test_multiple_nested_dbg() {
         check(
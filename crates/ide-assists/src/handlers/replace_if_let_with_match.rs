@@ -236,6 +236,18 @@ pub(crate) fn replace_match_with_if_let(acc: &mut Assists, ctx: &AssistContext<'
         second_arm.expr()?,
     )?;
     let scrutinee = match_expr.expr()?;
+    // For a boolean match, branch on the positive condition unless that leaves the `then` branch
+    // empty, so `false => a, _ => b` becomes `if cond { b } else { a }`.
+    let (if_let_pat, then_expr, else_expr) = match &if_let_pat {
+        ast::Pat::LiteralPat(p)
+            if p.literal().map_or(false, |it| it.token().kind() == T![false])
+                && !is_empty_expr(&else_expr)
+                && ctx.sema.type_of_expr(&scrutinee).map_or(false, |it| it.original.is_bool()) =>
+        {
+            (make::literal_pat("true").into(), else_expr, then_expr)
+        }
+        _ => (if_let_pat, then_expr, else_expr),
+    };
 
     let let_ = match &if_let_pat {
         ast::Pat::LiteralPat(p)
@@ -1152,4 +1164,63 @@ fn main() {
 "#,
         )
     }
+
+    #[test]
+    fn test_replace_match_with_if_bool_arm_order() {
+        check_assist(
+            replace_match_with_if_let,
+            r#"
+fn f(b: bool) -> u8 {
+    match$0 b && true {
+        false => 0,
+        true => {
+            let x = 1;
+            x
+        }
+    }
+}
+"#,
+            r#"
+fn f(b: bool) -> u8 {
+    if b && true {
+        let x = 1;
+        x
+    } else {
+        0
+    }
+}
+"#,
+        );
+        check_assist(
+            replace_match_with_if_let,
+            r#"
+fn f(b: bool) -> u8 {
+    match$0 b {
+        false => 0,
+        _ => 1,
+    }
+}
+"#,
+            r#"
+fn f(b: bool) -> u8 {
+    if b {
+        1
+    } else {
+        0
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            replace_match_with_if_let,
+            r#"
+fn f(b: bool) -> u8 {
+    match$0 b {
+        _ => 0,
+        true => 1,
+    }
+}
+"#,
+        );
+    }
 }
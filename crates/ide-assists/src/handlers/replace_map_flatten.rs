@@ -0,0 +1,210 @@
+use hir::AsAssocItem;
+use ide_db::famous_defs::FamousDefs;
+use syntax::{
+    ast::{self, AstNode, HasArgList},
+    TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: replace_map_flatten
+//
+// Replaces `.map(f).flatten()` with `.flat_map(f)` on iterators and with `.and_then(f)` on
+// `Option` and `Result`.
+//
+// ```
+// # //- minicore: flatten
+// fn f(xs: [u32; 2]) {
+//     let _ = xs.into_iter().map(|x| [x, x + 1]).flat$0ten();
+// }
+// ```
+// ->
+// ```
+// fn f(xs: [u32; 2]) {
+//     let _ = xs.into_iter().flat_map(|x| [x, x + 1]);
+// }
+// ```
+pub(crate) fn replace_map_flatten(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let name_ref = ctx.find_node_at_offset::<ast::NameRef>()?;
+    let call = ast::MethodCallExpr::cast(name_ref.syntax().parent()?)?;
+    let (map_call, flatten_call) = match name_ref.text().as_str() {
+        "map" => (call.clone(), ast::MethodCallExpr::cast(call.syntax().parent()?)?),
+        "flatten" => match call.receiver()? {
+            ast::Expr::MethodCallExpr(receiver) => (receiver, call),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    // `flatten` has to be called directly on the result of `map`.
+    if flatten_call.receiver()?.syntax() != map_call.syntax()
+        || flatten_call.name_ref()?.text() != "flatten"
+        || flatten_call.arg_list()?.args().next().is_some()
+    {
+        return None;
+    }
+    let map_name = map_call.name_ref()?;
+    // A turbofish on `map` names the closure's output, which isn't what `and_then` is generic over.
+    if map_name.text() != "map"
+        || map_call.generic_arg_list().is_some()
+        || map_call.arg_list()?.args().count() != 1
+    {
+        return None;
+    }
+
+    let db = ctx.db();
+    let famous_defs = FamousDefs(&ctx.sema, ctx.sema.scope(flatten_call.syntax())?.krate());
+    let map_fn = ctx.sema.resolve_method_call(&map_call)?.as_assoc_item(db)?;
+    let flatten_fn = ctx.sema.resolve_method_call(&flatten_call)?.as_assoc_item(db)?;
+    let replacement = if map_fn.container_or_implemented_trait(db).is_some() {
+        let iterator = famous_defs.core_iter_Iterator()?;
+        if map_fn.container_or_implemented_trait(db)? != iterator
+            || flatten_fn.container_or_implemented_trait(db)? != iterator
+        {
+            return None;
+        }
+        "flat_map"
+    } else {
+        let adt = map_fn.implementing_ty(db)?.as_adt()?;
+        let is_option_or_result =
+            [famous_defs.core_option_Option(), famous_defs.core_result_Result()]
+                .into_iter()
+                .flatten()
+                .any(|it| hir::Adt::from(it) == adt);
+        if !is_option_or_result || flatten_fn.implementing_ty(db)?.as_adt()? != adt {
+            return None;
+        }
+        "and_then"
+    };
+
+    let target = TextRange::new(
+        map_name.syntax().text_range().start(),
+        flatten_call.syntax().text_range().end(),
+    );
+    acc.add(
+        AssistId("replace_map_flatten", AssistKind::RefactorRewrite),
+        format!("Replace `map(..).flatten()` with `{replacement}(..)`"),
+        target,
+        |builder| {
+            let args = map_call.arg_list().map(|it| it.syntax().text().to_string());
+            builder.replace(target, format!("{replacement}{}", args.unwrap_or_default()));
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn iterator_map_flatten() {
+        check_assist(
+            replace_map_flatten,
+            r#"
+//- minicore: flatten
+fn f(xs: [[u8; 2]; 3]) {
+    let _ = xs
+        .into_iter()
+        .ma$0p(|pair| {
+            // Swap the halves.
+            [pair[1], pair[0]]
+        })
+        .flatten();
+}
+"#,
+            r#"
+fn f(xs: [[u8; 2]; 3]) {
+    let _ = xs
+        .into_iter()
+        .flat_map(|pair| {
+            // Swap the halves.
+            [pair[1], pair[0]]
+        });
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn option_and_result_map_flatten() {
+        check_assist(
+            replace_map_flatten,
+            r#"
+//- minicore: flatten
+fn parse(s: &str) -> Option<u8> { None }
+fn f(s: Option<&str>) -> Option<u8> {
+    s.map(parse).flat$0ten()
+}
+"#,
+            r#"
+fn parse(s: &str) -> Option<u8> { None }
+fn f(s: Option<&str>) -> Option<u8> {
+    s.and_then(parse)
+}
+"#,
+        );
+        check_assist(
+            replace_map_flatten,
+            r#"
+//- minicore: flatten, result
+fn f(r: Result<u8, ()>) -> Result<u8, ()> {
+    r.map(|x| if x > 0 { Ok(x) } else { Err(()) }).flat$0ten()
+}
+"#,
+            r#"
+fn f(r: Result<u8, ()>) -> Result<u8, ()> {
+    r.and_then(|x| if x > 0 { Ok(x) } else { Err(()) })
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable() {
+        // Not directly adjacent.
+        check_assist_not_applicable(
+            replace_map_flatten,
+            r#"
+//- minicore: flatten
+fn f(xs: [[u8; 2]; 3]) {
+    let _ = xs.into_iter().map(|pair| pair).take(1).flat$0ten();
+}
+"#,
+        );
+        // `flatten` isn't called on the output of `map`.
+        check_assist_not_applicable(
+            replace_map_flatten,
+            r#"
+//- minicore: flatten
+fn f(xs: [[u8; 2]; 3]) {
+    let _ = Some(xs.into_iter().ma$0p(|pair| pair)).flatten();
+}
+"#,
+        );
+        // Methods of other types named like the iterator adapters.
+        check_assist_not_applicable(
+            replace_map_flatten,
+            r#"
+struct S;
+impl S {
+    fn map<F>(self, _f: F) -> S { S }
+    fn flatten(self) -> S { S }
+}
+fn f(s: S) {
+    s.map(|it| it).flat$0ten();
+}
+"#,
+        );
+        check_assist_not_applicable(
+            replace_map_flatten,
+            r#"
+//- minicore: flatten
+fn f(xs: [[u8; 2]; 3]) {
+    let _ = xs.into_iter().ma$0p::<[u8; 2], _>(|pair| pair).flatten();
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,427 @@
+use hir::HirDisplay;
+use ide_db::FxHashSet;
+use syntax::{
+    ast::{self, edit::IndentLevel, HasName},
+    match_ast, AstNode, SyntaxKind, TextRange,
+};
+
+use crate::{utils::suggest_name, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: split_try_chain
+//
+// Splits a method chain with several `?` into a sequence of `let` statements, one for every
+// fallible step.
+//
+// ```
+// # //- minicore: option, try
+// struct Config;
+// struct Section;
+// struct Entry;
+// impl Config {
+//     fn section(&self) -> Option<Section> { None }
+// }
+// impl Section {
+//     fn entry(&self) -> Option<Entry> { None }
+// }
+// impl Entry {
+//     fn value(&self) -> Option<u32> { None }
+// }
+// fn value(config: &Config) -> Option<u32> {
+//     config.section()?.entry()?.value()$0
+// }
+// ```
+// ->
+// ```
+// struct Config;
+// struct Section;
+// struct Entry;
+// impl Config {
+//     fn section(&self) -> Option<Section> { None }
+// }
+// impl Section {
+//     fn entry(&self) -> Option<Entry> { None }
+// }
+// impl Entry {
+//     fn value(&self) -> Option<u32> { None }
+// }
+// fn value(config: &Config) -> Option<u32> {
+//     let section: Section = config.section()?;
+//     let entry: Entry = section.entry()?;
+//     entry.value()
+// }
+// ```
+pub(crate) fn split_try_chain(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let expr = ctx.find_node_at_offset::<ast::Expr>()?;
+    let mut chain = expr.syntax().ancestors().filter_map(ast::Expr::cast).find(is_chain_link)?;
+    while let Some(parent) = chain.syntax().parent().and_then(ast::Expr::cast) {
+        let continues_chain = match &parent {
+            ast::Expr::MethodCallExpr(it) => it.receiver().as_ref() == Some(&chain),
+            ast::Expr::FieldExpr(_) | ast::Expr::TryExpr(_) | ast::Expr::AwaitExpr(_) => true,
+            _ => false,
+        };
+        if !continues_chain {
+            break;
+        }
+        chain = parent;
+    }
+
+    // The temporaries are inserted in front of the statement the chain makes up.
+    let anchor = chain.syntax().parent()?;
+    if !matches!(
+        anchor.kind(),
+        SyntaxKind::LET_STMT | SyntaxKind::EXPR_STMT | SyntaxKind::STMT_LIST
+    ) {
+        return None;
+    }
+    let anchor =
+        if anchor.kind() == SyntaxKind::STMT_LIST { chain.syntax().clone() } else { anchor };
+
+    // Every `?` but the outermost ends a step, innermost first.
+    let mut steps = Vec::new();
+    let mut link = Some(chain.clone());
+    while let Some(expr) = link {
+        link = match &expr {
+            ast::Expr::TryExpr(it) => {
+                if expr != chain {
+                    steps.push(it.clone());
+                }
+                it.expr()
+            }
+            ast::Expr::MethodCallExpr(it) => it.receiver(),
+            ast::Expr::FieldExpr(it) => it.expr(),
+            ast::Expr::AwaitExpr(it) => it.expr(),
+            _ => None,
+        };
+    }
+    if steps.is_empty() {
+        return None;
+    }
+    steps.reverse();
+
+    let module = ctx.sema.scope(chain.syntax())?.module();
+    let mut taken: FxHashSet<String> = match anchor.ancestors().find_map(ast::Fn::cast) {
+        Some(fn_) => fn_
+            .syntax()
+            .descendants()
+            .filter_map(|it| {
+                match_ast! {
+                    match it {
+                        ast::IdentPat(it) => Some(it.name()?.to_string()),
+                        ast::PathSegment(it) => Some(it.name_ref()?.to_string()),
+                        _ => None,
+                    }
+                }
+            })
+            .collect(),
+        None => FxHashSet::default(),
+    };
+
+    acc.add(
+        AssistId("split_try_chain", AssistKind::RefactorRewrite),
+        "Split `?` chain into `let` statements",
+        chain.syntax().text_range(),
+        |edit| {
+            let indent = IndentLevel::from_node(&anchor);
+            let mut lets = String::new();
+            let mut prev: Option<(TextRange, String)> = None;
+            for step in &steps {
+                let expr = ast::Expr::TryExpr(step.clone());
+                let name = suggest_name::for_unique_variable(&expr, &ctx.sema, &taken);
+                taken.insert(name.to_string());
+
+                let ty = ctx
+                    .sema
+                    .type_of_expr(&expr)
+                    .map(|it| it.original)
+                    .filter(|it| !it.contains_unknown())
+                    // `impl Trait` types can't be written down in a `let`.
+                    .and_then(|it| it.display_source_code(ctx.db(), module.into(), false).ok())
+                    .map(|it| format!(": {it}"))
+                    .unwrap_or_default();
+                let init = replace_step(step.syntax().text_range(), &step.to_string(), &prev);
+                lets.push_str(&format!("let {name}{ty} = {init};\n{indent}"));
+                prev = Some((step.syntax().text_range(), name.to_string()));
+            }
+
+            let rest = replace_step(chain.syntax().text_range(), &chain.to_string(), &prev);
+            edit.insert(anchor.text_range().start(), lets);
+            edit.replace(chain.syntax().text_range(), rest);
+        },
+    )
+}
+
+fn is_chain_link(expr: &ast::Expr) -> bool {
+    matches!(
+        expr,
+        ast::Expr::MethodCallExpr(_)
+            | ast::Expr::FieldExpr(_)
+            | ast::Expr::TryExpr(_)
+            | ast::Expr::AwaitExpr(_)
+    )
+}
+
+/// Replaces the previous step, which is part of the text of the expression at `range`, with the
+/// name of its temporary.
+fn replace_step(range: TextRange, text: &str, prev: &Option<(TextRange, String)>) -> String {
+    match prev {
+        Some((prev_range, name)) => {
+            let prev_range = *prev_range - range.start();
+            let mut text = text.to_owned();
+            text.replace_range(std::ops::Range::<usize>::from(prev_range), name);
+            text
+        }
+        None => text.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn split_tail_expr_chain() {
+        check_assist(
+            split_try_chain,
+            r#"
+//- minicore: option, try
+struct A;
+struct B;
+struct C;
+impl A {
+    fn b(&self) -> Option<B> { None }
+}
+impl B {
+    fn c(&self) -> Option<C> { None }
+}
+impl C {
+    fn d(&self) -> Option<u32> { None }
+}
+fn f(a: A) -> Option<u32> {
+    a.b()?.c()?.d$0()
+}
+"#,
+            r#"
+struct A;
+struct B;
+struct C;
+impl A {
+    fn b(&self) -> Option<B> { None }
+}
+impl B {
+    fn c(&self) -> Option<C> { None }
+}
+impl C {
+    fn d(&self) -> Option<u32> { None }
+}
+fn f(a: A) -> Option<u32> {
+    let b: B = a.b()?;
+    let c: C = b.c()?;
+    c.d()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn split_let_initializer_with_plain_calls() {
+        check_assist(
+            split_try_chain,
+            r#"
+//- minicore: option, try
+struct A;
+struct B;
+struct C;
+impl A {
+    fn b(&self) -> Option<B> { None }
+}
+impl B {
+    fn c(&self) -> Option<C> { None }
+    fn plain(&self) -> B { B }
+}
+fn f(a: A) -> Option<()> {
+    let c = a.b()?.plain().plain()$0.c()?;
+    None
+}
+"#,
+            r#"
+struct A;
+struct B;
+struct C;
+impl A {
+    fn b(&self) -> Option<B> { None }
+}
+impl B {
+    fn c(&self) -> Option<C> { None }
+    fn plain(&self) -> B { B }
+}
+fn f(a: A) -> Option<()> {
+    let b: B = a.b()?;
+    let c = b.plain().plain().c()?;
+    None
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn split_with_unique_names() {
+        check_assist(
+            split_try_chain,
+            r#"
+//- minicore: option, try
+struct A;
+struct B;
+struct C;
+impl A {
+    fn b(&self) -> Option<B> { None }
+}
+impl B {
+    fn c(&self) -> Option<C> { None }
+}
+impl C {
+    fn d(&self) -> Option<u32> { None }
+}
+fn f(a: A, b: u32) -> Option<u32> {
+    if b == 0 {
+        return None;
+    }
+    $0a.b()?.c()?.d()?;
+    None
+}
+"#,
+            r#"
+struct A;
+struct B;
+struct C;
+impl A {
+    fn b(&self) -> Option<B> { None }
+}
+impl B {
+    fn c(&self) -> Option<C> { None }
+}
+impl C {
+    fn d(&self) -> Option<u32> { None }
+}
+fn f(a: A, b: u32) -> Option<u32> {
+    if b == 0 {
+        return None;
+    }
+    let b0: B = a.b()?;
+    let c: C = b0.c()?;
+    c.d()?;
+    None
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn split_without_annotating_opaque_types() {
+        check_assist(
+            split_try_chain,
+            r#"
+//- minicore: option, try
+struct A;
+struct C;
+trait Tr {
+    fn c(&self) -> Option<C>;
+}
+impl Tr for () {
+    fn c(&self) -> Option<C> { None }
+}
+impl A {
+    fn b(&self) -> Option<impl Tr> { Some(()) }
+}
+impl C {
+    fn d(&self) -> Option<u32> { None }
+}
+fn f(a: A) -> Option<u32> {
+    a.b()?.c()?.d$0()
+}
+"#,
+            r#"
+struct A;
+struct C;
+trait Tr {
+    fn c(&self) -> Option<C>;
+}
+impl Tr for () {
+    fn c(&self) -> Option<C> { None }
+}
+impl A {
+    fn b(&self) -> Option<impl Tr> { Some(()) }
+}
+impl C {
+    fn d(&self) -> Option<u32> { None }
+}
+fn f(a: A) -> Option<u32> {
+    let b = a.b()?;
+    let c: C = b.c()?;
+    c.d()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_intermediate_try() {
+        check_assist_not_applicable(
+            split_try_chain,
+            r#"
+//- minicore: option, try
+struct A;
+struct B;
+impl A {
+    fn b(&self) -> Option<B> { None }
+}
+impl B {
+    fn plain(&self) -> B { B }
+}
+fn f(a: A) -> Option<B> {
+    Some(a.b()?.plain$0())
+}
+"#,
+        );
+        check_assist_not_applicable(
+            split_try_chain,
+            r#"
+//- minicore: option, try
+struct A;
+struct B;
+impl A {
+    fn b(&self) -> Option<B> { None }
+}
+fn f(a: A) -> Option<B> {
+    a.b$0()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_nested_in_other_expr() {
+        check_assist_not_applicable(
+            split_try_chain,
+            r#"
+//- minicore: option, try
+struct A;
+struct B;
+struct C;
+impl A {
+    fn b(&self) -> Option<B> { None }
+}
+impl B {
+    fn c(&self) -> Option<C> { None }
+}
+impl C {
+    fn d(&self) -> Option<u32> { None }
+}
+fn f(a: A) -> Option<u32> {
+    Some(a.b()?.c()?.d$0()?)
+}
+"#,
+        );
+    }
+}
@@ -1,15 +1,18 @@
+use ide_db::FxHashSet;
+use itertools::Itertools;
 use syntax::{
     algo::neighbor,
-    ast::{self, edit::IndentLevel, make, AstNode},
+    ast::{self, edit::IndentLevel, make, AstNode, HasAttrs, HasName},
     ted::{self, Position},
-    Direction, SyntaxKind, T,
+    Direction, SyntaxElement, SyntaxKind, TextRange, T,
 };
 
 use crate::{AssistContext, AssistId, AssistKind, Assists};
 
 // Assist: unmerge_match_arm
 //
-// Splits the current match with a `|` pattern into two arms with identical bodies.
+// Splits the current match with a `|` pattern into two arms with identical bodies. The arm can
+// also be split into one arm per pattern at once.
 //
 // ```
 // enum Action { Move { distance: u32 }, Stop }
@@ -32,6 +35,11 @@ use crate::{AssistContext, AssistId, AssistKind, Assists};
 // }
 // ```
 pub(crate) fn unmerge_match_arm(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    unmerge_at_pipe(acc, ctx);
+    unmerge_all_patterns(acc, ctx)
+}
+
+fn unmerge_at_pipe(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
     let pipe_token = ctx.find_token_syntax_at_offset(T![|])?;
     let or_pat = ast::OrPat::cast(pipe_token.parent()?)?.clone_for_update();
     let match_arm = ast::MatchArm::cast(or_pat.syntax().parent()?)?;
@@ -58,6 +66,17 @@ pub(crate) fn unmerge_match_arm(acc: &mut Assists, ctx: &AssistContext<'_>) -> O
                 match_arm_body,
             )
             .clone_for_update();
+            let indent = IndentLevel::from_node(match_arm.syntax());
+            let attrs: Vec<SyntaxElement> = match_arm
+                .attrs()
+                .flat_map(|attr| {
+                    [
+                        attr.syntax().clone_subtree().clone_for_update().into(),
+                        make::tokens::whitespace(&format!("\n{indent}")).into(),
+                    ]
+                })
+                .collect();
+            ted::insert_all_raw(Position::first_child_of(new_match_arm.syntax()), attrs);
 
             let mut pipe_index = pipe_token.index();
             if pipe_token
@@ -93,7 +112,6 @@ pub(crate) fn unmerge_match_arm(acc: &mut Assists, ctx: &AssistContext<'_>) -> O
                 insert_after_old_arm.push(make::token(T![,]).into());
             }
 
-            let indent = IndentLevel::from_node(match_arm.syntax());
             insert_after_old_arm.push(make::tokens::whitespace(&format!("\n{indent}")).into());
 
             insert_after_old_arm.push(new_match_arm.syntax().clone().into());
@@ -112,9 +130,62 @@ pub(crate) fn unmerge_match_arm(acc: &mut Assists, ctx: &AssistContext<'_>) -> O
     )
 }
 
+fn unmerge_all_patterns(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let body = match_arm.expr()?;
+    if body.syntax().text_range().contains_inclusive(ctx.offset()) {
+        return None;
+    }
+    let ast::Pat::OrPat(or_pat) = match_arm.pat()? else { return None };
+    let pats: Vec<_> = or_pat.pats().collect();
+    // With two patterns, this is the same as unmerging at the `|`.
+    if pats.len() < 2 || pats.len() == 2 && ctx.find_token_syntax_at_offset(T![|]).is_some() {
+        return None;
+    }
+
+    let bindings = |pat: &ast::Pat| -> FxHashSet<String> {
+        pat.syntax()
+            .descendants()
+            .filter_map(ast::IdentPat::cast)
+            .filter(|ident_pat| ctx.sema.to_def(ident_pat).is_some())
+            .filter_map(|ident_pat| Some(ident_pat.name()?.text().to_string()))
+            .collect()
+    };
+    let first_bindings = bindings(&pats[0]);
+    if pats[1..].iter().any(|pat| bindings(pat) != first_bindings) {
+        cov_mark::hit!(unmerge_match_arm_different_bindings);
+        return None;
+    }
+
+    acc.add(
+        AssistId("unmerge_match_arm", AssistKind::RefactorRewrite),
+        "Unmerge match arm into one arm per pattern",
+        or_pat.syntax().text_range(),
+        |edit| {
+            let indent = IndentLevel::from_node(match_arm.syntax());
+            let attrs: String = match_arm.attrs().map(|attr| format!("{attr}\n{indent}")).collect();
+            let guard = match_arm.guard().map(|guard| format!(" {guard}")).unwrap_or_default();
+            let separator = if body.is_block_like() { "\n" } else { ",\n" };
+            let arms = pats
+                .iter()
+                .map(|pat| format!("{attrs}{pat}{guard} => {body}"))
+                .join(&format!("{separator}{indent}"));
+            edit.replace(
+                TextRange::new(
+                    match_arm.syntax().text_range().start(),
+                    body.syntax().text_range().end(),
+                ),
+                arms,
+            );
+        },
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_not_applicable};
+    use crate::tests::{
+        check_assist, check_assist_by_label, check_assist_not_applicable, check_assist_target,
+    };
 
     use super::*;
 
@@ -200,7 +271,7 @@ fn main() {
 
     #[test]
     fn unmerge_match_arm_multiple_pipes() {
-        check_assist(
+        check_assist_by_label(
             unmerge_match_arm,
             r#"
 #[derive(Debug)]
@@ -227,6 +298,7 @@ fn main() {
     };
 }
 "#,
+            "Unmerge match arm",
         );
     }
 
@@ -286,6 +358,179 @@ fn main() {
         X::B => {},
     }
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn unmerge_match_arm_keeps_attributes() {
+        check_assist(
+            unmerge_match_arm,
+            r#"
+fn main() {
+    match 0 {
+        #[allow(unused)]
+        1 $0| 2 => (),
+        _ => (),
+    }
+}
+"#,
+            r#"
+fn main() {
+    match 0 {
+        #[allow(unused)]
+        1 => (),
+        #[allow(unused)]
+        2 => (),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unmerge_all_patterns() {
+        check_assist_by_label(
+            unmerge_match_arm,
+            r#"
+enum E { A, B, C, D }
+fn f(e: E) -> u32 {
+    match e {
+        #[allow(unused)]
+        E::A | E::B$0 | E::C => 1,
+        E::D => 2,
+    }
+}
+"#,
+            r#"
+enum E { A, B, C, D }
+fn f(e: E) -> u32 {
+    match e {
+        #[allow(unused)]
+        E::A => 1,
+        #[allow(unused)]
+        E::B => 1,
+        #[allow(unused)]
+        E::C => 1,
+        E::D => 2,
+    }
+}
+"#,
+            "Unmerge match arm into one arm per pattern",
+        );
+    }
+
+    #[test]
+    fn unmerge_all_patterns_keeps_guard_and_block_body() {
+        check_assist(
+            unmerge_match_arm,
+            r#"
+enum E { A(u32), B(u32), C }
+fn f(e: E) {
+    match e {
+        $0E::A(x) | E::B(x) if x > 2 => {
+            foo(x);
+        }
+        E::C => {}
+    }
+}
+"#,
+            r#"
+enum E { A(u32), B(u32), C }
+fn f(e: E) {
+    match e {
+        E::A(x) if x > 2 => {
+            foo(x);
+        }
+        E::B(x) if x > 2 => {
+            foo(x);
+        }
+        E::C => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unmerge_all_patterns_of_last_arm_without_comma() {
+        check_assist(
+            unmerge_match_arm,
+            r#"
+fn f(x: u32) -> bool {
+    match x {
+        0 => false,
+        1 | 2 =>$0 true
+    }
+}
+"#,
+            r#"
+fn f(x: u32) -> bool {
+    match x {
+        0 => false,
+        1 => true,
+        2 => true
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unmerge_all_patterns_target() {
+        check_assist_target(
+            unmerge_match_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        1 | 2$0 => (),
+        _ => (),
+    }
+}
+"#,
+            "1 | 2",
+        );
+    }
+
+    #[test]
+    fn unmerge_all_patterns_not_applicable_in_body_or_nested() {
+        check_assist_not_applicable(
+            unmerge_match_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        1 | 2 => ($0),
+        _ => (),
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            unmerge_match_arm,
+            r#"
+fn f(x: Option<u32>) {
+    match x {
+        Some(1 $0| 2) => (),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unmerge_all_patterns_not_applicable_with_different_bindings() {
+        cov_mark::check!(unmerge_match_arm_different_bindings);
+        check_assist_not_applicable(
+            unmerge_match_arm,
+            r#"
+enum E { A(u32), B(u32) }
+fn f(e: E) {
+    match e {
+        E::A(x) | E::B(y)$0 => (),
+    }
+}
 "#,
         );
     }
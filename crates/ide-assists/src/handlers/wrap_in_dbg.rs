@@ -0,0 +1,291 @@
+use syntax::{
+    ast::{self, AstNode},
+    match_ast, NodeOrToken,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: wrap_in_dbg
+//
+// Wraps an expression in a `dbg!()` macro call, which evaluates to the value of the expression.
+//
+// ```
+// fn main() {
+//     let x = $042 * 2$0;
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let x = dbg!(42 * 2);
+// }
+// ```
+pub(crate) fn wrap_in_dbg(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    // Nearly every cursor position is inside some expression, so only offer this for selections.
+    if ctx.has_empty_selection() {
+        return None;
+    }
+    let selection = ctx.selection_trimmed();
+    let node = match ctx.covering_element() {
+        NodeOrToken::Node(it) => it,
+        NodeOrToken::Token(it) => it.parent()?,
+    };
+    let expr =
+        node.ancestors().take_while(|it| it.text_range() == selection).find_map(ast::Expr::cast)?;
+    if matches!(expr, ast::Expr::LetExpr(_) | ast::Expr::UnderscoreExpr(_)) {
+        return None;
+    }
+    if is_place(&expr) {
+        // `dbg!()` moves its argument, so it can't be used where a place is borrowed or assigned
+        // to, and it would move a mutable reference instead of reborrowing it.
+        if used_as_place(&expr) {
+            cov_mark::hit!(wrap_in_dbg_place);
+            return None;
+        }
+        let ty = ctx.sema.type_of_expr(&expr)?.original;
+        if ty.is_mutable_reference() {
+            return None;
+        }
+        // Places that are only read, like a `match` scrutinee or an operand of `==`, would be
+        // moved out of.
+        if !ty.is_copy(ctx.db()) && !consumed_by_parent(&expr) {
+            cov_mark::hit!(wrap_in_dbg_moved_place);
+            return None;
+        }
+    }
+
+    let range = expr.syntax().text_range();
+    acc.add(AssistId("wrap_in_dbg", AssistKind::Refactor), "Wrap in dbg!()", range, |builder| {
+        builder.insert(range.start(), "dbg!(");
+        builder.insert(range.end(), ")");
+    })
+}
+
+fn is_place(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::PathExpr(_) | ast::Expr::FieldExpr(_) | ast::Expr::IndexExpr(_) => true,
+        ast::Expr::PrefixExpr(it) => it.op_kind() == Some(ast::UnaryOp::Deref),
+        ast::Expr::ParenExpr(it) => it.expr().is_some_and(|it| is_place(&it)),
+        _ => false,
+    }
+}
+
+fn used_as_place(expr: &ast::Expr) -> bool {
+    let Some(parent) = expr.syntax().parent().and_then(ast::Expr::cast) else { return false };
+    match parent {
+        ast::Expr::RefExpr(_) => true,
+        ast::Expr::MethodCallExpr(it) => it.receiver().as_ref() == Some(expr),
+        ast::Expr::FieldExpr(it) => it.expr().as_ref() == Some(expr),
+        ast::Expr::IndexExpr(it) => it.base().as_ref() == Some(expr),
+        ast::Expr::BinExpr(it) => {
+            matches!(it.op_kind(), Some(ast::BinaryOp::Assignment { .. }))
+                && it.lhs().as_ref() == Some(expr)
+        }
+        ast::Expr::PrefixExpr(it) => it.op_kind() == Some(ast::UnaryOp::Deref),
+        ast::Expr::ParenExpr(it) => used_as_place(&ast::Expr::ParenExpr(it)),
+        _ => false,
+    }
+}
+
+/// Checks whether the parent of `expr` moves out of it anyway.
+fn consumed_by_parent(expr: &ast::Expr) -> bool {
+    let Some(parent) = expr.syntax().parent() else { return false };
+    match_ast! {
+        match parent {
+            ast::ArgList(_) => true,
+            // Only tail expressions are direct children of a statement list.
+            ast::StmtList(_) => true,
+            ast::ReturnExpr(_) => true,
+            ast::RecordExprField(_) => true,
+            ast::TupleExpr(_) => true,
+            ast::ArrayExpr(_) => true,
+            ast::LetStmt(it) => {
+                matches!(it.pat(), Some(ast::Pat::IdentPat(pat)) if pat.ref_token().is_none())
+            },
+            ast::BinExpr(it) => match it.op_kind() {
+                Some(ast::BinaryOp::Assignment { op: None }) => it.rhs().as_ref() == Some(expr),
+                Some(ast::BinaryOp::ArithOp(_)) => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn wraps_selected_call() {
+        check_assist(
+            wrap_in_dbg,
+            r#"
+fn f(v: Vec<u32>) -> usize {
+    $0v.len()$0
+}
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn len(&self) -> usize { 0 }
+}
+"#,
+            r#"
+fn f(v: Vec<u32>) -> usize {
+    dbg!(v.len())
+}
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn len(&self) -> usize { 0 }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_selection() {
+        check_assist_not_applicable(
+            wrap_in_dbg,
+            r#"
+fn f(a: u32) -> u32 {
+    a $0+ 1
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn wraps_selection() {
+        check_assist(
+            wrap_in_dbg,
+            r#"
+fn f(a: u32, b: u32) -> bool {
+    $0a * 2$0 > b
+}
+"#,
+            r#"
+fn f(a: u32, b: u32) -> bool {
+    dbg!(a * 2) > b
+}
+"#,
+        );
+        check_assist(
+            wrap_in_dbg,
+            r#"
+fn f(a: u32) -> u32 {
+    let b = $0a$0;
+    b
+}
+"#,
+            r#"
+fn f(a: u32) -> u32 {
+    let b = dbg!(a);
+    b
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn moved_places() {
+        check_assist(
+            wrap_in_dbg,
+            r#"
+struct S;
+fn f(s: S) -> S {
+    $0s$0
+}
+"#,
+            r#"
+struct S;
+fn f(s: S) -> S {
+    dbg!(s)
+}
+"#,
+        );
+        check_assist(
+            wrap_in_dbg,
+            r#"
+//- minicore: copy
+fn f(a: u32) {
+    match $0a$0 {
+        _ => (),
+    }
+}
+"#,
+            r#"
+fn f(a: u32) {
+    match dbg!(a) {
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_read_non_copy_places() {
+        cov_mark::check_count!(wrap_in_dbg_moved_place, 2);
+        check_assist_not_applicable(
+            wrap_in_dbg,
+            r#"
+struct S;
+fn f(s: S) {
+    match $0s$0 {
+        S => (),
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            wrap_in_dbg,
+            r#"
+//- minicore: eq
+struct S;
+impl PartialEq for S {
+    fn eq(&self, _: &S) -> bool { true }
+}
+fn f(s: S, t: S) -> bool {
+    $0s$0 == t
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_places() {
+        cov_mark::check!(wrap_in_dbg_place);
+        check_assist_not_applicable(
+            wrap_in_dbg,
+            r#"
+fn f(mut a: u32) {
+    $0a$0 = 2;
+}
+"#,
+        );
+        check_assist_not_applicable(
+            wrap_in_dbg,
+            r#"
+fn f(a: u32) -> &u32 {
+    &$0a$0
+}
+"#,
+        );
+        check_assist_not_applicable(
+            wrap_in_dbg,
+            r#"
+fn f(a: &mut u32) {
+    g($0a$0);
+}
+fn g(_: &mut u32) {}
+"#,
+        );
+        check_assist_not_applicable(
+            wrap_in_dbg,
+            r#"
+fn f(a: $0u32$0) {}
+"#,
+        );
+    }
+}
@@ -102,21 +102,32 @@ mod handlers {
     pub(crate) type Handler = fn(&mut Assists, &AssistContext<'_>) -> Option<()>;
 
     mod add_braces;
+    mod add_explicit_generic_args;
     mod add_explicit_type;
     mod add_label_to_loop;
     mod add_lifetime_to_type;
     mod add_missing_impl_members;
     mod add_missing_match_arms;
+    mod add_missing_variant_arms;
+    mod add_non_exhaustive;
     mod add_return_type;
+    mod add_tracing_instrumentation;
     mod add_turbo_fish;
     mod apply_demorgan;
     mod auto_import;
     mod bind_unused_param;
     mod bool_to_enum;
     mod change_visibility;
+    mod collapse_nested_if_let;
     mod convert_bool_then;
+    mod convert_boxed_error_to_enum;
+    mod convert_closure_to_fn;
     mod convert_comment_block;
+    mod convert_enum_to_trait;
+    mod convert_fn_to_method;
+    mod convert_for_loop_to_iterator_chain;
     mod convert_from_to_tryfrom;
+    mod convert_guard_to_nested_match;
     mod convert_integer_literal;
     mod convert_into_to_from;
     mod convert_iter_for_each_to_for;
@@ -124,11 +135,17 @@ mod handlers {
     mod convert_match_to_let_else;
     mod convert_named_struct_to_tuple_struct;
     mod convert_nested_function_to_closure;
+    mod convert_recursion_to_explicit_stack;
+    mod convert_result_to_anyhow;
+    mod convert_slice_param_to_impl_into_iterator;
+    mod convert_string_concat_to_format;
     mod convert_to_guarded_return;
     mod convert_tuple_return_type_to_struct;
     mod convert_tuple_struct_to_named_struct;
     mod convert_two_arm_bool_match_to_matches_macro;
+    mod convert_vec_field_to_boxed_slice;
     mod convert_while_to_loop;
+    mod destructure_param_in_signature;
     mod destructure_struct_binding;
     mod destructure_tuple_binding;
     mod desugar_doc_comment;
@@ -144,7 +161,9 @@ mod handlers {
     mod flip_binexpr;
     mod flip_comma;
     mod flip_trait_bound;
+    mod generate_builder;
     mod generate_constant;
+    mod generate_debug_impl;
     mod generate_default_from_enum_variant;
     mod generate_default_from_new;
     mod generate_delegate_methods;
@@ -160,11 +179,14 @@ mod handlers {
     mod generate_getter_or_setter;
     mod generate_impl;
     mod generate_is_empty_from_len;
+    mod generate_lock_helper;
     mod generate_mut_trait_impl;
     mod generate_new;
+    mod generate_newtype_conversion;
     mod generate_trait_from_impl;
     mod inline_call;
     mod inline_const_as_literal;
+    mod inline_format_args;
     mod inline_local_variable;
     mod inline_macro;
     mod inline_type_alias;
@@ -200,6 +222,7 @@ mod handlers {
     mod replace_if_let_with_match;
     mod replace_is_method_with_if_let_method;
     mod replace_let_with_if_let;
+    mod replace_map_flatten;
     mod replace_method_eager_lazy;
     mod replace_named_generic_with_impl;
     mod replace_qualified_name_with_use;
@@ -208,6 +231,7 @@ mod handlers {
     mod replace_turbofish_with_explicit_type;
     mod sort_items;
     mod split_import;
+    mod split_try_chain;
     mod term_search;
     mod toggle_ignore;
     mod unmerge_match_arm;
@@ -217,6 +241,7 @@ mod handlers {
     mod unwrap_block;
     mod unwrap_result_return_type;
     mod unwrap_tuple;
+    mod wrap_in_dbg;
     mod wrap_return_type_in_result;
     mod wrap_unwrap_cfg_attr;
 
@@ -224,11 +249,16 @@ mod handlers {
         &[
             // These are alphabetic for the foolish consistency
             add_braces::add_braces,
+            add_explicit_generic_args::add_explicit_generic_args,
             add_explicit_type::add_explicit_type,
             add_label_to_loop::add_label_to_loop,
             add_missing_match_arms::add_missing_match_arms,
+            add_missing_variant_arms::add_missing_variant_arms,
+            add_non_exhaustive::add_non_exhaustive,
             add_lifetime_to_type::add_lifetime_to_type,
             add_return_type::add_return_type,
+            add_tracing_instrumentation::add_tracing_instrumentation,
+            add_tracing_instrumentation::add_tracing_span,
             add_turbo_fish::add_turbo_fish,
             apply_demorgan::apply_demorgan,
             apply_demorgan::apply_demorgan_iterator,
@@ -236,10 +266,17 @@ mod handlers {
             bind_unused_param::bind_unused_param,
             bool_to_enum::bool_to_enum,
             change_visibility::change_visibility,
+            collapse_nested_if_let::collapse_nested_if_let,
             convert_bool_then::convert_bool_then_to_if,
             convert_bool_then::convert_if_to_bool_then,
+            convert_boxed_error_to_enum::convert_boxed_error_to_enum,
+            convert_closure_to_fn::convert_closure_to_fn,
             convert_comment_block::convert_comment_block,
+            convert_enum_to_trait::convert_enum_to_trait,
+            convert_fn_to_method::convert_fn_to_method,
+            convert_for_loop_to_iterator_chain::convert_for_loop_to_iterator_chain,
             convert_from_to_tryfrom::convert_from_to_tryfrom,
+            convert_guard_to_nested_match::convert_guard_to_nested_match,
             convert_integer_literal::convert_integer_literal,
             convert_into_to_from::convert_into_to_from,
             convert_iter_for_each_to_for::convert_iter_for_each_to_for,
@@ -249,13 +286,20 @@ mod handlers {
             convert_tuple_return_type_to_struct::convert_tuple_return_type_to_struct,
             convert_named_struct_to_tuple_struct::convert_named_struct_to_tuple_struct,
             convert_nested_function_to_closure::convert_nested_function_to_closure,
+            convert_recursion_to_explicit_stack::convert_recursion_to_explicit_stack,
+            convert_result_to_anyhow::convert_result_to_anyhow,
+            convert_result_to_anyhow::convert_anyhow_to_result,
+            convert_slice_param_to_impl_into_iterator::convert_slice_param_to_impl_into_iterator,
+            convert_string_concat_to_format::convert_string_concat_to_format,
             convert_to_guarded_return::convert_to_guarded_return,
             convert_tuple_struct_to_named_struct::convert_tuple_struct_to_named_struct,
             convert_two_arm_bool_match_to_matches_macro::convert_two_arm_bool_match_to_matches_macro,
+            convert_vec_field_to_boxed_slice::convert_vec_field_to_boxed_slice,
             convert_while_to_loop::convert_while_to_loop,
             desugar_doc_comment::desugar_doc_comment,
             destructure_tuple_binding::destructure_tuple_binding,
             destructure_struct_binding::destructure_struct_binding,
+            destructure_param_in_signature::destructure_param_in_signature,
             expand_glob_import::expand_glob_import,
             extract_expressions_from_format_string::extract_expressions_from_format_string,
             extract_struct_from_enum_variant::extract_struct_from_enum_variant,
@@ -265,11 +309,15 @@ mod handlers {
             flip_binexpr::flip_binexpr,
             flip_comma::flip_comma,
             flip_trait_bound::flip_trait_bound,
+            generate_builder::generate_builder,
+            generate_builder::generate_fallible_builder,
             generate_constant::generate_constant,
+            generate_debug_impl::generate_debug_impl,
             generate_default_from_enum_variant::generate_default_from_enum_variant,
             generate_default_from_new::generate_default_from_new,
             generate_delegate_trait::generate_delegate_trait,
             generate_derive::generate_derive,
+            generate_derive::generate_derive_clone,
             generate_documentation_template::generate_documentation_template,
             generate_documentation_template::generate_doc_example,
             generate_enum_is_method::generate_enum_is_method,
@@ -282,11 +330,15 @@ mod handlers {
             generate_impl::generate_trait_impl,
             generate_mut_trait_impl::generate_mut_trait_impl,
             generate_is_empty_from_len::generate_is_empty_from_len,
+            generate_lock_helper::generate_lock_helper,
+            generate_lock_helper::generate_try_lock_helper,
             generate_new::generate_new,
+            generate_newtype_conversion::generate_newtype_conversion,
             generate_trait_from_impl::generate_trait_from_impl,
             inline_call::inline_call,
             inline_call::inline_into_callers,
             inline_const_as_literal::inline_const_as_literal,
+            inline_format_args::inline_format_args,
             inline_local_variable::inline_local_variable,
             inline_type_alias::inline_type_alias,
             inline_type_alias::inline_type_alias_uses,
@@ -298,6 +350,7 @@ mod handlers {
             merge_match_arms::merge_match_arms,
             merge_nested_if::merge_nested_if,
             move_bounds::move_bounds_to_where_clause,
+            move_bounds::move_where_clause_to_bounds,
             move_const_to_impl::move_const_to_impl,
             move_guard::move_arm_cond_to_match_guard,
             move_guard::move_guard_to_arm_body,
@@ -325,6 +378,7 @@ mod handlers {
             replace_if_let_with_match::replace_match_with_if_let,
             replace_is_method_with_if_let_method::replace_is_method_with_if_let_method,
             replace_let_with_if_let::replace_let_with_if_let,
+            replace_map_flatten::replace_map_flatten,
             replace_method_eager_lazy::replace_with_eager_method,
             replace_method_eager_lazy::replace_with_lazy_method,
             replace_named_generic_with_impl::replace_named_generic_with_impl,
@@ -335,6 +389,7 @@ mod handlers {
             replace_arith_op::replace_arith_with_saturating,
             sort_items::sort_items,
             split_import::split_import,
+            split_try_chain::split_try_chain,
             term_search::term_search,
             toggle_ignore::toggle_ignore,
             unmerge_match_arm::unmerge_match_arm,
@@ -344,6 +399,7 @@ mod handlers {
             unwrap_result_return_type::unwrap_result_return_type,
             unwrap_tuple::unwrap_tuple,
             unqualify_method_call::unqualify_method_call,
+            wrap_in_dbg::wrap_in_dbg,
             wrap_return_type_in_result::wrap_return_type_in_result,
             wrap_unwrap_cfg_attr::wrap_unwrap_cfg_attr,
 
@@ -353,6 +353,7 @@ pub fn test_some_range(a: int) -> bool {
 
     expect![[r#"
         Convert integer base
+        Wrap in dbg!()
         Extract into variable
         Extract into function
         Replace if let with match
@@ -382,6 +383,7 @@ pub fn test_some_range(a: int) -> bool {
 
         expect![[r#"
             Convert integer base
+            Wrap in dbg!()
             Extract into variable
             Extract into function
             Replace if let with match
@@ -27,6 +27,25 @@ fn foo(n: i32) -> i32 {
     )
 }
 
+#[test]
+fn doctest_add_explicit_generic_args() {
+    check_doc_test(
+        "add_explicit_generic_args",
+        r#####"
+fn make<T>() -> T { loop {} }
+fn main() {
+    let x: u32 = make$0();
+}
+"#####,
+        r#####"
+fn make<T>() -> T { loop {} }
+fn main() {
+    let x: u32 = make::<u32>();
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_explicit_type() {
     check_doc_test(
@@ -195,6 +214,49 @@ fn handle(action: Action) {
     )
 }
 
+#[test]
+fn doctest_add_missing_variant_arms() {
+    check_doc_test(
+        "add_missing_variant_arms",
+        r#####"
+enum Action { Move { distance: u32 }, Jump(u32), Stop }
+
+fn handle(action: Action) {
+    $0match action {
+        Action::Stop => {}
+        _ => {}
+    }
+}
+"#####,
+        r#####"
+enum Action { Move { distance: u32 }, Jump(u32), Stop }
+
+fn handle(action: Action) {
+    match action {
+        Action::Stop => {}
+        Action::Move { distance } => todo!(),
+        Action::Jump(_) => todo!(),
+        _ => {}
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_add_non_exhaustive() {
+    check_doc_test(
+        "add_non_exhaustive",
+        r#####"
+pub enum $0Color { Red, Green }
+"#####,
+        r#####"
+#[non_exhaustive]
+pub enum Color { Red, Green }
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_return_type() {
     check_doc_test(
@@ -208,6 +270,46 @@ fn foo() -> i32 { 42i32 }
     )
 }
 
+#[test]
+fn doctest_add_tracing_instrumentation() {
+    check_doc_test(
+        "add_tracing_instrumentation",
+        r#####"
+//- /main.rs crate:main deps:tracing
+fn comp$0ute(a: u32) -> u32 {
+    a + 1
+}
+//- /tracing.rs crate:tracing
+"#####,
+        r#####"
+#[tracing::instrument]
+fn compute(a: u32) -> u32 {
+    a + 1
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_add_tracing_span() {
+    check_doc_test(
+        "add_tracing_span",
+        r#####"
+//- /main.rs crate:main deps:tracing
+fn comp$0ute(a: u32) -> u32 {
+    a + 1
+}
+//- /tracing.rs crate:tracing
+"#####,
+        r#####"
+fn compute(a: u32) -> u32 {
+    let _span = tracing::span!(tracing::Level::INFO, "compute").entered();
+    a + 1
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_turbo_fish() {
     check_doc_test(
@@ -345,6 +447,54 @@ pub(crate) fn frobnicate() {}
     )
 }
 
+#[test]
+fn doctest_collapse_nested_if_let() {
+    check_doc_test(
+        "collapse_nested_if_let",
+        r#####"
+//- /main.rs crate:main edition:2024
+fn main() {
+    let (x, y) = (Some(1), Some(2));
+    $0if let Some(a) = x {
+        if let Some(b) = y {
+            println!("{a} {b}");
+        }
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    let (x, y) = (Some(1), Some(2));
+    if let Some(a) = x && let Some(b) = y {
+        println!("{a} {b}");
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_anyhow_to_result() {
+    check_doc_test(
+        "convert_anyhow_to_result",
+        r#####"
+//- minicore: result
+//- /main.rs crate:main deps:anyhow
+fn parse(text: &str) -> anyhow::Result<u32>$0 {
+    Ok(0)
+}
+//- /anyhow.rs crate:anyhow
+pub struct Error;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+"#####,
+        r#####"
+fn parse(text: &str) -> Result<u32, ${0:anyhow::Error}> {
+    Ok(0)
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_bool_then_to_if() {
     check_doc_test(
@@ -367,6 +517,196 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_boxed_error_to_enum() {
+    check_doc_test(
+        "convert_boxed_error_to_enum",
+        r#####"
+//- minicore: result, try, error, fmt
+//- /main.rs crate:main deps:alloc
+use alloc::boxed::Box;
+use core::error::Error;
+struct IoError;
+fn read() -> Result<u32, IoError> { loop {} }
+
+fn load() -> $0Result<u32, Box<dyn Error>> {
+    Ok(read()?)
+}
+//- /alloc.rs crate:alloc
+pub mod boxed { pub struct Box<T: ?Sized>(T); }
+"#####,
+        r#####"
+use alloc::boxed::Box;
+use core::error::Error;
+struct IoError;
+fn read() -> Result<u32, IoError> { loop {} }
+
+fn load() -> Result<u32, LoadError> {
+    Ok(read()?)
+}
+
+#[derive(Debug)]
+enum LoadError {
+    Io(IoError),
+}
+
+impl core::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+impl From<IoError> for LoadError {
+    fn from(err: IoError) -> Self {
+        Self::Io(err)
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_closure_to_fn() {
+    check_doc_test(
+        "convert_closure_to_fn",
+        r#####"
+fn main() {
+    let double = $0|x: u32| x * 2;
+    double(2);
+}
+"#####,
+        r#####"
+fn main() {
+    let double = fun_name;
+    double(2);
+}
+
+fn fun_name(x: u32) -> u32 {
+    x * 2
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_enum_to_trait() {
+    check_doc_test(
+        "convert_enum_to_trait",
+        r#####"
+enum $0Shape {
+    Circle { radius: f64 },
+    Square(f64),
+}
+
+fn area(shape: &Shape) -> f64 {
+    match shape {
+        Shape::Circle { radius } => 3.14 * radius * radius,
+        Shape::Square(side) => side * side,
+    }
+}
+"#####,
+        r#####"
+trait Shape {
+    /// Replaces the `match` in `area`.
+    fn area(&self) -> f64;
+}
+
+struct Circle { radius: f64 }
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        todo!("move the `Shape::Circle` arm of the `match` in `area` here")
+    }
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        todo!("move the `Shape::Square` arm of the `match` in `area` here")
+    }
+}
+
+fn area(shape: &dyn Shape) -> f64 {
+    shape.area()
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_fn_to_method() {
+    check_doc_test(
+        "convert_fn_to_method",
+        r#####"
+struct Counter { count: u32 }
+
+fn $0increment(counter: &mut Counter, by: u32) {
+    counter.count += by;
+}
+
+fn main() {
+    let mut counter = Counter { count: 0 };
+    increment(&mut counter, 2);
+}
+"#####,
+        r#####"
+struct Counter { count: u32 }
+
+impl Counter {
+    fn increment(&mut self, by: u32) {
+        self.count += by;
+    }
+}
+
+fn main() {
+    let mut counter = Counter { count: 0 };
+    counter.increment(2);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_for_loop_to_iterator_chain() {
+    check_doc_test(
+        "convert_for_loop_to_iterator_chain",
+        r#####"
+//- minicore: iterator, copy
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+fn main() {
+    let xs = [1, 2, 3];
+    let mut ys = Vec::new();
+    $0for x in xs {
+        if x > 1 {
+            ys.push(x * 2);
+        }
+    }
+}
+//- /alloc.rs crate:alloc
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn new() -> Self { loop {} }
+        pub fn push(&mut self, value: T) {}
+    }
+}
+"#####,
+        r#####"
+use alloc::vec::Vec;
+fn main() {
+    let xs = [1, 2, 3];
+    let mut ys: Vec<_> = xs.into_iter().filter(|&x| x > 1).map(|x| x * 2).collect();
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_for_loop_with_for_each() {
     check_doc_test(
@@ -420,6 +760,35 @@ impl TryFrom<usize> for Thing {
     )
 }
 
+#[test]
+fn doctest_convert_guard_to_nested_match() {
+    check_doc_test(
+        "convert_guard_to_nested_match",
+        r#####"
+//- minicore: option, copy
+fn handle(value: Option<i32>) {
+    match value {
+        Some(x) $0if x > 0 => foo(x),
+        _ => (),
+    }
+}
+"#####,
+        r#####"
+fn handle(value: Option<i32>) {
+    match value {
+        Some(x) => match x > 0 {
+            true => foo(x),
+            false => match value {
+                _ => (),
+            }
+        },
+        _ => (),
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_if_to_bool_then() {
     check_doc_test(
@@ -616,6 +985,119 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_recursion_to_explicit_stack() {
+    check_doc_test(
+        "convert_recursion_to_explicit_stack",
+        r#####"
+struct Node { children: Vec<Node> }
+fn visit$0(node: &Node) {
+    for child in &node.children {
+        visit(child);
+    }
+}
+"#####,
+        r#####"
+struct Node { children: Vec<Node> }
+fn visit(node: &Node) {
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        let mut pending = Vec::new();
+        for child in &node.children {
+            pending.push(child);
+        }
+        stack.extend(pending.into_iter().rev());
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_result_to_anyhow() {
+    check_doc_test(
+        "convert_result_to_anyhow",
+        r#####"
+//- minicore: result
+//- /main.rs crate:main deps:anyhow
+struct ParseError;
+
+fn parse(text: &str) -> Result<u32, ParseError>$0 {
+    if text.is_empty() {
+        return Err(ParseError);
+    }
+    Ok(0)
+}
+//- /anyhow.rs crate:anyhow
+pub struct Error;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+"#####,
+        r#####"
+use anyhow::Result;
+
+struct ParseError;
+
+fn parse(text: &str) -> Result<u32> {
+    if text.is_empty() {
+        return Err(ParseError.into());
+    }
+    Ok(0)
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_slice_param_to_impl_into_iterator() {
+    check_doc_test(
+        "convert_slice_param_to_impl_into_iterator",
+        r#####"
+fn sum(values$0: &[u32]) -> u32 {
+    let mut total = 0;
+    for value in values {
+        total += value;
+    }
+    total
+}
+"#####,
+        r#####"
+fn sum<'a>(values: impl IntoIterator<Item = &'a u32>) -> u32 {
+    let mut total = 0;
+    for value in values {
+        total += value;
+    }
+    total
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_string_concat_to_format() {
+    check_doc_test(
+        "convert_string_concat_to_format",
+        r#####"
+//- /main.rs crate:main deps:alloc
+use alloc::string::{String, ToString};
+fn greet(name: &str, punctuation: String) -> String {
+    name.to_string() $0+ " says hi" + &punctuation
+}
+//- /alloc.rs crate:alloc
+pub mod string {
+    pub struct String;
+    pub trait ToString { fn to_string(&self) -> String; }
+    impl ToString for str { fn to_string(&self) -> String { String } }
+}
+"#####,
+        r#####"
+use alloc::string::{String, ToString};
+fn greet(name: &str, punctuation: String) -> String {
+    format!("{name} says hi{punctuation}")
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_to_guarded_return() {
     check_doc_test(
@@ -728,6 +1210,35 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_vec_field_to_boxed_slice() {
+    check_doc_test(
+        "convert_vec_field_to_boxed_slice",
+        r#####"
+//- /main.rs crate:main deps:alloc
+use alloc::{boxed::Box, vec::Vec};
+struct Data {
+    $0items: Vec<u32>,
+}
+fn make(items: Vec<u32>) -> Data {
+    Data { items }
+}
+//- /alloc.rs crate:alloc
+pub mod boxed { pub struct Box<T: ?Sized>(T); }
+pub mod vec { pub struct Vec<T>(T); }
+"#####,
+        r#####"
+use alloc::{boxed::Box, vec::Vec};
+struct Data {
+    items: Box<[u32]>,
+}
+fn make(items: Vec<u32>) -> Data {
+    Data { items: items.into_boxed_slice() }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_while_to_loop() {
     check_doc_test(
@@ -752,6 +1263,28 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_destructure_param_in_signature() {
+    check_doc_test(
+        "destructure_param_in_signature",
+        r#####"
+struct Point { x: i32, y: i32 }
+
+fn length(p$0: Point) -> i32 {
+    let Point { x, y } = p;
+    x * x + y * y
+}
+"#####,
+        r#####"
+struct Point { x: i32, y: i32 }
+
+fn length(Point { x, y }: Point) -> i32 {
+    x * x + y * y
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_destructure_struct_binding() {
     check_doc_test(
@@ -1059,6 +1592,53 @@ fn foo<T: Copy + Clone>() { }
     )
 }
 
+#[test]
+fn doctest_generate_builder() {
+    check_doc_test(
+        "generate_builder",
+        r#####"
+//- minicore: option
+struct $0Request {
+    url: String,
+    timeout: Option<u32>,
+}
+"#####,
+        r#####"
+struct Request {
+    url: String,
+    timeout: Option<u32>,
+}
+
+impl Request {
+    fn builder(url: String) -> RequestBuilder {
+        RequestBuilder { url, timeout: None }
+    }
+}
+
+struct RequestBuilder {
+    url: String,
+    timeout: Option<u32>,
+}
+
+impl RequestBuilder {
+    fn url(mut self, url: String) -> Self {
+        self.url = url;
+        self
+    }
+
+    fn timeout(mut self, timeout: u32) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn build(self) -> Request {
+        Request { url: self.url, timeout: self.timeout }
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_constant() {
     check_doc_test(
@@ -1081,6 +1661,39 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_generate_debug_impl() {
+    check_doc_test(
+        "generate_debug_impl",
+        r#####"
+//- minicore: fmt, builtin_impls
+struct Secret;
+
+struct $0Login {
+    user: u32,
+    password: Secret,
+}
+"#####,
+        r#####"
+struct Secret;
+
+struct Login {
+    user: u32,
+    password: Secret,
+}
+
+impl core::fmt::Debug for Login {
+    $0fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Login")
+            .field("user", &self.user)
+            .field("password", &"...")
+            .finish()
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_default_from_enum_variant() {
     check_doc_test(
@@ -1288,6 +1901,32 @@ struct Point {
     )
 }
 
+#[test]
+fn doctest_generate_derive_clone() {
+    check_doc_test(
+        "generate_derive_clone",
+        r#####"
+//- minicore: derive, clone
+struct Point { x: u32, y: u32 }
+
+struct Line {$0
+    from: Point,
+    to: Point,
+}
+"#####,
+        r#####"
+#[derive(Clone)]
+struct Point { x: u32, y: u32 }
+
+#[derive(Clone)]
+struct Line {
+    from: Point,
+    to: Point,
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_doc_example() {
     check_doc_test(
@@ -1458,6 +2097,53 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_generate_fallible_builder() {
+    check_doc_test(
+        "generate_fallible_builder",
+        r#####"
+//- minicore: option, result
+struct $0Request {
+    url: String,
+    timeout: Option<u32>,
+}
+"#####,
+        r#####"
+struct Request {
+    url: String,
+    timeout: Option<u32>,
+}
+
+impl Request {
+    fn builder() -> RequestBuilder {
+        RequestBuilder { url: None, timeout: None }
+    }
+}
+
+struct RequestBuilder {
+    url: Option<String>,
+    timeout: Option<u32>,
+}
+
+impl RequestBuilder {
+    fn url(mut self, url: String) -> Self {
+        self.url = Some(url);
+        self
+    }
+
+    fn timeout(mut self, timeout: u32) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn build(self) -> Result<Request, &'static str> {
+        Ok(Request { url: self.url.ok_or("missing field `url`")?, timeout: self.timeout })
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_from_impl_for_enum() {
     check_doc_test(
@@ -1630,6 +2316,38 @@ impl MyStruct {
     )
 }
 
+#[test]
+fn doctest_generate_lock_helper() {
+    check_doc_test(
+        "generate_lock_helper",
+        r#####"
+//- /main.rs crate:main deps:std,alloc
+use alloc::sync::Arc;
+use std::sync::Mutex;
+struct Counter {
+    coun$0t: Arc<Mutex<u32>>,
+}
+//- /alloc.rs crate:alloc
+pub mod sync { pub struct Arc<T>(T); }
+//- /std.rs crate:std
+pub mod sync { pub struct Mutex<T>(T); }
+"#####,
+        r#####"
+use alloc::sync::Arc;
+use std::sync::Mutex;
+struct Counter {
+    count: Arc<Mutex<u32>>,
+}
+
+impl Counter {
+    fn with_count<R>(&self, f: impl FnOnce(&mut u32) -> R) -> R {
+        f(&mut self.count.lock().unwrap())
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_mut_trait_impl() {
     check_doc_test(
@@ -1689,6 +2407,28 @@ impl<T: Clone> Ctx<T> {
     )
 }
 
+#[test]
+fn doctest_generate_newtype_conversion() {
+    check_doc_test(
+        "generate_newtype_conversion",
+        r#####"
+//- minicore: from
+struct $0Meters(f64);
+struct Length(f64);
+"#####,
+        r#####"
+struct Meters(f64);
+
+impl From<Meters> for Length {
+    fn from(value: Meters) -> Self {
+        Self(value.0)
+    }
+}
+struct Length(f64);
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_setter() {
     check_doc_test(
@@ -1787,6 +2527,46 @@ impl<T: Clone> ${0:_} for Ctx<T> {}
     )
 }
 
+#[test]
+fn doctest_generate_try_lock_helper() {
+    check_doc_test(
+        "generate_try_lock_helper",
+        r#####"
+//- /main.rs crate:main deps:std,alloc
+use alloc::sync::Arc;
+use std::sync::Mutex;
+struct Counter {
+    coun$0t: Arc<Mutex<u32>>,
+}
+//- /alloc.rs crate:alloc
+pub mod sync { pub struct Arc<T>(T); }
+//- /std.rs crate:std
+pub mod sync {
+    pub struct Mutex<T>(T);
+    pub struct MutexGuard<'a, T>(&'a T);
+    pub struct PoisonError<T>(T);
+}
+"#####,
+        r#####"
+use alloc::sync::Arc;
+use std::sync::Mutex;
+struct Counter {
+    count: Arc<Mutex<u32>>,
+}
+
+impl Counter {
+    fn try_with_count<R>(
+        &self,
+        f: impl FnOnce(&mut u32) -> R,
+    ) -> Result<R, std::sync::PoisonError<std::sync::MutexGuard<'_, u32>>> {
+        let mut guard = self.count.lock()?;
+        Ok(f(&mut guard))
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_inline_call() {
     check_doc_test(
@@ -1829,6 +2609,28 @@ fn something() -> &'static str {
     )
 }
 
+#[test]
+fn doctest_inline_format_args() {
+    check_doc_test(
+        "inline_format_args",
+        r#####"
+//- minicore: fmt
+fn main() {
+    let name = "world";
+    let count = 3;
+    print!("$0{} {:?}", name, count);
+}
+"#####,
+        r#####"
+fn main() {
+    let name = "world";
+    let count = 3;
+    print!("{name} {count:?}");
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_inline_into_callers() {
     check_doc_test(
@@ -2309,6 +3111,23 @@ fn t() {}
     )
 }
 
+#[test]
+fn doctest_move_where_clause_to_bounds() {
+    check_doc_test(
+        "move_where_clause_to_bounds",
+        r#####"
+fn apply<T, U, F>(f: F, x: T) -> U where F: FnOnce(T) -> U, $0Vec<T>: Clone {
+    f(x)
+}
+"#####,
+        r#####"
+fn apply<T, U, F: FnOnce(T) -> U>(f: F, x: T) -> U where Vec<T>: Clone {
+    f(x)
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_normalize_import() {
     check_doc_test(
@@ -2761,6 +3580,24 @@ fn compute() -> Option<i32> { None }
     )
 }
 
+#[test]
+fn doctest_replace_map_flatten() {
+    check_doc_test(
+        "replace_map_flatten",
+        r#####"
+//- minicore: flatten
+fn f(xs: [u32; 2]) {
+    let _ = xs.into_iter().map(|x| [x, x + 1]).flat$0ten();
+}
+"#####,
+        r#####"
+fn f(xs: [u32; 2]) {
+    let _ = xs.into_iter().flat_map(|x| [x, x + 1]);
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_replace_match_with_if_let() {
     check_doc_test(
@@ -3020,6 +3857,50 @@ use std::{collections::HashMap};
     )
 }
 
+#[test]
+fn doctest_split_try_chain() {
+    check_doc_test(
+        "split_try_chain",
+        r#####"
+//- minicore: option, try
+struct Config;
+struct Section;
+struct Entry;
+impl Config {
+    fn section(&self) -> Option<Section> { None }
+}
+impl Section {
+    fn entry(&self) -> Option<Entry> { None }
+}
+impl Entry {
+    fn value(&self) -> Option<u32> { None }
+}
+fn value(config: &Config) -> Option<u32> {
+    config.section()?.entry()?.value()$0
+}
+"#####,
+        r#####"
+struct Config;
+struct Section;
+struct Entry;
+impl Config {
+    fn section(&self) -> Option<Section> { None }
+}
+impl Section {
+    fn entry(&self) -> Option<Entry> { None }
+}
+impl Entry {
+    fn value(&self) -> Option<u32> { None }
+}
+fn value(config: &Config) -> Option<u32> {
+    let section: Section = config.section()?;
+    let entry: Entry = section.entry()?;
+    entry.value()
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_toggle_ignore() {
     check_doc_test(
@@ -3168,6 +4049,23 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_wrap_in_dbg() {
+    check_doc_test(
+        "wrap_in_dbg",
+        r#####"
+fn main() {
+    let x = $042 * 2$0;
+}
+"#####,
+        r#####"
+fn main() {
+    let x = dbg!(42 * 2);
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_wrap_return_type_in_result() {
     check_doc_test(
@@ -1,6 +1,6 @@
 //! Assorted functions shared by several assists.
 
-pub(crate) use gen_trait_fn_body::gen_trait_fn_body;
+pub(crate) use gen_trait_fn_body::{gen_debug_struct_expr, gen_trait_fn_body};
 use hir::{db::HirDatabase, HasAttrs as HirHasAttrs, HirDisplay, InFile, Semantics};
 use ide_db::{
     famous_defs::FamousDefs, path_transform::PathTransform,
@@ -150,6 +150,10 @@ pub fn filter_assoc_items(
                 (default_methods, def.body()),
                 (DefaultMethods::Only, Some(_)) | (DefaultMethods::No, None)
             ),
+            ast::AssocItem::TypeAlias(def) => matches!(
+                (default_methods, def.ty()),
+                (DefaultMethods::Only, Some(_)) | (DefaultMethods::No, None)
+            ),
             _ => default_methods == DefaultMethods::No,
         })
         .collect();
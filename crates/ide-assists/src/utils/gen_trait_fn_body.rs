@@ -1,5 +1,6 @@
 //! This module contains functions to generate default trait impl function bodies where possible.
 
+use either::Either;
 use hir::TraitRef;
 use syntax::{
     ast::{self, edit::AstNodeEdit, make, AstNode, BinaryOp, CmpOp, HasName, LogicOp},
@@ -144,7 +145,6 @@ fn gen_clone_impl(adt: &ast::Adt, func: &ast::Fn) -> Option<()> {
 
 /// Generate a `Debug` impl based on the fields and members of the target type.
 fn gen_debug_impl(adt: &ast::Adt, func: &ast::Fn) -> Option<()> {
-    let annotated_name = adt.name()?;
     match adt {
         // `Debug` cannot be derived for unions, so no default impl can be provided.
         ast::Adt::Union(_) => None,
@@ -248,47 +248,7 @@ fn gen_debug_impl(adt: &ast::Adt, func: &ast::Fn) -> Option<()> {
         }
 
         ast::Adt::Struct(strukt) => {
-            let name = format!("\"{annotated_name}\"");
-            let args = make::arg_list(Some(make::expr_literal(&name).into()));
-            let target = make::expr_path(make::ext::ident_path("f"));
-
-            let expr = match strukt.field_list() {
-                // => f.debug_struct("Name").finish()
-                None => make::expr_method_call(target, make::name_ref("debug_struct"), args),
-
-                // => f.debug_struct("Name").field("foo", &self.foo).finish()
-                Some(ast::FieldList::RecordFieldList(field_list)) => {
-                    let method = make::name_ref("debug_struct");
-                    let mut expr = make::expr_method_call(target, method, args);
-                    for field in field_list.fields() {
-                        let name = field.name()?;
-                        let f_name = make::expr_literal(&(format!("\"{name}\""))).into();
-                        let f_path = make::expr_path(make::ext::ident_path("self"));
-                        let f_path = make::expr_ref(f_path, false);
-                        let f_path = make::expr_field(f_path, &format!("{name}"));
-                        let args = make::arg_list([f_name, f_path]);
-                        expr = make::expr_method_call(expr, make::name_ref("field"), args);
-                    }
-                    expr
-                }
-
-                // => f.debug_tuple("Name").field(self.0).finish()
-                Some(ast::FieldList::TupleFieldList(field_list)) => {
-                    let method = make::name_ref("debug_tuple");
-                    let mut expr = make::expr_method_call(target, method, args);
-                    for (i, _) in field_list.fields().enumerate() {
-                        let f_path = make::expr_path(make::ext::ident_path("self"));
-                        let f_path = make::expr_ref(f_path, false);
-                        let f_path = make::expr_field(f_path, &format!("{i}"));
-                        let method = make::name_ref("field");
-                        expr = make::expr_method_call(expr, method, make::arg_list(Some(f_path)));
-                    }
-                    expr
-                }
-            };
-
-            let method = make::name_ref("finish");
-            let expr = make::expr_method_call(expr, method, make::arg_list(None));
+            let expr = gen_debug_struct_expr(strukt, &mut |_| true)?;
             let body = make::block_expr(None, Some(expr)).indent(ast::edit::IndentLevel(1));
             ted::replace(func.body()?.syntax(), body.clone_for_update().syntax());
             Some(())
@@ -296,6 +256,64 @@ fn gen_debug_impl(adt: &ast::Adt, func: &ast::Fn) -> Option<()> {
     }
 }
 
+/// Generate the expression formatting a struct in its `Debug` impl. Fields for which `is_included`
+/// returns `false` are printed as `"..."` instead of their value.
+pub(crate) fn gen_debug_struct_expr(
+    strukt: &ast::Struct,
+    is_included: &mut dyn FnMut(Either<&ast::RecordField, &ast::TupleField>) -> bool,
+) -> Option<ast::Expr> {
+    let name = format!("\"{}\"", strukt.name()?);
+    let args = make::arg_list(Some(make::expr_literal(&name).into()));
+    let target = make::expr_path(make::ext::ident_path("f"));
+    let placeholder = || make::expr_ref(make::expr_literal("\"...\"").into(), false);
+
+    let expr = match strukt.field_list() {
+        // => f.debug_struct("Name").finish()
+        None => make::expr_method_call(target, make::name_ref("debug_struct"), args),
+
+        // => f.debug_struct("Name").field("foo", &self.foo).finish()
+        Some(ast::FieldList::RecordFieldList(field_list)) => {
+            let method = make::name_ref("debug_struct");
+            let mut expr = make::expr_method_call(target, method, args);
+            for field in field_list.fields() {
+                let name = field.name()?;
+                let f_name = make::expr_literal(&(format!("\"{name}\""))).into();
+                let f_path = if is_included(Either::Left(&field)) {
+                    let f_path = make::expr_path(make::ext::ident_path("self"));
+                    let f_path = make::expr_ref(f_path, false);
+                    make::expr_field(f_path, &format!("{name}"))
+                } else {
+                    placeholder()
+                };
+                let args = make::arg_list([f_name, f_path]);
+                expr = make::expr_method_call(expr, make::name_ref("field"), args);
+            }
+            expr
+        }
+
+        // => f.debug_tuple("Name").field(self.0).finish()
+        Some(ast::FieldList::TupleFieldList(field_list)) => {
+            let method = make::name_ref("debug_tuple");
+            let mut expr = make::expr_method_call(target, method, args);
+            for (i, field) in field_list.fields().enumerate() {
+                let f_path = if is_included(Either::Right(&field)) {
+                    let f_path = make::expr_path(make::ext::ident_path("self"));
+                    let f_path = make::expr_ref(f_path, false);
+                    make::expr_field(f_path, &format!("{i}"))
+                } else {
+                    placeholder()
+                };
+                let method = make::name_ref("field");
+                expr = make::expr_method_call(expr, method, make::arg_list(Some(f_path)));
+            }
+            expr
+        }
+    };
+
+    let method = make::name_ref("finish");
+    Some(make::expr_method_call(expr, method, make::arg_list(None)))
+}
+
 /// Generate a `Debug` impl based on the fields and members of the target type.
 fn gen_default_impl(adt: &ast::Adt, func: &ast::Fn) -> Option<()> {
     fn gen_default_call() -> Option<ast::Expr> {
@@ -1,7 +1,7 @@
 //! This module contains functions to suggest names for expressions, functions and other items
 
 use hir::Semantics;
-use ide_db::{FxHashSet, RootDatabase};
+use ide_db::{syntax_helpers::suggest_name::unique_generic_name, FxHashSet, RootDatabase};
 use itertools::Itertools;
 use stdx::to_lower_snake_case;
 use syntax::{
@@ -9,6 +9,10 @@ use syntax::{
     match_ast, AstNode, SmolStr,
 };
 
+pub(crate) use ide_db::syntax_helpers::suggest_name::{
+    for_impl_trait_as_generic, for_unique_generic_name,
+};
+
 /// Trait names, that will be ignored when in `impl Trait` and `dyn Trait`
 const USELESS_TRAITS: &[&str] = &["Send", "Sync", "Copy", "Clone", "Eq", "PartialEq"];
 
@@ -58,61 +62,6 @@ const USELESS_METHODS: &[&str] = &[
     "into_future",
 ];
 
-/// Suggest a unique name for generic parameter.
-///
-/// `existing_params` is used to check if the name conflicts with existing
-/// generic parameters.
-///
-/// The function checks if the name conflicts with existing generic parameters.
-/// If so, it will try to resolve the conflict by adding a number suffix, e.g.
-/// `T`, `T0`, `T1`, ...
-pub(crate) fn for_unique_generic_name(
-    name: &str,
-    existing_params: &ast::GenericParamList,
-) -> SmolStr {
-    let param_names = existing_params
-        .generic_params()
-        .map(|param| match param {
-            ast::GenericParam::TypeParam(t) => t.name().unwrap().to_string(),
-            p => p.to_string(),
-        })
-        .collect::<FxHashSet<_>>();
-    let mut name = name.to_owned();
-    let base_len = name.len();
-    let mut count = 0;
-    while param_names.contains(&name) {
-        name.truncate(base_len);
-        name.push_str(&count.to_string());
-        count += 1;
-    }
-
-    name.into()
-}
-
-/// Suggest name of impl trait type
-///
-/// `existing_params` is used to check if the name conflicts with existing
-/// generic parameters.
-///
-/// # Current implementation
-///
-/// In current implementation, the function tries to get the name from the first
-/// character of the name for the first type bound.
-///
-/// If the name conflicts with existing generic parameters, it will try to
-/// resolve the conflict with `for_unique_generic_name`.
-pub(crate) fn for_impl_trait_as_generic(
-    ty: &ast::ImplTraitType,
-    existing_params: &ast::GenericParamList,
-) -> SmolStr {
-    let c = ty
-        .type_bound_list()
-        .and_then(|bounds| bounds.syntax().text().char_at(0.into()))
-        .unwrap_or('T');
-
-    for_unique_generic_name(c.encode_utf8(&mut [0; 4]), existing_params)
-}
-
 /// Suggest name of variable for given expression
 ///
 /// **NOTE**: it is caller's responsibility to guarantee uniqueness of the name.
@@ -168,6 +117,16 @@ pub(crate) fn for_variable(expr: &ast::Expr, sema: &Semantics<'_, RootDatabase>)
     "var_name".to_owned()
 }
 
+/// Like [`for_variable`], but if the name is already `taken`, a number suffix is added like in
+/// `for_unique_generic_name`.
+pub(crate) fn for_unique_variable(
+    expr: &ast::Expr,
+    sema: &Semantics<'_, RootDatabase>,
+    taken: &FxHashSet<String>,
+) -> SmolStr {
+    unique_generic_name(&for_variable(expr, sema), taken)
+}
+
 fn normalize(name: &str) -> Option<String> {
     let name = to_lower_snake_case(name);
 
@@ -278,6 +237,13 @@ fn from_type(expr: &ast::Expr, sema: &Semantics<'_, RootDatabase>) -> Option<Str
     name_of_type(&ty, sema.db)
 }
 
+/// Suggest name of variable for the given type, e.g. `point` for `&Point`.
+///
+/// **NOTE**: it is caller's responsibility to guarantee uniqueness of the name.
+pub(crate) fn for_type(ty: &hir::Type, db: &RootDatabase) -> Option<String> {
+    name_of_type(&ty.strip_references(), db)
+}
+
 fn name_of_type(ty: &hir::Type, db: &RootDatabase) -> Option<String> {
     let name = if let Some(adt) = ty.as_adt() {
         let name = adt.name(db).display(db).to_string();
@@ -41,7 +41,7 @@ use crate::{
         macro_::render_macro,
         pattern::{render_struct_pat, render_variant_pat},
         render_expr, render_field, render_path_resolution, render_pattern_resolution,
-        render_tuple_field,
+        render_tuple_field, render_turbofish_type,
         type_alias::{render_type_alias, render_type_alias_with_eq},
         union_literal::render_union_literal,
         RenderContext,
@@ -206,6 +206,36 @@ impl Completions {
         );
     }
 
+    pub(crate) fn add_turbofish_type(
+        &mut self,
+        ctx: &CompletionContext<'_>,
+        path_ctx: &PathCompletionCtx,
+        local_name: hir::Name,
+        adt: hir::Adt,
+        doc_aliases: Vec<syntax::SmolStr>,
+    ) {
+        let resolution = hir::ScopeDef::ModuleDef(adt.into());
+        if !ctx.check_stability(resolution.attrs(ctx.db).as_deref()) {
+            return;
+        }
+        let is_private_editable = match ctx.def_is_visible(&resolution) {
+            Visible::Yes => false,
+            Visible::Editable => true,
+            Visible::No => return,
+        };
+        self.add(
+            render_turbofish_type(
+                RenderContext::new(ctx)
+                    .private_editable(is_private_editable)
+                    .doc_aliases(doc_aliases),
+                path_ctx,
+                local_name,
+                adt,
+            )
+            .build(ctx.db),
+        );
+    }
+
     pub(crate) fn add_pattern_resolution(
         &mut self,
         ctx: &CompletionContext<'_>,
@@ -476,23 +506,25 @@ impl Completions {
         field: hir::Field,
         ty: &hir::Type,
     ) {
-        if !ctx.check_stability(Some(&field.attrs(ctx.db))) {
-            return;
+        if let Some(item) = field_item(ctx, dot_access, receiver, field, ty) {
+            self.add(item);
+        }
+    }
+
+    /// Adds a field missing from a record literal or pattern, ranked by its `position` among the
+    /// missing fields so that they are listed in declaration order.
+    pub(crate) fn add_missing_field(
+        &mut self,
+        ctx: &CompletionContext<'_>,
+        dot_access: &DotAccess,
+        field: hir::Field,
+        ty: &hir::Type,
+        position: u32,
+    ) {
+        if let Some(mut item) = field_item(ctx, dot_access, None, field, ty) {
+            item.relevance.missing_field_position = Some(position);
+            self.add(item);
         }
-        let is_private_editable = match ctx.is_visible(&field) {
-            Visible::Yes => false,
-            Visible::Editable => true,
-            Visible::No => return,
-        };
-        let doc_aliases = ctx.doc_aliases(&field);
-        let item = render_field(
-            RenderContext::new(ctx).private_editable(is_private_editable).doc_aliases(doc_aliases),
-            dot_access,
-            receiver,
-            field,
-            ty,
-        );
-        self.add(item);
     }
 
     pub(crate) fn add_struct_literal(
@@ -606,6 +638,31 @@ impl Completions {
     }
 }
 
+fn field_item(
+    ctx: &CompletionContext<'_>,
+    dot_access: &DotAccess,
+    receiver: Option<hir::Name>,
+    field: hir::Field,
+    ty: &hir::Type,
+) -> Option<CompletionItem> {
+    if !ctx.check_stability(Some(&field.attrs(ctx.db))) {
+        return None;
+    }
+    let is_private_editable = match ctx.is_visible(&field) {
+        Visible::Yes => false,
+        Visible::Editable => true,
+        Visible::No => return None,
+    };
+    let doc_aliases = ctx.doc_aliases(&field);
+    Some(render_field(
+        RenderContext::new(ctx).private_editable(is_private_editable).doc_aliases(doc_aliases),
+        dot_access,
+        receiver,
+        field,
+        ty,
+    ))
+}
+
 /// Calls the callback for each variant of the provided enum with the path to the variant.
 /// Skips variants that are visible with single segment paths.
 fn enum_variants_with_paths(
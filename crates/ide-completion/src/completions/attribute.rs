@@ -28,7 +28,7 @@ mod lint;
 mod macro_use;
 mod repr;
 
-pub(crate) use self::derive::complete_derive_path;
+pub(crate) use self::{cfg::complete_cfg_value_in_string, derive::complete_derive_path};
 
 /// Complete inputs to known builtin attributes as well as derive attributes
 pub(crate) fn complete_known_attribute_input(
@@ -2,17 +2,15 @@
 
 use ide_db::SymbolKind;
 use itertools::Itertools;
-use syntax::{algo, ast::Ident, AstToken, Direction, NodeOrToken, SyntaxKind};
+use syntax::{
+    algo,
+    ast::{self, Ident, IsString},
+    AstNode, AstToken, Direction, NodeOrToken, SyntaxKind, TextRange, T,
+};
 
 use crate::{completions::Completions, context::CompletionContext, CompletionItem};
 
 pub(crate) fn complete_cfg(acc: &mut Completions, ctx: &CompletionContext<'_>) {
-    let add_completion = |item: &str| {
-        let mut completion = CompletionItem::new(SymbolKind::BuiltinAttr, ctx.source_range(), item);
-        completion.insert_text(format!(r#""{item}""#));
-        acc.add(completion.build(ctx.db));
-    };
-
     // FIXME: Move this into context/analysis.rs
     let previous = ctx
         .original_token
@@ -32,20 +30,7 @@ pub(crate) fn complete_cfg(acc: &mut Completions, ctx: &CompletionContext<'_>) {
         });
     match previous {
         Some(None) => (),
-        Some(Some(p)) => match p.text() {
-            "target_arch" => KNOWN_ARCH.iter().copied().for_each(add_completion),
-            "target_env" => KNOWN_ENV.iter().copied().for_each(add_completion),
-            "target_os" => KNOWN_OS.iter().copied().for_each(add_completion),
-            "target_vendor" => KNOWN_VENDOR.iter().copied().for_each(add_completion),
-            "target_endian" => ["little", "big"].into_iter().for_each(add_completion),
-            name => ctx.krate.potential_cfg(ctx.db).get_cfg_values(name).cloned().for_each(|s| {
-                let insert_text = format!(r#""{s}""#);
-                let mut item = CompletionItem::new(SymbolKind::BuiltinAttr, ctx.source_range(), s);
-                item.insert_text(insert_text);
-
-                acc.add(item.build(ctx.db));
-            }),
-        },
+        Some(Some(p)) => complete_cfg_values(acc, ctx, p.text(), ctx.source_range(), true),
         None => ctx.krate.potential_cfg(ctx.db).get_cfg_keys().cloned().unique().for_each(|s| {
             let item = CompletionItem::new(SymbolKind::BuiltinAttr, ctx.source_range(), s);
             acc.add(item.build(ctx.db));
@@ -53,6 +38,55 @@ pub(crate) fn complete_cfg(acc: &mut Completions, ctx: &CompletionContext<'_>) {
     }
 }
 
+/// Completes the value of a `key = "$0"` predicate of `cfg` or `cfg_attr`, for example the
+/// features of the crate.
+pub(crate) fn complete_cfg_value_in_string(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    original: &ast::String,
+) -> Option<()> {
+    let token = original.syntax();
+    let attr = token.parent_ancestors().find_map(ast::Attr::cast)?;
+    if !matches!(attr.simple_name().as_deref(), Some("cfg" | "cfg_attr")) {
+        return None;
+    }
+    let eq = algo::non_trivia_sibling(token.clone().into(), Direction::Prev)?
+        .into_token()
+        .filter(|it| it.kind() == T![=])?;
+    let key = algo::non_trivia_sibling(eq.into(), Direction::Prev)?.into_token()?;
+    let key = Ident::cast(key)?;
+    complete_cfg_values(acc, ctx, key.text(), original.text_range_between_quotes()?, false);
+    Some(())
+}
+
+fn complete_cfg_values(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    key: &str,
+    source_range: TextRange,
+    quote: bool,
+) {
+    let mut add_completion = |item: &str| {
+        let mut completion = CompletionItem::new(SymbolKind::BuiltinAttr, source_range, item);
+        if quote {
+            completion.insert_text(format!(r#""{item}""#));
+        }
+        acc.add(completion.build(ctx.db));
+    };
+    match key {
+        "target_arch" => KNOWN_ARCH.iter().copied().for_each(add_completion),
+        "target_env" => KNOWN_ENV.iter().copied().for_each(add_completion),
+        "target_os" => KNOWN_OS.iter().copied().for_each(add_completion),
+        "target_vendor" => KNOWN_VENDOR.iter().copied().for_each(add_completion),
+        "target_endian" => ["little", "big"].into_iter().for_each(add_completion),
+        name => ctx
+            .krate
+            .potential_cfg(ctx.db)
+            .get_cfg_values(name)
+            .for_each(|s| add_completion(s.as_str())),
+    }
+}
+
 const KNOWN_ARCH: [&str; 20] = [
     "aarch64",
     "arm",
@@ -1,7 +1,7 @@
 //! Completes references after dot (fields and method calls).
 
 use ide_db::FxHashSet;
-use syntax::SmolStr;
+use syntax::{ast, match_ast, AstNode, SmolStr};
 
 use crate::{
     context::{
@@ -22,8 +22,11 @@ pub(crate) fn complete_dot(
         _ => return,
     };
 
-    // Suggest .await syntax for types that implement Future trait
-    if receiver_ty.impls_into_future(ctx.db) {
+    // Suggest .await syntax for types that implement Future trait, or for types we failed to infer
+    // if we are in an async context, as the receiver might still be a future then.
+    if receiver_ty.impls_into_future(ctx.db)
+        || (receiver_ty.is_unknown() && is_in_async_context(ctx))
+    {
         let mut item = CompletionItem::new(
             CompletionItemKind::Keyword,
             ctx.source_range(),
@@ -50,6 +53,22 @@ pub(crate) fn complete_dot(
     complete_methods(ctx, receiver_ty, |func| acc.add_method(ctx, dot_access, func, None, None));
 }
 
+fn is_in_async_context(ctx: &CompletionContext<'_>) -> bool {
+    ctx.token
+        .parent_ancestors()
+        .find_map(|node| {
+            match_ast! {
+                match node {
+                    ast::Fn(it) => Some(it.async_token().is_some()),
+                    ast::ClosureExpr(it) => Some(it.async_token().is_some()),
+                    ast::BlockExpr(it) => it.async_token().map(|_| true),
+                    _ => ast::Item::can_cast(node.kind()).then_some(false),
+                }
+            }
+        })
+        .unwrap_or(false)
+}
+
 pub(crate) fn complete_undotted_self(
     acc: &mut Completions,
     ctx: &CompletionContext<'_>,
@@ -176,8 +195,19 @@ fn complete_methods(
 mod tests {
     use expect_test::{expect, Expect};
 
-    use crate::tests::{
-        check_edit, completion_list_no_kw, completion_list_no_kw_with_private_editable,
+    use ide_db::{
+        base_db::{FilePosition, SourceDatabaseExt2},
+        SymbolKind,
+    };
+    use stdx::format_to;
+    use test_utils::{bench, skip_slow_tests};
+
+    use crate::{
+        tests::{
+            check_edit, completion_list_no_kw, completion_list_no_kw_with_private_editable,
+            position, TEST_CONFIG,
+        },
+        CompletionItemKind,
     };
 
     fn check(ra_fixture: &str, expect: Expect) {
@@ -1278,4 +1308,47 @@ fn baz() {
             "#]],
         );
     }
+
+    #[test]
+    fn benchmark_method_completion_with_many_trait_imports() {
+        if skip_slow_tests() {
+            return;
+        }
+
+        let n = 64;
+        let mut traits = String::new();
+        for i in 0..n {
+            format_to!(traits, "pub trait Trait{i} {{ fn method{i}(&self) {{}} }}\n");
+            format_to!(traits, "impl<T> Trait{i} for T {{}}\n");
+        }
+        let imports = (0..n).map(|i| format!("Trait{i}")).collect::<Vec<_>>().join(", ");
+        let main = |body: &str| {
+            format!("mod traits;\nuse traits::{{{imports}}};\nstruct S;\nfn f(s: S) {{\n    {body}\n}}\n")
+        };
+        let (mut db, position) =
+            position(&format!("//- /main.rs\n{}//- /traits.rs\n{traits}", main("s.$0")));
+        let count_methods = |db: &_, position| {
+            crate::completions(db, &TEST_CONFIG, position, None)
+                .unwrap()
+                .into_iter()
+                .filter(|it| it.kind == CompletionItemKind::SymbolKind(SymbolKind::Method))
+                .count()
+        };
+
+        {
+            let _b = bench("method completion with many trait imports (initial)");
+            assert_eq!(count_methods(&db, position), n);
+        }
+
+        // Typing in the function body keeps the def maps, but invalidates the body's inference.
+        let text = main("let _ = 92;\n    s.");
+        let offset = text.rfind("s.").unwrap() + 2;
+        db.set_file_text(position.file_id, &text);
+        let position =
+            FilePosition { file_id: position.file_id, offset: offset.try_into().unwrap() };
+        {
+            let _b = bench("method completion with many trait imports (after typing)");
+            assert_eq!(count_methods(&db, position), n);
+        }
+    }
 }
@@ -149,6 +149,111 @@ fn foo(a: A) { a.$0 }
         );
     }
 
+    #[test]
+    fn test_completion_await_impl_future_and_nested_futures() {
+        check(
+            r#"
+//- minicore: future
+use core::future::Future;
+fn make() -> impl Future<Output = impl Future<Output = ()>> { loop {} }
+async fn foo() {
+    make().await.$0
+}
+"#,
+            expect![[r#"
+                me into_future() (use core::future::IntoFuture) fn(self) -> <Self as IntoFuture>::IntoFuture
+                kw await                  expr.await
+                sn box                    Box::new(expr)
+                sn call                   function(expr)
+                sn dbg                    dbg!(expr)
+                sn dbgr                   dbg!(&expr)
+                sn deref                  *expr
+                sn let                    let
+                sn letm                   let mut
+                sn match                  match expr {}
+                sn ref                    &expr
+                sn refm                   &mut expr
+                sn return                 return expr
+                sn unsafe                 unsafe {}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_completion_await_not_on_non_futures() {
+        check(
+            r#"
+//- minicore: future
+async fn foo(a: u32) { a.$0 }
+"#,
+            expect![[r#"
+                sn box    Box::new(expr)
+                sn call   function(expr)
+                sn dbg    dbg!(expr)
+                sn dbgr   dbg!(&expr)
+                sn deref  *expr
+                sn let    let
+                sn letm   let mut
+                sn match  match expr {}
+                sn ref    &expr
+                sn refm   &mut expr
+                sn return return expr
+                sn unsafe unsafe {}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_completion_await_on_unknown_type() {
+        check(
+            r#"
+//- minicore: future
+async fn foo() { unknown().$0 }
+"#,
+            expect![[r#"
+                kw await  expr.await
+                sn box    Box::new(expr)
+                sn call   function(expr)
+                sn dbg    dbg!(expr)
+                sn dbgr   dbg!(&expr)
+                sn deref  *expr
+                sn if     if expr {}
+                sn let    let
+                sn letm   let mut
+                sn match  match expr {}
+                sn not    !expr
+                sn ref    &expr
+                sn refm   &mut expr
+                sn return return expr
+                sn unsafe unsafe {}
+                sn while  while expr {}
+            "#]],
+        );
+        check(
+            r#"
+//- minicore: future
+fn foo() { unknown().$0 }
+"#,
+            expect![[r#"
+                sn box    Box::new(expr)
+                sn call   function(expr)
+                sn dbg    dbg!(expr)
+                sn dbgr   dbg!(&expr)
+                sn deref  *expr
+                sn if     if expr {}
+                sn let    let
+                sn letm   let mut
+                sn match  match expr {}
+                sn not    !expr
+                sn ref    &expr
+                sn refm   &mut expr
+                sn return return expr
+                sn unsafe unsafe {}
+                sn while  while expr {}
+            "#]],
+        );
+    }
+
     #[test]
     fn let_semi() {
         cov_mark::check!(let_semi);
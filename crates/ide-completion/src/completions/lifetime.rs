@@ -21,12 +21,14 @@ pub(crate) fn complete_lifetime(
     ctx: &CompletionContext<'_>,
     lifetime_ctx: &LifetimeContext,
 ) {
-    let (lp, lifetime) = match lifetime_ctx {
-        LifetimeContext { kind: LifetimeKind::Lifetime, lifetime } => (None, lifetime),
+    let (lp, lifetime, in_elision_position) = match lifetime_ctx {
+        LifetimeContext { kind: LifetimeKind::Lifetime { in_elision_position }, lifetime } => {
+            (None, lifetime, *in_elision_position)
+        }
         LifetimeContext {
             kind: LifetimeKind::LifetimeParam { is_decl: false, param },
             lifetime,
-        } => (Some(param), lifetime),
+        } => (Some(param), lifetime, false),
         _ => return,
     };
     let param_lifetime = match (lifetime, lp.and_then(|lp| lp.lifetime())) {
@@ -49,6 +51,9 @@ pub(crate) fn complete_lifetime(
     if param_lifetime.is_none() {
         acc.add_lifetime(ctx, known::STATIC_LIFETIME);
     }
+    if in_elision_position {
+        acc.add_lifetime(ctx, known::UNDERSCORE_LIFETIME);
+    }
 }
 
 /// Completes labels.
@@ -108,6 +113,7 @@ fn func<'lifetime>(foo: &'lifetime) {}
 fn foo<'lifetime>(foo: &'a$0 usize) {}
 "#,
             expect![[r#"
+                lt '_
                 lt 'lifetime
                 lt 'static
             "#]],
@@ -121,6 +127,7 @@ fn foo<'lifetime>(foo: &'a$0 usize) {}
 fn foo<'lifetime>(foo: &'a$0) {}
 "#,
             expect![[r#"
+                lt '_
                 lt 'lifetime
                 lt 'static
             "#]],
@@ -136,6 +143,7 @@ impl<'impl> Foo {
 }
 "#,
             expect![[r#"
+                lt '_
                 lt 'func
                 lt 'impl
                 lt 'static
@@ -149,6 +157,59 @@ impl<'impl> Foo {
             r#"
 struct Foo<'lt>;
 fn foo<'lifetime>(_: Foo<'a$0>) {}
+"#,
+            expect![[r#"
+                lt '_
+                lt 'lifetime
+                lt 'static
+            "#]],
+        );
+    }
+
+    #[test]
+    fn complete_lifetime_in_body_and_impl_header() {
+        check(
+            r#"
+struct Foo<'lt>;
+fn foo<'lifetime>() {
+    let _: &'a$0 usize;
+}
+"#,
+            expect![[r#"
+                lt '_
+                lt 'lifetime
+                lt 'static
+            "#]],
+        );
+        check(
+            r#"
+struct Foo<'lt>;
+impl Foo<'a$0> {}
+"#,
+            expect![[r#"
+                lt '_
+                lt 'static
+            "#]],
+        );
+    }
+
+    #[test]
+    fn dont_complete_anonymous_lifetime_in_item_signature() {
+        check(
+            r#"
+struct Foo<'lifetime> {
+    field: &'a$0 usize,
+}
+"#,
+            expect![[r#"
+                lt 'lifetime
+                lt 'static
+            "#]],
+        );
+        check(
+            r#"
+struct Foo<'lt>;
+type Alias<'lifetime> = Foo<'a$0>;
 "#,
             expect![[r#"
                 lt 'lifetime
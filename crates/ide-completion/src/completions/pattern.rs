@@ -1,11 +1,15 @@
 //! Completes constants and paths in unqualified patterns.
 
-use hir::{db::DefDatabase, AssocItem, ScopeDef};
-use syntax::ast::Pat;
+use hir::{db::DefDatabase, AssocItem, HasAttrs, ScopeDef, StructKind};
+use itertools::Itertools;
+use syntax::{
+    ast::{self, edit::IndentLevel, Pat},
+    AstNode,
+};
 
 use crate::{
     context::{PathCompletionCtx, PatternContext, PatternRefutability, Qualified},
-    CompletionContext, Completions,
+    CompletionContext, CompletionItem, CompletionItemKind, Completions,
 };
 
 /// Completes constants and paths in unqualified patterns.
@@ -43,6 +47,10 @@ pub(crate) fn complete_pattern(
         return;
     }
 
+    if pattern_ctx.is_only_match_arm {
+        complete_all_match_arms(acc, ctx);
+    }
+
     let refutable = pattern_ctx.refutability == PatternRefutability::Refutable;
     let single_variant_enum = |enum_: hir::Enum| ctx.db.enum_data(enum_.into()).variants.len() == 1;
 
@@ -184,3 +192,59 @@ pub(crate) fn complete_pattern_path(
         Qualified::TypeAnchor { .. } | Qualified::With { .. } => {}
     }
 }
+
+/// Completes the arms of a `match` on an enum, with one arm for each of its variants.
+fn complete_all_match_arms(acc: &mut Completions, ctx: &CompletionContext<'_>) -> Option<()> {
+    let cap = ctx.config.snippet_cap?;
+    let enum_ = match ctx.expected_type.as_ref()?.strip_references().as_adt()? {
+        hir::Adt::Enum(it) => it,
+        _ => return None,
+    };
+    let db = ctx.db;
+
+    let mut pats = Vec::new();
+    for variant in enum_.variants(db) {
+        let path = ctx.module.find_use_path(
+            db,
+            hir::ModuleDef::from(variant),
+            ctx.config.prefer_no_std,
+            ctx.config.prefer_prelude,
+        )?;
+        let path = path.display(db);
+        pats.push(match variant.kind(db) {
+            StructKind::Tuple => {
+                let fields = variant.fields(db).iter().map(|_| "_").join(", ");
+                format!("{path}({fields})")
+            }
+            StructKind::Record => format!("{path} {{ .. }}"),
+            StructKind::Unit => path.to_string(),
+        });
+    }
+    // Variants added to non-exhaustive enums of other crates need to be covered as well.
+    let is_non_exhaustive =
+        enum_.attrs(db).by_key("non_exhaustive").exists() && enum_.module(db).krate() != ctx.krate;
+    if is_non_exhaustive {
+        pats.push("_".to_owned());
+    }
+    if pats.is_empty() {
+        return None;
+    }
+
+    let indent = ctx
+        .original_token
+        .parent_ancestors()
+        .find_map(ast::MatchExpr::cast)
+        .map_or(IndentLevel(0), |it| IndentLevel::from_node(it.syntax()) + 1);
+    let snippet = pats
+        .iter()
+        .enumerate()
+        .map(|(i, pat)| format!("{pat} => ${{{}:todo!()}},", i + 1))
+        .join(&format!("\n{indent}"));
+
+    let mut item =
+        CompletionItem::new(CompletionItemKind::Snippet, ctx.source_range(), "all variants");
+    item.detail(format!("match arms for every variant of {}", enum_.name(db).display(db)))
+        .insert_snippet(cap, snippet);
+    item.add_to(acc, db);
+    Some(())
+}
@@ -12,6 +12,7 @@ use ide_db::{
 use stdx::never;
 use syntax::{
     ast::{self, make, AstNode, AstToken},
+    match_ast,
     SyntaxKind::{BLOCK_EXPR, EXPR_STMT, FOR_EXPR, IF_EXPR, LOOP_EXPR, STMT_LIST, WHILE_EXPR},
     TextRange, TextSize,
 };
@@ -147,6 +148,10 @@ pub(crate) fn complete_postfix(
         }
     }
 
+    if is_try_applicable(ctx, dot_receiver, receiver_ty) {
+        postfix_snippet("try", "expr?", &format!("{receiver_text}?")).add_to(acc, ctx.db);
+    }
+
     postfix_snippet("ref", "&expr", &format!("&{receiver_text}")).add_to(acc, ctx.db);
     postfix_snippet("refm", "&mut expr", &format!("&mut {receiver_text}")).add_to(acc, ctx.db);
     postfix_snippet("deref", "*expr", &format!("*{receiver_text}")).add_to(acc, ctx.db);
@@ -258,6 +263,61 @@ pub(crate) fn complete_postfix(
     }
 }
 
+/// Whether `?` compiles on the receiver: it has to be an `Option` or `Result` inside of a function
+/// or closure returning the same kind of value, with an error type the receiver's one converts to.
+fn is_try_applicable(
+    ctx: &CompletionContext<'_>,
+    receiver: &ast::Expr,
+    receiver_ty: &hir::Type,
+) -> bool {
+    let Some(try_enum) = TryEnum::from_ty(&ctx.sema, receiver_ty) else { return false };
+    let Some(ret_ty) = enclosing_return_type(ctx, receiver) else { return false };
+    if ret_ty.as_adt() != receiver_ty.as_adt() {
+        return false;
+    }
+    match try_enum {
+        TryEnum::Option => true,
+        TryEnum::Result => {
+            let (Some(receiver_err), Some(ret_err)) =
+                (receiver_ty.type_arguments().nth(1), ret_ty.type_arguments().nth(1))
+            else {
+                return false;
+            };
+            if receiver_err.contains_unknown() || ret_err.contains_unknown() {
+                return false;
+            }
+            // `?` converts the error with `From`.
+            receiver_err == ret_err
+                || ctx
+                    .famous_defs()
+                    .core_convert_From()
+                    .map_or(false, |from| ret_err.impls_trait(ctx.db, from, &[receiver_err]))
+        }
+    }
+}
+
+/// Returns the type a `?` at `expr` would return from, if it is a function or closure.
+fn enclosing_return_type(ctx: &CompletionContext<'_>, expr: &ast::Expr) -> Option<hir::Type> {
+    for node in ctx.sema.ancestors_with_macros(expr.syntax().clone()) {
+        match_ast! {
+            match node {
+                ast::Fn(it) => return Some(ctx.sema.to_def(&it)?.ret_type(ctx.db)),
+                ast::ClosureExpr(it) => {
+                    let closure_ty = ctx.sema.type_of_expr(&it.into())?.original;
+                    return Some(closure_ty.as_callable(ctx.db)?.return_type());
+                },
+                // `?` in async and try blocks doesn't return from the function.
+                ast::BlockExpr(it) => if it.async_token().is_some() || it.try_token().is_some() {
+                    return None;
+                },
+                ast::Item(_) => return None,
+                _ => (),
+            }
+        }
+    }
+    None
+}
+
 fn get_receiver_text(receiver: &ast::Expr, receiver_is_ambiguous_float_literal: bool) -> String {
     let mut text = if receiver_is_ambiguous_float_literal {
         let text = receiver.syntax().text();
@@ -577,6 +637,99 @@ fn main() {
         );
     }
 
+    #[test]
+    fn try_in_fallible_fns() {
+        check_edit(
+            "try",
+            r#"
+//- minicore: option
+fn first(v: Option<u32>) -> Option<u32> {
+    let x = v.$0;
+    Some(x)
+}
+"#,
+            r#"
+fn first(v: Option<u32>) -> Option<u32> {
+    let x = v?;
+    Some(x)
+}
+"#,
+        );
+        check_edit(
+            "try",
+            r#"
+//- minicore: result, from
+struct Error;
+impl From<()> for Error {
+    fn from(_: ()) -> Self { Error }
+}
+fn parse(r: Result<u32, ()>) -> Result<u32, Error> {
+    Ok(r.$0)
+}
+"#,
+            r#"
+struct Error;
+impl From<()> for Error {
+    fn from(_: ()) -> Self { Error }
+}
+fn parse(r: Result<u32, ()>) -> Result<u32, Error> {
+    Ok(r?)
+}
+"#,
+        );
+        check_edit(
+            "try",
+            r#"
+//- minicore: option, fn
+fn f(v: Option<u32>) {
+    let g = || -> Option<u32> { Some(v.$0) };
+}
+"#,
+            r#"
+fn f(v: Option<u32>) {
+    let g = || -> Option<u32> { Some(v?) };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_try_where_question_mark_does_not_compile() {
+        let fixtures = [
+            r#"
+//- minicore: option
+fn main() {
+    let bar = Some(true);
+    bar.$0
+}
+"#,
+            r#"
+//- minicore: option, result
+fn mismatched(v: Option<u32>) -> Result<u32, ()> {
+    Ok(v.$0)
+}
+"#,
+            r#"
+//- minicore: result, from
+struct Error;
+fn not_convertible(r: Result<u32, ()>) -> Result<u32, Error> {
+    Ok(r.$0)
+}
+"#,
+            r#"
+//- minicore: option
+fn in_async_block(v: Option<u32>) -> Option<u32> {
+    let _ = async { v.$0 };
+    None
+}
+"#,
+        ];
+        for ra_fixture in fixtures {
+            let completions = completion_list(ra_fixture);
+            assert!(!completions.contains("sn try"), "{completions}");
+        }
+    }
+
     #[test]
     fn postfix_completion_works_for_ambiguous_float_literal() {
         check_edit("refm", r#"fn main() { 42.$0 }"#, r#"fn main() { &mut 42 }"#)
@@ -117,9 +117,9 @@ fn complete_fields(
     ctx: &CompletionContext<'_>,
     missing_fields: Vec<(hir::Field, hir::Type)>,
 ) {
-    for (field, ty) in missing_fields {
+    for (position, (field, ty)) in (0..).zip(missing_fields) {
         // This should call something else, we shouldn't be synthesizing a DotAccess here
-        acc.add_field(
+        acc.add_missing_field(
             ctx,
             &DotAccess {
                 receiver: None,
@@ -130,9 +130,9 @@ fn complete_fields(
                     in_breakable: crate::context::BreakableKind::None,
                 },
             },
-            None,
             field,
             &ty,
+            position,
         );
     }
 }
@@ -1,6 +1,7 @@
 //! Completion of names from the current scope in type position.
 
 use hir::{HirDisplay, ScopeDef};
+use ide_db::FxHashSet;
 use syntax::{ast, AstNode};
 
 use crate::{
@@ -207,16 +208,66 @@ pub(crate) fn complete_type_path(
                 _ => {}
             };
 
+            let turbofish_candidates = turbofish_candidates(ctx, location);
             acc.add_nameref_keywords_with_colon(ctx);
-            ctx.process_all_names(&mut |name, def, doc_aliases| {
-                if scope_def_applicable(def) {
-                    acc.add_path_resolution(ctx, path_ctx, name, def, doc_aliases);
+            ctx.process_all_names(&mut |name, def, doc_aliases| match def {
+                ScopeDef::ModuleDef(hir::ModuleDef::Adt(adt))
+                    if turbofish_candidates.contains(&adt) =>
+                {
+                    acc.add_turbofish_type(ctx, path_ctx, name, adt, doc_aliases)
                 }
+                _ if scope_def_applicable(def) => {
+                    acc.add_path_resolution(ctx, path_ctx, name, def, doc_aliases)
+                }
+                _ => (),
             });
         }
     }
 }
 
+/// Collects the types implementing the trait bounds of the type parameter of a function that is
+/// being filled in, as in `iter.collect::<$0>()`, where `collect` requires `B: FromIterator<_>`.
+///
+/// This only looks at which traits are implemented, not at their generic arguments, so a type
+/// implementing `FromIterator<char>` is offered for an iterator of `u32`s as well. The candidates
+/// are all boosted equally; they are not ranked by how commonly they are used.
+fn turbofish_candidates(
+    ctx: &CompletionContext<'_>,
+    location: &TypeLocation,
+) -> FxHashSet<hir::Adt> {
+    let TypeLocation::GenericArg {
+        args: Some(args),
+        of_trait: None,
+        corresponding_param: Some(ast::GenericParam::TypeParam(param)),
+    } = location
+    else {
+        return FxHashSet::default();
+    };
+    let is_fn_call = args.syntax().parent().map_or(false, |parent| {
+        ast::MethodCallExpr::can_cast(parent.kind())
+            || parent.ancestors().any(|it| ast::PathExpr::can_cast(it.kind()))
+    });
+    let Some(param) = is_fn_call.then(|| ctx.sema.to_def(param)).flatten() else {
+        return FxHashSet::default();
+    };
+
+    let mut candidates: Option<FxHashSet<hir::Adt>> = None;
+    for trait_ in param.trait_bounds(ctx.db) {
+        let impls = hir::Impl::all_for_trait(ctx.db, trait_);
+        // Bounds like `Sized` are implemented by the compiler, and don't narrow anything down.
+        if impls.is_empty() {
+            continue;
+        }
+        let adts: FxHashSet<_> =
+            impls.into_iter().filter_map(|impl_| impl_.self_ty(ctx.db).as_adt()).collect();
+        candidates = Some(match candidates {
+            Some(candidates) => candidates.intersection(&adts).copied().collect(),
+            None => adts,
+        });
+    }
+    candidates.unwrap_or_default()
+}
+
 pub(crate) fn complete_ascribed_type(
     acc: &mut Completions,
     ctx: &CompletionContext<'_>,
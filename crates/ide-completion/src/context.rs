@@ -272,6 +272,8 @@ pub(crate) struct PatternContext {
     pub(crate) impl_: Option<ast::Impl>,
     /// List of missing variants in a match expr
     pub(crate) missing_variants: Vec<hir::Variant>,
+    /// Whether the pattern is the only arm of a match expr, without a body yet
+    pub(crate) is_only_match_arm: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -292,7 +294,7 @@ pub(crate) struct LifetimeContext {
 #[derive(Debug)]
 pub(crate) enum LifetimeKind {
     LifetimeParam { is_decl: bool, param: ast::LifetimeParam },
-    Lifetime,
+    Lifetime { in_elision_position: bool },
     LabelRef,
     LabelDef,
 }
@@ -447,6 +449,9 @@ pub(crate) struct CompletionContext<'a> {
     pub(crate) qualifier_ctx: QualifierCtx,
 
     pub(crate) locals: FxHashMap<Name, Local>,
+    /// How many of the locals in scope were declared after a given local, so the most recently
+    /// declared local maps to `0`.
+    pub(crate) local_recency: FxHashMap<Local, u32>,
 
     /// The module depth of the current module of the cursor position.
     /// - crate-root
@@ -729,6 +734,20 @@ impl<'a> CompletionContext<'a> {
                 locals.insert(name, local);
             }
         });
+        let mut declarations = locals
+            .values()
+            .map(|&local| {
+                let source = local.primary_source(db).syntax_ptr();
+                let range = source.map(|ptr| ptr.text_range()).original_node_file_range_rooted(db);
+                (local, range.range.start())
+            })
+            .collect::<Vec<_>>();
+        declarations.sort_by_key(|&(_, offset)| std::cmp::Reverse(offset));
+        let local_recency = declarations
+            .into_iter()
+            .enumerate()
+            .map(|(recency, (local, _))| (local, recency as u32))
+            .collect();
 
         let depth_from_crate_root = iter::successors(module.parent(db), |m| m.parent(db)).count();
 
@@ -747,6 +766,7 @@ impl<'a> CompletionContext<'a> {
             expected_type,
             qualifier_ctx,
             locals,
+            local_recency,
             depth_from_crate_root,
         };
         Some((ctx, analysis))
@@ -224,12 +224,20 @@ fn analyze(
             let parent = name_ref.syntax().parent()?;
             let (mut nameref_ctx, _) = classify_name_ref(sema, &original_file, name_ref, parent)?;
             if let NameRefKind::Path(path_ctx) = &mut nameref_ctx.kind {
+                // The derive we are currently completing shouldn't count as already present,
+                // otherwise a fully typed out `#[derive(Default$0)]` would filter itself out.
+                let completed_derive = find_node_at_offset::<ast::Path>(&original_file, offset)
+                    .and_then(|path| match sema.resolve_path(&path)? {
+                        hir::PathResolution::Def(hir::ModuleDef::Macro(mac)) => Some(mac),
+                        _ => None,
+                    });
                 path_ctx.kind = PathKind::Derive {
                     existing_derives: sema
                         .resolve_derive_macro(&origin_attr)
                         .into_iter()
                         .flatten()
                         .flatten()
+                        .filter(|mac| Some(*mac) != completed_derive)
                         .collect(),
                 };
             }
@@ -511,7 +519,9 @@ fn classify_lifetime(
             ast::BreakExpr(_) => LifetimeKind::LabelRef,
             ast::ContinueExpr(_) => LifetimeKind::LabelRef,
             ast::Label(_) => LifetimeKind::LabelDef,
-            _ => LifetimeKind::Lifetime,
+            _ => LifetimeKind::Lifetime {
+                in_elision_position: is_in_elision_position(&lifetime),
+            },
         }
     };
     let lifetime = find_node_at_offset(original_file, lifetime.syntax().text_range().start());
@@ -519,6 +529,36 @@ fn classify_lifetime(
     Some(LifetimeContext { lifetime, kind })
 }
 
+/// Whether the anonymous lifetime `'_` is permitted where `lifetime` sits.
+fn is_in_elision_position(lifetime: &ast::Lifetime) -> bool {
+    let Some(parent) = lifetime.syntax().parent() else { return false };
+    if !matches!(
+        parent.kind(),
+        SyntaxKind::REF_TYPE | SyntaxKind::LIFETIME_ARG | SyntaxKind::SELF_PARAM
+    ) {
+        return false;
+    }
+    parent
+        .ancestors()
+        .find_map(|node| match node.kind() {
+            SyntaxKind::PARAM_LIST
+            | SyntaxKind::RET_TYPE
+            | SyntaxKind::STMT_LIST
+            | SyntaxKind::IMPL => Some(true),
+            SyntaxKind::GENERIC_PARAM_LIST
+            | SyntaxKind::WHERE_CLAUSE
+            | SyntaxKind::RECORD_FIELD
+            | SyntaxKind::TUPLE_FIELD
+            | SyntaxKind::VARIANT
+            | SyntaxKind::TYPE_ALIAS
+            | SyntaxKind::CONST
+            | SyntaxKind::STATIC
+            | SyntaxKind::TRAIT => Some(false),
+            _ => None,
+        })
+        .unwrap_or(false)
+}
+
 fn classify_name(
     sema: &Semantics<'_, RootDatabase>,
     original_file: &SyntaxNode,
@@ -1259,6 +1299,7 @@ fn pattern_context_for(
     let mut param_ctx = None;
 
     let mut missing_variants = vec![];
+    let mut is_only_match_arm = false;
 
     let (refutability, has_type_ascription) =
     pat
@@ -1289,6 +1330,13 @@ fn pattern_context_for(
                         return (PatternRefutability::Irrefutable, has_type_ascription)
                     },
                     ast::MatchArm(match_arm) => {
+                       is_only_match_arm = match_arm.pat().as_ref() == Some(&pat)
+                           && match_arm.fat_arrow_token().is_none()
+                           && match_arm
+                               .syntax()
+                               .parent()
+                               .and_then(ast::MatchArmList::cast)
+                               .map_or(false, |list| list.arms().count() == 1);
                        let missing_variants_opt = match_arm
                             .syntax()
                             .parent()
@@ -1352,6 +1400,7 @@ fn pattern_context_for(
         record_pat: None,
         impl_: fetch_immediate_impl(sema, original_file, pat.syntax()),
         missing_variants,
+        is_only_match_arm,
     }
 }
 
@@ -423,3 +423,32 @@ fn f(thing: u32) -> &u32 {
         expect!["ty: u32, name: ?"],
     );
 }
+
+#[test]
+fn expected_type_generic_struct_field_in_call_argument() {
+    check_expected_type_and_name(
+        r#"
+struct Foo<T> { a: T, b: u8 }
+fn takes(_: Foo<u32>) {}
+fn foo() {
+    takes(Foo { b: 0, a: $0 });
+}
+"#,
+        expect![[r#"ty: u32, name: a"#]],
+    )
+}
+
+#[test]
+fn expected_type_generic_struct_field_in_method_call_argument() {
+    check_expected_type_and_name(
+        r#"
+struct Foo<T> { a: T }
+struct S;
+impl S { fn takes(&self, _: Foo<i64>) {} }
+fn foo() {
+    S.takes(Foo { a: $0 });
+}
+"#,
+        expect![[r#"ty: i64, name: a"#]],
+    )
+}
@@ -150,10 +150,32 @@ pub struct CompletionRelevance {
     /// }
     /// ```
     pub is_local: bool,
+    /// This is set for locals, counting the locals in scope that were declared after this one:
+    ///
+    /// ```
+    /// fn foo(a: u32) {
+    ///     let b = 0;
+    ///     $0 // `b` has a recency of 0, `a` has a recency of 1
+    /// }
+    /// ```
+    pub local_recency: Option<u32>,
+    /// This is set for the fields completed in a record literal or pattern, to the position of
+    /// the field among the missing ones:
+    ///
+    /// ```
+    /// struct S { b: u32, a: u32 }
+    /// fn foo() {
+    ///     S { $0 } // `b` has a position of 0, `a` has a position of 1
+    /// }
+    /// ```
+    pub missing_field_position: Option<u32>,
     /// This is set when trait items are completed in an impl of that trait.
     pub is_item_from_trait: bool,
     /// This is set for when trait items are from traits with `#[doc(notable_trait)]`
     pub is_item_from_notable_trait: bool,
+    /// This is set for associated constants and types of inherent impls, to rank them above the
+    /// ones coming from traits.
+    pub is_inherent_assoc_item: bool,
     /// This is set when an import is suggested whose name is already imported.
     pub is_name_already_imported: bool,
     /// This is set for completions that will insert a `use` item.
@@ -243,6 +265,8 @@ impl CompletionRelevance {
             exact_name_match,
             type_match,
             is_local,
+            local_recency,
+            missing_field_position,
             is_item_from_trait,
             is_name_already_imported,
             requires_import,
@@ -251,6 +275,7 @@ impl CompletionRelevance {
             postfix_match,
             is_definite,
             is_item_from_notable_trait,
+            is_inherent_assoc_item,
             function,
         } = self;
 
@@ -287,12 +312,20 @@ impl CompletionRelevance {
         if is_local {
             score += 1;
         }
+        // prefer the missing fields of a record, ties between them are broken by their position
+        // when the sort text is computed, so that they are listed in declaration order
+        if missing_field_position.is_some() {
+            score += 10;
+        }
         if is_item_from_trait {
             score += 1;
         }
         if is_item_from_notable_trait {
             score += 1;
         }
+        if is_inherent_assoc_item {
+            score += 1;
+        }
         if is_definite {
             score += 10;
         }
@@ -323,6 +356,14 @@ impl CompletionRelevance {
             })
             .unwrap_or_default();
 
+        // among locals of a fitting type, prefer the more recently declared ones. This only breaks
+        // ties, so it never lifts a local above one with a matching name or a better fitting type.
+        const LOCAL_RECENCY_WINDOW: u32 = 8;
+        score *= LOCAL_RECENCY_WINDOW;
+        if let (Some(recency), Some(_)) = (local_recency, type_match) {
+            score += (LOCAL_RECENCY_WINDOW - 1).saturating_sub(recency);
+        }
+
         score
     }
 
@@ -706,7 +747,42 @@ mod tests {
             vec![Cr { is_local: true, ..default }],
             vec![Cr { type_match: Some(CompletionRelevanceTypeMatch::CouldUnify), ..default }],
             vec![Cr { type_match: Some(CompletionRelevanceTypeMatch::Exact), ..default }],
-            vec![Cr { exact_name_match: true, ..default }],
+            vec![
+                Cr {
+                    type_match: Some(CompletionRelevanceTypeMatch::Exact),
+                    is_local: true,
+                    ..default
+                },
+                Cr {
+                    type_match: Some(CompletionRelevanceTypeMatch::Exact),
+                    is_local: true,
+                    local_recency: Some(20),
+                    ..default
+                },
+            ],
+            vec![Cr {
+                type_match: Some(CompletionRelevanceTypeMatch::Exact),
+                is_local: true,
+                local_recency: Some(2),
+                ..default
+            }],
+            vec![Cr {
+                type_match: Some(CompletionRelevanceTypeMatch::Exact),
+                is_local: true,
+                local_recency: Some(1),
+                ..default
+            }],
+            vec![Cr {
+                type_match: Some(CompletionRelevanceTypeMatch::Exact),
+                is_local: true,
+                local_recency: Some(0),
+                ..default
+            }],
+            vec![
+                Cr { exact_name_match: true, ..default },
+                Cr { missing_field_position: Some(0), ..default },
+                Cr { missing_field_position: Some(20), ..default },
+            ],
             vec![Cr { exact_name_match: true, is_local: true, ..default }],
             vec![Cr {
                 exact_name_match: true,
@@ -208,6 +208,7 @@ pub fn completions(
                 completions::extern_abi::complete_extern_abi(acc, ctx, expanded);
                 completions::format_string::format_string(acc, ctx, original, expanded);
                 completions::env_vars::complete_cargo_env_vars(acc, ctx, original, expanded);
+                completions::attribute::complete_cfg_value_in_string(acc, ctx, original);
             }
             CompletionAnalysis::UnexpandedAttrTT {
                 colon_prefix,
@@ -240,6 +240,48 @@ pub(crate) fn render_path_resolution(
     render_resolution_path(ctx, path_ctx, local_name, None, resolution)
 }
 
+/// Renders a type that implements the traits bounding the generic parameter being filled in by a
+/// turbofish, e.g. `Vec` in `iter.collect::<$0>()`, with its type arguments as placeholders.
+pub(crate) fn render_turbofish_type(
+    ctx: RenderContext<'_>,
+    path_ctx: &PathCompletionCtx,
+    local_name: hir::Name,
+    adt: hir::Adt,
+) -> Builder {
+    let db = ctx.db();
+    let cap = ctx.snippet_cap();
+    let mut item = render_resolution_path(
+        ctx,
+        path_ctx,
+        local_name.clone(),
+        None,
+        ScopeDef::ModuleDef(adt.into()),
+    );
+    item.with_relevance(|relevance| CompletionRelevance {
+        type_match: Some(CompletionRelevanceTypeMatch::CouldUnify),
+        ..relevance
+    });
+
+    // Const parameters can't be inferred with `_` in a turbofish.
+    let params = hir::GenericDef::from(adt).type_or_const_params(db);
+    let mut placeholders = Vec::new();
+    for param in params {
+        match param.as_type_param(db) {
+            Some(param) if param.default(db).is_some() => (),
+            Some(_) => placeholders.push(format!("${{{}:_}}", placeholders.len() + 1)),
+            None => return item,
+        }
+    }
+    if let (Some(cap), false) = (cap, placeholders.is_empty()) {
+        let name = local_name.to_smol_str();
+        item.lookup_by(name.clone()).label(SmolStr::from_iter([&name, "<…>"])).insert_snippet(
+            cap,
+            format!("{}<{}>", local_name.display(db), placeholders.join(", ")),
+        );
+    }
+    item
+}
+
 pub(crate) fn render_pattern_resolution(
     ctx: RenderContext<'_>,
     pattern_ctx: &PatternContext,
@@ -454,6 +496,10 @@ fn render_resolution_path(
             type_match: compute_type_match(completion, &ty),
             exact_name_match: compute_exact_name_match(completion, &name),
             is_local: matches!(resolution, ScopeDef::Local(_)),
+            local_recency: match resolution {
+                ScopeDef::Local(local) => completion.local_recency.get(&local).copied(),
+                _ => None,
+            },
             requires_import,
             ..CompletionRelevance::default()
         });
@@ -748,13 +794,40 @@ mod tests {
                 (relevance.requires_import, "requires_import"),
             ]
             .into_iter()
-            .filter_map(|(cond, desc)| if cond { Some(desc) } else { None })
+            .filter_map(|(cond, desc)| if cond { Some(desc.to_owned()) } else { None })
+            .chain(relevance.local_recency.map(|it| format!("local_recency={it}")))
+            .chain(relevance.missing_field_position.map(|it| format!("field_position={it}")))
             .join("+");
 
             format!("[{relevance_factors}]")
         }
     }
 
+    #[test]
+    fn inherent_assoc_items_rank_above_trait_items() {
+        check_relevance(
+            r#"
+trait Trait {
+    const FROM_TRAIT: u8;
+}
+struct S;
+impl Trait for S {
+    const FROM_TRAIT: u8 = 0;
+}
+impl S {
+    const INHERENT: u8 = 0;
+}
+fn f() {
+    S::$0
+}
+"#,
+            expect![[r#"
+                ct INHERENT []
+                ct FROM_TRAIT (as Trait) []
+            "#]],
+        );
+    }
+
     #[test]
     fn set_struct_type_completion_info() {
         check_relevance(
@@ -788,6 +861,27 @@ fn main() {
         );
     }
 
+    #[test]
+    fn turbofish_types_implementing_bounds_first() {
+        check_relevance(
+            r#"
+struct Vec<T>(T);
+struct Other;
+trait FromIterator {}
+impl<T> FromIterator for Vec<T> {}
+fn collect<B: FromIterator>() -> B { loop {} }
+fn main() {
+    collect::<$0>();
+}
+"#,
+            expect![[r#"
+                st Vec<…> [type_could_unify]
+                tt FromIterator []
+                st Other []
+            "#]],
+        );
+    }
+
     #[test]
     fn set_union_type_completion_info() {
         check_relevance(
@@ -1049,7 +1143,7 @@ fn func(input: Struct) { }
                 sp Self [type]
                 st Struct [type]
                 ex Struct [type]
-                lc self [local]
+                lc self [local+local_recency=0]
                 fn func(…) []
                 me self.test() []
             "#]],
@@ -1072,11 +1166,11 @@ fn main() {
 }
 "#,
             expect![[r#"
-                lc input [type+name+local]
+                lc input [type+name+local+local_recency=1]
                 ex input [type]
                 ex true [type]
                 ex false [type]
-                lc inputbad [local]
+                lc inputbad [local+local_recency=0]
                 fn main() []
                 fn test(…) []
             "#]],
@@ -1249,7 +1343,7 @@ fn main() { let _: m::Spam = S$0 }
                         kind: SymbolKind(
                             Variant,
                         ),
-                        lookup: "Spam::Bar()",
+                        lookup: "Bar()",
                         detail: "m::Spam::Bar(i32)",
                         relevance: CompletionRelevance {
                             exact_name_match: false,
@@ -1257,8 +1351,11 @@ fn main() { let _: m::Spam = S$0 }
                                 Exact,
                             ),
                             is_local: false,
+                            local_recency: None,
+                            missing_field_position: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
+                            is_inherent_assoc_item: false,
                             is_name_already_imported: false,
                             requires_import: false,
                             is_op_method: false,
@@ -1277,7 +1374,7 @@ fn main() { let _: m::Spam = S$0 }
                         kind: SymbolKind(
                             Variant,
                         ),
-                        lookup: "Spam::Foo",
+                        lookup: "Foo",
                         detail: "m::Spam::Foo",
                         relevance: CompletionRelevance {
                             exact_name_match: false,
@@ -1285,8 +1382,11 @@ fn main() { let _: m::Spam = S$0 }
                                 Exact,
                             ),
                             is_local: false,
+                            local_recency: None,
+                            missing_field_position: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
+                            is_inherent_assoc_item: false,
                             is_name_already_imported: false,
                             requires_import: false,
                             is_op_method: false,
@@ -1365,8 +1465,13 @@ fn foo() { A { the$0 } }
                                 CouldUnify,
                             ),
                             is_local: false,
+                            local_recency: None,
+                            missing_field_position: Some(
+                                0,
+                            ),
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
+                            is_inherent_assoc_item: false,
                             is_name_already_imported: false,
                             requires_import: false,
                             is_op_method: false,
@@ -1416,8 +1521,11 @@ impl S {
                             exact_name_match: false,
                             type_match: None,
                             is_local: false,
+                            local_recency: None,
+                            missing_field_position: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
+                            is_inherent_assoc_item: false,
                             is_name_already_imported: false,
                             requires_import: false,
                             is_op_method: false,
@@ -1543,8 +1651,11 @@ fn foo(s: S) { s.$0 }
                             exact_name_match: false,
                             type_match: None,
                             is_local: false,
+                            local_recency: None,
+                            missing_field_position: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
+                            is_inherent_assoc_item: false,
                             is_name_already_imported: false,
                             requires_import: false,
                             is_op_method: false,
@@ -1730,6 +1841,23 @@ fn foo(a: A) { B { bar: a.$0 }; }
         )
     }
 
+    #[test]
+    fn missing_record_fields_in_declaration_order() {
+        check_relevance(
+            r#"
+struct Bar<T> { zed: T, alpha: u8, mid: T }
+fn foo(_: Bar<u32>) {}
+fn main() {
+    foo(Bar { alpha: 0, $0 });
+}
+"#,
+            expect![[r#"
+                fd zed [field_position=0]
+                fd mid [field_position=1]
+            "#]],
+        );
+    }
+
     #[test]
     fn record_field_and_call_relevances() {
         check_relevance(
@@ -1768,7 +1896,7 @@ struct WorldSnapshot { _f: () };
 fn go(world: &WorldSnapshot) { go(w$0) }
 "#,
             expect![[r#"
-                lc world [type+name+local]
+                lc world [type+name+local+local_recency=0]
                 st WorldSnapshot {…} []
                 st &WorldSnapshot {…} [type]
                 st WorldSnapshot []
@@ -1787,7 +1915,7 @@ struct Foo;
 fn f(foo: &Foo) { f(foo, w$0) }
 "#,
             expect![[r#"
-                lc foo [local]
+                lc foo [local+local_recency=0]
                 st Foo []
                 fn f(…) []
             "#]],
@@ -1871,8 +1999,8 @@ fn main() {
 }
             "#,
             expect![[r#"
-                lc s [name+local]
-                lc &mut s [type+name+local]
+                lc s [name+local+local_recency=0]
+                lc &mut s [type+name+local+local_recency=0]
                 st S []
                 st &mut S [type]
                 st S []
@@ -1891,7 +2019,7 @@ fn main() {
 }
             "#,
             expect![[r#"
-                lc s [type+name+local]
+                lc s [type+name+local+local_recency=0]
                 st S [type]
                 st S [type]
                 ex s [type]
@@ -1910,7 +2038,7 @@ fn main() {
 }
             "#,
             expect![[r#"
-                lc ssss [type+local]
+                lc ssss [type+local+local_recency=0]
                 st S [type]
                 st S [type]
                 ex ssss [type]
@@ -1949,9 +2077,9 @@ fn main() {
             expect![[r#"
                 ex core::ops::Deref::deref(&T(S)) (use core::ops::Deref) [type_could_unify]
                 ex core::ops::Deref::deref(&t) (use core::ops::Deref) [type_could_unify]
-                lc m [local]
-                lc t [local]
-                lc &t [type+local]
+                lc m [local+local_recency=0]
+                lc t [local+local_recency=1]
+                lc &t [type+local+local_recency=1]
                 st S []
                 st &S [type]
                 st S []
@@ -1999,9 +2127,9 @@ fn main() {
             expect![[r#"
                 ex core::ops::DerefMut::deref_mut(&mut T(S)) (use core::ops::DerefMut) [type_could_unify]
                 ex core::ops::DerefMut::deref_mut(&mut t) (use core::ops::DerefMut) [type_could_unify]
-                lc m [local]
-                lc t [local]
-                lc &mut t [type+local]
+                lc m [local+local_recency=0]
+                lc t [local+local_recency=1]
+                lc &mut t [type+local+local_recency=1]
                 st S []
                 st &mut S [type]
                 st S []
@@ -2026,13 +2154,35 @@ fn foo(bar: u32) {
 }
 "#,
             expect![[r#"
-                lc baz [local]
-                lc bar [local]
+                lc baz [local+local_recency=0]
+                lc bar [local+local_recency=1]
                 fn foo(…) []
             "#]],
         );
     }
 
+    #[test]
+    fn recent_locals_of_matching_type() {
+        check_relevance_for_kinds(
+            r#"
+fn take(x: u32) {}
+fn main() {
+    let first = 0u32;
+    let text = "";
+    let (alpha, beta) = (0u32, 1u32);
+    take($0);
+}
+"#,
+            &[CompletionItemKind::SymbolKind(SymbolKind::Local)],
+            expect![[r#"
+                lc beta [type+local+local_recency=0]
+                lc alpha [type+local+local_recency=1]
+                lc first [type+local+local_recency=3]
+                lc text [local+local_recency=2]
+            "#]],
+        );
+    }
+
     #[test]
     fn enum_owned() {
         check_relevance(
@@ -2436,8 +2586,11 @@ fn foo(f: Foo) { let _: &u32 = f.b$0 }
                             exact_name_match: false,
                             type_match: None,
                             is_local: false,
+                            local_recency: None,
+                            missing_field_position: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
+                            is_inherent_assoc_item: false,
                             is_name_already_imported: false,
                             requires_import: false,
                             is_op_method: false,
@@ -2518,8 +2671,11 @@ fn foo() {
                                 Exact,
                             ),
                             is_local: false,
+                            local_recency: None,
+                            missing_field_position: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
+                            is_inherent_assoc_item: false,
                             is_name_already_imported: false,
                             requires_import: false,
                             is_op_method: false,
@@ -2568,8 +2724,11 @@ fn main() {
                             exact_name_match: false,
                             type_match: None,
                             is_local: false,
+                            local_recency: None,
+                            missing_field_position: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
+                            is_inherent_assoc_item: false,
                             is_name_already_imported: false,
                             requires_import: false,
                             is_op_method: false,
@@ -2610,7 +2769,7 @@ fn foo() {
 }
 "#,
             expect![[r#"
-                lc foo [type+local]
+                lc foo [type+local+local_recency=0]
                 ex foo [type]
                 ex Foo::B [type]
                 ev Foo::A(…) [type_could_unify]
@@ -2940,8 +3099,11 @@ fn main() {
                             exact_name_match: false,
                             type_match: None,
                             is_local: false,
+                            local_recency: None,
+                            missing_field_position: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: true,
+                            is_inherent_assoc_item: false,
                             is_name_already_imported: false,
                             requires_import: false,
                             is_op_method: false,
@@ -2965,8 +3127,11 @@ fn main() {
                             exact_name_match: false,
                             type_match: None,
                             is_local: false,
+                            local_recency: None,
+                            missing_field_position: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: true,
+                            is_inherent_assoc_item: false,
                             is_name_already_imported: false,
                             requires_import: false,
                             is_op_method: false,
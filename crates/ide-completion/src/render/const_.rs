@@ -3,7 +3,10 @@
 use hir::{AsAssocItem, HirDisplay};
 use ide_db::SymbolKind;
 
-use crate::{item::CompletionItem, render::RenderContext};
+use crate::{
+    item::{CompletionItem, CompletionRelevance},
+    render::RenderContext,
+};
 
 pub(crate) fn render_const(ctx: RenderContext<'_>, const_: hir::Const) -> Option<CompletionItem> {
     let _p = tracing::span!(tracing::Level::INFO, "render_const").entered();
@@ -16,16 +19,20 @@ fn render(ctx: RenderContext<'_>, const_: hir::Const) -> Option<CompletionItem>
     let (name, escaped_name) = (name.unescaped().to_smol_str(), name.to_smol_str());
     let detail = const_.display(db).to_string();
 
+    let actm = const_.as_assoc_item(db);
+    let trait_ = actm.and_then(|it| it.container_or_implemented_trait(db));
+
     let mut item = CompletionItem::new(SymbolKind::Const, ctx.source_range(), name);
     item.set_documentation(ctx.docs(const_))
         .set_deprecated(ctx.is_deprecated(const_) || ctx.is_deprecated_assoc_item(const_))
         .detail(detail)
-        .set_relevance(ctx.completion_relevance());
+        .set_relevance(CompletionRelevance {
+            is_inherent_assoc_item: actm.is_some() && trait_.is_none(),
+            ..ctx.completion_relevance()
+        });
 
-    if let Some(actm) = const_.as_assoc_item(db) {
-        if let Some(trt) = actm.container_or_implemented_trait(db) {
-            item.trait_name(trt.name(db).to_smol_str());
-        }
+    if let Some(trt) = trait_ {
+        item.trait_name(trt.name(db).to_smol_str());
     }
     item.insert_text(escaped_name);
 
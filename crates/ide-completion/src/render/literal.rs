@@ -102,10 +102,15 @@ fn render(
         kind = StructKind::Unit;
     }
     let label = format_literal_label(&qualified_name, kind, snippet_cap);
-    let lookup = if qualified {
-        format_literal_lookup(&short_qualified_name.display(ctx.db()).to_string(), kind)
-    } else {
-        format_literal_lookup(&qualified_name, kind)
+    let lookup = match thing {
+        // Let the bare variant name match, the qualifier gets inserted regardless.
+        Variant::EnumVariant(variant) if qualified => {
+            format_literal_lookup(&variant.name(db).unescaped().to_smol_str(), kind)
+        }
+        _ if qualified => {
+            format_literal_lookup(&short_qualified_name.display(ctx.db()).to_string(), kind)
+        }
+        _ => format_literal_lookup(&qualified_name, kind),
     };
 
     let mut item = CompletionItem::new(
@@ -74,7 +74,12 @@ pub(crate) fn render_variant_pat(
         _ => {
             let kind = variant.kind(ctx.db());
             let label = format_literal_label(name.as_str(), kind, ctx.snippet_cap());
-            let lookup = format_literal_lookup(name.as_str(), kind);
+            let lookup = match path {
+                Some(_) => {
+                    format_literal_lookup(&variant.name(ctx.db()).unescaped().to_smol_str(), kind)
+                }
+                None => format_literal_lookup(name.as_str(), kind),
+            };
             let pat = render_pat(
                 &ctx,
                 pattern_ctx,
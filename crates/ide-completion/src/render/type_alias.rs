@@ -4,7 +4,10 @@ use hir::{AsAssocItem, HirDisplay};
 use ide_db::SymbolKind;
 use syntax::SmolStr;
 
-use crate::{item::CompletionItem, render::RenderContext};
+use crate::{
+    item::{CompletionItem, CompletionRelevance},
+    render::RenderContext,
+};
 
 pub(crate) fn render_type_alias(
     ctx: RenderContext<'_>,
@@ -40,16 +43,20 @@ fn render(
     };
     let detail = type_alias.display(db).to_string();
 
+    let actm = type_alias.as_assoc_item(db);
+    let trait_ = actm.and_then(|it| it.container_or_implemented_trait(db));
+
     let mut item = CompletionItem::new(SymbolKind::TypeAlias, ctx.source_range(), name);
     item.set_documentation(ctx.docs(type_alias))
         .set_deprecated(ctx.is_deprecated(type_alias) || ctx.is_deprecated_assoc_item(type_alias))
         .detail(detail)
-        .set_relevance(ctx.completion_relevance());
+        .set_relevance(CompletionRelevance {
+            is_inherent_assoc_item: actm.is_some() && trait_.is_none(),
+            ..ctx.completion_relevance()
+        });
 
-    if let Some(actm) = type_alias.as_assoc_item(db) {
-        if let Some(trt) = actm.container_or_implemented_trait(db) {
-            item.trait_name(trt.name(db).to_smol_str());
-        }
+    if let Some(trt) = trait_ {
+        item.trait_name(trt.name(db).to_smol_str());
     }
     item.insert_text(escaped_name);
 
@@ -656,6 +656,50 @@ mod cfg {
         );
     }
 
+    #[test]
+    fn cfg_feature() {
+        check(
+            r#"
+//- /main.rs cfg:feature=std,feature=serde
+#[cfg(feature = "$0")]
+"#,
+            expect![[r#"
+                ba serde
+                ba std
+            "#]],
+        );
+        check(
+            r#"
+//- /main.rs cfg:feature=std,feature=serde
+#[cfg(any(test, feature = $0))]
+"#,
+            expect![[r#"
+                ba serde
+                ba std
+            "#]],
+        );
+        check(
+            r#"
+//- /main.rs cfg:feature=std,feature=serde
+#[cfg(all(unix, not(feature = "s$0")))]
+"#,
+            expect![[r#"
+                ba serde
+                ba std
+            "#]],
+        );
+        check(
+            r#"
+//- /main.rs cfg:feature=std,feature=serde
+#[cfg_attr(feature = "$0", derive(Debug))]
+"#,
+            expect![[r#"
+                ba serde
+                ba std
+            "#]],
+        );
+    }
+
     #[test]
     fn cfg_target_endian() {
         check(
@@ -778,6 +822,44 @@ mod derive {
         );
     }
 
+    #[test]
+    fn derive_with_completed_derive_under_cursor() {
+        check_derive(
+            r#"
+//- minicore: derive, copy, clone, ord, eq, default, fmt
+#[derive(PartialEq, Default$0)] struct Test;
+"#,
+            expect![[r#"
+                de Clone               macro Clone
+                de Clone, Copy
+                de Default             macro Default
+                de Eq
+                de Eq, PartialOrd, Ord
+                de PartialOrd
+                md core
+                kw crate::
+                kw self::
+            "#]],
+        );
+        check_derive(
+            r#"
+//- minicore: derive, copy, clone, ord, eq, default, fmt
+#[derive(Default, Defa$0)] struct Test;
+"#,
+            expect![[r#"
+                de Clone                  macro Clone
+                de Clone, Copy
+                de PartialEq              macro PartialEq
+                de PartialEq, Eq
+                de PartialEq, Eq, PartialOrd, Ord
+                de PartialEq, PartialOrd
+                md core
+                kw crate::
+                kw self::
+            "#]],
+        );
+    }
+
     #[test]
     fn derive_flyimport() {
         check_derive(
@@ -811,6 +893,23 @@ use proc_macros::DeriveIdentity;
         );
     }
 
+    #[test]
+    fn derive_flyimport_skips_existing_proc_macro_derive() {
+        check_derive(
+            r#"
+//- proc_macros: derive_identity
+//- minicore: derive
+#[derive(proc_macros::DeriveIdentity, der$0)] struct Test;
+"#,
+            expect![[r#"
+                md core
+                md proc_macros
+                kw crate::
+                kw self::
+            "#]],
+        );
+    }
+
     #[test]
     fn derive_flyimport_edit() {
         check_edit(
@@ -1309,3 +1309,116 @@ fn main() {
         "#]],
     );
 }
+
+#[test]
+fn expected_enum_variant_by_bare_name() {
+    check_edit(
+        "Pending",
+        r#"
+mod status {
+    pub enum Status { Pending, Done(u32) }
+}
+fn set(_: status::Status) {}
+fn f() {
+    set(Pen$0);
+}
+"#,
+        r#"
+mod status {
+    pub enum Status { Pending, Done(u32) }
+}
+fn set(_: status::Status) {}
+fn f() {
+    set(status::Status::Pending$0);
+}
+"#,
+    );
+    check_edit(
+        "Done()",
+        r#"
+enum Status { Pending, Done(u32) }
+fn f() {
+    let _: Status = Do$0;
+}
+"#,
+        r#"
+enum Status { Pending, Done(u32) }
+fn f() {
+    let _: Status = Status::Done(${1:()})$0;
+}
+"#,
+    );
+}
+
+#[test]
+fn expected_enum_variant_glob_imported() {
+    check_empty(
+        r#"
+enum Status { Pending, Done }
+use Status::*;
+fn f() {
+    let _: Status = Pen$0;
+}
+"#,
+        expect![[r#"
+            en Status    Status
+            fn f()       fn()
+            ev Done      Done
+            ev Pending   Pending
+            bt u32       u32
+            kw crate::
+            kw false
+            kw for
+            kw if
+            kw if let
+            kw loop
+            kw match
+            kw return
+            kw self::
+            kw true
+            kw unsafe
+            kw while
+            kw while let
+            ex Done
+            ex Pending
+        "#]],
+    );
+    check_empty(
+        r#"
+//- minicore: option
+fn f() {
+    let _: Option<u32> = No$0;
+}
+"#,
+        expect![[r#"
+            en Option                Option<{unknown}>
+            en Result                Result<{unknown}, {unknown}>
+            fn f()                   fn()
+            ma const_format_args!(…) macro_rules! const_format_args
+            ma format_args!(…)       macro_rules! format_args
+            ma format_args_nl!(…)    macro_rules! format_args_nl
+            ma panic!(…)             macro_rules! panic
+            ma print!(…)             macro_rules! print
+            md core
+            ev Err(…)                Err(E)
+            ev None                  None
+            ev Ok(…)                 Ok(T)
+            ev Some(…)               Some(T)
+            bt u32                   u32
+            kw crate::
+            kw false
+            kw for
+            kw if
+            kw if let
+            kw loop
+            kw match
+            kw return
+            kw self::
+            kw true
+            kw unsafe
+            kw while
+            kw while let
+            ex None
+        "#]],
+    );
+}
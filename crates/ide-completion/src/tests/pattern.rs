@@ -238,10 +238,11 @@ fn foo() {
 "#,
         expect![[r#"
             en E
-            ma m!(…) macro_rules! m
-            bn E::X  E::X$0
+            ma m!(…)        macro_rules! m
+            bn E::X         E::X$0
             kw mut
             kw ref
+            sn all variants match arms for every variant of E
         "#]],
     );
 }
@@ -540,6 +541,7 @@ fn foo() {
             bn Enum::type       Enum::r#type$0
             kw mut
             kw ref
+            sn all variants     match arms for every variant of Enum
         "#]],
     );
 
@@ -819,3 +821,142 @@ pub enum Enum {
         "#]],
     );
 }
+
+#[test]
+fn expected_enum_variant_pat_by_bare_name() {
+    check_edit(
+        "Pending",
+        r#"
+enum Status { Pending, Done }
+fn foo(s: Status) {
+    match s {
+        Pen$0
+    }
+}
+"#,
+        r#"
+enum Status { Pending, Done }
+fn foo(s: Status) {
+    match s {
+        Status::Pending$0
+    }
+}
+"#,
+    );
+}
+
+#[test]
+fn match_arms_for_all_variants() {
+    check_edit(
+        "all variants",
+        r#"
+enum Shape { Circle(f32), Rect { w: f32, h: f32 }, Empty }
+fn foo(s: Shape) {
+    match s {
+        $0
+    }
+}
+"#,
+        r#"
+enum Shape { Circle(f32), Rect { w: f32, h: f32 }, Empty }
+fn foo(s: Shape) {
+    match s {
+        Shape::Circle(_) => ${1:todo!()},
+        Shape::Rect { .. } => ${2:todo!()},
+        Shape::Empty => ${3:todo!()},
+    }
+}
+"#,
+    );
+}
+
+#[test]
+fn match_arms_for_all_variants_of_option_and_result() {
+    check_edit(
+        "all variants",
+        r#"
+//- minicore: option
+fn foo(o: Option<i32>) {
+    match o {
+        $0
+    }
+}
+"#,
+        r#"
+fn foo(o: Option<i32>) {
+    match o {
+        None => ${1:todo!()},
+        Some(_) => ${2:todo!()},
+    }
+}
+"#,
+    );
+    check_edit(
+        "all variants",
+        r#"
+//- minicore: result
+fn foo(r: &Result<i32, ()>) {
+    match r {
+        $0
+    }
+}
+"#,
+        r#"
+fn foo(r: &Result<i32, ()>) {
+    match r {
+        Ok(_) => ${1:todo!()},
+        Err(_) => ${2:todo!()},
+    }
+}
+"#,
+    );
+}
+
+#[test]
+fn match_arms_for_all_variants_of_non_exhaustive_enum() {
+    check_edit(
+        "all variants",
+        r#"
+//- /main.rs crate:main deps:lib
+fn foo(e: lib::E) {
+    match e {
+        $0
+    }
+}
+//- /lib.rs crate:lib
+#[non_exhaustive]
+pub enum E { A, B }
+"#,
+        r#"
+fn foo(e: lib::E) {
+    match e {
+        lib::E::A => ${1:todo!()},
+        lib::E::B => ${2:todo!()},
+        _ => ${3:todo!()},
+    }
+}
+"#,
+    );
+}
+
+#[test]
+fn no_match_arms_for_all_variants_with_other_arms() {
+    check_empty(
+        r#"
+enum E { A, B }
+fn foo(e: E) {
+    match e {
+        E::A => (),
+        $0
+    }
+}
+"#,
+        expect![[r#"
+            en E
+            bn E::A E::A$0
+            bn E::B E::B$0
+            kw mut
+            kw ref
+        "#]],
+    );
+}
@@ -301,3 +301,38 @@ fn foo() {
         expect![[r#""#]],
     )
 }
+
+#[test]
+fn record_literal_fields_in_call_argument() {
+    check(
+        r#"
+//- minicore: option
+struct Bar<T> { zed: T, alpha: u8, mid: Option<T> }
+fn foo(_: Bar<u32>) {}
+fn main() {
+    foo(Bar { alpha: 0, $0 });
+}
+"#,
+        expect![[r#"
+            fd mid Option<u32>
+            fd zed u32
+        "#]],
+    );
+}
+
+#[test]
+fn record_literal_fields_in_call_argument_empty() {
+    check(
+        r#"
+struct Bar<T> { zed: T, alpha: u8 }
+fn foo(_: Bar<u32>) {}
+fn main() {
+    foo(Bar { $0 });
+}
+"#,
+        expect![[r#"
+            fd alpha u8
+            fd zed   u32
+        "#]],
+    );
+}
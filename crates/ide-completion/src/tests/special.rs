@@ -296,6 +296,36 @@ fn foo() { let _ = <S as Trait>::$0 }
     );
 }
 
+#[test]
+fn completes_inherent_and_trait_assoc_consts() {
+    check_no_kw(
+        r#"
+trait Trait {
+    type Assoc;
+    const TRAIT_CONST: u8;
+}
+trait NotImplemented {
+    const MISSING: u8;
+}
+
+struct S;
+impl S {
+    const INHERENT_CONST: u8 = 0;
+}
+impl Trait for S {
+    type Assoc = ();
+    const TRAIT_CONST: u8 = 0;
+}
+
+fn foo() { let _ = S::$0 }
+"#,
+        expect![[r#"
+            ct INHERENT_CONST         const INHERENT_CONST: u8
+            ct TRAIT_CONST (as Trait) const TRAIT_CONST: u8
+        "#]],
+    );
+}
+
 #[test]
 fn completes_ty_param_assoc_ty() {
     check_no_kw(
@@ -1,7 +1,7 @@
 //! Completion tests for type position.
 use expect_test::{expect, Expect};
 
-use crate::tests::{check_empty, completion_list, BASE_ITEMS_FIXTURE};
+use crate::tests::{check_edit, check_empty, completion_list, BASE_ITEMS_FIXTURE};
 
 fn check(ra_fixture: &str, expect: Expect) {
     let actual = completion_list(&format!("{BASE_ITEMS_FIXTURE}\n{ra_fixture}"));
@@ -1029,3 +1029,64 @@ impl outer::$0 for Bar { }
         "#]],
     );
 }
+
+#[test]
+fn complete_turbofish_types_implementing_bounds() {
+    let fixture = r#"
+mod collections {
+    pub struct HashMap<K, V, S = ()>(K, V, S);
+}
+use collections::HashMap;
+struct Vec<T>(T);
+struct Set<T>(T);
+struct Unrelated;
+trait FromIterator<A> {}
+impl<T> FromIterator<T> for Vec<T> {}
+impl<K, V> FromIterator<(K, V)> for HashMap<K, V> {}
+struct Iter;
+impl Iter {
+    fn collect<B: FromIterator<u32>>(self) -> B { loop {} }
+}
+fn main() {
+    Iter.collect::<$0>();
+}
+"#;
+    check_empty(
+        fixture,
+        expect![[r#"
+            md collections
+            st HashMap<…>   HashMap<{unknown}, {unknown}, {unknown}>
+            st Iter         Iter
+            st Set<…>       Set<{unknown}>
+            st Unrelated    Unrelated
+            st Vec<…>       Vec<{unknown}>
+            tt FromIterator
+            bt u32          u32
+            kw crate::
+            kw self::
+        "#]],
+    );
+    check_edit("Vec", fixture, &fixture.replace("$0", "Vec<${1:_}>"));
+    check_edit("HashMap", fixture, &fixture.replace("$0", "HashMap<${1:_}, ${2:_}>"));
+}
+
+#[test]
+fn complete_turbofish_types_without_narrowing_bounds() {
+    check_empty(
+        r#"
+struct Vec<T>(T);
+trait NoImpls {}
+fn make<T: NoImpls>() -> T { loop {} }
+fn main() {
+    make::<$0>();
+}
+"#,
+        expect![[r#"
+            st Vec<…>  Vec<{unknown}>
+            tt NoImpls
+            bt u32     u32
+            kw crate::
+            kw self::
+        "#]],
+    );
+}
@@ -1,7 +1,7 @@
 //! See [`FamousDefs`].
 
 use base_db::{CrateOrigin, LangCrateOrigin, SourceDatabase};
-use hir::{Crate, Enum, Function, Macro, Module, ScopeDef, Semantics, Trait};
+use hir::{Crate, Enum, Function, Macro, Module, ScopeDef, Semantics, Struct, Trait};
 
 use crate::RootDatabase;
 
@@ -42,14 +42,26 @@ impl FamousDefs<'_, '_> {
         self.find_lang_crate(LangCrateOrigin::ProcMacro)
     }
 
+    pub fn core_clone_Clone(&self) -> Option<Trait> {
+        self.find_trait("core:clone:Clone")
+    }
+
     pub fn core_cmp_Ord(&self) -> Option<Trait> {
         self.find_trait("core:cmp:Ord")
     }
 
+    pub fn core_cmp_PartialEq(&self) -> Option<Trait> {
+        self.find_trait("core:cmp:PartialEq")
+    }
+
     pub fn core_convert_From(&self) -> Option<Trait> {
         self.find_trait("core:convert:From")
     }
 
+    pub fn core_convert_TryFrom(&self) -> Option<Trait> {
+        self.find_trait("core:convert:TryFrom")
+    }
+
     pub fn core_convert_Into(&self) -> Option<Trait> {
         self.find_trait("core:convert:Into")
     }
@@ -58,6 +70,18 @@ impl FamousDefs<'_, '_> {
         self.find_trait("core:ops:Index")
     }
 
+    pub fn core_fmt_Debug(&self) -> Option<Trait> {
+        self.find_trait("core:fmt:Debug")
+    }
+
+    pub fn core_error_Error(&self) -> Option<Trait> {
+        self.find_trait("core:error:Error")
+    }
+
+    pub fn core_hash_Hash(&self) -> Option<Trait> {
+        self.find_trait("core:hash:Hash")
+    }
+
     pub fn core_option_Option(&self) -> Option<Enum> {
         self.find_enum("core:option:Option")
     }
@@ -110,6 +134,10 @@ impl FamousDefs<'_, '_> {
         self.find_macro("core:macros:builtin:derive")
     }
 
+    pub fn core_mem_ManuallyDrop(&self) -> Option<Struct> {
+        self.find_struct("core:mem:ManuallyDrop")
+    }
+
     pub fn core_mem_drop(&self) -> Option<Function> {
         self.find_function("core:mem:drop")
     }
@@ -122,6 +150,42 @@ impl FamousDefs<'_, '_> {
         self.find_macro("core:unimplemented")
     }
 
+    pub fn alloc_boxed_Box(&self) -> Option<Struct> {
+        self.find_struct("alloc:boxed:Box")
+    }
+
+    pub fn alloc_vec_Vec(&self) -> Option<Struct> {
+        self.find_struct("alloc:vec:Vec")
+    }
+
+    pub fn alloc_string_String(&self) -> Option<Struct> {
+        self.find_struct("alloc:string:String")
+    }
+
+    pub fn alloc_sync_Arc(&self) -> Option<Struct> {
+        self.find_struct("alloc:sync:Arc")
+    }
+
+    pub fn std_collections_HashMap(&self) -> Option<Struct> {
+        self.find_struct("std:collections:HashMap")
+    }
+
+    pub fn std_collections_HashSet(&self) -> Option<Struct> {
+        self.find_struct("std:collections:HashSet")
+    }
+
+    pub fn std_sync_Mutex(&self) -> Option<Struct> {
+        self.find_struct("std:sync:Mutex")
+    }
+
+    pub fn std_sync_MutexGuard(&self) -> Option<Struct> {
+        self.find_struct("std:sync:MutexGuard")
+    }
+
+    pub fn std_sync_PoisonError(&self) -> Option<Struct> {
+        self.find_struct("std:sync:PoisonError")
+    }
+
     pub fn builtin_crates(&self) -> impl Iterator<Item = Crate> {
         IntoIterator::into_iter([
             self.std(),
@@ -147,6 +211,13 @@ impl FamousDefs<'_, '_> {
         }
     }
 
+    fn find_struct(&self, path: &str) -> Option<Struct> {
+        match self.find_def(path)? {
+            hir::ScopeDef::ModuleDef(hir::ModuleDef::Adt(hir::Adt::Struct(it))) => Some(it),
+            _ => None,
+        }
+    }
+
     fn find_enum(&self, path: &str) -> Option<Enum> {
         match self.find_def(path)? {
             hir::ScopeDef::ModuleDef(hir::ModuleDef::Adt(hir::Adt::Enum(it))) => Some(it),
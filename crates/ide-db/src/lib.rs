@@ -40,6 +40,7 @@ pub mod syntax_helpers {
     pub mod format_string_exprs;
     pub mod insert_whitespace_into_node;
     pub mod node_ext;
+    pub mod suggest_name;
 
     pub use parser::LexedStr;
 }
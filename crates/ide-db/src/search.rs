@@ -265,7 +265,8 @@ impl IntoIterator for SearchScope {
 }
 
 impl Definition {
-    fn search_scope(&self, db: &RootDatabase) -> SearchScope {
+    /// Returns the set of files, or file ranges, in which this definition can be referenced.
+    pub fn search_scope(&self, db: &RootDatabase) -> SearchScope {
         let _p = tracing::span!(tracing::Level::INFO, "search_scope").entered();
 
         if let Definition::BuiltinType(_) = self {
@@ -893,25 +894,52 @@ impl ReferenceCategory {
             return result;
         }
 
-        let mode = r.syntax().ancestors().find_map(|node| {
-            match_ast! {
-                match node {
-                    ast::BinExpr(expr) => {
-                        if matches!(expr.op_kind()?, ast::BinaryOp::Assignment { .. }) {
-                            // If the variable or field ends on the LHS's end then it's a Write
-                            // (covers fields and locals). FIXME: This is not terribly accurate.
-                            if let Some(lhs) = expr.lhs() {
-                                if lhs.syntax().text_range().end() == r.syntax().text_range().end() {
-                                    return Some(ReferenceCategory::WRITE)
+        let ends_at = |expr: Option<ast::Expr>| {
+            expr.map_or(false, |it| it.syntax().text_range().end() == r.syntax().text_range().end())
+        };
+        let mode = r
+            .syntax()
+            .ancestors()
+            .find_map(|node| {
+                match_ast! {
+                    match node {
+                        ast::BinExpr(expr) => {
+                            if let ast::BinaryOp::Assignment { op } = expr.op_kind()? {
+                                // If the variable or field ends on the LHS's end then it's a Write
+                                // (covers fields and locals). FIXME: This is not terribly accurate.
+                                if ends_at(expr.lhs()) {
+                                    // Compound assignments like `+=` also read the old value.
+                                    return Some(if op.is_some() {
+                                        ReferenceCategory::WRITE | ReferenceCategory::READ
+                                    } else {
+                                        ReferenceCategory::WRITE
+                                    });
                                 }
                             }
-                        }
-                        Some(ReferenceCategory::READ)
-                    },
-                    _ => None,
+                            Some(ReferenceCategory::READ)
+                        },
+                        ast::RefExpr(expr) => {
+                            if expr.mut_token().is_some() && ends_at(expr.expr()) {
+                                return Some(ReferenceCategory::WRITE);
+                            }
+                            Some(ReferenceCategory::READ)
+                        },
+                        // `x.push(1)` implicitly borrows `x` mutably.
+                        ast::MethodCallExpr(call) => {
+                            if !ends_at(call.receiver()) {
+                                return None;
+                            }
+                            let self_param = sema.resolve_method_call(&call)?.self_param(sema.db)?;
+                            match self_param.access(sema.db) {
+                                hir::Access::Exclusive => Some(ReferenceCategory::WRITE),
+                                _ => None,
+                            }
+                        },
+                        _ => None,
+                    }
                 }
-            }
-        }).unwrap_or(ReferenceCategory::READ);
+            })
+            .unwrap_or(ReferenceCategory::READ);
 
         result | mode
     }
@@ -42,7 +42,7 @@ use rayon::prelude::*;
 use rustc_hash::FxHashSet;
 use triomphe::Arc;
 
-use crate::RootDatabase;
+use crate::{RootDatabase, SymbolKind};
 
 #[derive(Debug, Clone)]
 pub struct Query {
@@ -52,6 +52,7 @@ pub struct Query {
     assoc_mode: AssocSearchMode,
     case_sensitive: bool,
     only_types: bool,
+    only_kind: Option<SymbolKind>,
     libs: bool,
 }
 
@@ -62,6 +63,7 @@ impl Query {
             query,
             lowercased,
             only_types: false,
+            only_kind: None,
             libs: false,
             mode: SearchMode::Fuzzy,
             assoc_mode: AssocSearchMode::Include,
@@ -73,6 +75,11 @@ impl Query {
         self.only_types = true;
     }
 
+    /// Only return symbols of the given kind, see [`split_kind_prefix`].
+    pub fn only_kind(&mut self, kind: SymbolKind) {
+        self.only_kind = Some(kind);
+    }
+
     pub fn libs(&mut self) {
         self.libs = true;
     }
@@ -189,6 +196,10 @@ impl<DB> std::ops::Deref for Snap<DB> {
 // That is, `#` switches from "types" to all symbols, `*` switches from the current
 // workspace to dependencies.
 //
+// A query can also be restricted to one kind of symbol with a `fn:`, `struct:`, `enum:`,
+// `union:`, `trait:`, `type:`, `const:`, `static:`, `mod:` or `macro:` prefix, e.g.
+// `#fn:parse` only lists functions matching `parse`.
+//
 // Note that filtering does not currently work in VSCode due to the editor never
 // sending the special symbols to the language server. Instead, you can configure
 // the filtering via the `rust-analyzer.workspace.symbol.search.scope` and
@@ -371,7 +382,12 @@ impl Query {
                                 | hir::ModuleDef::TraitAlias(..)
                                 | hir::ModuleDef::Trait(..)
                         );
-                    if non_type_for_type_only_query || !self.matches_assoc_mode(symbol.is_assoc) {
+                    let wrong_kind =
+                        self.only_kind.map_or(false, |kind| symbol_kind(symbol.def) != Some(kind));
+                    if non_type_for_type_only_query
+                        || wrong_kind
+                        || !self.matches_assoc_mode(symbol.is_assoc)
+                    {
                         continue;
                     }
                     if self.mode.check(&self.query, self.case_sensitive, &symbol.name) {
@@ -390,6 +406,45 @@ impl Query {
     }
 }
 
+/// Splits a kind filter like `fn:` off the start of a symbol query, so that `fn:parse` only
+/// searches for functions matching `parse`.
+pub fn split_kind_prefix(query: &str) -> (Option<SymbolKind>, &str) {
+    let Some((prefix, rest)) = query.split_once(':') else { return (None, query) };
+    let kind = match prefix.trim() {
+        "fn" => SymbolKind::Function,
+        "struct" => SymbolKind::Struct,
+        "enum" => SymbolKind::Enum,
+        "union" => SymbolKind::Union,
+        "trait" => SymbolKind::Trait,
+        "type" => SymbolKind::TypeAlias,
+        "const" => SymbolKind::Const,
+        "static" => SymbolKind::Static,
+        "mod" => SymbolKind::Module,
+        "macro" => SymbolKind::Macro,
+        _ => return (None, query),
+    };
+    (Some(kind), rest.trim_start())
+}
+
+fn symbol_kind(def: hir::ModuleDef) -> Option<SymbolKind> {
+    let kind = match def {
+        hir::ModuleDef::Module(_) => SymbolKind::Module,
+        hir::ModuleDef::Function(_) => SymbolKind::Function,
+        hir::ModuleDef::Adt(hir::Adt::Struct(_)) => SymbolKind::Struct,
+        hir::ModuleDef::Adt(hir::Adt::Enum(_)) => SymbolKind::Enum,
+        hir::ModuleDef::Adt(hir::Adt::Union(_)) => SymbolKind::Union,
+        hir::ModuleDef::Variant(_) => SymbolKind::Variant,
+        hir::ModuleDef::Const(_) => SymbolKind::Const,
+        hir::ModuleDef::Static(_) => SymbolKind::Static,
+        hir::ModuleDef::Trait(_) => SymbolKind::Trait,
+        hir::ModuleDef::TraitAlias(_) => SymbolKind::TraitAlias,
+        hir::ModuleDef::TypeAlias(_) => SymbolKind::TypeAlias,
+        hir::ModuleDef::Macro(_) => SymbolKind::Macro,
+        hir::ModuleDef::BuiltinType(_) => return None,
+    };
+    Some(kind)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -2,8 +2,8 @@
 use itertools::Itertools;
 use parser::T;
 use syntax::{
-    ast::{self, HasLoopBody, MacroCall, PathSegmentKind, VisibilityKind},
-    AstNode, AstToken, Preorder, RustLanguage, WalkEvent,
+    ast::{self, edit::IndentLevel, HasLoopBody, MacroCall, PathSegmentKind, VisibilityKind},
+    AstNode, AstToken, Preorder, RustLanguage, SyntaxKind, SyntaxNode, TextSize, WalkEvent,
 };
 
 pub fn expr_as_name_ref(expr: &ast::Expr) -> Option<ast::NameRef> {
@@ -472,3 +472,14 @@ pub fn macro_call_for_string_token(string: &ast::String) -> Option<MacroCall> {
     let macro_call = string.syntax().parent_ancestors().find_map(ast::MacroCall::cast)?;
     Some(macro_call)
 }
+
+/// Returns the offset and the text to insert to add the outer attribute `#[{attr}]` to `item`.
+///
+/// The attribute is put after existing doc comments and attributes, on its own line.
+pub fn insert_outer_attr(item: &SyntaxNode, attr: &str) -> Option<(TextSize, String)> {
+    let insert_before = item.children_with_tokens().find(|it| {
+        !matches!(it.kind(), SyntaxKind::ATTR | SyntaxKind::COMMENT | SyntaxKind::WHITESPACE)
+    })?;
+    let indent = IndentLevel::from_node(item);
+    Some((insert_before.text_range().start(), format!("#[{attr}]\n{indent}")))
+}
@@ -0,0 +1,81 @@
+//! Functions to suggest names for new generic parameters.
+use syntax::{
+    ast::{self, HasGenericParams, HasName},
+    match_ast, AstNode, SmolStr,
+};
+
+use crate::FxHashSet;
+
+/// Suggest a unique name for generic parameter.
+///
+/// `existing_params` is used to check if the name conflicts with existing
+/// generic parameters.
+///
+/// The function checks if the name conflicts with existing generic parameters.
+/// If so, it will try to resolve the conflict by adding a number suffix, e.g.
+/// `T`, `T0`, `T1`, ...
+pub fn for_unique_generic_name(name: &str, existing_params: &ast::GenericParamList) -> SmolStr {
+    unique_generic_name(name, &generic_param_names(existing_params))
+}
+
+/// Suggest name of impl trait type
+///
+/// # Current implementation
+///
+/// In current implementation, the function tries to get the name from the first
+/// character of the name for the first type bound.
+///
+/// If the name conflicts with the generic parameters in scope of the enclosing function or
+/// with a name that is referenced inside of it (which the new parameter would shadow),
+/// a number suffix is added like in `for_unique_generic_name`.
+pub fn for_impl_trait_as_generic(ty: &ast::ImplTraitType) -> SmolStr {
+    let c = ty
+        .type_bound_list()
+        .and_then(|bounds| bounds.syntax().text().char_at(0.into()))
+        .unwrap_or('T');
+
+    let mut taken = FxHashSet::default();
+    if let Some(fn_) = ty.syntax().ancestors().find_map(ast::Fn::cast) {
+        taken.extend(
+            fn_.syntax().descendants().filter_map(ast::NameRef::cast).map(|it| it.to_string()),
+        );
+        let generic_param_lists = fn_.syntax().ancestors().filter_map(|it| {
+            match_ast! {
+                match it {
+                    ast::Fn(it) => it.generic_param_list(),
+                    ast::Impl(it) => it.generic_param_list(),
+                    ast::Trait(it) => it.generic_param_list(),
+                    _ => None,
+                }
+            }
+        });
+        for params in generic_param_lists {
+            taken.extend(generic_param_names(&params));
+        }
+    }
+    unique_generic_name(c.encode_utf8(&mut [0; 4]), &taken)
+}
+
+/// Returns `name`, or `name` with the first number suffix that makes it not `taken`.
+pub fn unique_generic_name(name: &str, taken: &FxHashSet<String>) -> SmolStr {
+    let mut name = name.to_owned();
+    let base_len = name.len();
+    let mut count = 0;
+    while taken.contains(&name) {
+        name.truncate(base_len);
+        name.push_str(&count.to_string());
+        count += 1;
+    }
+
+    name.into()
+}
+
+fn generic_param_names(existing_params: &ast::GenericParamList) -> FxHashSet<String> {
+    existing_params
+        .generic_params()
+        .map(|param| match param {
+            ast::GenericParam::TypeParam(t) => t.name().unwrap().to_string(),
+            p => p.to_string(),
+        })
+        .collect()
+}
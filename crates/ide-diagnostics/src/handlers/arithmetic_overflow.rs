@@ -0,0 +1,116 @@
+use hir::HirDisplay;
+
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+// Diagnostic: arithmetic-overflow
+//
+// This diagnostic is triggered if evaluating a constant expression, like the value of a `const`,
+// an enum discriminant or an array length, overflows or divides by zero.
+pub(crate) fn arithmetic_overflow(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::ArithmeticOverflow,
+) -> Diagnostic {
+    let ty = d.ty.display(ctx.sema.db);
+    let (lhs, rhs) = (format!("{}_{ty}", d.lhs), format!("{}_{ty}", d.rhs));
+    let message = match d.op.as_str() {
+        "/" if d.rhs == 0 => format!("attempt to divide `{lhs}` by zero"),
+        "%" if d.rhs == 0 => {
+            format!("attempt to calculate the remainder of `{lhs}` with a divisor of zero")
+        }
+        // The right operand of a shift may have a different type.
+        "<<" => format!("attempt to shift `{lhs}` left by `{}`, which would overflow", d.rhs),
+        ">>" => format!("attempt to shift `{lhs}` right by `{}`, which would overflow", d.rhs),
+        op => format!("attempt to compute `{lhs} {op} {rhs}`, which would overflow"),
+    };
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::RustcHardError("E0080"),
+        message,
+        d.expr.map(|it| it.into()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::check_diagnostics;
+
+    #[test]
+    fn consts_and_statics() {
+        check_diagnostics(
+            r#"
+const ADD: u8 = 255 + 1;
+             // ^^^^^^^ error: attempt to compute `255_u8 + 1_u8`, which would overflow
+const SUB: usize = { let zero = 0; zero - 1 };
+                                // ^^^^^^^^ error: attempt to compute `0_usize - 1_usize`, which would overflow
+static MUL: i8 = -64 * 2 * 2;
+              // ^^^^^^^^^^^ error: attempt to compute `-128_i8 * 2_i8`, which would overflow
+const DIV: i32 = 1 / (ADD as i32 - 255);
+const REM: i32 = 7 % 0;
+              // ^^^^^ error: attempt to calculate the remainder of `7_i32` with a divisor of zero
+const SHL: i8 = 1 << 8;
+             // ^^^^^^ error: attempt to shift `1_i8` left by `8`, which would overflow
+const FINE: u8 = 254 + 1;
+"#,
+        );
+    }
+
+    #[test]
+    fn through_const_fn() {
+        check_diagnostics(
+            r#"
+const fn double(x: u8) -> u8 {
+    x * 2
+}
+const OK: u8 = double(100);
+const TOO_BIG: u8 = double(200);
+                 // ^^^^^^^^^^^ error: attempt to compute `200_u8 * 2_u8`, which would overflow
+"#,
+        );
+    }
+
+    #[test]
+    fn discriminants_and_array_lengths() {
+        check_diagnostics(
+            r#"
+#[repr(u8)]
+enum E {
+    A = 200,
+    B = 200 + 100,
+     // ^^^^^^^^^ error: attempt to compute `200_u8 + 100_u8`, which would overflow
+}
+
+const LEN: usize = 0;
+fn f() {
+    let _a = [0u8; LEN - 1];
+                // ^^^^^^^ error: attempt to compute `0_usize - 1_usize`, which would overflow
+    let _b = [0u8; 2 / LEN];
+                // ^^^^^^^ error: attempt to divide `2_usize` by zero
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn wrapping_arithmetic() {
+        check_diagnostics(
+            r#"
+//- minicore: add, copy, derive
+extern "rust-intrinsic" {
+    pub fn wrapping_add<T: Copy>(a: T, b: T) -> T;
+}
+
+#[derive(Clone, Copy)]
+struct Wrapping(u8);
+impl core::ops::Add for Wrapping {
+    type Output = Wrapping;
+    fn add(self, other: Wrapping) -> Wrapping {
+        Wrapping(unsafe { wrapping_add(self.0, other.0) })
+    }
+}
+
+const INTRINSIC: u8 = unsafe { wrapping_add(255, 1) };
+const WRAPPING: Wrapping = Wrapping(255) + Wrapping(1);
+"#,
+        );
+    }
+}
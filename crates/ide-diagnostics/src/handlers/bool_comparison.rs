@@ -0,0 +1,222 @@
+use hir::{InFile, Semantics};
+use ide_db::{
+    base_db::{FileId, FileRange},
+    source_change::SourceChange,
+    RootDatabase,
+};
+use syntax::{ast, AstNode, SyntaxNode, SyntaxNodePtr};
+use text_edit::TextEdit;
+
+use crate::{fix, Diagnostic, DiagnosticCode, DiagnosticsConfig};
+
+// Diagnostic: bool-comparison
+//
+// This diagnostic is triggered when a boolean expression is compared to a `true` or `false`
+// literal with `==` or `!=`. The comparison can be replaced by the expression itself or its
+// negation.
+//
+// This is a style lint, it is only emitted if style lints are enabled.
+pub(crate) fn bool_comparison(
+    sema: &Semantics<'_, RootDatabase>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    node: &SyntaxNode,
+    config: &DiagnosticsConfig,
+) -> Option<()> {
+    if !config.style_lints {
+        return None;
+    }
+    let bin_expr = ast::BinExpr::cast(node.clone())?;
+    let negated = match bin_expr.op_kind()? {
+        ast::BinaryOp::CmpOp(ast::CmpOp::Eq { negated }) => negated,
+        _ => return None,
+    };
+    let (lhs, rhs) = (bin_expr.lhs()?, bin_expr.rhs()?);
+    let (operand, literal) = match (bool_literal(&lhs), bool_literal(&rhs)) {
+        (_, Some(literal)) => (lhs, literal),
+        (Some(literal), None) => (rhs, literal),
+        (None, None) => return None,
+    };
+    if !sema.type_of_expr(&operand)?.original.is_bool() {
+        return None;
+    }
+
+    // `x == true` and `x != false` both turn into `x`, `x == false` and `x != true` into `!x`.
+    let keep = literal != negated;
+    let replacement = if keep {
+        operand.syntax().text().to_string()
+    } else if binds_tighter_than_not(&operand) {
+        format!("!{operand}")
+    } else {
+        format!("!({operand})")
+    };
+    let message = if keep {
+        "equality checks against a boolean literal are unnecessary"
+    } else {
+        "equality checks against a boolean literal can be replaced by a negation"
+    };
+
+    let range = bin_expr.syntax().text_range();
+    let edit = TextEdit::replace(range, replacement);
+    acc.push(
+        Diagnostic::new(
+            DiagnosticCode::Clippy("bool_comparison"),
+            message,
+            FileRange { file_id, range },
+        )
+        .with_main_node(InFile::new(file_id.into(), SyntaxNodePtr::new(node)))
+        .with_fixes(Some(vec![fix(
+            "simplify_bool_comparison",
+            "Simplify boolean comparison",
+            SourceChange::from_text_edit(file_id, edit),
+            range,
+        )])),
+    );
+
+    Some(())
+}
+
+fn bool_literal(expr: &ast::Expr) -> Option<bool> {
+    match expr {
+        ast::Expr::Literal(it) => match it.kind() {
+            ast::LiteralKind::Bool(value) => Some(value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether `expr` can be negated with a leading `!` without wrapping it in parentheses.
+fn binds_tighter_than_not(expr: &ast::Expr) -> bool {
+    matches!(
+        expr,
+        ast::Expr::PathExpr(_)
+            | ast::Expr::Literal(_)
+            | ast::Expr::ParenExpr(_)
+            | ast::Expr::CallExpr(_)
+            | ast::Expr::MethodCallExpr(_)
+            | ast::Expr::FieldExpr(_)
+            | ast::Expr::IndexExpr(_)
+            | ast::Expr::TryExpr(_)
+            | ast::Expr::AwaitExpr(_)
+            | ast::Expr::MacroExpr(_)
+            | ast::Expr::PrefixExpr(_)
+            | ast::Expr::BlockExpr(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{check_diagnostics, check_diagnostics_with_config, check_fix},
+        DiagnosticsConfig,
+    };
+
+    #[test]
+    fn bool_comparisons() {
+        check_diagnostics(
+            r#"
+fn f(a: bool, b: bool) {
+    if a == true {}
+     //^^^^^^^^^ 💡 weak: equality checks against a boolean literal are unnecessary
+    if false != b {}
+     //^^^^^^^^^^ 💡 weak: equality checks against a boolean literal are unnecessary
+    if a == false {}
+     //^^^^^^^^^^ 💡 weak: equality checks against a boolean literal can be replaced by a negation
+    if b != true {}
+     //^^^^^^^^^ 💡 weak: equality checks against a boolean literal can be replaced by a negation
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_diagnostic_for_non_bool_or_non_literal_operands() {
+        check_diagnostics(
+            r#"
+//- minicore: eq
+struct S;
+impl PartialEq<bool> for S {
+    fn eq(&self, _: &bool) -> bool { false }
+}
+fn f(a: bool, b: bool, s: S) {
+    let _ = a == b;
+    let _ = s == true;
+    let _ = a & b;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_diagnostic_without_style_lints() {
+        let mut config = DiagnosticsConfig::test_sample();
+        config.style_lints = false;
+        check_diagnostics_with_config(
+            config,
+            r#"
+fn f(a: bool) -> bool {
+    a == true
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn fix_keeps_operand() {
+        check_fix(
+            r#"
+fn f(a: bool) -> bool {
+    a =$0= true
+}
+"#,
+            r#"
+fn f(a: bool) -> bool {
+    a
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn fix_negates_operand() {
+        check_fix(
+            r#"
+fn f(a: bool) -> bool {
+    g(a) =$0= false
+}
+fn g(a: bool) -> bool { a }
+"#,
+            r#"
+fn f(a: bool) -> bool {
+    !g(a)
+}
+fn g(a: bool) -> bool { a }
+"#,
+        );
+        check_fix(
+            r#"
+fn f(a: u8, b: bool) -> bool {
+    (a > 1 || b) !$0= true
+}
+"#,
+            r#"
+fn f(a: u8, b: bool) -> bool {
+    !(a > 1 || b)
+}
+"#,
+        );
+        check_fix(
+            r#"
+fn f(a: bool) -> bool {
+    a as bool =$0= false
+}
+"#,
+            r#"
+fn f(a: bool) -> bool {
+    !(a as bool)
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use hir::{db::ExpandDatabase, diagnostics::CloneOnCopy};
+use ide_db::{assists::Assist, source_change::SourceChange};
+use syntax::{ast, AstNode, TextRange};
+use text_edit::TextEdit;
+
+use crate::{adjusted_display_range, fix, Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+// Diagnostic: clone-on-copy
+//
+// This diagnostic is triggered when `.clone()` is called on a value whose type implements `Copy`.
+pub(crate) fn clone_on_copy(ctx: &DiagnosticsContext<'_>, d: &CloneOnCopy) -> Option<Diagnostic> {
+    if d.method_call_expr.file_id.macro_file().is_some() {
+        // FIXME: Our infra can't handle allow from within macro expansions rn
+        return None;
+    }
+
+    let display_range = adjusted_display_range(ctx, d.method_call_expr, &|method_call| {
+        Some(TextRange::new(
+            method_call.dot_token()?.text_range().start(),
+            method_call.syntax().text_range().end(),
+        ))
+    });
+    Some(
+        Diagnostic::new(
+            DiagnosticCode::Clippy("clone_on_copy"),
+            "using `clone` on a type that implements `Copy`",
+            display_range,
+        )
+        .with_fixes(fixes(ctx, d)),
+    )
+}
+
+fn fixes(ctx: &DiagnosticsContext<'_>, d: &CloneOnCopy) -> Option<Vec<Assist>> {
+    let root = ctx.sema.db.parse_or_expand(d.method_call_expr.file_id);
+    let method_call: ast::MethodCallExpr = d.method_call_expr.value.to_node(&root);
+    let receiver = method_call.receiver()?;
+
+    let range = method_call.syntax().text_range();
+    let removed_range = TextRange::new(receiver.syntax().text_range().end(), range.end());
+    let edit = TextEdit::delete(removed_range);
+    let source_change = SourceChange::from_text_edit(d.method_call_expr.file_id.file_id()?, edit);
+
+    Some(vec![fix("remove_clone", "Remove redundant `.clone()`", source_change, range)])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_diagnostics, check_fix};
+
+    #[test]
+    fn clone_on_copy() {
+        check_diagnostics(
+            r#"
+//- minicore: copy, clone, builtin_impls, derive
+#[derive(Clone, Copy)]
+struct Foo;
+fn foo(x: i32, f: Foo) {
+    let _ = x.clone();
+           //^^^^^^^^ 💡 weak: using `clone` on a type that implements `Copy`
+    let _ = f.clone();
+           //^^^^^^^^ 💡 weak: using `clone` on a type that implements `Copy`
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn clone_on_copy_generic() {
+        check_diagnostics(
+            r#"
+//- minicore: copy, clone, builtin_impls
+fn foo<T: Copy, U: Clone>(t: T, u: U) {
+    let _ = t.clone();
+           //^^^^^^^^ 💡 weak: using `clone` on a type that implements `Copy`
+    let _ = u.clone();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_clone_on_copy_for_non_copy_or_deref() {
+        check_diagnostics(
+            r#"
+//- minicore: copy, clone, builtin_impls, derive
+#[derive(Clone)]
+struct Foo;
+fn foo(f: Foo, r: &i32) {
+    let _ = f.clone();
+    let _: i32 = r.clone();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_clone_on_copy_in_macro() {
+        check_diagnostics(
+            r#"
+//- minicore: copy, clone, builtin_impls
+macro_rules! m {
+    ($e:expr) => { $e.clone() };
+}
+fn foo(x: i32) {
+    let _ = m!(x);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn fix_clone_on_copy() {
+        check_fix(
+            r#"
+//- minicore: copy, clone, builtin_impls
+fn foo(x: i32) {
+    let _ = x.clo$0ne();
+}
+"#,
+            r#"
+fn foo(x: i32) {
+    let _ = x;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn fix_clone_on_copy_method_chain() {
+        check_fix(
+            r#"
+//- minicore: copy, clone, builtin_impls
+struct S(u8);
+impl S {
+    fn get(&self) -> (u8, u32) { (self.0, 0) }
+}
+fn foo(s: S) {
+    let _ = s.get().1.clone$0();
+}
+"#,
+            r#"
+struct S(u8);
+impl S {
+    fn get(&self) -> (u8, u32) { (self.0, 0) }
+}
+fn foo(s: S) {
+    let _ = s.get().1;
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use hir::{AsAssocItem, AssocItemContainer, CallableKind, InFile, PathResolution, Semantics};
+use ide_db::{
+    base_db::{FileId, FileRange},
+    source_change::SourceChange,
+    RootDatabase,
+};
+use syntax::{
+    ast::{self, HasAttrs, HasName},
+    match_ast, AstNode, SyntaxNode, SyntaxNodePtr, WalkEvent,
+};
+use text_edit::TextEdit;
+
+use crate::{fix, Diagnostic, DiagnosticCode, DiagnosticsConfig, Severity};
+
+// Diagnostic: could-be-const-fn
+//
+// This diagnostic is triggered for functions that could be declared as `const fn`, as their
+// bodies only consist of operations that can be evaluated at compile time.
+//
+// The check is conservative: every called function has to be a `const fn` itself, and trait
+// methods, operator overloading, closures, macros, loops over iterators and mutable references
+// aren't allowed.
+//
+// This is a style lint, it is only emitted if style lints are enabled.
+pub(crate) fn could_be_const_fn(
+    sema: &Semantics<'_, RootDatabase>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    node: &SyntaxNode,
+    config: &DiagnosticsConfig,
+) -> Option<()> {
+    if !config.style_lints {
+        return None;
+    }
+    let fn_ = ast::Fn::cast(node.clone())?;
+    if fn_.const_token().is_some()
+        || fn_.async_token().is_some()
+        || fn_.unsafe_token().is_some()
+        || fn_.abi().is_some()
+        || fn_.attrs().any(|attr| !is_harmless_attr(&attr))
+    {
+        return None;
+    }
+    let name = fn_.name()?;
+    let body = fn_.body()?;
+    let db = sema.db;
+    let def = sema.to_def(&fn_)?;
+    if def.is_main(db) || is_trait_fn(db, def) {
+        return None;
+    }
+
+    // Values that are dropped at the end of the function might have a destructor that can't be
+    // run at compile time.
+    let params_ok = def.assoc_fn_params(db).iter().all(|param| {
+        let ty = param.ty();
+        ty.is_copy(db) && !ty.is_mutable_reference()
+    });
+    if !params_ok {
+        return None;
+    }
+
+    let mut preorder = body.syntax().preorder();
+    while let Some(event) = preorder.next() {
+        let WalkEvent::Enter(node) = event else { continue };
+        if node != *body.syntax() && ast::Item::can_cast(node.kind()) {
+            preorder.skip_subtree();
+            continue;
+        }
+        if !is_const_compatible(sema, &node) {
+            return None;
+        }
+    }
+
+    let fn_token = fn_.fn_token()?;
+    let edit = TextEdit::insert(fn_token.text_range().start(), "const ".to_owned());
+    acc.push(
+        Diagnostic::new(
+            DiagnosticCode::Ra("could-be-const-fn", Severity::WeakWarning),
+            format!("function `{name}` could be `const fn`"),
+            FileRange { file_id, range: name.syntax().text_range() },
+        )
+        .with_main_node(InFile::new(file_id.into(), SyntaxNodePtr::new(node)))
+        .with_fixes(Some(vec![fix(
+            "make_const_fn",
+            "Make `const fn`",
+            SourceChange::from_text_edit(file_id, edit),
+            name.syntax().text_range(),
+        )])),
+    );
+
+    Some(())
+}
+
+fn is_const_compatible(sema: &Semantics<'_, RootDatabase>, node: &SyntaxNode) -> bool {
+    let db = sema.db;
+    let ty_of = |expr: Option<ast::Expr>| sema.type_of_expr(&expr?).map(|it| it.adjusted());
+    let is_primitive = |expr: Option<ast::Expr>| {
+        ty_of(expr).map_or(false, |ty| ty.is_int_or_uint() || ty.is_bool() || ty.is_char())
+    };
+    match_ast! {
+        match node {
+            ast::CallExpr(call) => {
+                let Some(callable) = ty_of(call.expr()).and_then(|ty| ty.as_callable(db)) else {
+                    return false;
+                };
+                match callable.kind() {
+                    CallableKind::Function(it) => is_const_fn(db, it),
+                    CallableKind::TupleStruct(_) | CallableKind::TupleEnumVariant(_) => true,
+                    CallableKind::Closure | CallableKind::FnPtr | CallableKind::Other => false,
+                }
+            },
+            ast::MethodCallExpr(call) => {
+                sema.resolve_method_call(&call).map_or(false, |it| is_const_fn(db, it))
+            },
+            // Operators on other types are calls to trait methods.
+            ast::BinExpr(expr) => match expr.op_kind() {
+                Some(ast::BinaryOp::Assignment { op: None }) => true,
+                Some(_) => is_primitive(expr.lhs()) && is_primitive(expr.rhs()),
+                None => false,
+            },
+            ast::PrefixExpr(expr) => match expr.op_kind() {
+                Some(ast::UnaryOp::Deref) => ty_of(expr.expr()).map_or(false, |ty| ty.is_reference()),
+                Some(_) => is_primitive(expr.expr()),
+                None => false,
+            },
+            ast::IndexExpr(expr) => ty_of(expr.base()).map_or(false, |ty| {
+                let ty = ty.strip_references();
+                ty.is_array() || ty.as_slice().is_some()
+            }),
+            ast::CastExpr(expr) => {
+                let target = expr.ty().and_then(|ty| sema.resolve_type(&ty));
+                is_primitive(expr.expr()) && target.map_or(false, |ty| ty.is_int_or_uint())
+            },
+            ast::RefExpr(expr) => expr.mut_token().is_none() && expr.raw_token().is_none(),
+            ast::PathExpr(expr) => match expr.path().and_then(|path| sema.resolve_path(&path)) {
+                Some(PathResolution::Def(hir::ModuleDef::Static(_))) => false,
+                Some(_) => true,
+                None => false,
+            },
+            ast::LetStmt(stmt) => stmt
+                .pat()
+                .and_then(|pat| sema.type_of_pat(&pat))
+                .map_or(false, |ty| ty.original.is_copy(db)),
+            ast::ForExpr(_) => false,
+            ast::TryExpr(_) => false,
+            ast::AwaitExpr(_) => false,
+            ast::ClosureExpr(_) => false,
+            ast::MacroCall(_) => false,
+            ast::MacroExpr(_) => false,
+            ast::BlockExpr(block) => {
+                block.unsafe_token().is_none()
+                    && block.async_token().is_none()
+                    && block.try_token().is_none()
+            },
+            _ => true,
+        }
+    }
+}
+
+/// Whether the attribute doesn't conflict with `const`, unlike e.g. `#[test]` or `#[no_mangle]`.
+fn is_harmless_attr(attr: &ast::Attr) -> bool {
+    let name = attr.simple_name();
+    matches!(
+        name.as_deref(),
+        Some("inline" | "must_use" | "doc" | "allow" | "warn" | "deny" | "expect" | "cfg")
+    )
+}
+
+fn is_const_fn(db: &RootDatabase, func: hir::Function) -> bool {
+    func.is_const(db) && !is_trait_fn(db, func) && !func.is_unsafe_to_call(db)
+}
+
+/// Trait methods and their implementations can't be `const`.
+fn is_trait_fn(db: &RootDatabase, func: hir::Function) -> bool {
+    match func.as_assoc_item(db).map(|it| it.container(db)) {
+        Some(AssocItemContainer::Trait(_)) => true,
+        Some(AssocItemContainer::Impl(impl_)) => impl_.trait_(db).is_some(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{check_diagnostics_with_config, check_fix},
+        DiagnosticsConfig,
+    };
+
+    fn check_diagnostics(ra_fixture: &str) {
+        check_diagnostics_with_config(DiagnosticsConfig::test_sample(), ra_fixture);
+    }
+
+    #[test]
+    fn const_compatible_bodies() {
+        check_diagnostics(
+            r#"
+//- minicore: copy
+struct Point(i32, i32);
+const ORIGIN: Point = Point(0, 0);
+const fn twice(x: i32) -> i32 { x * 2 }
+
+fn add(a: i32, b: i32) -> i32 { a + b }
+ //^^^ 💡 weak: function `add` could be `const fn`
+fn point(x: i32) -> Point {
+ //^^^^^ 💡 weak: function `point` could be `const fn`
+    let mut y = twice(x);
+    if y > 10 {
+        y -= 1;
+    }
+    Point(x, y)
+}
+fn first(values: &[u8; 4]) -> u8 { values[0] as u8 }
+ //^^^^^ 💡 weak: function `first` could be `const fn`
+fn origin() -> &'static Point { &ORIGIN }
+ //^^^^^^ 💡 weak: function `origin` could be `const fn`
+"#,
+        );
+    }
+
+    #[test]
+    fn no_diagnostic_for_non_const_operations() {
+        check_diagnostics(
+            r#"
+//- minicore: add, copy, fn
+static COUNTER: u32 = 0;
+struct Meters(u32);
+impl core::ops::Add for Meters {
+    type Output = Meters;
+    fn add(self, other: Meters) -> Meters { Meters(self.0 + other.0) }
+}
+fn not_const(x: u32) -> u32 { x + COUNTER }
+
+fn calls_non_const(x: u32) -> u32 { not_const(x) }
+fn overloaded_op(a: Meters, b: Meters) -> Meters { a + b }
+fn reads_static() -> u32 { COUNTER }
+fn closure(x: u32) -> u32 { (|| x)() }
+fn mut_ref(x: &mut u32) { *x = 1; }
+fn floats(x: f32) -> f32 { x * 2.0 }
+fn unsafe_block() -> u32 { unsafe { 0 } }
+"#,
+        );
+    }
+
+    #[test]
+    fn no_diagnostic_for_special_fns() {
+        check_diagnostics(
+            r#"
+//- minicore: copy
+trait Tr {
+    fn provided() -> u32 { 0 }
+}
+struct S;
+impl Tr for S {
+    fn provided() -> u32 { 1 }
+}
+impl S {
+    fn inherent() -> u32 { 2 }
+     //^^^^^^^^ 💡 weak: function `inherent` could be `const fn`
+}
+const fn already() {}
+async fn asynchronous() {}
+unsafe fn not_safe() {}
+#[inline]
+fn inlined() {}
+ //^^^^^^^ 💡 weak: function `inlined` could be `const fn`
+#[no_mangle]
+fn attributed() {}
+fn main() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_diagnostic_without_style_lints() {
+        let mut config = DiagnosticsConfig::test_sample();
+        config.style_lints = false;
+        check_diagnostics_with_config(
+            config,
+            r#"
+fn add(a: i32, b: i32) -> i32 { a + b }
+"#,
+        );
+    }
+
+    #[test]
+    fn make_const_fn() {
+        check_fix(
+            r#"
+//- minicore: copy
+pub fn add$0(a: i32, b: i32) -> i32 { a + b }
+"#,
+            r#"
+pub const fn add(a: i32, b: i32) -> i32 { a + b }
+"#,
+        );
+    }
+}
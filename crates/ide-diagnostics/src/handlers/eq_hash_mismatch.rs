@@ -0,0 +1,539 @@
+use hir::{HasSource, InFile, ModuleDef, Semantics};
+use ide_db::{
+    assists::Assist,
+    base_db::{FileId, FileRange},
+    famous_defs::FamousDefs,
+    helpers::mod_path_to_ast,
+    source_change::SourceChange,
+    syntax_helpers::node_ext::insert_outer_attr,
+    RootDatabase,
+};
+use syntax::{
+    ast::{self, edit::IndentLevel, HasAttrs, HasGenericParams, HasName},
+    match_ast, AstNode, SyntaxKind, SyntaxNode, SyntaxNodePtr, TextRange, T,
+};
+use text_edit::TextEdit;
+
+use crate::{fix, Diagnostic, DiagnosticCode, DiagnosticsConfig, Severity};
+
+// Diagnostic: eq-hash-mismatch
+//
+// This diagnostic is triggered when a type is used as a `HashMap` or `HashSet` key although only
+// one of its `PartialEq` and `Hash` impls is written by hand. Values that compare equal must have
+// the same hash, which a derived impl can't guarantee when the other impl is custom.
+//
+// Both collections in written types and ones created in a `let` with an inferred type, as in
+// `let set = HashSet::new();`, are checked.
+pub(crate) fn eq_hash_mismatch(
+    sema: &Semantics<'_, RootDatabase>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    node: &SyntaxNode,
+    config: &DiagnosticsConfig,
+) -> Option<()> {
+    // The type of the collection, and the node the diagnostic is reported on.
+    let (collection, key) = match_ast! {
+        match node {
+            ast::PathType(path_type) => {
+                let collection = sema.resolve_type(&path_type.clone().into())?;
+                // Point at the key type if it's written out.
+                let key = path_type
+                    .path()?
+                    .segment()?
+                    .generic_arg_list()
+                    .and_then(|it| {
+                        it.generic_args().find_map(|arg| match arg {
+                            ast::GenericArg::TypeArg(it) => it.ty(),
+                            _ => None,
+                        })
+                    })
+                    .map_or_else(|| path_type.syntax().clone(), |it| it.syntax().clone());
+                (collection, key)
+            },
+            ast::LetStmt(let_stmt) => {
+                if let_stmt.ty().is_some() {
+                    return None;
+                }
+                let ast::Expr::CallExpr(init) = let_stmt.initializer()? else { return None };
+                (sema.type_of_expr(&init.clone().into())?.original, init.syntax().clone())
+            },
+            _ => return None,
+        }
+    };
+
+    let db = sema.db;
+    let famous_defs = FamousDefs(sema, sema.scope(node)?.krate());
+    let hash_collections =
+        [famous_defs.std_collections_HashMap(), famous_defs.std_collections_HashSet()];
+    match collection.as_adt() {
+        Some(hir::Adt::Struct(it)) if hash_collections.contains(&Some(it)) => (),
+        _ => return None,
+    }
+    let ty = collection.type_arguments().next()?;
+    let adt = ty.as_adt()?;
+    if !adt.module(db).krate().origin(db).is_local() {
+        return None;
+    }
+    let eq_trait = famous_defs.core_cmp_PartialEq()?;
+    let hash_trait = famous_defs.core_hash_Hash()?;
+    let eq = impl_kind(db, &ty, eq_trait);
+    let hash = impl_kind(db, &ty, hash_trait);
+
+    let adt_name = adt.name(db);
+    let adt_name = adt_name.display(db);
+    let (message, missing) = match (eq, hash) {
+        (ImplKind::Manual, ImplKind::Derived) => (
+            format!("`{adt_name}` implements `PartialEq` manually, but derives `Hash`"),
+            Missing::Hash,
+        ),
+        (ImplKind::Manual, ImplKind::Missing) => (
+            format!("`{adt_name}` is used as a hash key, but doesn't implement `Hash`"),
+            Missing::Hash,
+        ),
+        (ImplKind::Derived, ImplKind::Manual) => (
+            format!("`{adt_name}` implements `Hash` manually, but derives `PartialEq`"),
+            Missing::PartialEq,
+        ),
+        _ => return None,
+    };
+
+    let fixes = (|| {
+        let source = adt.source(db)?;
+        let adt_file = source.file_id.file_id()?;
+        let adt_node = source.value;
+        let module = adt.module(db);
+        let mut fixes = Vec::new();
+        if let Some(impl_text) = stub_impl(db, &adt_node, module, missing, hash_trait, config) {
+            let mut builder = TextEdit::builder();
+            if let Some(derive) = derived_entry(&adt_node, missing.trait_name()) {
+                builder.delete(derive);
+            }
+            let indent = IndentLevel::from_node(adt_node.syntax());
+            builder
+                .insert(adt_node.syntax().text_range().end(), format!("\n\n{indent}{impl_text}"));
+            fixes.push(fix(
+                "add_stub_impl",
+                &format!("Implement `{}` manually", missing.trait_name()),
+                SourceChange::from_text_edit(adt_file, builder.finish()),
+                key.text_range(),
+            ));
+        }
+        if hash == ImplKind::Missing {
+            fixes.push(fix(
+                "add_derive_hash",
+                "Add `#[derive(Hash)]`",
+                SourceChange::from_text_edit(adt_file, add_derive(&adt_node, "Hash")?),
+                key.text_range(),
+            ));
+        }
+        Some(fixes)
+    })();
+
+    acc.push(
+        Diagnostic::new(
+            DiagnosticCode::Ra("eq-hash-mismatch", Severity::Warning),
+            message,
+            FileRange { file_id, range: key.text_range() },
+        )
+        .with_main_node(InFile::new(file_id.into(), SyntaxNodePtr::new(&key)))
+        .with_fixes(fixes.filter(|it: &Vec<Assist>| !it.is_empty())),
+    );
+
+    Some(())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ImplKind {
+    Manual,
+    Derived,
+    Missing,
+}
+
+fn impl_kind(db: &RootDatabase, ty: &hir::Type, trait_: hir::Trait) -> ImplKind {
+    let impl_ = hir::Impl::all_for_type(db, ty.clone())
+        .into_iter()
+        .find(|it| it.trait_(db) == Some(trait_));
+    match impl_ {
+        Some(it) if it.as_builtin_derive_path(db).is_some() => ImplKind::Derived,
+        Some(_) => ImplKind::Manual,
+        None => ImplKind::Missing,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Missing {
+    PartialEq,
+    Hash,
+}
+
+impl Missing {
+    fn trait_name(self) -> &'static str {
+        match self {
+            Missing::PartialEq => "PartialEq",
+            Missing::Hash => "Hash",
+        }
+    }
+}
+
+/// Renders an impl of the missing trait with a `todo!()` body, for types without generics.
+fn stub_impl(
+    db: &RootDatabase,
+    adt: &ast::Adt,
+    module: hir::Module,
+    missing: Missing,
+    hash_trait: hir::Trait,
+    config: &DiagnosticsConfig,
+) -> Option<String> {
+    if adt.generic_param_list().is_some() {
+        return None;
+    }
+    let name = adt.name()?;
+    let indent = IndentLevel::from_node(adt.syntax());
+    let body = match missing {
+        Missing::Hash => {
+            let hash_module = ModuleDef::Module(hash_trait.module(db));
+            let path = mod_path_to_ast(&module.find_use_path(
+                db,
+                hash_module,
+                config.prefer_no_std,
+                config.prefer_prelude,
+            )?);
+            format!("impl {path}::Hash for {name} {{\n{indent}    fn hash<H: {path}::Hasher>(&self, state: &mut H) {{\n")
+        }
+        Missing::PartialEq => format!(
+            "impl PartialEq for {name} {{\n{indent}    fn eq(&self, other: &Self) -> bool {{\n"
+        ),
+    };
+    Some(format!("{body}{indent}        todo!()\n{indent}    }}\n{indent}}}"))
+}
+
+/// Finds the range to delete to remove `trait_name` from the `derive` attributes of `adt`.
+fn derived_entry(adt: &ast::Adt, trait_name: &str) -> Option<TextRange> {
+    adt.attrs().filter(|attr| attr.simple_name().as_deref() == Some("derive")).find_map(|attr| {
+        let tt = attr.token_tree()?;
+        let tokens: Vec<_> = tt
+            .syntax()
+            .children_with_tokens()
+            .filter_map(|it| it.into_token())
+            .filter(|it| !matches!(it.kind(), T!['('] | T![')']))
+            .collect();
+        let entries: Vec<_> = tokens
+            .split(|it| it.kind() == T![,])
+            .map(|entry| {
+                let mut entry = entry.iter().filter(|it| it.kind() != SyntaxKind::WHITESPACE);
+                let first = entry.next()?;
+                let last = entry.last().unwrap_or(first);
+                Some((first.text_range().cover(last.text_range()), last.text() == trait_name))
+            })
+            .collect::<Option<_>>()?;
+        let idx = entries.iter().position(|(_, found)| *found)?;
+        let range = entries[idx].0;
+        if entries.len() == 1 {
+            let attr_range = attr.syntax().text_range();
+            let end = attr
+                .syntax()
+                .next_sibling_or_token()
+                .filter(|it| it.kind() == SyntaxKind::WHITESPACE)
+                .map_or(attr_range.end(), |it| it.text_range().end());
+            return Some(TextRange::new(attr_range.start(), end));
+        }
+        Some(match entries.get(idx + 1) {
+            Some((next, _)) => TextRange::new(range.start(), next.start()),
+            None => TextRange::new(entries[idx - 1].0.end(), range.end()),
+        })
+    })
+}
+
+fn add_derive(adt: &ast::Adt, trait_name: &str) -> Option<TextEdit> {
+    let derive = adt.attrs().find(|attr| attr.simple_name().as_deref() == Some("derive"));
+    if let Some(tt) = derive.and_then(|it| it.token_tree()) {
+        let r_paren = tt.right_delimiter_token()?;
+        let has_entries = tt
+            .syntax()
+            .children_with_tokens()
+            .any(|it| !matches!(it.kind(), T!['('] | T![')'] | SyntaxKind::WHITESPACE));
+        let text = if has_entries { format!(", {trait_name}") } else { trait_name.to_owned() };
+        return Some(TextEdit::insert(r_paren.text_range().start(), text));
+    }
+    let (offset, attr) = insert_outer_attr(adt.syntax(), &format!("derive({trait_name})"))?;
+    Some(TextEdit::insert(offset, attr))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_diagnostics, check_fix, check_fixes};
+
+    #[test]
+    fn manual_eq_with_derived_hash() {
+        check_diagnostics(
+            r#"
+//- minicore: eq, hash, derive
+//- /main.rs crate:main deps:std
+use core::hash::Hash;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Hash)]
+struct Name(u32);
+impl PartialEq for Name {
+    fn eq(&self, _other: &Self) -> bool { true }
+}
+
+#[derive(PartialEq)]
+struct Id(u32);
+impl core::hash::Hash for Id {
+    fn hash<H: core::hash::Hasher>(&self, _state: &mut H) {}
+}
+
+#[derive(PartialEq, Hash)]
+struct Consistent(u32);
+
+fn f(_: HashMap<Name, u32>, _: HashSet<Id>, _: HashSet<Consistent>) {}
+              //^^^^ 💡 warn: `Name` implements `PartialEq` manually, but derives `Hash`
+                                     //^^ 💡 warn: `Id` implements `Hash` manually, but derives `PartialEq`
+//- /std.rs crate:std
+pub mod prelude {
+    pub mod rust_2021 { pub use core::prelude::rust_2021::*; }
+}
+pub mod collections {
+    pub struct HashMap<K, V>(K, V);
+    pub struct HashSet<T>(T);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn manual_eq_without_hash() {
+        check_diagnostics(
+            r#"
+//- minicore: eq, hash, derive
+//- /main.rs crate:main deps:std
+use core::hash::Hash;
+use std::collections::HashSet;
+
+struct Name(u32);
+impl PartialEq for Name {
+    fn eq(&self, _other: &Self) -> bool { true }
+}
+#[derive(PartialEq)]
+struct Derived(u32);
+
+fn f(_: HashSet<Name>, _: HashSet<Derived>) {}
+              //^^^^ 💡 warn: `Name` is used as a hash key, but doesn't implement `Hash`
+//- /std.rs crate:std
+pub mod prelude {
+    pub mod rust_2021 { pub use core::prelude::rust_2021::*; }
+}
+pub mod collections {
+    pub struct HashMap<K, V>(K, V);
+    pub struct HashSet<T>(T);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_derived_hash() {
+        check_fix(
+            r#"
+//- minicore: eq, hash, derive, clone
+//- /main.rs crate:main deps:std
+use core::hash::Hash;
+use std::collections::HashSet;
+
+#[derive(Clone, Hash, Eq)]
+struct Name(u32);
+impl PartialEq for Name {
+    fn eq(&self, _other: &Self) -> bool { true }
+}
+
+fn f(_: HashSet<Name$0>) {}
+//- /std.rs crate:std
+pub mod prelude {
+    pub mod rust_2021 { pub use core::prelude::rust_2021::*; }
+}
+pub mod collections {
+    pub struct HashMap<K, V>(K, V);
+    pub struct HashSet<T>(T);
+}
+"#,
+            r#"
+use core::hash::Hash;
+use std::collections::HashSet;
+
+#[derive(Clone, Eq)]
+struct Name(u32);
+
+impl core::hash::Hash for Name {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        todo!()
+    }
+}
+impl PartialEq for Name {
+    fn eq(&self, _other: &Self) -> bool { true }
+}
+
+fn f(_: HashSet<Name>) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_derived_partial_eq() {
+        check_fix(
+            r#"
+//- minicore: eq, hash, derive
+//- /main.rs crate:main deps:std
+use core::hash::Hash;
+use std::collections::HashSet;
+
+#[derive(PartialEq)]
+struct Id(u32);
+impl core::hash::Hash for Id {
+    fn hash<H: core::hash::Hasher>(&self, _state: &mut H) {}
+}
+
+fn f(_: HashSet<$0Id>) {}
+//- /std.rs crate:std
+pub mod prelude {
+    pub mod rust_2021 { pub use core::prelude::rust_2021::*; }
+}
+pub mod collections {
+    pub struct HashMap<K, V>(K, V);
+    pub struct HashSet<T>(T);
+}
+"#,
+            r#"
+use core::hash::Hash;
+use std::collections::HashSet;
+
+struct Id(u32);
+
+impl PartialEq for Id {
+    fn eq(&self, other: &Self) -> bool {
+        todo!()
+    }
+}
+impl core::hash::Hash for Id {
+    fn hash<H: core::hash::Hasher>(&self, _state: &mut H) {}
+}
+
+fn f(_: HashSet<Id>) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_missing_hash() {
+        check_fixes(
+            r#"
+//- minicore: eq, hash, derive
+//- /main.rs crate:main deps:std
+use core::hash::Hash;
+use std::collections::HashSet;
+
+/// A name.
+#[repr(transparent)]
+struct Name(u32);
+impl PartialEq for Name {
+    fn eq(&self, _other: &Self) -> bool { true }
+}
+
+fn f(_: HashSet<Name$0>) {}
+//- /std.rs crate:std
+pub mod prelude {
+    pub mod rust_2021 { pub use core::prelude::rust_2021::*; }
+}
+pub mod collections {
+    pub struct HashMap<K, V>(K, V);
+    pub struct HashSet<T>(T);
+}
+"#,
+            vec![
+                r#"
+use core::hash::Hash;
+use std::collections::HashSet;
+
+/// A name.
+#[repr(transparent)]
+struct Name(u32);
+
+impl core::hash::Hash for Name {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        todo!()
+    }
+}
+impl PartialEq for Name {
+    fn eq(&self, _other: &Self) -> bool { true }
+}
+
+fn f(_: HashSet<Name>) {}
+"#,
+                r#"
+use core::hash::Hash;
+use std::collections::HashSet;
+
+/// A name.
+#[repr(transparent)]
+#[derive(Hash)]
+struct Name(u32);
+impl PartialEq for Name {
+    fn eq(&self, _other: &Self) -> bool { true }
+}
+
+fn f(_: HashSet<Name>) {}
+"#,
+            ],
+        );
+    }
+
+    #[test]
+    fn inferred_key_type() {
+        check_diagnostics(
+            r#"
+//- minicore: eq, hash, derive
+//- /main.rs crate:main deps:std
+use std::collections::HashSet;
+
+struct Name(u32);
+impl PartialEq for Name {
+    fn eq(&self, _other: &Self) -> bool { true }
+}
+
+fn f() {
+    let mut set = HashSet::new();
+                //^^^^^^^^^^^^^^ 💡 warn: `Name` is used as a hash key, but doesn't implement `Hash`
+    set.insert(Name(0));
+}
+//- /std.rs crate:std
+pub mod prelude {
+    pub mod rust_2021 { pub use core::prelude::rust_2021::*; }
+}
+pub mod collections {
+    pub struct HashSet<T>(T);
+    impl<T> HashSet<T> {
+        pub fn new() -> Self { loop {} }
+        pub fn insert(&mut self, _value: T) {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn ignores_other_collections() {
+        check_diagnostics(
+            r#"
+//- minicore: eq, hash, derive
+struct HashSet<T>(T);
+
+struct Name(u32);
+impl PartialEq for Name {
+    fn eq(&self, _other: &Self) -> bool { true }
+}
+
+fn f(_: HashSet<Name>) {}
+"#,
+        );
+    }
+}
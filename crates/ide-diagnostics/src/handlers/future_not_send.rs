@@ -0,0 +1,124 @@
+use hir::{db::ExpandDatabase, diagnostics::NonSendHeldAcrossAwait};
+use syntax::{
+    ast::{self, HasName},
+    AstNode, TextRange,
+};
+
+use crate::{adjusted_display_range, Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+// Diagnostic: future-not-send
+//
+// This diagnostic is triggered when a value that is not `Send` is still alive at an `.await`,
+// which makes the future of the surrounding `async fn` or async block non-`Send`. Both the
+// `.await` and the binding holding the value are highlighted.
+pub(crate) fn future_not_send(
+    ctx: &DiagnosticsContext<'_>,
+    d: &NonSendHeldAcrossAwait,
+) -> Vec<Diagnostic> {
+    if d.await_expr.file_id.macro_file().is_some() {
+        // FIXME: Our infra can't handle allow from within macro expansions rn
+        return Vec::new();
+    }
+    let root = ctx.sema.db.parse_or_expand(d.binding.file_id);
+    let Some(name) = d.binding.value.to_node(&root).name() else { return Vec::new() };
+    let ty = &d.ty;
+
+    let await_range = adjusted_display_range(ctx, d.await_expr, &|it: ast::AwaitExpr| {
+        Some(TextRange::new(
+            it.dot_token()?.text_range().start(),
+            it.await_token()?.text_range().end(),
+        ))
+    });
+    let binding_range =
+        adjusted_display_range(ctx, d.binding, &|it| Some(it.name()?.syntax().text_range()));
+    vec![
+        Diagnostic::new(
+            DiagnosticCode::Clippy("future_not_send"),
+            format!("future is not `Send` as `{name}` of type `{ty}` is held across this await"),
+            await_range,
+        )
+        .experimental(),
+        Diagnostic::new(
+            DiagnosticCode::Clippy("future_not_send"),
+            format!("`{name}` of non-`Send` type `{ty}` is held across an await"),
+            binding_range,
+        )
+        .experimental(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::check_diagnostics;
+
+    #[test]
+    fn held_across_await() {
+        check_diagnostics(
+            r#"
+//- minicore: future, send, copy
+struct NotSend(*const u8);
+fn make() -> NotSend { loop {} }
+fn use_it(_: &NotSend) {}
+async fn other() {}
+
+async fn dropped_at_end() {
+    let _guard = make();
+      //^^^^^^ weak: `_guard` of non-`Send` type `NotSend` is held across an await
+    other().await;
+         //^^^^^^ weak: future is not `Send` as `_guard` of type `NotSend` is held across this await
+}
+
+fn spawned() {
+    let _ = async {
+        let value = make();
+          //^^^^^ weak: `value` of non-`Send` type `NotSend` is held across an await
+        use_it(&value);
+        other().await;
+             //^^^^^^ weak: future is not `Send` as `value` of type `NotSend` is held across this await
+        use_it(&value);
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_held_across_await() {
+        check_diagnostics(
+            r#"
+//- minicore: future, send, copy
+struct NotSend(*const u8);
+fn make() -> NotSend { loop {} }
+fn use_it(_: &NotSend) {}
+fn consume(_: NotSend) {}
+async fn other() {}
+
+async fn moved_before() {
+    let value = make();
+    consume(value);
+    other().await;
+}
+
+async fn scoped() {
+    {
+        let value = make();
+        use_it(&value);
+    }
+    other().await;
+}
+
+async fn send_value() {
+    let value = 1u32;
+    other().await;
+    let _ = value;
+}
+
+async fn awaited_elsewhere() {
+    let value = make();
+    let _ = async { other().await };
+    consume(value);
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use hir::InFile;
+use ide_db::{
+    assists::Assist,
+    base_db::{FileId, FileRange},
+    source_change::SourceChange,
+    syntax_helpers::suggest_name::for_impl_trait_as_generic,
+};
+use syntax::{
+    ast::{self, HasGenericParams, HasName, HasVisibility},
+    AstNode, Direction, SyntaxNode, SyntaxNodePtr, T,
+};
+use text_edit::TextEdit;
+
+use crate::{fix, is_trait_impl_fn, Diagnostic, DiagnosticCode, DiagnosticsConfig};
+
+// Diagnostic: impl-trait-in-params
+//
+// This diagnostic is triggered for `impl Trait` parameters of public functions. Callers can't
+// name the type of such a parameter with turbofish syntax, so a named generic parameter is more
+// flexible.
+//
+// This is a style lint, it is only emitted if style lints are enabled.
+pub(crate) fn impl_trait_in_params(
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    node: &SyntaxNode,
+    config: &DiagnosticsConfig,
+) -> Option<()> {
+    if !config.style_lints {
+        return None;
+    }
+    let fn_ = ast::Fn::cast(node.clone())?;
+    if fn_.visibility()?.syntax().text() != "pub" {
+        return None;
+    }
+    if is_trait_impl_fn(&fn_) {
+        return None;
+    }
+
+    for param in fn_.param_list()?.params() {
+        let Some(ty) = param.ty() else { continue };
+        for impl_trait in ty.syntax().descendants().filter_map(ast::ImplTraitType::cast) {
+            acc.push(
+                Diagnostic::new(
+                    DiagnosticCode::Clippy("impl_trait_in_params"),
+                    "`impl Trait` used as a function parameter",
+                    FileRange { file_id, range: impl_trait.syntax().text_range() },
+                )
+                .with_main_node(InFile::new(file_id.into(), SyntaxNodePtr::new(node)))
+                .with_fixes(named_generic_fix(file_id, &fn_, &impl_trait).map(|it| vec![it])),
+            );
+        }
+    }
+
+    Some(())
+}
+
+fn named_generic_fix(
+    file_id: FileId,
+    fn_: &ast::Fn,
+    impl_trait: &ast::ImplTraitType,
+) -> Option<Assist> {
+    let bounds = impl_trait.type_bound_list()?;
+    // `impl Trait` isn't allowed in the bounds of a generic parameter.
+    if bounds.syntax().descendants().any(|it| ast::ImplTraitType::can_cast(it.kind())) {
+        return None;
+    }
+
+    let name = for_impl_trait_as_generic(impl_trait);
+
+    let mut edit = TextEdit::builder();
+    edit.replace(impl_trait.syntax().text_range(), name.to_string());
+    let mut param = format!("{name}: {bounds}");
+    match fn_.generic_param_list() {
+        Some(generic_params) => {
+            let r_angle = generic_params.r_angle_token()?;
+            let ends_with_comma = r_angle
+                .siblings_with_tokens(Direction::Prev)
+                .skip(1)
+                .find(|it| !it.kind().is_trivia())
+                .map_or(false, |it| it.kind() == T![,]);
+            if generic_params.generic_params().next().is_some() && !ends_with_comma {
+                param.insert_str(0, ", ");
+            }
+            edit.insert(r_angle.text_range().start(), param);
+        }
+        None => edit.insert(fn_.name()?.syntax().text_range().end(), format!("<{param}>")),
+    }
+
+    let range = impl_trait.syntax().text_range();
+    Some(fix(
+        "replace_impl_trait_with_generic",
+        "Replace `impl Trait` with a named generic",
+        SourceChange::from_text_edit(file_id, edit.finish()),
+        range,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{check_diagnostics, check_diagnostics_with_config, check_fix},
+        DiagnosticsConfig,
+    };
+
+    #[test]
+    fn impl_trait_in_params() {
+        check_diagnostics(
+            r#"
+trait Foo {}
+trait Bar {}
+pub fn foo(_a: impl Foo, _b: &impl Bar, _c: u32) {}
+             //^^^^^^^^ 💡 weak: `impl Trait` used as a function parameter
+                            //^^^^^^^^ 💡 weak: `impl Trait` used as a function parameter
+"#,
+        );
+    }
+
+    #[test]
+    fn impl_trait_in_params_inherent_method() {
+        check_diagnostics(
+            r#"
+trait Foo {}
+pub struct S;
+impl S {
+    pub fn foo(&self, _a: impl Foo) {}
+                        //^^^^^^^^ 💡 weak: `impl Trait` used as a function parameter
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_impl_trait_in_params_for_private_fns_and_trait_impls() {
+        check_diagnostics(
+            r#"
+trait Foo {}
+pub trait Tr {
+    fn foo(&self, a: impl Foo);
+}
+pub struct S;
+impl Tr for S {
+    fn foo(&self, _a: impl Foo) {}
+}
+fn private(_a: impl Foo) {}
+pub(crate) fn crate_visible(_a: impl Foo) {}
+pub fn named<F: Foo>(a: F) -> impl Foo { a }
+"#,
+        );
+    }
+
+    #[test]
+    fn no_impl_trait_in_params_without_style_lints() {
+        let mut config = DiagnosticsConfig::test_sample();
+        config.style_lints = false;
+        check_diagnostics_with_config(
+            config,
+            r#"
+trait Foo {}
+pub fn foo(_a: impl Foo) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_with_named_generic() {
+        check_fix(
+            r#"
+trait Foo {}
+pub fn foo(_a: impl $0Foo, _b: u32) {}
+"#,
+            r#"
+trait Foo {}
+pub fn foo<F: Foo>(_a: F, _b: u32) {}
+"#,
+        );
+        check_fix(
+            r#"
+trait Foo {}
+pub struct F;
+pub fn foo<T>(_a: &T, _b: &impl $0Foo) -> F { F }
+"#,
+            r#"
+trait Foo {}
+pub struct F;
+pub fn foo<T, F0: Foo>(_a: &T, _b: &F0) -> F { F }
+"#,
+        );
+        check_fix(
+            r#"
+trait Foo {}
+pub struct S<F>(F);
+impl<F> S<F> {
+    pub fn foo(_a: impl $0Foo) {}
+}
+"#,
+            r#"
+trait Foo {}
+pub struct S<F>(F);
+impl<F> S<F> {
+    pub fn foo<F0: Foo>(_a: F0) {}
+}
+"#,
+        );
+    }
+}
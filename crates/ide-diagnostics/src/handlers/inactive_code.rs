@@ -1,4 +1,4 @@
-use cfg::DnfExpr;
+use cfg::{CfgAtom, DnfExpr};
 use stdx::format_to;
 
 use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
@@ -37,12 +37,36 @@ pub(crate) fn inactive_code(
     Some(res)
 }
 
+/// Returns the smallest set of cargo features that activates the inactive code, if enabling
+/// features alone is enough.
+pub(crate) fn enabling_features(d: &hir::InactiveCode) -> Option<Vec<String>> {
+    let features_only = |atoms: &[CfgAtom]| {
+        atoms
+            .iter()
+            .map(|atom| match atom {
+                CfgAtom::KeyValue { key, value } if key == "feature" => Some(value.to_string()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+    };
+    DnfExpr::new(d.cfg.clone())
+        .compute_enable_hints(&d.opts)
+        .filter(|diff| diff.disable().is_empty())
+        .filter_map(|diff| features_only(diff.enable()))
+        .filter(|features| !features.is_empty())
+        .min_by_key(|features| features.len())
+}
+
 #[cfg(test)]
 mod tests {
+    use ide_db::{base_db::SourceDatabaseExt, RootDatabase};
+    use test_fixture::WithFixture;
+
     use crate::{tests::check_diagnostics_with_config, DiagnosticsConfig};
 
     pub(crate) fn check(ra_fixture: &str) {
-        let config = DiagnosticsConfig::test_sample();
+        let mut config = DiagnosticsConfig::test_sample();
+        config.disabled.insert("could-be-const-fn".to_owned());
         check_diagnostics_with_config(config, ra_fixture)
     }
 
@@ -171,4 +195,54 @@ union FooBar {
 "#,
         );
     }
+
+    fn check_features(ra_fixture: &str, expected: &[(&str, &[&str])]) {
+        let (db, frange) = RootDatabase::with_range(ra_fixture);
+        let text = db.file_text(frange.file_id);
+        let actual: Vec<_> = crate::inactive_code_features(&db, frange)
+            .into_iter()
+            .map(|it| {
+                let range = std::ops::Range::<usize>::from(it.range.range);
+                (text[range][..8].to_owned(), it.krate, it.features)
+            })
+            .collect();
+        let expected: Vec<_> = expected
+            .iter()
+            .map(|&(text, features)| {
+                let features = features.iter().map(|&it| it.to_owned()).collect();
+                (text.to_owned(), "my_crate".to_owned(), features)
+            })
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn features_to_enable() {
+        check_features(
+            r#"
+//- /lib.rs crate:my_crate cfg:feature=enabled
+$0#[cfg(feature = "a")] fn a() {}
+#[cfg(all(feature = "b", feature = "c", feature = "enabled"))] fn bc() {}
+#[cfg(any(all(feature = "d", feature = "e"), feature = "f"))] fn f() {}
+#[cfg(not(feature = "enabled"))] fn disable() {}
+#[cfg(all(unix_like, feature = "g"))] fn not_a_feature() {}$0
+"#,
+            &[("#[cfg(fe", &["a"]), ("#[cfg(al", &["b", "c"]), ("#[cfg(an", &["f"])],
+        );
+    }
+
+    #[test]
+    fn features_to_enable_in_range() {
+        check_features(
+            r#"
+//- /lib.rs crate:my_crate
+#[cfg(feature = "a")] fn a() {}
+fn body() {
+    #[cfg(feature = "b")]
+    let $0x$0 = 0;
+}
+"#,
+            &[("#[cfg(fe", &["b"])],
+        );
+    }
 }
@@ -0,0 +1,152 @@
+use hir::{AsAssocItem, AssocItemContainer, InFile, ModuleDef, PathResolution, Semantics};
+use ide_db::{
+    base_db::{FileId, FileRange},
+    famous_defs::FamousDefs,
+    FxHashSet, RootDatabase,
+};
+use syntax::{
+    ast::{self, HasArgList, HasName},
+    AstNode, SyntaxNode, SyntaxNodePtr,
+};
+
+use crate::{Diagnostic, DiagnosticCode, Severity};
+
+// Diagnostic: manually-drop-leak
+//
+// This diagnostic is triggered when a struct field of type `ManuallyDrop<T>` is never passed to
+// `ManuallyDrop::drop` or `ManuallyDrop::take` in the struct's `Drop` impl, which leaks the
+// wrapped value whenever the struct is dropped.
+pub(crate) fn manually_drop_leak(
+    sema: &Semantics<'_, RootDatabase>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    node: &SyntaxNode,
+) -> Option<()> {
+    let impl_ = ast::Impl::cast(node.clone())?;
+    let drop_fn = impl_.assoc_item_list()?.assoc_items().find_map(|item| match item {
+        ast::AssocItem::Fn(it) if it.name()?.text() == "drop" => Some(it),
+        _ => None,
+    })?;
+    let body = drop_fn.body()?;
+
+    let db = sema.db;
+    let famous_defs = FamousDefs(sema, sema.scope(node)?.krate());
+    let manually_drop = hir::Adt::Struct(famous_defs.core_mem_ManuallyDrop()?);
+    let impl_def = sema.to_def(&impl_)?;
+    if impl_def.trait_(db)? != famous_defs.core_ops_Drop()? {
+        return None;
+    }
+    let hir::Adt::Struct(strukt) = impl_def.self_ty(db).as_adt()? else { return None };
+    let fields = strukt
+        .fields(db)
+        .into_iter()
+        .filter(|field| field.ty(db).as_adt() == Some(manually_drop))
+        .collect::<Vec<_>>();
+    if fields.is_empty() {
+        return None;
+    }
+
+    let dropped = body
+        .syntax()
+        .descendants()
+        .filter_map(ast::CallExpr::cast)
+        .filter_map(|call| {
+            let ast::Expr::PathExpr(callee) = call.expr()? else { return None };
+            let PathResolution::Def(ModuleDef::Function(func)) =
+                sema.resolve_path(&callee.path()?)?
+            else {
+                return None;
+            };
+            if !matches!(func.name(db).as_str(), Some("drop" | "take")) {
+                return None;
+            }
+            match func.as_assoc_item(db)?.container(db) {
+                AssocItemContainer::Impl(it) if it.self_ty(db).as_adt() == Some(manually_drop) => {}
+                _ => return None,
+            }
+            let mut slot = call.arg_list()?.args().next()?;
+            if let ast::Expr::RefExpr(it) = &slot {
+                slot = it.expr()?;
+            }
+            let ast::Expr::FieldExpr(field_expr) = slot else { return None };
+            sema.resolve_field(&field_expr)?.left()
+        })
+        .collect::<FxHashSet<_>>();
+
+    let range = FileRange { file_id, range: drop_fn.name()?.syntax().text_range() };
+    for field in fields.into_iter().filter(|field| !dropped.contains(field)) {
+        acc.push(
+            Diagnostic::new(
+                DiagnosticCode::Ra("manually-drop-leak", Severity::Warning),
+                format!(
+                    "`ManuallyDrop` field `{}` is never dropped in `Drop::drop`, leaking its value",
+                    field.name(db).display(db)
+                ),
+                range,
+            )
+            .with_main_node(InFile::new(file_id.into(), SyntaxNodePtr::new(drop_fn.syntax())))
+            .experimental(),
+        );
+    }
+
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::check_diagnostics;
+
+    #[test]
+    fn undropped_fields() {
+        check_diagnostics(
+            r#"
+//- minicore: manually_drop, drop
+use core::mem::ManuallyDrop;
+struct Guard {
+    name: ManuallyDrop<u32>,
+    handle: ManuallyDrop<u32>,
+    count: u32,
+}
+impl Drop for Guard {
+    fn drop(&mut self) {
+     //^^^^ warn: `ManuallyDrop` field `handle` is never dropped in `Drop::drop`, leaking its value
+        unsafe { ManuallyDrop::drop(&mut self.name) };
+    }
+}
+struct Pair(ManuallyDrop<u32>);
+impl Drop for Pair {
+    fn drop(&mut self) {}
+     //^^^^ warn: `ManuallyDrop` field `0` is never dropped in `Drop::drop`, leaking its value
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn dropped_or_taken_fields() {
+        check_diagnostics(
+            r#"
+//- minicore: manually_drop, drop
+use core::mem::ManuallyDrop;
+struct Guard {
+    name: ManuallyDrop<u32>,
+    handle: ManuallyDrop<u32>,
+}
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let _handle = unsafe { ManuallyDrop::take(&mut self.handle) };
+        unsafe {
+            ManuallyDrop::drop(&mut self.name);
+        }
+    }
+}
+struct NoDropImpl {
+    leaked: ManuallyDrop<u32>,
+}
+impl NoDropImpl {
+    fn drop(&mut self) {}
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use hir::{HasAttrs, InFile, ModuleDef, PathResolution, Semantics};
+use ide_db::{
+    base_db::{FileId, FileRange},
+    source_change::SourceChange,
+    syntax_helpers::node_ext::insert_outer_attr,
+    RootDatabase,
+};
+use syntax::{
+    ast::{self, HasName},
+    AstNode, SyntaxKind, SyntaxNode, SyntaxNodePtr,
+};
+use text_edit::TextEdit;
+
+use crate::{fix, is_trait_impl_fn, Diagnostic, DiagnosticCode, DiagnosticsConfig, Severity};
+
+// Diagnostic: missing-must-use
+//
+// This diagnostic is triggered when a function just returns the result of calling a
+// `#[must_use]` function without being marked `#[must_use]` itself, so that callers of the
+// wrapper silently lose the warning.
+pub(crate) fn missing_must_use(
+    sema: &Semantics<'_, RootDatabase>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    node: &SyntaxNode,
+    config: &DiagnosticsConfig,
+) -> Option<()> {
+    if !config.style_lints {
+        return None;
+    }
+    let fn_ = ast::Fn::cast(node.clone())?;
+    fn_.ret_type()?;
+    if is_trait_impl_fn(&fn_) {
+        // `#[must_use]` has no effect on trait impl methods.
+        return None;
+    }
+
+    let body = fn_.body()?;
+    let has_return =
+        body.syntax().descendants().filter(|it| it.kind() == SyntaxKind::RETURN_EXPR).any(|ret| {
+            ret.ancestors()
+                .take_while(|it| it != body.syntax())
+                .all(|it| it.kind() != SyntaxKind::CLOSURE_EXPR && !ast::Item::can_cast(it.kind()))
+        });
+    if has_return {
+        return None;
+    }
+    let callee = match body.tail_expr()? {
+        ast::Expr::CallExpr(call) => match call.expr()? {
+            ast::Expr::PathExpr(path) => match sema.resolve_path(&path.path()?)? {
+                PathResolution::Def(ModuleDef::Function(it)) => it,
+                _ => return None,
+            },
+            _ => return None,
+        },
+        ast::Expr::MethodCallExpr(call) => sema.resolve_method_call(&call)?,
+        _ => return None,
+    };
+    if !callee.attrs(sema.db).by_key("must_use").exists() {
+        return None;
+    }
+    let wrapper = sema.to_def(&fn_)?;
+    if wrapper == callee || wrapper.attrs(sema.db).by_key("must_use").exists() {
+        return None;
+    }
+    // Callers are already warned about unused values of `#[must_use]` types like `Result`.
+    let ret_is_must_use = wrapper
+        .ret_type(sema.db)
+        .as_adt()
+        .map_or(false, |adt| adt.attrs(sema.db).by_key("must_use").exists());
+    if ret_is_must_use {
+        return None;
+    }
+
+    let name = fn_.name()?;
+    let range = FileRange { file_id, range: name.syntax().text_range() };
+    let (offset, text) = insert_outer_attr(fn_.syntax(), "must_use")?;
+    let edit = TextEdit::insert(offset, text);
+    acc.push(
+        Diagnostic::new(
+            DiagnosticCode::Ra("missing-must-use", Severity::WeakWarning),
+            format!(
+                "function returns the result of `#[must_use]` function `{}` but is not `#[must_use]` itself",
+                callee.name(sema.db).display(sema.db)
+            ),
+            range,
+        )
+        .with_main_node(InFile::new(file_id.into(), SyntaxNodePtr::new(node)))
+        .with_fixes(Some(vec![fix(
+            "add_must_use",
+            "Add `#[must_use]`",
+            SourceChange::from_text_edit(file_id, edit),
+            range.range,
+        )])),
+    );
+
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{check_diagnostics, check_diagnostics_with_config, check_fix},
+        DiagnosticsConfig,
+    };
+
+    #[test]
+    fn missing_must_use() {
+        check_diagnostics(
+            r#"
+struct S;
+impl S {
+    #[must_use]
+    fn get(&self) -> u32 { 0 }
+    fn wrapper(&self) -> u32 { self.get() }
+     //^^^^^^^ 💡 weak: function returns the result of `#[must_use]` function `get` but is not `#[must_use]` itself
+}
+#[must_use]
+fn compute(a: u32) -> u32 { a }
+fn call_wrapper(a: u32) -> u32 {
+ //^^^^^^^^^^^^ 💡 weak: function returns the result of `#[must_use]` function `compute` but is not `#[must_use]` itself
+    let b = a + 1;
+    compute(b)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_missing_must_use() {
+        check_diagnostics(
+            r#"
+#[must_use]
+fn compute(a: u32) -> u32 { a }
+fn plain(a: u32) -> u32 { a }
+
+#[must_use]
+fn already_marked(a: u32) -> u32 { compute(a) }
+fn discards(a: u32) { compute(a); }
+fn transforms(a: u32) -> u32 { compute(a) + 1 }
+fn not_must_use(a: u32) -> u32 { plain(a) }
+fn early_return(a: u32) -> u32 {
+    if a == 0 {
+        return 0;
+    }
+    compute(a)
+}
+
+trait Tr {
+    fn f(&self) -> u32;
+}
+impl Tr for () {
+    fn f(&self) -> u32 { compute(0) }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_missing_must_use_for_must_use_return_type() {
+        check_diagnostics(
+            r#"
+#[must_use]
+struct Token(u32);
+#[must_use]
+fn make(a: u32) -> Token { Token(a) }
+fn wrapper(a: u32) -> Token { make(a) }
+"#,
+        );
+    }
+
+    #[test]
+    fn no_missing_must_use_without_style_lints() {
+        let mut config = DiagnosticsConfig::test_sample();
+        config.style_lints = false;
+        check_diagnostics_with_config(
+            config,
+            r#"
+#[must_use]
+fn compute(a: u32) -> u32 { a }
+fn wrapper(a: u32) -> u32 { compute(a) }
+"#,
+        );
+    }
+
+    #[test]
+    fn fix_missing_must_use() {
+        check_fix(
+            r#"
+#[must_use]
+fn compute(a: u32) -> u32 { a }
+mod m {
+    /// Docs.
+    #[inline]
+    pub fn wrap$0per(a: u32) -> u32 { super::compute(a) }
+}
+"#,
+            r#"
+#[must_use]
+fn compute(a: u32) -> u32 { a }
+mod m {
+    /// Docs.
+    #[inline]
+    #[must_use]
+    pub fn wrapper(a: u32) -> u32 { super::compute(a) }
+}
+"#,
+        );
+    }
+}
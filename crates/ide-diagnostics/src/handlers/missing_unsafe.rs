@@ -140,6 +140,26 @@ fn main() {
         );
     }
 
+    #[test]
+    fn missing_unsafe_diagnostic_with_unsafe_fn_ptr_call() {
+        check_diagnostics(
+            r#"
+unsafe fn unsafe_fn() {}
+
+fn main() {
+    let f: unsafe fn() = unsafe_fn;
+    f();
+  //^^^💡 error: this operation is unsafe and requires an unsafe function or block
+    let g: fn() = main;
+    g();
+    unsafe {
+        f();
+    }
+}
+"#,
+        );
+    }
+
     #[test]
     fn missing_unsafe_diagnostic_with_static_mut() {
         check_diagnostics(
@@ -16,7 +16,7 @@ pub(crate) fn moved_out_of_ref(ctx: &DiagnosticsContext<'_>, d: &hir::MovedOutOf
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::check_diagnostics;
+    use crate::tests::{check_diagnostics, check_diagnostics_with_disabled};
 
     // FIXME: spans are broken
 
@@ -70,7 +70,7 @@ fn main() {
 
     #[test]
     fn generic_types() {
-        check_diagnostics(
+        check_diagnostics_with_disabled(
             r#"
 //- minicore: derive, copy
 
@@ -90,6 +90,7 @@ fn main() {
     consume(*a);
 }
 "#,
+            &["shadowed-type-change"],
         );
     }
 
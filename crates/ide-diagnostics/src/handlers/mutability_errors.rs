@@ -720,7 +720,7 @@ fn f() {
 
     #[test]
     fn overloaded_index() {
-        check_diagnostics(
+        check_diagnostics_with_disabled(
             r#"
 //- minicore: index, copy
 use core::ops::{Index, IndexMut};
@@ -769,6 +769,7 @@ fn f() {
     _ = y;
 }
 "#,
+            &["shadowed-type-change"],
         );
     }
 
@@ -938,7 +939,7 @@ fn fn_once(mut x: impl FnOnce(u8) -> u8) -> u8 {
     #[test]
     fn closure() {
         // FIXME: Diagnostic spans are inconsistent inside and outside closure
-        check_diagnostics(
+        check_diagnostics_with_disabled(
             r#"
         //- minicore: copy, fn
         struct X;
@@ -967,6 +968,7 @@ fn fn_once(mut x: impl FnOnce(u8) -> u8) -> u8 {
             _ = (closure2, closure3, closure4);
         }
                     "#,
+            &["shadowed-type-change"],
         );
         check_diagnostics(
             r#"
@@ -1020,7 +1022,7 @@ fn f() {
 }
             "#,
         );
-        check_diagnostics(
+        check_diagnostics_with_disabled(
             r#"
         //- minicore: copy, fn, deref_mut
         struct X(i32, i64);
@@ -1052,6 +1054,7 @@ fn f() {
                   //^^^^^^^^ 💡 error: cannot mutate immutable variable `closure1`
         }
                     "#,
+            &["shadowed-type-change"],
         );
     }
 
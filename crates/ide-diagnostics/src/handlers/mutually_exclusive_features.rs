@@ -0,0 +1,129 @@
+use syntax::{
+    ast::{self, HasAttrs},
+    AstNode, AstToken, NodeOrToken, T,
+};
+
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+// Diagnostic: mutually-exclusive-features
+//
+// This diagnostic is shown instead of the error of a `compile_error!` that is guarded by a
+// `#[cfg]` requiring two cargo features, which is the usual way for a crate to declare features
+// that can't be enabled together.
+//
+// ```rust
+// #[cfg(all(feature = "std", feature = "no_std"))]
+// compile_error!("`std` and `no_std` can't be enabled at the same time");
+// ```
+pub(crate) fn mutually_exclusive_features(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::MacroError,
+) -> Option<Diagnostic> {
+    let root = ctx.sema.parse_or_expand(d.node.file_id);
+    let macro_call = d.node.value.to_node(&root).ancestors().find_map(ast::MacroCall::cast)?;
+    if macro_call.path()?.segment()?.name_ref()?.text() != "compile_error" {
+        return None;
+    }
+
+    let mut features = Vec::new();
+    for attr in macro_call.attrs() {
+        if attr.simple_name().as_deref() == Some("cfg") {
+            required_features(&attr.meta()?.token_tree()?, &mut features)?;
+        }
+    }
+    let [first, second] = &features[..] else {
+        return None;
+    };
+    if first == second {
+        return None;
+    }
+    // The guard expanded, so both features have to come from the crate's cargo features, but
+    // double-check in case the error is caused by something else.
+    let cfg = ctx.sema.scope(macro_call.syntax())?.krate().cfg(ctx.sema.db);
+    if !features.iter().all(|feature| cfg.get_cfg_values("feature").any(|it| it == feature)) {
+        return None;
+    }
+
+    Some(Diagnostic::new(
+        DiagnosticCode::Ra("mutually-exclusive-features", Severity::Error),
+        format!(
+            "features `{first}` and `{second}` are mutually exclusive, but both are enabled; disable one of them ({})",
+            d.message
+        ),
+        ctx.resolve_precise_location(&d.node, d.precise_location),
+    ))
+}
+
+/// Collects the features required by the predicate in `tt`, if it only consists of
+/// `feature = "..."` predicates combined with `all(..)`.
+fn required_features(tt: &ast::TokenTree, acc: &mut Vec<String>) -> Option<()> {
+    let elements: Vec<_> = tt
+        .token_trees_and_tokens()
+        .filter(|it| it.as_token().map_or(true, |it| !it.kind().is_trivia()))
+        .collect();
+    // Skip the delimiters.
+    let inner = elements.get(1..elements.len().checked_sub(1)?)?;
+    for predicate in inner.split(|it| it.as_token().map_or(false, |it| it.kind() == T![,])) {
+        match predicate {
+            // Trailing comma.
+            [] => (),
+            [NodeOrToken::Token(key), NodeOrToken::Token(eq), NodeOrToken::Token(value)]
+                if key.text() == "feature" && eq.kind() == T![=] =>
+            {
+                acc.push(ast::String::cast(value.clone())?.value()?.into_owned());
+            }
+            [NodeOrToken::Token(all), NodeOrToken::Node(tt)] if all.text() == "all" => {
+                required_features(tt, acc)?;
+            }
+            _ => return None,
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::check_diagnostics;
+
+    #[test]
+    fn both_features_enabled() {
+        check_diagnostics(
+            r#"
+//- /lib.rs crate:foo cfg:feature=std,feature=no_std
+#[rustc_builtin_macro]
+macro_rules! compile_error { () => {} }
+
+#[cfg(all(feature = "std", feature = "no_std"))]
+  compile_error!("`std` and `no_std` can't be enabled at the same time");
+//^^^^^^^^^^^^^ error: features `std` and `no_std` are mutually exclusive, but both are enabled; disable one of them (`std` and `no_std` can't be enabled at the same time)
+
+#[cfg(feature = "std")]
+#[cfg(feature = "no_std")]
+  compile_error!("`std` and `no_std` can't be enabled at the same time");
+//^^^^^^^^^^^^^ error: features `std` and `no_std` are mutually exclusive, but both are enabled; disable one of them (`std` and `no_std` can't be enabled at the same time)
+"#,
+        );
+    }
+
+    #[test]
+    fn other_guards_keep_the_compile_error() {
+        check_diagnostics(
+            r#"
+//- /lib.rs crate:foo cfg:feature=std,feature=no_std
+#[rustc_builtin_macro]
+macro_rules! compile_error { () => {} }
+
+#[cfg(any(feature = "std", feature = "no_std"))]
+  compile_error!("enabling a feature is not supported");
+//^^^^^^^^^^^^^ error: enabling a feature is not supported
+
+#[cfg(all(feature = "std", not(feature = "alloc")))]
+  compile_error!("`std` requires `alloc`");
+//^^^^^^^^^^^^^ error: `std` requires `alloc`
+
+  compile_error!("unconditional");
+//^^^^^^^^^^^^^ error: unconditional
+"#,
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use hir::{db::ExpandDatabase, diagnostics::NeedlessCollect};
+use ide_db::{assists::Assist, source_change::SourceChange};
+use syntax::{ast, AstNode, TextRange};
+use text_edit::TextEdit;
+
+use crate::{adjusted_display_range, fix, Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+// Diagnostic: needless-collect
+//
+// This diagnostic is triggered when an iterator is collected into a `Vec` only to iterate over
+// it right away, as in `.collect::<Vec<_>>().into_iter()`.
+pub(crate) fn needless_collect(
+    ctx: &DiagnosticsContext<'_>,
+    d: &NeedlessCollect,
+) -> Option<Diagnostic> {
+    if d.iter_expr.file_id.macro_file().is_some() {
+        // FIXME: Our infra can't handle allow from within macro expansions rn
+        return None;
+    }
+    let display_range = adjusted_display_range(ctx, d.iter_expr, &|it| removed_range(&it));
+    Some(
+        Diagnostic::new(
+            DiagnosticCode::Clippy("needless_collect"),
+            "collecting into a `Vec` only to iterate over it",
+            display_range,
+        )
+        .with_fixes(fixes(ctx, d)),
+    )
+}
+
+/// The range from the `.collect()` call to the end of the call iterating over the `Vec`.
+fn removed_range(iter: &ast::MethodCallExpr) -> Option<TextRange> {
+    let collect = iter
+        .syntax()
+        .descendants()
+        .filter_map(ast::MethodCallExpr::cast)
+        .find(|it| it.name_ref().map_or(false, |name| name.text() == "collect"))?;
+    let start = collect.receiver()?.syntax().text_range().end();
+    Some(TextRange::new(start, iter.syntax().text_range().end()))
+}
+
+fn fixes(ctx: &DiagnosticsContext<'_>, d: &NeedlessCollect) -> Option<Vec<Assist>> {
+    let root = ctx.sema.db.parse_or_expand(d.iter_expr.file_id);
+    let iter: ast::MethodCallExpr = d.iter_expr.value.to_node(&root);
+    let range = removed_range(&iter)?;
+    // `.iter().copied()` and `.iter().cloned()` yield the items that were collected, so dropping
+    // the whole chain keeps the item type.
+    let edit = TextEdit::delete(range);
+    let source_change = SourceChange::from_text_edit(d.iter_expr.file_id.file_id()?, edit);
+    let label = match iter.name_ref()?.text().as_str() {
+        "into_iter" => "Remove `.collect()` and `.into_iter()`".to_owned(),
+        name => format!("Remove `.collect()`, `.iter()` and `.{name}()`"),
+    };
+    Some(vec![fix("remove_collect", &label, source_change, range)])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_diagnostics, check_fix};
+
+    #[test]
+    fn into_iter_and_iter_copied() {
+        check_diagnostics(
+            r#"
+//- minicore: option, copy, clone, fn
+//- /main.rs crate:main deps:alloc
+use alloc::{iter::{IntoIterator, Iterator}, vec::Vec};
+
+fn f(v: Vec<u32>) {
+    let _ = v.iter().map(|it| *it + 1).collect::<Vec<_>>().into_iter().count();
+                                    //^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 weak: collecting into a `Vec` only to iterate over it
+    let _ = v.iter().collect::<Vec<&u32>>().iter().copied().count();
+                  //^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 weak: collecting into a `Vec` only to iterate over it
+}
+//- /alloc.rs crate:alloc
+pub mod iter {
+    pub trait FromIterator<A> {}
+    pub trait Iterator {
+        type Item;
+        #[lang = "next"]
+        fn next(&mut self) -> Option<Self::Item>;
+        fn collect<B: FromIterator<Self::Item>>(self) -> B where Self: Sized { loop {} }
+        fn map<B, F: FnMut(Self::Item) -> B>(self, _f: F) -> Map<Self, F> where Self: Sized { loop {} }
+        fn count(self) -> usize where Self: Sized { 0 }
+        fn copied<'a, T: 'a + Copy>(self) -> Copied<Self>
+        where
+            Self: Sized + Iterator<Item = &'a T>,
+        {
+            loop {}
+        }
+    }
+    pub trait IntoIterator {
+        type Item;
+        type IntoIter: Iterator<Item = Self::Item>;
+        #[lang = "into_iter"]
+        fn into_iter(self) -> Self::IntoIter;
+    }
+    pub struct Map<I, F>(I, F);
+    impl<B, I: Iterator, F: FnMut(I::Item) -> B> Iterator for Map<I, F> {
+        type Item = B;
+        fn next(&mut self) -> Option<B> { loop {} }
+    }
+    pub struct Copied<I>(I);
+    impl<'a, T: 'a + Copy, I: Iterator<Item = &'a T>> Iterator for Copied<I> {
+        type Item = T;
+        fn next(&mut self) -> Option<T> { loop {} }
+    }
+}
+pub mod vec {
+    use crate::iter::{FromIterator, IntoIterator, Iterator};
+
+    pub struct Vec<T>(T);
+    impl<T> FromIterator<T> for Vec<T> {}
+    pub struct IntoIter<T>(T);
+    impl<T> Iterator for IntoIter<T> {
+        type Item = T;
+        fn next(&mut self) -> Option<T> { loop {} }
+    }
+    impl<T> IntoIterator for Vec<T> {
+        type Item = T;
+        type IntoIter = IntoIter<T>;
+        fn into_iter(self) -> IntoIter<T> { loop {} }
+    }
+    pub struct Iter<'a, T>(&'a T);
+    impl<'a, T> Iterator for Iter<'a, T> {
+        type Item = &'a T;
+        fn next(&mut self) -> Option<&'a T> { loop {} }
+    }
+    impl<T> Vec<T> {
+        pub fn iter(&self) -> Iter<'_, T> { loop {} }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn needed_for_borrows() {
+        check_diagnostics(
+            r#"
+//- minicore: option, copy, clone, fn
+//- /main.rs crate:main deps:alloc
+use alloc::{iter::{IntoIterator, Iterator}, vec::Vec};
+
+fn returned(v: &Vec<u32>) -> impl Iterator<Item = u32> {
+    v.iter().map(|it| *it).collect::<Vec<_>>().into_iter()
+}
+fn stored(mut v: Vec<u32>) {
+    let it = v.iter().map(|it| *it).collect::<Vec<_>>().into_iter().map(|it| it + 1);
+    v.push(1);
+    it.count();
+}
+fn mutating_closure(mut v: Vec<u32>) {
+    v.iter().map(|it| *it).collect::<Vec<_>>().into_iter().for_each(|it| v.push(it));
+}
+fn kept(v: Vec<u32>) {
+    let collected = v.into_iter().collect::<Vec<_>>();
+    let _ = collected.into_iter().count();
+    let _ = Vec::new(1u32).into_iter().count();
+}
+//- /alloc.rs crate:alloc
+pub mod iter {
+    pub trait FromIterator<A> {}
+    pub trait Iterator {
+        type Item;
+        #[lang = "next"]
+        fn next(&mut self) -> Option<Self::Item>;
+        fn collect<B: FromIterator<Self::Item>>(self) -> B where Self: Sized { loop {} }
+        fn map<B, F: FnMut(Self::Item) -> B>(self, _f: F) -> Map<Self, F> where Self: Sized { loop {} }
+        fn for_each<F: FnMut(Self::Item)>(self, _f: F) where Self: Sized {}
+        fn count(self) -> usize where Self: Sized { 0 }
+    }
+    pub trait IntoIterator {
+        type Item;
+        type IntoIter: Iterator<Item = Self::Item>;
+        #[lang = "into_iter"]
+        fn into_iter(self) -> Self::IntoIter;
+    }
+    pub struct Map<I, F>(I, F);
+    impl<B, I: Iterator, F: FnMut(I::Item) -> B> Iterator for Map<I, F> {
+        type Item = B;
+        fn next(&mut self) -> Option<B> { loop {} }
+    }
+}
+pub mod vec {
+    use crate::iter::{FromIterator, IntoIterator, Iterator};
+
+    pub struct Vec<T>(T);
+    impl<T> FromIterator<T> for Vec<T> {}
+    pub struct IntoIter<T>(T);
+    impl<T> Iterator for IntoIter<T> {
+        type Item = T;
+        fn next(&mut self) -> Option<T> { loop {} }
+    }
+    impl<T> IntoIterator for Vec<T> {
+        type Item = T;
+        type IntoIter = IntoIter<T>;
+        fn into_iter(self) -> IntoIter<T> { loop {} }
+    }
+    pub struct Iter<'a, T>(&'a T);
+    impl<'a, T> Iterator for Iter<'a, T> {
+        type Item = &'a T;
+        fn next(&mut self) -> Option<&'a T> { loop {} }
+    }
+    impl<T> Vec<T> {
+        pub fn new(value: T) -> Vec<T> { Vec(value) }
+        pub fn iter(&self) -> Iter<'_, T> { loop {} }
+        pub fn push(&mut self, _value: T) {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn ignores_other_vec_types() {
+        check_diagnostics(
+            r#"
+//- minicore: option, copy, clone, fn
+//- /main.rs crate:main deps:alloc
+use alloc::iter::{FromIterator, IntoIterator, Iterator};
+
+struct Vec<T>(T);
+impl<T> FromIterator<T> for Vec<T> {}
+impl<T> IntoIterator for Vec<T> {
+    type Item = T;
+    type IntoIter = alloc::vec::IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter { loop {} }
+}
+
+fn f(v: alloc::vec::Vec<u32>) {
+    let _ = v.into_iter().collect::<Vec<_>>().into_iter().count();
+}
+//- /alloc.rs crate:alloc
+pub mod iter {
+    pub trait FromIterator<A> {}
+    pub trait Iterator {
+        type Item;
+        #[lang = "next"]
+        fn next(&mut self) -> Option<Self::Item>;
+        fn collect<B: FromIterator<Self::Item>>(self) -> B where Self: Sized { loop {} }
+        fn count(self) -> usize where Self: Sized { 0 }
+    }
+    pub trait IntoIterator {
+        type Item;
+        type IntoIter: Iterator<Item = Self::Item>;
+        #[lang = "into_iter"]
+        fn into_iter(self) -> Self::IntoIter;
+    }
+}
+pub mod vec {
+    use crate::iter::{IntoIterator, Iterator};
+
+    pub struct Vec<T>(T);
+    pub struct IntoIter<T>(T);
+    impl<T> Iterator for IntoIter<T> {
+        type Item = T;
+        fn next(&mut self) -> Option<T> { loop {} }
+    }
+    impl<T> IntoIterator for Vec<T> {
+        type Item = T;
+        type IntoIter = IntoIter<T>;
+        fn into_iter(self) -> IntoIter<T> { loop {} }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn fix_removes_collect() {
+        check_fix(
+            r#"
+//- minicore: option, copy, clone, fn
+//- /main.rs crate:main deps:alloc
+use alloc::{iter::{IntoIterator, Iterator}, vec::Vec};
+
+fn f(v: Vec<u32>) -> usize {
+    v.iter().map(|it| *it).collect::<Vec<_>>().into_iter$0().count()
+}
+//- /alloc.rs crate:alloc
+pub mod iter {
+    pub trait FromIterator<A> {}
+    pub trait Iterator {
+        type Item;
+        #[lang = "next"]
+        fn next(&mut self) -> Option<Self::Item>;
+        fn collect<B: FromIterator<Self::Item>>(self) -> B where Self: Sized { loop {} }
+        fn map<B, F: FnMut(Self::Item) -> B>(self, _f: F) -> Map<Self, F> where Self: Sized { loop {} }
+        fn count(self) -> usize where Self: Sized { 0 }
+    }
+    pub trait IntoIterator {
+        type Item;
+        type IntoIter: Iterator<Item = Self::Item>;
+        #[lang = "into_iter"]
+        fn into_iter(self) -> Self::IntoIter;
+    }
+    pub struct Map<I, F>(I, F);
+    impl<B, I: Iterator, F: FnMut(I::Item) -> B> Iterator for Map<I, F> {
+        type Item = B;
+        fn next(&mut self) -> Option<B> { loop {} }
+    }
+}
+pub mod vec {
+    use crate::iter::{FromIterator, IntoIterator, Iterator};
+
+    pub struct Vec<T>(T);
+    impl<T> FromIterator<T> for Vec<T> {}
+    pub struct IntoIter<T>(T);
+    impl<T> Iterator for IntoIter<T> {
+        type Item = T;
+        fn next(&mut self) -> Option<T> { loop {} }
+    }
+    impl<T> IntoIterator for Vec<T> {
+        type Item = T;
+        type IntoIter = IntoIter<T>;
+        fn into_iter(self) -> IntoIter<T> { loop {} }
+    }
+    pub struct Iter<'a, T>(&'a T);
+    impl<'a, T> Iterator for Iter<'a, T> {
+        type Item = &'a T;
+        fn next(&mut self) -> Option<&'a T> { loop {} }
+    }
+    impl<T> Vec<T> {
+        pub fn iter(&self) -> Iter<'_, T> { loop {} }
+    }
+}
+"#,
+            r#"
+use alloc::{iter::{IntoIterator, Iterator}, vec::Vec};
+
+fn f(v: Vec<u32>) -> usize {
+    v.iter().map(|it| *it).count()
+}
+"#,
+        );
+    }
+}
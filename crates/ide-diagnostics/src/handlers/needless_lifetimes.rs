@@ -0,0 +1,328 @@
+use hir::{InFile, ModuleDef, PathResolution, Semantics};
+use ide_db::{
+    base_db::{FileId, FileRange},
+    source_change::SourceChange,
+    RootDatabase,
+};
+use syntax::{
+    ast::{self, HasGenericParams},
+    AstNode, Direction, SyntaxKind, SyntaxNode, SyntaxNodePtr, T,
+};
+use text_edit::TextEdit;
+
+use crate::{fix, is_trait_impl_fn, Diagnostic, DiagnosticCode, DiagnosticsConfig};
+
+// Diagnostic: needless-lifetimes
+//
+// This diagnostic is triggered when a lifetime parameter of a function is used only once in
+// its parameters, and maybe in its return type, so that lifetime elision would infer the same
+// signature without it.
+//
+// This is a style lint, it is only emitted if style lints are enabled.
+pub(crate) fn needless_lifetimes(
+    sema: &Semantics<'_, RootDatabase>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    node: &SyntaxNode,
+    config: &DiagnosticsConfig,
+) -> Option<()> {
+    if !config.style_lints {
+        return None;
+    }
+    let fn_ = ast::Fn::cast(node.clone())?;
+    if is_trait_impl_fn(&fn_) {
+        // The signature mirrors the one of the trait.
+        return None;
+    }
+    let generic_params = fn_.generic_param_list()?;
+    let param_list = fn_.param_list()?;
+    for lifetime_param in generic_params.lifetime_params() {
+        if let Some(diagnostic) =
+            needless_lifetime(sema, file_id, &fn_, &generic_params, &param_list, &lifetime_param)
+        {
+            acc.push(diagnostic);
+        }
+    }
+    Some(())
+}
+
+fn needless_lifetime(
+    sema: &Semantics<'_, RootDatabase>,
+    file_id: FileId,
+    fn_: &ast::Fn,
+    generic_params: &ast::GenericParamList,
+    param_list: &ast::ParamList,
+    lifetime_param: &ast::LifetimeParam,
+) -> Option<Diagnostic> {
+    // `'a: 'b` relates the lifetime to another one, which elision can't express.
+    if lifetime_param.syntax().children_with_tokens().any(|it| it.kind() == T![:]) {
+        return None;
+    }
+    let declaration = lifetime_param.lifetime()?;
+    let name = declaration.text();
+    let ret_type = fn_.ret_type();
+    let (mut inputs, mut outputs) = (Vec::new(), Vec::new());
+    for lifetime in fn_.syntax().descendants().filter_map(ast::Lifetime::cast) {
+        if lifetime == declaration || lifetime.text() != name {
+            continue;
+        }
+        if param_list.syntax().text_range().contains_range(lifetime.syntax().text_range()) {
+            inputs.push(lifetime);
+        } else if ret_type.as_ref().map_or(false, |it| {
+            it.syntax().text_range().contains_range(lifetime.syntax().text_range())
+        }) {
+            outputs.push(lifetime);
+        } else {
+            // Used in bounds, the where clause or the body.
+            return None;
+        }
+    }
+    let [input] = &inputs[..] else {
+        return None;
+    };
+    if !is_elidable(input, param_list.syntax())
+        || !outputs.iter().all(|it| is_elidable(it, ret_type.as_ref().unwrap().syntax()))
+    {
+        return None;
+    }
+    if !outputs.is_empty() && !output_elision_picks(sema, param_list, input) {
+        return None;
+    }
+
+    let mut edit = TextEdit::builder();
+    if generic_params.generic_params().count() == 1 {
+        edit.delete(generic_params.syntax().text_range());
+    } else {
+        edit.delete(param_with_separator(lifetime_param.syntax()));
+    }
+    for lifetime in inputs.iter().chain(&outputs) {
+        if lifetime.syntax().parent()?.kind() == SyntaxKind::LIFETIME_ARG {
+            // Leaving out lifetimes of paths entirely is discouraged.
+            edit.replace(lifetime.syntax().text_range(), "'_".to_owned());
+        } else {
+            let range = match lifetime.syntax().next_sibling_or_token() {
+                Some(ws) if ws.kind() == SyntaxKind::WHITESPACE => {
+                    lifetime.syntax().text_range().cover(ws.text_range())
+                }
+                _ => lifetime.syntax().text_range(),
+            };
+            edit.delete(range);
+        }
+    }
+
+    let range = lifetime_param.syntax().text_range();
+    Some(
+        Diagnostic::new(
+            DiagnosticCode::Clippy("needless_lifetimes"),
+            format!("the explicit lifetime `{name}` could be elided"),
+            FileRange { file_id, range },
+        )
+        .with_main_node(InFile::new(file_id.into(), SyntaxNodePtr::new(lifetime_param.syntax())))
+        .with_fixes(Some(vec![fix(
+            "remove_needless_lifetime",
+            &format!("Elide the lifetime `{name}`"),
+            SourceChange::from_text_edit(file_id, edit.finish()),
+            range,
+        )])),
+    )
+}
+
+/// Whether `lifetime` can be left out without changing its meaning: trait objects, `impl Trait`
+/// and function pointers give elided lifetimes a meaning of their own.
+fn is_elidable(lifetime: &ast::Lifetime, root: &SyntaxNode) -> bool {
+    let Some(parent) = lifetime.syntax().parent() else { return false };
+    if !matches!(
+        parent.kind(),
+        SyntaxKind::REF_TYPE | SyntaxKind::LIFETIME_ARG | SyntaxKind::SELF_PARAM
+    ) {
+        return false;
+    }
+    parent.ancestors().take_while(|it| it != root).all(|it| {
+        !matches!(
+            it.kind(),
+            SyntaxKind::DYN_TRAIT_TYPE
+                | SyntaxKind::IMPL_TRAIT_TYPE
+                | SyntaxKind::FN_PTR_TYPE
+                | SyntaxKind::FOR_TYPE
+                | SyntaxKind::TYPE_BOUND
+                | SyntaxKind::PARAM_LIST
+        )
+    })
+}
+
+/// Whether the lifetime elided at `input` would be assigned to the elided lifetimes of the return
+/// type, that is, whether it is the lifetime of `&self` or the only lifetime of the parameters.
+fn output_elision_picks(
+    sema: &Semantics<'_, RootDatabase>,
+    param_list: &ast::ParamList,
+    input: &ast::Lifetime,
+) -> bool {
+    if let Some(self_param) = param_list.self_param() {
+        // Any other `self` may have lifetimes of its own.
+        return input.syntax().parent().as_ref() == Some(self_param.syntax());
+    }
+
+    let mut input_lifetimes = 0;
+    for node in param_list.syntax().descendants() {
+        match node.kind() {
+            SyntaxKind::LIFETIME => input_lifetimes += 1,
+            SyntaxKind::REF_TYPE
+                if ast::RefType::cast(node.clone()).unwrap().lifetime().is_none() =>
+            {
+                input_lifetimes += 1
+            }
+            // These may contain lifetimes that don't show up in the syntax.
+            SyntaxKind::DYN_TRAIT_TYPE | SyntaxKind::IMPL_TRAIT_TYPE | SyntaxKind::FN_PTR_TYPE => {
+                return false
+            }
+            SyntaxKind::PATH_TYPE => {
+                let Some(path) = ast::PathType::cast(node).and_then(|it| it.path()) else {
+                    return false;
+                };
+                let Some(hidden) = hidden_lifetimes(sema, &path) else { return false };
+                input_lifetimes += hidden;
+            }
+            _ => (),
+        }
+    }
+    input_lifetimes == 1
+}
+
+/// Counts the lifetime parameters of the type `path` resolves to that it leaves out.
+fn hidden_lifetimes(sema: &Semantics<'_, RootDatabase>, path: &ast::Path) -> Option<usize> {
+    let generic_def: hir::GenericDef = match sema.resolve_path(path)? {
+        PathResolution::Def(ModuleDef::Adt(it)) => it.into(),
+        PathResolution::Def(ModuleDef::TypeAlias(it)) => it.into(),
+        PathResolution::Def(ModuleDef::BuiltinType(_)) | PathResolution::TypeParam(_) => {
+            return Some(0)
+        }
+        // `Self` and traits may hide lifetimes in ways that aren't visible here.
+        _ => return None,
+    };
+    let has_lifetime_args = path
+        .segment()
+        .and_then(|it| it.generic_arg_list())
+        .map_or(false, |it| it.lifetime_args().next().is_some());
+    if has_lifetime_args {
+        return Some(0);
+    }
+    Some(generic_def.lifetime_params(sema.db).len())
+}
+
+/// The range of a generic parameter together with the comma separating it from its neighbours.
+fn param_with_separator(param: &SyntaxNode) -> syntax::TextRange {
+    let range = param.text_range();
+    let next =
+        param.siblings_with_tokens(Direction::Next).skip(1).find(|it| !it.kind().is_trivia());
+    match next {
+        Some(comma) if comma.kind() == T![,] => {
+            let next_param = comma.as_token().and_then(|it| {
+                it.siblings_with_tokens(Direction::Next).skip(1).find(|it| !it.kind().is_trivia())
+            });
+            match next_param {
+                Some(next_param) => range.cover_offset(next_param.text_range().start()),
+                None => range.cover(comma.text_range()),
+            }
+        }
+        _ => {
+            let prev_comma =
+                param.siblings_with_tokens(Direction::Prev).skip(1).find(|it| it.kind() == T![,]);
+            match prev_comma {
+                Some(comma) => range.cover(comma.text_range()),
+                None => range,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{check_diagnostics, check_diagnostics_with_config, check_fix},
+        DiagnosticsConfig,
+    };
+
+    #[test]
+    fn needless_lifetimes() {
+        check_diagnostics(
+            r#"
+struct S;
+impl S {
+    fn get<'a>(&'a self) -> &'a S { self }
+         //^^ 💡 weak: the explicit lifetime `'a` could be elided
+}
+fn first<'a, T>(items: &'a [T]) -> &'a T { &items[0] }
+       //^^ 💡 weak: the explicit lifetime `'a` could be elided
+fn print<'a>(_s: &'a str, _n: usize) {}
+       //^^ 💡 weak: the explicit lifetime `'a` could be elided
+"#,
+        );
+    }
+
+    #[test]
+    fn needed_lifetimes() {
+        check_diagnostics(
+            r#"
+struct Wrapper<'a>(&'a str);
+struct S;
+impl<'s> Wrapper<'s> {
+    fn inner<'a>(&self, other: &'a str) -> &'a str { other }
+    fn header(&self) -> &'s str { self.0 }
+}
+trait Tr<'a> {}
+fn two<'a>(a: &'a str, _b: &'a str) -> &'a str { a }
+fn other_input<'a>(a: &'a str, _b: &str) -> &'a str { a }
+fn hidden<'a>(a: &'a str, _w: Wrapper) -> &'a str { a }
+fn bounded<'a, 'b: 'a>(_a: &'a str, _b: &'b str) {}
+fn outlives<'a, T: 'a>(_a: &'a T) {}
+fn in_body<'a>(a: &'a str) { let _b: &'a str = a; }
+fn trait_object<'a>(_a: &dyn Tr<'a>) {}
+fn fn_pointer<'a>(_f: fn(&'a str)) {}
+fn only_output<'a>() -> &'a str { "" }
+impl<'a> Tr<'a> for S {}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_diagnostic_without_style_lints() {
+        let mut config = DiagnosticsConfig::test_sample();
+        config.style_lints = false;
+        check_diagnostics_with_config(
+            config,
+            r#"
+fn print<'a>(_s: &'a str) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn elide_lifetime() {
+        check_fix(
+            r#"
+struct Wrapper<'a>(&'a str);
+fn first<'a$0, T>(w: Wrapper<'a>, _x: T) -> &'a str { w.0 }
+"#,
+            r#"
+struct Wrapper<'a>(&'a str);
+fn first<T>(w: Wrapper<'_>, _x: T) -> &str { w.0 }
+"#,
+        );
+        check_fix(
+            r#"
+fn print<'a$0>(_s: &'a mut str) {}
+"#,
+            r#"
+fn print(_s: &mut str) {}
+"#,
+        );
+        check_fix(
+            r#"
+fn print<'b, 'a$0>(_s: &'a str, _t: &'b str) {}
+"#,
+            r#"
+fn print<'b>(_s: &str, _t: &'b str) {}
+"#,
+        );
+    }
+}
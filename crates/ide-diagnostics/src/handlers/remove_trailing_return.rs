@@ -201,6 +201,33 @@ fn foo() -> u8 {
         );
     }
 
+    #[test]
+    fn no_diagnostic_if_value_is_discarded() {
+        check_diagnostics(
+            r#"
+fn foo(x: bool) -> u8 {
+    match x {
+        true => return 1,
+        false => return 0,
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_diagnostic_in_loop() {
+        check_diagnostics(
+            r#"
+fn foo() -> u8 {
+    loop {
+        return 2;
+    }
+}
+"#,
+        );
+    }
+
     #[test]
     fn replace_with_expr() {
         check_fix(
@@ -0,0 +1,184 @@
+use hir::{HirDisplay, InFile, PathResolution, Semantics};
+use ide_db::{
+    base_db::{FileId, FileRange},
+    LineIndexDatabase, RootDatabase,
+};
+use syntax::{
+    ast::{self, make, HasName},
+    AstNode, SyntaxKind, SyntaxNode, SyntaxNodePtr,
+};
+
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsConfig, Severity};
+
+// Diagnostic: shadowed-type-change
+//
+// This diagnostic is triggered when a `let` binding shadows an earlier binding of the same name
+// with a value of an unrelated type that is not computed from the shadowed binding, which is often
+// a mistake.
+//
+// This is a style lint, it is only emitted if style lints are enabled. It is also experimental,
+// as shadowing with a different type is perfectly fine Rust.
+pub(crate) fn shadowed_type_change(
+    sema: &Semantics<'_, RootDatabase>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    node: &SyntaxNode,
+    config: &DiagnosticsConfig,
+) -> Option<()> {
+    if !config.style_lints {
+        return None;
+    }
+    let let_stmt = ast::LetStmt::cast(node.clone())?;
+    let ast::Pat::IdentPat(ident_pat) = let_stmt.pat()? else { return None };
+    if ident_pat.pat().is_some() {
+        return None;
+    }
+    let name = ident_pat.name()?;
+    let name_text = name.text();
+    if name_text.starts_with('_') {
+        return None;
+    }
+    let initializer = let_stmt.initializer()?;
+    let deliberate_rebinding = initializer
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .any(|token| token.kind() == SyntaxKind::IDENT && token.text() == name_text);
+    if deliberate_rebinding {
+        // Things like `let x = x.unwrap();` are the usual reason for shadowing.
+        return None;
+    }
+
+    let new = sema.to_def(&ident_pat)?;
+    let scope = sema.scope(initializer.syntax())?;
+    let old = match scope.speculative_resolve(&make::ext::ident_path(&name_text))? {
+        PathResolution::Local(it) => it,
+        _ => return None,
+    };
+    if old == new {
+        return None;
+    }
+
+    let db = sema.db;
+    let (old_ty, new_ty) = (old.ty(db), new.ty(db));
+    if old_ty.contains_unknown() || new_ty.contains_unknown() {
+        return None;
+    }
+    if old_ty.is_closure() || new_ty.is_closure() {
+        // Every closure has its own type, rebinding one is not a type change worth reporting.
+        return None;
+    }
+    if old_ty.could_coerce_to(db, &new_ty) || new_ty.could_coerce_to(db, &old_ty) {
+        return None;
+    }
+
+    let old_range = old.primary_source(db).name()?.syntax().original_file_range_rooted(db);
+    let old_line = db.line_index(old_range.file_id).line_col(old_range.range.start()).line + 1;
+    acc.push(
+        Diagnostic::new(
+            DiagnosticCode::Ra("shadowed-type-change", Severity::WeakWarning),
+            format!(
+                "`{name_text}` of type `{}` shadows a binding of type `{}` declared on line {old_line}",
+                new_ty.display(db),
+                old_ty.display(db),
+            ),
+            FileRange { file_id, range: name.syntax().text_range() },
+        )
+        .with_main_node(InFile::new(file_id.into(), SyntaxNodePtr::new(node)))
+        .experimental(),
+    );
+
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{check_diagnostics, check_diagnostics_with_config},
+        DiagnosticsConfig,
+    };
+
+    #[test]
+    fn shadowed_type_change() {
+        check_diagnostics(
+            r#"
+struct Config;
+fn load() -> Config { Config }
+fn main() {
+    let value = 1u32;
+    let _ = value;
+    let value = load();
+      //^^^^^ weak: `value` of type `Config` shadows a binding of type `u32` declared on line 4
+    let _ = value;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn shadowed_param_type_change() {
+        check_diagnostics(
+            r#"
+fn f(count: u32) {
+    let _ = count;
+    let count = "many";
+      //^^^^^ weak: `count` of type `&str` shadows a binding of type `u32` declared on line 1
+    let _ = count;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_shadowed_type_change() {
+        check_diagnostics(
+            r#"
+//- minicore: option, coerce_unsized
+fn f(opt: Option<u32>, slice: &[u8; 2], _unused: u8) {
+    let opt = opt.unwrap();
+    let first = 1u32;
+    let _ = first;
+    let first = 2u32;
+    let slice: &[u8] = slice;
+    let _unused = "";
+    let other = first;
+    let _ = other;
+    let other = unknown();
+              //^^^^^^^ error: no such value in this scope
+    let _ = (opt, first, slice, other);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_shadowed_type_change_for_closures() {
+        check_diagnostics(
+            r#"
+//- minicore: fn
+fn f() {
+    let closure = || 1;
+    let _ = closure();
+    let closure = || 2;
+    let _ = closure();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_shadowed_type_change_without_style_lints() {
+        let mut config = DiagnosticsConfig::test_sample();
+        config.style_lints = false;
+        check_diagnostics_with_config(
+            config,
+            r#"
+fn f(count: u32) {
+    let _ = count;
+    let count = "many";
+    let _ = count;
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use hir::{InFile, Semantics};
+use ide_db::{
+    base_db::{FileId, FileRange},
+    famous_defs::FamousDefs,
+    source_change::SourceChange,
+    RootDatabase,
+};
+use syntax::{
+    ast::{self, HasArgList},
+    match_ast, AstNode, SyntaxNode, SyntaxNodePtr, TextRange,
+};
+use text_edit::TextEdit;
+
+use crate::{fix, Diagnostic, DiagnosticCode, DiagnosticsConfig, Severity};
+
+// Diagnostic: unwrap-could-be-question-mark
+//
+// This diagnostic is triggered when `.unwrap()` or `.expect(..)` is called on an `Option` or
+// `Result` inside of a function that returns the same kind of value, so the `None` or error
+// could be propagated with `?` instead.
+//
+// This is a style lint, it is only emitted if style lints are enabled.
+pub(crate) fn unwrap_could_be_question_mark(
+    sema: &Semantics<'_, RootDatabase>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    node: &SyntaxNode,
+    config: &DiagnosticsConfig,
+) -> Option<()> {
+    if !config.style_lints {
+        return None;
+    }
+    let method_call = ast::MethodCallExpr::cast(node.clone())?;
+    let name_ref = method_call.name_ref()?;
+    let arg_count = method_call.arg_list()?.args().count();
+    match (name_ref.text().as_str(), arg_count) {
+        ("unwrap", 0) | ("expect", 1) => (),
+        _ => return None,
+    }
+    let receiver = method_call.receiver()?;
+    let db = sema.db;
+    sema.resolve_method_call(&method_call)?;
+
+    let famous_defs = FamousDefs(sema, sema.scope(node)?.krate());
+    let result = famous_defs.core_result_Result();
+    let receiver_ty = sema.type_of_expr(&receiver)?.adjusted();
+    let receiver_enum = match receiver_ty.as_adt()? {
+        hir::Adt::Enum(it)
+            if Some(it) == famous_defs.core_option_Option() || Some(it) == result =>
+        {
+            it
+        }
+        _ => return None,
+    };
+
+    let ret_ty = enclosing_return_type(sema, &method_call)?;
+    if ret_ty.as_adt()? != hir::Adt::Enum(receiver_enum) {
+        return None;
+    }
+    if Some(receiver_enum) == result {
+        // `?` converts the error with `From`, so that needs to be implemented.
+        let receiver_err = receiver_ty.type_arguments().nth(1)?;
+        let ret_err = ret_ty.type_arguments().nth(1)?;
+        if receiver_err.contains_unknown() || ret_err.contains_unknown() {
+            return None;
+        }
+        let convertible = receiver_err == ret_err
+            || famous_defs
+                .core_convert_From()
+                .map_or(false, |from| ret_err.impls_trait(db, from, &[receiver_err]));
+        if !convertible {
+            return None;
+        }
+    }
+
+    let range = TextRange::new(
+        receiver.syntax().text_range().end(),
+        method_call.syntax().text_range().end(),
+    );
+    let edit = TextEdit::replace(range, "?".to_owned());
+    acc.push(
+        Diagnostic::new(
+            DiagnosticCode::Ra("unwrap-could-be-question-mark", Severity::WeakWarning),
+            format!("`{name_ref}` could be replaced with `?`"),
+            FileRange { file_id, range: name_ref.syntax().text_range() },
+        )
+        .with_main_node(InFile::new(file_id.into(), SyntaxNodePtr::new(node)))
+        .with_fixes(Some(vec![fix(
+            "replace_unwrap_with_question_mark",
+            "Replace with `?`",
+            SourceChange::from_text_edit(file_id, edit),
+            range,
+        )])),
+    );
+
+    Some(())
+}
+
+/// Returns the type a `?` at `expr` would return from, if it is a function or closure.
+fn enclosing_return_type(
+    sema: &Semantics<'_, RootDatabase>,
+    expr: &ast::MethodCallExpr,
+) -> Option<hir::Type> {
+    for node in expr.syntax().ancestors() {
+        match_ast! {
+            match node {
+                ast::Fn(it) => return Some(sema.to_def(&it)?.ret_type(sema.db)),
+                ast::ClosureExpr(it) => {
+                    let closure_ty = sema.type_of_expr(&it.into())?.original;
+                    return Some(closure_ty.as_callable(sema.db)?.return_type());
+                },
+                // `?` in async and try blocks doesn't return from the function.
+                ast::BlockExpr(it) => if it.async_token().is_some() || it.try_token().is_some() {
+                    return None;
+                },
+                ast::Const(_) => return None,
+                ast::Static(_) => return None,
+                _ => (),
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{check_diagnostics, check_diagnostics_with_config, check_fix},
+        DiagnosticsConfig,
+    };
+
+    #[test]
+    fn unwrap_in_fallible_fns() {
+        check_diagnostics(
+            r#"
+//- minicore: option, result
+fn first(v: Option<u32>) -> Option<u32> {
+    let x = v.unwrap();
+            //^^^^^^ 💡 weak: `unwrap` could be replaced with `?`
+    Some(x)
+}
+fn parse(r: Result<u32, ()>) -> Result<u32, ()> {
+    let x = r.expect("valid");
+            //^^^^^^ 💡 weak: `expect` could be replaced with `?`
+    Ok(x)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unwrap_with_from_conversion() {
+        check_diagnostics(
+            r#"
+//- minicore: result, from
+struct Error;
+struct OtherError;
+impl From<()> for Error {
+    fn from(_: ()) -> Self { Error }
+}
+fn convertible(r: Result<u32, ()>) -> Result<u32, Error> {
+    Ok(r.unwrap())
+       //^^^^^^ 💡 weak: `unwrap` could be replaced with `?`
+}
+fn not_convertible(r: Result<u32, ()>) -> Result<u32, OtherError> {
+    Ok(r.unwrap())
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_diagnostic_without_matching_return_type() {
+        check_diagnostics(
+            r#"
+//- minicore: option, result, fn
+fn infallible(v: Option<u32>) -> u32 {
+    v.unwrap()
+}
+fn mismatched(v: Option<u32>) -> Result<u32, ()> {
+    Ok(v.unwrap())
+}
+fn in_closure(v: Option<u32>) -> Option<u32> {
+    let f = || v.unwrap();
+    let _ = async { v.unwrap() };
+    Some(f())
+}
+fn in_fallible_closure(v: Option<u32>) {
+    let f = || -> Option<u32> { Some(v.unwrap()) };
+                                     //^^^^^^ 💡 weak: `unwrap` could be replaced with `?`
+    let _ = f;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_diagnostic_without_style_lints() {
+        let mut config = DiagnosticsConfig::test_sample();
+        config.style_lints = false;
+        check_diagnostics_with_config(
+            config,
+            r#"
+//- minicore: option
+fn first(v: Option<u32>) -> Option<u32> {
+    Some(v.unwrap())
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_unwrap_with_question_mark() {
+        check_fix(
+            r#"
+//- minicore: result
+fn parse(r: Result<u32, ()>) -> Result<u32, ()> {
+    Ok(r.expect$0("valid") + 1)
+}
+"#,
+            r#"
+fn parse(r: Result<u32, ()>) -> Result<u32, ()> {
+    Ok(r? + 1)
+}
+"#,
+        );
+    }
+}
@@ -26,20 +26,31 @@
 #![warn(rust_2018_idioms, unused_lifetimes)]
 
 mod handlers {
+    pub(crate) mod arithmetic_overflow;
+    pub(crate) mod bool_comparison;
     pub(crate) mod break_outside_of_loop;
+    pub(crate) mod clone_on_copy;
+    pub(crate) mod could_be_const_fn;
+    pub(crate) mod eq_hash_mismatch;
     pub(crate) mod expected_function;
+    pub(crate) mod future_not_send;
     pub(crate) mod inactive_code;
     pub(crate) mod incoherent_impl;
     pub(crate) mod incorrect_case;
     pub(crate) mod invalid_derive_target;
     pub(crate) mod macro_error;
     pub(crate) mod malformed_derive;
+    pub(crate) mod manually_drop_leak;
     pub(crate) mod mismatched_arg_count;
     pub(crate) mod missing_fields;
     pub(crate) mod missing_match_arms;
+    pub(crate) mod missing_must_use;
     pub(crate) mod missing_unsafe;
     pub(crate) mod moved_out_of_ref;
     pub(crate) mod mutability_errors;
+    pub(crate) mod mutually_exclusive_features;
+    pub(crate) mod needless_collect;
+    pub(crate) mod needless_lifetimes;
     pub(crate) mod no_such_field;
     pub(crate) mod non_exhaustive_let;
     pub(crate) mod private_assoc_item;
@@ -69,8 +80,11 @@ mod handlers {
 
     // The handlers below are unusual, the implement the diagnostics as well.
     pub(crate) mod field_shorthand;
+    pub(crate) mod impl_trait_in_params;
     pub(crate) mod json_is_not_rust;
+    pub(crate) mod shadowed_type_change;
     pub(crate) mod unlinked_file;
+    pub(crate) mod unwrap_could_be_question_mark;
     pub(crate) mod useless_braces;
 }
 
@@ -92,7 +106,7 @@ use once_cell::sync::Lazy;
 use stdx::never;
 use syntax::{
     ast::{self, AstNode},
-    AstPtr, SyntaxNode, SyntaxNodePtr, TextRange,
+    match_ast, AstPtr, SyntaxNode, SyntaxNodePtr, TextRange,
 };
 
 // FIXME: Make this an enum
@@ -314,7 +328,20 @@ pub fn diagnostics(
     for node in parse.syntax().descendants() {
         handlers::useless_braces::useless_braces(&mut res, file_id, &node);
         handlers::field_shorthand::field_shorthand(&mut res, file_id, &node);
+        handlers::impl_trait_in_params::impl_trait_in_params(&mut res, file_id, &node, config);
         handlers::json_is_not_rust::json_in_items(&sema, &mut res, file_id, &node, config);
+        handlers::missing_must_use::missing_must_use(&sema, &mut res, file_id, &node, config);
+        handlers::shadowed_type_change::shadowed_type_change(
+            &sema, &mut res, file_id, &node, config,
+        );
+        handlers::unwrap_could_be_question_mark::unwrap_could_be_question_mark(
+            &sema, &mut res, file_id, &node, config,
+        );
+        handlers::could_be_const_fn::could_be_const_fn(&sema, &mut res, file_id, &node, config);
+        handlers::eq_hash_mismatch::eq_hash_mismatch(&sema, &mut res, file_id, &node, config);
+        handlers::manually_drop_leak::manually_drop_leak(&sema, &mut res, file_id, &node);
+        handlers::bool_comparison::bool_comparison(&sema, &mut res, file_id, &node, config);
+        handlers::needless_lifetimes::needless_lifetimes(&sema, &mut res, file_id, &node, config);
     }
 
     let module = sema.file_to_module_def(file_id);
@@ -329,6 +356,10 @@ pub fn diagnostics(
 
     for diag in diags {
         let d = match diag {
+            AnyDiagnostic::CloneOnCopy(d) => match handlers::clone_on_copy::clone_on_copy(&ctx, &d) {
+                Some(it) => it,
+                None => continue,
+            },
             AnyDiagnostic::ExpectedFunction(d) => handlers::expected_function::expected_function(&ctx, &d),
             AnyDiagnostic::InactiveCode(d) => match handlers::inactive_code::inactive_code(&ctx, &d) {
                 Some(it) => it,
@@ -338,7 +369,8 @@ pub fn diagnostics(
             AnyDiagnostic::IncorrectCase(d) => handlers::incorrect_case::incorrect_case(&ctx, &d),
             AnyDiagnostic::InvalidDeriveTarget(d) => handlers::invalid_derive_target::invalid_derive_target(&ctx, &d),
             AnyDiagnostic::MacroDefError(d) => handlers::macro_error::macro_def_error(&ctx, &d),
-            AnyDiagnostic::MacroError(d) => handlers::macro_error::macro_error(&ctx, &d),
+            AnyDiagnostic::MacroError(d) => handlers::mutually_exclusive_features::mutually_exclusive_features(&ctx, &d)
+                .unwrap_or_else(|| handlers::macro_error::macro_error(&ctx, &d)),
             AnyDiagnostic::MacroExpansionParseError(d) => {
                 res.extend(d.errors.iter().take(32).map(|err| {
                     {
@@ -358,11 +390,19 @@ pub fn diagnostics(
             AnyDiagnostic::MissingMatchArms(d) => handlers::missing_match_arms::missing_match_arms(&ctx, &d),
             AnyDiagnostic::MissingUnsafe(d) => handlers::missing_unsafe::missing_unsafe(&ctx, &d),
             AnyDiagnostic::MovedOutOfRef(d) => handlers::moved_out_of_ref::moved_out_of_ref(&ctx, &d),
+            AnyDiagnostic::NeedlessCollect(d) => match handlers::needless_collect::needless_collect(&ctx, &d) {
+                Some(it) => it,
+                None => continue,
+            },
             AnyDiagnostic::NeedMut(d) => match handlers::mutability_errors::need_mut(&ctx, &d) {
                 Some(it) => it,
                 None => continue,
             },
             AnyDiagnostic::NonExhaustiveLet(d) => handlers::non_exhaustive_let::non_exhaustive_let(&ctx, &d),
+            AnyDiagnostic::NonSendHeldAcrossAwait(d) => {
+                res.extend(handlers::future_not_send::future_not_send(&ctx, &d));
+                continue;
+            }
             AnyDiagnostic::NoSuchField(d) => handlers::no_such_field::no_such_field(&ctx, &d),
             AnyDiagnostic::PrivateAssocItem(d) => handlers::private_assoc_item::private_assoc_item(&ctx, &d),
             AnyDiagnostic::PrivateField(d) => handlers::private_field::private_field(&ctx, &d),
@@ -393,6 +433,7 @@ pub fn diagnostics(
                 Some(it) => it,
                 None => continue,
             },
+            AnyDiagnostic::ArithmeticOverflow(d) => handlers::arithmetic_overflow::arithmetic_overflow(&ctx, &d),
             AnyDiagnostic::BreakOutsideOfLoop(d) => handlers::break_outside_of_loop::break_outside_of_loop(&ctx, &d),
             AnyDiagnostic::MismatchedTupleStructPatArgCount(d) => handlers::mismatched_arg_count::mismatched_tuple_struct_pat_arg_count(&ctx, &d),
             AnyDiagnostic::RemoveTrailingReturn(d) => match handlers::remove_trailing_return::remove_trailing_return(&ctx, &d) {
@@ -453,6 +494,62 @@ static RUSTC_LINT_GROUPS_DICT: Lazy<FxHashMap<&str, Vec<&str>>> =
 static CLIPPY_LINT_GROUPS_DICT: Lazy<FxHashMap<&str, Vec<&str>>> =
     Lazy::new(|| build_group_dict(CLIPPY_LINT_GROUPS, &["__RA_EVERY_LINT"], "clippy::"));
 
+/// The cargo features that would have to be enabled to activate some `#[cfg]`-disabled code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InactiveFeatures {
+    pub range: FileRange,
+    /// The name of the crate as specified in its `Cargo.toml`.
+    pub krate: String,
+    pub features: Vec<String>,
+}
+
+/// Returns the features activating the `#[cfg]`-disabled code in `frange`.
+///
+/// This only looks at the item tree and the lowered body at the range, which are computed anyway,
+/// instead of running all diagnostics.
+pub fn inactive_code_features(db: &RootDatabase, frange: FileRange) -> Vec<InactiveFeatures> {
+    let _p = tracing::span!(tracing::Level::INFO, "inactive_code_features").entered();
+    let sema = Semantics::new(db);
+    let source_file = sema.parse(frange.file_id);
+    let node = match source_file.syntax().covering_element(frange.range) {
+        syntax::NodeOrToken::Node(it) => it,
+        syntax::NodeOrToken::Token(it) => match it.parent() {
+            Some(it) => it,
+            None => return Vec::new(),
+        },
+    };
+    let Some(scope) = sema.scope(&node) else { return Vec::new() };
+    let module = scope.module();
+    let Some(krate) = module.krate().display_name(db) else { return Vec::new() };
+
+    let mut inactive = module.inactive_code(db);
+    let body = node.ancestors().find_map(|it| {
+        match_ast! {
+            match it {
+                ast::Fn(it) => sema.to_def(&it).map(hir::DefWithBody::from),
+                ast::Const(it) => sema.to_def(&it).map(hir::DefWithBody::from),
+                ast::Static(it) => sema.to_def(&it).map(hir::DefWithBody::from),
+                _ => None,
+            }
+        }
+    });
+    if let Some(body) = body {
+        inactive.extend(body.inactive_code(db));
+    }
+    inactive
+        .into_iter()
+        .filter(|d| !d.node.file_id.is_macro())
+        .filter_map(|d| {
+            let range = sema.diagnostics_display_range(d.node);
+            if range.file_id != frange.file_id || range.range.intersect(frange.range).is_none() {
+                return None;
+            }
+            let features = handlers::inactive_code::enabling_features(&d)?;
+            Some(InactiveFeatures { range, krate: krate.canonical_name().to_owned(), features })
+        })
+        .collect()
+}
+
 fn build_group_dict(
     lint_group: &'static [LintGroup],
     all_groups: &'static [&'static str],
@@ -604,6 +701,15 @@ fn unresolved_fix(id: &'static str, label: &str, target: TextRange) -> Assist {
     }
 }
 
+/// Whether `fn_` is an item of a trait impl, whose signature is dictated by the trait.
+fn is_trait_impl_fn(fn_: &ast::Fn) -> bool {
+    fn_.syntax()
+        .ancestors()
+        .nth(2)
+        .and_then(ast::Impl::cast)
+        .map_or(false, |impl_| impl_.trait_().is_some())
+}
+
 fn adjusted_display_range<N: AstNode>(
     ctx: &DiagnosticsContext<'_>,
     diag_ptr: InFile<AstPtr<N>>,
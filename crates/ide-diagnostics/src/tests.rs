@@ -49,6 +49,15 @@ pub(crate) fn check_fix_with_disabled(
     check_nth_fix_with_config(config, 0, ra_fixture_before, ra_fixture_after)
 }
 
+#[track_caller]
+pub(crate) fn check_fix_with_config(
+    config: DiagnosticsConfig,
+    ra_fixture_before: &str,
+    ra_fixture_after: &str,
+) {
+    check_nth_fix_with_config(config, 0, ra_fixture_before, ra_fixture_after)
+}
+
 #[track_caller]
 fn check_nth_fix_with_config(
     config: DiagnosticsConfig,
@@ -192,12 +201,15 @@ pub(crate) fn check_no_fix(ra_fixture: &str) {
 pub(crate) fn check_diagnostics(ra_fixture: &str) {
     let mut config = DiagnosticsConfig::test_sample();
     config.disabled.insert("inactive-code".to_owned());
+    // Most fixtures are full of functions that could be `const fn`.
+    config.disabled.insert("could-be-const-fn".to_owned());
     check_diagnostics_with_config(config, ra_fixture)
 }
 
 #[track_caller]
 pub(crate) fn check_diagnostics_with_disabled(ra_fixture: &str, disabled: &[&str]) {
     let mut config = DiagnosticsConfig::test_sample();
+    config.disabled.insert("could-be-const-fn".to_owned());
     config.disabled.extend(disabled.iter().map(|&s| s.to_owned()));
     check_diagnostics_with_config(config, ra_fixture)
 }
@@ -293,6 +305,8 @@ fn minicore_smoke_test() {
         // This should be ignored since we conditionally remove code which creates single item use with braces
         config.disabled.insert("unused_braces".to_owned());
         config.disabled.insert("unused_variables".to_owned());
+        config.disabled.insert("could-be-const-fn".to_owned());
+        config.disabled.insert("needless_lifetimes".to_owned());
         check_diagnostics_with_config(config, &source);
     }
 
@@ -2,16 +2,16 @@
 
 use std::iter;
 
-use hir::{DescendPreference, Semantics};
+use hir::{AsAssocItem, DescendPreference, Semantics};
 use ide_db::{
     defs::{Definition, NameClass, NameRefClass},
     helpers::pick_best_token,
     search::FileReference,
     FxIndexMap, RootDatabase,
 };
-use syntax::{ast, AstNode, SyntaxKind::IDENT, TextRange};
+use syntax::{ast, match_ast, AstNode, SyntaxKind::IDENT, SyntaxNode, TextRange};
 
-use crate::{goto_definition, FilePosition, NavigationTarget, RangeInfo, TryToNav};
+use crate::{goto_definition, FilePosition, NavigationTarget, RangeInfo, ToNav, TryToNav};
 
 #[derive(Debug, Clone)]
 pub struct CallItem {
@@ -47,20 +47,52 @@ pub(crate) fn incoming_calls(
         .find_nodes_at_offset_with_descend(file, offset)
         .filter_map(move |node| match node {
             ast::NameLike::NameRef(name_ref) => match NameRefClass::classify(sema, &name_ref)? {
-                NameRefClass::Definition(def @ Definition::Function(_)) => Some(def),
+                NameRefClass::Definition(def) => Some(def),
                 _ => None,
             },
             ast::NameLike::Name(name) => match NameClass::classify(sema, &name)? {
-                NameClass::Definition(def @ Definition::Function(_)) => Some(def),
+                NameClass::Definition(def) => Some(def),
                 _ => None,
             },
             ast::NameLike::Lifetime(_) => None,
         })
-        .flat_map(|func| func.usages(sema).all());
-
-    for (_, references) in references {
-        let references =
-            references.iter().filter_map(|FileReference { name, .. }| name.as_name_ref());
+        .flat_map(|def| match def {
+            Definition::Function(func) => {
+                let mut usages = vec![(def.usages(sema).all(), None, false)];
+                // Calls through `dyn Trait` or a generic bound resolve to the trait method, but
+                // might dispatch to this impl method at runtime.
+                if let Some(trait_func) = trait_method_of(db, func) {
+                    usages.push((
+                        Definition::Function(trait_func).usages(sema).all(),
+                        Some(trait_func),
+                        false,
+                    ));
+                }
+                usages
+            }
+            // Closures are anonymous, so we treat the binding holding them as the call target.
+            Definition::Local(local) if local.ty(db).is_closure() => {
+                vec![(def.usages(sema).all(), None, true)]
+            }
+            _ => Vec::new(),
+        });
+
+    for (usages, only_dispatched_to, only_calls) in references {
+        let references = usages.iter().flat_map(|(_, refs)| refs).filter_map(
+            |FileReference { name, .. }| {
+                let name = name.as_name_ref()?;
+                if let Some(trait_func) = only_dispatched_to {
+                    match NameRefClass::classify(sema, name)? {
+                        NameRefClass::Definition(Definition::Function(it)) if it == trait_func => {}
+                        _ => return None,
+                    }
+                }
+                if only_calls && !is_callee(name) {
+                    return None;
+                }
+                Some(name)
+            },
+        );
         for name in references {
             // This target is the containing function
             let nav = sema.ancestors_with_macros(name.syntax().clone()).find_map(|node| {
@@ -94,35 +126,43 @@ pub(crate) fn outgoing_calls(
 
     sema.descend_into_macros(DescendPreference::None, token)
         .into_iter()
-        .filter_map(|it| it.parent_ancestors().nth(1).and_then(ast::Item::cast))
-        .filter_map(|item| match item {
-            ast::Item::Const(c) => c.body().map(|it| it.syntax().descendants()),
-            ast::Item::Fn(f) => f.body().map(|it| it.syntax().descendants()),
-            ast::Item::Static(s) => s.body().map(|it| it.syntax().descendants()),
-            _ => None,
-        })
-        .flatten()
-        .filter_map(ast::CallableExpr::cast)
+        .filter_map(|it| callable_body(it.parent_ancestors().nth(1)?))
+        .flat_map(|body| callable_exprs(&sema, &body))
         .filter_map(|call_node| {
             let (nav_target, range) = match call_node {
                 ast::CallableExpr::Call(call) => {
                     let expr = call.expr()?;
                     let callable = sema.type_of_expr(&expr)?.original.as_callable(db)?;
+                    let range = sema.original_range(expr.syntax()).range;
                     match callable.kind() {
-                        hir::CallableKind::Function(it) => {
-                            let range = expr.syntax().text_range();
-                            it.try_to_nav(db).zip(Some(range))
+                        hir::CallableKind::Function(it) => it.try_to_nav(db).zip(Some(range)),
+                        hir::CallableKind::Closure => {
+                            let ast::Expr::PathExpr(path_expr) = expr else { return None };
+                            match sema.resolve_path(&path_expr.path()?)? {
+                                hir::PathResolution::Local(it) => Some((it.to_nav(db), range)),
+                                _ => None,
+                            }
                         }
                         _ => None,
                     }
                 }
                 ast::CallableExpr::MethodCall(expr) => {
-                    let range = expr.name_ref()?.syntax().text_range();
+                    let range = sema.original_range(expr.name_ref()?.syntax()).range;
                     let function = sema.resolve_method_call(&expr)?;
-                    function.try_to_nav(db).zip(Some(range))
+                    let receiver_ty = sema.type_of_expr(&expr.receiver()?)?.original;
+                    let mut navs = function.try_to_nav(db)?.into_iter().collect::<Vec<_>>();
+                    // A call on a trait object could reach any implementation of the method.
+                    if receiver_ty.strip_references().as_dyn_trait().is_some() {
+                        navs.extend(
+                            impl_methods_of(db, function)
+                                .filter_map(|it| it.try_to_nav(db))
+                                .flat_map(|it| it.into_iter()),
+                        );
+                    }
+                    return Some(navs.into_iter().zip(iter::repeat(range)).collect::<Vec<_>>());
                 }
             }?;
-            Some(nav_target.into_iter().zip(iter::repeat(range)))
+            Some(nav_target.into_iter().zip(iter::repeat(range)).collect())
         })
         .flatten()
         .for_each(|(nav, range)| calls.add(nav, range));
@@ -130,6 +170,79 @@ pub(crate) fn outgoing_calls(
     Some(calls.into_items())
 }
 
+/// Returns the body whose calls are the outgoing calls of the item or closure binding `node`.
+fn callable_body(node: SyntaxNode) -> Option<SyntaxNode> {
+    let body = match_ast! {
+        match node {
+            ast::Const(it) => it.body()?,
+            ast::Fn(it) => ast::Expr::BlockExpr(it.body()?),
+            ast::Static(it) => it.body()?,
+            ast::IdentPat(it) => {
+                let let_stmt = it.syntax().parent().and_then(ast::LetStmt::cast)?;
+                match let_stmt.initializer()? {
+                    ast::Expr::ClosureExpr(closure) => closure.body()?,
+                    _ => return None,
+                }
+            },
+            _ => return None,
+        }
+    };
+    Some(body.syntax().clone())
+}
+
+/// Collects the calls in `node`, including the ones produced by macro calls within it.
+fn callable_exprs(sema: &Semantics<'_, RootDatabase>, node: &SyntaxNode) -> Vec<ast::CallableExpr> {
+    let mut calls = Vec::new();
+    for it in node.descendants() {
+        if let Some(call) = ast::CallableExpr::cast(it.clone()) {
+            calls.push(call);
+        } else if let Some(expansion) = ast::MacroCall::cast(it).and_then(|it| sema.expand(&it)) {
+            calls.extend(callable_exprs(sema, &expansion));
+        }
+    }
+    calls
+}
+
+/// Whether `name_ref` is the callee of a call expression.
+fn is_callee(name_ref: &ast::NameRef) -> bool {
+    let Some(path_expr) = name_ref.syntax().ancestors().find_map(ast::PathExpr::cast) else {
+        return false;
+    };
+    path_expr
+        .syntax()
+        .parent()
+        .and_then(ast::CallExpr::cast)
+        .and_then(|call| call.expr())
+        .map_or(false, |callee| callee.syntax() == path_expr.syntax())
+}
+
+/// If `func` is a method of a trait impl, returns the corresponding method of the trait.
+fn trait_method_of(db: &RootDatabase, func: hir::Function) -> Option<hir::Function> {
+    let trait_ = func.as_assoc_item(db)?.implemented_trait(db)?;
+    let name = func.name(db);
+    trait_.items(db).into_iter().find_map(|item| match item {
+        hir::AssocItem::Function(it) if it.name(db) == name => Some(it),
+        _ => None,
+    })
+}
+
+/// If `func` is a trait method, returns the methods overriding it in the impls of the trait.
+fn impl_methods_of(
+    db: &RootDatabase,
+    func: hir::Function,
+) -> impl Iterator<Item = hir::Function> + '_ {
+    let trait_ = func.as_assoc_item(db).and_then(|it| it.container_trait(db));
+    let name = func.name(db);
+    trait_
+        .into_iter()
+        .flat_map(move |trait_| hir::Impl::all_for_trait(db, trait_))
+        .flat_map(move |impl_| impl_.items(db))
+        .filter_map(move |item| match item {
+            hir::AssocItem::Function(it) if it.name(db) == name => Some(it),
+            _ => None,
+        })
+}
+
 #[derive(Default)]
 struct CallLocations {
     funcs: FxIndexMap<NavigationTarget, Vec<TextRange>>,
@@ -462,8 +575,41 @@ fn caller$0() {
 "#,
             expect![[r#"caller Function FileId(0) 160..194 163..169"#]],
             expect![[]],
-            // FIXME
+            expect![[r#"callee Function FileId(0) 144..159 152..158 : [184..190]"#]],
+        );
+    }
+
+    #[test]
+    fn test_call_hierarchy_closure() {
+        check_hierarchy(
+            r#"
+//- /lib.rs
+fn callee() {}
+fn caller() {
+    let clo$0sure = || callee();
+    closure();
+    closure();
+}
+"#,
+            expect![["closure Local FileId(0) 37..44 37..44"]],
+            expect![["caller Function FileId(0) 15..91 18..24 : [64..71, 79..86]"]],
+            expect![["callee Function FileId(0) 0..14 3..9 : [50..56]"]],
+        );
+    }
+
+    #[test]
+    fn test_call_hierarchy_outgoing_to_closure() {
+        check_hierarchy(
+            r#"
+//- /lib.rs
+fn call$0er() {
+    let closure = || {};
+    closure();
+}
+"#,
+            expect![["caller Function FileId(0) 0..55 3..9"]],
             expect![[]],
+            expect![["closure Local FileId(0) 22..29 22..29 : [43..50]"]],
         );
     }
 
@@ -490,4 +636,70 @@ fn caller() {
             expect![[]],
         );
     }
+
+    #[test]
+    fn test_call_hierarchy_incoming_through_trait_dispatch() {
+        check_hierarchy(
+            r#"
+trait Tr {
+    fn method(&self);
+}
+struct S1;
+struct S2;
+impl Tr for S1 {
+    fn meth$0od(&self) {}
+}
+impl Tr for S2 {
+    fn method(&self) {}
+}
+fn direct(s: S1) {
+    s.method();
+}
+fn other(s: S2) {
+    s.method();
+}
+fn dynamic(t: &dyn Tr) {
+    t.method();
+}
+fn generic<T: Tr>(t: T) {
+    t.method();
+}
+"#,
+            expect!["method Function FileId(0) 78..97 81..87"],
+            expect![[r#"
+                direct Function FileId(0) 143..179 146..152 : [168..174]
+                dynamic Function FileId(0) 216..258 219..226 : [247..253]
+                generic Function FileId(0) 259..302 262..269 : [291..297]"#]],
+            expect![[]],
+        );
+    }
+
+    #[test]
+    fn test_call_hierarchy_outgoing_through_trait_dispatch() {
+        check_hierarchy(
+            r#"
+trait Tr {
+    fn method(&self);
+}
+struct S1;
+struct S2;
+impl Tr for S1 {
+    fn method(&self) {}
+}
+impl Tr for S2 {
+    fn method(&self) {}
+}
+fn call$0er<T: Tr>(d: &dyn Tr, t: T) {
+    d.method();
+    t.method();
+}
+"#,
+            expect!["caller Function FileId(0) 143..213 146..152"],
+            expect![[]],
+            expect![[r#"
+                method Function FileId(0) 15..32 18..24 Tr : [186..192, 202..208]
+                method Function FileId(0) 78..97 81..87 : [186..192]
+                method Function FileId(0) 121..140 124..130 : [186..192]"#]],
+        );
+    }
 }
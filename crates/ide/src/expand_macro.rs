@@ -1,9 +1,14 @@
-use hir::{DescendPreference, InFile, MacroFileIdExt, Semantics};
+use hir::{DescendPreference, InFile, InMacroFile, MacroFileIdExt, Semantics};
 use ide_db::{
-    base_db::FileId, helpers::pick_best_token,
-    syntax_helpers::insert_whitespace_into_node::insert_ws_into, RootDatabase,
+    base_db::{FileId, SourceDatabaseExt},
+    helpers::pick_best_token,
+    syntax_helpers::insert_whitespace_into_node::insert_ws_into,
+    LineIndexDatabase, RootDatabase,
+};
+use syntax::{
+    ast::{self, make},
+    ted, AstNode, NodeOrToken, SyntaxKind, SyntaxNode, T,
 };
-use syntax::{ast, ted, AstNode, NodeOrToken, SyntaxKind, SyntaxNode, T};
 
 use crate::FilePosition;
 
@@ -16,6 +21,10 @@ pub struct ExpandedMacro {
 //
 // Shows the full macro expansion of the macro at the current caret position.
 //
+// With `rust-analyzer.expandMacro.annotateOrigins.enable`, each expanded item and statement is
+// preceded by a comment naming the file and line its tokens come from, which is either part of
+// the macro's definition or of its input.
+//
 // |===
 // | Editor  | Action Name
 //
@@ -23,7 +32,11 @@ pub struct ExpandedMacro {
 // |===
 //
 // image::https://user-images.githubusercontent.com/48062697/113020648-b3973180-917a-11eb-84a9-ecb921293dc5.gif[]
-pub(crate) fn expand_macro(db: &RootDatabase, position: FilePosition) -> Option<ExpandedMacro> {
+pub(crate) fn expand_macro(
+    db: &RootDatabase,
+    position: FilePosition,
+    annotate_origins: bool,
+) -> Option<ExpandedMacro> {
     let sema = Semantics::new(db);
     let file = sema.parse(position.file_id);
 
@@ -86,7 +99,7 @@ pub(crate) fn expand_macro(db: &RootDatabase, position: FilePosition) -> Option<
             if let Some(def) = sema.resolve_attr_macro_call(&item) {
                 break (
                     def.name(db).display(db).to_string(),
-                    expand_attr_macro_recur(&sema, &item)?,
+                    expand_attr_macro_recur(&sema, &item, annotate_origins)?,
                     SyntaxKind::MACRO_ITEMS,
                 );
             }
@@ -96,7 +109,7 @@ pub(crate) fn expand_macro(db: &RootDatabase, position: FilePosition) -> Option<
             name.push('!');
             break (
                 name,
-                expand_macro_recur(&sema, &mac)?,
+                expand_macro_recur(&sema, &mac, annotate_origins)?,
                 mac.syntax().parent().map(|it| it.kind()).unwrap_or(SyntaxKind::MACRO_ITEMS),
             );
         }
@@ -113,30 +126,62 @@ pub(crate) fn expand_macro(db: &RootDatabase, position: FilePosition) -> Option<
 fn expand_macro_recur(
     sema: &Semantics<'_, RootDatabase>,
     macro_call: &ast::MacroCall,
+    annotate_origins: bool,
 ) -> Option<SyntaxNode> {
-    let expanded = sema.expand(macro_call)?.clone_for_update();
-    expand(sema, expanded, ast::MacroCall::cast, expand_macro_recur)
+    let expanded = sema.expand(macro_call)?;
+    let origins = if annotate_origins { origins(sema, &expanded) } else { Vec::new() };
+    let expanded = expanded.clone_for_update();
+    expand(sema, expanded, ast::MacroCall::cast, expand_macro_recur, annotate_origins, origins)
 }
 
 fn expand_attr_macro_recur(
     sema: &Semantics<'_, RootDatabase>,
     item: &ast::Item,
+    annotate_origins: bool,
 ) -> Option<SyntaxNode> {
-    let expanded = sema.expand_attr_macro(item)?.clone_for_update();
-    expand(sema, expanded, ast::Item::cast, expand_attr_macro_recur)
+    let expanded = sema.expand_attr_macro(item)?;
+    let origins = if annotate_origins { origins(sema, &expanded) } else { Vec::new() };
+    let expanded = expanded.clone_for_update();
+    expand(sema, expanded, ast::Item::cast, expand_attr_macro_recur, annotate_origins, origins)
+}
+
+/// Finds the source file and line the first token of every item or statement in the expansion
+/// comes from, formatted as `path:line`, by index of the item or statement.
+fn origins(sema: &Semantics<'_, RootDatabase>, expanded: &SyntaxNode) -> Vec<(usize, String)> {
+    let Some(macro_file) = sema.hir_file_for(expanded).macro_file() else { return Vec::new() };
+    if !matches!(expanded.kind(), SyntaxKind::MACRO_ITEMS | SyntaxKind::MACRO_STMTS) {
+        return Vec::new();
+    }
+    expanded
+        .children()
+        .enumerate()
+        .filter_map(|(idx, region)| {
+            let start = region.first_token()?.text_range().start();
+            let (origin, _) = InMacroFile::new(macro_file, start).original_file_range(sema.db);
+            let db = sema.db;
+            let path = db
+                .source_root(db.file_source_root(origin.file_id))
+                .path_for_file(&origin.file_id)
+                .map_or_else(|| "<unknown file>".to_owned(), |it| it.to_string());
+            let line = db.line_index(origin.file_id).line_col(origin.range.start()).line + 1;
+            Some((idx, format!("{path}:{line}")))
+        })
+        .collect()
 }
 
 fn expand<T: AstNode>(
     sema: &Semantics<'_, RootDatabase>,
     expanded: SyntaxNode,
     f: impl FnMut(SyntaxNode) -> Option<T>,
-    exp: impl Fn(&Semantics<'_, RootDatabase>, &T) -> Option<SyntaxNode>,
+    exp: impl Fn(&Semantics<'_, RootDatabase>, &T, bool) -> Option<SyntaxNode>,
+    annotate_origins: bool,
+    origins: Vec<(usize, String)>,
 ) -> Option<SyntaxNode> {
     let children = expanded.descendants().filter_map(f);
     let mut replacements = Vec::new();
 
     for child in children {
-        if let Some(new_node) = exp(sema, &child) {
+        if let Some(new_node) = exp(sema, &child, annotate_origins) {
             // check if the whole original syntax is replaced
             if expanded == *child.syntax() {
                 return Some(new_node);
@@ -145,6 +190,16 @@ fn expand<T: AstNode>(
         }
     }
 
+    // Only modify the tree once all nested macro calls are expanded, as `Semantics` can't find
+    // them in a modified tree anymore.
+    let regions: Vec<_> = expanded.children().collect();
+    for (idx, origin) in origins {
+        let comment = make::tokens::comment(&format!("// {origin}"));
+        ted::insert_all_raw(
+            ted::Position::before(&regions[idx]),
+            vec![comment.into(), make::tokens::single_newline().into()],
+        );
+    }
     replacements.into_iter().rev().for_each(|(old, new)| ted::replace(old.syntax(), new));
     Some(expanded)
 }
@@ -234,8 +289,13 @@ mod tests {
 
     #[track_caller]
     fn check(ra_fixture: &str, expect: Expect) {
+        check_with_annotations(false, ra_fixture, expect)
+    }
+
+    #[track_caller]
+    fn check_with_annotations(annotate_origins: bool, ra_fixture: &str, expect: Expect) {
         let (analysis, pos) = fixture::position(ra_fixture);
-        let expansion = analysis.expand_macro(pos).unwrap().unwrap();
+        let expansion = analysis.expand_macro(pos, annotate_origins).unwrap().unwrap();
         let actual = format!("{}\n{}", expansion.name, expansion.expansion);
         expect.assert_eq(&actual);
     }
@@ -295,6 +355,61 @@ f$0oo!();
         );
     }
 
+    #[test]
+    fn macro_expand_recursive_expansion_with_origins() {
+        check_with_annotations(
+            true,
+            r#"
+macro_rules! bar {
+    ($name:ident) => {
+        fn $name() {}
+    }
+}
+macro_rules! foo {
+    ($name:ident) => {
+        struct S;
+        bar!($name);
+    }
+}
+f$0oo!(
+    baz
+);
+"#,
+            expect![[r#"
+                foo!
+                // /main.rs:8
+                struct S;
+
+                // /main.rs:9
+                // /main.rs:3
+                fn baz(){}"#]],
+        );
+    }
+
+    #[test]
+    fn macro_expand_origins_of_input_tokens() {
+        check_with_annotations(
+            true,
+            r#"
+macro_rules! id {
+    ($($t:tt)*) => { $($t)* }
+}
+fn main() {
+    id$0! {
+        let a = 1;
+        let b = a;
+    }
+}
+"#,
+            expect![[r#"
+                id!
+                // /main.rs:6
+                let a = 1;
+                // /main.rs:7
+                let b = a;"#]],
+        );
+    }
+
     #[test]
     fn macro_expand_multiple_lines() {
         check(
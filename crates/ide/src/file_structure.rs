@@ -7,6 +7,8 @@ use syntax::{
 
 #[derive(Debug, Clone)]
 pub struct StructureNode {
+    /// The index of the enclosing node. Inherent impls are nested under the type they are for,
+    /// so their `node_range` is not contained in the parent's.
     pub parent: Option<usize>,
     pub label: String,
     pub navigation_range: TextRange,
@@ -41,11 +43,17 @@ pub enum StructureNodeKind {
 pub(crate) fn file_structure(file: &SourceFile) -> Vec<StructureNode> {
     let mut res = Vec::new();
     let mut stack = Vec::new();
+    let mut impls = Vec::new();
 
     for event in file.syntax().preorder_with_tokens() {
         match event {
             WalkEvent::Enter(NodeOrToken::Node(node)) => {
                 if let Some(mut symbol) = structure_node(&node) {
+                    if let Some(impl_) = ast::Impl::cast(node.clone()) {
+                        if impl_.trait_().is_none() {
+                            impls.extend(impl_self_ty_name(impl_).map(|it| (res.len(), it)));
+                        }
+                    }
                     symbol.parent = stack.last().copied();
                     stack.push(res.len());
                     res.push(symbol);
@@ -70,9 +78,42 @@ pub(crate) fn file_structure(file: &SourceFile) -> Vec<StructureNode> {
             }
         }
     }
+
+    // Nest inherent impls under the type they are for, if it is declared before them in the same
+    // scope. Parents have to precede their children, so impls of types declared later stay where
+    // they are. Note that the `node_range` of a nested impl lies outside of its parent's.
+    //
+    // Trait impls stay where they are, as they are labelled with both the trait and the type.
+    for (impl_idx, self_ty) in impls {
+        let parent = res[impl_idx].parent;
+        let adt = res[..impl_idx].iter().position(|it| {
+            it.parent == parent
+                && it.label == self_ty
+                && matches!(
+                    it.kind,
+                    StructureNodeKind::SymbolKind(
+                        SymbolKind::Struct | SymbolKind::Enum | SymbolKind::Union
+                    )
+                )
+        });
+        if adt.is_some() {
+            res[impl_idx].parent = adt;
+        }
+    }
     res
 }
 
+/// Returns the name of the impl's self type if it refers to a type of the same scope, like
+/// `Foo` or `Foo<T>`.
+fn impl_self_ty_name(impl_: ast::Impl) -> Option<String> {
+    let ast::Type::PathType(path_ty) = impl_.self_ty()? else { return None };
+    let path = path_ty.path()?;
+    if path.qualifier().is_some() {
+        return None;
+    }
+    Some(path.segment()?.name_ref()?.text().to_string())
+}
+
 fn structure_node(node: &SyntaxNode) -> Option<StructureNode> {
     fn decl<N: HasName + HasAttrs>(node: N, kind: StructureNodeKind) -> Option<StructureNode> {
         decl_with_detail(&node, None, kind)
@@ -490,7 +531,9 @@ fn g() {}
                         deprecated: false,
                     },
                     StructureNode {
-                        parent: None,
+                        parent: Some(
+                            6,
+                        ),
                         label: "impl E",
                         navigation_range: 269..270,
                         node_range: 264..273,
@@ -635,4 +678,107 @@ fn g() {}
             "#]],
         );
     }
+
+    #[test]
+    fn test_impls_nested_under_their_type() {
+        check(
+            r#"
+impl Later {}
+struct Later;
+mod m {
+    pub struct Foo<T>(T);
+    impl<T> Foo<T> {}
+    impl<T> Clone for Foo<T> {}
+}
+impl m::Foo<()> {}
+"#,
+            expect![[r#"
+                [
+                    StructureNode {
+                        parent: None,
+                        label: "impl Later",
+                        navigation_range: 6..11,
+                        node_range: 1..14,
+                        kind: SymbolKind(
+                            Impl,
+                        ),
+                        detail: None,
+                        deprecated: false,
+                    },
+                    StructureNode {
+                        parent: None,
+                        label: "Later",
+                        navigation_range: 22..27,
+                        node_range: 15..28,
+                        kind: SymbolKind(
+                            Struct,
+                        ),
+                        detail: None,
+                        deprecated: false,
+                    },
+                    StructureNode {
+                        parent: None,
+                        label: "m",
+                        navigation_range: 33..34,
+                        node_range: 29..118,
+                        kind: SymbolKind(
+                            Module,
+                        ),
+                        detail: None,
+                        deprecated: false,
+                    },
+                    StructureNode {
+                        parent: Some(
+                            2,
+                        ),
+                        label: "Foo",
+                        navigation_range: 52..55,
+                        node_range: 41..62,
+                        kind: SymbolKind(
+                            Struct,
+                        ),
+                        detail: None,
+                        deprecated: false,
+                    },
+                    StructureNode {
+                        parent: Some(
+                            3,
+                        ),
+                        label: "impl Foo<T>",
+                        navigation_range: 75..81,
+                        node_range: 67..84,
+                        kind: SymbolKind(
+                            Impl,
+                        ),
+                        detail: None,
+                        deprecated: false,
+                    },
+                    StructureNode {
+                        parent: Some(
+                            2,
+                        ),
+                        label: "impl Clone for Foo<T>",
+                        navigation_range: 107..113,
+                        node_range: 89..116,
+                        kind: SymbolKind(
+                            Impl,
+                        ),
+                        detail: None,
+                        deprecated: false,
+                    },
+                    StructureNode {
+                        parent: None,
+                        label: "impl m::Foo<()>",
+                        navigation_range: 124..134,
+                        node_range: 119..137,
+                        kind: SymbolKind(
+                            Impl,
+                        ),
+                        detail: None,
+                        deprecated: false,
+                    },
+                ]
+            "#]],
+        );
+    }
 }
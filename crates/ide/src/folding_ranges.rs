@@ -36,11 +36,13 @@ pub struct Fold {
 // Feature: Folding
 //
 // Defines folding regions for curly braced blocks, runs of consecutive use, mod, const or static
-// items, and `region` / `endregion` comment markers.
+// items, the whole import section of a module, match arms, and `region` / `endregion` comment
+// markers.
 pub(crate) fn folding_ranges(file: &SourceFile) -> Vec<Fold> {
     let mut res = vec![];
     let mut visited_comments = FxHashSet::default();
     let mut visited_imports = FxHashSet::default();
+    let mut visited_import_sections = FxHashSet::default();
     let mut visited_mods = FxHashSet::default();
     let mut visited_consts = FxHashSet::default();
     let mut visited_statics = FxHashSet::default();
@@ -99,9 +101,17 @@ pub(crate) fn folding_ranges(file: &SourceFile) -> Vec<Fold> {
                             }
                         },
                         ast::Use(use_) => {
-                            if let Some(range) = contiguous_range_for_item_group(use_, &mut visited_imports) {
+                            let group = contiguous_range_for_item_group(use_.clone(), &mut visited_imports);
+                            if let Some(range) = group {
                                 res.push(Fold { range, kind: FoldKind::Imports })
                             }
+                            if let Some(range) = range_for_import_section(use_, &mut visited_import_sections) {
+                                // Clients key folds by their start line, so only fold the section
+                                // if it doesn't start with a group that is folded already.
+                                if group.is_none() {
+                                    res.push(Fold { range, kind: FoldKind::Imports })
+                                }
+                            }
                         },
                         ast::Const(konst) => {
                             if let Some(range) = contiguous_range_for_item_group(konst, &mut visited_consts) {
@@ -119,6 +129,9 @@ pub(crate) fn folding_ranges(file: &SourceFile) -> Vec<Fold> {
                             }
                         },
                         ast::MatchArm(match_arm) => {
+                            if let Some(range) = fold_range_for_block_match_arm(&match_arm) {
+                                res.push(Fold { range, kind: FoldKind::MatchArm })
+                            }
                             if let Some(range) = fold_range_for_multiline_match_arm(match_arm) {
                                 res.push(Fold {range, kind: FoldKind::MatchArm})
                             }
@@ -200,6 +213,61 @@ where
     }
 }
 
+/// Finds the run of module-level `use` items starting at `first`, regardless of blank lines,
+/// comments and visibility in between. The run ends at any other item and at `region` markers, so
+/// that it never overlaps a region partially.
+fn range_for_import_section(
+    first: ast::Use,
+    visited: &mut FxHashSet<ast::Use>,
+) -> Option<TextRange> {
+    if !matches!(first.syntax().parent()?.kind(), SOURCE_FILE | ITEM_LIST) {
+        return None;
+    }
+    if !visited.insert(first.clone()) {
+        return None;
+    }
+
+    let mut last = first.clone();
+    for element in first.syntax().siblings_with_tokens(Direction::Next).skip(1) {
+        match element {
+            NodeOrToken::Token(token) => {
+                if let Some(comment) = ast::Comment::cast(token.clone()) {
+                    if is_region_marker(&comment) {
+                        break;
+                    }
+                } else if token.kind() != WHITESPACE {
+                    break;
+                }
+            }
+            NodeOrToken::Node(node) => {
+                let Some(next) = ast::Use::cast(node) else { break };
+                // Comments directly above an item are attached to it.
+                let has_region_marker = next
+                    .syntax()
+                    .children_with_tokens()
+                    .filter_map(|it| ast::Comment::cast(it.into_token()?))
+                    .any(|comment| is_region_marker(&comment));
+                if has_region_marker {
+                    break;
+                }
+                visited.insert(next.clone());
+                last = next;
+            }
+        }
+    }
+
+    if first != last {
+        Some(TextRange::new(first.syntax().text_range().start(), last.syntax().text_range().end()))
+    } else {
+        None
+    }
+}
+
+fn is_region_marker(comment: &ast::Comment) -> bool {
+    let text = comment.text().trim_start();
+    text.starts_with(REGION_START) || text.starts_with(REGION_END)
+}
+
 fn eq_visibility(vis0: Option<ast::Visibility>, vis1: Option<ast::Visibility>) -> bool {
     match (vis0, vis1) {
         (None, None) => true,
@@ -270,6 +338,22 @@ fn fold_range_for_where_clause(where_clause: ast::WhereClause) -> Option<TextRan
     None
 }
 
+/// Folds a whole arm with a block body, unless the arm fits on the line the block starts on, in
+/// which case folding the block already hides the same lines.
+fn fold_range_for_block_match_arm(match_arm: &ast::MatchArm) -> Option<TextRange> {
+    let ast::Expr::BlockExpr(block) = match_arm.expr()? else {
+        return None;
+    };
+    let arm_range = match_arm.syntax().text_range();
+    let head_range = TextRange::new(arm_range.start(), block.syntax().text_range().start());
+    let head = match_arm.syntax().text().slice(head_range - arm_range.start());
+    if head.contains_char('\n') && block.syntax().text().contains_char('\n') {
+        Some(arm_range)
+    } else {
+        None
+    }
+}
+
 fn fold_range_for_multiline_match_arm(match_arm: ast::MatchArm) -> Option<TextRange> {
     if fold_kind(match_arm.expr()?.syntax().kind()).is_some() {
         None
@@ -428,6 +512,35 @@ use std::collections::<fold block>{
         );
     }
 
+    #[test]
+    fn test_fold_import_section() {
+        check(
+            r#"
+<fold imports>pub use std::str;
+
+use std::vec;
+<fold imports>pub(crate) use std::mem;
+pub(crate) use std::f64;</fold>
+
+// Some random comment
+
+use std::io;</fold>
+
+<fold region><fold imports>// region: more imports
+use std::fmt;
+use std::ops;</fold>
+// endregion</fold>
+
+use std::cell;
+fn f() <fold block>{
+    use std::str;
+
+    use std::vec;
+}</fold>
+"#,
+        );
+    }
+
     #[test]
     fn test_folds_structs() {
         check(
@@ -503,6 +616,33 @@ fn main() <fold block>{
         )
     }
 
+    #[test]
+    fn test_fold_multiline_block_match_arm() {
+        check(
+            r#"
+fn main() <fold block>{
+    match foo <fold block>{
+        <fold matcharm>Foo::A
+        | Foo::B
+        | Foo::C => <fold block>{
+            ()
+        }</fold></fold>
+        <fold matcharm>Foo::D(x)
+            if x > 0 =>
+        <fold block>{
+            ()
+        }</fold>,</fold>
+        Foo::E => <fold block>{
+            ()
+        }</fold>
+        Foo::F
+        | Foo::G => {}
+    }</fold>
+}</fold>
+"#,
+        );
+    }
+
     #[test]
     fn fold_big_calls() {
         check(
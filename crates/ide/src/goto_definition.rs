@@ -4,15 +4,18 @@ use crate::{
     doc_links::token_as_doc_comment, navigation_target::ToNav, FilePosition, NavigationTarget,
     RangeInfo, TryToNav,
 };
-use hir::{AsAssocItem, AssocItem, DescendPreference, MacroFileIdExt, ModuleDef, Semantics};
+use hir::{
+    AsAssocItem, AssocItem, DescendPreference, ItemInNs, MacroFileIdExt, ModuleDef, PathResolution,
+    Semantics,
+};
 use ide_db::{
     base_db::{AnchoredPath, FileId, FileLoader},
     defs::{Definition, IdentClass},
     helpers::pick_best_token,
-    RootDatabase,
+    RootDatabase, SymbolKind,
 };
 use itertools::Itertools;
-use syntax::{ast, AstNode, AstToken, SyntaxKind::*, SyntaxToken, TextRange, T};
+use syntax::{ast, AstNode, AstToken, SyntaxKind::*, SyntaxNode, SyntaxToken, TextRange, T};
 
 // Feature: Go to Definition
 //
@@ -98,8 +101,12 @@ pub(crate) fn goto_definition(
                                 .flatten()
                                 .collect();
                         }
-                        try_filter_trait_item_definition(sema, &def)
-                            .unwrap_or_else(|| def_to_nav(sema.db, def))
+                        let mut navs = try_filter_trait_item_definition(sema, &def)
+                            .unwrap_or_else(|| def_to_nav(sema.db, def));
+                        if let Some(kind) = navs.first().and_then(|it| it.kind) {
+                            navs.extend(reexport_navs(sema, &parent, def, kind));
+                        }
+                        navs
                     })
                     .collect(),
             )
@@ -190,6 +197,60 @@ fn try_filter_trait_item_definition(
     }
 }
 
+/// For a qualified path like `a::b::Name` in a `use` item, returns the use trees re-exporting
+/// `def` from `a::b`, so that the re-exports can be navigated to as well as the definition itself.
+///
+/// Other paths only navigate to the definition, to not turn every use of a re-exported item into
+/// a list of targets.
+fn reexport_navs(
+    sema: &Semantics<'_, RootDatabase>,
+    parent: &SyntaxNode,
+    def: Definition,
+    kind: SymbolKind,
+) -> Vec<NavigationTarget> {
+    let db = sema.db;
+    let Some(name_ref) = ast::NameRef::cast(parent.clone()) else { return vec![] };
+    if !name_ref.syntax().ancestors().any(|it| ast::Use::can_cast(it.kind())) {
+        return vec![];
+    }
+    let Some(qualifier) = name_ref
+        .syntax()
+        .parent()
+        .and_then(ast::PathSegment::cast)
+        .and_then(|it| it.parent_path().qualifier())
+    else {
+        return vec![];
+    };
+    let Some(PathResolution::Def(ModuleDef::Module(module))) = sema.resolve_path(&qualifier) else {
+        return vec![];
+    };
+    let item: ItemInNs = match def {
+        Definition::Macro(it) => it.into(),
+        Definition::Module(it) => ModuleDef::Module(it).into(),
+        Definition::Function(it) => ModuleDef::Function(it).into(),
+        Definition::Adt(it) => ModuleDef::Adt(it).into(),
+        Definition::Variant(it) => ModuleDef::Variant(it).into(),
+        Definition::Const(it) => ModuleDef::Const(it).into(),
+        Definition::Static(it) => ModuleDef::Static(it).into(),
+        Definition::Trait(it) => ModuleDef::Trait(it).into(),
+        Definition::TraitAlias(it) => ModuleDef::TraitAlias(it).into(),
+        Definition::TypeAlias(it) => ModuleDef::TypeAlias(it).into(),
+        _ => return vec![],
+    };
+    let Some((name, _)) = module
+        .scope(db, None)
+        .into_iter()
+        .find(|(name, _)| name.to_smol_str() == name_ref.text().as_str())
+    else {
+        return vec![];
+    };
+    module
+        .reexport_chain(db, &name, item)
+        .into_iter()
+        .flat_map(|use_tree| NavigationTarget::from_use_tree(db, use_tree, kind))
+        .collect()
+}
+
 fn def_to_nav(db: &RootDatabase, def: Definition) -> Vec<NavigationTarget> {
     def.try_to_nav(db).map(|it| it.collect()).unwrap_or_default()
 }
@@ -1173,6 +1234,46 @@ pub mod module {
         );
     }
 
+    #[test]
+    fn goto_def_through_reexports() {
+        check(
+            r#"
+//- /main.rs crate:main deps:lib
+use lib::Renamed$0;
+//- /lib.rs crate:lib
+mod inner;
+pub use inner::*;
+      //^^^^^^^^
+//- /inner.rs
+mod detail;
+pub use self::detail::Thing as Renamed;
+                             //^^^^^^^
+//- /inner/detail.rs
+pub struct Thing;
+         //^^^^^
+"#,
+        );
+    }
+
+    #[test]
+    fn goto_def_skips_reexports_outside_of_use_items() {
+        check(
+            r#"
+//- /main.rs crate:main deps:lib
+fn f() -> lib::Renamed$0 { loop {} }
+//- /lib.rs crate:lib
+mod inner;
+pub use inner::*;
+//- /inner.rs
+mod detail;
+pub use self::detail::Thing as Renamed;
+//- /inner/detail.rs
+pub struct Thing;
+         //^^^^^
+"#,
+        );
+    }
+
     #[test]
     fn goto_def_for_assoc_ty_in_path() {
         check(
@@ -2247,6 +2348,63 @@ fn test() {
         );
     }
 
+    #[test]
+    fn implicit_format_args_with_spec_in_wrapping_macro() {
+        check(
+            r#"
+//- minicore: fmt
+fn test() {
+    let a = "world";
+     // ^
+    print!("hello {a$0:?}");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn implicit_format_args_field_access_unresolved() {
+        check_unresolved(
+            r#"
+//- minicore: fmt
+struct S { field: u32 }
+impl S {
+    fn test(&self) {
+        format_args!("hello {self.fi$0eld}");
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn implicit_format_args_self() {
+        check(
+            r#"
+//- minicore: fmt
+struct S { field: u32 }
+impl S {
+    fn test(&self) {
+           //^^^^
+        format_args!("hello {se$0lf}");
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn implicit_format_args_unresolved() {
+        check_unresolved(
+            r#"
+//- minicore: fmt
+fn test() {
+    format_args!("hello {nope$0}");
+}
+"#,
+        );
+    }
+
     #[test]
     fn goto_macro_def_from_macro_use() {
         check(
@@ -6,11 +6,12 @@ use ide_db::{
 };
 use syntax::{ast, AstNode, SyntaxKind::*, T};
 
-use crate::{FilePosition, NavigationTarget, RangeInfo, TryToNav};
+use crate::{FilePosition, NavigationTarget, RangeInfo, TryToNav, UpmappingResult};
 
 // Feature: Go to Implementation
 //
-// Navigates to the impl blocks of types.
+// Navigates to the impl blocks of types. Blanket impls, like `impl<T: Display> Trait for T`, are
+// listed after the other impls, including the ones that apply to a type.
 //
 // |===
 // | Editor  | Shortcut
@@ -79,22 +80,16 @@ pub(crate) fn goto_implementation(
 }
 
 fn impls_for_ty(sema: &Semantics<'_, RootDatabase>, ty: hir::Type) -> Vec<NavigationTarget> {
-    Impl::all_for_type(sema.db, ty)
-        .into_iter()
-        .filter_map(|imp| imp.try_to_nav(sema.db))
-        .flatten()
-        .collect()
+    let blanket_impls = Impl::blanket_impls_for_type(sema.db, ty.clone());
+    let impls = Impl::all_for_type(sema.db, ty).into_iter().chain(blanket_impls);
+    impl_navs(sema, impls, |imp| imp.try_to_nav(sema.db), true)
 }
 
 fn impls_for_trait(
     sema: &Semantics<'_, RootDatabase>,
     trait_: hir::Trait,
 ) -> Vec<NavigationTarget> {
-    Impl::all_for_trait(sema.db, trait_)
-        .into_iter()
-        .filter_map(|imp| imp.try_to_nav(sema.db))
-        .flatten()
-        .collect()
+    impl_navs(sema, Impl::all_for_trait(sema.db, trait_), |imp| imp.try_to_nav(sema.db), true)
 }
 
 fn impls_for_trait_item(
@@ -102,17 +97,35 @@ fn impls_for_trait_item(
     trait_: hir::Trait,
     fun_name: hir::Name,
 ) -> Vec<NavigationTarget> {
-    Impl::all_for_trait(sema.db, trait_)
-        .into_iter()
-        .filter_map(|imp| {
-            let item = imp.items(sema.db).iter().find_map(|itm| {
-                let itm_name = itm.name(sema.db)?;
-                (itm_name == fun_name).then_some(*itm)
-            })?;
-            item.try_to_nav(sema.db)
-        })
-        .flatten()
-        .collect()
+    let to_nav = |imp: Impl| {
+        let item = imp.items(sema.db).iter().find_map(|itm| {
+            let itm_name = itm.name(sema.db)?;
+            (itm_name == fun_name).then_some(*itm)
+        })?;
+        item.try_to_nav(sema.db)
+    };
+    // The items keep their own description.
+    impl_navs(sema, Impl::all_for_trait(sema.db, trait_), to_nav, false)
+}
+
+/// Lists the navigation targets for `impls`, with the blanket impls last and, if `label_blanket`
+/// is set, described as such.
+fn impl_navs(
+    sema: &Semantics<'_, RootDatabase>,
+    impls: impl IntoIterator<Item = Impl>,
+    to_nav: impl Fn(Impl) -> Option<UpmappingResult<NavigationTarget>>,
+    label_blanket: bool,
+) -> Vec<NavigationTarget> {
+    let (blanket, concrete): (Vec<_>, Vec<_>) =
+        impls.into_iter().partition(|imp| imp.is_blanket(sema.db));
+    let concrete = concrete.into_iter().filter_map(&to_nav).flatten();
+    let blanket = blanket.into_iter().filter_map(&to_nav).flatten().map(|mut nav| {
+        if label_blanket {
+            nav.description = Some("blanket impl".to_owned());
+        }
+        nav
+    });
+    concrete.chain(blanket).collect()
 }
 
 #[cfg(test)]
@@ -408,6 +421,76 @@ fn test() {
          //^^^
     }
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn goto_implementation_blanket_impls() {
+        check(
+            r#"
+trait Display {}
+trait Tr$0 {}
+struct Foo;
+impl Tr for Foo {}
+          //^^^
+impl<T: Display> Tr for T {}
+                      //^
+"#,
+        );
+        let (analysis, position) = fixture::position(
+            r#"
+trait Display {}
+trait Tr$0 {}
+impl<T: Display> Tr for T {}
+struct Foo;
+impl Tr for Foo {}
+"#,
+        );
+        let navs = analysis.goto_implementation(position).unwrap().unwrap().info;
+        let descriptions = navs.iter().map(|nav| nav.description.as_deref()).collect::<Vec<_>>();
+        assert_eq!(descriptions, [None, Some("blanket impl")]);
+    }
+
+    #[test]
+    fn goto_implementation_blanket_impls_for_type() {
+        check(
+            r#"
+//- /lib.rs crate:main deps:dep
+use dep::{Display, Named, Tr};
+struct Foo$0;
+impl Display for Foo {}
+               //^^^
+struct Bar;
+impl Named for Foo {}
+             //^^^
+impl Named for Bar {}
+
+trait Local {}
+impl<T: Display> Local for T {}
+                         //^
+//- /dep.rs crate:dep
+pub trait Display {}
+pub trait Tr {}
+impl<T: Display> Tr for T {}
+                      //^
+pub trait Named {}
+impl<T: Display> Named for T {}
+pub trait Generic<U> {}
+impl<T: Display, U> Generic<U> for T {}
+"#,
+        );
+    }
+
+    #[test]
+    fn goto_implementation_skips_sysroot_blanket_impls() {
+        check(
+            r#"
+//- /lib.rs crate:main deps:core
+struct Foo$0;
+//- /core.rs crate:core
+pub trait Any {}
+impl<T> Any for T {}
 "#,
         );
     }
@@ -251,6 +251,8 @@ fn hover_simple(
         .or_else(|| descended().find_map(|token| render::keyword(sema, config, token)))
         // try _ hovers
         .or_else(|| descended().find_map(|token| render::underscore(sema, config, token)))
+        // try '_ hovers
+        .or_else(|| descended().find_map(render::anonymous_lifetime))
         // try rest pattern hover
         .or_else(|| {
             descended().find_map(|token| {
@@ -20,7 +20,7 @@ use stdx::format_to;
 use syntax::{
     algo,
     ast::{self, RecordPat},
-    match_ast, AstNode, Direction, SyntaxToken, T,
+    match_ast, AstNode, Direction, SyntaxKind, SyntaxToken, T,
 };
 
 use crate::{
@@ -246,6 +246,83 @@ pub(super) fn underscore(
     None
 }
 
+/// Explains what the anonymous lifetime `'_` stands for at its position.
+pub(super) fn anonymous_lifetime(token: &SyntaxToken) -> Option<HoverResult> {
+    if token.kind() != SyntaxKind::LIFETIME_IDENT || token.text() != "'_" {
+        return None;
+    }
+    let lifetime = token.parent().and_then(ast::Lifetime::cast)?;
+
+    let mut description = "An inferred lifetime.".to_owned();
+    for node in lifetime.syntax().ancestors() {
+        match_ast! {
+            match node {
+                ast::ParamList(it) => if ast::Fn::can_cast(it.syntax().parent()?.kind()) {
+                    description = "An elided input lifetime, which is a distinct lifetime parameter of the function.".to_owned();
+                    break;
+                },
+                ast::RetType(it) => if let Some(fn_) = it.syntax().parent().and_then(ast::Fn::cast) {
+                    description = output_lifetime_elision(&fn_);
+                    break;
+                },
+                ast::AssocItemList(_) => break,
+                ast::Impl(_) => {
+                    description = "An elided lifetime, which is a distinct lifetime parameter of the impl.".to_owned();
+                    break;
+                },
+                ast::BlockExpr(_) => break,
+                ast::Item(_) => break,
+                _ => (),
+            }
+        }
+    }
+    Some(HoverResult { markup: markup(Some(description), "'_".to_owned(), None), actions: vec![] })
+}
+
+fn output_lifetime_elision(fn_: &ast::Fn) -> String {
+    let Some(param_list) = fn_.param_list() else { return String::new() };
+    if let Some(self_param) = param_list.self_param() {
+        let by_ref = self_param.amp_token().is_some()
+            || self_param.ty().map_or(false, |ty| {
+                ty.syntax().descendants().any(|it| {
+                    ast::RefType::can_cast(it.kind()) || ast::Lifetime::can_cast(it.kind())
+                })
+            });
+        if by_ref {
+            return "An elided output lifetime, which is the lifetime of `self`.".to_owned();
+        }
+    }
+
+    // Every elided or anonymous input lifetime is distinct, named ones are counted once.
+    let mut inputs = Vec::new();
+    for param in param_list.params() {
+        let Some(ty) = param.ty() else { continue };
+        for node in ty.syntax().descendants() {
+            let name = match_ast! {
+                match node {
+                    ast::RefType(it) => match it.lifetime() {
+                        Some(_) => continue,
+                        None => None,
+                    },
+                    ast::Lifetime(it) => Some(it.text().to_string()).filter(|it| it != "'_"),
+                    _ => continue,
+                }
+            };
+            if name.is_none() || !inputs.contains(&name) {
+                inputs.push(name);
+            }
+        }
+    }
+    match inputs.as_slice() {
+        [] => "An elided output lifetime, which can't be inferred as there is no input lifetime."
+            .to_owned(),
+        [Some(name)] => format!("An elided output lifetime, which is the input lifetime `{name}`."),
+        [None] => "An elided output lifetime, which is the only input lifetime.".to_owned(),
+        _ => "An elided output lifetime, which can't be inferred as there are multiple input lifetimes."
+            .to_owned(),
+    }
+}
+
 pub(super) fn keyword(
     sema: &Semantics<'_, RootDatabase>,
     config: &HoverConfig,
@@ -421,6 +498,22 @@ pub(super) fn definition(
             }
             label
         }
+        // Show what the `async fn` actually returns, so users can see the desugaring. Without the
+        // `Future` lang item, there is nothing it desugars to.
+        Definition::Function(it)
+            if it.is_async(db)
+                && Trait::lang(db, it.krate(db), &hir::known::future_trait).is_some() =>
+        {
+            let mut label = def.label(db);
+            if let Some(output) = it.async_ret_type(db) {
+                format_to!(
+                    label,
+                    "\n// desugared return type: impl Future<Output = {}>",
+                    output.display(db)
+                );
+            }
+            label
+        }
         _ => def.label(db),
     };
     let docs = def.docs(db, famous_defs);
@@ -477,9 +570,7 @@ pub(super) fn definition(
             },
             |_| None,
         ),
-        Definition::Adt(it) => {
-            render_memory_layout(config.memory_layout, || it.layout(db), |_| None, |_| None)
-        }
+        Definition::Adt(it) => render_adt_memory_layout(db, config, it),
         Definition::Variant(it) => render_memory_layout(
             config.memory_layout,
             || it.layout(db),
@@ -803,6 +894,45 @@ fn render_memory_layout(
     Some(label)
 }
 
+fn render_adt_memory_layout(db: &RootDatabase, config: &HoverConfig, adt: Adt) -> Option<String> {
+    let memory_layout = config.memory_layout?;
+    let layout = match adt.layout(db) {
+        Ok(layout) => layout,
+        Err(LayoutError::HasErrorType | LayoutError::HasPlaceholder)
+            if !hir::GenericDef::from(adt).type_or_const_params(db).is_empty() =>
+        {
+            return Some("// layout unavailable: type is generic".to_owned());
+        }
+        Err(e) => return Some(format!("// layout unavailable: {e}")),
+    };
+    let mut label =
+        render_memory_layout(Some(memory_layout), || Ok(layout.clone()), |_| None, |_| None)?;
+
+    let (Some(render), Adt::Struct(struct_)) = (memory_layout.offset, adt) else {
+        return Some(label);
+    };
+    let fields = struct_.fields(db);
+    if fields.len() < 2 {
+        return Some(label);
+    }
+    let limit = config.max_struct_field_count.unwrap_or(usize::MAX);
+    let offsets = fields.iter().take(limit).filter_map(|&field| {
+        let offset = layout.field_offset(field)?;
+        let offset = match render {
+            MemoryLayoutHoverRenderKind::Decimal => offset.to_string(),
+            MemoryLayoutHoverRenderKind::Hexadecimal => format!("{offset:#X}"),
+            MemoryLayoutHoverRenderKind::Both if offset >= 10 => format!("{offset} ({offset:#X})"),
+            MemoryLayoutHoverRenderKind::Both => offset.to_string(),
+        };
+        Some(format!("{} = {offset}", field.name(db).display(db)))
+    });
+    format_to!(label, "\n// field offsets: {}", offsets.format(", "));
+    if fields.len() > limit {
+        label.push_str(", ..");
+    }
+    Some(label)
+}
+
 struct KeywordHint {
     description: String,
     keyword_mod: String,
@@ -821,7 +951,15 @@ fn keyword_hints(
     parent: syntax::SyntaxNode,
 ) -> KeywordHint {
     match token.kind() {
-        T![await] | T![loop] | T![match] | T![unsafe] | T![as] | T![try] | T![if] | T![else] => {
+        T![async]
+        | T![await]
+        | T![loop]
+        | T![match]
+        | T![unsafe]
+        | T![as]
+        | T![try]
+        | T![if]
+        | T![else] => {
             let keyword_mod = format!("{}_keyword", token.text());
 
             match ast::Expr::cast(parent).and_then(|site| sema.type_of_expr(&site)) {
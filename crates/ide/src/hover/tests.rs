@@ -452,8 +452,8 @@ fn main() {
                                 file_id: FileId(
                                     1,
                                 ),
-                                full_range: 632..867,
-                                focus_range: 693..699,
+                                full_range: 633..868,
+                                focus_range: 694..700,
                                 name: "FnOnce",
                                 kind: Trait,
                                 container_name: "function",
@@ -901,6 +901,85 @@ struct Foo$0 where u32: Copy { field: u32 }
     );
 }
 
+#[test]
+fn hover_struct_field_offsets() {
+    check(
+        r#"
+struct Foo$0 { a: u8, b: u32, c: u16 }
+"#,
+        expect![[r#"
+            *Foo*
+
+            ```rust
+            test
+            ```
+
+            ```rust
+            // size = 8, align = 4
+            // field offsets: a = 6, b = 0, c = 4
+            struct Foo
+            ```
+        "#]],
+    );
+    check(
+        r#"
+#[repr(C)]
+struct Foo$0 { a: u8, b: u32, c: u16 }
+"#,
+        expect![[r#"
+            *Foo*
+
+            ```rust
+            test
+            ```
+
+            ```rust
+            // size = 12 (0xC), align = 4
+            // field offsets: a = 0, b = 4, c = 8
+            struct Foo
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn hover_struct_layout_unavailable() {
+    check(
+        r#"
+struct Foo$0<T> { a: T, b: u32 }
+"#,
+        expect![[r#"
+            *Foo*
+
+            ```rust
+            test
+            ```
+
+            ```rust
+            // layout unavailable: type is generic
+            struct Foo<T>
+            ```
+        "#]],
+    );
+    check(
+        r#"
+struct Foo$0 { a: Unresolved, b: u32 }
+"#,
+        expect![[r#"
+            *Foo*
+
+            ```rust
+            test
+            ```
+
+            ```rust
+            // layout unavailable: type contains an error
+            struct Foo
+            ```
+        "#]],
+    );
+}
+
 #[test]
 fn hover_record_struct_limit() {
     check_hover_struct_limit(
@@ -917,6 +996,7 @@ fn hover_record_struct_limit() {
 
             ```rust
             // size = 12 (0xC), align = 4
+            // field offsets: a = 0, b = 4, c = 8
             struct Foo  {
                 a: u32,
                 b: i32,
@@ -959,6 +1039,7 @@ fn hover_record_struct_limit() {
 
             ```rust
             // size = 16 (0x10), align = 4
+            // field offsets: a = 0, b = 4, c = 8, ..
             struct Foo  {
                 a: u32,
                 b: i32,
@@ -1862,6 +1943,55 @@ fn test_hover_function_show_qualifiers() {
     );
 }
 
+#[test]
+fn test_hover_async_fn_shows_desugared_return_type() {
+    check(
+        r#"
+//- minicore: future
+async fn foo$0() -> u32 { 0 }
+"#,
+        expect![[r#"
+            *foo*
+
+            ```rust
+            test
+            ```
+
+            ```rust
+            async fn foo() -> u32
+            // desugared return type: impl Future<Output = u32>
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn hover_async_keyword_on_block() {
+    check(
+        r#"
+//- minicore: future
+//- /main.rs crate:main deps:std
+fn f() {
+    let _ = asy$0nc { 0u32 };
+}
+//- /libstd.rs crate:std
+/// Docs for async_keyword
+mod async_keyword {}
+"#,
+        expect![[r#"
+            *async*
+
+            ```rust
+            async: impl Future<Output = u32>
+            ```
+
+            ---
+
+            Docs for async_keyword
+        "#]],
+    );
+}
+
 #[test]
 fn test_hover_function_show_types() {
     check(
@@ -3736,6 +3866,79 @@ struct S$0T<const C: usize = 1, T = Foo>(T);
     );
 }
 
+#[test]
+fn const_generic_evaluated_value() {
+    check(
+        r#"
+const SIZE: usize = 4 * 4;
+fn main() {
+    let buf$0 = [0u8; SIZE];
+}
+"#,
+        expect![[r#"
+            *buf*
+
+            ```rust
+            // size = 16 (0x10), align = 1
+            let buf: [u8; 16]
+            ```
+        "#]],
+    );
+    check(
+        r#"
+fn generic<const N: usize>() {
+    let buf$0 = [0u8; N];
+}
+"#,
+        expect![[r#"
+            *buf*
+
+            ```rust
+            let buf: [u8; N]
+            ```
+        "#]],
+    );
+    check(
+        r#"
+const SIZE: usize = 4 * 4;
+struct Buf {
+    da$0ta: [u8; SIZE],
+}
+"#,
+        expect![[r#"
+            *data*
+
+            ```rust
+            test::Buf
+            ```
+
+            ```rust
+            // size = 16 (0x10), align = 1, offset = 0
+            data: [u8; 16]
+            ```
+        "#]],
+    );
+    check(
+        r#"
+const BROKEN: usize = 1 / 0;
+struct Buf {
+    da$0ta: [u8; BROKEN],
+}
+"#,
+        expect![[r#"
+            *data*
+
+            ```rust
+            test::Buf
+            ```
+
+            ```rust
+            data: [u8; BROKEN]
+            ```
+        "#]],
+    );
+}
+
 #[test]
 fn const_generic_default_value() {
     check(
@@ -6454,6 +6657,7 @@ pub struct Foo<T>(T);
             ```
 
             ```rust
+            // layout unavailable: type is generic
             pub struct Foo<T>(T)
             ```
 
@@ -7864,8 +8068,8 @@ impl Iterator for S {
                                 file_id: FileId(
                                     1,
                                 ),
-                                full_range: 6290..6498,
-                                focus_range: 6355..6361,
+                                full_range: 6548..6756,
+                                focus_range: 6613..6619,
                                 name: "Future",
                                 kind: Trait,
                                 container_name: "future",
@@ -7878,8 +8082,8 @@ impl Iterator for S {
                                 file_id: FileId(
                                     1,
                                 ),
-                                full_range: 7128..7594,
-                                focus_range: 7172..7180,
+                                full_range: 7386..7852,
+                                focus_range: 7430..7438,
                                 name: "Iterator",
                                 kind: Trait,
                                 container_name: "iterator",
@@ -7976,3 +8180,152 @@ fn main(a$0: T) {}
         "#]],
     );
 }
+
+#[test]
+fn hover_lifetime_param_bounds() {
+    check(
+        r#"
+struct S<'a, 'b: 'a, 'c, T: 'b>(&'a &'b &'c T) where 'c: 'b + 'a, &'a T: Copy;
+fn f<'a$0, 'b: 'a>(_: &'a &'b ()) {}
+"#,
+        expect![[r#"
+            *'a*
+
+            ```rust
+            'a
+            where
+                'b: 'a
+            ```
+        "#]],
+    );
+    check(
+        r#"
+struct S<'a, 'b: 'a, 'c, T: 'b>(&'a &'b &'c T) where 'c$0: 'b + 'a, &'a T: Copy;
+"#,
+        expect![[r#"
+            *'c*
+
+            ```rust
+            'c: 'b + 'a
+            ```
+        "#]],
+    );
+    check(
+        r#"
+struct S<'a, 'b$0: 'a, 'c, T: 'b>(&'a &'b &'c T) where 'c: 'b + 'a, &'a T: 'b;
+"#,
+        expect![[r#"
+            *'b*
+
+            ```rust
+            'b: 'a
+            where
+                T: 'b,
+                'c: 'b,
+                &'a T: 'b
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn hover_anonymous_lifetime() {
+    check(
+        r#"
+struct S<'a>(&'a ());
+fn f(_: S<'_$0>) {}
+"#,
+        expect![[r#"
+            *'_*
+            ```rust
+            '_
+            ```
+            ___
+
+            An elided input lifetime, which is a distinct lifetime parameter of the function.
+        "#]],
+    );
+    check(
+        r#"
+struct S<'a>(&'a ());
+impl S<'_> {
+    fn get(&self, _: &u32) -> S<'_$0> { loop {} }
+}
+"#,
+        expect![[r#"
+            *'_*
+            ```rust
+            '_
+            ```
+            ___
+
+            An elided output lifetime, which is the lifetime of `self`.
+        "#]],
+    );
+    check(
+        r#"
+struct S<'a>(&'a ());
+fn f<'a>(_: &'a u32, _: S<'a>) -> S<'_$0> { loop {} }
+fn g(_: &u32, _: &u32) -> S<'_> { loop {} }
+"#,
+        expect![[r#"
+            *'_*
+            ```rust
+            '_
+            ```
+            ___
+
+            An elided output lifetime, which is the input lifetime `'a`.
+        "#]],
+    );
+    check(
+        r#"
+struct S<'a>(&'a ());
+fn g(_: &u32, _: &u32) -> S<'_$0> { loop {} }
+"#,
+        expect![[r#"
+            *'_*
+            ```rust
+            '_
+            ```
+            ___
+
+            An elided output lifetime, which can't be inferred as there are multiple input lifetimes.
+        "#]],
+    );
+    check(
+        r#"
+struct S<'a>(&'a ());
+impl S<'_$0> {}
+fn f() {
+    let _: S<'_> = S(&());
+}
+"#,
+        expect![[r#"
+            *'_*
+            ```rust
+            '_
+            ```
+            ___
+
+            An elided lifetime, which is a distinct lifetime parameter of the impl.
+        "#]],
+    );
+    check(
+        r#"
+struct S<'a>(&'a ());
+fn f() {
+    let _: S<'_$0> = S(&());
+}
+"#,
+        expect![[r#"
+            *'_*
+            ```rust
+            '_
+            ```
+            ___
+
+            An inferred lifetime.
+        "#]],
+    );
+}
@@ -31,8 +31,11 @@ mod discriminant;
 mod fn_lifetime_fn;
 mod implicit_drop;
 mod implicit_static;
+mod numeric_fallback;
 mod param_name;
 mod range_exclusive;
+mod receiver_adjustment;
+mod trait_method;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct InlayHintsConfig {
@@ -53,6 +56,9 @@ pub struct InlayHintsConfig {
     pub hide_named_constructor_hints: bool,
     pub hide_closure_initialization_hints: bool,
     pub range_exclusive_hints: bool,
+    pub numeric_fallback_hints: bool,
+    pub trait_method_hints: bool,
+    pub receiver_adjustment_hints: bool,
     pub closure_style: ClosureStyle,
     pub max_length: Option<usize>,
     pub closing_brace_hints_min_lines: Option<usize>,
@@ -130,6 +136,8 @@ pub enum InlayKind {
     Type,
     Drop,
     RangeExclusive,
+    NumericFallback,
+    TraitMethod,
 }
 
 #[derive(Debug, Hash)]
@@ -534,6 +542,8 @@ fn hints(
                 match expr {
                     ast::Expr::CallExpr(it) => param_name::hints(hints, sema, config, ast::Expr::from(it)),
                     ast::Expr::MethodCallExpr(it) => {
+                        trait_method::hints(hints, sema, config, &it);
+                        receiver_adjustment::hints(hints, sema, config, &it);
                         param_name::hints(hints, sema, config, ast::Expr::from(it))
                     }
                     ast::Expr::ClosureExpr(it) => {
@@ -541,6 +551,7 @@ fn hints(
                         closure_ret::hints(hints, famous_defs, config, file_id, it)
                     },
                     ast::Expr::RangeExpr(it) => range_exclusive::hints(hints, config, it),
+                    ast::Expr::Literal(it) => numeric_fallback::hints(hints, sema, config, it),
                     _ => None,
                 }
             },
@@ -652,6 +663,9 @@ mod tests {
         fields_to_resolve: InlayFieldsToResolve::empty(),
         implicit_drop_hints: false,
         range_exclusive_hints: false,
+        numeric_fallback_hints: false,
+        trait_method_hints: false,
+        receiver_adjustment_hints: false,
     };
     pub(super) const TEST_CONFIG: InlayHintsConfig = InlayHintsConfig {
         type_hints: true,
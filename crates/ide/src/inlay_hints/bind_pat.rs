@@ -518,6 +518,28 @@ fn main() {
         );
     }
 
+    #[test]
+    fn const_generic_values() {
+        check_types(
+            r#"
+const SIZE: usize = 4 * 4;
+struct Buf<const N: usize>([u8; N]);
+fn buf<const N: usize>() -> Buf<N> { loop {} }
+
+fn generic<const N: usize>() {
+    let symbolic = [0u8; N];
+      //^^^^^^^^ [u8; N]
+}
+
+fn main() {
+    let array = [0u8; SIZE];
+      //^^^^^ [u8; 16]
+    let instantiated = buf::<SIZE>();
+      //^^^^^^^^^^^^ Buf<16>
+}"#,
+        );
+    }
+
     #[test]
     fn if_expr() {
         check_types(
@@ -38,7 +38,7 @@ pub(super) fn hints(
     let ty = sema.type_of_expr(&ast::Expr::ClosureExpr(closure.clone()))?.adjusted();
     let callable = ty.as_callable(sema.db)?;
     let ty = callable.return_type();
-    if arrow.is_none() && ty.is_unit() {
+    if ty.is_unknown() || (arrow.is_none() && ty.is_unit()) {
         return None;
     }
 
@@ -94,6 +94,41 @@ fn main() {
           //^^ -> i32
     let b = || 0;
           //^^ -> i32
+}"#,
+        );
+    }
+
+    #[test]
+    fn return_type_hints_for_diverging_closure() {
+        check_with_config(
+            InlayHintsConfig {
+                closure_return_type_hints: ClosureReturnTypeHints::WithBlock,
+                ..DISABLED_CONFIG
+            },
+            r#"
+fn never() -> ! { loop {} }
+fn main() {
+    let a = || { loop {} };
+          //^^ -> !
+    let b = |x: u32| { if x > 0 { never() } else { never() } };
+          //^^^^^^^^ -> !
+    let c = || -> ! { never() };
+}"#,
+        );
+    }
+
+    #[test]
+    fn no_return_type_hints_for_unknown_type() {
+        check_with_config(
+            InlayHintsConfig {
+                closure_return_type_hints: ClosureReturnTypeHints::Always,
+                ..DISABLED_CONFIG
+            },
+            r#"
+fn main() {
+    let a = || { unresolved() };
+    let b = || unresolved();
+    let c = || { () };
 }"#,
         );
     }
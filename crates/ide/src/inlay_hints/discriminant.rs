@@ -2,6 +2,7 @@
 //! ```no_run
 //! enum Foo {
 //!    Bar/* = 0*/,
+//!    Baz = 1 << 4/* = 16*/,
 //! }
 //! ```
 use hir::Semantics;
@@ -44,7 +45,14 @@ fn variant_hints(
     sema: &Semantics<'_, RootDatabase>,
     variant: &ast::Variant,
 ) -> Option<()> {
-    if variant.expr().is_some() {
+    // Literal discriminants are already their own value, but computed ones are worth spelling out.
+    let expr = variant.expr();
+    let is_literal = |expr: &ast::Expr| match expr {
+        ast::Expr::Literal(_) => true,
+        ast::Expr::PrefixExpr(prefix) => matches!(prefix.expr(), Some(ast::Expr::Literal(_))),
+        _ => false,
+    };
+    if expr.as_ref().map_or(false, is_literal) {
         return None;
     }
 
@@ -60,7 +68,7 @@ fn variant_hints(
         Some(field_list) => name.syntax().text_range().cover(field_list.syntax().text_range()),
         None => name.syntax().text_range(),
     };
-    let eq_ = if eq_token.is_none() { " =" } else { "" };
+    let eq_ = if eq_token.is_none() || expr.is_some() { " =" } else { "" };
     let label = InlayHintLabel::simple(
         match d {
             Ok(x) => {
@@ -79,9 +87,10 @@ fn variant_hints(
         None,
     );
     acc.push(InlayHint {
-        range: match eq_token {
-            Some(t) => range.cover(t.text_range()),
-            _ => range,
+        range: match (eq_token, &expr) {
+            (_, Some(expr)) => range.cover(expr.syntax().text_range()),
+            (Some(t), None) => range.cover(t.text_range()),
+            (None, None) => range,
         },
         kind: InlayKind::Discriminant,
         label,
@@ -153,6 +162,26 @@ enum Enum {
         );
     }
 
+    #[test]
+    fn computed_discriminants() {
+        check_discriminants(
+            r#"
+const BASE: u8 = 8;
+#[repr(u8)]
+enum Enum {
+    Literal = 1,
+    Negative = -1,
+    Shifted = 1 << 4,
+//  ^^^^^^^^^^^^^^^^ = 16 (0x10)
+    Next,
+//  ^^^^ = 17 (0x11)
+    FromConst = BASE + 1,
+//  ^^^^^^^^^^^^^^^^^^^^ = 9
+}
+"#,
+        );
+    }
+
     #[test]
     fn datacarrying_mixed() {
         check_discriminants(
@@ -34,7 +34,12 @@ pub(super) fn hints(
     let param_list = func.param_list()?;
     let generic_param_list = func.generic_param_list();
     let ret_type = func.ret_type();
-    let self_param = param_list.self_param().filter(|it| it.amp_token().is_some());
+    // `&self`, `&mut self` as well as `self: &Self` and `self: &mut Self` take part in elision
+    let self_param = param_list.self_param().and_then(|it| match it.ty() {
+        Some(ast::Type::RefType(r)) => Some((r.amp_token(), r.lifetime())),
+        Some(_) => None,
+        None => it.amp_token().map(|amp| (Some(amp), it.lifetime())),
+    });
 
     let is_elided = |lt: &Option<ast::Lifetime>| match lt {
         Some(lt) => matches!(lt.text().as_str(), "'_"),
@@ -43,10 +48,9 @@ pub(super) fn hints(
 
     let potential_lt_refs = {
         let mut acc: Vec<_> = vec![];
-        if let Some(self_param) = &self_param {
-            let lifetime = self_param.lifetime();
-            let is_elided = is_elided(&lifetime);
-            acc.push((None, self_param.amp_token(), lifetime, is_elided));
+        if let Some((amp_token, lifetime)) = &self_param {
+            let is_elided = is_elided(lifetime);
+            acc.push((None, amp_token.clone(), lifetime.clone(), is_elided));
         }
         param_list.params().filter_map(|it| Some((it.pat(), it.ty()?))).for_each(|(pat, ty)| {
             // FIXME: check path types
@@ -96,8 +100,9 @@ pub(super) fn hints(
             false => Default::default(),
         };
     {
+        let self_elided = self_param.as_ref().map_or(false, |(_, lt)| is_elided(lt));
         let mut potential_lt_refs = potential_lt_refs.iter().filter(|&&(.., is_elided)| is_elided);
-        if self_param.is_some() && potential_lt_refs.next().is_some() {
+        if self_elided && potential_lt_refs.next().is_some() {
             allocated_lifetimes.push(if config.param_names_for_lifetime_elision_hints {
                 // self can't be used as a lifetime, so no need to check for collisions
                 "'self".into()
@@ -260,6 +265,18 @@ impl () {
     fn foo(&self, a: &()) -> &() {}
     // ^^^<'0, '1>
         // ^'0       ^'1     ^'0
+    fn foo(self: &Self, a: &()) -> &() {}
+    // ^^^<'0, '1>
+              // ^'0       ^'1     ^'0
+    fn foo(self: &mut Self) -> &() {}
+    // ^^^<'0>
+              // ^'0           ^'0
+    fn foo<'a>(&'a self, a: &()) -> &() {}
+    //    ^'0, $
+                         // ^'0     ^'a
+    fn foo(self, a: &()) -> &() {}
+    // ^^^<'0>
+                 // ^'0     ^'0
 }
 "#,
         );
@@ -273,6 +290,14 @@ impl () {
 fn nested_in<'named>(named: &        &X<      &()>) {}
 //          ^'named1, 'named2, 'named3, $
                           //^'named1 ^'named2 ^'named3
+impl () {
+    fn foo(&self, a: &()) {}
+    // ^^^<'self, 'a>
+        // ^'self    ^'a
+    fn foo<'b>(&'b self, a: &()) {}
+    //    ^'a, $
+                         // ^'a
+}
 "#,
         );
     }
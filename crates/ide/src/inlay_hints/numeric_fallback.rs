@@ -0,0 +1,102 @@
+//! Implementation of "numeric fallback" inlay hints:
+//! ```ignore
+//! let x = 5/* i32 */;
+//! let y = 1.0/* f64 */;
+//! ```
+use hir::{HirDisplay, Semantics};
+use ide_db::RootDatabase;
+use syntax::{ast, AstNode};
+
+use crate::{InlayHint, InlayHintLabel, InlayHintPosition, InlayHintsConfig, InlayKind};
+
+pub(super) fn hints(
+    acc: &mut Vec<InlayHint>,
+    sema: &Semantics<'_, RootDatabase>,
+    config: &InlayHintsConfig,
+    literal: ast::Literal,
+) -> Option<()> {
+    if !config.numeric_fallback_hints {
+        return None;
+    }
+    let expr = ast::Expr::Literal(literal.clone());
+    if !sema.is_defaulted_literal(&expr) {
+        return None;
+    }
+    let ty = sema.type_of_expr(&expr)?.original;
+
+    acc.push(InlayHint {
+        range: literal.syntax().text_range(),
+        position: InlayHintPosition::After,
+        pad_left: false,
+        pad_right: false,
+        kind: InlayKind::NumericFallback,
+        label: InlayHintLabel::from(ty.display(sema.db).to_string()),
+        text_edit: None,
+    });
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        inlay_hints::tests::{check_with_config, DISABLED_CONFIG},
+        InlayHintsConfig,
+    };
+
+    const CONFIG: InlayHintsConfig =
+        InlayHintsConfig { numeric_fallback_hints: true, ..DISABLED_CONFIG };
+
+    #[test]
+    fn numeric_fallback_hints() {
+        check_with_config(
+            CONFIG,
+            r#"
+fn main() {
+    let a = 5;
+          //^ i32
+    let b = 1.5;
+          //^^^ f64
+    let c = -(2 + 3);
+            //^ i32
+                //^ i32
+    let _ = (a, b, c);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_numeric_fallback_hints_for_constrained_literals() {
+        check_with_config(
+            CONFIG,
+            r#"
+fn take(_: u8) {}
+fn main() {
+    let a: u64 = 5;
+    let b = 5u16;
+    take(3);
+    let c = 1;
+    let d: i64 = c;
+    let e = 2.0;
+    let f: f32 = e * 3.0;
+    let g: i32 = 7;
+    let h = d + 1;
+    let _ = (a, b, f, g, h);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_numeric_fallback_hints_when_disabled() {
+        check_with_config(
+            DISABLED_CONFIG,
+            r#"
+fn main() {
+    let a = 5;
+    let _ = a;
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Implementation of "receiver adjustment" inlay hints:
+//! ```no_run
+//! let s = String::new();
+//! let _ = /* (&* */s/* ) */.len();
+//! ```
+use hir::{Adjust, AutoBorrow, Mutability, Semantics};
+use ide_db::RootDatabase;
+use syntax::{ast, AstNode};
+
+use crate::{
+    AdjustmentHints, InlayHint, InlayHintLabel, InlayHintPosition, InlayHintsConfig, InlayKind,
+};
+
+pub(super) fn hints(
+    acc: &mut Vec<InlayHint>,
+    sema: &Semantics<'_, RootDatabase>,
+    config: &InlayHintsConfig,
+    call: &ast::MethodCallExpr,
+) -> Option<()> {
+    if !config.receiver_adjustment_hints {
+        return None;
+    }
+    // Expression adjustment hints already cover method receivers.
+    if config.adjustment_hints != AdjustmentHints::Never {
+        return None;
+    }
+    let receiver = call.receiver()?;
+    let adjustments = sema.expr_adjustments(&receiver)?;
+    // A plain reborrow like `&*r` for a receiver `r: &T` doesn't change anything worth showing.
+    if adjustments.first()?.source == adjustments.last()?.target {
+        return None;
+    }
+
+    let mut prefix = String::from("(");
+    for adjustment in adjustments.iter().rev() {
+        prefix.push_str(match adjustment.kind {
+            Adjust::Deref(_) => "*",
+            Adjust::Borrow(AutoBorrow::Ref(Mutability::Shared)) => "&",
+            Adjust::Borrow(AutoBorrow::Ref(Mutability::Mut)) => "&mut ",
+            Adjust::Borrow(AutoBorrow::RawPtr(Mutability::Shared)) => "&raw const ",
+            Adjust::Borrow(AutoBorrow::RawPtr(Mutability::Mut)) => "&raw mut ",
+            // Unsizing the autoref'd receiver happens implicitly anyway.
+            Adjust::Pointer(_) | Adjust::NeverToAny => "",
+        });
+    }
+
+    let range = receiver.syntax().text_range();
+    acc.push(InlayHint {
+        range,
+        kind: InlayKind::Adjustment,
+        label: InlayHintLabel::from(prefix),
+        text_edit: None,
+        position: InlayHintPosition::Before,
+        pad_left: false,
+        pad_right: false,
+    });
+    acc.push(InlayHint::closing_paren_after(InlayKind::Adjustment, range));
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        inlay_hints::tests::{check_with_config, DISABLED_CONFIG},
+        AdjustmentHints, InlayHintsConfig,
+    };
+
+    const CONFIG: InlayHintsConfig =
+        InlayHintsConfig { receiver_adjustment_hints: true, ..DISABLED_CONFIG };
+
+    #[test]
+    fn receiver_adjustment_hints() {
+        check_with_config(
+            CONFIG,
+            r#"
+//- minicore: deref, deref_mut
+struct Inner;
+impl Inner {
+    fn by_value(self) {}
+    fn by_ref(&self) {}
+    fn by_mut(&mut self) {}
+}
+struct Wrapper(Inner);
+impl core::ops::Deref for Wrapper {
+    type Target = Inner;
+    fn deref(&self) -> &Inner { &self.0 }
+}
+impl core::ops::DerefMut for Wrapper {
+    fn deref_mut(&mut self) -> &mut Inner { &mut self.0 }
+}
+
+fn f(mut inner: Inner, r: &Inner, mut w: Wrapper, rw: &Wrapper) {
+    inner.by_ref();
+  //^^^^^(&
+  //^^^^^)
+    inner.by_mut();
+  //^^^^^(&mut $
+  //^^^^^)
+    r.by_ref();
+    w.by_ref();
+  //^(&*
+  //^)
+    w.by_mut();
+  //^(&mut *
+  //^)
+    rw.by_ref();
+  //^^(&**
+  //^^)
+    inner.by_value();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_receiver_hints_with_adjustment_hints() {
+        check_with_config(
+            InlayHintsConfig {
+                receiver_adjustment_hints: true,
+                adjustment_hints: AdjustmentHints::Always,
+                ..DISABLED_CONFIG
+            },
+            r#"
+struct Inner;
+impl Inner {
+    fn by_ref(&self) {}
+}
+fn f(inner: Inner) {
+    inner.by_ref();
+  //^^^^^(
+  //^^^^^&
+  //^^^^^)
+}
+"#,
+        );
+    }
+}
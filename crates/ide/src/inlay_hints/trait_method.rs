@@ -0,0 +1,107 @@
+//! Implementation of "trait method" inlay hints:
+//! ```ignore
+//! let s = 92.to_string/* (ToString) */();
+//! ```
+use hir::{AsAssocItem, ModuleDef, Semantics};
+use ide_db::{base_db::FileRange, RootDatabase};
+use syntax::{ast, AstNode};
+
+use crate::{
+    navigation_target::TryToNav, InlayHint, InlayHintLabel, InlayHintPosition, InlayHintsConfig,
+    InlayKind,
+};
+
+pub(super) fn hints(
+    acc: &mut Vec<InlayHint>,
+    sema: &Semantics<'_, RootDatabase>,
+    config: &InlayHintsConfig,
+    call: &ast::MethodCallExpr,
+) -> Option<()> {
+    if !config.trait_method_hints {
+        return None;
+    }
+    let name_ref = call.name_ref()?;
+    let func = sema.resolve_method_call(call)?;
+    // Inherent methods are the common case, so only methods provided by traits get a hint.
+    let trait_ = func.as_assoc_item(sema.db)?.container_or_implemented_trait(sema.db)?;
+
+    let location = ModuleDef::Trait(trait_).try_to_nav(sema.db).map(|it| {
+        let it = it.call_site();
+        FileRange { file_id: it.file_id, range: it.focus_or_full_range() }
+    });
+    acc.push(InlayHint {
+        range: name_ref.syntax().text_range(),
+        position: InlayHintPosition::After,
+        pad_left: false,
+        pad_right: false,
+        kind: InlayKind::TraitMethod,
+        label: InlayHintLabel::simple(
+            format!("({})", trait_.name(sema.db).display(sema.db)),
+            None,
+            location,
+        ),
+        text_edit: None,
+    });
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        inlay_hints::tests::{check_with_config, DISABLED_CONFIG},
+        InlayHintsConfig,
+    };
+
+    const CONFIG: InlayHintsConfig =
+        InlayHintsConfig { trait_method_hints: true, ..DISABLED_CONFIG };
+
+    #[test]
+    fn trait_method_hints() {
+        check_with_config(
+            CONFIG,
+            r#"
+//- minicore: clone, derive
+mod shapes {
+    pub trait Area { fn size(&self) -> u32; }
+    pub trait Volume { fn size(&self) -> u32 { 0 } }
+}
+use shapes::{Area, Volume};
+
+#[derive(Clone)]
+struct S;
+impl S {
+    fn inherent(&self) {}
+}
+impl Area for S {
+    fn size(&self) -> u32 { 1 }
+}
+impl Volume for u8 {}
+
+fn main() {
+    S.inherent();
+    S.size();
+    //^^^^ (Area)
+    0u8.size();
+      //^^^^ (Volume)
+    S.clone();
+    //^^^^^ (Clone)
+    Area::size(&S);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generic_receiver() {
+        check_with_config(
+            CONFIG,
+            r#"
+trait Named { fn name(&self) -> &str; }
+fn f<T: Named>(t: T) {
+    t.name();
+    //^^^^ (Named)
+}
+"#,
+        );
+    }
+}
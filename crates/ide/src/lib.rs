@@ -51,6 +51,7 @@ mod status;
 mod syntax_highlighting;
 mod syntax_tree;
 mod test_explorer;
+mod type_hierarchy;
 mod typing;
 mod view_crate_graph;
 mod view_hir;
@@ -134,7 +135,7 @@ pub use ide_db::{
     RootDatabase, SymbolKind,
 };
 pub use ide_diagnostics::{
-    Diagnostic, DiagnosticCode, DiagnosticsConfig, ExprFillDefaultMode, Severity,
+    Diagnostic, DiagnosticCode, DiagnosticsConfig, ExprFillDefaultMode, InactiveFeatures, Severity,
 };
 pub use ide_ssr::SsrError;
 pub use span::Edition;
@@ -371,8 +372,12 @@ impl Analysis {
         self.with_db(fetch_crates::fetch_crates)
     }
 
-    pub fn expand_macro(&self, position: FilePosition) -> Cancellable<Option<ExpandedMacro>> {
-        self.with_db(|db| expand_macro::expand_macro(db, position))
+    pub fn expand_macro(
+        &self,
+        position: FilePosition,
+        annotate_origins: bool,
+    ) -> Cancellable<Option<ExpandedMacro>> {
+        self.with_db(|db| expand_macro::expand_macro(db, position, annotate_origins))
     }
 
     /// Returns an edit to remove all newlines in the range, cleaning up minor
@@ -553,6 +558,24 @@ impl Analysis {
         self.with_db(|db| call_hierarchy::outgoing_calls(db, position))
     }
 
+    /// Computes type hierarchy candidates for the given file position.
+    pub fn type_hierarchy(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Option<RangeInfo<Vec<NavigationTarget>>>> {
+        self.with_db(|db| type_hierarchy::type_hierarchy(db, position))
+    }
+
+    /// Computes the supertypes of the type at the given file position.
+    pub fn supertypes(&self, position: FilePosition) -> Cancellable<Option<Vec<NavigationTarget>>> {
+        self.with_db(|db| type_hierarchy::supertypes(db, position))
+    }
+
+    /// Computes the subtypes of the type at the given file position.
+    pub fn subtypes(&self, position: FilePosition) -> Cancellable<Option<Vec<NavigationTarget>>> {
+        self.with_db(|db| type_hierarchy::subtypes(db, position))
+    }
+
     /// Returns a `mod name;` declaration which created the current module.
     pub fn parent_module(&self, position: FilePosition) -> Cancellable<Vec<NavigationTarget>> {
         self.with_db(|db| parent_module::parent_module(db, position))
@@ -672,6 +695,11 @@ impl Analysis {
         self.with_db(|db| ide_diagnostics::diagnostics(db, config, &resolve, file_id))
     }
 
+    /// Returns the cargo features that activate the `#[cfg]`-disabled code in the range.
+    pub fn inactive_code_features(&self, frange: FileRange) -> Cancellable<Vec<InactiveFeatures>> {
+        self.with_db(|db| ide_diagnostics::inactive_code_features(db, frange))
+    }
+
     /// Convenience function to return assists + quick fixes for diagnostics
     pub fn assists_with_fixes(
         &self,
@@ -719,7 +747,7 @@ impl Analysis {
     pub fn prepare_rename(
         &self,
         position: FilePosition,
-    ) -> Cancellable<Result<RangeInfo<()>, RenameError>> {
+    ) -> Cancellable<Result<RangeInfo<String>, RenameError>> {
         self.with_db(|db| rename::prepare_rename(db, position))
     }
 
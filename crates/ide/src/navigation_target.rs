@@ -153,6 +153,33 @@ impl NavigationTarget {
         )
     }
 
+    /// Creates a `NavigationTarget` pointing at the use tree of an import, focused on the name
+    /// it imports the item as.
+    pub(crate) fn from_use_tree(
+        db: &RootDatabase,
+        InFile { file_id, value }: InFile<ast::UseTree>,
+        kind: SymbolKind,
+    ) -> UpmappingResult<NavigationTarget> {
+        let rename = value.rename().and_then(|it| it.name());
+        let name_ref = value.path().and_then(|it| it.segment()?.name_ref());
+        let name: SmolStr = match (&rename, &name_ref) {
+            (Some(name), _) => name.text().into(),
+            (None, Some(name_ref)) => name_ref.text().into(),
+            (None, None) => "*".into(),
+        };
+        let focus = match (rename, name_ref) {
+            _ if value.star_token().is_some() => None,
+            (Some(name), _) => Some(name.syntax().text_range()),
+            (None, name_ref) => name_ref.map(|it| it.syntax().text_range()),
+        };
+
+        orig_range_with_focus_r(db, file_id, value.syntax().text_range(), focus).map(
+            |(FileRange { file_id, range: full_range }, focus_range)| {
+                NavigationTarget::from_syntax(file_id, name.clone(), focus_range, full_range, kind)
+            },
+        )
+    }
+
     fn from_syntax(
         file_id: FileId,
         name: SmolStr,
@@ -924,4 +951,33 @@ struct Foo;
         let navs = analysis.symbol_search(Query::new("foo".to_owned()), !0).unwrap();
         assert_eq!(navs.len(), 2)
     }
+
+    #[test]
+    fn test_world_symbols_kind_filter() {
+        let (analysis, _) = fixture::file(
+            r#"
+fn parse() {}
+struct Parse;
+mod parse {}
+struct ConfigData;
+fn config() {}
+"#,
+        );
+
+        let search = |query: &str| {
+            let (kind, query) = ide_db::symbol_index::split_kind_prefix(query);
+            let mut query = Query::new(query.to_owned());
+            if let Some(kind) = kind {
+                query.only_kind(kind);
+            }
+            let navs = analysis.symbol_search(query, !0).unwrap();
+            navs.into_iter()
+                .map(|nav| format!("{:?} {}", nav.kind.unwrap(), nav.name))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(search("fn:parse"), ["Function parse"]);
+        assert_eq!(search("mod: parse"), ["Module parse"]);
+        assert_eq!(search("struct:Cfg"), ["Struct ConfigData"]);
+        assert_eq!(search("parse").len(), 3);
+    }
 }
@@ -1028,6 +1028,54 @@ fn foo() {
         );
     }
 
+    #[test]
+    fn test_highlight_compound_assignment_and_mut_borrows() {
+        check(
+            r#"
+struct Counter;
+impl Counter {
+    fn bump(&mut self) {}
+    fn get(&self) -> u32 { 0 }
+}
+
+fn foo(mut c: Counter) {
+    let mut i$0 = c.get();
+    i += 1;
+    let r = &mut i;
+    let s = &i;
+    c.bump();
+}
+"#,
+            expect![[r#"
+                i Local FileId(0) 124..129 128..129 write
+
+                FileId(0) 145..146 write read
+                FileId(0) 170..171 write
+                FileId(0) 186..187 read
+            "#]],
+        );
+        check(
+            r#"
+struct Counter;
+impl Counter {
+    fn bump(&mut self) {}
+    fn get(&self) -> u32 { 0 }
+}
+
+fn foo(mut c$0: Counter) {
+    let i = c.get();
+    c.bump();
+}
+"#,
+            expect![[r#"
+                c ValueParam FileId(0) 98..103 102..103 write
+
+                FileId(0) 128..129 read
+                FileId(0) 141..142 write
+            "#]],
+        );
+    }
+
     #[test]
     fn test_find_struct_function_refs_outside_module() {
         check(
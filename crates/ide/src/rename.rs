@@ -6,7 +6,7 @@
 
 use hir::{AsAssocItem, HirFileIdExt, InFile, Semantics};
 use ide_db::{
-    base_db::{FileId, FileRange},
+    base_db::{FileId, FileRange, SourceDatabaseExt},
     defs::{Definition, NameClass, NameRefClass},
     rename::{bail, format_err, source_edit_from_references, IdentifierKind},
     source_change::SourceChangeBuilder,
@@ -15,32 +15,71 @@ use ide_db::{
 use itertools::Itertools;
 use stdx::{always, never};
 use syntax::{
-    ast, utils::is_raw_identifier, AstNode, SmolStr, SyntaxKind, SyntaxNode, TextRange, TextSize,
+    ast::{self, HasAttrs},
+    utils::is_raw_identifier,
+    AstNode, AstToken, SmolStr, SyntaxKind, SyntaxNode, TextRange, TextSize, T,
 };
 
 use text_edit::TextEdit;
 
-use crate::{FilePosition, RangeInfo, SourceChange};
+use crate::{doc_links::token_as_doc_comment, FilePosition, RangeInfo, SourceChange};
 
 pub use ide_db::rename::RenameError;
 
 type RenameResult<T> = Result<T, RenameError>;
 
 /// Prepares a rename. The sole job of this function is to return the TextRange of the thing that is
-/// being targeted for a rename.
+/// being targeted for a rename, together with its current name as a placeholder for the new one.
 pub(crate) fn prepare_rename(
     db: &RootDatabase,
     position: FilePosition,
-) -> RenameResult<RangeInfo<()>> {
+) -> RenameResult<RangeInfo<String>> {
     let sema = Semantics::new(db);
     let source_file = sema.parse(position.file_id);
     let syntax = source_file.syntax();
 
+    // `self` can be renamed to a regular parameter and `Self` is reported below.
+    let tokens = syntax.token_at_offset(position.offset);
+    if !tokens.clone().any(|t| matches!(t.kind(), SyntaxKind::IDENT | SyntaxKind::LIFETIME_IDENT)) {
+        if let Some(keyword) = tokens
+            .into_iter()
+            .find(|t| t.kind().is_keyword() && !matches!(t.kind(), T![self] | T![Self]))
+        {
+            bail!("Cannot rename keyword `{}`", keyword.text())
+        }
+    }
+
     let res = find_definitions(&sema, syntax, position)?
-        .map(|(frange, kind, def)| {
+        .map(|(frange, _, def)| {
             // ensure all ranges are valid
 
             if def.range_for_rename(&sema).is_none() {
+                match def {
+                    Definition::BuiltinType(_) => bail!("Cannot rename builtin type"),
+                    Definition::BuiltinAttr(_) => bail!("Cannot rename a builtin attr."),
+                    Definition::ToolModule(_) => bail!("Cannot rename a tool module"),
+                    Definition::SelfType(_) => bail!("Cannot rename `Self`"),
+                    Definition::Function(_)
+                    | Definition::Adt(_)
+                    | Definition::Variant(_)
+                    | Definition::Const(_)
+                    | Definition::Static(_)
+                    | Definition::Trait(_)
+                    | Definition::TraitAlias(_)
+                    | Definition::TypeAlias(_)
+                    | Definition::Macro(_)
+                    | Definition::Local(_)
+                    | Definition::GenericParam(_)
+                    | Definition::Label(_) => {
+                        if let Some(name) = def.name(db) {
+                            bail!(
+                                "Cannot rename `{}` as it is generated by a macro",
+                                name.display(db)
+                            )
+                        }
+                    }
+                    _ => (),
+                }
                 bail!("No references found at position")
             }
 
@@ -49,12 +88,7 @@ pub(crate) fn prepare_rename(
                     && frange.file_id == position.file_id
             );
 
-            Ok(match kind {
-                SyntaxKind::LIFETIME => {
-                    TextRange::new(frange.range.start() + TextSize::from(1), frange.range.end())
-                }
-                _ => frange.range,
-            })
+            Ok(frange.range)
         })
         .reduce(|acc, cur| match (acc, cur) {
             // ensure all ranges are the same
@@ -65,7 +99,10 @@ pub(crate) fn prepare_rename(
 
     match res {
         // ensure at least one definition was found
-        Some(res) => res.map(|range| RangeInfo::new(range, ())),
+        Some(res) => res.map(|range| {
+            let name = db.file_text(position.file_id)[range].to_owned();
+            RangeInfo::new(range, name)
+        }),
         None => bail!("No references found at position"),
     }
 }
@@ -138,7 +175,11 @@ pub(crate) fn rename(
                         return rename_to_self(&sema, local);
                     }
                 }
-                def.rename(&sema, new_name)
+                let change = def.rename(&sema, new_name)?;
+                Ok(match rename_doc_links(&sema, def, new_name) {
+                    Some(links) => change.merge(links),
+                    None => change,
+                })
             })
             .collect(),
     };
@@ -149,6 +190,68 @@ pub(crate) fn rename(
         .ok_or_else(|| format_err!("No references found at position"))
 }
 
+/// Renames the intra-doc links resolving to `def`, like `[foo]`, `[foo()]` or `[crate::foo]`.
+fn rename_doc_links(
+    sema: &Semantics<'_, RootDatabase>,
+    def: Definition,
+    new_name: &str,
+) -> Option<SourceChange> {
+    let old_name = def.name(sema.db)?.unescaped().to_smol_str();
+    let new_name = new_name.trim_start_matches("r#");
+    let mut change = SourceChange::default();
+    for (file_id, range) in def.search_scope(sema.db) {
+        if !sema.db.file_text(file_id).contains(&*old_name) {
+            continue;
+        }
+        let mut builder = TextEdit::builder();
+        let tokens = sema
+            .parse(file_id)
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+            .filter(|token| matches!(token.kind(), SyntaxKind::COMMENT | SyntaxKind::STRING))
+            .filter(|token| range.map_or(true, |range| range.contains_range(token.text_range())));
+        for token in tokens {
+            let text = token.text();
+            for (idx, _) in text.match_indices(&*old_name) {
+                if !is_last_path_segment(text, idx, old_name.len()) {
+                    continue;
+                }
+                let Some(doc_token) = token_as_doc_comment(&token) else { break };
+                let occurrence = TextRange::at(
+                    token.text_range().start() + TextSize::from(idx as u32),
+                    TextSize::of(&*old_name),
+                );
+                let links_to_def = doc_token.get_definition_with_descend_at(
+                    sema,
+                    occurrence.start(),
+                    |link_def, _, link_range| {
+                        (link_def == def && link_range.contains_range(occurrence)).then_some(())
+                    },
+                );
+                if links_to_def.is_some() {
+                    builder.replace(occurrence, new_name.to_owned());
+                }
+            }
+        }
+        let edit = builder.finish();
+        if !edit.is_empty() {
+            change.insert_source_edit(file_id, edit);
+        }
+    }
+    Some(change)
+}
+
+/// Checks that the name at `idx` is a whole identifier that isn't followed by further path
+/// segments, as in `[foo]` or `[mod::foo()]` but not `[foo::bar]` or `[foobar]`.
+fn is_last_path_segment(text: &str, idx: usize, len: usize) -> bool {
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+    let (before, after) = (&text[..idx], &text[idx + len..]);
+    !before.ends_with(is_ident_char)
+        && !after.starts_with(is_ident_char)
+        && !after.starts_with("::")
+}
+
 /// Called by the client when it is about to rename a file.
 pub(crate) fn will_rename_file(
     db: &RootDatabase,
@@ -157,6 +260,15 @@ pub(crate) fn will_rename_file(
 ) -> Option<SourceChange> {
     let sema = Semantics::new(db);
     let module = sema.file_to_module_def(file_id)?;
+    if let Some(src) = module.declaration_source(db) {
+        // The module name is unrelated to the file name then, so only the attribute has to change.
+        if let Some(path) =
+            src.value.attrs().find(|attr| attr.simple_name().as_deref() == Some("path"))
+        {
+            let file_id = src.file_id.file_id()?;
+            return rename_path_attr(file_id, path, new_name_stem);
+        }
+    }
     let def = Definition::Module(module);
     let mut change = if is_raw_identifier(new_name_stem) {
         def.rename(&sema, &SmolStr::from_iter(["r#", new_name_stem])).ok()?
@@ -167,6 +279,39 @@ pub(crate) fn will_rename_file(
     Some(change)
 }
 
+/// Points the `#[path]` attribute of a module at the renamed file, or at the renamed directory if
+/// the attribute names a `mod.rs` file.
+fn rename_path_attr(file_id: FileId, attr: ast::Attr, new_name_stem: &str) -> Option<SourceChange> {
+    let ast::Expr::Literal(literal) = attr.expr()? else {
+        return None;
+    };
+    let ast::LiteralKind::String(string) = literal.kind() else {
+        return None;
+    };
+    let path = string.value()?;
+    let (dir, file_name) = match path.rsplit_once('/') {
+        Some((dir, file_name)) => (Some(dir), file_name),
+        None => (None, &*path),
+    };
+    let new_path = match file_name.rsplit_once('.')? {
+        ("mod", _) => {
+            let parent = dir?.rsplit_once('/').map(|(parent, _)| format!("{parent}/"));
+            format!("{}{new_name_stem}/{file_name}", parent.unwrap_or_default())
+        }
+        (_, extension) => {
+            let dir = dir.map(|dir| format!("{dir}/"));
+            format!("{}{new_name_stem}.{extension}", dir.unwrap_or_default())
+        }
+    };
+
+    let mut change = SourceChange::default();
+    change.insert_source_edit(
+        file_id,
+        TextEdit::replace(string.syntax().text_range(), format!("{new_path:?}")),
+    );
+    Some(change)
+}
+
 // FIXME: Should support `extern crate`.
 fn alias_fallback(
     syntax: &SyntaxNode,
@@ -509,9 +654,10 @@ mod tests {
             .prepare_rename(position)
             .unwrap_or_else(|err| panic!("PrepareRename was cancelled: {err}"));
         match result {
-            Ok(RangeInfo { range, info: () }) => {
+            Ok(RangeInfo { range, info: placeholder }) => {
                 let source = analysis.file_text(position.file_id).unwrap();
-                expect.assert_eq(&format!("{range:?}: {}", &source[range]))
+                assert_eq!(placeholder, &source[range]);
+                expect.assert_eq(&format!("{range:?}: {placeholder}"))
             }
             Err(RenameError(err)) => expect.assert_eq(&err),
         };
@@ -533,7 +679,7 @@ mod tests {
     #[test]
     fn test_prepare_rename_namelikes() {
         check_prepare(r"fn name$0<'lifetime>() {}", expect![[r#"3..7: name"#]]);
-        check_prepare(r"fn name<'lifetime$0>() {}", expect![[r#"9..17: lifetime"#]]);
+        check_prepare(r"fn name<'lifetime$0>() {}", expect![[r#"8..17: 'lifetime"#]]);
         check_prepare(r"fn name<'lifetime>() { name$0(); }", expect![[r#"23..27: name"#]]);
     }
 
@@ -552,7 +698,27 @@ foo!(Foo$0);",
 
     #[test]
     fn test_prepare_rename_keyword() {
-        check_prepare(r"struct$0 Foo;", expect![[r#"No references found at position"#]]);
+        check_prepare(r"struct$0 Foo;", expect![[r#"Cannot rename keyword `struct`"#]]);
+        check_prepare(r"use crate$0::foo;", expect![[r#"Cannot rename keyword `crate`"#]]);
+        check_prepare(r"fn f(self$0) {}", expect![[r#"5..9: self"#]]);
+    }
+
+    #[test]
+    fn test_prepare_rename_macro_generated() {
+        check_prepare(
+            r#"
+macro_rules! make {
+    () => {
+        fn generated() {}
+    };
+}
+make!();
+fn f() {
+    generated$0();
+}
+"#,
+            expect![[r#"Cannot rename `generated` as it is generated by a macro"#]],
+        );
     }
 
     #[test]
@@ -578,7 +744,7 @@ fn foo() {
     let x: i32$0 = 0;
 }
 "#,
-            expect![[r#"No references found at position"#]],
+            expect![[r#"Cannot rename builtin type"#]],
         );
     }
 
@@ -594,7 +760,7 @@ impl Foo {
     }
 }
 "#,
-            expect![[r#"No references found at position"#]],
+            expect![[r#"Cannot rename `Self`"#]],
         );
     }
 
@@ -1402,6 +1568,62 @@ mod bar$0;
         )
     }
 
+    #[test]
+    fn test_will_rename_file_with_path_attr() {
+        check_expect_will_rename_file(
+            "tools",
+            r#"
+//- /lib.rs
+#[path = "util/helpers.rs"]
+mod helpers;
+//- /util/helpers.rs
+$0
+"#,
+            expect![[r#"
+                source_file_edits: [
+                    (
+                        FileId(
+                            0,
+                        ),
+                        [
+                            Indel {
+                                insert: "\"util/tools.rs\"",
+                                delete: 9..26,
+                            },
+                        ],
+                    ),
+                ]
+                file_system_edits: []
+            "#]],
+        );
+        check_expect_will_rename_file(
+            "deep",
+            r#"
+//- /lib.rs
+#[path = "nested/inner/mod.rs"]
+mod inner;
+//- /nested/inner/mod.rs
+$0
+"#,
+            expect![[r#"
+                source_file_edits: [
+                    (
+                        FileId(
+                            0,
+                        ),
+                        [
+                            Indel {
+                                insert: "\"nested/deep/mod.rs\"",
+                                delete: 9..30,
+                            },
+                        ],
+                    ),
+                ]
+                file_system_edits: []
+            "#]],
+        );
+    }
+
     #[test]
     fn test_rename_mod_to_raw_ident() {
         check_expect(
@@ -2779,4 +3001,87 @@ fn main() { let _: Baz; }
 "#,
         )
     }
+
+    #[test]
+    fn rename_updates_intra_doc_links() {
+        check(
+            "bar",
+            r#"
+/// Calls [foo], [`foo`], [foo()], [fn@foo], [crate::foo], and [the function](foo).
+/// Mentions foo and [foobar].
+fn foo$0() {}
+fn foobar() {}
+mod m {
+    /// See [super::foo].
+    fn g() {}
+}
+"#,
+            r#"
+/// Calls [bar], [`bar`], [bar()], [fn@bar], [crate::bar], and [the function](bar).
+/// Mentions foo and [foobar].
+fn bar() {}
+fn foobar() {}
+mod m {
+    /// See [super::bar].
+    fn g() {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn rename_skips_intra_doc_links_resolving_elsewhere() {
+        check(
+            "bar",
+            r#"
+mod m {
+    pub fn foo() {}
+}
+/// See [m::foo] and [foo].
+struct S;
+fn foo$0() {}
+/// See [foo].
+#[doc = "Also [foo]."]
+fn other() {}
+"#,
+            r#"
+mod m {
+    pub fn foo() {}
+}
+/// See [m::foo] and [bar].
+struct S;
+fn bar() {}
+/// See [bar].
+#[doc = "Also [bar]."]
+fn other() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn rename_method_updates_intra_doc_links() {
+        check(
+            "run",
+            r#"
+struct S;
+impl S {
+    /// Like [Self::go], but also see [S::go()].
+    fn go$0(&self) {}
+    fn go_twice(&self) {}
+}
+/// [S::go_twice]
+fn f() {}
+"#,
+            r#"
+struct S;
+impl S {
+    /// Like [Self::run], but also see [S::run()].
+    fn run(&self) {}
+    fn go_twice(&self) {}
+}
+/// [S::go_twice]
+fn f() {}
+"#,
+        );
+    }
 }
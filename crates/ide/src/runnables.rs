@@ -9,16 +9,16 @@ use ide_assists::utils::{has_test_related_attribute, test_related_attribute_syn}
 use ide_db::{
     base_db::{FilePosition, FileRange},
     defs::Definition,
-    documentation::docs_from_attrs,
+    documentation::{docs_from_attrs, docs_with_rangemap},
     helpers::visit_file_defs,
     search::{FileReferenceNode, SearchScope},
-    FxHashMap, FxHashSet, RootDatabase, SymbolKind,
+    FxHashMap, FxHashSet, LineIndexDatabase, RootDatabase, SymbolKind,
 };
 use itertools::Itertools;
 use stdx::{always, format_to};
 use syntax::{
-    ast::{self, AstNode},
-    SmolStr, SyntaxNode,
+    ast::{self, make, AstNode},
+    format_smolstr, SmolStr, SyntaxKind, SyntaxNode, TextRange, TextSize, T,
 };
 
 use crate::{references, FileId, NavigationTarget, ToNav, TryToNav};
@@ -126,6 +126,7 @@ pub(crate) fn runnables(db: &RootDatabase, file_id: FileId) -> Vec<Runnable> {
     let sema = Semantics::new(db);
 
     let mut res = Vec::new();
+    let mut criterion_benches = CriterionBenches::default();
     // Record all runnables that come from macro expansions here instead.
     // In case an expansion creates multiple runnables we want to name them to avoid emitting a bunch of equally named runnables.
     let mut in_macro_expansion = FxHashMap::<hir::HirFileId, Vec<Runnable>>::default();
@@ -157,26 +158,35 @@ pub(crate) fn runnables(db: &RootDatabase, file_id: FileId) -> Vec<Runnable> {
     visit_file_defs(&sema, file_id, &mut |def| {
         let runnable = match def {
             Definition::Module(it) => runnable_mod(&sema, it),
-            Definition::Function(it) => runnable_fn(&sema, it),
+            Definition::Function(it) => runnable_fn_with_benches(&sema, it, &mut criterion_benches),
             Definition::SelfType(impl_) => runnable_impl(&sema, &impl_),
             _ => None,
         };
-        add_opt(
-            runnable
-                .or_else(|| module_def_doctest(sema.db, def))
-                // #[macro_export] mbe macros are declared in the root, while their definition may reside in a different module
-                .filter(|it| it.nav.file_id == file_id),
-            Some(def),
-        );
+        let runnable = runnable
+            .or_else(|| module_def_doctest(sema.db, def))
+            // #[macro_export] mbe macros are declared in the root, while their definition may reside in a different module
+            .filter(|it| it.nav.file_id == file_id);
+        if let Some(runnable) = &runnable {
+            doc_test_code_blocks(sema.db, def, runnable)
+                .into_iter()
+                .for_each(|it| add_opt(Some(it), Some(def)));
+        }
+        add_opt(runnable, Some(def));
         if let Definition::SelfType(impl_) = def {
             impl_.items(db).into_iter().for_each(|assoc| {
                 let runnable = match assoc {
                     hir::AssocItem::Function(it) => {
-                        runnable_fn(&sema, it).or_else(|| module_def_doctest(sema.db, it.into()))
+                        runnable_fn_with_benches(&sema, it, &mut criterion_benches)
+                            .or_else(|| module_def_doctest(sema.db, it.into()))
                     }
                     hir::AssocItem::Const(it) => module_def_doctest(sema.db, it.into()),
                     hir::AssocItem::TypeAlias(it) => module_def_doctest(sema.db, it.into()),
                 };
+                if let Some(runnable) = &runnable {
+                    doc_test_code_blocks(sema.db, assoc.into(), runnable)
+                        .into_iter()
+                        .for_each(|it| add_opt(Some(it), Some(assoc.into())));
+                }
                 add_opt(runnable, Some(assoc.into()))
             });
         }
@@ -306,6 +316,14 @@ fn parent_test_module(sema: &Semantics<'_, RootDatabase>, fn_def: &ast::Fn) -> O
 pub(crate) fn runnable_fn(
     sema: &Semantics<'_, RootDatabase>,
     def: hir::Function,
+) -> Option<Runnable> {
+    runnable_fn_with_benches(sema, def, &mut CriterionBenches::default())
+}
+
+fn runnable_fn_with_benches(
+    sema: &Semantics<'_, RootDatabase>,
+    def: hir::Function,
+    criterion_benches: &mut CriterionBenches,
 ) -> Option<Runnable> {
     let under_cfg_test = has_cfg_test(def.module(sema.db).attrs(sema.db));
     let kind = if !under_cfg_test && def.is_main(sema.db) {
@@ -326,6 +344,9 @@ pub(crate) fn runnable_fn(
             RunnableKind::Test { test_id: test_id(), attr }
         } else if def.is_bench(sema.db) {
             RunnableKind::Bench { test_id: test_id() }
+        } else if criterion_benches.contains(sema, def) {
+            // Criterion filters benchmarks by substring, and doesn't know about module paths.
+            RunnableKind::Bench { test_id: TestId::Name(def.name(sema.db).to_smol_str()) }
         } else {
             return None;
         }
@@ -341,6 +362,89 @@ pub(crate) fn runnable_fn(
     Some(Runnable { use_name_in_title: false, nav, kind, cfg })
 }
 
+/// Functions registered as benchmarks of `harness = false` bench targets, through
+/// `criterion_group!(group, bench_a, bench_b)` or `criterion_group! { name = group; config = ..;
+/// targets = bench_a, bench_b }` invocations in the crate root, collected once per crate.
+#[derive(Default)]
+struct CriterionBenches(FxHashMap<hir::Crate, FxHashSet<hir::Function>>);
+
+impl CriterionBenches {
+    fn contains(&mut self, sema: &Semantics<'_, RootDatabase>, def: hir::Function) -> bool {
+        let krate = def.module(sema.db).krate();
+        self.0.entry(krate).or_insert_with(|| criterion_benches(sema, krate)).contains(&def)
+    }
+}
+
+fn criterion_benches(
+    sema: &Semantics<'_, RootDatabase>,
+    krate: hir::Crate,
+) -> FxHashSet<hir::Function> {
+    let root = sema.parse(krate.root_file(sema.db));
+    root.syntax()
+        .descendants()
+        .filter_map(ast::MacroCall::cast)
+        .filter(|call| {
+            sema.resolve_macro_call(call).map_or(false, |mac| {
+                mac.name(sema.db).as_str() == Some("criterion_group")
+                    && mac
+                        .module(sema.db)
+                        .krate()
+                        .display_name(sema.db)
+                        .map_or(false, |name| name.canonical_name() == "criterion")
+            })
+        })
+        .flat_map(|call| {
+            let scope = sema.scope(call.syntax());
+            let targets = call.token_tree().map(|tt| criterion_targets(&tt)).unwrap_or_default();
+            targets.into_iter().filter_map(move |target| {
+                match scope.as_ref()?.speculative_resolve(&make::path_from_text(&target))? {
+                    hir::PathResolution::Def(hir::ModuleDef::Function(it)) => Some(it),
+                    _ => None,
+                }
+            })
+        })
+        .filter(|it| it.as_assoc_item(sema.db).is_none())
+        .collect()
+}
+
+/// Returns the paths of the benchmark functions listed in a `criterion_group!` invocation.
+fn criterion_targets(tt: &ast::TokenTree) -> Vec<String> {
+    let tokens: Vec<_> = tt
+        .syntax()
+        .children_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| {
+            !it.kind().is_trivia() && !matches!(it.kind(), T!['('] | T![')'] | T!['{'] | T!['}'])
+        })
+        .collect();
+    let targets = match tokens.iter().position(|it| it.text() == "targets") {
+        Some(idx) => {
+            let rest = &tokens[idx + 1..];
+            let end = rest.iter().position(|it| it.kind() == T![;]).unwrap_or(rest.len());
+            // Skip the `=`.
+            rest.get(1..end).unwrap_or_default()
+        }
+        // The first argument names the group.
+        None => match tokens.iter().position(|it| it.kind() == T![,]) {
+            Some(idx) => &tokens[idx + 1..],
+            None => &[],
+        },
+    };
+    targets
+        .split(|it| it.kind() == T![,])
+        .filter(|path| {
+            !path.is_empty()
+                && path.iter().all(|it| {
+                    matches!(
+                        it.kind(),
+                        SyntaxKind::IDENT | T![:] | T![::] | T![crate] | T![self] | T![super]
+                    )
+                })
+        })
+        .map(|path| path.iter().map(|it| it.text()).collect())
+        .collect()
+}
+
 pub(crate) fn runnable_mod(
     sema: &Semantics<'_, RootDatabase>,
     def: hir::Module,
@@ -423,7 +527,7 @@ fn runnable_mod_outline_definition(
     }
 }
 
-fn module_def_doctest(db: &RootDatabase, def: Definition) -> Option<Runnable> {
+fn doc_test_attrs(db: &RootDatabase, def: Definition) -> Option<AttrsWithOwner> {
     let attrs = match def {
         Definition::Module(it) => it.attrs(db),
         Definition::Function(it) => it.attrs(db),
@@ -438,6 +542,11 @@ fn module_def_doctest(db: &RootDatabase, def: Definition) -> Option<Runnable> {
         Definition::SelfType(it) => it.attrs(db),
         _ => return None,
     };
+    Some(attrs)
+}
+
+fn module_def_doctest(db: &RootDatabase, def: Definition) -> Option<Runnable> {
+    let attrs = doc_test_attrs(db, def)?;
     if !has_runnable_doc_test(&attrs) {
         return None;
     }
@@ -503,27 +612,73 @@ const RUSTDOC_CODE_BLOCK_ATTRIBUTES_RUNNABLE: &[&str] =
     &["", "rust", "should_panic", "edition2015", "edition2018", "edition2021"];
 
 fn has_runnable_doc_test(attrs: &hir::Attrs) -> bool {
-    docs_from_attrs(attrs).map_or(false, |doc| {
-        let mut in_code_block = false;
+    docs_from_attrs(attrs).map_or(false, |doc| runnable_code_block_fences(&doc).next().is_some())
+}
 
-        for line in doc.lines() {
-            if let Some(header) =
-                RUSTDOC_FENCES.into_iter().find_map(|fence| line.strip_prefix(fence))
-            {
-                in_code_block = !in_code_block;
+/// Returns the ranges of the opening fences of all runnable code blocks in `doc`.
+fn runnable_code_block_fences(doc: &str) -> impl Iterator<Item = TextRange> + '_ {
+    let mut in_code_block = false;
+    let mut offset = TextSize::from(0);
+    doc.split('\n').filter_map(move |line| {
+        let range = TextRange::at(offset, TextSize::of(line));
+        offset += TextSize::of(line) + TextSize::of('\n');
+
+        let header = RUSTDOC_FENCES.into_iter().find_map(|fence| line.strip_prefix(fence))?;
+        in_code_block = !in_code_block;
+        let runnable = in_code_block
+            && header
+                .split(',')
+                .all(|sub| RUSTDOC_CODE_BLOCK_ATTRIBUTES_RUNNABLE.contains(&sub.trim()));
+        runnable.then_some(range)
+    })
+}
 
-                if in_code_block
-                    && header
-                        .split(',')
-                        .all(|sub| RUSTDOC_CODE_BLOCK_ATTRIBUTES_RUNNABLE.contains(&sub.trim()))
-                {
-                    return true;
-                }
-            }
-        }
+/// Creates a runnable for each individual doctest of `def` if it has more than one, so that they
+/// can be run separately from the `item_runnable` that runs all of them.
+fn doc_test_code_blocks(
+    db: &RootDatabase,
+    def: Definition,
+    item_runnable: &Runnable,
+) -> Vec<Runnable> {
+    let RunnableKind::DocTest { test_id } = &item_runnable.kind else {
+        return Vec::new();
+    };
+    let Some((docs, doc_mapping)) =
+        doc_test_attrs(db, def).and_then(|attrs| docs_with_rangemap(db, &attrs))
+    else {
+        return Vec::new();
+    };
+    let fences: Vec<_> = runnable_code_block_fences(docs.as_str()).collect();
+    if fences.len() < 2 {
+        return Vec::new();
+    }
 
-        false
-    })
+    let file_id = item_runnable.nav.file_id;
+    let line_index = db.line_index(file_id);
+    fences
+        .into_iter()
+        .filter_map(|fence| {
+            let range = doc_mapping.map(fence)?;
+            if range.file_id != file_id.into() {
+                return None;
+            }
+            // rustdoc names doctests after the (one-based) line of their opening fence
+            let line = line_index.line_col(range.value.start()).line + 1;
+            let test_id = match test_id {
+                TestId::Name(name) => TestId::Name(format_smolstr!("{name} (line {line})")),
+                TestId::Path(path) => TestId::Path(format!("{path} (line {line})")),
+            };
+            let mut nav = item_runnable.nav.clone();
+            nav.full_range = range.value;
+            nav.focus_range = None;
+            Some(Runnable {
+                use_name_in_title: false,
+                nav,
+                kind: RunnableKind::DocTest { test_id },
+                cfg: item_runnable.cfg.clone(),
+            })
+        })
+        .collect()
 }
 
 // We could create runnables for modules with number_of_test_submodules > 0,
@@ -640,6 +795,50 @@ mod not_a_root {
         );
     }
 
+    #[test]
+    fn test_runnables_criterion_benches() {
+        check(
+            r#"
+//- /main.rs crate:main deps:criterion
+$0
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn parse(c: &mut Criterion) {}
+fn format(c: &mut Criterion) {}
+fn helper() {}
+mod inner {
+    pub fn lex(c: &mut Criterion) {}
+    pub fn parse(c: &mut Criterion) {}
+}
+
+criterion_group!(benches, parse, inner::lex);
+criterion_group! {
+    name = more_benches;
+    config = Criterion::default();
+    targets = format
+}
+criterion_main!(benches, more_benches);
+//- /criterion.rs crate:criterion
+pub struct Criterion;
+#[macro_export]
+macro_rules! criterion_group {
+    ($($tt:tt)*) => {};
+}
+#[macro_export]
+macro_rules! criterion_main {
+    ($($tt:tt)*) => {};
+}
+"#,
+            expect![[r#"
+                [
+                    "(Bench, NavigationTarget { file_id: FileId(0), full_range: 63..93, focus_range: 66..71, name: \"parse\", kind: Function })",
+                    "(Bench, NavigationTarget { file_id: FileId(0), full_range: 94..125, focus_range: 97..103, name: \"format\", kind: Function })",
+                    "(Bench, NavigationTarget { file_id: FileId(0), full_range: 157..189, focus_range: 164..167, name: \"lex\", kind: Function })",
+                ]
+            "#]],
+        );
+    }
+
     #[test]
     fn test_runnables_doc_test() {
         check(
@@ -752,6 +951,42 @@ impl Test for StructWithRunnable {}
         );
     }
 
+    #[test]
+    fn test_runnables_doc_test_code_blocks() {
+        let fixture = r#"
+//- /lib.rs
+$0
+/// ```
+/// let x = 5;
+/// ```
+///
+/// ```no_run
+/// let y = 6;
+/// ```
+///
+/// ```rust
+/// let z = 7;
+/// ```
+fn foo() {}
+"#;
+        check(
+            fixture,
+            expect![[r#"
+            [
+                "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 1..123, name: \"foo\" })",
+                "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 5..8, name: \"foo\" })",
+                "(DocTest, NavigationTarget { file_id: FileId(0), full_range: 81..88, name: \"foo\" })",
+            ]
+        "#]],
+        );
+
+        let (analysis, position) = fixture::position(fixture);
+        let mut runnables = analysis.runnables(position.file_id).unwrap();
+        runnables.sort_by_key(|it| it.nav.full_range.start());
+        let labels = runnables.iter().map(|it| it.label(None)).collect::<Vec<_>>();
+        assert_eq!(labels, ["doctest foo", "doctest foo (line 2)", "doctest foo (line 10)"]);
+    }
+
     #[test]
     fn test_runnables_doc_test_in_impl() {
         check(
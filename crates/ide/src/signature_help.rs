@@ -5,8 +5,8 @@ use std::collections::BTreeSet;
 
 use either::Either;
 use hir::{
-    AssocItem, DescendPreference, GenericParam, HirDisplay, ModuleDef, PathResolution, Semantics,
-    Trait,
+    AssocItem, DescendPreference, GenericParam, HasSource, HirDisplay, ModuleDef, PathResolution,
+    Semantics, Trait,
 };
 use ide_db::{
     active_parameter::{callable_for_node, generic_def_for_node},
@@ -14,6 +14,7 @@ use ide_db::{
     documentation::{Documentation, HasDocs},
     FxIndexMap,
 };
+use itertools::Itertools;
 use stdx::format_to;
 use syntax::{
     algo,
@@ -82,6 +83,7 @@ pub(crate) fn signature_help(
         // if the cursor is sandwiched between two space tokens and the call is unclosed
         // this prevents us from leaving the CallExpression
         .and_then(|tok| algo::skip_trivia_token(tok, Direction::Prev))?;
+    let original_token = token.clone();
     let token = sema.descend_into_macros_single(DescendPreference::None, token);
 
     for node in token.parent_ancestors() {
@@ -136,6 +138,9 @@ pub(crate) fn signature_help(
                     }
                     return signature_help_for_tuple_expr(&sema, tuple_expr, token);
                 },
+                ast::MacroCall(macro_call) => {
+                    return signature_help_for_macro_call(&sema, macro_call, token);
+                },
                 _ => (),
             }
         }
@@ -146,12 +151,15 @@ pub(crate) fn signature_help(
             if !matches!(expr, ast::Expr::RecordExpr(..))
                 && expr.syntax().text().contains_char('\n')
             {
-                break;
+                return None;
             }
         }
     }
 
-    None
+    // The token was mapped into a macro expansion without hitting anything of interest, so show
+    // the signature of the macro call it came from.
+    let macro_call = original_token.parent_ancestors().find_map(ast::MacroCall::cast)?;
+    signature_help_for_macro_call(&sema, macro_call, original_token)
 }
 
 fn signature_help_for_call(
@@ -375,6 +383,115 @@ fn add_assoc_type_bindings(
     }
 }
 
+fn signature_help_for_macro_call(
+    sema: &Semantics<'_, RootDatabase>,
+    macro_call: ast::MacroCall,
+    token: SyntaxToken,
+) -> Option<SignatureHelp> {
+    let token_tree = macro_call.token_tree()?;
+    let (l_delim, r_delim) =
+        (token_tree.left_delimiter_token()?, token_tree.right_delimiter_token());
+    let cursor_outside = token.text_range().start() < l_delim.text_range().start()
+        || r_delim.as_ref() == Some(&token);
+    if cursor_outside {
+        return None;
+    }
+
+    let db = sema.db;
+    let mac = sema.resolve_macro_call(&macro_call)?;
+    let mut res = SignatureHelp {
+        doc: mac.docs(db),
+        signature: format!("{}!", mac.name(db).display(db)),
+        active_parameter: None,
+        parameters: vec![],
+    };
+    let (open, close) = match l_delim.kind() {
+        T!['['] => ("[", "]"),
+        T!['{'] => ("{", "}"),
+        _ => ("(", ")"),
+    };
+
+    let Some(arms) = macro_matchers(db, mac) else {
+        // Function-like proc macros don't have matchers we could show, so we only have the docs.
+        res.signature.push_str(open);
+        res.signature.push_str("..");
+        res.signature.push_str(close);
+        return Some(res);
+    };
+    let arms: Vec<_> = arms.iter().map(matcher_fragments).collect();
+
+    let active_parameter = token_tree
+        .syntax()
+        .children_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .filter(|t| t.kind() == T![,])
+        .take_while(|t| t.text_range().start() <= token.text_range().start())
+        .count();
+
+    // Prefer the arm the call matched, but while the call is still being typed that arm may well
+    // have fewer fragments than what the cursor is at.
+    let arm = sema
+        .resolve_macro_call_arm(&macro_call)
+        .and_then(|idx| arms.get(idx as usize))
+        .filter(|it| it.len() > active_parameter)
+        .or_else(|| arms.iter().find(|it| it.len() > active_parameter))
+        .or_else(|| arms.last())?;
+
+    res.signature.push_str(open);
+    for fragment in arm {
+        res.push_param(open, fragment);
+    }
+    res.signature.push_str(close);
+    if !arm.is_empty() {
+        res.active_parameter = Some(active_parameter.min(arm.len() - 1));
+    }
+    Some(res)
+}
+
+/// Returns the matchers of all arms of the given declarative macro.
+fn macro_matchers(db: &RootDatabase, mac: hir::Macro) -> Option<Vec<ast::TokenTree>> {
+    let rules = match mac.source(db)?.value {
+        Either::Left(ast::Macro::MacroRules(it)) => it.token_tree()?,
+        Either::Left(ast::Macro::MacroDef(it)) => {
+            // `macro m($matcher) { ... }` has a single arm, `macro m { rules }` has several.
+            let mut token_trees = it.syntax().children().filter_map(ast::TokenTree::cast);
+            let first = token_trees.next()?;
+            if token_trees.next().is_some() {
+                return Some(vec![first]);
+            }
+            first
+        }
+        Either::Right(_) => return None,
+    };
+    // The rules are a sequence of `matcher => transcriber;`.
+    Some(rules.syntax().children().filter_map(ast::TokenTree::cast).step_by(2).collect())
+}
+
+/// Splits a matcher at its top-level commas, keeping separators of repetitions like `$($e:expr),*`
+/// intact.
+fn matcher_fragments(matcher: &ast::TokenTree) -> Vec<String> {
+    let mut fragments = Vec::new();
+    let mut current = String::new();
+    let mut elements = matcher.syntax().children_with_tokens().skip(1).peekable();
+    while let Some(element) = elements.next() {
+        if elements.peek().is_none() {
+            // the closing delimiter
+            break;
+        }
+        let is_separator = element.kind() == T![,] && {
+            let next = elements.clone().find(|it| !it.kind().is_trivia());
+            !matches!(next.map(|it| it.kind()), Some(T![*] | T![+] | T![?]))
+        };
+        if is_separator {
+            fragments.push(std::mem::take(&mut current));
+        } else {
+            format_to!(current, "{element}");
+        }
+    }
+    fragments.push(current);
+    fragments.iter().map(|it| it.split_whitespace().join(" ")).filter(|it| !it.is_empty()).collect()
+}
+
 fn signature_help_for_record_lit(
     sema: &Semantics<'_, RootDatabase>,
     record: ast::RecordExpr,
@@ -1339,6 +1456,97 @@ fn test() { S.foo($0); }
         );
     }
 
+    #[test]
+    fn signature_help_for_macro_rules_call() {
+        check(
+            r#"
+/// Adds things.
+macro_rules! add {
+    ($a:expr) => { $a };
+    ($a:expr, $b:expr) => { $a + $b };
+}
+fn main() { add!(1, $0); }
+"#,
+            expect![[r#"
+                Adds things.
+                ------
+                add!($a:expr, $b:expr)
+                     -------  ^^^^^^^
+            "#]],
+        );
+        check(
+            r#"
+macro_rules! add {
+    ($a:expr) => { $a };
+    ($a:expr, $b:expr) => { $a + $b };
+}
+fn main() { add![$0]; }
+"#,
+            expect![[r#"
+                add![$a:expr]
+                     ^^^^^^^
+            "#]],
+        );
+    }
+
+    #[test]
+    fn signature_help_for_macro_call_with_repetition() {
+        check(
+            r#"
+macro_rules! list {
+    ($name:ident: $($item:expr),* $(,)?) => {};
+}
+fn main() { list!(foo: 1, 2, $0); }
+"#,
+            expect![[r#"
+                list!($name:ident: $($item:expr),* $(,)?)
+                      ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+            "#]],
+        );
+    }
+
+    #[test]
+    fn signature_help_for_macro_2_call() {
+        check(
+            r#"
+macro foo($a:ident, $b:ty) {}
+fn main() { foo!(x, $0); }
+"#,
+            expect![[r#"
+                foo!($a:ident, $b:ty)
+                     --------  ^^^^^
+            "#]],
+        );
+    }
+
+    #[test]
+    fn signature_help_for_function_like_proc_macro() {
+        let (db, position) = position(
+            r#"
+//- proc_macros: mirror
+fn main() { proc_macros::mirror!($0); }
+"#,
+        );
+        let sig_help = crate::signature_help::signature_help(&db, position).unwrap();
+        assert_eq!(sig_help.signature, "mirror!(..)");
+        assert_eq!(sig_help.active_parameter, None);
+    }
+
+    #[test]
+    fn call_inside_macro_call_takes_precedence() {
+        check(
+            r#"
+macro_rules! id { ($e:expr) => { $e } }
+fn foo(x: u32) {}
+fn main() { id!(foo($0)); }
+"#,
+            expect![[r#"
+                fn foo(x: u32)
+                       ^^^^^^
+            "#]],
+        );
+    }
+
     #[test]
     fn call_info_for_lambdas() {
         check(
@@ -148,6 +148,9 @@ impl StaticIndex<'_> {
                     closing_brace_hints_min_lines: Some(25),
                     fields_to_resolve: InlayFieldsToResolve::empty(),
                     range_exclusive_hints: false,
+                    numeric_fallback_hints: false,
+                    trait_method_hints: false,
+                    receiver_adjustment_hints: false,
                 },
                 file_id,
                 None,
@@ -1,7 +1,7 @@
 //! Computes color for a single element.
 
 use either::Either;
-use hir::{AsAssocItem, HasVisibility, MacroFileIdExt, Semantics};
+use hir::{AsAssocItem, HasAttrs, HasVisibility, MacroFileIdExt, Semantics};
 use ide_db::{
     defs::{Definition, IdentClass, NameClass, NameRefClass},
     FxHashMap, RootDatabase, SymbolKind,
@@ -91,15 +91,15 @@ fn punctuation(
         (T![?], TRY_EXPR) => HlTag::Operator(HlOperator::Other) | HlMod::ControlFlow,
         (T![&], BIN_EXPR) => HlOperator::Bitwise.into(),
         (T![&], REF_EXPR) => {
-            let h = HlTag::Operator(HlOperator::Other).into();
-            let is_unsafe = parent
-                .and_then(ast::RefExpr::cast)
-                .map(|ref_expr| sema.is_unsafe_ref_expr(&ref_expr));
-            if let Some(true) = is_unsafe {
-                h | HlMod::Unsafe
-            } else {
-                h
+            let mut h = HlTag::Operator(HlOperator::Other).into();
+            let Some(ref_expr) = parent.and_then(ast::RefExpr::cast) else { return h };
+            if ref_expr.raw_token().is_none() && ref_expr.mut_token().is_some() {
+                h |= HlMod::MutableReference;
             }
+            if sema.is_unsafe_ref_expr(&ref_expr) {
+                h |= HlMod::Unsafe;
+            }
+            h
         }
         (T![::] | T![->] | T![=>] | T![..] | T![..=] | T![=] | T![@] | T![.], _) => {
             HlOperator::Other.into()
@@ -268,7 +268,9 @@ fn highlight_name_ref(
                 }
                 Definition::Field(field) => {
                     if let Some(parent) = name_ref.syntax().parent() {
-                        if matches!(parent.kind(), FIELD_EXPR | RECORD_PAT_FIELD) {
+                        if matches!(parent.kind(), FIELD_EXPR | RECORD_PAT_FIELD)
+                            && !is_assignee(&parent)
+                        {
                             if let hir::VariantDef::Union(_) = field.parent_def(db) {
                                 h |= HlMod::Unsafe;
                             }
@@ -287,6 +289,9 @@ fn highlight_name_ref(
                 }
                 _ => (),
             }
+            if is_unsafe_fn_ptr_callee(sema, &name_ref) {
+                h |= HlMod::Unsafe;
+            }
 
             h
         }
@@ -321,6 +326,31 @@ fn highlight_name_ref(
     h
 }
 
+/// Writes to union fields are safe, only reads need an `unsafe` block.
+fn is_assignee(field_expr: &SyntaxNode) -> bool {
+    field_expr.parent().and_then(ast::BinExpr::cast).map_or(false, |bin_expr| {
+        bin_expr.op_kind() == Some(ast::BinaryOp::Assignment { op: None })
+            && bin_expr.lhs().map_or(false, |lhs| lhs.syntax() == field_expr)
+    })
+}
+
+fn is_unsafe_fn_ptr_callee(sema: &Semantics<'_, RootDatabase>, name_ref: &ast::NameRef) -> bool {
+    let Some(path_expr) = ide_db::syntax_helpers::node_ext::full_path_of_name_ref(name_ref)
+        .and_then(|path| path.syntax().parent())
+        .and_then(ast::PathExpr::cast)
+    else {
+        return false;
+    };
+    let callee = ast::Expr::from(path_expr);
+    let is_callee = callee
+        .syntax()
+        .parent()
+        .and_then(ast::CallExpr::cast)
+        .and_then(|call| call.expr())
+        .map_or(false, |expr| expr == callee);
+    is_callee && sema.type_of_expr(&callee).map_or(false, |ty| ty.original.is_unsafe_fn_ptr())
+}
+
 fn highlight_name(
     sema: &Semantics<'_, RootDatabase>,
     bindings_shadow_count: &mut FxHashMap<hir::Name, u32>,
@@ -520,6 +550,9 @@ pub(super) fn highlight_def(
             if local.is_ref(db) || ty.is_reference() {
                 h |= HlMod::Reference;
             }
+            if ty.is_mutable_reference() {
+                h |= HlMod::MutableReference;
+            }
             if ty.as_callable(db).is_some() || ty.impls_fnonce(db) {
                 h |= HlMod::Callable;
             }
@@ -555,9 +588,32 @@ pub(super) fn highlight_def(
         h |= HlMod::DefaultLibrary;
     }
 
+    if is_deprecated(db, def) {
+        h |= HlMod::Deprecated;
+    }
+
     h
 }
 
+fn is_deprecated(db: &RootDatabase, def: Definition) -> bool {
+    let attrs = match def {
+        Definition::Macro(it) => it.attrs(db),
+        Definition::Field(it) => it.attrs(db),
+        Definition::Module(it) => it.attrs(db),
+        Definition::Function(it) => it.attrs(db),
+        Definition::Adt(it) => it.attrs(db),
+        Definition::Variant(it) => it.attrs(db),
+        Definition::Const(it) => it.attrs(db),
+        Definition::Static(it) => it.attrs(db),
+        Definition::Trait(it) => it.attrs(db),
+        Definition::TraitAlias(it) => it.attrs(db),
+        Definition::TypeAlias(it) => it.attrs(db),
+        Definition::ExternCrateDecl(it) => it.attrs(db),
+        _ => return false,
+    };
+    attrs.by_key("deprecated").exists()
+}
+
 fn highlight_method_call_by_name_ref(
     sema: &Semantics<'_, RootDatabase>,
     krate: hir::Crate,
@@ -605,6 +661,10 @@ fn highlight_method_call(
         h |= HlMod::DefaultLibrary;
     }
 
+    if is_deprecated(sema.db, func.into()) {
+        h |= HlMod::Deprecated;
+    }
+
     if let Some(self_param) = func.self_param(sema.db) {
         match self_param.access(sema.db) {
             hir::Access::Shared => h |= HlMod::Reference,
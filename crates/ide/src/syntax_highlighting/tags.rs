@@ -67,6 +67,8 @@ pub enum HlMod {
     /// `foo` in `fn foo(x: i32)` is a definition, `foo` in `foo(90 + 2)` is
     /// not.
     Definition,
+    /// Used for items marked with `#[deprecated]`.
+    Deprecated,
     /// Doc-strings like this one.
     Documentation,
     /// Highlighting injection like rust code in doc strings or ra_fixture.
@@ -81,6 +83,8 @@ pub enum HlMod {
     ProcMacro,
     /// Mutable binding.
     Mutable,
+    /// Mutable reference, or the `&` of a `&mut` borrow.
+    MutableReference,
     /// Used for public items.
     Public,
     /// Immutable reference.
@@ -221,12 +225,14 @@ impl HlMod {
         HlMod::CrateRoot,
         HlMod::DefaultLibrary,
         HlMod::Definition,
+        HlMod::Deprecated,
         HlMod::Documentation,
         HlMod::Injected,
         HlMod::IntraDocLink,
         HlMod::Library,
         HlMod::Macro,
         HlMod::Mutable,
+        HlMod::MutableReference,
         HlMod::ProcMacro,
         HlMod::Public,
         HlMod::Reference,
@@ -247,6 +253,7 @@ impl HlMod {
             HlMod::CrateRoot => "crate_root",
             HlMod::DefaultLibrary => "default_library",
             HlMod::Definition => "declaration",
+            HlMod::Deprecated => "deprecated",
             HlMod::Documentation => "documentation",
             HlMod::Injected => "injected",
             HlMod::IntraDocLink => "intra_doc_link",
@@ -254,6 +261,7 @@ impl HlMod {
             HlMod::Macro => "macro",
             HlMod::ProcMacro => "proc_macro",
             HlMod::Mutable => "mutable",
+            HlMod::MutableReference => "mutable_reference",
             HlMod::Public => "public",
             HlMod::Reference => "reference",
             HlMod::Static => "static",
@@ -589,7 +589,11 @@ impl DoTheAutoref for u16 {
 
 fn main() {
     let x = &5 as *const _ as *const usize;
-    let u = Union { b: 0 };
+    let mut u = Union { b: 0 };
+    let f: unsafe fn() = unsafe_fn;
+
+    // writes to union fields are safe
+    u.a = 1;
 
     id! {
         unsafe { unsafe_deref!() }
@@ -607,6 +611,8 @@ fn main() {
             Union { a } => (),
         }
         Struct { field: 0 }.unsafe_method();
+        f();
+        id!(f());
 
         // unsafe deref
         *x;
@@ -919,6 +925,57 @@ fn main() {
     );
 }
 
+#[test]
+fn test_deprecated_highlighting() {
+    check_highlighting(
+        r#"
+//- /main.rs crate:main deps:lib
+use lib::{old_fn, OldStruct};
+
+#[deprecated]
+macro_rules! old_macro {
+    () => {};
+}
+
+struct Foo {
+    #[deprecated]
+    old_field: u32,
+    field: u32,
+}
+
+impl Foo {
+    #[deprecated(note = "use `new_method`")]
+    fn old_method(&self) {}
+    fn new_method(&self) {}
+}
+
+enum E {
+    #[deprecated]
+    Old,
+    New,
+}
+
+fn main() {
+    old_macro!();
+    old_fn();
+    let s: OldStruct = OldStruct;
+    let foo = Foo { old_field: 0, field: 0 };
+    foo.old_method();
+    foo.new_method();
+    let _ = foo.old_field + foo.field;
+    let _ = (E::Old, E::New);
+}
+//- /lib.rs crate:lib
+#[deprecated(since = "1.0.0")]
+pub fn old_fn() {}
+#[deprecated]
+pub struct OldStruct;
+"#,
+        expect_file!["./test_data/highlight_deprecated.html"],
+        false,
+    );
+}
+
 #[test]
 fn test_associated_function() {
     check_highlighting(
@@ -999,6 +1056,22 @@ fn main() {
     );
 }
 
+#[test]
+fn test_reference_highlighting() {
+    check_highlighting(
+        r##"
+fn main() {
+    let mut a = 0;
+    let mut b = &a;
+    let ref mut c = a;
+    let d = &mut a;
+}
+"##,
+        expect_file!["./test_data/highlight_references.html"],
+        false,
+    );
+}
+
 #[test]
 fn test_mod_hl_injection() {
     check_highlighting(
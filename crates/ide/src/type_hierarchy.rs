@@ -0,0 +1,197 @@
+//! Entry point for type-hierarchy
+
+use hir::Semantics;
+use ide_db::{
+    defs::{Definition, NameClass, NameRefClass},
+    FxIndexSet, RootDatabase,
+};
+use syntax::{ast, AstNode, TextRange};
+
+use crate::{FilePosition, NavigationTarget, RangeInfo, TryToNav};
+
+pub(crate) fn type_hierarchy(
+    db: &RootDatabase,
+    position: FilePosition,
+) -> Option<RangeInfo<Vec<NavigationTarget>>> {
+    let sema = &Semantics::new(db);
+    let (range, def) = type_at(sema, position)?;
+    let navs = def.try_to_nav(db)?.call_site;
+    Some(RangeInfo::new(range, vec![navs]))
+}
+
+/// Returns the traits implemented by the type at the given position, or the direct supertraits if
+/// it is a trait.
+pub(crate) fn supertypes(
+    db: &RootDatabase,
+    position: FilePosition,
+) -> Option<Vec<NavigationTarget>> {
+    let sema = &Semantics::new(db);
+    let (_, def) = type_at(sema, position)?;
+    let traits: FxIndexSet<_> = match def {
+        Definition::Adt(adt) => hir::Impl::all_for_type(db, adt.ty(db))
+            .into_iter()
+            .filter_map(|impl_| impl_.trait_(db))
+            .collect(),
+        Definition::Trait(trait_) => trait_.direct_supertraits(db).into_iter().collect(),
+        _ => return None,
+    };
+    Some(traits.into_iter().filter_map(|trait_| Some(trait_.try_to_nav(db)?.call_site)).collect())
+}
+
+/// Returns the types implementing the trait at the given position.
+///
+/// Implementations for types other than ADTs, like references or blanket implementations, are
+/// represented by the impl block itself.
+pub(crate) fn subtypes(db: &RootDatabase, position: FilePosition) -> Option<Vec<NavigationTarget>> {
+    let sema = &Semantics::new(db);
+    let (_, def) = type_at(sema, position)?;
+    let trait_ = match def {
+        Definition::Trait(it) => it,
+        Definition::Adt(_) => return Some(Vec::new()),
+        _ => return None,
+    };
+    let mut seen = FxIndexSet::default();
+    let navs = hir::Impl::all_for_trait(db, trait_)
+        .into_iter()
+        .filter_map(|impl_| {
+            let nav = match impl_.self_ty(db).as_adt() {
+                Some(adt) if seen.insert(adt) => adt.try_to_nav(db)?,
+                Some(_) => return None,
+                None => impl_.try_to_nav(db)?,
+            };
+            Some(nav.call_site)
+        })
+        .collect();
+    Some(navs)
+}
+
+fn type_at(
+    sema: &Semantics<'_, RootDatabase>,
+    FilePosition { file_id, offset }: FilePosition,
+) -> Option<(TextRange, Definition)> {
+    let file = sema.parse(file_id);
+    sema.find_nodes_at_offset_with_descend(file.syntax(), offset).find_map(|node| {
+        let def = match &node {
+            ast::NameLike::NameRef(name_ref) => match NameRefClass::classify(sema, name_ref)? {
+                NameRefClass::Definition(def) => def,
+                _ => return None,
+            },
+            ast::NameLike::Name(name) => match NameClass::classify(sema, name)? {
+                NameClass::Definition(def) => def,
+                _ => return None,
+            },
+            ast::NameLike::Lifetime(_) => return None,
+        };
+        let def = match def {
+            Definition::Adt(_) | Definition::Trait(_) => def,
+            Definition::SelfType(impl_) => Definition::Adt(impl_.self_ty(sema.db).as_adt()?),
+            _ => return None,
+        };
+        Some((sema.original_range(node.syntax()).range, def))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+    use ide_db::base_db::FilePosition;
+    use itertools::Itertools;
+
+    use crate::fixture;
+
+    fn check_hierarchy(
+        ra_fixture: &str,
+        expected_nav: Expect,
+        expected_supertypes: Expect,
+        expected_subtypes: Expect,
+    ) {
+        let (analysis, pos) = fixture::position(ra_fixture);
+
+        let mut navs = analysis.type_hierarchy(pos).unwrap().unwrap().info;
+        assert_eq!(navs.len(), 1);
+        let nav = navs.pop().unwrap();
+        expected_nav.assert_eq(&nav.debug_render());
+
+        let item_pos =
+            FilePosition { file_id: nav.file_id, offset: nav.focus_or_full_range().start() };
+        let supertypes = analysis.supertypes(item_pos).unwrap().unwrap();
+        expected_supertypes.assert_eq(&supertypes.iter().map(|nav| nav.debug_render()).join("\n"));
+
+        let subtypes = analysis.subtypes(item_pos).unwrap().unwrap();
+        expected_subtypes.assert_eq(&subtypes.iter().map(|nav| nav.debug_render()).join("\n"));
+    }
+
+    #[test]
+    fn test_type_hierarchy_on_struct() {
+        check_hierarchy(
+            r#"
+trait Foo {}
+trait Bar {}
+struct S$0;
+impl S {}
+impl Foo for S {}
+impl Bar for S {}
+"#,
+            expect![["S Struct FileId(0) 26..35 33..34"]],
+            expect![[r#"
+                Foo Trait FileId(0) 0..12 6..9
+                Bar Trait FileId(0) 13..25 19..22"#]],
+            expect![[]],
+        );
+    }
+
+    #[test]
+    fn test_type_hierarchy_on_trait() {
+        check_hierarchy(
+            r#"
+trait Base {}
+trait Foo$0: Base {}
+struct S;
+enum E {}
+impl Foo for S {}
+impl Foo for E {}
+impl<T> Foo for &T {}
+"#,
+            expect![["Foo Trait FileId(0) 14..32 20..23"]],
+            expect![["Base Trait FileId(0) 0..13 6..10"]],
+            expect![[r#"
+                impl Impl FileId(0) 89..110 105..107
+                S Struct FileId(0) 33..42 40..41
+                E Enum FileId(0) 43..52 48..49"#]],
+        );
+    }
+
+    #[test]
+    fn test_type_hierarchy_on_ref() {
+        check_hierarchy(
+            r#"
+trait Foo {}
+struct S;
+impl Foo for S$0 {}
+"#,
+            expect![["S Struct FileId(0) 13..22 20..21"]],
+            expect![["Foo Trait FileId(0) 0..12 6..9"]],
+            expect![[]],
+        );
+    }
+
+    #[test]
+    fn test_type_hierarchy_across_crates() {
+        check_hierarchy(
+            r#"
+//- /lib.rs crate:lib
+pub trait Foo$0 {}
+pub struct Local;
+impl Foo for Local {}
+//- /main.rs crate:main deps:lib
+struct S;
+impl lib::Foo for S {}
+"#,
+            expect![["Foo Trait FileId(0) 0..16 10..13"]],
+            expect![[]],
+            expect![[r#"
+                Local Struct FileId(0) 17..34 28..33
+                S Struct FileId(1) 0..9 7..8"#]],
+        );
+    }
+}
@@ -30,10 +30,14 @@ mod proc_macros;
 mod server;
 
 use std::{
+    backtrace::Backtrace,
+    cell::RefCell,
     collections::{hash_map::Entry, HashMap},
     env,
     ffi::OsString,
-    fs, thread,
+    fs, panic,
+    sync::Once,
+    thread,
     time::SystemTime,
 };
 
@@ -182,33 +186,11 @@ fn expand_id(
 
     let macro_body = task.macro_body.to_subtree_unresolved(CURRENT_API_VERSION);
     let attributes = task.attributes.map(|it| it.to_subtree_unresolved(CURRENT_API_VERSION));
-    let result = thread::scope(|s| {
-        let thread = thread::Builder::new()
-            .stack_size(EXPANDER_STACK_SIZE)
-            .name(task.macro_name.clone())
-            .spawn_scoped(s, || {
-                expander
-                    .expand(
-                        &task.macro_name,
-                        macro_body,
-                        attributes,
-                        def_site,
-                        call_site,
-                        mixed_site,
-                    )
-                    .map(|it| msg::FlatTree::new_raw(&it, CURRENT_API_VERSION))
-            });
-        let res = match thread {
-            Ok(handle) => handle.join(),
-            Err(e) => std::panic::resume_unwind(Box::new(e)),
-        };
-
-        match res {
-            Ok(res) => res,
-            Err(e) => std::panic::resume_unwind(e),
-        }
-    });
-    result
+    run_expander_thread(&task.macro_name, || {
+        expander
+            .expand(&task.macro_name, macro_body, attributes, def_site, call_site, mixed_site)
+            .map(|it| msg::FlatTree::new_raw(&it, CURRENT_API_VERSION))
+    })
 }
 
 fn expand_ra_span(
@@ -227,38 +209,76 @@ fn expand_ra_span(
     let macro_body = task.macro_body.to_subtree_resolved(CURRENT_API_VERSION, &span_data_table);
     let attributes =
         task.attributes.map(|it| it.to_subtree_resolved(CURRENT_API_VERSION, &span_data_table));
-    let result = thread::scope(|s| {
+    run_expander_thread(&task.macro_name, || {
+        expander
+            .expand(&task.macro_name, macro_body, attributes, def_site, call_site, mixed_site)
+            .map(|it| {
+                (
+                    msg::FlatTree::new(&it, CURRENT_API_VERSION, &mut span_data_table),
+                    serialize_span_data_index_map(&span_data_table),
+                )
+            })
+    })
+}
+
+/// Runs an expansion on a fresh thread with a big enough stack.
+///
+/// Panics are turned into errors carrying the panic message and a backtrace, so that the server
+/// keeps serving requests and the client can show them.
+fn run_expander_thread<T: Send>(
+    macro_name: &str,
+    expand: impl FnOnce() -> Result<T, String> + Send,
+) -> Result<T, String> {
+    install_panic_hook();
+    thread::scope(|s| {
         let thread = thread::Builder::new()
             .stack_size(EXPANDER_STACK_SIZE)
-            .name(task.macro_name.clone())
-            .spawn_scoped(s, || {
-                expander
-                    .expand(
-                        &task.macro_name,
-                        macro_body,
-                        attributes,
-                        def_site,
-                        call_site,
-                        mixed_site,
-                    )
-                    .map(|it| {
-                        (
-                            msg::FlatTree::new(&it, CURRENT_API_VERSION, &mut span_data_table),
-                            serialize_span_data_index_map(&span_data_table),
-                        )
-                    })
-            });
-        let res = match thread {
-            Ok(handle) => handle.join(),
-            Err(e) => std::panic::resume_unwind(Box::new(e)),
-        };
+            .name(macro_name.to_owned())
+            .spawn_scoped(s, || match panic::catch_unwind(panic::AssertUnwindSafe(expand)) {
+                // Panics are caught by the bridge and turned into an error already. The
+                // proc-macro itself links its own copy of `std` with its own panic hook though,
+                // so only panics in the server's code come with a backtrace.
+                Ok(res) => res.map_err(with_panic_backtrace),
+                Err(payload) => {
+                    let message = match payload.downcast::<String>() {
+                        Ok(it) => *it,
+                        Err(payload) => match payload.downcast::<&'static str>() {
+                            Ok(it) => (*it).to_owned(),
+                            Err(_) => "proc-macro server panicked".to_owned(),
+                        },
+                    };
+                    Err(with_panic_backtrace(message))
+                }
+            })
+            .map_err(|err| format!("failed to spawn expansion thread: {err}"))?;
+        thread.join().unwrap_or_else(|_| Err("proc-macro server panicked".to_owned()))
+    })
+}
 
-        match res {
-            Ok(res) => res,
-            Err(e) => std::panic::resume_unwind(e),
-        }
+thread_local! {
+    /// The backtrace of the last panic on this thread.
+    static PANIC_BACKTRACE: RefCell<Option<Backtrace>> = const { RefCell::new(None) };
+}
+
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            // Captured regardless of `RUST_BACKTRACE`, as the server's stderr is usually not
+            // visible to the user.
+            let backtrace = Backtrace::force_capture();
+            PANIC_BACKTRACE.with(|it| *it.borrow_mut() = Some(backtrace));
+            default_hook(info)
+        }));
     });
-    result
+}
+
+fn with_panic_backtrace(message: String) -> String {
+    match PANIC_BACKTRACE.with(|it| it.borrow_mut().take()) {
+        Some(backtrace) => format!("{message}\n\nstack backtrace:\n{backtrace}"),
+        None => message,
+    }
 }
 
 pub struct PanicMessage {
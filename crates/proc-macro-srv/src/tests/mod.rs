@@ -270,3 +270,19 @@ fn list_test_macros() {
         DeriveError [CustomDerive]"#]]
     .assert_eq(&res);
 }
+
+#[test]
+fn test_panicking_macro_reports_message() {
+    let error = expand_error("fn_like_panic", "foo");
+    assert!(error.starts_with("fn_like_panic!(foo)"), "{error}");
+}
+
+#[test]
+fn test_server_panic_is_reported() {
+    let error = crate::run_expander_thread::<()>("panicking", || panic!("expander bug"));
+    let error = error.unwrap_err();
+    assert!(error.starts_with("expander bug\n\nstack backtrace:\n"), "{error}");
+    assert!(error.lines().count() > 3, "{error}");
+    // The server is still usable afterwards.
+    assert_eq!(crate::run_expander_thread("fine", || Ok(1)), Ok(1));
+}
@@ -100,3 +100,13 @@ pub(crate) fn list() -> Vec<String> {
     let res = srv.list_macros(&dylib_path).unwrap();
     res.into_iter().map(|(name, kind)| format!("{name} [{kind:?}]")).collect()
 }
+
+pub(crate) fn expand_error(macro_name: &str, input: &str) -> String {
+    let expander = dylib::Expander::new(&proc_macro_test_dylib_path()).unwrap();
+    let call_site = TokenId(1);
+    let input = parse_string(call_site, input).into_subtree(call_site);
+    crate::run_expander_thread(macro_name, || {
+        expander.expand(macro_name, input, None, TokenId(0), call_site, TokenId(2))
+    })
+    .unwrap_err()
+}
@@ -237,16 +237,15 @@ fn run_server() -> anyhow::Result<()> {
 
     let server_capabilities = rust_analyzer::server_capabilities(&config);
 
-    let initialize_result = lsp_types::InitializeResult {
-        capabilities: server_capabilities,
-        server_info: Some(lsp_types::ServerInfo {
+    // Not an `lsp_types::InitializeResult`, as the capabilities include some which
+    // `lsp_types::ServerCapabilities` has no field for yet.
+    let initialize_result = serde_json::json!({
+        "capabilities": server_capabilities,
+        "serverInfo": lsp_types::ServerInfo {
             name: String::from("rust-analyzer"),
             version: Some(rust_analyzer::version().to_string()),
-        }),
-        offset_encoding: None,
-    };
-
-    let initialize_result = serde_json::to_value(initialize_result).unwrap();
+        },
+    });
 
     if let Err(e) = connection.initialize_finish(initialize_id, initialize_result) {
         if e.channel_is_disconnected() {
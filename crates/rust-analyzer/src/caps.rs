@@ -23,7 +23,14 @@ use crate::{
     lsp_ext::negotiated_encoding,
 };
 
-pub fn server_capabilities(config: &Config) -> ServerCapabilities {
+pub fn server_capabilities(config: &Config) -> serde_json::Value {
+    let mut caps = serde_json::to_value(lsp_server_capabilities(config)).unwrap();
+    // FIXME: `lsp_types::ServerCapabilities` has no field for this yet.
+    caps["typeHierarchyProvider"] = json!(true);
+    caps
+}
+
+fn lsp_server_capabilities(config: &Config) -> ServerCapabilities {
     ServerCapabilities {
         position_encoding: match negotiated_encoding(config.caps()) {
             PositionEncoding::Utf8 => Some(PositionEncodingKind::UTF8),
@@ -1015,6 +1015,9 @@ impl flags::AnalysisStats {
                     closing_brace_hints_min_lines: Some(20),
                     fields_to_resolve: InlayFieldsToResolve::empty(),
                     range_exclusive_hints: true,
+                    numeric_fallback_hints: true,
+                    trait_method_hints: true,
+                    receiver_adjustment_hints: true,
                 },
                 file_id,
                 None,
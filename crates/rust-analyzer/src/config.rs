@@ -272,6 +272,9 @@ config_data! {
         /// The warnings will be indicated by a blue squiggly underline in code
         /// and a blue icon in the `Problems Panel`.
         diagnostics_warningsAsInfo: Vec<String> = vec![],
+        /// Whether the "Expand macro recursively" command should precede every expanded item and
+        /// statement with a comment naming the source file and line it originates from.
+        expandMacro_annotateOrigins_enable: bool = false,
         /// These directories will be ignored by rust-analyzer. They are
         /// relative to the workspace root, and globs are not supported. You may
         /// also need to add the folders to Code's `files.watcherExclude`.
@@ -571,6 +574,9 @@ config_data! {
         inlayHints_lifetimeElisionHints_useParameterNames: bool    = false,
         /// Maximum length for inlay hints. Set to null to have an unlimited length.
         inlayHints_maxLength: Option<usize>                        = Some(25),
+        /// Whether to show inlay hints for integer and float literals whose type was not
+        /// constrained and fell back to `i32` or `f64`.
+        inlayHints_numericFallbackHints_enable: bool               = false,
         /// Whether to show function parameter name inlay hints at the call
         /// site.
         inlayHints_parameterHints_enable: bool                     = true,
@@ -579,8 +585,14 @@ config_data! {
         /// Whether to show inlay hints for compiler inserted reborrows.
         /// This setting is deprecated in favor of #rust-analyzer.inlayHints.expressionAdjustmentHints.enable#.
         inlayHints_reborrowHints_enable: ReborrowHintsDef          = ReborrowHintsDef::Never,
+        /// Whether to show inlay hints for the implicit borrows and dereferences of method call
+        /// receivers, like `(&*s).len()`. Not shown if expression adjustment hints are enabled.
+        inlayHints_receiverAdjustmentHints_enable: bool            = false,
         /// Whether to render leading colons for type hints, and trailing colons for parameter hints.
         inlayHints_renderColons: bool                              = true,
+        /// Whether to show inlay hints naming the trait that provides a called method. Methods
+        /// of inherent impls don't get a hint.
+        inlayHints_traitMethodHints_enable: bool                   = false,
         /// Whether to show inlay type hints for variables.
         inlayHints_typeHints_enable: bool                          = true,
         /// Whether to hide inlay type hints for `let` statements that initialize to a closure.
@@ -855,6 +867,7 @@ pub struct ClientCommandsConfig {
     pub show_reference: bool,
     pub goto_location: bool,
     pub trigger_parameter_hints: bool,
+    pub enable_cargo_features: bool,
 }
 
 #[derive(Debug)]
@@ -1066,6 +1079,10 @@ impl Config {
             style_lints: self.diagnostics_styleLints_enable().to_owned(),
         }
     }
+    pub fn expand_macro_annotate_origins(&self) -> bool {
+        self.expandMacro_annotateOrigins_enable().to_owned()
+    }
+
     pub fn expand_proc_attr_macros(&self) -> bool {
         self.procMacro_enable().to_owned() && self.procMacro_attributes_enable().to_owned()
     }
@@ -1225,6 +1242,13 @@ impl Config {
             range_exclusive_hints: self
                 .inlayHints_rangeExclusiveHints_enable(source_root)
                 .to_owned(),
+            numeric_fallback_hints: self
+                .inlayHints_numericFallbackHints_enable(source_root)
+                .to_owned(),
+            trait_method_hints: self.inlayHints_traitMethodHints_enable(source_root).to_owned(),
+            receiver_adjustment_hints: self
+                .inlayHints_receiverAdjustmentHints_enable(source_root)
+                .to_owned(),
         }
     }
 
@@ -1844,6 +1868,7 @@ impl Config {
             show_reference: get("rust-analyzer.showReferences"),
             goto_location: get("rust-analyzer.gotoLocation"),
             trigger_parameter_hints: get("editor.action.triggerParameterHints"),
+            enable_cargo_features: get("rust-analyzer.enableCargoFeatures"),
         }
     }
 
@@ -153,19 +153,24 @@ fn diagnostic_related_information(
 
 /// Resolves paths applying any matching path prefix remappings, and then
 /// joining the path to the workspace root.
+///
+/// The result is normalized, as rustc reports files of modules included via `#[path]` relative to
+/// the declaring module, e.g. as `src/../generated/foo.rs`, and the VFS only knows the file by its
+/// normalized path.
 fn resolve_path(
     config: &DiagnosticsMapConfig,
     workspace_root: &AbsPath,
     file_name: &str,
 ) -> AbsPathBuf {
-    match config
+    let path = match config
         .remap_prefix
         .iter()
         .find_map(|(from, to)| file_name.strip_prefix(from).map(|file_name| (to, file_name)))
     {
         Some((to, file_name)) => workspace_root.join(format!("{to}{file_name}")),
         None => workspace_root.join(file_name),
-    }
+    };
+    path.normalize()
 }
 
 struct SubDiagnostic {
@@ -684,6 +689,56 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(not(windows))]
+    fn rustc_unused_variable_in_path_module() {
+        check(
+            r##"{
+    "message": "unused variable: `foo`",
+    "code": {
+        "code": "unused_variables",
+        "explanation": null
+    },
+    "level": "warning",
+    "spans": [
+        {
+            "file_name": "src/../generated/bindings.rs",
+            "byte_start": 24,
+            "byte_end": 27,
+            "line_start": 2,
+            "line_end": 2,
+            "column_start": 9,
+            "column_end": 12,
+            "is_primary": true,
+            "text": [
+                {
+                    "text": "    let foo = 42;",
+                    "highlight_start": 9,
+                    "highlight_end": 12
+                }
+            ],
+            "label": null,
+            "suggested_replacement": null,
+            "suggestion_applicability": null,
+            "expansion": null
+        }
+    ],
+    "children": [
+        {
+            "message": "`#[warn(unused_variables)]` on by default",
+            "code": null,
+            "level": "note",
+            "spans": [],
+            "children": [],
+            "rendered": null
+        }
+    ],
+    "rendered": "warning: unused variable: `foo`\n --> src/../generated/bindings.rs:2:9\n  |\n2 |     let foo = 42;\n  |         ^^^\n  |\n  = note: `#[warn(unused_variables)]` on by default\n\n"
+    }"##,
+            expect_file!["./test_data/rustc_unused_variable_in_path_module.txt"],
+        );
+    }
+
     #[test]
     #[cfg(not(windows))]
     fn rustc_unused_variable_as_info() {
@@ -14,7 +14,7 @@ use ide::{
     HoverAction, HoverGotoTypeData, InlayFieldsToResolve, Query, RangeInfo, ReferenceCategory,
     Runnable, RunnableKind, SingleResolve, SourceChange, TextEdit,
 };
-use ide_db::SymbolKind;
+use ide_db::{symbol_index, SymbolKind};
 use itertools::Itertools;
 use lsp_server::ErrorCode;
 use lsp_types::{
@@ -24,7 +24,9 @@ use lsp_types::{
     InlayHintParams, Location, LocationLink, Position, PrepareRenameResponse, Range, RenameParams,
     ResourceOp, ResourceOperationKind, SemanticTokensDeltaParams, SemanticTokensFullDeltaResult,
     SemanticTokensParams, SemanticTokensRangeParams, SemanticTokensRangeResult,
-    SemanticTokensResult, SymbolInformation, SymbolTag, TextDocumentIdentifier, Url, WorkspaceEdit,
+    SemanticTokensResult, SymbolInformation, SymbolTag, TextDocumentIdentifier, TypeHierarchyItem,
+    TypeHierarchyPrepareParams, TypeHierarchySubtypesParams, TypeHierarchySupertypesParams, Url,
+    WorkspaceEdit,
 };
 use paths::Utf8PathBuf;
 use project_model::{ManifestPath, ProjectWorkspace, TargetKind};
@@ -293,7 +295,10 @@ pub(crate) fn handle_expand_macro(
     let line_index = snap.file_line_index(file_id)?;
     let offset = from_proto::offset(&line_index, params.position)?;
 
-    let res = snap.analysis.expand_macro(FilePosition { file_id, offset })?;
+    let res = snap.analysis.expand_macro(
+        FilePosition { file_id, offset },
+        snap.config.expand_macro_annotate_origins(),
+    )?;
     Ok(res.map(|it| lsp_ext::ExpandedMacro { name: it.name, expansion: it.expansion }))
 }
 
@@ -545,8 +550,11 @@ pub(crate) fn handle_workspace_symbol(
 
     let query = {
         let query: String = params.query.chars().filter(|&c| c != '#' && c != '*').collect();
-        let mut q = Query::new(query);
-        if !all_symbols {
+        let (kind, query) = symbol_index::split_kind_prefix(&query);
+        let mut q = Query::new(query.to_owned());
+        if let Some(kind) = kind {
+            q.only_kind(kind);
+        } else if !all_symbols {
             q.only_types();
         }
         if libs {
@@ -1095,7 +1103,7 @@ pub(crate) fn handle_prepare_rename(
 
     let line_index = snap.file_line_index(position.file_id)?;
     let range = to_proto::range(&line_index, change.range);
-    Ok(Some(PrepareRenameResponse::Range(range)))
+    Ok(Some(PrepareRenameResponse::RangeWithPlaceholder { range, placeholder: change.info }))
 }
 
 pub(crate) fn handle_rename(
@@ -1253,6 +1261,35 @@ pub(crate) fn handle_code_action(
         res.push(code_action)
     }
 
+    // Enabling the cargo features that activate `#[cfg]`-disabled code.
+    let include_quick_fixes = assists_config.allowed.as_ref().map_or(true, |it| {
+        it.iter().any(|&it| it == AssistKind::None || it == AssistKind::QuickFix)
+    });
+    if include_quick_fixes && snap.config.client_commands().enable_cargo_features {
+        for inactive in snap.analysis.inactive_code_features(frange)? {
+            let features = inactive
+                .features
+                .iter()
+                .map(|feature| format!("{}/{feature}", inactive.krate))
+                .collect::<Vec<_>>();
+            let title = format!(
+                "Enable feature{} {} of crate `{}`",
+                if inactive.features.len() == 1 { "" } else { "s" },
+                inactive.features.iter().map(|it| format!("`{it}`")).join(", "),
+                inactive.krate,
+            );
+            res.push(lsp_ext::CodeAction {
+                title: title.clone(),
+                group: None,
+                kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+                command: Some(to_proto::command::enable_cargo_features(title, &features)),
+                edit: None,
+                is_preferred: None,
+                data: None,
+            });
+        }
+    }
+
     // Fixes from `cargo check`.
     for fix in snap.check_fixes.values().filter_map(|it| it.get(&frange.file_id)).flatten() {
         // FIXME: this mapping is awkward and shouldn't exist. Refactor
@@ -1649,6 +1686,75 @@ pub(crate) fn handle_call_hierarchy_outgoing(
     Ok(Some(res))
 }
 
+pub(crate) fn handle_type_hierarchy_prepare(
+    snap: GlobalStateSnapshot,
+    params: TypeHierarchyPrepareParams,
+) -> anyhow::Result<Option<Vec<TypeHierarchyItem>>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_type_hierarchy_prepare").entered();
+    let position = from_proto::file_position(&snap, params.text_document_position_params)?;
+
+    let nav_info = match snap.analysis.type_hierarchy(position)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+
+    let RangeInfo { range: _, info: navs } = nav_info;
+    let res = navs
+        .into_iter()
+        .map(|it| to_proto::type_hierarchy_item(&snap, it))
+        .collect::<Cancellable<Vec<_>>>()?;
+
+    Ok(Some(res))
+}
+
+pub(crate) fn handle_type_hierarchy_supertypes(
+    snap: GlobalStateSnapshot,
+    params: TypeHierarchySupertypesParams,
+) -> anyhow::Result<Option<Vec<TypeHierarchyItem>>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_type_hierarchy_supertypes").entered();
+    let item = params.item;
+
+    let doc = TextDocumentIdentifier::new(item.uri);
+    let frange = from_proto::file_range(&snap, &doc, item.selection_range)?;
+    let fpos = FilePosition { file_id: frange.file_id, offset: frange.range.start() };
+
+    let navs = match snap.analysis.supertypes(fpos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+
+    let res = navs
+        .into_iter()
+        .map(|it| to_proto::type_hierarchy_item(&snap, it))
+        .collect::<Cancellable<Vec<_>>>()?;
+
+    Ok(Some(res))
+}
+
+pub(crate) fn handle_type_hierarchy_subtypes(
+    snap: GlobalStateSnapshot,
+    params: TypeHierarchySubtypesParams,
+) -> anyhow::Result<Option<Vec<TypeHierarchyItem>>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_type_hierarchy_subtypes").entered();
+    let item = params.item;
+
+    let doc = TextDocumentIdentifier::new(item.uri);
+    let frange = from_proto::file_range(&snap, &doc, item.selection_range)?;
+    let fpos = FilePosition { file_id: frange.file_id, offset: frange.range.start() };
+
+    let navs = match snap.analysis.subtypes(fpos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+
+    let res = navs
+        .into_iter()
+        .map(|it| to_proto::type_hierarchy_item(&snap, it))
+        .collect::<Cancellable<Vec<_>>>()?;
+
+    Ok(Some(res))
+}
+
 pub(crate) fn handle_semantic_tokens_full(
     snap: GlobalStateSnapshot,
     params: SemanticTokensParams,
@@ -141,6 +141,7 @@ define_semantic_token_modifiers![
         DECLARATION,
         STATIC,
         DEFAULT_LIBRARY,
+        DEPRECATED,
     }
     custom {
         (ASSOCIATED, "associated"),
@@ -155,6 +156,7 @@ define_semantic_token_modifiers![
         (LIBRARY, "library"),
         (MACRO_MODIFIER, "macro"),
         (MUTABLE, "mutable"),
+        (MUTABLE_REFERENCE, "mutableReference"),
         (PROC_MACRO_MODIFIER, "procMacro"),
         (PUBLIC, "public"),
         (REFERENCE, "reference"),
@@ -18,6 +18,7 @@ use itertools::Itertools;
 use paths::{Utf8Component, Utf8Prefix};
 use semver::VersionReq;
 use serde_json::to_value;
+use stdx::format_to;
 use vfs::AbsPath;
 
 use crate::{
@@ -360,7 +361,12 @@ fn completion_item(
         // by the client. Hex format is used because it is easier to
         // visually compare very large values, which the sort text
         // tends to be since it is the opposite of the score.
-        res.sort_text = Some(format!("{sort_score:08x}"));
+        let mut sort_text = format!("{sort_score:08x}");
+        // Missing record fields all share the same score, keep them in declaration order.
+        if let Some(position) = relevance.missing_field_position {
+            format_to!(sort_text, "{position:08x}");
+        }
+        res.sort_text = Some(sort_text);
     }
 }
 
@@ -498,7 +504,9 @@ pub(crate) fn inlay_hint(
         padding_right: Some(inlay_hint.pad_right),
         kind: match inlay_hint.kind {
             InlayKind::Parameter => Some(lsp_types::InlayHintKind::PARAMETER),
-            InlayKind::Type | InlayKind::Chaining => Some(lsp_types::InlayHintKind::TYPE),
+            InlayKind::Type | InlayKind::Chaining | InlayKind::NumericFallback => {
+                Some(lsp_types::InlayHintKind::TYPE)
+            }
             _ => None,
         },
         text_edits,
@@ -736,6 +744,7 @@ fn semantic_token_type_and_modifiers(
             HlMod::CrateRoot => mods::CRATE_ROOT,
             HlMod::DefaultLibrary => mods::DEFAULT_LIBRARY,
             HlMod::Definition => mods::DECLARATION,
+            HlMod::Deprecated => mods::DEPRECATED,
             HlMod::Documentation => mods::DOCUMENTATION,
             HlMod::Injected => mods::INJECTED,
             HlMod::IntraDocLink => mods::INTRA_DOC_LINK,
@@ -743,6 +752,7 @@ fn semantic_token_type_and_modifiers(
             HlMod::Macro => mods::MACRO_MODIFIER,
             HlMod::ProcMacro => mods::PROC_MACRO_MODIFIER,
             HlMod::Mutable => mods::MUTABLE,
+            HlMod::MutableReference => mods::MUTABLE_REFERENCE,
             HlMod::Public => mods::PUBLIC,
             HlMod::Reference => mods::REFERENCE,
             HlMod::Static => mods::STATIC,
@@ -1304,6 +1314,26 @@ pub(crate) fn call_hierarchy_item(
     })
 }
 
+pub(crate) fn type_hierarchy_item(
+    snap: &GlobalStateSnapshot,
+    target: NavigationTarget,
+) -> Cancellable<lsp_types::TypeHierarchyItem> {
+    let name = target.name.to_string();
+    let detail = target.description.clone();
+    let kind = target.kind.map(symbol_kind).unwrap_or(lsp_types::SymbolKind::STRUCT);
+    let (uri, range, selection_range) = location_info(snap, target)?;
+    Ok(lsp_types::TypeHierarchyItem {
+        name,
+        kind,
+        tags: None,
+        detail,
+        uri,
+        range,
+        selection_range,
+        data: None,
+    })
+}
+
 pub(crate) fn code_action_kind(kind: AssistKind) -> lsp_types::CodeActionKind {
     match kind {
         AssistKind::None | AssistKind::Generate => lsp_types::CodeActionKind::EMPTY,
@@ -1637,6 +1667,14 @@ pub(crate) mod command {
         })
     }
 
+    pub(crate) fn enable_cargo_features(title: String, features: &[String]) -> lsp_types::Command {
+        lsp_types::Command {
+            title,
+            command: "rust-analyzer.enableCargoFeatures".into(),
+            arguments: Some(vec![to_value(features).unwrap()]),
+        }
+    }
+
     pub(crate) fn trigger_parameter_hints() -> lsp_types::Command {
         lsp_types::Command {
             title: "triggerParameterHints".into(),
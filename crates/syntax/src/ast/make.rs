@@ -792,7 +792,7 @@ pub fn match_arm_list(arms: impl IntoIterator<Item = ast::MatchArm>) -> ast::Mat
 }
 
 pub fn where_pred(
-    path: ast::Path,
+    path: either::Either<ast::Lifetime, ast::Type>,
     bounds: impl IntoIterator<Item = ast::TypeBound>,
 ) -> ast::WherePred {
     let bounds = bounds.into_iter().join(" + ");
@@ -874,6 +874,11 @@ pub fn mut_self_param() -> ast::SelfParam {
     ast_from_text("fn f(&mut self) { }")
 }
 
+pub fn owned_self_param(is_mut: bool) -> ast::SelfParam {
+    let mut_ = if is_mut { "mut " } else { "" };
+    ast_from_text(&format!("fn f({mut_}self) {{ }}"))
+}
+
 pub fn ret_type(ty: ast::Type) -> ast::RetType {
     ast_from_text(&format!("fn f() -> {ty} {{ }}"))
 }
@@ -1197,6 +1202,15 @@ pub mod tokens {
         sf.syntax().first_child_or_token().unwrap().into_token().unwrap()
     }
 
+    pub fn comment(text: &str) -> SyntaxToken {
+        assert!(text.starts_with("//") || text.starts_with("/*"));
+        let sf = SourceFile::parse(text, Edition::CURRENT).ok().unwrap();
+        let res =
+            sf.syntax().clone_for_update().first_child_or_token().unwrap().into_token().unwrap();
+        res.detach();
+        res
+    }
+
     pub fn literal(text: &str) -> SyntaxToken {
         assert_eq!(text.trim(), text);
         let lit: ast::Literal = super::ast_from_text(&format!("fn f() {{ let _ = {text}; }}"));
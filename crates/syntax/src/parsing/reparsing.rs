@@ -2,7 +2,8 @@
 //!
 //! We use two simple strategies for this:
 //!   - if the edit modifies only a single token (like changing an identifier's
-//!     letter), we replace only this token.
+//!     letter or typing inside a string or a comment), we replace only this
+//!     token.
 //!   - otherwise, we search for the nearest `{}` block which contains the edit
 //!     and try to parse only this block.
 
@@ -10,9 +11,10 @@ use parser::Reparser;
 use text_edit::Indel;
 
 use crate::{
+    ast::CommentKind,
     parsing::build_tree,
-    syntax_node::{GreenNode, GreenToken, NodeOrToken, SyntaxElement, SyntaxNode},
-    SyntaxError,
+    syntax_node::{GreenNode, GreenToken, NodeOrToken, SyntaxElement, SyntaxNode, SyntaxToken},
+    SyntaxError, SyntaxKind,
     SyntaxKind::*,
     TextRange, TextSize, T,
 };
@@ -39,15 +41,32 @@ fn reparse_token(
     edit: &Indel,
 ) -> Option<(GreenNode, Vec<SyntaxError>, TextRange)> {
     let prev_token = root.covering_element(edit.delete).as_token()?.clone();
+    if let Some(res) = reparse_single_token(root, &prev_token, edit) {
+        return Some(res);
+    }
+    // An insertion at a token boundary is covered by the token on its left, but it might as well
+    // extend the token on its right, e.g. when typing in front of an identifier.
+    if edit.delete.is_empty() && edit.delete.start() == prev_token.text_range().end() {
+        let next_token = prev_token.next_token()?;
+        return reparse_single_token(root, &next_token, edit);
+    }
+    None
+}
+
+fn reparse_single_token(
+    root: &SyntaxNode,
+    prev_token: &SyntaxToken,
+    edit: &Indel,
+) -> Option<(GreenNode, Vec<SyntaxError>, TextRange)> {
     let prev_token_kind = prev_token.kind();
     match prev_token_kind {
-        WHITESPACE | COMMENT | IDENT | STRING | BYTE_STRING | C_STRING => {
-            if prev_token_kind == WHITESPACE || prev_token_kind == COMMENT {
-                // removing a new line may extends previous token
-                let deleted_range = edit.delete - prev_token.text_range().start();
-                if prev_token.text()[deleted_range].contains('\n') {
-                    return None;
-                }
+        WHITESPACE | COMMENT | IDENT | LIFETIME_IDENT | STRING | BYTE_STRING | C_STRING | CHAR
+        | BYTE | INT_NUMBER | FLOAT_NUMBER => {
+            if matches!(prev_token_kind, INT_NUMBER | FLOAT_NUMBER)
+                && prev_token.parent().map_or(true, |it| it.kind() != LITERAL)
+            {
+                // Numbers in field accesses like `x.0.1` are split off float literals.
+                return None;
             }
 
             let mut new_text = get_text_after_edit(prev_token.clone().into(), edit);
@@ -55,7 +74,11 @@ fn reparse_token(
 
             if new_token_kind != prev_token_kind
                 || (new_token_kind == IDENT && is_contextual_kw(&new_text))
+                || (new_token_kind == COMMENT
+                    && CommentKind::from_text(&new_text)
+                        != CommentKind::from_text(prev_token.text()))
             {
+                // Doc comments are attached to items, so they can't change kind either.
                 return None;
             }
 
@@ -70,6 +93,16 @@ fn reparse_token(
                 }
                 new_text.pop();
             }
+            // The same goes for the previous token, e.g. removing the newline after a line comment
+            // makes the comment extend into the next line.
+            if let Some(prev) = prev_token.prev_token() {
+                let mut text = prev.text().to_owned();
+                text.push(new_text.chars().next()?);
+                let token_with_first_char = parser::LexedStr::single_token(&text);
+                if let Some((_kind, _error)) = token_with_first_char {
+                    return None;
+                }
+            }
 
             let new_token = GreenToken::new(rowan::SyntaxKind(prev_token_kind.into()), &new_text);
             let range = TextRange::up_to(TextSize::of(&new_text));
@@ -116,7 +149,7 @@ fn get_text_after_edit(element: SyntaxElement, edit: &Indel) -> String {
 }
 
 fn is_contextual_kw(text: &str) -> bool {
-    matches!(text, "auto" | "default" | "union")
+    SyntaxKind::from_contextual_keyword(text).is_some()
 }
 
 fn find_reparsable_node(node: &SyntaxNode, range: TextRange) -> Option<(SyntaxNode, Reparser)> {
@@ -250,7 +283,7 @@ fn foo {
 }
 ",
             "62",
-            31, // FIXME: reparse only int literal here
+            2,
         );
         do_check(
             r"
@@ -409,6 +442,56 @@ enum Foo {
         );
     }
 
+    #[test]
+    fn reparse_literal_tokens() {
+        do_check(r"const C: u32 = 4$0$02;", "0", 2);
+        do_check(r"const C: f32 = 1.$05$0;", "25", 3);
+        do_check(r"const C: char = '$0a$0';", "\\n", 3);
+        do_check(r"const C: u8 = b'$0a$0';", "b", 4);
+        do_check(r"fn foo<'a$0$0>(x: &'a ()) {}", "b", 2);
+        do_check(r"fn foo() { x.0.$01$0 }", "2", 9);
+    }
+
+    #[test]
+    fn reparse_token_at_boundary() {
+        do_check(r"fn f() { foo($0$0bar) }", "x", 3);
+        do_check(r"fn f() { foo($0$0bar) }", "&", 12);
+        do_check(r#"fn f() { foo($0$0"s") }"#, "r", 3);
+    }
+
+    #[test]
+    fn reparse_token_newlines() {
+        do_check(
+            r"
+fn foo() {
+    x;$0
+$0    y;
+}
+",
+            "",
+            5,
+        );
+        do_check(
+            r"
+/* foo$0
+$0bar */
+fn foo() {}
+",
+            " ",
+            13,
+        );
+        do_check(
+            r"
+fn foo() {
+    // x;$0
+$0    y;
+}
+",
+            "",
+            20,
+        );
+    }
+
     #[test]
     fn reparse_str_token_with_error_unchanged() {
         do_check(r#""$0Unclosed$0 string literal"#, "Still unclosed", 24);
@@ -429,7 +512,7 @@ enum Foo {
                 if {}
             }"#,
             "23",
-            105,
+            1,
         )
     }
 
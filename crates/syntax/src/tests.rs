@@ -9,6 +9,7 @@ use parser::Edition;
 use rayon::prelude::*;
 use stdx::format_to_acc;
 use test_utils::{bench, bench_fixture, project_root};
+use text_edit::Indel;
 
 use crate::{ast, fuzz, AstNode, SourceFile, SyntaxError};
 
@@ -48,6 +49,41 @@ fn benchmark_parser() {
     }
 }
 
+#[test]
+fn benchmark_incremental_reparse() {
+    if std::env::var("RUN_SLOW_BENCHES").is_err() {
+        return;
+    }
+
+    let data = bench_fixture::glorious_old_parser();
+    let parse = SourceFile::parse(&data, Edition::CURRENT);
+    let edits: Vec<Indel> = parse
+        .tree()
+        .syntax()
+        .descendants()
+        .filter_map(ast::Fn::cast)
+        .filter_map(|f| f.name())
+        .map(|name| Indel::insert(name.syntax().text_range().end(), "x".to_owned()))
+        .collect();
+    assert!(!edits.is_empty());
+
+    let reparsed: Vec<_> = {
+        let _b = bench("incremental reparse");
+        edits.iter().map(|edit| parse.reparse(edit, Edition::CURRENT)).collect()
+    };
+
+    for (i, (edit, reparsed)) in edits.iter().zip(reparsed).enumerate() {
+        let mut text = data.clone();
+        edit.apply(&mut text);
+        assert_eq!(reparsed.tree().syntax().text(), text.as_str());
+        // Comparing against a full parse is slow, so only do it for some of the edits.
+        if i % 10 == 0 {
+            let fully_reparsed = SourceFile::parse(&text, Edition::CURRENT);
+            assert_eq!(fully_reparsed.debug_dump(), reparsed.debug_dump());
+        }
+    }
+}
+
 #[test]
 fn validation_tests() {
     dir_tests(&test_data_dir(), &["parser/validation"], "rast", |text, path| {
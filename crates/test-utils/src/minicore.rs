@@ -28,6 +28,7 @@
 //!     env: option
 //!     eq: sized
 //!     error: fmt
+//!     flatten: iterators
 //!     fmt: option, result, transmute, coerce_unsized
 //!     fn:
 //!     from: sized
@@ -58,6 +59,7 @@
 //!     sync: sized
 //!     transmute:
 //!     try: infallible
+//!     try_from: from, result
 //!     unpin: sized
 //!     unsize: sized
 //!     todo: panic
@@ -319,6 +321,13 @@ pub mod convert {
     }
     // endregion:from
 
+    // region:try_from
+    pub trait TryFrom<T>: Sized {
+        type Error;
+        fn try_from(value: T) -> Result<Self, Self::Error>;
+    }
+    // endregion:try_from
+
     // region:as_ref
     pub trait AsRef<T: ?Sized> {
         fn as_ref(&self) -> &T;
@@ -341,6 +350,15 @@ pub mod mem {
         pub const fn new(value: T) -> ManuallyDrop<T> {
             ManuallyDrop { value }
         }
+        pub unsafe fn take(slot: &mut ManuallyDrop<T>) -> T {
+            crate::ptr::read(&slot.value)
+        }
+    }
+
+    impl<T: ?Sized> ManuallyDrop<T> {
+        pub unsafe fn drop(slot: &mut ManuallyDrop<T>) {
+            crate::ptr::drop_in_place(&mut slot.value)
+        }
     }
 
     // region:deref
@@ -1055,7 +1073,31 @@ pub mod slice {
         pub fn len(&self) -> usize {
             loop {}
         }
+        // region:iterator
+        pub fn iter(&self) -> Iter<'_, T> {
+            loop {}
+        }
+        // endregion:iterator
+    }
+
+    // region:iterator
+    pub struct Iter<'a, T> {
+        slice: &'a [T],
+    }
+    impl<'a, T> crate::iter::Iterator for Iter<'a, T> {
+        type Item = &'a T;
+        fn next(&mut self) -> Option<&'a T> {
+            loop {}
+        }
+    }
+    impl<'a, T> crate::iter::IntoIterator for &'a [T] {
+        type Item = &'a T;
+        type IntoIter = Iter<'a, T>;
+        fn into_iter(self) -> Iter<'a, T> {
+            loop {}
+        }
     }
+    // endregion:iterator
 }
 // endregion:slice
 
@@ -1080,6 +1122,10 @@ pub mod option {
             }
         }
 
+        pub fn expect(self, _msg: &str) -> T {
+            loop {}
+        }
+
         pub const fn as_ref(&self) -> Option<&T> {
             match self {
                 Some(x) => Some(x),
@@ -1130,8 +1176,24 @@ pub mod option {
         {
             loop {}
         }
+        // region:flatten
+        pub fn map<U, F>(self, _f: F) -> Option<U>
+        where
+            F: FnOnce(T) -> U,
+        {
+            loop {}
+        }
+        // endregion:flatten
         // endregion:fn
     }
+    // region:flatten
+
+    impl<T> Option<Option<T>> {
+        pub fn flatten(self) -> Option<T> {
+            loop {}
+        }
+    }
+    // endregion:flatten
 }
 // endregion:option
 
@@ -1143,6 +1205,37 @@ pub mod result {
         #[lang = "Err"]
         Err(E),
     }
+
+    impl<T, E> Result<T, E> {
+        pub fn unwrap(self) -> T {
+            loop {}
+        }
+        pub fn expect(self, _msg: &str) -> T {
+            loop {}
+        }
+        // region:flatten
+        pub fn map<U, F>(self, _op: F) -> Result<U, E>
+        where
+            F: FnOnce(T) -> U,
+        {
+            loop {}
+        }
+        pub fn and_then<U, F>(self, _op: F) -> Result<U, E>
+        where
+            F: FnOnce(T) -> Result<U, E>,
+        {
+            loop {}
+        }
+        // endregion:flatten
+    }
+    // region:flatten
+
+    impl<T, E> Result<Result<T, E>, E> {
+        pub fn flatten(self) -> Result<T, E> {
+            loop {}
+        }
+    }
+    // endregion:flatten
 }
 // endregion:result
 
@@ -1247,8 +1340,58 @@ pub mod iter {
                 loop {}
             }
         }
+
+        // region:flatten
+        pub struct Map<I, F> {
+            iter: I,
+            f: F,
+        }
+        impl<B, I: Iterator, F> Iterator for Map<I, F>
+        where
+            F: FnMut(I::Item) -> B,
+        {
+            type Item = B;
+
+            fn next(&mut self) -> Option<B> {
+                loop {}
+            }
+        }
+
+        pub struct Flatten<I> {
+            iter: I,
+        }
+        impl<I: Iterator> Iterator for Flatten<I>
+        where
+            I::Item: crate::iter::IntoIterator,
+        {
+            type Item = <I::Item as crate::iter::IntoIterator>::Item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                loop {}
+            }
+        }
+
+        pub struct FlatMap<I, U, F> {
+            iter: I,
+            f: F,
+            current: Option<U>,
+        }
+        impl<I: Iterator, U: crate::iter::IntoIterator, F> Iterator for FlatMap<I, U, F>
+        where
+            F: FnMut(I::Item) -> U,
+        {
+            type Item = U::Item;
+
+            fn next(&mut self) -> Option<U::Item> {
+                loop {}
+            }
+        }
+        // endregion:flatten
     }
     pub use self::adapters::{FilterMap, Take};
+    // region:flatten
+    pub use self::adapters::{FlatMap, Flatten, Map};
+    // endregion:flatten
 
     mod sources {
         mod repeat {
@@ -1300,6 +1443,30 @@ pub mod iter {
                 {
                     loop {}
                 }
+                // region:flatten
+                fn map<B, F>(self, _f: F) -> crate::iter::Map<Self, F>
+                where
+                    Self: Sized,
+                    F: FnMut(Self::Item) -> B,
+                {
+                    loop {}
+                }
+                fn flatten(self) -> crate::iter::Flatten<Self>
+                where
+                    Self: Sized,
+                    Self::Item: crate::iter::IntoIterator,
+                {
+                    loop {}
+                }
+                fn flat_map<U, F>(self, _f: F) -> crate::iter::FlatMap<Self, U, F>
+                where
+                    Self: Sized,
+                    U: crate::iter::IntoIterator,
+                    F: FnMut(Self::Item) -> U,
+                {
+                    loop {}
+                }
+                // endregion:flatten
                 // endregion:iterators
             }
             impl<I: Iterator + ?Sized> Iterator for &mut I {
@@ -1569,6 +1736,7 @@ pub mod prelude {
             cmp::{Ord, PartialOrd},                  // :ord
             convert::AsRef,                          // :as_ref
             convert::{From, Into},                   // :from
+            convert::TryFrom,                        // :try_from
             default::Default,                        // :default
             iter::{IntoIterator, Iterator},          // :iterator
             macros::builtin::{derive, derive_const}, // :derive
@@ -407,6 +407,7 @@ class ExperimentalFeatures implements lc.StaticFeature {
                     "rust-analyzer.showReferences",
                     "rust-analyzer.gotoLocation",
                     "editor.action.triggerParameterHints",
+                    "rust-analyzer.enableCargoFeatures",
                 ],
             },
             ...capabilities.experimental,
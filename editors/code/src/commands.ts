@@ -113,6 +113,26 @@ export function triggerParameterHints(_: CtxInit): Cmd {
     };
 }
 
+export function enableCargoFeatures(_: CtxInit): Cmd {
+    return async (features: string[]) => {
+        const config = vscode.workspace.getConfiguration("rust-analyzer");
+        const current = config.get<string[] | "all">("cargo.features");
+        if (current === "all") {
+            return;
+        }
+        const enabled = new Set(current ?? []);
+        features.forEach((feature) => enabled.add(feature));
+        await config.update(
+            "cargo.features",
+            Array.from(enabled),
+            vscode.ConfigurationTarget.Workspace,
+        );
+        void vscode.window.showInformationMessage(
+            `Enabled cargo feature${features.length === 1 ? "" : "s"} ${features.join(", ")}`,
+        );
+    };
+}
+
 export function openLogs(ctx: CtxInit): Cmd {
     return async () => {
         if (ctx.client.outputChannel) {
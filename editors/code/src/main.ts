@@ -181,6 +181,7 @@ function createCommands(): Record<string, CommandFactory> {
         applyActionGroup: { enabled: commands.applyActionGroup },
         applySnippetWorkspaceEdit: { enabled: commands.applySnippetWorkspaceEditCommand },
         debugSingle: { enabled: commands.debugSingle },
+        enableCargoFeatures: { enabled: commands.enableCargoFeatures },
         gotoLocation: { enabled: commands.gotoLocation },
         linkToCommand: { enabled: commands.linkToCommand },
         resolveCodeAction: { enabled: commands.resolveCodeAction },